@@ -4,25 +4,32 @@
 
 use tauri::{
     image::Image,
-    menu::{Menu, MenuItem, PredefinedMenuItem, Submenu},
+    menu::{IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu},
     tray::TrayIconBuilder,
-    AppHandle, Emitter, Manager,
+    AppHandle, Emitter, Manager, Wry,
 };
 
-use crate::{commands, keyboard_hook, mcp_gateway, storage};
+use crate::{
+    commands, favorites_menu, git_executable, keyboard_hook, mcp_gateway, shutdown, startup,
+    storage, tool_windows, tray_badge,
+};
 
 pub fn run_setup(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
-    apply_macos_window_style(app);
-    init_storage_and_db();
-    init_logging(app.handle())?;
-    init_tray(app)?;
-    init_workers(app);
-    init_global_shortcuts(app.handle())?;
-    init_keyboard_hook(app);
+    startup::time_phase("window_style", || apply_macos_window_style(app));
+    startup::time_phase("storage_and_db", init_storage_and_db);
+    startup::time_phase("logging", || init_logging(app.handle()))?;
+    startup::time_phase("tray", || init_tray(app))?;
+    startup::time_phase("workers", || init_workers(app));
+    startup::time_phase("global_shortcuts", || init_global_shortcuts(app.handle()))?;
+    startup::time_phase("keyboard_hook", || init_keyboard_hook(app));
 
     // 启动剪贴板监控（后台任务，无需 manage 返回值）
     commands::toolbox::clipboard::start_clipboard_monitor(app.handle().clone());
 
+    // 工具模块的 JSON 存储放到后台加载，不阻塞窗口显示
+    startup::spawn_preload(app.handle().clone());
+    startup::mark_setup_done();
+
     println!("Tauri app setup completed with tray icon");
     Ok(())
 }
@@ -32,12 +39,10 @@ fn apply_macos_window_style(app: &mut tauri::App) {
     #[cfg(target_os = "macos")]
     {
         // 默认 Accessory（仅菜单栏），若设置 show_dock_icon=true 则改为 Regular。
-        let show_dock = storage::get_storage_config()
-            .ok()
-            .and_then(|cfg| std::fs::read_to_string(cfg.app_settings_file()).ok())
-            .and_then(|s| serde_json::from_str::<storage::AppSettings>(&s).ok())
-            .map(|s| s.show_dock_icon)
-            .unwrap_or(false);
+        let show_dock =
+            storage::documents::load::<storage::AppSettings>(&storage::documents::APP_SETTINGS)
+                .map(|s| s.show_dock_icon)
+                .unwrap_or(false);
 
         let policy = if show_dock {
             tauri::ActivationPolicy::Regular
@@ -84,6 +89,9 @@ fn init_storage_and_db() {
     if let Err(e) = storage::init_storage() {
         eprintln!("存储系统初始化警告: {}", e);
     }
+    // 设置类 JSON 文件升级到当前版本（结果记录在 settings_migrations.json）
+    storage::documents::migrate_all();
+    crate::read_only::init();
 
     if let Ok(config) = storage::get_storage_config() {
         let db_path = config.db_file();
@@ -96,7 +104,9 @@ fn init_storage_and_db() {
 
         if let Err(e) = tauri::async_runtime::block_on(async {
             storage::db::init_db(&db_path).await?;
-            storage::migrations::run_migrations(&data_dir).await
+            storage::migrations::run_migrations(&data_dir).await?;
+            commands::deploy::mark_interrupted_runs().await;
+            Ok::<_, crate::error::AppError>(())
         }) {
             eprintln!("SQLite 初始化或迁移失败: {}", e);
             log::error!("SQLite 初始化或迁移失败: {}", e);
@@ -134,11 +144,45 @@ fn init_logging(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-/// 构建托盘菜单 + 图标，并绑定事件处理。
-fn init_tray(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
+/// 托盘图标 id，刷新收藏菜单时按 id 取回托盘
+pub const TRAY_ID: &str = "main";
+
+/// 构建托盘菜单：显示主窗口 / 收藏项目 / 工具箱 / 退出。收藏变化时由 favorites_menu 重建。
+pub fn build_tray_menu<M: Manager<Wry>>(
+    app: &M,
+    favorites: &[favorites_menu::FavoriteEntry],
+) -> tauri::Result<Menu<Wry>> {
     let show = MenuItem::with_id(app, "show", "显示主窗口", true, None::<&str>)?;
     let quit = MenuItem::with_id(app, "quit", "退出程序", true, None::<&str>)?;
 
+    let favorite_items = if favorites.is_empty() {
+        vec![MenuItem::with_id(
+            app,
+            "no_favorites",
+            "暂无收藏项目",
+            false,
+            None::<&str>,
+        )?]
+    } else {
+        favorites
+            .iter()
+            .map(|f| {
+                MenuItem::with_id(
+                    app,
+                    format!("{}{}", favorites_menu::MENU_ID_PREFIX, f.id),
+                    &f.name,
+                    true,
+                    None::<&str>,
+                )
+            })
+            .collect::<tauri::Result<Vec<_>>>()?
+    };
+    let favorite_refs: Vec<&dyn IsMenuItem<Wry>> = favorite_items
+        .iter()
+        .map(|item| item as &dyn IsMenuItem<Wry>)
+        .collect();
+    let favorites_submenu = Submenu::with_items(app, "收藏项目", true, &favorite_refs)?;
+
     let tool_monitor = MenuItem::with_id(app, "tool_monitor", "系统监控", true, None::<&str>)?;
     let tool_downloader =
         MenuItem::with_id(app, "tool_downloader", "文件下载", true, None::<&str>)?;
@@ -168,12 +212,27 @@ fn init_tray(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
 
     let sep1 = PredefinedMenuItem::separator(app)?;
     let sep2 = PredefinedMenuItem::separator(app)?;
-    let menu = Menu::with_items(app, &[&show, &sep1, &toolbox_submenu, &sep2, &quit])?;
+    Menu::with_items(
+        app,
+        &[
+            &show,
+            &sep1,
+            &favorites_submenu,
+            &toolbox_submenu,
+            &sep2,
+            &quit,
+        ],
+    )
+}
+
+/// 构建托盘菜单 + 图标，并绑定事件处理。
+fn init_tray(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
+    let menu = build_tray_menu(app, &[])?;
 
     let icon =
         Image::from_bytes(include_bytes!("../icons/icon.png")).expect("Failed to load tray icon");
 
-    let _tray = TrayIconBuilder::new()
+    let _tray = TrayIconBuilder::with_id(TRAY_ID)
         .icon(icon)
         .tooltip("CodeShelf - 代码书架")
         .menu(&menu)
@@ -185,11 +244,46 @@ fn init_tray(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// 闪烁托盘图标（原图标与红色版本交替几秒），并把提示文字改为告警内容
+pub fn flash_tray(app: &AppHandle, tooltip: &str) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let _ = tray.set_tooltip(Some(tooltip));
+    let Ok(normal) = Image::from_bytes(include_bytes!("../icons/icon.png")) else {
+        return;
+    };
+    let mut rgba = normal.rgba().to_vec();
+    for px in rgba.chunks_exact_mut(4) {
+        px[0] = 255;
+        px[1] /= 3;
+        px[2] /= 3;
+    }
+    let (width, height) = (normal.width(), normal.height());
+    tauri::async_runtime::spawn(async move {
+        for i in 0..12 {
+            let icon = if i % 2 == 0 {
+                Image::new_owned(rgba.clone(), width, height)
+            } else {
+                normal.clone()
+            };
+            let _ = tray.set_icon(Some(icon));
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        }
+        let _ = tray.set_icon(Some(normal));
+        // 闪烁结束后恢复角标
+        crate::tray_badge::invalidate();
+    });
+}
+
 fn handle_tray_menu_event(app: &AppHandle, event: tauri::menu::MenuEvent) {
     let id = event.id().as_ref();
     match id {
         "show" => focus_main_window(app),
-        "quit" => app.exit(0),
+        "quit" => shutdown::request_exit(app),
+        _ if id.starts_with(favorites_menu::MENU_ID_PREFIX) => {
+            favorites_menu::open_favorite(app, &id[favorites_menu::MENU_ID_PREFIX.len()..]);
+        }
         _ if id.starts_with("tool_") => {
             focus_main_window(app);
             let tool_type = &id[5..]; // strip "tool_" prefix
@@ -230,20 +324,78 @@ fn focus_main_window(app: &AppHandle) {
     }
 }
 
-/// 启动后台 worker：netcat 状态、workflow 调度器、chat bridge poller、MCP gateway。
+/// 启动后台 worker：netcat 状态、workflow / mirror 调度器、端口/HTTP 监控、chat bridge poller、MCP gateway。
 fn init_workers(app: &mut tauri::App) {
     app.manage(commands::toolbox::netcat::NetcatState::new());
+    app.manage(tool_windows::ToolWindowRegistry::new());
 
     {
         let handle = commands::workflows::spawn_scheduler(app.handle().clone());
         app.manage(std::sync::Arc::new(tokio::sync::RwLock::new(handle)));
     }
 
+    // JSON store 变更转发给前端
+    {
+        let handle = app.handle().clone();
+        let mut changes = storage::persisted_store::subscribe();
+        tauri::async_runtime::spawn(async move {
+            loop {
+                match changes.recv().await {
+                    Ok(change) => {
+                        let _ = handle.emit("store-changed", change);
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(_) => break,
+                }
+            }
+        });
+    }
+
+    // 设置文件被外部修改（手动编辑、同步盘、另一个实例）时重新应用并通知前端
+    {
+        let handle = app.handle().clone();
+        let mut changes = storage::external_changes::subscribe();
+        storage::external_changes::spawn_watcher();
+        tauri::async_runtime::spawn(async move {
+            loop {
+                match changes.recv().await {
+                    Ok(change) => {
+                        if change.document == "app_settings" && change.conflict_backup.is_none() {
+                            if let Err(e) = commands::settings::reload_app_settings(&handle).await {
+                                log::warn!("重新应用设置失败: {}", e);
+                            }
+                        }
+                        let _ = handle.emit("settings-changed", change);
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(_) => break,
+                }
+            }
+        });
+    }
+
+    commands::mirror::spawn_scheduler(app.handle().clone());
+    commands::divergence::spawn_divergence_monitor(app.handle().clone());
+    commands::toolbox::port_watch::spawn_port_watcher(app.handle().clone());
+    commands::toolbox::http_monitor::spawn_http_monitor(app.handle().clone());
+    commands::toolbox::resource_alerts::spawn_resource_monitor(app.handle().clone());
+    commands::idle_policy::spawn_idle_janitor(app.handle().clone());
+    commands::toolbox::download_handoff::init(app.handle());
+    commands::toolbox::metrics::init();
+    commands::toolbox::downloader::init(app.handle());
+    commands::git::init_repo_queue(app.handle());
+    favorites_menu::init(app.handle());
+    tray_badge::init(app.handle());
+    commands::usage_stats::init();
+
     {
         let handle = commands::chat_bridge::spawn_bridge(app.handle().clone());
         app.manage(std::sync::Arc::new(tokio::sync::RwLock::new(handle)));
     }
 
+    // git 可执行文件覆盖读入内存
+    tauri::async_runtime::spawn(git_executable::reload());
+
     // 按设置启动内置 MCP Gateway（CodeShelf 面板的一部分）
     tauri::async_runtime::spawn(async {
         if let Err(e) = mcp_gateway::apply_settings_from_storage().await {
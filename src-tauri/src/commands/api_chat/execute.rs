@@ -35,7 +35,7 @@ pub(super) async fn drop_session_client(group_id: &str) {
 }
 
 fn build_session_base_client() -> AppResult<reqwest::Client> {
-    reqwest::Client::builder()
+    crate::http_client::builder()
         .cookie_store(true)
         .timeout(Duration::from_secs(30))
         .build()
@@ -463,7 +463,7 @@ pub async fn execute_api_endpoint(
         .timeout_ms
         .map(|m| Duration::from_millis(m as u64))
         .unwrap_or_else(|| Duration::from_secs(30));
-    let default_client = reqwest::Client::builder()
+    let default_client = crate::http_client::builder()
         .timeout(req_timeout)
         .build()
         .map_err(|e| crate::error::AppError::from(format!("构建 client 失败: {}", e)))?;
@@ -613,7 +613,7 @@ pub async fn fetch_api_document_url(url: String) -> AppResult<String> {
         return Err("只支持 http 或 https 链接".into());
     }
 
-    let client = reqwest::Client::builder()
+    let client = crate::http_client::builder()
         .timeout(Duration::from_secs(30))
         .redirect(reqwest::redirect::Policy::limited(5))
         .build()
@@ -0,0 +1,487 @@
+// 应用配置档案 - 如「工作」「家里」，切换后使用各自的项目范围、静态服务、转发规则、编辑器与终端设置
+//
+// 存储叠加在 StorageConfig 之上：
+// - data/app_profiles.json 记录档案列表与当前激活的档案
+// - data/profiles/<id>/ 存放档案相关的文件（server_configs / forward_rules / editors / terminal），
+//   激活档案后 StorageConfig 的对应路径指向该目录；没有激活档案时即默认档案，文件仍在 data/ 下
+// - 项目都在同一个数据库里，档案只记录可见的项目 id（None 为全部项目）
+// 切换时先停止当前档案运行中的服务和转发、写出未保存的修改，再切换路径并让相关 store 重新读盘。
+
+use super::toolbox::{forwarder, server};
+use crate::error::{AppError, AppResult};
+use crate::storage::config::{sanitize_id, set_active_profile_dir, StorageConfig};
+use crate::storage::db::pool;
+use crate::storage::{current_iso_time, generate_id, get_storage_config, persisted_store};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Mutex;
+
+/// 导出文件格式版本，导入时拒绝更高版本
+const EXPORT_VERSION: u32 = 1;
+
+const EXPORT_FORMAT: &str = "codeshelf-app-profile";
+
+/// 导入文件大小上限；档案只包含几个配置文件，正常导出远小于此
+const MAX_IMPORT_SIZE: u64 = 16 * 1024 * 1024;
+
+/// 随档案切换的文件
+const SCOPED_FILES: &[fn(&StorageConfig) -> PathBuf] = &[
+    StorageConfig::server_configs_file,
+    StorageConfig::forward_rules_file,
+    StorageConfig::editors_file,
+    StorageConfig::terminal_file,
+];
+
+/// 串行化档案的读改写与切换
+static LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AppProfile {
+    pub id: String,
+    pub name: String,
+    /// 档案内可见的项目；None 为全部项目
+    pub project_ids: Option<Vec<String>>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AppProfileList {
+    /// 当前档案 id，None 为默认档案
+    pub active: Option<String>,
+    pub profiles: Vec<AppProfile>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProfileExportFile {
+    format: String,
+    version: u32,
+    exported_at: String,
+    name: String,
+    /// 项目 id 换台机器就对不上，导出项目路径，导入时按路径匹配本机项目
+    project_paths: Option<Vec<String>>,
+    /// 文件名 -> 文件内容
+    files: BTreeMap<String, serde_json::Value>,
+}
+
+fn load_list() -> AppResult<AppProfileList> {
+    let path = get_storage_config()?.app_profiles_file();
+    if !path.exists() {
+        return Ok(AppProfileList::default());
+    }
+    let content = std::fs::read_to_string(&path)?;
+    serde_json::from_str(&content)
+        .map_err(|e| AppError::internal(format!("解析配置档案失败: {}", e)))
+}
+
+fn save_list(list: &AppProfileList) -> AppResult<()> {
+    let config = get_storage_config()?;
+    config.ensure_dirs()?;
+    let path = config.app_profiles_file();
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_string_pretty(list)?)?;
+    std::fs::rename(&tmp, &path)?;
+    Ok(())
+}
+
+fn profile_dir(id: &str) -> AppResult<PathBuf> {
+    Ok(get_storage_config()?
+        .app_profiles_dir()
+        .join(sanitize_id(id)))
+}
+
+fn check_name(list: &AppProfileList, name: &str, except: Option<&str>) -> AppResult<()> {
+    if name.is_empty() {
+        return Err(AppError::invalid("档案名称不能为空"));
+    }
+    let taken = list
+        .profiles
+        .iter()
+        .any(|p| Some(p.id.as_str()) != except && p.name.eq_ignore_ascii_case(name));
+    if taken {
+        return Err(AppError::invalid(format!("档案名称已存在: {}", name)));
+    }
+    Ok(())
+}
+
+/// 把当前档案的文件复制到 target 目录（先写出未保存的修改）
+async fn copy_current_files(target: &Path) -> AppResult<()> {
+    persisted_store::flush_all().await;
+    let config = get_storage_config()?;
+    for path_of in SCOPED_FILES {
+        let source = path_of(config);
+        if let Some(name) = source.file_name() {
+            if source.is_file() {
+                std::fs::copy(&source, target.join(name))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 当前档案可见的项目 id；None 表示不限制
+pub(crate) fn visible_project_ids() -> Option<HashSet<String>> {
+    let list = load_list().ok()?;
+    let active = list.active.as_deref()?;
+    list.profiles
+        .into_iter()
+        .find(|p| p.id == active)?
+        .project_ids
+        .map(|ids| ids.into_iter().collect())
+}
+
+/// 新增的项目加入当前档案（档案限定了项目范围时）
+pub(crate) async fn add_projects_to_active(ids: &[String]) {
+    if ids.is_empty() {
+        return;
+    }
+    let _guard = LOCK.lock().await;
+    let Ok(mut list) = load_list() else {
+        return;
+    };
+    let Some(active) = list.active.clone() else {
+        return;
+    };
+    let Some(profile) = list.profiles.iter_mut().find(|p| p.id == active) else {
+        return;
+    };
+    let Some(project_ids) = profile.project_ids.as_mut() else {
+        return;
+    };
+    project_ids.extend(ids.iter().cloned());
+    profile.updated_at = current_iso_time();
+    if let Err(e) = save_list(&list) {
+        log::warn!("新项目加入配置档案失败: {}", e);
+    }
+}
+
+/// 获取配置档案列表
+#[tauri::command]
+#[specta::specta]
+pub async fn list_app_profiles() -> AppResult<AppProfileList> {
+    load_list()
+}
+
+/// 新建档案；copy_current 为 true 时复制当前档案的项目范围与设置，否则从空白开始（全部项目可见）
+#[tauri::command]
+#[specta::specta]
+pub async fn create_app_profile(name: String, copy_current: Option<bool>) -> AppResult<AppProfile> {
+    let _guard = LOCK.lock().await;
+    let name = name.trim().to_string();
+    let mut list = load_list()?;
+    check_name(&list, &name, None)?;
+
+    let id = generate_id();
+    let dir = profile_dir(&id)?;
+    std::fs::create_dir_all(&dir)?;
+    let mut project_ids = None;
+    if copy_current.unwrap_or(false) {
+        if let Err(e) = copy_current_files(&dir).await {
+            let _ = std::fs::remove_dir_all(&dir);
+            return Err(e);
+        }
+        project_ids = list
+            .active
+            .as_deref()
+            .and_then(|active| list.profiles.iter().find(|p| p.id == active))
+            .and_then(|p| p.project_ids.clone());
+    }
+
+    let now = current_iso_time();
+    let profile = AppProfile {
+        id,
+        name,
+        project_ids,
+        created_at: now.clone(),
+        updated_at: now,
+    };
+    list.profiles.push(profile.clone());
+    save_list(&list)?;
+    Ok(profile)
+}
+
+/// 重命名档案
+#[tauri::command]
+#[specta::specta]
+pub async fn rename_app_profile(id: String, name: String) -> AppResult<AppProfile> {
+    let _guard = LOCK.lock().await;
+    let name = name.trim().to_string();
+    let mut list = load_list()?;
+    check_name(&list, &name, Some(&id))?;
+    let profile = list
+        .profiles
+        .iter_mut()
+        .find(|p| p.id == id)
+        .ok_or_else(|| AppError::invalid(format!("档案不存在: {}", id)))?;
+    profile.name = name;
+    profile.updated_at = current_iso_time();
+    let profile = profile.clone();
+    save_list(&list)?;
+    Ok(profile)
+}
+
+/// 设置档案可见的项目；None 为全部项目
+#[tauri::command]
+#[specta::specta]
+pub async fn set_app_profile_projects(
+    id: String,
+    project_ids: Option<Vec<String>>,
+) -> AppResult<AppProfile> {
+    let _guard = LOCK.lock().await;
+    let mut list = load_list()?;
+    let profile = list
+        .profiles
+        .iter_mut()
+        .find(|p| p.id == id)
+        .ok_or_else(|| AppError::invalid(format!("档案不存在: {}", id)))?;
+    profile.project_ids = project_ids.map(|ids| {
+        let mut seen = HashSet::new();
+        ids.into_iter()
+            .filter(|id| seen.insert(id.clone()))
+            .collect()
+    });
+    profile.updated_at = current_iso_time();
+    let profile = profile.clone();
+    save_list(&list)?;
+    Ok(profile)
+}
+
+/// 删除档案（不能删除当前档案）
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_app_profile(id: String) -> AppResult<()> {
+    let _guard = LOCK.lock().await;
+    let mut list = load_list()?;
+    if list.active.as_deref() == Some(id.as_str()) {
+        return Err(AppError::invalid(
+            "不能删除当前使用的档案，请先切换到其他档案",
+        ));
+    }
+    let before = list.profiles.len();
+    list.profiles.retain(|p| p.id != id);
+    if list.profiles.len() == before {
+        return Err(AppError::invalid(format!("档案不存在: {}", id)));
+    }
+    save_list(&list)?;
+    let dir = profile_dir(&id)?;
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir)?;
+    }
+    Ok(())
+}
+
+/// 切换档案；id 为 None 时回到默认档案。广播 `app-profile-switched`，前端收到后重新加载数据
+#[tauri::command]
+#[specta::specta]
+pub async fn switch_app_profile(app: AppHandle, id: Option<String>) -> AppResult<AppProfileList> {
+    let _guard = LOCK.lock().await;
+    let mut list = load_list()?;
+    if list.active == id {
+        return Ok(list);
+    }
+    let dir = match &id {
+        Some(id) => {
+            if !list.profiles.iter().any(|p| &p.id == id) {
+                return Err(AppError::invalid(format!("档案不存在: {}", id)));
+            }
+            let dir = profile_dir(id)?;
+            std::fs::create_dir_all(&dir)?;
+            Some(dir)
+        }
+        None => None,
+    };
+
+    // 运行中的服务和转发属于当前档案，切换前停止
+    for s in server::get_servers().await? {
+        if s.status == "running" {
+            let _ = server::stop_server(s.id).await;
+        }
+    }
+    for r in forwarder::get_forward_rules().await? {
+        if r.status == "running" {
+            let _ = forwarder::stop_forwarding(r.id).await;
+        }
+    }
+    persisted_store::flush_all().await;
+
+    set_active_profile_dir(dir);
+    server::unload_servers().await;
+    forwarder::unload_rules().await;
+
+    list.active = id;
+    save_list(&list)?;
+    log::info!("切换配置档案: {}", list.active.as_deref().unwrap_or("默认"));
+    let _ = app.emit("app-profile-switched", &list);
+    Ok(list)
+}
+
+/// 导出档案到 file_path；id 为 None 时导出默认档案
+#[tauri::command]
+#[specta::specta]
+pub async fn export_app_profile(id: Option<String>, file_path: String) -> AppResult<()> {
+    let _guard = LOCK.lock().await;
+    let list = load_list()?;
+    let config = get_storage_config()?;
+    let (name, project_ids, dir) = match &id {
+        Some(id) => {
+            let profile = list
+                .profiles
+                .iter()
+                .find(|p| &p.id == id)
+                .ok_or_else(|| AppError::invalid(format!("档案不存在: {}", id)))?;
+            (
+                profile.name.clone(),
+                profile.project_ids.clone(),
+                profile_dir(id)?,
+            )
+        }
+        None => ("默认".to_string(), None, config.data_dir.clone()),
+    };
+    if list.active == id {
+        persisted_store::flush_all().await;
+    }
+
+    let mut files = BTreeMap::new();
+    for path_of in SCOPED_FILES {
+        let Some(file_name) = path_of(config).file_name().map(|n| n.to_owned()) else {
+            continue;
+        };
+        let path = dir.join(&file_name);
+        if path.is_file() {
+            let value = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+            files.insert(file_name.to_string_lossy().into_owned(), value);
+        }
+    }
+
+    let project_paths = match project_ids {
+        Some(ids) => {
+            let ids: HashSet<String> = ids.into_iter().collect();
+            let rows: Vec<(String, String)> = sqlx::query_as("SELECT id, path FROM projects")
+                .fetch_all(pool())
+                .await
+                .map_err(|e| AppError::internal(format!("查询项目失败: {}", e)))?;
+            Some(
+                rows.into_iter()
+                    .filter(|(id, _)| ids.contains(id))
+                    .map(|(_, path)| path)
+                    .collect(),
+            )
+        }
+        None => None,
+    };
+
+    let export = ProfileExportFile {
+        format: EXPORT_FORMAT.to_string(),
+        version: EXPORT_VERSION,
+        exported_at: current_iso_time(),
+        name,
+        project_paths,
+        files,
+    };
+    std::fs::write(&file_path, serde_json::to_string_pretty(&export)?)
+        .map_err(|e| AppError::other(format!("写入导出文件失败: {}", e)))?;
+    Ok(())
+}
+
+/// 从导出文件新建档案；name 为空时沿用文件中的名称（重名时追加序号）
+#[tauri::command]
+#[specta::specta]
+pub async fn import_app_profile(file_path: String, name: Option<String>) -> AppResult<AppProfile> {
+    let size = std::fs::metadata(&file_path)
+        .map_err(|e| AppError::other(format!("读取导入文件失败: {}", e)))?
+        .len();
+    if size > MAX_IMPORT_SIZE {
+        return Err(AppError::invalid(format!(
+            "导入文件过大（{} MB），不是有效的配置档案导出文件",
+            size / 1024 / 1024
+        )));
+    }
+    let content = std::fs::read_to_string(&file_path)
+        .map_err(|e| AppError::other(format!("读取导入文件失败: {}", e)))?;
+    let export: ProfileExportFile = serde_json::from_str(&content)
+        .ok()
+        .filter(|f: &ProfileExportFile| f.format == EXPORT_FORMAT)
+        .ok_or_else(|| AppError::invalid("不是有效的配置档案导出文件"))?;
+    if export.version > EXPORT_VERSION {
+        return Err(AppError::invalid(format!(
+            "导出文件版本 {} 高于当前支持的版本 {}，请升级应用后再导入",
+            export.version, EXPORT_VERSION
+        )));
+    }
+
+    let config = get_storage_config()?;
+    let allowed: HashSet<String> = SCOPED_FILES
+        .iter()
+        .filter_map(|path_of| Some(path_of(config).file_name()?.to_string_lossy().into_owned()))
+        .collect();
+
+    let project_ids = match export.project_paths {
+        Some(paths) => {
+            let paths: HashSet<String> = paths.into_iter().collect();
+            let rows: Vec<(String, String)> = sqlx::query_as("SELECT id, path FROM projects")
+                .fetch_all(pool())
+                .await
+                .map_err(|e| AppError::internal(format!("查询项目失败: {}", e)))?;
+            Some(
+                rows.into_iter()
+                    .filter(|(_, path)| paths.contains(path))
+                    .map(|(id, _)| id)
+                    .collect(),
+            )
+        }
+        None => None,
+    };
+
+    let _guard = LOCK.lock().await;
+    let mut list = load_list()?;
+    let name = match name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()) {
+        Some(name) => {
+            check_name(&list, &name, None)?;
+            name
+        }
+        None => {
+            let base = export.name.trim().to_string();
+            let base = if base.is_empty() {
+                "导入的档案".to_string()
+            } else {
+                base
+            };
+            let mut candidate = base.clone();
+            let mut n = 2;
+            while check_name(&list, &candidate, None).is_err() {
+                candidate = format!("{} ({})", base, n);
+                n += 1;
+            }
+            candidate
+        }
+    };
+
+    let id = generate_id();
+    let dir = profile_dir(&id)?;
+    std::fs::create_dir_all(&dir)?;
+    for (file_name, value) in &export.files {
+        if !allowed.contains(file_name) {
+            log::warn!("导入配置档案时跳过未知文件: {}", file_name);
+            continue;
+        }
+        std::fs::write(dir.join(file_name), serde_json::to_string(value)?)?;
+    }
+
+    let now = current_iso_time();
+    let profile = AppProfile {
+        id,
+        name,
+        project_ids,
+        created_at: now.clone(),
+        updated_at: now,
+    };
+    list.profiles.push(profile.clone());
+    save_list(&list)?;
+    Ok(profile)
+}
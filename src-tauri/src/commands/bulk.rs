@@ -0,0 +1,462 @@
+//! 批量项目操作：对选中的项目逐个执行耗时操作，推送逐项进度与结果。
+//!
+//! 支持的操作：
+//!   - fetch：`git fetch --all`
+//!   - prune：对每个远程执行 `git remote prune`，清理已删除的远程分支引用
+//!   - refreshStats：重新统计项目提交数据，结束后重新聚合 dashboard
+//!   - addLabel / removeLabel：批量添加 / 移除标签
+//!
+//! 操作记录持久化到 bulk_operations.json：取消在当前项完成后生效，剩余项保持 pending；
+//! 应用退出时仍在执行的操作再次读取时显示为 interrupted。两者都可以从未完成的项继续执行。
+
+use crate::error::AppResult;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::commands::git::{lock_repo, run_git_command};
+use crate::storage::db::pool;
+use crate::storage::persisted_store::backup_invalid_file;
+use crate::storage::{current_iso_time, generate_id, get_storage_config};
+
+/// 保留的操作记录条数
+const MAX_OPERATIONS: usize = 20;
+
+const ACTIONS: &[&str] = &["fetch", "prune", "refreshStats", "addLabel", "removeLabel"];
+
+/// 正在执行的操作 id -> 取消标记
+static RUNNING: Lazy<std::sync::Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    Lazy::new(|| std::sync::Mutex::new(HashMap::new()));
+
+/// 串行化 bulk_operations.json 的读-改-写
+static FILE_LOCK: Lazy<std::sync::Mutex<()>> = Lazy::new(|| std::sync::Mutex::new(()));
+
+// ========== 数据模型 ==========
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkItemResult {
+    pub project_id: String,
+    pub project_name: String,
+    pub project_path: String,
+    /// "pending" | "success" | "failed" | "skipped"
+    pub status: String,
+    pub message: Option<String>,
+    pub duration_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkOperation {
+    pub id: String,
+    pub action: String,
+    /// addLabel / removeLabel 使用的标签
+    pub label: Option<String>,
+    /// "running" | "completed" | "cancelled" | "interrupted"
+    pub status: String,
+    pub items: Vec<BulkItemResult>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub finished_at: Option<String>,
+}
+
+/// 每完成一项推送一次 `bulk-operation-progress`
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkProgress {
+    pub operation_id: String,
+    pub index: u32,
+    pub done: u32,
+    pub total: u32,
+    pub item: BulkItemResult,
+}
+
+// ========== 存储 ==========
+
+fn is_running(id: &str) -> bool {
+    RUNNING
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .contains_key(id)
+}
+
+fn read_operations() -> AppResult<Vec<BulkOperation>> {
+    let path = get_storage_config()?.bulk_operations_file();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path)
+        .map_err(|e| crate::error::AppError::from(format!("读取批量操作记录失败: {}", e)))?;
+    match serde_json::from_str(&content) {
+        Ok(operations) => Ok(operations),
+        Err(e) => {
+            log::error!("解析批量操作记录失败: {}", e);
+            // 备份后移走原文件：下次保存不会覆盖原记录，之后的读取也不会重复备份
+            if backup_invalid_file(&path, "批量操作记录").is_some() {
+                let _ = fs::remove_file(&path);
+            }
+            Ok(Vec::new())
+        }
+    }
+}
+
+fn write_operations(operations: &[BulkOperation]) -> AppResult<()> {
+    let config = get_storage_config()?;
+    config.ensure_dirs()?;
+    let content = serde_json::to_string_pretty(operations)
+        .map_err(|e| crate::error::AppError::from(format!("序列化批量操作记录失败: {}", e)))?;
+    fs::write(config.bulk_operations_file(), content)
+        .map_err(|e| crate::error::AppError::from(format!("保存批量操作记录失败: {}", e)))
+}
+
+/// 读取操作记录；记录为 running 但当前进程没有在执行的（上次退出时中断）显示为 interrupted
+fn load_operations() -> AppResult<Vec<BulkOperation>> {
+    let _guard = FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut operations = read_operations()?;
+    for op in operations.iter_mut() {
+        if op.status == "running" && !is_running(&op.id) {
+            op.status = "interrupted".to_string();
+        }
+    }
+    Ok(operations)
+}
+
+/// 写回一条操作记录（新记录插到最前面，超出上限的旧记录丢弃）
+fn save_operation(operation: &BulkOperation) -> AppResult<()> {
+    let _guard = FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut operations = read_operations()?;
+    match operations.iter_mut().find(|op| op.id == operation.id) {
+        Some(existing) => *existing = operation.clone(),
+        None => operations.insert(0, operation.clone()),
+    }
+    operations.truncate(MAX_OPERATIONS);
+    write_operations(&operations)
+}
+
+fn find_operation(id: &str) -> AppResult<BulkOperation> {
+    load_operations()?
+        .into_iter()
+        .find(|op| op.id == id)
+        .ok_or_else(|| crate::error::AppError::invalid("批量操作不存在"))
+}
+
+// ========== 执行 ==========
+
+fn is_git_repo(path: &str) -> bool {
+    Path::new(path).join(".git").exists()
+}
+
+fn git_fetch_all(path: &str) -> AppResult<String> {
+    run_git_command(path, &["fetch", "--all"])?;
+    Ok("已拉取所有远程".to_string())
+}
+
+fn git_prune_remotes(path: &str) -> AppResult<String> {
+    let remotes = run_git_command(path, &["remote"])?;
+    let mut pruned = 0;
+    for remote in remotes.lines().filter(|r| !r.trim().is_empty()) {
+        let output = run_git_command(path, &["remote", "prune", remote.trim()])?;
+        pruned += output.lines().filter(|l| l.contains("[pruned]")).count();
+    }
+    Ok(format!("清理了 {} 个远程分支引用", pruned))
+}
+
+async fn set_label(project_id: &str, label: &str, add: bool) -> AppResult<String> {
+    let sql = if add {
+        "INSERT INTO project_labels (project_id, label) VALUES (?, ?) ON CONFLICT DO NOTHING"
+    } else {
+        "DELETE FROM project_labels WHERE project_id = ? AND label = ?"
+    };
+    let result = sqlx::query(sql)
+        .bind(project_id)
+        .bind(label)
+        .execute(pool())
+        .await
+        .map_err(|e| crate::error::AppError::from(format!("更新标签失败: {}", e)))?;
+    Ok(match (add, result.rows_affected()) {
+        (true, 0) => "已有该标签".to_string(),
+        (true, _) => "已添加标签".to_string(),
+        (false, 0) => "没有该标签".to_string(),
+        (false, _) => "已移除标签".to_string(),
+    })
+}
+
+/// 执行单项：返回 (状态, 说明)
+async fn run_item(action: &str, label: Option<&str>, item: &BulkItemResult) -> (String, String) {
+    let needs_git = matches!(action, "fetch" | "prune" | "refreshStats");
+    if needs_git && !is_git_repo(&item.project_path) {
+        return ("skipped".to_string(), "不是 Git 仓库".to_string());
+    }
+    let result = match action {
+        "fetch" | "prune" => {
+            let path = item.project_path.clone();
+            let fetch = action == "fetch";
+            let _guard = lock_repo(
+                &path,
+                if fetch {
+                    "获取远程更新"
+                } else {
+                    "清理远程分支引用"
+                },
+            )
+            .await;
+            tokio::task::spawn_blocking(move || {
+                if fetch {
+                    git_fetch_all(&path)
+                } else {
+                    git_prune_remotes(&path)
+                }
+            })
+            .await
+            .map_err(|e| crate::error::AppError::internal(e.to_string()))
+            .and_then(|r| r)
+        }
+        "refreshStats" => super::stats::refresh_project_stats(
+            item.project_name.clone(),
+            item.project_path.clone(),
+        )
+        .await
+        .map(|_| "统计已刷新".to_string()),
+        "addLabel" | "removeLabel" => {
+            set_label(&item.project_id, label.unwrap_or(""), action == "addLabel").await
+        }
+        _ => Err(crate::error::AppError::invalid(format!(
+            "不支持的操作: {}",
+            action
+        ))),
+    };
+    match result {
+        Ok(message) => ("success".to_string(), message),
+        Err(e) => ("failed".to_string(), e.to_string()),
+    }
+}
+
+/// 依次执行所有 pending 项，每项完成后落盘并推送进度
+async fn run_operation(app: AppHandle, mut operation: BulkOperation, cancel: Arc<AtomicBool>) {
+    let total = operation.items.len() as u32;
+    let mut changed_stats = false;
+
+    for index in 0..operation.items.len() {
+        if operation.items[index].status != "pending" {
+            continue;
+        }
+        if cancel.load(Ordering::Relaxed) {
+            operation.status = "cancelled".to_string();
+            break;
+        }
+        let started = Instant::now();
+        let (status, message) = run_item(
+            &operation.action,
+            operation.label.as_deref(),
+            &operation.items[index],
+        )
+        .await;
+        changed_stats |= operation.action == "refreshStats" && status == "success";
+
+        let item = &mut operation.items[index];
+        item.status = status;
+        item.message = Some(message);
+        item.duration_ms = Some(started.elapsed().as_millis() as u64);
+        operation.updated_at = current_iso_time();
+        if let Err(e) = save_operation(&operation) {
+            log::warn!("保存批量操作进度失败: {}", e);
+        }
+
+        let done = operation
+            .items
+            .iter()
+            .filter(|i| i.status != "pending")
+            .count() as u32;
+        let _ = app.emit(
+            "bulk-operation-progress",
+            BulkProgress {
+                operation_id: operation.id.clone(),
+                index: index as u32,
+                done,
+                total,
+                item: operation.items[index].clone(),
+            },
+        );
+    }
+
+    if changed_stats {
+        if let Err(e) = super::stats::rebuild_dashboard().await {
+            log::warn!("批量刷新统计后聚合 dashboard 失败: {}", e);
+        }
+    }
+    if operation.status == "running" {
+        operation.status = "completed".to_string();
+    }
+    operation.updated_at = current_iso_time();
+    operation.finished_at = Some(operation.updated_at.clone());
+    if let Err(e) = save_operation(&operation) {
+        log::warn!("保存批量操作结果失败: {}", e);
+    }
+    RUNNING
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(&operation.id);
+    let _ = app.emit("bulk-operation-finished", &operation);
+}
+
+/// 标记为 running 并在后台执行
+fn spawn_operation(app: AppHandle, mut operation: BulkOperation) -> AppResult<BulkOperation> {
+    let cancel = Arc::new(AtomicBool::new(false));
+    {
+        let mut running = RUNNING.lock().unwrap_or_else(|e| e.into_inner());
+        if running.contains_key(&operation.id) {
+            return Err(crate::error::AppError::invalid("该批量操作正在执行"));
+        }
+        running.insert(operation.id.clone(), cancel.clone());
+    }
+    operation.status = "running".to_string();
+    operation.finished_at = None;
+    operation.updated_at = current_iso_time();
+    if let Err(e) = save_operation(&operation) {
+        RUNNING
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&operation.id);
+        return Err(e);
+    }
+    tauri::async_runtime::spawn(run_operation(app, operation.clone(), cancel));
+    Ok(operation)
+}
+
+// ========== Tauri 命令 ==========
+
+/// 开始批量操作；不存在的项目记为 skipped。立即返回，进度通过事件推送
+#[tauri::command]
+#[specta::specta]
+pub async fn start_bulk_operation(
+    app: AppHandle,
+    action: String,
+    project_ids: Vec<String>,
+    label: Option<String>,
+) -> AppResult<BulkOperation> {
+    if !ACTIONS.contains(&action.as_str()) {
+        return Err(crate::error::AppError::invalid(format!(
+            "不支持的操作: {}，可选：{}",
+            action,
+            ACTIONS.join(", ")
+        )));
+    }
+    let label = label
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty());
+    if action.ends_with("Label") && label.is_none() {
+        return Err(crate::error::AppError::invalid("标签不能为空"));
+    }
+    if project_ids.is_empty() {
+        return Err(crate::error::AppError::invalid("至少选择一个项目"));
+    }
+
+    let mut items = Vec::with_capacity(project_ids.len());
+    for id in project_ids {
+        let row: Option<(String, String)> =
+            sqlx::query_as("SELECT name, path FROM projects WHERE id = ?")
+                .bind(&id)
+                .fetch_optional(pool())
+                .await
+                .map_err(|e| crate::error::AppError::from(format!("查询项目失败: {}", e)))?;
+        let (status, message) = match row {
+            Some(_) => ("pending", None),
+            None => ("skipped", Some("项目不存在".to_string())),
+        };
+        let (name, path) = row.unwrap_or_default();
+        items.push(BulkItemResult {
+            project_id: id,
+            project_name: name,
+            project_path: path,
+            status: status.to_string(),
+            message,
+            duration_ms: None,
+        });
+    }
+
+    let now = current_iso_time();
+    let operation = BulkOperation {
+        id: generate_id(),
+        action,
+        label,
+        status: "running".to_string(),
+        items,
+        created_at: now.clone(),
+        updated_at: now,
+        finished_at: None,
+    };
+    spawn_operation(app, operation)
+}
+
+/// 取消批量操作：当前项完成后停止，剩余项保持 pending，可继续执行
+#[tauri::command]
+#[specta::specta]
+pub async fn cancel_bulk_operation(id: String) -> AppResult<()> {
+    let running = RUNNING.lock().unwrap_or_else(|e| e.into_inner());
+    let cancel = running
+        .get(&id)
+        .ok_or_else(|| crate::error::AppError::invalid("该批量操作未在执行"))?;
+    cancel.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+/// 继续执行已取消 / 中断的批量操作；retry_failed 为 true 时失败项也重新执行
+#[tauri::command]
+#[specta::specta]
+pub async fn resume_bulk_operation(
+    app: AppHandle,
+    id: String,
+    retry_failed: Option<bool>,
+) -> AppResult<BulkOperation> {
+    let mut operation = find_operation(&id)?;
+    if operation.status == "running" {
+        return Err(crate::error::AppError::invalid("该批量操作正在执行"));
+    }
+    let retry_failed = retry_failed.unwrap_or(false);
+    for item in operation.items.iter_mut() {
+        if retry_failed && item.status == "failed" {
+            item.status = "pending".to_string();
+            item.message = None;
+            item.duration_ms = None;
+        }
+    }
+    if !operation.items.iter().any(|i| i.status == "pending") {
+        return Err(crate::error::AppError::invalid("没有待执行的项目"));
+    }
+    spawn_operation(app, operation)
+}
+
+/// 批量操作记录（最新在前）
+#[tauri::command]
+#[specta::specta]
+pub async fn list_bulk_operations() -> AppResult<Vec<BulkOperation>> {
+    load_operations()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_bulk_operation(id: String) -> AppResult<BulkOperation> {
+    find_operation(&id)
+}
+
+/// 删除已结束的批量操作记录
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_bulk_operation(id: String) -> AppResult<()> {
+    if is_running(&id) {
+        return Err(crate::error::AppError::invalid(
+            "该批量操作正在执行，请先取消",
+        ));
+    }
+    let _guard = FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut operations = read_operations()?;
+    operations.retain(|op| op.id != id);
+    write_operations(&operations)
+}
@@ -114,8 +114,9 @@ static CHAT_ABORTS: Lazy<Arc<RwLock<HashMap<String, tokio::task::AbortHandle>>>>
 
 /// 复用的 HTTP 客户端：设连接超时 + TCP keepalive；
 /// 流式请求不设整体超时（长连接），仅靠 connect_timeout 守护拨号阶段。
+/// 代理设置在首次使用时读取，修改后需重启生效。
 static HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
-    reqwest::Client::builder()
+    crate::http_client::builder()
         .connect_timeout(std::time::Duration::from_secs(15))
         .tcp_keepalive(std::time::Duration::from_secs(30))
         .build()
@@ -133,20 +134,12 @@ fn get_default_chat_dir() -> AppResult<PathBuf> {
     Ok(config.conversations_dir())
 }
 
-fn get_app_settings() -> AppResult<AppSettings> {
-    let config = get_storage_config()?;
-    let path = config.app_settings_file();
-    if !path.exists() {
-        return Ok(AppSettings::default());
-    }
-    let content = fs::read_to_string(&path)
-        .map_err(|e| crate::error::AppError::from(format!("读取应用设置失败: {}", e)))?;
-    let settings: AppSettings = serde_json::from_str(&content).unwrap_or_default();
-    Ok(settings)
+fn load_app_settings() -> AppResult<AppSettings> {
+    crate::storage::documents::load(&crate::storage::documents::APP_SETTINGS)
 }
 
 fn resolve_chat_history_dir() -> AppResult<PathBuf> {
-    let settings = get_app_settings()?;
+    let settings = load_app_settings()?;
     if let Some(dir) = settings.chat_history_dir {
         if dir.trim().is_empty() {
             return get_default_chat_dir();
@@ -48,13 +48,7 @@ pub struct BridgeHandle {
 }
 
 fn load_settings_sync() -> Option<AppSettings> {
-    let config = crate::storage::get_storage_config().ok()?;
-    let path = config.app_settings_file();
-    if !path.exists() {
-        return Some(AppSettings::default());
-    }
-    let content = std::fs::read_to_string(&path).ok()?;
-    serde_json::from_str::<AppSettings>(&content).ok()
+    crate::storage::documents::load(&crate::storage::documents::APP_SETTINGS).ok()
 }
 
 async fn fetch_pending(
@@ -123,7 +117,7 @@ async fn run_llm(provider_id: &str, model_id: &str, prompt: &str) -> AppResult<S
         "{}/chat/completions",
         provider.base_url.trim_end_matches('/')
     );
-    let client = reqwest::Client::builder()
+    let client = crate::http_client::builder()
         .timeout(Duration::from_secs(120))
         .build()
         .map_err(|e| crate::error::AppError::from(e.to_string()))?;
@@ -179,7 +173,7 @@ async fn tick(app: &AppHandle, settings: &AppSettings) {
         .unwrap_or("codeshelf")
         .to_string();
 
-    let client = match reqwest::Client::builder()
+    let client = match crate::http_client::builder()
         .timeout(Duration::from_secs(15))
         .build()
     {
@@ -261,7 +255,7 @@ pub async fn notify_reload(app: &AppHandle) {
 #[specta::specta]
 pub async fn chat_bridge_test(relay: String) -> AppResult<String> {
     let url = format!("{}/health", relay.trim_end_matches('/'));
-    let client = reqwest::Client::builder()
+    let client = crate::http_client::builder()
         .timeout(Duration::from_secs(10))
         .build()
         .map_err(|e| crate::error::AppError::from(e.to_string()))?;
@@ -0,0 +1,387 @@
+// 跨项目提交搜索索引（SQLite FTS5，表结构见 migrations/v3_commit_index.sql）
+//
+// - 统计刷新脏项目时顺带增量索引（schedule_index）：记录每个项目上次索引时的引用位置，
+//   下次只用 `git log --all --not <旧位置>` 取新增提交
+// - 引用被改写（rebase、删除分支）导致旧位置失效时，整个项目重建
+// - search_all_commits 直接查索引，不再逐个仓库跑 git log
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sqlx::Acquire;
+use tokio::sync::Mutex;
+
+use crate::commands::git::run_git_command;
+use crate::commands::stats::ProjectInfo;
+use crate::error::{AppError, AppResult};
+use crate::storage::current_iso_time;
+use crate::storage::db::pool;
+
+/// 单个项目一次最多索引的提交数（首次索引超大仓库时避免长时间占用，更早的提交不再补录）
+const MAX_COMMITS_PER_RUN: usize = 50_000;
+
+/// 串行化索引写入，避免同一项目被并发索引写出重复行
+static INDEX_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitSearchFilters {
+    /// 只搜索这些项目（为空搜索全部）
+    #[serde(default)]
+    pub project_paths: Option<Vec<String>>,
+    /// 作者名或邮箱（模糊匹配）
+    #[serde(default)]
+    pub author: Option<String>,
+    /// 起始日期，如 "2024-01-01"
+    #[serde(default)]
+    pub since: Option<String>,
+    /// 截止日期（含当天）
+    #[serde(default)]
+    pub until: Option<String>,
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitSearchHit {
+    pub project_path: String,
+    pub project_name: Option<String>,
+    pub hash: String,
+    pub short_hash: String,
+    pub message: String,
+    pub body: Option<String>,
+    pub author: String,
+    pub email: String,
+    pub date: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitIndexStatus {
+    pub projects: u32,
+    pub commits: u32,
+    pub last_indexed_at: Option<String>,
+}
+
+struct IndexedCommit {
+    hash: String,
+    short_hash: String,
+    author: String,
+    email: String,
+    date: String,
+    message: String,
+    body: String,
+}
+
+fn db_err(action: &str, e: sqlx::Error) -> AppError {
+    AppError::from(format!("{}失败: {}", action, e))
+}
+
+/// 当前所有分支/远程分支/标签及 HEAD 指向的提交
+fn current_tips(path: &str) -> AppResult<Vec<String>> {
+    let refs = run_git_command(
+        path,
+        &[
+            "for-each-ref",
+            "--format=%(objectname)",
+            "refs/heads",
+            "refs/remotes",
+            "refs/tags",
+        ],
+    )?;
+    let mut tips: Vec<String> = refs.lines().map(|l| l.trim().to_string()).collect();
+    if let Ok(head) = run_git_command(path, &["rev-parse", "HEAD"]) {
+        tips.push(head);
+    }
+    tips.retain(|t| !t.is_empty());
+    tips.sort();
+    tips.dedup();
+    Ok(tips)
+}
+
+fn read_commits(path: &str, exclude: &[String]) -> AppResult<Vec<IndexedCommit>> {
+    let limit = format!("-{}", MAX_COMMITS_PER_RUN);
+    let mut args = vec![
+        "log",
+        "--all",
+        &limit,
+        "--format=%x1e%H%x1f%h%x1f%an%x1f%ae%x1f%aI%x1f%s%x1f%b",
+    ];
+    if !exclude.is_empty() {
+        args.push("--not");
+        args.extend(exclude.iter().map(|s| s.as_str()));
+    }
+    let output = run_git_command(path, &args)?;
+
+    Ok(output
+        .split('\x1e')
+        .filter_map(|record| {
+            let parts: Vec<&str> = record.split('\x1f').collect();
+            (parts.len() >= 7).then(|| IndexedCommit {
+                hash: parts[0].trim().to_string(),
+                short_hash: parts[1].trim().to_string(),
+                author: parts[2].trim().to_string(),
+                email: parts[3].trim().to_string(),
+                date: parts[4].trim().to_string(),
+                message: parts[5].trim().to_string(),
+                body: parts[6].trim().to_string(),
+            })
+        })
+        .collect())
+}
+
+/// 增量索引单个项目，返回新增的提交数
+async fn index_project(path: &str, rebuild: bool) -> AppResult<usize> {
+    let _guard = INDEX_LOCK.lock().await;
+
+    let old_tips: Vec<String> = if rebuild {
+        Vec::new()
+    } else {
+        sqlx::query_scalar::<_, String>(
+            "SELECT tips FROM commit_index_state WHERE project_path = ?",
+        )
+        .bind(path)
+        .fetch_optional(pool())
+        .await
+        .map_err(|e| db_err("读取索引状态", e))?
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+    };
+
+    let repo = path.to_string();
+    let old = old_tips.clone();
+    let (tips, commits, full) = tokio::task::spawn_blocking(move || {
+        let tips = current_tips(&repo)?;
+        if !old.is_empty() && tips == old {
+            return Ok((tips, Vec::new(), false));
+        }
+        match read_commits(&repo, &old) {
+            Ok(commits) => Ok((tips, commits, old.is_empty())),
+            // 旧位置已不存在（历史被改写）→ 全量重建
+            Err(_) if !old.is_empty() => read_commits(&repo, &[]).map(|c| (tips, c, true)),
+            Err(e) => Err(e),
+        }
+    })
+    .await
+    .map_err(|e| AppError::internal(e.to_string()))??;
+
+    if !full && commits.is_empty() && tips == old_tips {
+        return Ok(0);
+    }
+
+    let mut conn = pool().acquire().await.map_err(|e| db_err("获取连接", e))?;
+    let mut tx = conn.begin().await.map_err(|e| db_err("开启事务", e))?;
+    if full {
+        sqlx::query("DELETE FROM commit_index WHERE project_path = ?")
+            .bind(path)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_err("清除旧索引", e))?;
+    }
+    for c in &commits {
+        sqlx::query(
+            "INSERT INTO commit_index
+             (message, body, author, email, project_path, hash, short_hash, date)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&c.message)
+        .bind(&c.body)
+        .bind(&c.author)
+        .bind(&c.email)
+        .bind(path)
+        .bind(&c.hash)
+        .bind(&c.short_hash)
+        .bind(&c.date)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_err("写入提交索引", e))?;
+    }
+    let tips_json = serde_json::to_string(&tips).unwrap_or_else(|_| "[]".to_string());
+    sqlx::query(
+        "INSERT INTO commit_index_state (project_path, tips, indexed_at) VALUES (?, ?, ?)
+         ON CONFLICT(project_path) DO UPDATE SET tips = excluded.tips, indexed_at = excluded.indexed_at",
+    )
+    .bind(path)
+    .bind(&tips_json)
+    .bind(current_iso_time())
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| db_err("写入索引状态", e))?;
+    tx.commit().await.map_err(|e| db_err("提交事务", e))?;
+
+    Ok(commits.len())
+}
+
+/// 后台增量索引（统计刷新脏项目后调用）
+pub fn schedule_index(paths: Vec<String>) {
+    if paths.is_empty() {
+        return;
+    }
+    tokio::spawn(async move {
+        for path in paths {
+            if let Err(e) = index_project(&path, false).await {
+                log::warn!("索引提交失败 {}: {}", path, e);
+            }
+        }
+    });
+}
+
+/// 索引给定项目并清理已移除项目的索引；rebuild=true 时全量重建
+#[tauri::command]
+#[specta::specta]
+pub async fn update_commit_index(
+    projects: Vec<ProjectInfo>,
+    rebuild: Option<bool>,
+) -> AppResult<u32> {
+    let rebuild = rebuild.unwrap_or(false);
+    let mut added = 0usize;
+    for project in &projects {
+        match index_project(&project.path, rebuild).await {
+            Ok(n) => added += n,
+            Err(e) => log::warn!("索引提交失败 {}: {}", project.path, e),
+        }
+    }
+
+    let indexed: Vec<String> = sqlx::query_scalar("SELECT project_path FROM commit_index_state")
+        .fetch_all(pool())
+        .await
+        .map_err(|e| db_err("读取索引状态", e))?;
+    for path in indexed {
+        if projects.iter().any(|p| p.path == path) {
+            continue;
+        }
+        sqlx::query("DELETE FROM commit_index WHERE project_path = ?")
+            .bind(&path)
+            .execute(pool())
+            .await
+            .map_err(|e| db_err("清理索引", e))?;
+        sqlx::query("DELETE FROM commit_index_state WHERE project_path = ?")
+            .bind(&path)
+            .execute(pool())
+            .await
+            .map_err(|e| db_err("清理索引状态", e))?;
+    }
+
+    Ok(added as u32)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_commit_index_status() -> AppResult<CommitIndexStatus> {
+    let (projects, last_indexed_at): (i64, Option<String>) =
+        sqlx::query_as("SELECT COUNT(*), MAX(indexed_at) FROM commit_index_state")
+            .fetch_one(pool())
+            .await
+            .map_err(|e| db_err("读取索引状态", e))?;
+    let commits: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM commit_index")
+        .fetch_one(pool())
+        .await
+        .map_err(|e| db_err("统计索引", e))?;
+    Ok(CommitIndexStatus {
+        projects: projects as u32,
+        commits: commits as u32,
+        last_indexed_at,
+    })
+}
+
+/// 构造子串匹配的 LIKE 模式，转义 `%` `_` 与转义符本身；配合 `ESCAPE '\'` 使用
+fn like_pattern(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+/// 跨项目搜索提交信息（按提交时间倒序）
+#[tauri::command]
+#[specta::specta]
+pub async fn search_all_commits(
+    query: String,
+    filters: Option<CommitSearchFilters>,
+) -> AppResult<Vec<CommitSearchHit>> {
+    let filters = filters.unwrap_or_default();
+    let query = query.trim();
+    let mut sql = String::from(
+        "SELECT c.project_path, p.name, c.hash, c.short_hash, c.message, c.body, c.author, c.email, c.date
+         FROM commit_index c LEFT JOIN projects p ON p.path = c.project_path WHERE 1 = 1",
+    );
+    let mut binds: Vec<String> = Vec::new();
+
+    if query.chars().count() >= 3 {
+        // 整体作为短语匹配，避免用户输入被当作 FTS 语法
+        sql.push_str(" AND c.commit_index MATCH ?");
+        binds.push(format!("\"{}\"", query.replace('"', "\"\"")));
+    } else if !query.is_empty() {
+        // trigram 不支持少于 3 个字符，退回 LIKE
+        sql.push_str(" AND (c.message LIKE ? ESCAPE '\\' OR c.body LIKE ? ESCAPE '\\')");
+        let like = like_pattern(query);
+        binds.push(like.clone());
+        binds.push(like);
+    }
+    if let Some(author) = filters.author.as_deref().filter(|a| !a.trim().is_empty()) {
+        sql.push_str(" AND (c.author LIKE ? ESCAPE '\\' OR c.email LIKE ? ESCAPE '\\')");
+        let like = like_pattern(author.trim());
+        binds.push(like.clone());
+        binds.push(like);
+    }
+    // 按给定日期的精度比较（"2024-01" / "2024-01-31" / 完整时间都可以）
+    if let Some(since) = filters.since.filter(|s| !s.is_empty()) {
+        sql.push_str(" AND substr(c.date, 1, length(?)) >= ?");
+        binds.push(since.clone());
+        binds.push(since);
+    }
+    if let Some(until) = filters.until.filter(|s| !s.is_empty()) {
+        sql.push_str(" AND substr(c.date, 1, length(?)) <= ?");
+        binds.push(until.clone());
+        binds.push(until);
+    }
+    if let Some(paths) = filters.project_paths.filter(|p| !p.is_empty()) {
+        sql.push_str(&format!(
+            " AND c.project_path IN ({})",
+            vec!["?"; paths.len()].join(", ")
+        ));
+        binds.extend(paths);
+    }
+    sql.push_str(" ORDER BY c.date DESC LIMIT ?");
+
+    type Row = (
+        String,
+        Option<String>,
+        String,
+        String,
+        String,
+        String,
+        String,
+        String,
+        String,
+    );
+    let mut q = sqlx::query_as::<_, Row>(&sql);
+    for b in &binds {
+        q = q.bind(b);
+    }
+    let rows = q
+        .bind(filters.limit.unwrap_or(100).min(1000) as i64)
+        .fetch_all(pool())
+        .await
+        .map_err(|e| db_err("搜索提交", e))?;
+
+    Ok(rows
+        .into_iter()
+        .map(
+            |(project_path, project_name, hash, short_hash, message, body, author, email, date)| {
+                CommitSearchHit {
+                    project_path,
+                    project_name,
+                    hash,
+                    short_hash,
+                    message,
+                    body: (!body.is_empty()).then_some(body),
+                    author,
+                    email,
+                    date,
+                }
+            },
+        )
+        .collect())
+}
@@ -0,0 +1,525 @@
+// 项目合规扫描 - 依赖许可证 + 疑似提交的密钥
+//
+// - 许可证：读取 package.json（依赖的 license 取自 node_modules 下已安装的包）、
+//   Cargo.toml / Cargo.lock（依赖的 license 取自 ~/.cargo/registry/src 中的包）、
+//   requirements.txt（只列出依赖名，许可证未知）
+// - 密钥：按 SECRET_RULES 正则逐行扫描；git 仓库只扫描已跟踪文件（git ls-files），
+//   否则遍历目录并跳过依赖 / 构建目录；匹配内容打码后再写入报告
+// 扫描报告保存在 SQLite project_compliance 表，随项目删除级联删除。
+
+use crate::commands::git::run_git_command;
+use crate::error::{AppError, AppResult};
+use crate::storage::current_iso_time;
+use crate::storage::db::pool;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// 单个文件大小上限，超过的文件不扫描（多为数据 / 打包产物）
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+/// 最多扫描的文件数
+const MAX_FILES: usize = 20_000;
+
+/// 最多记录的密钥命中数
+const MAX_FINDINGS: usize = 500;
+
+/// 非 git 仓库时跳过的目录
+const SKIP_DIRS: &[&str] = &[
+    ".git",
+    "node_modules",
+    "target",
+    "dist",
+    "build",
+    "vendor",
+    ".venv",
+    "venv",
+    "__pycache__",
+    ".next",
+    ".cache",
+];
+
+/// 锁文件里全是哈希，容易误报，不做密钥扫描
+const SKIP_FILES: &[&str] = &[
+    "package-lock.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "Cargo.lock",
+    "poetry.lock",
+    "go.sum",
+];
+
+/// 传染性许可证关键字（命中时标记 copyleft）
+const COPYLEFT: &[&str] = &["AGPL", "LGPL", "GPL", "SSPL", "EUPL", "OSL"];
+
+/// 依赖许可证
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyLicense {
+    pub name: String,
+    pub version: Option<String>,
+    /// "npm" | "cargo" | "pypi"
+    pub ecosystem: String,
+    /// 未能读取时为 None
+    pub license: Option<String>,
+    pub copyleft: bool,
+}
+
+/// 疑似密钥
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretFinding {
+    pub rule: String,
+    /// "high" | "medium" | "low"
+    pub severity: String,
+    /// 相对项目根目录的路径
+    pub file: String,
+    pub line: u32,
+    /// 打码后的匹配内容
+    pub preview: String,
+}
+
+/// 合规扫描报告
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ComplianceReport {
+    pub project_path: String,
+    /// 项目自身声明的许可证
+    pub project_license: Option<String>,
+    pub dependencies: Vec<DependencyLicense>,
+    /// license -> 依赖数量
+    pub license_summary: BTreeMap<String, u32>,
+    pub secrets: Vec<SecretFinding>,
+    pub files_scanned: u32,
+    /// 命中数超过上限被截断
+    pub truncated: bool,
+    pub scanned_at: String,
+}
+
+struct SecretRule {
+    name: &'static str,
+    severity: &'static str,
+    regex: Regex,
+}
+
+static SECRET_RULES: Lazy<Vec<SecretRule>> = Lazy::new(|| {
+    [
+        ("aws_access_key", "high", r"\b(AKIA|ASIA)[0-9A-Z]{16}\b"),
+        (
+            "aws_secret_key",
+            "high",
+            r#"(?i)aws_?secret_?access_?key["']?\s*[:=]\s*["']?[A-Za-z0-9/+=]{40}"#,
+        ),
+        (
+            "private_key",
+            "high",
+            r"-----BEGIN (RSA |EC |DSA |OPENSSH |PGP |ENCRYPTED )?PRIVATE KEY( BLOCK)?-----",
+        ),
+        ("github_token", "high", r"\b(gh[pousr]_[A-Za-z0-9]{36,}|github_pat_[A-Za-z0-9_]{60,})"),
+        ("gitlab_token", "high", r"\bglpat-[A-Za-z0-9_-]{20,}"),
+        ("slack_token", "high", r"\bxox[baprs]-[A-Za-z0-9-]{10,}"),
+        ("stripe_key", "high", r"\b[sr]k_live_[0-9A-Za-z]{24,}"),
+        ("google_api_key", "medium", r"\bAIza[0-9A-Za-z_-]{35}\b"),
+        ("api_secret_key", "medium", r"\bsk-[A-Za-z0-9_-]{32,}"),
+        (
+            "jwt",
+            "low",
+            r"\beyJ[A-Za-z0-9_-]{10,}\.eyJ[A-Za-z0-9_-]{10,}\.[A-Za-z0-9_-]{10,}",
+        ),
+        (
+            "generic_secret",
+            "low",
+            r#"(?i)(password|passwd|secret|api_?key|access_?token)["']?\s*[:=]\s*["'][^"'\s]{12,}["']"#,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(name, severity, pattern)| {
+        Regex::new(pattern).ok().map(|regex| SecretRule {
+            name,
+            severity,
+            regex,
+        })
+    })
+    .collect()
+});
+
+fn is_copyleft(license: &str) -> bool {
+    let upper = license.to_uppercase();
+    COPYLEFT.iter().any(|k| upper.contains(k))
+}
+
+fn dependency(
+    ecosystem: &str,
+    name: &str,
+    version: Option<String>,
+    license: Option<String>,
+) -> DependencyLicense {
+    DependencyLicense {
+        name: name.to_string(),
+        version,
+        ecosystem: ecosystem.to_string(),
+        copyleft: license.as_deref().map(is_copyleft).unwrap_or(false),
+        license,
+    }
+}
+
+// ============ 许可证 ============
+
+/// package.json 的 license 字段（兼容旧的 { type } 对象和 licenses 数组写法）
+fn npm_license(manifest: &serde_json::Value) -> Option<String> {
+    match manifest.get("license") {
+        Some(serde_json::Value::String(s)) => Some(s.clone()),
+        Some(obj) => obj.get("type").and_then(|t| t.as_str()).map(String::from),
+        None => manifest
+            .get("licenses")
+            .and_then(|l| l.as_array())
+            .map(|list| {
+                list.iter()
+                    .filter_map(|l| l.get("type").and_then(|t| t.as_str()))
+                    .collect::<Vec<_>>()
+                    .join(" OR ")
+            })
+            .filter(|s| !s.is_empty()),
+    }
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+fn scan_npm(root: &Path, deps: &mut Vec<DependencyLicense>) -> Option<String> {
+    let manifest = read_json(&root.join("package.json"))?;
+    for section in ["dependencies", "devDependencies", "optionalDependencies"] {
+        let Some(map) = manifest.get(section).and_then(|d| d.as_object()) else {
+            continue;
+        };
+        for (name, range) in map {
+            let installed = read_json(&root.join("node_modules").join(name).join("package.json"));
+            let version = installed
+                .as_ref()
+                .and_then(|m| m.get("version"))
+                .and_then(|v| v.as_str())
+                .or(range.as_str())
+                .map(String::from);
+            let license = installed.as_ref().and_then(npm_license);
+            deps.push(dependency("npm", name, version, license));
+        }
+    }
+    npm_license(&manifest)
+}
+
+/// 读取 TOML 中 [package] 段的字符串字段（只处理 key = "value" 这种简单写法）
+fn toml_package_field(content: &str, key: &str) -> Option<String> {
+    let mut in_package = false;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+            continue;
+        }
+        if !in_package {
+            continue;
+        }
+        if let Some((k, v)) = line.split_once('=') {
+            if k.trim() == key {
+                return Some(v.trim().trim_matches('"').to_string());
+            }
+        }
+    }
+    None
+}
+
+/// Cargo.lock 中来自 registry 的包：(name, version)
+fn cargo_lock_packages(content: &str) -> Vec<(String, String)> {
+    let mut packages = Vec::new();
+    for block in content.split("[[package]]").skip(1) {
+        let field = |key: &str| {
+            block.lines().find_map(|line| {
+                let (k, v) = line.split_once('=')?;
+                (k.trim() == key).then(|| v.trim().trim_matches('"').to_string())
+            })
+        };
+        let is_registry = field("source")
+            .map(|s| s.starts_with("registry+") || s.starts_with("sparse+"))
+            .unwrap_or(false);
+        if let (true, Some(name), Some(version)) = (is_registry, field("name"), field("version")) {
+            packages.push((name, version));
+        }
+    }
+    packages
+}
+
+fn scan_cargo(root: &Path, deps: &mut Vec<DependencyLicense>) -> Option<String> {
+    let manifest = std::fs::read_to_string(root.join("Cargo.toml")).ok()?;
+    let registry_dirs: Vec<PathBuf> = dirs::home_dir()
+        .map(|home| home.join(".cargo").join("registry").join("src"))
+        .and_then(|src| std::fs::read_dir(src).ok())
+        .map(|entries| entries.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default();
+
+    let lock = std::fs::read_to_string(root.join("Cargo.lock")).unwrap_or_default();
+    for (name, version) in cargo_lock_packages(&lock) {
+        let license = registry_dirs.iter().find_map(|dir| {
+            let content = std::fs::read_to_string(
+                dir.join(format!("{}-{}", name, version)).join("Cargo.toml"),
+            )
+            .ok()?;
+            toml_package_field(&content, "license")
+        });
+        deps.push(dependency("cargo", &name, Some(version), license));
+    }
+    toml_package_field(&manifest, "license")
+}
+
+fn scan_requirements(root: &Path, deps: &mut Vec<DependencyLicense>) {
+    let Ok(content) = std::fs::read_to_string(root.join("requirements.txt")) else {
+        return;
+    };
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() || line.starts_with('-') {
+            continue;
+        }
+        let end = line
+            .find(|c: char| "=<>!~;[ ".contains(c))
+            .unwrap_or(line.len());
+        let version = line
+            .split_once("==")
+            .map(|(_, v)| v.split(';').next().unwrap_or(v).trim().to_string());
+        deps.push(dependency("pypi", &line[..end], version, None));
+    }
+}
+
+/// 项目根目录的 LICENSE 文件，按首行粗略识别
+fn license_file(root: &Path) -> Option<String> {
+    let file = ["LICENSE", "LICENSE.md", "LICENSE.txt", "COPYING"]
+        .iter()
+        .map(|n| root.join(n))
+        .find(|p| p.is_file())?;
+    let head: String = std::fs::read_to_string(file)
+        .ok()?
+        .lines()
+        .take(5)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let known = [
+        ("MIT License", "MIT"),
+        ("Apache License", "Apache-2.0"),
+        ("GNU AFFERO GENERAL PUBLIC LICENSE", "AGPL-3.0"),
+        ("GNU LESSER GENERAL PUBLIC LICENSE", "LGPL"),
+        ("GNU GENERAL PUBLIC LICENSE", "GPL"),
+        ("Mozilla Public License", "MPL-2.0"),
+        ("BSD 3-Clause", "BSD-3-Clause"),
+        ("BSD 2-Clause", "BSD-2-Clause"),
+        ("The Unlicense", "Unlicense"),
+    ];
+    Some(
+        known
+            .iter()
+            .find(|(needle, _)| head.contains(needle))
+            .map(|(_, id)| id.to_string())
+            .unwrap_or_else(|| "LICENSE (未识别)".to_string()),
+    )
+}
+
+// ============ 密钥 ============
+
+/// 打码：保留前 4 个字符
+fn mask(matched: &str) -> String {
+    let keep: String = matched.chars().take(4).collect();
+    format!(
+        "{}{}",
+        keep,
+        "*".repeat(matched.chars().count().saturating_sub(4).min(16))
+    )
+}
+
+fn walk_files(dir: &Path, root: &Path, out: &mut Vec<String>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if out.len() >= MAX_FILES {
+            return;
+        }
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        match entry.file_type() {
+            Ok(t) if t.is_dir() && !SKIP_DIRS.contains(&name.as_str()) => {
+                walk_files(&path, root, out);
+            }
+            Ok(t) if t.is_file() => {
+                if let Ok(rel) = path.strip_prefix(root) {
+                    out.push(rel.to_string_lossy().replace('\\', "/"));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 待扫描文件（相对路径）：git 仓库取已跟踪文件
+fn list_files(root: &Path) -> Vec<String> {
+    if root.join(".git").exists() {
+        if let Ok(output) = run_git_command(&root.to_string_lossy(), &["ls-files", "-z"]) {
+            return output
+                .split('\0')
+                .filter(|f| !f.is_empty())
+                .take(MAX_FILES)
+                .map(String::from)
+                .collect();
+        }
+    }
+    let mut files = Vec::new();
+    walk_files(root, root, &mut files);
+    files
+}
+
+fn scan_secrets(root: &Path, files: &[String], findings: &mut Vec<SecretFinding>) -> u32 {
+    let mut scanned = 0;
+    for rel in files {
+        if findings.len() >= MAX_FINDINGS {
+            break;
+        }
+        let name = rel.rsplit('/').next().unwrap_or(rel.as_str());
+        if SKIP_FILES.contains(&name) {
+            continue;
+        }
+        let path = root.join(rel);
+        let small = std::fs::metadata(&path)
+            .map(|m| m.is_file() && m.len() <= MAX_FILE_SIZE)
+            .unwrap_or(false);
+        if !small {
+            continue;
+        }
+        let Ok(bytes) = std::fs::read(&path) else {
+            continue;
+        };
+        // 含 NUL 视为二进制
+        if bytes.iter().take(8000).any(|b| *b == 0) {
+            continue;
+        }
+        scanned += 1;
+        let content = String::from_utf8_lossy(&bytes);
+        for (index, line) in content.lines().enumerate() {
+            for rule in SECRET_RULES.iter() {
+                if let Some(m) = rule.regex.find(line) {
+                    findings.push(SecretFinding {
+                        rule: rule.name.to_string(),
+                        severity: rule.severity.to_string(),
+                        file: rel.clone(),
+                        line: index as u32 + 1,
+                        preview: mask(m.as_str()),
+                    });
+                    // 一行只记录一条，避免同一处被多条规则重复报告
+                    break;
+                }
+            }
+        }
+    }
+    scanned
+}
+
+// ============ 存储 ============
+
+async fn project_id_by_path(path: &str) -> AppResult<Option<String>> {
+    sqlx::query_scalar("SELECT id FROM projects WHERE path = ?")
+        .bind(path)
+        .fetch_optional(pool())
+        .await
+        .map_err(|e| AppError::from(format!("查询项目失败: {}", e)))
+}
+
+async fn save_report(project_id: &str, report: &ComplianceReport) -> AppResult<()> {
+    sqlx::query(
+        "INSERT INTO project_compliance (project_id, report, scanned_at) VALUES (?, ?, ?)
+         ON CONFLICT(project_id) DO UPDATE SET report = excluded.report, scanned_at = excluded.scanned_at",
+    )
+    .bind(project_id)
+    .bind(serde_json::to_string(report)?)
+    .bind(&report.scanned_at)
+    .execute(pool())
+    .await
+    .map_err(|e| AppError::from(format!("保存合规报告失败: {}", e)))?;
+    Ok(())
+}
+
+fn build_report(project_path: String) -> ComplianceReport {
+    let root = PathBuf::from(&project_path);
+    let mut dependencies = Vec::new();
+    let npm = scan_npm(&root, &mut dependencies);
+    let cargo = scan_cargo(&root, &mut dependencies);
+    scan_requirements(&root, &mut dependencies);
+    dependencies.sort_by(|a, b| (&a.ecosystem, &a.name).cmp(&(&b.ecosystem, &b.name)));
+    dependencies.dedup_by(|a, b| a.ecosystem == b.ecosystem && a.name == b.name);
+
+    let mut license_summary = BTreeMap::new();
+    for dep in &dependencies {
+        let key = dep.license.clone().unwrap_or_else(|| "unknown".to_string());
+        *license_summary.entry(key).or_insert(0) += 1;
+    }
+
+    let files = list_files(&root);
+    let mut secrets = Vec::new();
+    let files_scanned = scan_secrets(&root, &files, &mut secrets);
+
+    ComplianceReport {
+        project_license: npm.or(cargo).or_else(|| license_file(&root)),
+        dependencies,
+        license_summary,
+        truncated: secrets.len() >= MAX_FINDINGS,
+        secrets,
+        files_scanned,
+        scanned_at: current_iso_time(),
+        project_path,
+    }
+}
+
+// ============ commands ============
+
+/// 扫描项目的依赖许可证与疑似密钥；路径属于已添加的项目时保存报告
+#[tauri::command]
+#[specta::specta]
+pub async fn scan_project_compliance(path: String) -> AppResult<ComplianceReport> {
+    if !Path::new(&path).is_dir() {
+        return Err(AppError::invalid(format!("目录不存在: {}", path)));
+    }
+    let report = tokio::task::spawn_blocking({
+        let path = path.clone();
+        move || build_report(path)
+    })
+    .await
+    .map_err(|e| AppError::internal(e.to_string()))?;
+
+    log::info!(
+        "合规扫描完成: {}，依赖 {} 个，疑似密钥 {} 处",
+        path,
+        report.dependencies.len(),
+        report.secrets.len()
+    );
+    if let Some(project_id) = project_id_by_path(&path).await? {
+        save_report(&project_id, &report).await?;
+    }
+    Ok(report)
+}
+
+/// 获取项目最近一次的合规扫描报告
+#[tauri::command]
+#[specta::specta]
+pub async fn get_project_compliance(path: String) -> AppResult<Option<ComplianceReport>> {
+    let Some(project_id) = project_id_by_path(&path).await? else {
+        return Ok(None);
+    };
+    let report: Option<String> =
+        sqlx::query_scalar("SELECT report FROM project_compliance WHERE project_id = ?")
+            .bind(&project_id)
+            .fetch_optional(pool())
+            .await
+            .map_err(|e| AppError::from(format!("查询合规报告失败: {}", e)))?;
+    match report {
+        Some(json) => Ok(Some(serde_json::from_str(&json)?)),
+        None => Ok(None),
+    }
+}
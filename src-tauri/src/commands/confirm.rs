@@ -0,0 +1,93 @@
+// 危险操作二次确认：第一次调用不执行，返回确认令牌和将被影响的具体内容；
+// 前端展示给用户确认后，带着令牌再调用一次才真正执行。
+//
+// 令牌一次性、限时，并绑定操作名和「指纹」（如解析后的绝对路径、PID + 进程名 + 启动时间）。
+// 执行前重新计算指纹，与签发时不一致（路径被替换、PID 被复用）则拒绝，需重新确认。
+//
+// 用法：
+//   if let Some(req) = confirm::require("action", confirm_token, plan)? {
+//       return Ok(Some(req));
+//   }
+//   // 走到这里说明令牌有效，执行操作
+
+use crate::error::{AppError, AppResult};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 令牌有效期
+const TOKEN_TTL: Duration = Duration::from_secs(120);
+
+struct PendingConfirm {
+    action: String,
+    fingerprint: String,
+    expires_at: Instant,
+}
+
+static PENDING: Lazy<Mutex<HashMap<String, PendingConfirm>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 返回给前端的确认请求
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmRequest {
+    pub token: String,
+    pub action: String,
+    /// 一句话说明将要发生什么
+    pub summary: String,
+    /// 受影响的具体对象（路径、进程等），逐行展示
+    pub details: Vec<String>,
+    pub expires_in_secs: u32,
+}
+
+/// 一次危险操作的计划：指纹用于执行时校验对象没有变化
+pub struct ConfirmPlan {
+    pub fingerprint: String,
+    pub summary: String,
+    pub details: Vec<String>,
+}
+
+/// 无令牌时签发确认请求（返回 Some）；有令牌时校验并消费，通过返回 None
+pub fn require(
+    action: &str,
+    token: Option<String>,
+    plan: ConfirmPlan,
+) -> AppResult<Option<ConfirmRequest>> {
+    let mut pending = PENDING
+        .lock()
+        .map_err(|_| AppError::internal("确认令牌表锁失败"))?;
+    let now = Instant::now();
+    pending.retain(|_, p| p.expires_at > now);
+
+    let Some(token) = token else {
+        let token = crate::storage::secrets::new_token()?;
+        pending.insert(
+            token.clone(),
+            PendingConfirm {
+                action: action.to_string(),
+                fingerprint: plan.fingerprint,
+                expires_at: now + TOKEN_TTL,
+            },
+        );
+        return Ok(Some(ConfirmRequest {
+            token,
+            action: action.to_string(),
+            summary: plan.summary,
+            details: plan.details,
+            expires_in_secs: TOKEN_TTL.as_secs() as u32,
+        }));
+    };
+
+    let entry = pending
+        .remove(&token)
+        .ok_or_else(|| AppError::invalid("确认令牌无效或已过期，请重新确认"))?;
+    if entry.action != action {
+        return Err(AppError::invalid("确认令牌与操作不匹配"));
+    }
+    if entry.fingerprint != plan.fingerprint {
+        return Err(AppError::invalid("确认后目标已发生变化，请重新确认"));
+    }
+    Ok(None)
+}
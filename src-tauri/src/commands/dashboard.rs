@@ -0,0 +1,110 @@
+// 仪表盘小部件 - 布局保存在 AppSettings.dashboard_widgets，数据一次批量取回
+//
+// 首页原先为每个区块各调一次命令；这里按布局只取已启用小部件需要的数据，
+// 统计 / 热力图 / 最近提交共用同一份 stats 缓存，只读一次。
+
+use super::settings::load_app_settings;
+use super::stats::{self, DailyActivity, DashboardStats, RecentCommit};
+use super::toolbox::{self, PortWatch, ServerConfig};
+use crate::error::{AppError, AppResult};
+use crate::storage::{documents, DashboardWidget};
+use serde::{Deserialize, Serialize};
+
+/// 可用的小部件
+const WIDGET_KINDS: &[&str] = &[
+    "stats",
+    "heatmap",
+    "recent_commits",
+    "running_services",
+    "watched_ports",
+];
+
+const WIDGET_SIZES: &[&str] = &["small", "medium", "large"];
+
+/// 端口监控小部件只带最近的探测记录，够画迷你折线
+const PORT_HISTORY_LIMIT: usize = 20;
+
+/// 未启用的小部件对应字段为 None
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardWidgetData {
+    pub widgets: Vec<DashboardWidget>,
+    pub stats: Option<DashboardStats>,
+    pub heatmap: Option<Vec<DailyActivity>>,
+    pub recent_commits: Option<Vec<RecentCommit>>,
+    /// 运行中的静态服务
+    pub running_services: Option<Vec<ServerConfig>>,
+    pub watched_ports: Option<Vec<PortWatch>>,
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_dashboard_layout() -> AppResult<Vec<DashboardWidget>> {
+    Ok(load_app_settings().await?.dashboard_widgets)
+}
+
+/// 保存布局；同一小部件只能出现一次，顺序即显示顺序
+#[tauri::command]
+#[specta::specta]
+pub async fn save_dashboard_layout(
+    widgets: Vec<DashboardWidget>,
+) -> AppResult<Vec<DashboardWidget>> {
+    for (i, widget) in widgets.iter().enumerate() {
+        if !WIDGET_KINDS.contains(&widget.kind.as_str()) {
+            return Err(AppError::invalid(format!("未知的小部件: {}", widget.kind)));
+        }
+        if !WIDGET_SIZES.contains(&widget.size.as_str()) {
+            return Err(AppError::invalid(format!(
+                "无效的小部件尺寸: {}",
+                widget.size
+            )));
+        }
+        if widgets[..i].iter().any(|w| w.kind == widget.kind) {
+            return Err(AppError::invalid(format!("小部件重复: {}", widget.kind)));
+        }
+    }
+    let mut settings = load_app_settings().await?;
+    settings.dashboard_widgets = widgets;
+    documents::save(&documents::APP_SETTINGS, &settings)?;
+    Ok(settings.dashboard_widgets)
+}
+
+/// 取回已启用小部件的数据；kinds 可临时指定只刷新其中几个
+#[tauri::command]
+#[specta::specta]
+pub async fn get_dashboard_widget_data(
+    kinds: Option<Vec<String>>,
+) -> AppResult<DashboardWidgetData> {
+    let widgets = load_app_settings().await?.dashboard_widgets;
+    let wanted = |kind: &str| {
+        widgets.iter().any(|w| w.kind == kind)
+            && kinds.as_ref().map_or(true, |k| k.iter().any(|k| k == kind))
+    };
+
+    let mut data = DashboardWidgetData::default();
+    if wanted("stats") || wanted("heatmap") || wanted("recent_commits") {
+        let cached = stats::get_dashboard_stats().await?;
+        data.stats = wanted("stats").then_some(cached.stats);
+        data.heatmap = wanted("heatmap").then_some(cached.heatmap_data);
+        data.recent_commits = wanted("recent_commits").then_some(cached.recent_commits);
+    }
+    if wanted("running_services") {
+        let mut servers: Vec<ServerConfig> = toolbox::server::get_servers()
+            .await?
+            .into_iter()
+            .filter(|s| s.status == "running")
+            .collect();
+        servers.sort_by(|a, b| a.name.cmp(&b.name));
+        data.running_services = Some(servers);
+    }
+    if wanted("watched_ports") {
+        let mut watches = toolbox::port_watch::get_port_watches().await?;
+        for watch in &mut watches {
+            let excess = watch.history.len().saturating_sub(PORT_HISTORY_LIMIT);
+            watch.history.drain(..excess);
+        }
+        data.watched_ports = Some(watches);
+    }
+    data.widgets = widgets;
+    Ok(data)
+}
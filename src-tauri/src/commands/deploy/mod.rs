@@ -0,0 +1,747 @@
+// 静态站点部署
+//
+// 项目构建后把输出目录（source_dir，相对项目目录，默认 dist）上传到部署目标：
+// - ssh:   通过 SSH 逐个文件写入远程目录（复用 SSH 隧道的认证方式）
+// - s3:    S3 兼容存储（AWS / R2 / MinIO / OSS 等），SigV4 签名逐个 PUT
+// - rsync: 调用系统 rsync（走 ssh），可选删除远程多余文件
+// 目标配置存 SQLite deploy_targets 表，其中的密码 / 口令 / Secret Key 加密存储（storage::secrets），
+// 返回给前端时以 SECRET_MASK 代替；每次部署写一条 deploy_runs 记录。
+// SSH 部署要求目标主机已记录在 ~/.ssh/known_hosts 中。
+// 部署在后台执行，进度推送 `deploy-progress`，结束推送 `deploy-finished`。
+
+mod rsync;
+mod s3;
+mod ssh;
+
+use crate::error::{AppError, AppResult};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+use crate::commands::project_tasks::project_path;
+use crate::commands::toolbox::SshAuthMethod;
+use crate::storage::db::pool;
+use crate::storage::{current_iso_time, generate_id, secrets};
+
+/// 每个项目保留的部署历史条数
+const MAX_HISTORY: i64 = 100;
+
+/// 返回给前端的凭据占位；保存时字段仍为该值表示"不修改"
+const SECRET_MASK: &str = "********";
+
+/// 进度事件的最小间隔
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// 正在部署的目标 id，防止重复触发
+static RUNNING_TARGETS: Lazy<std::sync::Mutex<HashSet<String>>> =
+    Lazy::new(|| std::sync::Mutex::new(HashSet::new()));
+
+// ========== 数据模型 ==========
+
+/// 部署目标配置（前端 type 区分：ssh / s3 / rsync）
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum DeployDestination {
+    #[serde(rename_all = "camelCase")]
+    Ssh {
+        host: String,
+        #[serde(default = "default_ssh_port")]
+        port: u16,
+        #[serde(default)]
+        user: String,
+        auth: SshAuthMethod,
+        /// 远程目录（绝对路径或相对登录目录）
+        remote_dir: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    S3 {
+        /// 如 https://<account>.r2.cloudflarestorage.com；为空时使用 AWS 区域地址
+        #[serde(default)]
+        endpoint: String,
+        region: String,
+        bucket: String,
+        /// 对象键前缀，如 "site/"
+        #[serde(default)]
+        prefix: String,
+        access_key_id: String,
+        secret_access_key: String,
+        /// 路径风格地址（MinIO 等自建服务通常需要）
+        #[serde(default)]
+        path_style: bool,
+    },
+    #[serde(rename_all = "camelCase")]
+    Rsync {
+        /// rsync 目标，如 "deploy@example.com:/var/www/site"
+        target: String,
+        #[serde(default)]
+        ssh_port: Option<u16>,
+        #[serde(default)]
+        key_path: Option<String>,
+        /// 删除远程目录中本地不存在的文件（--delete）
+        #[serde(default)]
+        delete: bool,
+    },
+}
+
+fn default_ssh_port() -> u16 {
+    22
+}
+
+impl DeployDestination {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Ssh { .. } => "ssh",
+            Self::S3 { .. } => "s3",
+            Self::Rsync { .. } => "rsync",
+        }
+    }
+
+    /// 敏感字段（按字段名标识），用于加密、解密与打码
+    fn secrets_mut(&mut self) -> Vec<(&'static str, &mut String)> {
+        match self {
+            Self::Ssh { auth, .. } => match auth {
+                SshAuthMethod::Password { password } => vec![("password", password)],
+                SshAuthMethod::Key {
+                    passphrase: Some(passphrase),
+                    ..
+                } => vec![("passphrase", passphrase)],
+                _ => vec![],
+            },
+            Self::S3 {
+                secret_access_key, ..
+            } => vec![("secretAccessKey", secret_access_key)],
+            Self::Rsync { .. } => vec![],
+        }
+    }
+
+    fn map_secrets(&mut self, f: impl Fn(&str) -> AppResult<String>) -> AppResult<()> {
+        for (_, value) in self.secrets_mut() {
+            *value = f(value)?;
+        }
+        Ok(())
+    }
+
+    /// 返回前端前把非空凭据替换为 SECRET_MASK
+    fn mask_secrets(&mut self) {
+        for (_, value) in self.secrets_mut() {
+            if !value.is_empty() {
+                *value = SECRET_MASK.to_string();
+            }
+        }
+    }
+
+    /// 保存时仍为 SECRET_MASK 的字段沿用已存储的值
+    fn restore_masked(&mut self, stored: Option<&DeployDestination>) -> AppResult<()> {
+        let mut stored = stored.cloned();
+        let stored: Vec<(&'static str, &mut String)> =
+            stored.as_mut().map(|d| d.secrets_mut()).unwrap_or_default();
+        for (name, value) in self.secrets_mut() {
+            if value != SECRET_MASK {
+                continue;
+            }
+            match stored.iter().find(|(n, _)| *n == name) {
+                Some((_, previous)) => *value = previous.to_string(),
+                None => return Err(AppError::invalid("凭据未填写，请重新输入")),
+            }
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> AppResult<()> {
+        let missing = |value: &str, label: &str| {
+            if value.trim().is_empty() {
+                Err(AppError::invalid(format!("{}不能为空", label)))
+            } else {
+                Ok(())
+            }
+        };
+        match self {
+            Self::Ssh {
+                host,
+                auth,
+                remote_dir,
+                ..
+            } => {
+                if !matches!(auth, SshAuthMethod::SshConfig { .. }) {
+                    missing(host, "SSH 主机")?;
+                }
+                missing(remote_dir, "远程目录")
+            }
+            Self::S3 {
+                region,
+                bucket,
+                access_key_id,
+                secret_access_key,
+                ..
+            } => {
+                missing(region, "区域")?;
+                missing(bucket, "存储桶")?;
+                missing(access_key_id, "Access Key ID")?;
+                missing(secret_access_key, "Secret Access Key")
+            }
+            Self::Rsync { target, .. } => missing(target, "rsync 目标"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DeployTarget {
+    pub id: String,
+    pub project_id: String,
+    pub name: String,
+    /// 要上传的目录，相对项目目录
+    pub source_dir: String,
+    pub destination: DeployDestination,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DeployTargetInput {
+    /// 为空时新建
+    pub id: Option<String>,
+    pub project_id: String,
+    pub name: String,
+    pub source_dir: Option<String>,
+    pub destination: DeployDestination,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DeployRun {
+    pub id: String,
+    pub project_id: String,
+    pub target_id: String,
+    pub target_name: String,
+    /// "ssh" | "s3" | "rsync"
+    pub kind: String,
+    /// "running" | "success" | "failed"
+    pub status: String,
+    pub files: u32,
+    pub bytes: u64,
+    pub message: Option<String>,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DeployProgress {
+    pub run_id: String,
+    pub project_id: String,
+    pub target_id: String,
+    pub files_done: u32,
+    pub files_total: u32,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    /// 正在上传的文件（相对路径）
+    pub current: Option<String>,
+}
+
+/// 待上传的本地文件
+pub(super) struct LocalFile {
+    /// 相对 source_dir，统一使用 `/` 分隔
+    pub relative: String,
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+/// 上传过程中的进度汇报，按 PROGRESS_INTERVAL 节流推送事件
+pub(super) struct Progress {
+    app: AppHandle,
+    state: DeployProgress,
+    last_emit: Option<Instant>,
+}
+
+impl Progress {
+    /// 一个文件上传完成
+    pub fn file_done(&mut self, relative: &str, size: u64) {
+        self.state.files_done += 1;
+        self.state.bytes_done += size;
+        self.state.current = Some(relative.to_string());
+        if self
+            .last_emit
+            .map_or(true, |t| t.elapsed() >= PROGRESS_INTERVAL)
+        {
+            self.emit();
+        }
+    }
+
+    fn emit(&mut self) {
+        self.last_emit = Some(Instant::now());
+        let _ = self.app.emit("deploy-progress", &self.state);
+    }
+}
+
+// ============ helpers ============
+
+type TargetRow = (
+    String, // id
+    String, // project_id
+    String, // name
+    String, // source_dir
+    String, // destination
+    String, // created_at
+    String, // updated_at
+);
+
+const TARGET_SELECT: &str = "SELECT id, project_id, name, source_dir, destination, created_at, updated_at FROM deploy_targets";
+
+fn target_from_row(row: TargetRow) -> AppResult<DeployTarget> {
+    let (id, project_id, name, source_dir, destination, created_at, updated_at) = row;
+    let destination = serde_json::from_str(&destination)
+        .map_err(|e| AppError::internal(format!("部署目标「{}」配置损坏: {}", name, e)))?;
+    Ok(DeployTarget {
+        id,
+        project_id,
+        name,
+        source_dir,
+        destination,
+        created_at,
+        updated_at,
+    })
+}
+
+/// 读取部署目标（凭据保持数据库中的加密形式）
+async fn fetch_target(id: &str) -> AppResult<DeployTarget> {
+    let row: Option<TargetRow> = sqlx::query_as(&format!("{} WHERE id = ?", TARGET_SELECT))
+        .bind(id)
+        .fetch_optional(pool())
+        .await
+        .map_err(|e| AppError::from(format!("查询部署目标失败: {}", e)))?;
+    target_from_row(row.ok_or_else(|| AppError::invalid("部署目标不存在"))?)
+}
+
+type RunRow = (
+    String,         // id
+    String,         // project_id
+    String,         // target_id
+    String,         // target_name
+    String,         // kind
+    String,         // status
+    i64,            // files
+    i64,            // bytes
+    Option<String>, // message
+    String,         // started_at
+    Option<String>, // finished_at
+);
+
+fn run_from_row(row: RunRow) -> DeployRun {
+    let (
+        id,
+        project_id,
+        target_id,
+        target_name,
+        kind,
+        status,
+        files,
+        bytes,
+        message,
+        started_at,
+        finished_at,
+    ) = row;
+    DeployRun {
+        id,
+        project_id,
+        target_id,
+        target_name,
+        kind,
+        status,
+        files: files as u32,
+        bytes: bytes as u64,
+        message,
+        started_at,
+        finished_at,
+    }
+}
+
+/// 部署目录：相对路径基于项目目录，且不能跳出项目目录
+fn resolve_source_dir(project_root: &str, source_dir: &str) -> AppResult<PathBuf> {
+    let relative = Path::new(source_dir);
+    if relative.is_absolute()
+        || relative
+            .components()
+            .any(|c| matches!(c, std::path::Component::ParentDir))
+    {
+        return Err(AppError::invalid("部署目录需为项目内的相对路径，如 dist"));
+    }
+    let dir = Path::new(project_root).join(relative);
+    if !dir.is_dir() {
+        return Err(AppError::invalid(format!(
+            "部署目录不存在: {}，请先构建项目",
+            dir.display()
+        )));
+    }
+    Ok(dir)
+}
+
+/// 递归列出目录下的文件（不跟随符号链接）
+fn collect_files(root: &Path) -> AppResult<Vec<LocalFile>> {
+    let mut files = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            let path = entry.path();
+            if file_type.is_dir() {
+                stack.push(path);
+            } else if file_type.is_file() {
+                let relative = path
+                    .strip_prefix(root)
+                    .unwrap_or(&path)
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                files.push(LocalFile {
+                    relative,
+                    size: entry.metadata()?.len(),
+                    path,
+                });
+            }
+        }
+    }
+    files.sort_by(|a, b| a.relative.cmp(&b.relative));
+    Ok(files)
+}
+
+async fn finish_run(run: &mut DeployRun, result: AppResult<()>) {
+    run.finished_at = Some(current_iso_time());
+    match result {
+        Ok(()) => run.status = "success".to_string(),
+        Err(e) => {
+            run.status = "failed".to_string();
+            run.message = Some(e.to_string());
+        }
+    }
+    let updated = sqlx::query(
+        "UPDATE deploy_runs SET status = ?, files = ?, bytes = ?, message = ?, finished_at = ? WHERE id = ?",
+    )
+    .bind(&run.status)
+    .bind(run.files as i64)
+    .bind(run.bytes as i64)
+    .bind(&run.message)
+    .bind(&run.finished_at)
+    .bind(&run.id)
+    .execute(pool())
+    .await;
+    if let Err(e) = updated {
+        log::error!("保存部署记录失败: {}", e);
+    }
+
+    // 只保留最近 MAX_HISTORY 条
+    let _ = sqlx::query(
+        "DELETE FROM deploy_runs WHERE project_id = ? AND id NOT IN
+         (SELECT id FROM deploy_runs WHERE project_id = ? ORDER BY started_at DESC LIMIT ?)",
+    )
+    .bind(&run.project_id)
+    .bind(&run.project_id)
+    .bind(MAX_HISTORY)
+    .execute(pool())
+    .await;
+}
+
+async fn execute(
+    target: &DeployTarget,
+    source: &Path,
+    files: &[LocalFile],
+    progress: &mut Progress,
+) -> AppResult<()> {
+    match &target.destination {
+        DeployDestination::Ssh {
+            host,
+            port,
+            user,
+            auth,
+            remote_dir,
+        } => ssh::upload(host, *port, user, auth, remote_dir, files, progress).await,
+        DeployDestination::S3 {
+            endpoint,
+            region,
+            bucket,
+            prefix,
+            access_key_id,
+            secret_access_key,
+            path_style,
+        } => {
+            let bucket = s3::Bucket {
+                endpoint,
+                region,
+                bucket,
+                access_key_id,
+                secret_access_key,
+                path_style: *path_style,
+            };
+            s3::upload(&bucket, prefix, files, progress).await
+        }
+        DeployDestination::Rsync {
+            target,
+            ssh_port,
+            key_path,
+            delete,
+        } => {
+            rsync::upload(
+                source,
+                target,
+                *ssh_port,
+                key_path.as_deref(),
+                *delete,
+                files,
+                progress,
+            )
+            .await
+        }
+    }
+}
+
+/// 启动时把上次退出前仍为 running 的部署记录标记为失败（后台任务已随进程结束）
+pub async fn mark_interrupted_runs() {
+    let result = sqlx::query(
+        "UPDATE deploy_runs SET status = 'failed', message = ?, finished_at = ? WHERE status = 'running'",
+    )
+    .bind("应用退出，部署未完成")
+    .bind(current_iso_time())
+    .execute(pool())
+    .await;
+    match result {
+        Ok(r) if r.rows_affected() > 0 => {
+            log::warn!("{} 条部署记录因应用退出而中断", r.rows_affected())
+        }
+        Ok(_) => {}
+        Err(e) => log::error!("更新中断的部署记录失败: {}", e),
+    }
+}
+
+// ============ commands ============
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_deploy_targets(project_id: String) -> AppResult<Vec<DeployTarget>> {
+    let rows: Vec<TargetRow> = sqlx::query_as(&format!(
+        "{} WHERE project_id = ? ORDER BY created_at",
+        TARGET_SELECT
+    ))
+    .bind(&project_id)
+    .fetch_all(pool())
+    .await
+    .map_err(|e| AppError::from(format!("查询部署目标失败: {}", e)))?;
+    rows.into_iter()
+        .map(|row| {
+            let mut target = target_from_row(row)?;
+            target.destination.mask_secrets();
+            Ok(target)
+        })
+        .collect()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn save_deploy_target(mut input: DeployTargetInput) -> AppResult<DeployTarget> {
+    let name = input.name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::invalid("部署目标名称不能为空"));
+    }
+    input.destination.validate()?;
+    let stored = match &input.id {
+        Some(id) => Some(fetch_target(id).await?.destination),
+        None => None,
+    };
+    input.destination.restore_masked(stored.as_ref())?;
+    input.destination.map_secrets(secrets::encrypt)?;
+    let source_dir = input
+        .source_dir
+        .as_deref()
+        .map(|s| s.trim().trim_matches('/'))
+        .filter(|s| !s.is_empty())
+        .unwrap_or("dist")
+        .to_string();
+    project_path(&input.project_id).await?;
+    let destination = serde_json::to_string(&input.destination)
+        .map_err(|e| AppError::internal(format!("序列化部署目标失败: {}", e)))?;
+
+    let now = current_iso_time();
+    let id = match input.id {
+        Some(id) => {
+            let result = sqlx::query(
+                "UPDATE deploy_targets SET name = ?, source_dir = ?, destination = ?, updated_at = ? WHERE id = ? AND project_id = ?",
+            )
+            .bind(&name)
+            .bind(&source_dir)
+            .bind(&destination)
+            .bind(&now)
+            .bind(&id)
+            .bind(&input.project_id)
+            .execute(pool())
+            .await
+            .map_err(|e| AppError::from(format!("更新部署目标失败: {}", e)))?;
+            if result.rows_affected() == 0 {
+                return Err(AppError::invalid("部署目标不存在"));
+            }
+            id
+        }
+        None => {
+            let id = generate_id();
+            sqlx::query(
+                "INSERT INTO deploy_targets (id, project_id, name, source_dir, destination, created_at, updated_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&id)
+            .bind(&input.project_id)
+            .bind(&name)
+            .bind(&source_dir)
+            .bind(&destination)
+            .bind(&now)
+            .bind(&now)
+            .execute(pool())
+            .await
+            .map_err(|e| AppError::from(format!("创建部署目标失败: {}", e)))?;
+            id
+        }
+    };
+
+    let mut target = fetch_target(&id).await?;
+    target.destination.mask_secrets();
+    Ok(target)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_deploy_target(project_id: String, target_id: String) -> AppResult<()> {
+    sqlx::query("DELETE FROM deploy_targets WHERE id = ? AND project_id = ?")
+        .bind(&target_id)
+        .bind(&project_id)
+        .execute(pool())
+        .await
+        .map_err(|e| AppError::from(format!("删除部署目标失败: {}", e)))?;
+    Ok(())
+}
+
+/// 开始部署：校验并登记后在后台上传，立即返回 running 状态的部署记录
+#[tauri::command]
+#[specta::specta]
+pub async fn run_deploy(app: AppHandle, target_id: String) -> AppResult<DeployRun> {
+    let mut target = fetch_target(&target_id).await?;
+    target.destination.map_secrets(secrets::decrypt)?;
+    let root = project_path(&target.project_id).await?;
+    let source = resolve_source_dir(&root, &target.source_dir)?;
+    let files = {
+        let source = source.clone();
+        tokio::task::spawn_blocking(move || collect_files(&source))
+            .await
+            .map_err(|e| AppError::internal(format!("读取部署目录失败: {}", e)))??
+    };
+    if files.is_empty() {
+        return Err(AppError::invalid(format!(
+            "部署目录为空: {}",
+            source.display()
+        )));
+    }
+
+    if !RUNNING_TARGETS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(target.id.clone())
+    {
+        return Err(AppError::invalid(format!("「{}」正在部署中", target.name)));
+    }
+
+    let mut run = DeployRun {
+        id: generate_id(),
+        project_id: target.project_id.clone(),
+        target_id: target.id.clone(),
+        target_name: target.name.clone(),
+        kind: target.destination.kind().to_string(),
+        status: "running".to_string(),
+        files: 0,
+        bytes: 0,
+        message: None,
+        started_at: current_iso_time(),
+        finished_at: None,
+    };
+    let inserted = sqlx::query(
+        "INSERT INTO deploy_runs (id, project_id, target_id, target_name, kind, status, started_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&run.id)
+    .bind(&run.project_id)
+    .bind(&run.target_id)
+    .bind(&run.target_name)
+    .bind(&run.kind)
+    .bind(&run.status)
+    .bind(&run.started_at)
+    .execute(pool())
+    .await;
+    if let Err(e) = inserted {
+        RUNNING_TARGETS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&target.id);
+        return Err(AppError::from(format!("创建部署记录失败: {}", e)));
+    }
+
+    let started = run.clone();
+    tauri::async_runtime::spawn(async move {
+        log::info!(
+            "开始部署「{}」: {} 个文件 -> {}",
+            target.name,
+            files.len(),
+            run.kind
+        );
+        let mut progress = Progress {
+            app: app.clone(),
+            state: DeployProgress {
+                run_id: run.id.clone(),
+                project_id: run.project_id.clone(),
+                target_id: run.target_id.clone(),
+                files_done: 0,
+                files_total: files.len() as u32,
+                bytes_done: 0,
+                bytes_total: files.iter().map(|f| f.size).sum(),
+                current: None,
+            },
+            last_emit: None,
+        };
+        progress.emit();
+        let result = execute(&target, &source, &files, &mut progress).await;
+        progress.emit();
+
+        run.files = progress.state.files_done;
+        run.bytes = progress.state.bytes_done;
+        if let Err(e) = &result {
+            log::warn!("部署「{}」失败: {}", target.name, e);
+        }
+        finish_run(&mut run, result).await;
+        RUNNING_TARGETS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&target.id);
+        let _ = app.emit("deploy-finished", &run);
+    });
+
+    Ok(started)
+}
+
+/// 项目的部署历史（最新在前）
+#[tauri::command]
+#[specta::specta]
+pub async fn list_deploy_history(
+    project_id: String,
+    limit: Option<u32>,
+) -> AppResult<Vec<DeployRun>> {
+    let rows: Vec<RunRow> = sqlx::query_as(
+        "SELECT id, project_id, target_id, target_name, kind, status, files, bytes, message, started_at, finished_at
+         FROM deploy_runs WHERE project_id = ? ORDER BY started_at DESC LIMIT ?",
+    )
+    .bind(&project_id)
+    .bind(limit.unwrap_or(20).min(MAX_HISTORY as u32) as i64)
+    .fetch_all(pool())
+    .await
+    .map_err(|e| AppError::from(format!("查询部署历史失败: {}", e)))?;
+    Ok(rows.into_iter().map(run_from_row).collect())
+}
@@ -0,0 +1,93 @@
+// rsync 部署：调用系统 rsync（通过 ssh 传输），按输出的文件名统计进度。
+// rsync 只传输有变化的文件，未变化的文件不会出现在输出中，完成后按全部文件计入进度。
+
+use super::{LocalFile, Progress};
+use crate::error::{AppError, AppResult};
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::process::Command;
+
+pub(super) async fn upload(
+    source: &Path,
+    target: &str,
+    ssh_port: Option<u16>,
+    key_path: Option<&str>,
+    delete: bool,
+    files: &[LocalFile],
+    progress: &mut Progress,
+) -> AppResult<()> {
+    let mut ssh = "ssh -o BatchMode=yes".to_string();
+    if let Some(port) = ssh_port {
+        ssh.push_str(&format!(" -p {}", port));
+    }
+    if let Some(key) = key_path.filter(|k| !k.trim().is_empty()) {
+        ssh.push_str(&format!(" -i \"{}\"", key.trim()));
+    }
+
+    // 末尾的 / 表示同步目录内容而不是目录本身
+    let mut source_arg = source.to_string_lossy().replace('\\', "/");
+    if !source_arg.ends_with('/') {
+        source_arg.push('/');
+    }
+    let mut cmd = Command::new("rsync");
+    cmd.args(["-rlptz", "--out-format=%n", "-e", &ssh]);
+    if delete {
+        cmd.arg("--delete");
+    }
+    // -- 之后的参数不会被当作选项解析，避免以 - 开头的目标被注入为 rsync 参数
+    cmd.arg("--")
+        .arg(&source_arg)
+        .arg(target.trim())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(target_os = "windows")]
+    {
+        cmd.creation_flags(0x08000000);
+    }
+
+    let mut child = cmd.spawn().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            AppError::invalid("未找到 rsync，请先安装 rsync 或改用 SSH 部署")
+        } else {
+            AppError::from(e)
+        }
+    })?;
+
+    let sizes: HashMap<&str, u64> = files
+        .iter()
+        .map(|f| (f.relative.as_str(), f.size))
+        .collect();
+    let mut stderr = child.stderr.take();
+    let stderr_task = tokio::spawn(async move {
+        let mut buf = String::new();
+        if let Some(stderr) = stderr.as_mut() {
+            let _ = stderr.read_to_string(&mut buf).await;
+        }
+        buf
+    });
+    if let Some(stdout) = child.stdout.take() {
+        let mut lines = BufReader::new(stdout).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            // 目录行以 / 结尾，删除行以 "deleting " 开头，都不计入
+            if let Some(size) = sizes.get(line.trim()) {
+                progress.file_done(line.trim(), *size);
+            }
+        }
+    }
+
+    let status = child.wait().await?;
+    let stderr = stderr_task.await.unwrap_or_default();
+    if !status.success() {
+        return Err(AppError::other(format!(
+            "rsync 失败（{}）: {}",
+            status,
+            stderr.trim()
+        )));
+    }
+    progress.state.files_done = files.len() as u32;
+    progress.state.bytes_done = progress.state.bytes_total;
+    Ok(())
+}
@@ -0,0 +1,190 @@
+// S3 兼容存储部署：逐个文件 PUT，AWS Signature V4 签名（payload 整体计算 SHA-256）。
+
+use super::{LocalFile, Progress};
+use crate::error::{AppError, AppResult};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+pub(super) struct Bucket<'a> {
+    pub endpoint: &'a str,
+    pub region: &'a str,
+    pub bucket: &'a str,
+    pub access_key_id: &'a str,
+    pub secret_access_key: &'a str,
+    pub path_style: bool,
+}
+
+impl Bucket<'_> {
+    /// 对象地址；key 的每段按 RFC 3986 编码（与签名的 canonical URI 一致）
+    fn object_url(&self, key: &str) -> AppResult<url::Url> {
+        let endpoint = match self.endpoint.trim().trim_end_matches('/') {
+            "" => format!("https://s3.{}.amazonaws.com", self.region),
+            e if e.contains("://") => e.to_string(),
+            e => format!("https://{}", e),
+        };
+        let base = url::Url::parse(&endpoint)
+            .map_err(|e| AppError::invalid(format!("S3 地址无效: {}", e)))?;
+        let encoded_key = key
+            .split('/')
+            .map(|s| urlencoding::encode(s).into_owned())
+            .collect::<Vec<_>>()
+            .join("/");
+        let url = if self.path_style {
+            format!("{}/{}/{}", endpoint, self.bucket, encoded_key)
+        } else {
+            let host = base
+                .host_str()
+                .ok_or_else(|| AppError::invalid("S3 地址缺少主机名"))?;
+            let port = base.port().map(|p| format!(":{}", p)).unwrap_or_default();
+            format!(
+                "{}://{}.{}{}/{}",
+                base.scheme(),
+                self.bucket,
+                host,
+                port,
+                encoded_key
+            )
+        };
+        url::Url::parse(&url).map_err(|e| AppError::invalid(format!("S3 对象地址无效: {}", e)))
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn content_type(key: &str) -> &'static str {
+    let ext = key.rsplit_once('.').map(|(_, e)| e.to_lowercase());
+    match ext.as_deref() {
+        Some("html" | "htm") => "text/html; charset=utf-8",
+        Some("css") => "text/css; charset=utf-8",
+        Some("js" | "mjs") => "text/javascript; charset=utf-8",
+        Some("json" | "map") => "application/json",
+        Some("txt") => "text/plain; charset=utf-8",
+        Some("xml") => "application/xml",
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("avif") => "image/avif",
+        Some("ico") => "image/x-icon",
+        Some("woff") => "font/woff",
+        Some("woff2") => "font/woff2",
+        Some("ttf") => "font/ttf",
+        Some("wasm") => "application/wasm",
+        Some("pdf") => "application/pdf",
+        Some("webmanifest") => "application/manifest+json",
+        _ => "application/octet-stream",
+    }
+}
+
+async fn put_object(
+    client: &reqwest::Client,
+    bucket: &Bucket<'_>,
+    key: &str,
+    body: Vec<u8>,
+) -> AppResult<()> {
+    let url = bucket.object_url(key)?;
+    let host = match url.port() {
+        Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+        None => url.host_str().unwrap_or_default().to_string(),
+    };
+    let now = chrono::Utc::now();
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+    let payload_hash = hex(&Sha256::digest(&body));
+    let content_type = content_type(key);
+
+    let signed_headers = "content-type;host;x-amz-content-sha256;x-amz-date";
+    let canonical_request = format!(
+        "PUT\n{}\n\ncontent-type:{}\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+        url.path(),
+        content_type,
+        host,
+        payload_hash,
+        amz_date,
+        signed_headers,
+        payload_hash
+    );
+    let scope = format!("{}/{}/s3/aws4_request", date, bucket.region);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex(&Sha256::digest(canonical_request.as_bytes()))
+    );
+    let signing_key = ["s3", "aws4_request"].iter().fold(
+        hmac_sha256(
+            &hmac_sha256(
+                format!("AWS4{}", bucket.secret_access_key).as_bytes(),
+                &date,
+            ),
+            bucket.region,
+        ),
+        |key, part| hmac_sha256(&key, part),
+    );
+    let signature = hex(&hmac_sha256(&signing_key, &string_to_sign));
+    let authorization = format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        bucket.access_key_id, scope, signed_headers, signature
+    );
+
+    let response = client
+        .put(url)
+        .header("Content-Type", content_type)
+        .header("x-amz-content-sha256", &payload_hash)
+        .header("x-amz-date", &amz_date)
+        .header("Authorization", authorization)
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| AppError::other(format!("请求失败: {}", e)))?;
+    let status = response.status();
+    if !status.is_success() {
+        let text = response.text().await.unwrap_or_default();
+        // S3 错误体是 XML，取 <Message> 作为提示
+        let message = text
+            .split_once("<Message>")
+            .and_then(|(_, rest)| rest.split_once("</Message>"))
+            .map(|(m, _)| m.to_string())
+            .unwrap_or(text);
+        return Err(AppError::other(format!("{} {}", status, message.trim())));
+    }
+    Ok(())
+}
+
+pub(super) async fn upload(
+    bucket: &Bucket<'_>,
+    prefix: &str,
+    files: &[LocalFile],
+    progress: &mut Progress,
+) -> AppResult<()> {
+    let client = crate::http_client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| AppError::other(format!("创建 HTTP 客户端失败: {}", e)))?;
+    let prefix = prefix.trim().trim_matches('/');
+    for file in files {
+        let key = if prefix.is_empty() {
+            file.relative.clone()
+        } else {
+            format!("{}/{}", prefix, file.relative)
+        };
+        let body = tokio::fs::read(&file.path).await?;
+        put_object(&client, bucket, &key, body)
+            .await
+            .map_err(|e| AppError::other(format!("上传 {} 失败: {}", file.relative, e)))?;
+        progress.file_done(&file.relative, file.size);
+    }
+    Ok(())
+}
@@ -0,0 +1,127 @@
+// SSH 部署：建好远程目录后，每个文件开一个 exec 通道执行 `cat > 路径` 写入。
+// 不依赖远程 SFTP 子系统，只要求远程有 POSIX shell。
+
+use super::{LocalFile, Progress};
+use crate::commands::toolbox::ssh_tunnel::{connect_ssh_verified, SshClient};
+use crate::commands::toolbox::SshAuthMethod;
+use crate::error::{AppError, AppResult};
+use russh::client;
+use russh::ChannelMsg;
+use std::collections::BTreeSet;
+
+/// 单条 mkdir 命令包含的目录数，避免命令行过长
+const MKDIR_BATCH: usize = 100;
+
+/// 单引号转义，用于拼接远程 shell 命令
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// 规范化远程目录：去掉末尾的 /（根目录保留为 /）；
+/// 开头的 ~ / ~/ 改写为相对路径，exec 命令在登录用户的家目录下执行，
+/// 这样拼接后的路径可以整体加引号，而不依赖 shell 的波浪号展开
+fn normalize_remote_dir(dir: &str) -> String {
+    let dir = dir.trim();
+    let dir = if dir == "~" {
+        "."
+    } else {
+        dir.strip_prefix("~/").unwrap_or(dir)
+    };
+    let trimmed = dir.trim_end_matches('/');
+    if trimmed.is_empty() {
+        if dir.starts_with('/') { "/" } else { "." }.to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// 在远程执行命令，可选写入 stdin；非 0 退出码视为失败
+async fn exec(
+    handle: &client::Handle<SshClient>,
+    command: &str,
+    stdin: Option<&[u8]>,
+) -> AppResult<()> {
+    let mut channel = handle
+        .channel_open_session()
+        .await
+        .map_err(|e| AppError::other(format!("打开 SSH 通道失败: {}", e)))?;
+    channel
+        .exec(true, command)
+        .await
+        .map_err(|e| AppError::other(format!("执行远程命令失败: {}", e)))?;
+    if let Some(data) = stdin {
+        channel
+            .data(data)
+            .await
+            .map_err(|e| AppError::other(format!("发送文件内容失败: {}", e)))?;
+    }
+    channel
+        .eof()
+        .await
+        .map_err(|e| AppError::other(format!("发送文件内容失败: {}", e)))?;
+
+    let mut stderr = Vec::new();
+    let mut exit_status = None;
+    while let Some(msg) = channel.wait().await {
+        match msg {
+            ChannelMsg::ExtendedData { data, .. } => stderr.extend_from_slice(&data),
+            ChannelMsg::ExitStatus { exit_status: code } => exit_status = Some(code),
+            _ => {}
+        }
+    }
+    match exit_status {
+        Some(0) => Ok(()),
+        code => Err(AppError::other(format!(
+            "远程命令失败（退出码 {}）: {}",
+            code.map_or("未知".to_string(), |c| c.to_string()),
+            String::from_utf8_lossy(&stderr).trim()
+        ))),
+    }
+}
+
+pub(super) async fn upload(
+    host: &str,
+    port: u16,
+    user: &str,
+    auth: &SshAuthMethod,
+    remote_dir: &str,
+    files: &[LocalFile],
+    progress: &mut Progress,
+) -> AppResult<()> {
+    let handle = connect_ssh_verified(host, port, user, auth).await?;
+    let remote_dir = normalize_remote_dir(remote_dir);
+    let remote_path = |relative: &str| {
+        if remote_dir == "/" {
+            format!("/{}", relative)
+        } else {
+            format!("{}/{}", remote_dir, relative)
+        }
+    };
+
+    let mut dirs: BTreeSet<String> = BTreeSet::new();
+    dirs.insert(remote_dir.clone());
+    for file in files {
+        if let Some((parent, _)) = file.relative.rsplit_once('/') {
+            dirs.insert(remote_path(parent));
+        }
+    }
+    let dirs: Vec<String> = dirs.into_iter().collect();
+    for batch in dirs.chunks(MKDIR_BATCH) {
+        let args: Vec<String> = batch.iter().map(|d| quote(d)).collect();
+        exec(&handle, &format!("mkdir -p {}", args.join(" ")), None).await?;
+    }
+
+    for file in files {
+        let data = tokio::fs::read(&file.path).await?;
+        let target = remote_path(&file.relative);
+        exec(&handle, &format!("cat > {}", quote(&target)), Some(&data))
+            .await
+            .map_err(|e| AppError::other(format!("上传 {} 失败: {}", file.relative, e)))?;
+        progress.file_done(&file.relative, file.size);
+    }
+
+    let _ = handle
+        .disconnect(russh::Disconnect::ByApplication, "", "")
+        .await;
+    Ok(())
+}
@@ -0,0 +1,366 @@
+//! 上游分歧提醒
+//!
+//! 对开启监控的项目定时 `git fetch`，比较当前分支与上游的 ahead / behind，
+//! 落后超过阈值时推送通知，避免积攒成一次巨大的合并。
+//!   - 每个项目单独设置阈值与拉取间隔，存 divergence_watches.json；
+//!   - 同一次超限只提醒一次，回到阈值以内后重新计数；
+//!   - 稍后提醒（snooze）期间不推送，到期后仍超限会再提醒一次。
+
+use crate::error::{AppError, AppResult};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Mutex;
+
+use crate::commands::git::{lock_repo, run_git_command};
+use crate::commands::settings::push_notification;
+use crate::storage::config::StorageConfig;
+use crate::storage::db::pool;
+use crate::storage::{current_iso_time, PersistedStore};
+
+/// 调度器检查间隔
+const TICK_INTERVAL: Duration = Duration::from_secs(60);
+
+const DEFAULT_BEHIND_THRESHOLD: u32 = 20;
+const DEFAULT_FETCH_INTERVAL_MINUTES: u32 = 30;
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DivergenceWatch {
+    pub project_id: String,
+    pub enabled: bool,
+    /// 落后上游超过多少个提交时提醒
+    pub behind_threshold: u32,
+    /// 定时拉取间隔（分钟）
+    pub fetch_interval_minutes: u32,
+    /// 稍后提醒截止时间（RFC3339），之前不推送
+    #[serde(default)]
+    pub snoozed_until: Option<String>,
+    #[serde(default)]
+    pub last_fetched_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DivergenceWatchInput {
+    pub project_id: String,
+    pub enabled: Option<bool>,
+    pub behind_threshold: Option<u32>,
+    pub fetch_interval_minutes: Option<u32>,
+}
+
+/// 最近一次检查结果
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DivergenceStatus {
+    pub project_id: String,
+    pub project_name: String,
+    pub branch: Option<String>,
+    pub upstream: Option<String>,
+    pub ahead: u32,
+    pub behind: u32,
+    pub threshold: u32,
+    /// 落后数达到阈值
+    pub exceeded: bool,
+    pub snoozed: bool,
+    pub checked_at: String,
+    /// 拉取失败或没有上游分支时的说明
+    pub error: Option<String>,
+}
+
+static WATCHES: Lazy<PersistedStore<DivergenceWatch>> = Lazy::new(|| {
+    PersistedStore::new(
+        "divergenceWatches",
+        "上游分歧监控",
+        StorageConfig::divergence_watches_file,
+        |w| w.project_id.clone(),
+    )
+});
+
+/// 项目 id -> 最近一次检查结果
+static STATUS: Lazy<Mutex<HashMap<String, DivergenceStatus>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 本轮超限已提醒过的项目
+static NOTIFIED: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+fn parse_time(value: Option<&str>) -> Option<DateTime<Utc>> {
+    value
+        .and_then(|v| DateTime::parse_from_rfc3339(v).ok())
+        .map(|t| t.with_timezone(&Utc))
+}
+
+fn is_snoozed(watch: &DivergenceWatch, now: DateTime<Utc>) -> bool {
+    parse_time(watch.snoozed_until.as_deref()).is_some_and(|until| until > now)
+}
+
+fn is_due(watch: &DivergenceWatch, now: DateTime<Utc>) -> bool {
+    if !watch.enabled {
+        return false;
+    }
+    match parse_time(watch.last_fetched_at.as_deref()) {
+        Some(last) => {
+            now.signed_duration_since(last).num_minutes()
+                >= watch.fetch_interval_minutes.max(1) as i64
+        }
+        None => true,
+    }
+}
+
+async fn project_info(project_id: &str) -> AppResult<(String, String)> {
+    let row: Option<(String, String)> =
+        sqlx::query_as("SELECT name, path FROM projects WHERE id = ?")
+            .bind(project_id)
+            .fetch_optional(pool())
+            .await
+            .map_err(|e| AppError::from(format!("查询项目失败: {}", e)))?;
+    row.ok_or_else(|| AppError::invalid("项目不存在"))
+}
+
+/// 拉取上游并计算 (当前分支, 上游, ahead, behind)
+fn fetch_and_compare(path: &str) -> AppResult<(String, String, u32, u32)> {
+    let branch = run_git_command(path, &["rev-parse", "--abbrev-ref", "HEAD"])?;
+    let upstream = run_git_command(
+        path,
+        &[
+            "rev-parse",
+            "--abbrev-ref",
+            "--symbolic-full-name",
+            "@{upstream}",
+        ],
+    )
+    .map_err(|_| AppError::invalid(format!("分支 {} 没有设置上游", branch)))?;
+    let remote = upstream.split('/').next().unwrap_or("origin");
+    run_git_command(path, &["fetch", "--prune", "--quiet", remote])?;
+    let counts = run_git_command(
+        path,
+        &["rev-list", "--left-right", "--count", "HEAD...@{upstream}"],
+    )?;
+    let mut parts = counts.split_whitespace().map(|v| v.parse().unwrap_or(0));
+    let ahead = parts.next().unwrap_or(0);
+    let behind = parts.next().unwrap_or(0);
+    Ok((branch, upstream, ahead, behind))
+}
+
+/// 检查一个项目并在需要时提醒
+async fn check_project(app: &AppHandle, watch: &DivergenceWatch) -> AppResult<DivergenceStatus> {
+    let (name, path) = project_info(&watch.project_id).await?;
+    let compared = {
+        let _guard = lock_repo(&path, "检查与上游的差异").await;
+        tokio::task::spawn_blocking(move || fetch_and_compare(&path))
+            .await
+            .map_err(|e| AppError::internal(e.to_string()))?
+    };
+
+    let now = current_iso_time();
+    let snoozed = is_snoozed(watch, Utc::now());
+    let mut status = DivergenceStatus {
+        project_id: watch.project_id.clone(),
+        project_name: name,
+        branch: None,
+        upstream: None,
+        ahead: 0,
+        behind: 0,
+        threshold: watch.behind_threshold,
+        exceeded: false,
+        snoozed,
+        checked_at: now.clone(),
+        error: None,
+    };
+    match compared {
+        Ok((branch, upstream, ahead, behind)) => {
+            status.branch = Some(branch);
+            status.upstream = Some(upstream);
+            status.ahead = ahead;
+            status.behind = behind;
+            status.exceeded = behind >= watch.behind_threshold;
+        }
+        Err(e) => status.error = Some(e.to_string()),
+    }
+
+    if let Some(w) = WATCHES.lock().await.get_mut(&watch.project_id) {
+        w.last_fetched_at = Some(now);
+    }
+    let _ = WATCHES.save().await;
+
+    let should_notify = {
+        let mut notified = NOTIFIED.lock().await;
+        if !status.exceeded {
+            notified.remove(&status.project_id);
+            false
+        } else {
+            !snoozed && notified.insert(status.project_id.clone())
+        }
+    };
+    if should_notify {
+        let message = format!(
+            "{} 落后 {} {} 个提交（阈值 {}），本地领先 {} 个",
+            status.branch.as_deref().unwrap_or("当前分支"),
+            status.upstream.as_deref().unwrap_or("上游"),
+            status.behind,
+            status.threshold,
+            status.ahead
+        );
+        push_notification(
+            app,
+            "git",
+            "warning",
+            &format!("{} 与上游分歧过大", status.project_name),
+            &message,
+        )
+        .await;
+        let _ = app.emit("divergence-alert", &status);
+    }
+
+    STATUS
+        .lock()
+        .await
+        .insert(status.project_id.clone(), status.clone());
+    Ok(status)
+}
+
+/// 启动调度器：每分钟检查一次到期的项目
+pub fn spawn_divergence_monitor(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(TICK_INTERVAL).await;
+            WATCHES.ensure_loaded().await;
+            let now = Utc::now();
+            let due: Vec<DivergenceWatch> = WATCHES
+                .lock()
+                .await
+                .values()
+                .filter(|w| is_due(w, now))
+                .cloned()
+                .collect();
+            if due.is_empty() || super::power::should_defer("上游分歧检查", false).await {
+                continue;
+            }
+            for watch in due {
+                if let Err(e) = check_project(&app, &watch).await {
+                    log::warn!("上游分歧检查失败 {}: {}", watch.project_id, e);
+                }
+            }
+        }
+    });
+}
+
+// ========== Tauri 命令 ==========
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_divergence_watches() -> AppResult<Vec<DivergenceWatch>> {
+    WATCHES.ensure_loaded().await;
+    Ok(WATCHES.lock().await.values().cloned().collect())
+}
+
+/// 开启或更新项目的分歧监控
+#[tauri::command]
+#[specta::specta]
+pub async fn save_divergence_watch(input: DivergenceWatchInput) -> AppResult<DivergenceWatch> {
+    if input.behind_threshold == Some(0) {
+        return Err(AppError::invalid("落后阈值需大于 0"));
+    }
+    project_info(&input.project_id).await?;
+    WATCHES.ensure_loaded().await;
+    let watch = {
+        let mut watches = WATCHES.lock().await;
+        let watch = watches
+            .entry(input.project_id.clone())
+            .or_insert_with(|| DivergenceWatch {
+                project_id: input.project_id.clone(),
+                enabled: true,
+                behind_threshold: DEFAULT_BEHIND_THRESHOLD,
+                fetch_interval_minutes: DEFAULT_FETCH_INTERVAL_MINUTES,
+                snoozed_until: None,
+                last_fetched_at: None,
+            });
+        if let Some(enabled) = input.enabled {
+            watch.enabled = enabled;
+        }
+        if let Some(threshold) = input.behind_threshold {
+            watch.behind_threshold = threshold;
+        }
+        if let Some(interval) = input.fetch_interval_minutes {
+            watch.fetch_interval_minutes = interval.max(1);
+        }
+        watch.clone()
+    };
+    // 阈值变化后重新判断是否需要提醒
+    NOTIFIED.lock().await.remove(&watch.project_id);
+    WATCHES.save().await?;
+    Ok(watch)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_divergence_watch(project_id: String) -> AppResult<()> {
+    WATCHES.ensure_loaded().await;
+    WATCHES.lock().await.remove(&project_id);
+    STATUS.lock().await.remove(&project_id);
+    NOTIFIED.lock().await.remove(&project_id);
+    WATCHES.save().await
+}
+
+/// 稍后提醒：minutes 分钟内不再推送该项目的分歧提醒，0 表示取消
+#[tauri::command]
+#[specta::specta]
+pub async fn snooze_divergence_alert(
+    project_id: String,
+    minutes: u32,
+) -> AppResult<DivergenceWatch> {
+    WATCHES.ensure_loaded().await;
+    let watch = {
+        let mut watches = WATCHES.lock().await;
+        let watch = watches
+            .get_mut(&project_id)
+            .ok_or_else(|| AppError::invalid("该项目未开启分歧监控"))?;
+        watch.snoozed_until = (minutes > 0)
+            .then(|| (Utc::now() + chrono::Duration::minutes(minutes as i64)).to_rfc3339());
+        watch.clone()
+    };
+    // 到期后仍超限时再提醒一次
+    NOTIFIED.lock().await.remove(&project_id);
+    if let Some(status) = STATUS.lock().await.get_mut(&project_id) {
+        status.snoozed = minutes > 0;
+    }
+    WATCHES.save().await?;
+    Ok(watch)
+}
+
+/// 最近一次检查结果，落后最多的排在前面
+#[tauri::command]
+#[specta::specta]
+pub async fn get_divergence_status() -> AppResult<Vec<DivergenceStatus>> {
+    let mut list: Vec<DivergenceStatus> = STATUS.lock().await.values().cloned().collect();
+    list.sort_by_key(|d| std::cmp::Reverse(d.behind));
+    Ok(list)
+}
+
+/// 立即拉取并检查一个项目（不要求已开启监控，未开启时按默认阈值判断）
+#[tauri::command]
+#[specta::specta]
+pub async fn check_divergence_now(
+    app: AppHandle,
+    project_id: String,
+) -> AppResult<DivergenceStatus> {
+    WATCHES.ensure_loaded().await;
+    let watch = WATCHES
+        .lock()
+        .await
+        .get(&project_id)
+        .cloned()
+        .unwrap_or(DivergenceWatch {
+            project_id,
+            enabled: false,
+            behind_threshold: DEFAULT_BEHIND_THRESHOLD,
+            fetch_interval_minutes: DEFAULT_FETCH_INTERVAL_MINUTES,
+            snoozed_until: None,
+            last_fetched_at: None,
+        });
+    check_project(&app, &watch).await
+}
@@ -0,0 +1,455 @@
+// 项目文档预览 - 识别文档站点生成器，一键启动预览并返回访问地址
+//
+// 支持 mkdocs / docusaurus / mdbook / vitepress，在项目根目录及 docs/、website/ 子目录中查找配置。
+// 两种预览方式：
+// - dev：运行生成器自带的开发服务器（端口由这里分配，通过命令行参数传入），等端口可连接后返回
+// - static：已构建的产物目录（site/、build/、book/、.vitepress/dist）交给静态服务模块托管
+// 未指定方式时，产物目录存在就用 static，否则用 dev。
+
+use crate::commands::project_tasks::{kill_tree, shell_command};
+use crate::commands::toolbox::server::{create_server, get_servers, start_server, stop_server};
+use crate::commands::toolbox::ServerConfigInput;
+use crate::error::{AppError, AppResult};
+use crate::storage::current_iso_time;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use tauri::AppHandle;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::sync::Mutex;
+use tokio::time::{Duration, Instant};
+
+/// 等待开发服务器端口就绪的最长时间（首次运行 npx 可能要装依赖）
+const DEV_READY_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// 启动失败时附带的输出行数
+const ERROR_TAIL_LINES: usize = 20;
+
+/// 检测到的文档生成器
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DocsGenerator {
+    /// "mkdocs" | "docusaurus" | "mdbook" | "vitepress"
+    pub kind: String,
+    pub name: String,
+    /// 配置文件路径
+    pub config_path: String,
+    /// 运行开发服务器的目录
+    pub working_dir: String,
+    /// 开发服务器命令，{port} 为占位符
+    pub dev_command: String,
+    /// 构建产物目录
+    pub build_dir: String,
+    /// 构建产物是否已存在（可直接静态预览）
+    pub build_ready: bool,
+}
+
+/// 运行中的文档预览
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DocsPreview {
+    pub project_path: String,
+    pub kind: String,
+    /// "dev" | "static"
+    pub mode: String,
+    pub url: String,
+    /// dev 模式的进程 PID
+    pub pid: Option<u32>,
+    /// static 模式使用的静态服务 id
+    pub server_id: Option<String>,
+    pub started_at: String,
+}
+
+/// 运行中的预览及其静态服务是否由预览启动
+struct RunningPreview {
+    preview: DocsPreview,
+    /// 静态服务是预览新建或原本未运行、由预览启动的：停止预览时一并停止；
+    /// 原本就在运行的服务不属于预览，不停止
+    owns_server: bool,
+}
+
+/// 项目路径 -> 运行中的预览
+static PREVIEWS: Lazy<Mutex<HashMap<String, RunningPreview>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 串行化启动：检查已有预览到登记新预览之间不会有第二次启动插入
+static START_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+// ============ 检测 ============
+
+fn find_file(dir: &Path, names: &[&str]) -> Option<PathBuf> {
+    names.iter().map(|n| dir.join(n)).find(|p| p.is_file())
+}
+
+/// mdbook 的 build-dir 配置，默认 book
+fn mdbook_build_dir(config: &Path) -> String {
+    std::fs::read_to_string(config)
+        .ok()
+        .and_then(|content| {
+            content.lines().find_map(|line| {
+                let (key, value) = line.split_once('=')?;
+                (key.trim() == "build-dir").then(|| {
+                    value
+                        .trim()
+                        .trim_matches('"')
+                        .trim_matches('\'')
+                        .to_string()
+                })
+            })
+        })
+        .filter(|d| !d.is_empty())
+        .unwrap_or_else(|| "book".to_string())
+}
+
+fn generator(
+    kind: &str,
+    name: &str,
+    config: PathBuf,
+    working_dir: &Path,
+    dev_command: String,
+    build_dir: PathBuf,
+) -> DocsGenerator {
+    DocsGenerator {
+        kind: kind.to_string(),
+        name: name.to_string(),
+        config_path: config.to_string_lossy().to_string(),
+        working_dir: working_dir.to_string_lossy().to_string(),
+        dev_command,
+        build_ready: build_dir.join("index.html").is_file(),
+        build_dir: build_dir.to_string_lossy().to_string(),
+    }
+}
+
+fn detect_in(dir: &Path) -> Vec<DocsGenerator> {
+    let mut found = Vec::new();
+    if let Some(config) = find_file(dir, &["mkdocs.yml", "mkdocs.yaml"]) {
+        found.push(generator(
+            "mkdocs",
+            "MkDocs",
+            config,
+            dir,
+            "mkdocs serve -a 127.0.0.1:{port}".to_string(),
+            dir.join("site"),
+        ));
+    }
+    if let Some(config) = find_file(
+        dir,
+        &[
+            "docusaurus.config.js",
+            "docusaurus.config.ts",
+            "docusaurus.config.mjs",
+        ],
+    ) {
+        found.push(generator(
+            "docusaurus",
+            "Docusaurus",
+            config,
+            dir,
+            "npx docusaurus start --host 127.0.0.1 --port {port} --no-open".to_string(),
+            dir.join("build"),
+        ));
+    }
+    if let Some(config) = find_file(dir, &["book.toml"]) {
+        let build_dir = dir.join(mdbook_build_dir(&config));
+        found.push(generator(
+            "mdbook",
+            "mdBook",
+            config,
+            dir,
+            "mdbook serve -n 127.0.0.1 -p {port}".to_string(),
+            build_dir,
+        ));
+    }
+    let vitepress = dir.join(".vitepress");
+    if let Some(config) = find_file(
+        &vitepress,
+        &["config.ts", "config.mts", "config.js", "config.mjs"],
+    ) {
+        found.push(generator(
+            "vitepress",
+            "VitePress",
+            config,
+            dir,
+            "npx vitepress dev . --host 127.0.0.1 --port {port}".to_string(),
+            vitepress.join("dist"),
+        ));
+    }
+    found
+}
+
+fn detect(project_path: &Path) -> Vec<DocsGenerator> {
+    ["", "docs", "website"]
+        .iter()
+        .map(|sub| project_path.join(sub))
+        .filter(|dir| dir.is_dir())
+        .flat_map(|dir| detect_in(&dir))
+        .collect()
+}
+
+// ============ 启动 ============
+
+fn free_port() -> AppResult<u16> {
+    let listener = std::net::TcpListener::bind(("127.0.0.1", 0))?;
+    Ok(listener.local_addr()?.port())
+}
+
+fn collect_output<R: AsyncRead + Unpin + Send + 'static>(
+    reader: R,
+    tail: Arc<std::sync::Mutex<VecDeque<String>>>,
+) {
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(reader).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if let Ok(mut tail) = tail.lock() {
+                tail.push_back(line);
+                while tail.len() > ERROR_TAIL_LINES {
+                    tail.pop_front();
+                }
+            }
+        }
+    });
+}
+
+async fn start_dev(project_path: &str, generator: &DocsGenerator) -> AppResult<DocsPreview> {
+    let port = free_port()?;
+    let command = generator.dev_command.replace("{port}", &port.to_string());
+    let mut cmd = shell_command(&command);
+    cmd.current_dir(&generator.working_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| AppError::from(format!("启动 {} 失败: {}", generator.name, e)))?;
+    let pid = child.id();
+
+    let tail = Arc::new(std::sync::Mutex::new(VecDeque::new()));
+    if let Some(stdout) = child.stdout.take() {
+        collect_output(stdout, tail.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        collect_output(stderr, tail.clone());
+    }
+
+    let deadline = Instant::now() + DEV_READY_TIMEOUT;
+    loop {
+        if tokio::net::TcpStream::connect(("127.0.0.1", port))
+            .await
+            .is_ok()
+        {
+            break;
+        }
+        let exited = matches!(child.try_wait(), Ok(Some(_)));
+        if exited || Instant::now() >= deadline {
+            if let Some(pid) = pid {
+                kill_tree(pid, true);
+            }
+            let _ = child.start_kill();
+            let output = tail
+                .lock()
+                .map(|t| t.iter().cloned().collect::<Vec<_>>().join("\n"))
+                .unwrap_or_default();
+            let reason = if exited {
+                "进程已退出"
+            } else {
+                "等待端口就绪超时"
+            };
+            return Err(AppError::from(format!(
+                "{} 开发服务器启动失败（{}）: {}\n{}",
+                generator.name, reason, command, output
+            )));
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+
+    let key = project_path.to_string();
+    tokio::spawn(async move {
+        let _ = child.wait().await;
+        let mut previews = PREVIEWS.lock().await;
+        if previews
+            .get(&key)
+            .map(|p| p.preview.pid == pid)
+            .unwrap_or(false)
+        {
+            previews.remove(&key);
+        }
+    });
+
+    log::info!("文档预览已启动: {} ({})", command, project_path);
+    Ok(DocsPreview {
+        project_path: project_path.to_string(),
+        kind: generator.kind.clone(),
+        mode: "dev".to_string(),
+        url: format!("http://127.0.0.1:{}/", port),
+        pid,
+        server_id: None,
+        started_at: current_iso_time(),
+    })
+}
+
+/// 返回预览与静态服务是否由本次预览启动
+async fn start_static(
+    app: AppHandle,
+    project_path: &str,
+    generator: &DocsGenerator,
+) -> AppResult<(DocsPreview, bool)> {
+    // 同一产物目录复用已有的静态服务配置
+    let existing = get_servers()
+        .await?
+        .into_iter()
+        .find(|s| s.root_dir == generator.build_dir);
+    let server = match existing {
+        Some(server) => server,
+        None => {
+            let project_name = Path::new(project_path)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| project_path.to_string());
+            create_server(ServerConfigInput {
+                name: format!("{} 文档", project_name),
+                port: free_port()?,
+                root_dir: generator.build_dir.clone(),
+                cors: Some(true),
+                gzip: Some(true),
+                cache_control: Some("no-cache".to_string()),
+                url_prefix: Some("/".to_string()),
+                index_page: None,
+                proxies: None,
+                max_concurrent_requests: None,
+                max_connections: None,
+                rate_limit_per_ip: None,
+            })
+            .await?
+        }
+    };
+
+    let already_running = server.status == "running";
+    let url = if already_running {
+        format!("http://127.0.0.1:{}{}", server.port, server.url_prefix)
+    } else {
+        start_server(app, server.id.clone()).await?
+    };
+    let preview = DocsPreview {
+        project_path: project_path.to_string(),
+        kind: generator.kind.clone(),
+        mode: "static".to_string(),
+        url,
+        pid: None,
+        server_id: Some(server.id),
+        started_at: current_iso_time(),
+    };
+    Ok((preview, !already_running))
+}
+
+// ============ commands ============
+
+/// 检测项目中的文档生成器
+#[tauri::command]
+#[specta::specta]
+pub async fn detect_docs_generators(project_path: String) -> AppResult<Vec<DocsGenerator>> {
+    let path = PathBuf::from(&project_path);
+    if !path.is_dir() {
+        return Err(AppError::invalid(format!("目录不存在: {}", project_path)));
+    }
+    Ok(detect(&path))
+}
+
+/// 启动文档预览并返回访问地址；kind 为空时使用检测到的第一个生成器，
+/// mode 为 "dev" / "static"，为空时自动选择。项目已有预览在运行时直接返回。
+#[tauri::command]
+#[specta::specta]
+pub async fn start_docs_preview(
+    app: AppHandle,
+    project_path: String,
+    kind: Option<String>,
+    mode: Option<String>,
+) -> AppResult<DocsPreview> {
+    let _start = START_LOCK.lock().await;
+    let existing = PREVIEWS
+        .lock()
+        .await
+        .get(&project_path)
+        .map(|p| p.preview.clone());
+    if let Some(preview) = existing {
+        // 静态服务可能已在服务列表里被手动停止
+        let alive = match &preview.server_id {
+            Some(id) => get_servers()
+                .await?
+                .iter()
+                .any(|s| &s.id == id && s.status == "running"),
+            None => true,
+        };
+        if alive {
+            return Ok(preview);
+        }
+        PREVIEWS.lock().await.remove(&project_path);
+    }
+
+    let generators = detect_docs_generators(project_path.clone()).await?;
+    let generator = match kind.as_deref().filter(|k| !k.is_empty()) {
+        Some(kind) => generators
+            .into_iter()
+            .find(|g| g.kind == kind)
+            .ok_or_else(|| AppError::invalid(format!("项目中未找到 {} 配置", kind)))?,
+        None => generators.into_iter().next().ok_or_else(|| {
+            AppError::invalid("未检测到文档生成器（mkdocs / docusaurus / mdbook / vitepress）")
+        })?,
+    };
+
+    let (preview, owns_server) = match mode.as_deref() {
+        Some("static") if !generator.build_ready => {
+            return Err(AppError::invalid(format!(
+                "构建产物不存在，请先构建文档: {}",
+                generator.build_dir
+            )))
+        }
+        Some("static") => start_static(app, &project_path, &generator).await?,
+        Some("dev") => (start_dev(&project_path, &generator).await?, false),
+        Some(other) => return Err(AppError::invalid(format!("不支持的预览方式: {}", other))),
+        None if generator.build_ready => start_static(app, &project_path, &generator).await?,
+        None => (start_dev(&project_path, &generator).await?, false),
+    };
+
+    PREVIEWS.lock().await.insert(
+        project_path,
+        RunningPreview {
+            preview: preview.clone(),
+            owns_server,
+        },
+    );
+    Ok(preview)
+}
+
+/// 停止项目的文档预览；复用的、原本就在运行的静态服务保持运行
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_docs_preview(project_path: String) -> AppResult<()> {
+    let RunningPreview {
+        preview,
+        owns_server,
+    } = PREVIEWS
+        .lock()
+        .await
+        .remove(&project_path)
+        .ok_or_else(|| AppError::invalid("该项目没有运行中的文档预览"))?;
+    if let Some(pid) = preview.pid {
+        kill_tree(pid, false);
+    }
+    if let Some(server_id) = preview.server_id.filter(|_| owns_server) {
+        stop_server(server_id).await?;
+    }
+    Ok(())
+}
+
+/// 获取运行中的文档预览
+#[tauri::command]
+#[specta::specta]
+pub async fn get_docs_previews() -> AppResult<Vec<DocsPreview>> {
+    let previews = PREVIEWS.lock().await;
+    let mut list: Vec<DocsPreview> = previews.values().map(|p| p.preview.clone()).collect();
+    list.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+    Ok(list)
+}
@@ -0,0 +1,378 @@
+// 环境检查（首次启动引导页使用）
+//
+// 逐项检查运行环境：git / node 是否可用、WSL、Windows 长路径支持、数据目录写权限，
+// 以及静态服务 / 端口转发所用端口的占用与防火墙提示。
+// 每项返回状态和可选的修复动作（打开网址 / 复制执行的命令），前端按项渲染。
+
+use crate::error::AppResult;
+use serde::Serialize;
+use std::net::TcpListener;
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+#[cfg(target_os = "windows")]
+use std::process::Command;
+
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// 低于该版本的 git 缺少部分功能（worktree 列表、sparse-checkout 等）
+const MIN_GIT_VERSION: (u32, u32) = (2, 25);
+
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorFix {
+    pub label: String,
+    /// "url" 在浏览器打开 | "command" 需在终端执行的命令（前端提供复制）
+    pub kind: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorCheck {
+    /// "git" | "node" | "wsl" | "long_paths" | "data_dir" | "ports"
+    pub id: String,
+    pub title: String,
+    /// "ok" | "info" | "warning" | "error" | "skipped"
+    pub status: String,
+    pub detail: String,
+    pub fixes: Vec<DoctorFix>,
+}
+
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorReport {
+    pub os: String,
+    pub checks: Vec<DoctorCheck>,
+    /// 是否存在 error 级别的检查项
+    pub has_errors: bool,
+    pub checked_at: String,
+}
+
+fn check(id: &str, title: &str, status: &str, detail: impl Into<String>) -> DoctorCheck {
+    DoctorCheck {
+        id: id.to_string(),
+        title: title.to_string(),
+        status: status.to_string(),
+        detail: detail.into(),
+        fixes: Vec::new(),
+    }
+}
+
+fn fix(label: &str, kind: &str, value: impl Into<String>) -> DoctorFix {
+    DoctorFix {
+        label: label.to_string(),
+        kind: kind.to_string(),
+        value: value.into(),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn hidden_command(program: &str) -> Command {
+    let mut cmd = Command::new(program);
+    cmd.creation_flags(CREATE_NO_WINDOW);
+    cmd
+}
+
+/// 从 "2.39.3 (Apple Git-146)" / "2.43.0.windows.1" 中取主次版本号
+fn parse_version(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version
+        .trim()
+        .trim_start_matches('v')
+        .split(|c: char| !c.is_ascii_digit());
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+async fn check_git() -> DoctorCheck {
+    match super::system::check_git_version().await {
+        Ok(version) => match parse_version(&version) {
+            Some(v) if v < MIN_GIT_VERSION => {
+                let mut c = check(
+                    "git",
+                    "Git",
+                    "warning",
+                    format!(
+                        "当前版本 {}，建议升级到 {}.{} 及以上",
+                        version, MIN_GIT_VERSION.0, MIN_GIT_VERSION.1
+                    ),
+                );
+                c.fixes
+                    .push(fix("下载 Git", "url", "https://git-scm.com/downloads"));
+                c
+            }
+            _ => check("git", "Git", "ok", format!("已安装 {}", version)),
+        },
+        Err(_) => {
+            let mut c = check(
+                "git",
+                "Git",
+                "error",
+                "未找到 git，项目扫描、状态和提交等功能不可用",
+            );
+            c.fixes
+                .push(fix("下载 Git", "url", "https://git-scm.com/downloads"));
+            c
+        }
+    }
+}
+
+async fn check_node() -> DoctorCheck {
+    match super::system::check_node_version().await {
+        Ok(version) => check("node", "Node.js", "ok", format!("已安装 {}", version)),
+        Err(_) => {
+            let mut c = check(
+                "node",
+                "Node.js",
+                "warning",
+                "未找到 node，项目脚本、简历导出等依赖 Node.js 的功能不可用",
+            );
+            c.fixes
+                .push(fix("下载 Node.js", "url", "https://nodejs.org/"));
+            c
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn check_wsl() -> DoctorCheck {
+    let output = hidden_command("wsl").args(["--list", "--quiet"]).output();
+    match output {
+        Ok(out) if out.status.success() => {
+            // wsl.exe 输出 UTF-16LE，按字节去掉 \0 即可得到 ASCII 发行版名
+            let distros: Vec<String> = String::from_utf8_lossy(&out.stdout)
+                .lines()
+                .map(|s| s.trim().replace(['\0', '\r'], ""))
+                .filter(|s| !s.is_empty())
+                .collect();
+            if distros.is_empty() {
+                let mut c = check("wsl", "WSL", "info", "已启用 WSL，但尚未安装发行版");
+                c.fixes
+                    .push(fix("安装 Ubuntu", "command", "wsl --install -d Ubuntu"));
+                c
+            } else {
+                check(
+                    "wsl",
+                    "WSL",
+                    "ok",
+                    format!("已安装发行版：{}", distros.join(", ")),
+                )
+            }
+        }
+        _ => {
+            let mut c = check(
+                "wsl",
+                "WSL",
+                "info",
+                "未启用 WSL，WSL 中的 Claude Code 配置和项目将无法识别",
+            );
+            c.fixes.push(fix("启用 WSL", "command", "wsl --install"));
+            c
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn check_wsl() -> DoctorCheck {
+    check("wsl", "WSL", "skipped", "仅 Windows 需要")
+}
+
+#[cfg(target_os = "windows")]
+fn check_long_paths() -> DoctorCheck {
+    let system_enabled = hidden_command("reg")
+        .args([
+            "query",
+            r"HKLM\SYSTEM\CurrentControlSet\Control\FileSystem",
+            "/v",
+            "LongPathsEnabled",
+        ])
+        .output()
+        .map(|out| {
+            String::from_utf8_lossy(&out.stdout)
+                .split_whitespace()
+                .last()
+                .is_some_and(|v| v == "0x1")
+        })
+        .unwrap_or(false);
+    let git_enabled = hidden_command("git")
+        .args(["config", "--global", "--get", "core.longpaths"])
+        .output()
+        .map(|out| String::from_utf8_lossy(&out.stdout).trim() == "true")
+        .unwrap_or(false);
+
+    if system_enabled && git_enabled {
+        return check(
+            "long_paths",
+            "长路径支持",
+            "ok",
+            "系统与 git 均已启用长路径",
+        );
+    }
+    let mut c = check(
+        "long_paths",
+        "长路径支持",
+        "warning",
+        "路径超过 260 个字符时，依赖目录较深的项目可能出现克隆或读写失败",
+    );
+    if !system_enabled {
+        c.fixes.push(fix(
+            "启用系统长路径（管理员 PowerShell）",
+            "command",
+            r#"New-ItemProperty -Path "HKLM:\SYSTEM\CurrentControlSet\Control\FileSystem" -Name "LongPathsEnabled" -Value 1 -PropertyType DWORD -Force"#,
+        ));
+    }
+    if !git_enabled {
+        c.fixes.push(fix(
+            "启用 git 长路径",
+            "command",
+            "git config --global core.longpaths true",
+        ));
+    }
+    c
+}
+
+#[cfg(not(target_os = "windows"))]
+fn check_long_paths() -> DoctorCheck {
+    check("long_paths", "长路径支持", "skipped", "仅 Windows 需要")
+}
+
+fn check_data_dir() -> DoctorCheck {
+    let config = match crate::storage::get_storage_config() {
+        Ok(config) => config,
+        Err(e) => return check("data_dir", "数据目录", "error", e.to_string()),
+    };
+    let dir = &config.data_dir;
+    let probe = dir.join(format!(".doctor-{}", std::process::id()));
+    let result = std::fs::create_dir_all(dir)
+        .and_then(|_| std::fs::write(&probe, b"ok"))
+        .and_then(|_| std::fs::remove_file(&probe));
+    match result {
+        Ok(_) => check(
+            "data_dir",
+            "数据目录",
+            "ok",
+            format!("{} 可写", dir.display()),
+        ),
+        Err(e) => check(
+            "data_dir",
+            "数据目录",
+            "error",
+            format!(
+                "{} 不可写：{}。请检查目录权限或在设置中更换数据目录",
+                dir.display(),
+                e
+            ),
+        ),
+    }
+}
+
+fn firewall_fix(port: u16) -> DoctorFix {
+    #[cfg(target_os = "windows")]
+    {
+        fix(
+            &format!("放行端口 {}（管理员终端）", port),
+            "command",
+            format!(
+                "netsh advfirewall firewall add rule name=\"CodeShelf {0}\" dir=in action=allow protocol=TCP localport={0}",
+                port
+            ),
+        )
+    }
+    #[cfg(target_os = "macos")]
+    {
+        fix(
+            &format!("放行端口 {}", port),
+            "url",
+            "x-apple.systempreferences:com.apple.preference.security?Firewall",
+        )
+    }
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        fix(
+            &format!("放行端口 {}（ufw）", port),
+            "command",
+            format!("sudo ufw allow {}/tcp", port),
+        )
+    }
+}
+
+async fn check_ports() -> DoctorCheck {
+    let mut ports: Vec<(u16, String, bool)> = Vec::new();
+    if let Ok(servers) = super::toolbox::server::get_servers().await {
+        ports.extend(servers.into_iter().map(|s| {
+            (
+                s.port,
+                format!("静态服务「{}」", s.name),
+                s.status == "running",
+            )
+        }));
+    }
+    if let Ok(rules) = super::toolbox::forwarder::get_forward_rules().await {
+        ports.extend(rules.into_iter().map(|r| {
+            (
+                r.local_port,
+                format!("端口转发「{}」", r.name),
+                r.status == "running",
+            )
+        }));
+    }
+    if ports.is_empty() {
+        return check("ports", "服务端口", "skipped", "尚未配置静态服务或端口转发");
+    }
+    ports.sort_by_key(|(port, _, _)| *port);
+
+    // 未运行的端口若无法绑定，说明被其它程序占用
+    let busy: Vec<String> = ports
+        .iter()
+        .filter(|(port, _, running)| !running && TcpListener::bind(("0.0.0.0", *port)).is_err())
+        .map(|(port, owner, _)| format!("{}（{}）", port, owner))
+        .collect();
+
+    let mut unique: Vec<u16> = ports.iter().map(|(port, _, _)| *port).collect();
+    unique.dedup();
+
+    let mut c = if busy.is_empty() {
+        check(
+            "ports",
+            "服务端口",
+            "info",
+            "需要局域网内其它设备访问时，请确认防火墙已放行以下端口",
+        )
+    } else {
+        check(
+            "ports",
+            "服务端口",
+            "warning",
+            format!("以下端口已被其它程序占用：{}", busy.join("、")),
+        )
+    };
+    c.fixes = unique.into_iter().map(firewall_fix).collect();
+    c
+}
+
+/// 运行环境检查，返回逐项报告
+#[tauri::command]
+#[specta::specta]
+pub async fn run_environment_doctor() -> AppResult<DoctorReport> {
+    let (wsl, long_paths, data_dir) =
+        tokio::task::spawn_blocking(|| (check_wsl(), check_long_paths(), check_data_dir()))
+            .await
+            .map_err(|e| crate::error::AppError::internal(format!("环境检查失败: {}", e)))?;
+
+    let checks = vec![
+        check_git().await,
+        check_node().await,
+        wsl,
+        long_paths,
+        data_dir,
+        check_ports().await,
+    ];
+    Ok(DoctorReport {
+        os: std::env::consts::OS.to_string(),
+        has_errors: checks.iter().any(|c| c.status == "error"),
+        checks,
+        checked_at: crate::storage::current_iso_time(),
+    })
+}
@@ -1,8 +1,12 @@
-// 分支命令：get_branches / checkout_branch / create_branch
+// 分支命令：get_branches / checkout_branch / create_branch / compare_branches
 
-use super::{run_git_command, BranchInfo};
+use super::commits::parse_log_records;
+use super::{lock_repo, run_git_command, BranchComparison, BranchDiffFile, BranchInfo};
 use crate::error::AppResult;
 
+/// 对比时每侧最多返回的提交数
+const MAX_COMPARE_COMMITS: u32 = 300;
+
 #[tauri::command]
 #[specta::specta]
 pub async fn get_branches(path: String) -> AppResult<Vec<BranchInfo>> {
@@ -44,12 +48,14 @@ pub async fn get_branches(path: String) -> AppResult<Vec<BranchInfo>> {
 #[tauri::command]
 #[specta::specta]
 pub async fn checkout_branch(path: String, branch: String) -> AppResult<String> {
+    let _guard = lock_repo(&path, "切换分支").await;
     run_git_command(&path, &["checkout", &branch])
 }
 
 #[tauri::command]
 #[specta::specta]
 pub async fn create_branch(path: String, branch: String, checkout: bool) -> AppResult<String> {
+    let _guard = lock_repo(&path, "创建分支").await;
     if checkout {
         // Create and checkout the new branch
         run_git_command(&path, &["checkout", "-b", &branch])
@@ -58,3 +64,114 @@ pub async fn create_branch(path: String, branch: String, checkout: bool) -> AppR
         run_git_command(&path, &["branch", &branch])
     }
 }
+
+fn compare_log(path: &str, range: &str) -> AppResult<Vec<super::CommitInfo>> {
+    let format = ["%H", "%h", "%s", "%an", "%ae", "%aI", "%b", "%D", "%P"].join("%x1f");
+    let output = run_git_command(
+        path,
+        &[
+            "log",
+            &format!("-{}", MAX_COMPARE_COMMITS),
+            &format!("--format=%x1e{}", format),
+            range,
+        ],
+    )?;
+    Ok(parse_log_records(&output))
+}
+
+/// merge-base...head 的文件改动：--name-status 与 --numstat 输出顺序一致，按行对应合并
+fn compare_files(path: &str, base: &str, head: &str) -> AppResult<Vec<BranchDiffFile>> {
+    let range = format!("{}...{}", base, head);
+    let names = run_git_command(path, &["diff", "--name-status", "-M", &range])?;
+    let stats = run_git_command(path, &["diff", "--numstat", "-M", &range])?;
+    let files = names
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .zip(stats.lines().filter(|l| !l.trim().is_empty()))
+        .filter_map(|(name_line, stat_line)| {
+            let parts: Vec<&str> = name_line.split('\t').collect();
+            let status = parts.first()?.chars().next()?.to_string();
+            let (old_path, path) = match parts.as_slice() {
+                [_, old, new] => (Some(old.to_string()), new.to_string()),
+                [_, path] => (None, path.to_string()),
+                _ => return None,
+            };
+            let mut stat = stat_line.split('\t');
+            let insertions = stat.next().unwrap_or("-");
+            let deletions = stat.next().unwrap_or("-");
+            Some(BranchDiffFile {
+                path,
+                old_path,
+                status,
+                binary: insertions == "-" && deletions == "-",
+                insertions: insertions.parse().unwrap_or(0),
+                deletions: deletions.parse().unwrap_or(0),
+            })
+        })
+        .collect();
+    Ok(files)
+}
+
+/// 对比两个分支：ahead / behind、各自独有的提交，以及 head 相对合并基点的文件改动
+#[tauri::command]
+#[specta::specta]
+pub async fn compare_branches(
+    path: String,
+    base: String,
+    head: String,
+) -> AppResult<BranchComparison> {
+    tokio::task::spawn_blocking(move || {
+        for rev in [&base, &head] {
+            run_git_command(
+                &path,
+                &[
+                    "rev-parse",
+                    "--verify",
+                    "--quiet",
+                    &format!("{}^{{commit}}", rev),
+                ],
+            )
+            .map_err(|_| crate::error::AppError::invalid(format!("找不到分支或提交: {}", rev)))?;
+        }
+        let counts = run_git_command(
+            &path,
+            &[
+                "rev-list",
+                "--left-right",
+                "--count",
+                &format!("{}...{}", base, head),
+            ],
+        )?;
+        let mut counts = counts
+            .split_whitespace()
+            .map(|v| v.parse::<u32>().unwrap_or(0));
+        let behind = counts.next().unwrap_or(0);
+        let ahead = counts.next().unwrap_or(0);
+
+        let merge_base = run_git_command(&path, &["merge-base", &base, &head]).ok();
+        let ahead_commits = compare_log(&path, &format!("{}..{}", base, head))?;
+        let behind_commits = compare_log(&path, &format!("{}..{}", head, base))?;
+        // 没有共同祖先时三点 diff 无法计算，文件列表留空
+        let files = if merge_base.is_some() {
+            compare_files(&path, &base, &head)?
+        } else {
+            Vec::new()
+        };
+
+        Ok(BranchComparison {
+            insertions: files.iter().map(|f| f.insertions).sum(),
+            deletions: files.iter().map(|f| f.deletions).sum(),
+            truncated: ahead > MAX_COMPARE_COMMITS || behind > MAX_COMPARE_COMMITS,
+            base,
+            head,
+            merge_base,
+            ahead,
+            behind,
+            ahead_commits,
+            behind_commits,
+            files,
+        })
+    })
+    .await
+    .map_err(|e| crate::error::AppError::internal(e.to_string()))?
+}
@@ -1,18 +1,14 @@
 // git clone 与取消：包含进度解析、子进程管理
 
 use crate::error::AppResult;
+use crate::path_compat::{decode_text, display_path, git_command, native_path};
 use std::io::Read;
-use std::process::{Command, Stdio};
+use std::process::Stdio;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex as StdMutex;
 
-#[cfg(target_os = "windows")]
-use std::os::windows::process::CommandExt;
-
 use super::GitCloneProgress;
-
-#[cfg(target_os = "windows")]
-use super::CREATE_NO_WINDOW;
+use crate::commands::operations::kill_process_tree;
 
 // Git clone progress management
 static CLONE_PID: StdMutex<Option<u32>> = StdMutex::new(None);
@@ -66,27 +62,6 @@ fn parse_clone_progress(line: &str) -> Option<GitCloneProgress> {
     }
 }
 
-fn kill_process_tree(pid: u32) {
-    #[cfg(target_os = "windows")]
-    {
-        let _ = Command::new("taskkill")
-            .args(["/PID", &pid.to_string(), "/T", "/F"])
-            .creation_flags(CREATE_NO_WINDOW)
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .status();
-    }
-
-    #[cfg(not(target_os = "windows"))]
-    {
-        let _ = Command::new("kill")
-            .args(["-9", &pid.to_string()])
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .status();
-    }
-}
-
 #[tauri::command]
 #[specta::specta]
 pub async fn git_clone(
@@ -99,8 +74,8 @@ pub async fn git_clone(
     use std::path::PathBuf;
     use tauri::Emitter;
 
-    let target_path = PathBuf::from(&target_dir).join(&repo_name);
-    let target_path_str = target_path.to_string_lossy().to_string();
+    let target_path = native_path(PathBuf::from(&target_dir).join(&repo_name));
+    let target_path_str = display_path(&target_path.to_string_lossy());
 
     if target_path.exists() {
         return Err(crate::error::AppError::from(format!(
@@ -135,17 +110,7 @@ pub async fn git_clone(
     );
 
     // Spawn clone process with --progress flag
-    #[cfg(target_os = "windows")]
-    let mut child = Command::new("git")
-        .args(["clone", "--progress", &url, &target_path_str])
-        .creation_flags(CREATE_NO_WINDOW)
-        .stdout(Stdio::null())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| crate::error::AppError::from(format!("启动 git clone 失败: {}", e)))?;
-
-    #[cfg(not(target_os = "windows"))]
-    let mut child = Command::new("git")
+    let mut child = git_command()
         .args(["clone", "--progress", &url, &target_path_str])
         .stdout(Stdio::null())
         .stderr(Stdio::piped())
@@ -166,7 +131,8 @@ pub async fn git_clone(
     if let Some(stderr) = child.stderr.take() {
         let mut reader = BufReader::new(stderr);
         let mut buf = vec![0u8; 512];
-        let mut line = String::new();
+        // 按字节收集，整行再解码，避免中文路径等多字节字符被拆开
+        let mut bytes: Vec<u8> = Vec::new();
 
         loop {
             match reader.read(&mut buf) {
@@ -174,15 +140,16 @@ pub async fn git_clone(
                 Ok(n) => {
                     for &byte in &buf[..n] {
                         if byte == b'\r' || byte == b'\n' {
-                            if !line.is_empty() {
+                            if !bytes.is_empty() {
+                                let line = decode_text(&bytes);
                                 if let Some(progress) = parse_clone_progress(&line) {
                                     let _ = app.emit("git-clone-progress", progress);
                                 }
-                                last_error_line = line.clone();
-                                line.clear();
+                                last_error_line = line;
+                                bytes.clear();
                             }
                         } else {
-                            line.push(byte as char);
+                            bytes.push(byte);
                         }
                     }
                 }
@@ -190,7 +157,8 @@ pub async fn git_clone(
             }
         }
 
-        if !line.is_empty() {
+        if !bytes.is_empty() {
+            let line = decode_text(&bytes);
             if let Some(progress) = parse_clone_progress(&line) {
                 let _ = app.emit("git-clone-progress", progress);
             }
@@ -1,6 +1,6 @@
 // 提交历史、详情、文件变更、搜索
 
-use super::{run_git_command, CommitFileChange, CommitInfo};
+use super::{run_git_command, CommitFileChange, CommitInfo, CommitSearchOptions};
 use crate::error::AppResult;
 
 /// 解析分支/标签引用
@@ -232,46 +232,9 @@ pub async fn get_commit_files(
     Ok(files)
 }
 
-/// 搜索提交历史
-#[tauri::command]
-#[specta::specta]
-pub async fn search_commits(
-    path: String,
-    query: String,
-    search_type: Option<String>,
-    limit: Option<u32>,
-) -> AppResult<Vec<CommitInfo>> {
-    let limit_str = limit.unwrap_or(50).to_string();
-    let format = ["%H", "%h", "%s", "%an", "%ae", "%aI", "%b", "%D", "%P"].join("%x1f");
-
-    let mut args = vec![
-        "log".to_string(),
-        format!("-{}", limit_str),
-        format!("--format=%x1e{}", format),
-    ];
-
-    // 根据搜索类型添加参数
-    match search_type.as_deref() {
-        Some("author") => {
-            args.push(format!("--author={}", query));
-        }
-        Some("message") => {
-            args.push(format!("--grep={}", query));
-        }
-        Some("hash") => {
-            // 直接查找特定提交
-            return get_commit_detail(path, query).await.map(|c| vec![c]);
-        }
-        _ => {
-            // 默认搜索提交信息
-            args.push(format!("--grep={}", query));
-        }
-    }
-
-    let args_ref: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-    let output = run_git_command(&path, &args_ref)?;
-
-    let commits: Vec<CommitInfo> = output
+/// 解析 git log 输出（%x1e 分隔提交、%x1f 分隔字段，字段顺序同 search_commits），不含统计信息
+pub(super) fn parse_log_records(output: &str) -> Vec<CommitInfo> {
+    output
         .split('\x1e')
         .filter(|s| !s.trim().is_empty())
         .filter_map(|record| {
@@ -302,7 +265,88 @@ pub async fn search_commits(
                 None
             }
         })
+        .collect()
+}
+
+/// 把附加过滤条件转成 git log 参数；pathspec 需要放在最后，单独返回
+fn search_option_args(options: &CommitSearchOptions) -> (Vec<String>, Vec<String>) {
+    let mut args = Vec::new();
+    if let Some(since) = options.since.as_deref().filter(|v| !v.trim().is_empty()) {
+        args.push(format!("--since={}", since.trim()));
+    }
+    if let Some(until) = options.until.as_deref().filter(|v| !v.trim().is_empty()) {
+        args.push(format!("--until={}", until.trim()));
+    }
+    if options.all_branches {
+        args.push("--all".to_string());
+    }
+    if let Some(pickaxe) = options.pickaxe.as_deref().filter(|v| !v.is_empty()) {
+        let flag = if options.pickaxe_regex { "-G" } else { "-S" };
+        args.push(format!("{}{}", flag, pickaxe));
+    }
+    let paths = options
+        .paths
+        .iter()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        // "src/**" 之类的通配需要 glob 魔法前缀，git 默认 pathspec 不识别 **
+        .map(|p| {
+            if p.contains('*') && !p.starts_with(':') {
+                format!(":(glob){}", p)
+            } else {
+                p.to_string()
+            }
+        })
         .collect();
+    (args, paths)
+}
 
-    Ok(commits)
+/// 搜索提交历史；options 可附加日期范围、跨分支、路径与内容（pickaxe）过滤
+#[tauri::command]
+#[specta::specta]
+pub async fn search_commits(
+    path: String,
+    query: String,
+    search_type: Option<String>,
+    limit: Option<u32>,
+    options: Option<CommitSearchOptions>,
+) -> AppResult<Vec<CommitInfo>> {
+    let limit_str = limit.unwrap_or(50).to_string();
+    let format = ["%H", "%h", "%s", "%an", "%ae", "%aI", "%b", "%D", "%P"].join("%x1f");
+    let options = options.unwrap_or_default();
+
+    let mut args = vec![
+        "log".to_string(),
+        format!("-{}", limit_str),
+        format!("--format=%x1e{}", format),
+    ];
+
+    // 根据搜索类型添加参数；只按附加条件过滤时 query 可以为空
+    let query = query.trim().to_string();
+    match search_type.as_deref() {
+        Some("hash") => {
+            // 直接查找特定提交
+            return get_commit_detail(path, query).await.map(|c| vec![c]);
+        }
+        _ if query.is_empty() => {}
+        Some("author") => {
+            args.push(format!("--author={}", query));
+        }
+        _ => {
+            // 默认搜索提交信息
+            args.push(format!("--grep={}", query));
+        }
+    }
+
+    let (extra, paths) = search_option_args(&options);
+    args.extend(extra);
+    if !paths.is_empty() {
+        args.push("--".to_string());
+        args.extend(paths);
+    }
+
+    let args_ref: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+    let output = run_git_command(&path, &args_ref)?;
+
+    Ok(parse_log_records(&output))
 }
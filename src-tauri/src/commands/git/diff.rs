@@ -0,0 +1,321 @@
+// 文件比较：两个路径（文件或目录，不要求在仓库内）或同一仓库的两个版本
+//
+// 都基于 `git diff` 的 unified 输出解析出文件级与行级差异：
+//   - diff_paths 使用 `git diff --no-index`，输出路径带比较根目录前缀，解析时去掉
+//   - diff_revisions 使用 `git diff <rev_a> <rev_b>`，路径相对仓库根目录
+// 行数超过 MAX_LINES 后只保留文件级信息，避免巨大目录把前端撑爆。
+
+use crate::error::{AppError, AppResult};
+use crate::path_compat::{decode_text, git_command, git_in};
+use std::path::Path;
+
+use super::{unquote_git_path, DiffHunk, DiffLine, DiffResult, FileDiff};
+
+/// 返回给前端的最大差异行数
+const MAX_LINES: usize = 20_000;
+
+/// 执行 git diff；`--no-index` 有差异时退出码为 1，同样视为成功
+pub(crate) fn run_git_diff(cwd: Option<&str>, args: &[&str]) -> AppResult<String> {
+    let mut cmd = match cwd {
+        Some(dir) => git_in(dir),
+        None => git_command(),
+    };
+    cmd.args(args);
+
+    let output = cmd.output().map_err(|e| AppError::from(e.to_string()))?;
+    match output.status.code() {
+        Some(0) | Some(1) => Ok(decode_text(&output.stdout)),
+        _ => Err(AppError::from(
+            decode_text(&output.stderr).trim().to_string(),
+        )),
+    }
+}
+
+/// 与 git 输出中的路径写法对齐：正斜杠、去掉首尾 `/`
+fn normalize_root(path: &str) -> String {
+    path.replace('\\', "/").trim_matches('/').to_string()
+}
+
+/// 去掉 `a/`、`b/` 前缀与比较根目录，得到相对路径
+fn relativize(raw: &str, prefix: &str, root: &str) -> String {
+    let path = unquote_git_path(raw.trim_end_matches('\t'));
+    let path = path.strip_prefix(prefix).unwrap_or(&path);
+    let rest = path
+        .strip_prefix(root)
+        .unwrap_or(path)
+        .trim_start_matches('/');
+    if rest.is_empty() {
+        // 文件对文件比较时根目录就是文件本身
+        Path::new(root)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default()
+    } else {
+        rest.to_string()
+    }
+}
+
+/// 拆分 `diff --git a/x b/y` 头中的两个路径。
+///
+/// 含特殊字符的路径带引号，按引号边界拆分；未加引号的路径本身可能含 " b/"，
+/// 优先取两侧相对路径相同的拆分点，其次取 b 侧以比较根目录开头的拆分点
+fn split_header_paths(rest: &str, root_a: &str, root_b: &str) -> Option<(String, String)> {
+    if rest.starts_with('"') {
+        let mut escaped = false;
+        let end = rest
+            .char_indices()
+            .skip(1)
+            .find(|&(_, c)| {
+                let close = c == '"' && !escaped;
+                escaped = c == '\\' && !escaped;
+                close
+            })
+            .map(|(i, _)| i)?;
+        let b = rest.get(end + 2..)?;
+        return Some((rest[..=end].to_string(), b.to_string()));
+    }
+    if rest.ends_with('"') {
+        let (a, b) = rest.rsplit_once(" \"")?;
+        return Some((a.to_string(), format!("\"{}", b)));
+    }
+
+    let splits: Vec<(&str, &str)> = rest
+        .match_indices(" b/")
+        .map(|(i, _)| (&rest[..i], &rest[i + 1..]))
+        .collect();
+    splits
+        .iter()
+        .find(|(a, b)| relativize(a, "a/", root_a) == relativize(b, "b/", root_b))
+        .or_else(|| {
+            splits
+                .iter()
+                .find(|(_, b)| !root_b.is_empty() && b[2..].starts_with(root_b))
+        })
+        .or(splits.first())
+        .map(|(a, b)| (a.to_string(), b.to_string()))
+}
+
+/// 解析 `-12,3` / `+7` 形式的范围
+pub(crate) fn parse_range(s: &str) -> (u32, u32) {
+    let s = s.trim_start_matches(['-', '+']);
+    match s.split_once(',') {
+        Some((start, count)) => (start.parse().unwrap_or(0), count.parse().unwrap_or(0)),
+        None => (s.parse().unwrap_or(0), 1),
+    }
+}
+
+pub(crate) fn parse_unified_diff(output: &str, root_a: &str, root_b: &str) -> DiffResult {
+    let mut files: Vec<FileDiff> = Vec::new();
+    let mut current: Option<FileDiff> = None;
+    let mut header_paths: Option<(String, String)> = None;
+    let mut hunk: Option<DiffHunk> = None;
+    let (mut old_no, mut new_no, mut old_rem, mut new_rem) = (0u32, 0u32, 0u32, 0u32);
+    let mut budget = MAX_LINES;
+    let mut truncated = false;
+
+    let finish_file = |file: Option<FileDiff>,
+                       hunk: Option<DiffHunk>,
+                       header: Option<(String, String)>,
+                       files: &mut Vec<FileDiff>| {
+        let Some(mut file) = file else {
+            return;
+        };
+        if let Some(h) = hunk {
+            file.hunks.push(h);
+        }
+        // 二进制文件没有 ---/+++ 行，从 diff --git 头取路径
+        if let Some((a, b)) = header {
+            if file.old_path.is_none() && file.status != "added" {
+                file.old_path = Some(relativize(&a, "a/", root_a));
+            }
+            if file.new_path.is_none() && file.status != "removed" {
+                file.new_path = Some(relativize(&b, "b/", root_b));
+            }
+        }
+        match file.status.as_str() {
+            "added" => file.old_path = None,
+            "removed" => file.new_path = None,
+            _ => {}
+        }
+        files.push(file);
+    };
+
+    for line in output.lines() {
+        // hunk 内按行数计数，避免把以 "---" 开头的删除行误判为文件头
+        if current.is_some() && (old_rem > 0 || new_rem > 0) {
+            let (kind, old_line, new_line) = match line.chars().next() {
+                Some('+') => {
+                    new_rem = new_rem.saturating_sub(1);
+                    new_no += 1;
+                    ("add", None, Some(new_no - 1))
+                }
+                Some('-') => {
+                    old_rem = old_rem.saturating_sub(1);
+                    old_no += 1;
+                    ("remove", Some(old_no - 1), None)
+                }
+                Some('\\') => continue,
+                _ => {
+                    old_rem = old_rem.saturating_sub(1);
+                    new_rem = new_rem.saturating_sub(1);
+                    old_no += 1;
+                    new_no += 1;
+                    ("context", Some(old_no - 1), Some(new_no - 1))
+                }
+            };
+            if let Some(file) = current.as_mut() {
+                match kind {
+                    "add" => file.insertions += 1,
+                    "remove" => file.deletions += 1,
+                    _ => {}
+                }
+            }
+            if budget == 0 {
+                truncated = true;
+                continue;
+            }
+            budget -= 1;
+            if let Some(h) = hunk.as_mut() {
+                h.lines.push(DiffLine {
+                    kind: kind.to_string(),
+                    content: line.get(1..).unwrap_or("").to_string(),
+                    old_line,
+                    new_line,
+                    segments: None,
+                });
+            }
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            finish_file(current.take(), hunk.take(), header_paths.take(), &mut files);
+            header_paths = split_header_paths(rest, root_a, root_b);
+            current = Some(FileDiff {
+                status: "modified".to_string(),
+                old_path: None,
+                new_path: None,
+                binary: false,
+                insertions: 0,
+                deletions: 0,
+                hunks: Vec::new(),
+            });
+            continue;
+        }
+        let Some(file) = current.as_mut() else {
+            continue;
+        };
+
+        if let Some(rest) = line.strip_prefix("@@ ") {
+            if let Some(h) = hunk.take() {
+                file.hunks.push(h);
+            }
+            let mut parts = rest.split_whitespace();
+            let (old_start, old_lines) = parse_range(parts.next().unwrap_or(""));
+            let (new_start, new_lines) = parse_range(parts.next().unwrap_or(""));
+            old_no = old_start;
+            new_no = new_start;
+            old_rem = old_lines;
+            new_rem = new_lines;
+            if budget == 0 {
+                truncated = true;
+                continue;
+            }
+            hunk = Some(DiffHunk {
+                header: line.to_string(),
+                old_start,
+                old_lines,
+                new_start,
+                new_lines,
+                lines: Vec::new(),
+            });
+        } else if line.starts_with("new file mode") {
+            file.status = "added".to_string();
+        } else if line.starts_with("deleted file mode") {
+            file.status = "removed".to_string();
+        } else if let Some(rest) = line.strip_prefix("rename from ") {
+            file.status = "renamed".to_string();
+            file.old_path = Some(relativize(rest, "", root_a));
+        } else if let Some(rest) = line.strip_prefix("rename to ") {
+            file.new_path = Some(relativize(rest, "", root_b));
+        } else if line.starts_with("Binary files ") {
+            file.binary = true;
+        } else if let Some(rest) = line.strip_prefix("--- ") {
+            if rest != "/dev/null" {
+                file.old_path = Some(relativize(rest, "a/", root_a));
+            }
+        } else if let Some(rest) = line.strip_prefix("+++ ") {
+            if rest != "/dev/null" {
+                file.new_path = Some(relativize(rest, "b/", root_b));
+            }
+        }
+    }
+    finish_file(current.take(), hunk.take(), header_paths.take(), &mut files);
+
+    let count = |status: &str| files.iter().filter(|f| f.status == status).count() as u32;
+    DiffResult {
+        added: count("added"),
+        removed: count("removed"),
+        changed: count("modified") + count("renamed"),
+        insertions: files.iter().map(|f| f.insertions).sum(),
+        deletions: files.iter().map(|f| f.deletions).sum(),
+        truncated,
+        files,
+    }
+}
+
+/// 比较两个路径（同为文件或同为目录）
+#[tauri::command]
+#[specta::specta]
+pub async fn diff_paths(path_a: String, path_b: String) -> AppResult<DiffResult> {
+    let (a, b) = (Path::new(&path_a), Path::new(&path_b));
+    if !a.exists() || !b.exists() {
+        return Err(AppError::invalid("比较的路径不存在"));
+    }
+    if a.is_dir() != b.is_dir() {
+        return Err(AppError::invalid("只能比较两个文件或两个目录"));
+    }
+
+    let output = run_git_diff(
+        None,
+        &[
+            "diff",
+            "--no-index",
+            "--no-color",
+            "--no-ext-diff",
+            "-M",
+            "--",
+            &path_a,
+            &path_b,
+        ],
+    )?;
+    Ok(parse_unified_diff(
+        &output,
+        &normalize_root(&path_a),
+        &normalize_root(&path_b),
+    ))
+}
+
+/// 比较仓库的两个版本（分支、标签、提交均可）
+#[tauri::command]
+#[specta::specta]
+pub async fn diff_revisions(path: String, rev_a: String, rev_b: String) -> AppResult<DiffResult> {
+    for rev in [&rev_a, &rev_b] {
+        if rev.trim().is_empty() || rev.starts_with('-') {
+            return Err(AppError::invalid(format!("无效的版本: {}", rev)));
+        }
+    }
+
+    let output = run_git_diff(
+        Some(&path),
+        &[
+            "diff",
+            "--no-color",
+            "--no-ext-diff",
+            "-M",
+            &rev_a,
+            &rev_b,
+            "--",
+        ],
+    )?;
+    Ok(parse_unified_diff(&output, "", ""))
+}
@@ -0,0 +1,332 @@
+// Git hooks 管理：列出 / 从模板安装 / 卸载 / 启停 hook 脚本
+//
+// hooks 目录通过 `git rev-parse --git-path hooks` 取得（兼容 core.hooksPath 与 worktree）。
+// 停用的 hook 以 `.disabled` 后缀保存，git 不会执行；
+// 由模板安装的脚本第二行写入 `# codeshelf-template: <id>` 作为标记。
+
+use crate::error::{AppError, AppResult};
+use crate::storage::{self, generate_id};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{run_git_command, GitHookInfo, GitHookTemplate};
+
+const DISABLED_SUFFIX: &str = ".disabled";
+const TEMPLATE_MARKER: &str = "# codeshelf-template:";
+
+/// git 支持的客户端 hook
+const KNOWN_HOOKS: &[&str] = &[
+    "applypatch-msg",
+    "pre-applypatch",
+    "post-applypatch",
+    "pre-commit",
+    "pre-merge-commit",
+    "prepare-commit-msg",
+    "commit-msg",
+    "post-commit",
+    "pre-rebase",
+    "post-checkout",
+    "post-merge",
+    "pre-push",
+    "post-rewrite",
+    "pre-auto-gc",
+];
+
+fn builtin_templates() -> Vec<GitHookTemplate> {
+    vec![
+        GitHookTemplate {
+            id: "builtin-commit-msg-lint".to_string(),
+            name: "提交信息规范检查".to_string(),
+            hook: "commit-msg".to_string(),
+            description: "要求提交信息符合 Conventional Commits（feat/fix/docs/...）".to_string(),
+            content: r#"#!/bin/sh
+msg=$(head -n 1 "$1")
+pattern='^(feat|fix|docs|style|refactor|perf|test|build|ci|chore|revert)(\(.+\))?!?: .+'
+if ! echo "$msg" | grep -Eq "$pattern"; then
+  echo "提交信息不符合 Conventional Commits 规范: $msg" >&2
+  echo "示例: feat(scope): 添加新功能" >&2
+  exit 1
+fi
+"#
+            .to_string(),
+            builtin: true,
+        },
+        GitHookTemplate {
+            id: "builtin-pre-push-test".to_string(),
+            name: "推送前运行测试".to_string(),
+            hook: "pre-push".to_string(),
+            description: "按项目类型运行 cargo test / npm test，失败则阻止推送".to_string(),
+            content: r#"#!/bin/sh
+if [ -f Cargo.toml ]; then
+  cargo test || exit 1
+elif [ -f package.json ]; then
+  npm test || exit 1
+fi
+"#
+            .to_string(),
+            builtin: true,
+        },
+        GitHookTemplate {
+            id: "builtin-pre-commit-whitespace".to_string(),
+            name: "提交前检查空白错误".to_string(),
+            hook: "pre-commit".to_string(),
+            description: "拒绝包含行尾空白或冲突标记的提交".to_string(),
+            content: r#"#!/bin/sh
+exec git diff --cached --check
+"#
+            .to_string(),
+            builtin: true,
+        },
+    ]
+}
+
+fn load_templates() -> AppResult<Vec<GitHookTemplate>> {
+    let config = storage::get_storage_config()?;
+    let path = config.git_hook_templates_file();
+    if !path.exists() {
+        return Ok(builtin_templates());
+    }
+    let content = fs::read_to_string(&path)
+        .map_err(|e| AppError::from(format!("读取 hook 模板失败: {}", e)))?;
+    Ok(serde_json::from_str(&content).unwrap_or_else(|_| builtin_templates()))
+}
+
+fn save_templates(templates: &[GitHookTemplate]) -> AppResult<()> {
+    let config = storage::get_storage_config()?;
+    config.ensure_dirs()?;
+    let content = serde_json::to_string_pretty(templates)
+        .map_err(|e| AppError::from(format!("序列化 hook 模板失败: {}", e)))?;
+    fs::write(config.git_hook_templates_file(), content)
+        .map_err(|e| AppError::from(format!("保存 hook 模板失败: {}", e)))?;
+    Ok(())
+}
+
+fn validate_hook_name(name: &str) -> AppResult<()> {
+    if KNOWN_HOOKS.contains(&name) {
+        Ok(())
+    } else {
+        Err(AppError::invalid(format!("不支持的 hook: {}", name)))
+    }
+}
+
+fn hooks_dir(repo: &str) -> AppResult<PathBuf> {
+    let rel = run_git_command(repo, &["rev-parse", "--git-path", "hooks"])?;
+    let dir = PathBuf::from(&rel);
+    Ok(if dir.is_absolute() {
+        dir
+    } else {
+        Path::new(repo).join(dir)
+    })
+}
+
+fn template_id_of(path: &Path) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    content
+        .lines()
+        .take(5)
+        .find_map(|l| l.strip_prefix(TEMPLATE_MARKER))
+        .map(|id| id.trim().to_string())
+}
+
+#[cfg(unix)]
+/// 备份文件名：`<hook>.backup`，已存在时依次尝试 `<hook>.backup.1`、`<hook>.backup.2`…
+fn backup_path(dir: &Path, hook: &str) -> PathBuf {
+    let first = dir.join(format!("{}.backup", hook));
+    if !first.exists() {
+        return first;
+    }
+    (1..)
+        .map(|n| dir.join(format!("{}.backup.{}", hook, n)))
+        .find(|p| !p.exists())
+        .unwrap_or(first)
+}
+
+fn make_executable(path: &Path) -> AppResult<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+        .map_err(|e| AppError::from(format!("设置执行权限失败: {}", e)))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> AppResult<()> {
+    Ok(())
+}
+
+/// 在 shebang 之后插入模板标记
+fn with_marker(content: &str, template_id: &str) -> String {
+    let marker = format!("{} {}", TEMPLATE_MARKER, template_id);
+    match content.split_once('\n') {
+        Some((first, rest)) if first.starts_with("#!") => {
+            format!("{}\n{}\n{}", first, marker, rest)
+        }
+        _ => format!("#!/bin/sh\n{}\n{}", marker, content),
+    }
+}
+
+/// 列出仓库的 hooks（所有已知 hook，标注是否安装/启用）
+#[tauri::command]
+#[specta::specta]
+pub async fn list_git_hooks(path: String) -> AppResult<Vec<GitHookInfo>> {
+    let dir = hooks_dir(&path)?;
+    Ok(KNOWN_HOOKS
+        .iter()
+        .map(|name| {
+            let active = dir.join(name);
+            let disabled = dir.join(format!("{}{}", name, DISABLED_SUFFIX));
+            let (installed, enabled, file) = if active.is_file() {
+                (true, true, Some(active))
+            } else if disabled.is_file() {
+                (true, false, Some(disabled))
+            } else {
+                (false, false, None)
+            };
+            GitHookInfo {
+                name: name.to_string(),
+                installed,
+                enabled,
+                template_id: file.as_deref().and_then(template_id_of),
+            }
+        })
+        .collect())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_git_hook_templates() -> AppResult<Vec<GitHookTemplate>> {
+    load_templates()
+}
+
+/// 新增或更新模板（id 为空时新建）
+#[tauri::command]
+#[specta::specta]
+pub async fn save_git_hook_template(template: GitHookTemplate) -> AppResult<GitHookTemplate> {
+    validate_hook_name(&template.hook)?;
+    if template.name.trim().is_empty() {
+        return Err(AppError::invalid("模板名称不能为空"));
+    }
+
+    if builtin_templates().iter().any(|t| t.id == template.id) {
+        return Err(AppError::invalid("内置模板不能修改，请另存为新模板"));
+    }
+
+    let mut templates = load_templates()?;
+    let mut template = template;
+    template.builtin = false;
+    if template.id.is_empty() {
+        template.id = generate_id();
+        templates.push(template.clone());
+    } else if let Some(existing) = templates.iter_mut().find(|t| t.id == template.id) {
+        *existing = template.clone();
+    } else {
+        templates.push(template.clone());
+    }
+    save_templates(&templates)?;
+    Ok(template)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_git_hook_template(id: String) -> AppResult<()> {
+    let mut templates = load_templates()?;
+    templates.retain(|t| t.id != id);
+    save_templates(&templates)
+}
+
+/// 从模板安装 hook。已有非本应用管理的脚本时需 overwrite=true，原脚本备份为 `<hook>.backup`
+/// （已有同名备份时追加序号，不覆盖旧备份）
+#[tauri::command]
+#[specta::specta]
+pub async fn install_git_hook(
+    path: String,
+    template_id: String,
+    overwrite: Option<bool>,
+) -> AppResult<GitHookInfo> {
+    let template = load_templates()?
+        .into_iter()
+        .find(|t| t.id == template_id)
+        .ok_or_else(|| AppError::invalid("hook 模板不存在"))?;
+    validate_hook_name(&template.hook)?;
+
+    let dir = hooks_dir(&path)?;
+    fs::create_dir_all(&dir).map_err(|e| AppError::from(format!("创建 hooks 目录失败: {}", e)))?;
+
+    let target = dir.join(&template.hook);
+    let disabled = dir.join(format!("{}{}", template.hook, DISABLED_SUFFIX));
+    for existing in [&target, &disabled] {
+        if !existing.is_file() || template_id_of(existing).is_some() {
+            continue;
+        }
+        if !overwrite.unwrap_or(false) {
+            return Err(AppError::invalid(format!(
+                "{} 已存在自定义脚本，确认覆盖后会备份为 {}.backup",
+                template.hook, template.hook
+            )));
+        }
+        fs::rename(existing, backup_path(&dir, &template.hook))
+            .map_err(|e| AppError::from(format!("备份原 hook 失败: {}", e)))?;
+    }
+    let _ = fs::remove_file(&disabled);
+
+    fs::write(&target, with_marker(&template.content, &template.id))
+        .map_err(|e| AppError::from(format!("写入 hook 失败: {}", e)))?;
+    make_executable(&target)?;
+
+    Ok(GitHookInfo {
+        name: template.hook,
+        installed: true,
+        enabled: true,
+        template_id: Some(template.id),
+    })
+}
+
+/// 删除本应用安装的 hook 脚本（启用与停用状态的都会删除）；不带模板标记的自定义脚本不删除
+#[tauri::command]
+#[specta::specta]
+pub async fn uninstall_git_hook(path: String, hook: String) -> AppResult<()> {
+    validate_hook_name(&hook)?;
+    let dir = hooks_dir(&path)?;
+    let files: Vec<PathBuf> = [
+        dir.join(&hook),
+        dir.join(format!("{}{}", hook, DISABLED_SUFFIX)),
+    ]
+    .into_iter()
+    .filter(|f| f.is_file())
+    .collect();
+    if files.iter().any(|f| template_id_of(f).is_none()) {
+        return Err(AppError::invalid(format!(
+            "{} 不是由 CodeShelf 安装的脚本，请手动删除",
+            hook
+        )));
+    }
+    for file in files {
+        fs::remove_file(&file).map_err(|e| AppError::from(format!("删除 hook 失败: {}", e)))?;
+    }
+    Ok(())
+}
+
+/// 启用/停用 hook（通过 `.disabled` 后缀切换）
+#[tauri::command]
+#[specta::specta]
+pub async fn set_git_hook_enabled(path: String, hook: String, enabled: bool) -> AppResult<()> {
+    validate_hook_name(&hook)?;
+    let dir = hooks_dir(&path)?;
+    let active = dir.join(&hook);
+    let disabled = dir.join(format!("{}{}", hook, DISABLED_SUFFIX));
+
+    let (from, to) = if enabled {
+        (disabled, active)
+    } else {
+        (active, disabled)
+    };
+    if to.is_file() {
+        return Ok(());
+    }
+    if !from.is_file() {
+        return Err(AppError::invalid(format!("{} 未安装", hook)));
+    }
+    fs::rename(&from, &to).map_err(|e| AppError::from(format!("切换 hook 状态失败: {}", e)))?;
+    if enabled {
+        make_executable(&to)?;
+    }
+    Ok(())
+}
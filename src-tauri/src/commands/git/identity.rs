@@ -0,0 +1,175 @@
+// 提交身份管理：查看 / 设置仓库级 user.name、user.email，套用设置中的身份模板，
+// 并按远程地址规则检查身份是否正确（如工作仓库用了个人邮箱）。
+//
+// 规则匹配：远程地址统一成 "host/owner/repo" 形式，模板规则为其前缀（"github.com/my-company"）
+// 或主机名（"gitlab.corp.com"、"*.corp.com"）；多个模板命中时取规则最长（最具体）的那个。
+// git_commit 在开启 git_identity_guard 时调用 identity_mismatch，身份不符则拒绝提交。
+
+use crate::commands::settings::load_app_settings;
+use crate::error::{AppError, AppResult};
+use crate::storage::GitIdentityProfile;
+
+use super::{lock_repo, run_git_command, GitIdentity, GitIdentityCheck};
+
+/// 远程地址统一为小写的 "host/path"：去掉协议、用户名、端口与 .git 后缀
+fn normalize_remote(url: &str) -> String {
+    let url = url.trim().to_lowercase();
+    let rest = match url.split_once("://") {
+        Some((_, rest)) => rest.to_string(),
+        // scp 风格 git@host:owner/repo
+        None => url.replacen(':', "/", 1),
+    };
+    let rest = rest.rsplit_once('@').map(|(_, r)| r).unwrap_or(&rest);
+    let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+    let host = host.split(':').next().unwrap_or(host);
+    format!(
+        "{}/{}",
+        host,
+        path.trim_end_matches('/').trim_end_matches(".git")
+    )
+}
+
+/// 规则命中时返回规则长度（越长越具体）
+fn pattern_score(pattern: &str, remote: &str) -> Option<usize> {
+    let pattern = pattern.trim().trim_end_matches('/').to_lowercase();
+    if pattern.is_empty() {
+        return None;
+    }
+    let host = remote.split('/').next().unwrap_or(remote);
+    let hit = if let Some(suffix) = pattern.strip_prefix("*.") {
+        host == suffix || host.ends_with(&format!(".{}", suffix))
+    } else {
+        remote == pattern || remote.starts_with(&format!("{}/", pattern))
+    };
+    hit.then_some(pattern.len())
+}
+
+fn remote_urls(path: &str) -> Vec<String> {
+    run_git_command(path, &["config", "--get-regexp", r"^remote\..*\.url$"])
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| line.split_once(' ').map(|(_, url)| normalize_remote(url)))
+        .collect()
+}
+
+fn config_value(path: &str, args: &[&str]) -> Option<String> {
+    run_git_command(path, args).ok().filter(|v| !v.is_empty())
+}
+
+fn read_identity(path: &str) -> GitIdentity {
+    let local_name = config_value(path, &["config", "--local", "user.name"]);
+    let local_email = config_value(path, &["config", "--local", "user.email"]);
+    let user_name = config_value(path, &["config", "user.name"]);
+    let email = config_value(path, &["config", "user.email"]);
+    let source = if local_name.is_some() || local_email.is_some() {
+        "local"
+    } else if user_name.is_some() || email.is_some() {
+        "global"
+    } else {
+        "none"
+    };
+    GitIdentity {
+        user_name,
+        email,
+        source: source.to_string(),
+    }
+}
+
+/// 按远程地址匹配应使用的身份模板
+fn expected_profile(path: &str, profiles: &[GitIdentityProfile]) -> Option<GitIdentityProfile> {
+    let remotes = remote_urls(path);
+    profiles
+        .iter()
+        .filter_map(|profile| {
+            let score = profile
+                .remote_patterns
+                .iter()
+                .flat_map(|p| remotes.iter().filter_map(move |r| pattern_score(p, r)))
+                .max()?;
+            Some((score, profile))
+        })
+        .max_by_key(|(score, _)| *score)
+        .map(|(_, profile)| profile.clone())
+}
+
+fn check_identity(path: &str, profiles: &[GitIdentityProfile]) -> GitIdentityCheck {
+    let current = read_identity(path);
+    let expected = expected_profile(path, profiles);
+    let reason = expected.as_ref().and_then(|profile| {
+        let email = current.email.as_deref().unwrap_or("");
+        (!email.eq_ignore_ascii_case(profile.email.trim())).then(|| {
+            format!(
+                "远程地址匹配「{}」身份，应使用 {}，当前为 {}",
+                profile.name,
+                profile.email,
+                if email.is_empty() { "未配置" } else { email }
+            )
+        })
+    });
+    GitIdentityCheck {
+        current,
+        expected,
+        mismatch: reason.is_some(),
+        reason,
+    }
+}
+
+/// 开启提交前检查且身份不符时返回原因（供 git_commit 使用）
+pub(super) async fn identity_mismatch(path: &str) -> Option<String> {
+    let settings = load_app_settings().await.ok()?;
+    if !settings.git_identity_guard || settings.git_identities.is_empty() {
+        return None;
+    }
+    check_identity(path, &settings.git_identities).reason
+}
+
+/// 查看仓库当前的提交身份，并按身份模板检查是否正确
+#[tauri::command]
+#[specta::specta]
+pub async fn get_git_identity(path: String) -> AppResult<GitIdentityCheck> {
+    let settings = load_app_settings().await?;
+    Ok(check_identity(&path, &settings.git_identities))
+}
+
+/// 设置仓库级提交身份（写入 .git/config）；传空字符串时删除该项，改为继承全局配置
+#[tauri::command]
+#[specta::specta]
+pub async fn set_git_identity(
+    path: String,
+    user_name: Option<String>,
+    email: Option<String>,
+) -> AppResult<GitIdentity> {
+    let _guard = lock_repo(&path, "设置提交身份").await;
+    for (key, value) in [("user.name", user_name), ("user.email", email)] {
+        match value.as_deref().map(str::trim) {
+            None => {}
+            Some("") => {
+                // 未设置时 --unset 返回非零，忽略
+                let _ = run_git_command(&path, &["config", "--local", "--unset", key]);
+            }
+            Some(v) => {
+                if key == "user.email" && !v.contains('@') {
+                    return Err(AppError::invalid(format!("邮箱格式不正确: {}", v)));
+                }
+                run_git_command(&path, &["config", "--local", key, v])?;
+            }
+        }
+    }
+    Ok(read_identity(&path))
+}
+
+/// 把设置中的身份模板应用到仓库
+#[tauri::command]
+#[specta::specta]
+pub async fn apply_git_identity_profile(
+    path: String,
+    profile_id: String,
+) -> AppResult<GitIdentity> {
+    let settings = load_app_settings().await?;
+    let profile = settings
+        .git_identities
+        .into_iter()
+        .find(|p| p.id == profile_id)
+        .ok_or_else(|| AppError::invalid("身份模板不存在"))?;
+    set_git_identity(path, Some(profile.user_name), Some(profile.email)).await
+}
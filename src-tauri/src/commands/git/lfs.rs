@@ -0,0 +1,215 @@
+// Git LFS：检测仓库是否使用 LFS、统计对象数量与大小、pull / fetch / prune
+//
+// 是否使用 LFS 以 .gitattributes 中的 `filter=lfs` 判断，不依赖本机安装 git-lfs；
+// 对象统计来自 `git lfs ls-files --json`（git-lfs 3.0+），旧版本退回纯文本输出，只统计数量。
+// 未检出（工作区仍是指针文件）的对象 git status 显示为干净，需单独提示；这个数量随每次状态刷新
+// 返回，按仓库缓存 MISSING_CACHE_TTL，拉取 LFS 或查看 LFS 详情时更新。
+
+use crate::error::{AppError, AppResult};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::{lock_repo, run_git_command, LfsInfo};
+
+/// 未检出数量的缓存有效期
+const MISSING_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// 仓库路径 → (计算时间, 未检出数量)
+static MISSING_CACHE: Lazy<Mutex<HashMap<String, (Instant, u32)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn cache_missing(path: &str, missing: Option<u32>) {
+    let mut cache = MISSING_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    cache.retain(|_, (at, _)| at.elapsed() < MISSING_CACHE_TTL);
+    match missing {
+        Some(count) => cache.insert(path.to_string(), (Instant::now(), count)),
+        None => cache.remove(path),
+    };
+}
+
+/// 仓库内所有 .gitattributes 中声明为 LFS 的模式
+fn lfs_patterns(path: &str) -> Vec<String> {
+    let files = run_git_command(
+        path,
+        &["ls-files", "--", ".gitattributes", "*/.gitattributes"],
+    )
+    .unwrap_or_default();
+
+    let mut patterns = Vec::new();
+    for file in files.lines().filter(|l| !l.is_empty()) {
+        let dir = Path::new(file)
+            .parent()
+            .and_then(|p| p.to_str())
+            .unwrap_or("");
+        let Ok(content) = std::fs::read_to_string(Path::new(path).join(file)) else {
+            continue;
+        };
+        for line in content.lines() {
+            let mut parts = line.split_whitespace();
+            let Some(pattern) = parts.next() else {
+                continue;
+            };
+            if pattern.starts_with('#') || !parts.any(|attr| attr == "filter=lfs") {
+                continue;
+            }
+            patterns.push(if dir.is_empty() {
+                pattern.to_string()
+            } else {
+                format!("{}/{}", dir, pattern)
+            });
+        }
+    }
+    patterns
+}
+
+/// 本机 git-lfs 版本，未安装时为 None
+fn lfs_version(path: &str) -> Option<String> {
+    run_git_command(path, &["lfs", "version"]).ok()
+}
+
+/// (对象数, 总大小, 未检出数, 未检出大小)
+fn lfs_objects(path: &str) -> AppResult<(u32, u64, u32, u64)> {
+    if let Ok(output) = run_git_command(path, &["lfs", "ls-files", "--json"]) {
+        let value: serde_json::Value = serde_json::from_str(&output)?;
+        let files = value
+            .get("files")
+            .and_then(|f| f.as_array())
+            .cloned()
+            .unwrap_or_default();
+        let mut stats = (0u32, 0u64, 0u32, 0u64);
+        for file in files {
+            let size = file.get("size").and_then(|s| s.as_u64()).unwrap_or(0);
+            stats.0 += 1;
+            stats.1 += size;
+            if !file
+                .get("checkout")
+                .and_then(|c| c.as_bool())
+                .unwrap_or(true)
+            {
+                stats.2 += 1;
+                stats.3 += size;
+            }
+        }
+        return Ok(stats);
+    }
+
+    // 旧版 git-lfs：`<oid> <*|-> <path>`，`-` 表示只有指针
+    let output = run_git_command(path, &["lfs", "ls-files"])?;
+    let mut stats = (0u32, 0u64, 0u32, 0u64);
+    for line in output.lines() {
+        let mut parts = line.split_whitespace();
+        let (Some(_oid), Some(mark)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        stats.0 += 1;
+        if mark == "-" {
+            stats.2 += 1;
+        }
+    }
+    Ok(stats)
+}
+
+/// 工作区中未检出的 LFS 指针文件数量，未使用 LFS 时为 0（供 get_git_status 使用，结果有缓存）
+pub(super) fn lfs_missing_count(path: &str) -> u32 {
+    if let Some((at, count)) = MISSING_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(path)
+    {
+        if at.elapsed() < MISSING_CACHE_TTL {
+            return *count;
+        }
+    }
+    let count = if lfs_patterns(path).is_empty() {
+        0
+    } else {
+        lfs_objects(path)
+            .map(|(_, _, missing, _)| missing)
+            .unwrap_or(0)
+    };
+    cache_missing(path, Some(count));
+    count
+}
+
+fn ensure_lfs(path: &str) -> AppResult<()> {
+    if lfs_version(path).is_none() {
+        return Err(AppError::invalid(
+            "未安装 git-lfs，请先安装后执行 git lfs install",
+        ));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_lfs_info(path: String) -> AppResult<LfsInfo> {
+    let tracked_patterns = lfs_patterns(&path);
+    let version = lfs_version(&path);
+
+    let mut info = LfsInfo {
+        uses_lfs: !tracked_patterns.is_empty(),
+        installed: version.is_some(),
+        version,
+        tracked_patterns,
+        object_count: 0,
+        total_size: 0,
+        missing_count: 0,
+        missing_size: 0,
+    };
+    if info.uses_lfs && info.installed {
+        let (count, size, missing, missing_size) = lfs_objects(&path)?;
+        info.object_count = count;
+        info.total_size = size;
+        info.missing_count = missing;
+        info.missing_size = missing_size;
+    }
+    cache_missing(&path, Some(info.missing_count));
+    Ok(info)
+}
+
+/// 下载并检出 LFS 对象；include 为逗号分隔的路径模式，只拉取匹配的文件
+#[tauri::command]
+#[specta::specta]
+pub async fn git_lfs_pull(path: String, include: Option<String>) -> AppResult<String> {
+    ensure_lfs(&path)?;
+    let _guard = lock_repo(&path, "拉取 LFS 文件").await;
+    let mut args = vec!["lfs", "pull"];
+    if let Some(include) = include.as_deref().filter(|s| !s.trim().is_empty()) {
+        args.extend(["--include", include]);
+    }
+    let result = run_git_command(&path, &args);
+    cache_missing(&path, None);
+    result
+}
+
+/// 只下载 LFS 对象到本地缓存，不改动工作区；all 时下载所有引用的对象
+#[tauri::command]
+#[specta::specta]
+pub async fn git_lfs_fetch(path: String, remote: Option<String>, all: bool) -> AppResult<String> {
+    ensure_lfs(&path)?;
+    let _guard = lock_repo(&path, "下载 LFS 对象").await;
+    let mut args = vec!["lfs", "fetch"];
+    if all {
+        args.push("--all");
+    }
+    if let Some(remote) = remote.as_deref().filter(|s| !s.trim().is_empty()) {
+        args.push(remote);
+    }
+    run_git_command(&path, &args)
+}
+
+/// 清理本地不再需要的旧 LFS 对象；dry_run 时只报告将删除的内容
+#[tauri::command]
+#[specta::specta]
+pub async fn git_lfs_prune(path: String, dry_run: bool) -> AppResult<String> {
+    ensure_lfs(&path)?;
+    let _guard = lock_repo(&path, "清理 LFS 对象").await;
+    let mut args = vec!["lfs", "prune", "--verbose"];
+    if dry_run {
+        args.push("--dry-run");
+    }
+    run_git_command(&path, &args)
+}
@@ -1,32 +1,43 @@
 // Git 工具模块：类型、共享 helpers 与子模块声明
 
+use crate::commands::operations::Operation;
 use crate::error::AppResult;
+use crate::path_compat::{decode_text, git_in};
+pub(crate) use queue::lock_repo;
 use serde::{Deserialize, Serialize};
-use std::process::Command;
-
-#[cfg(target_os = "windows")]
-use std::os::windows::process::CommandExt;
 
 mod branches;
 mod clone;
 mod commits;
+mod diff;
+mod hooks;
+mod identity;
+mod lfs;
+mod owners;
+mod queue;
+mod remote_rewrite;
 mod remotes;
 mod scan;
+mod signing;
 mod staging;
 mod status;
 
 pub use branches::*;
 pub use clone::*;
 pub use commits::*;
+pub use diff::*;
+pub use hooks::*;
+pub use identity::*;
+pub use lfs::*;
+pub use owners::*;
+pub use queue::*;
+pub use remote_rewrite::*;
 pub use remotes::*;
 pub use scan::*;
+pub use signing::*;
 pub use staging::*;
 pub use status::*;
 
-/// Windows: CREATE_NO_WINDOW flag to hide console window
-#[cfg(target_os = "windows")]
-pub(super) const CREATE_NO_WINDOW: u32 = 0x08000000;
-
 #[derive(Debug, Serialize, Deserialize, specta::Type)]
 pub struct GitStatus {
     pub branch: String,
@@ -37,6 +48,32 @@ pub struct GitStatus {
     pub conflicted: Vec<String>,
     pub ahead: u32,
     pub behind: u32,
+    /// 使用 LFS 的仓库中工作区仍是指针文件（未拉取）的数量；按仓库缓存 1 分钟，拉取 LFS 后更新
+    #[serde(default)]
+    pub lfs_missing: u32,
+    /// HEAD 未指向分支（此时 branch 为短哈希，变基中为原分支名）
+    #[serde(default)]
+    pub detached: bool,
+    /// 进行中的合并 / 变基 / 拣选等操作
+    #[serde(default)]
+    pub operation: Option<RepoOperation>,
+}
+
+/// 仓库中未完成的操作，从 .git 下的状态文件识别
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoOperation {
+    /// "merge" | "rebase" | "am" | "cherryPick" | "revert" | "bisect"
+    pub kind: String,
+    /// 正在合并 / 拣选 / 回滚的提交，变基时为 onto
+    pub target: Option<String>,
+    /// target 的可读名称（git name-rev）
+    pub target_name: Option<String>,
+    /// 变基前所在的分支
+    pub head_name: Option<String>,
+    /// 变基 / am 的进度
+    pub step: Option<u32>,
+    pub total: Option<u32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, specta::Type)]
@@ -70,6 +107,26 @@ pub struct CommitInfo {
     pub parent_hashes: Option<Vec<String>>,
 }
 
+/// search_commits 的附加过滤条件
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitSearchOptions {
+    /// 起始日期，git 可识别的格式（如 "2024-01-01"、"2 weeks ago"）
+    pub since: Option<String>,
+    pub until: Option<String>,
+    /// 搜索所有分支与标签（--all）
+    #[serde(default)]
+    pub all_branches: bool,
+    /// 只看改动了这些路径的提交，支持 pathspec 通配（如 "src/**"）
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// 内容搜索：增删了该字符串的提交（-S）
+    pub pickaxe: Option<String>,
+    /// pickaxe 按正则匹配改动行（-G）
+    #[serde(default)]
+    pub pickaxe_regex: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct CommitFileChange {
     pub insertions: u32,
@@ -93,10 +150,344 @@ pub struct RemoteInfo {
     pub push_url: Option<String>,
 }
 
+/// compare_branches 中单个文件的改动
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchDiffFile {
+    pub path: String,
+    /// 重命名 / 复制前的路径
+    pub old_path: Option<String>,
+    /// git 的状态字母：A / M / D / R / C / T
+    pub status: String,
+    pub insertions: u32,
+    pub deletions: u32,
+    pub binary: bool,
+}
+
+/// 两个分支的对比结果（head 相对 base，与 PR 对比页一致）
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchComparison {
+    pub base: String,
+    pub head: String,
+    pub merge_base: Option<String>,
+    /// head 独有的提交数
+    pub ahead: u32,
+    /// base 独有的提交数
+    pub behind: u32,
+    pub ahead_commits: Vec<CommitInfo>,
+    pub behind_commits: Vec<CommitInfo>,
+    /// 提交数超过上限时列表被截断
+    pub truncated: bool,
+    /// 从 merge-base 到 head 的文件改动
+    pub files: Vec<BranchDiffFile>,
+    pub insertions: u32,
+    pub deletions: u32,
+}
+
+/// sync_to_remote 预览：单个分支推送到目标远程后的效果
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncBranchPreview {
+    pub branch: String,
+    /// "create" | "fastForward" | "forceUpdate" | "upToDate"
+    pub action: String,
+    pub source_sha: String,
+    pub target_sha: Option<String>,
+    /// 将推送到目标的提交数
+    pub ahead: Option<u32>,
+    /// 强制更新时目标上被丢弃的提交数；本地没有目标提交对象时为 None
+    pub discarded: Option<u32>,
+    /// 提交范围（target..source），可用于 git log 查看
+    pub range: Option<String>,
+    /// 未开启 force 时该分支会被拒绝
+    pub requires_force: bool,
+    pub is_default: bool,
+}
+
+/// 仓库的 Git LFS 使用情况
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LfsInfo {
+    /// .gitattributes 中声明了 filter=lfs
+    pub uses_lfs: bool,
+    /// 本机是否安装 git-lfs
+    pub installed: bool,
+    pub version: Option<String>,
+    pub tracked_patterns: Vec<String>,
+    pub object_count: u32,
+    /// 字节；旧版 git-lfs 无法取得大小时为 0
+    pub total_size: u64,
+    /// 工作区仍是指针文件的对象
+    pub missing_count: u32,
+    pub missing_size: u64,
+}
+
+/// 仓库当前生效的提交身份
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GitIdentity {
+    pub user_name: Option<String>,
+    pub email: Option<String>,
+    /// "local" 仓库配置 | "global" 继承全局配置 | "none" 未配置
+    pub source: String,
+}
+
+/// 提交身份检查结果
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GitIdentityCheck {
+    pub current: GitIdentity,
+    /// 按远程地址规则匹配到的身份模板
+    pub expected: Option<crate::storage::GitIdentityProfile>,
+    pub mismatch: bool,
+    /// 不一致的原因说明
+    pub reason: Option<String>,
+}
+
+/// 可用于签名提交的密钥
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SigningKey {
+    /// "openpgp" | "ssh"（对应 gpg.format）
+    pub format: String,
+    /// 写入 user.signingkey 的值：GPG 长 ID，或 SSH 公钥文件路径
+    pub key: String,
+    /// GPG 的 uid / SSH 公钥注释
+    pub label: Option<String>,
+    pub fingerprint: Option<String>,
+    /// GPG 密钥过期日期（YYYY-MM-DD）
+    pub expires: Option<String>,
+}
+
+/// 当前生效的签名配置
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SigningConfig {
+    /// "local" 仓库配置 | "global" 全局配置
+    pub scope: String,
+    pub format: String,
+    pub signing_key: Option<String>,
+    pub sign_commits: bool,
+    pub sign_tags: bool,
+    pub allowed_signers_file: Option<String>,
+}
+
+/// 签名验证结果（在临时仓库中做一次测试提交）
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SigningTestResult {
+    /// 提交是否带上了签名
+    pub signed: bool,
+    /// git verify-commit 是否通过
+    pub verified: bool,
+    pub output: String,
+}
+
+/// CODEOWNERS 中的一条规则
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeOwnerRule {
+    pub pattern: String,
+    pub owners: Vec<String>,
+    /// 在 CODEOWNERS 中的行号
+    pub line: u32,
+}
+
+/// 根据 blame 统计的贡献者
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnerContributor {
+    pub name: String,
+    pub email: String,
+    /// 当前代码中归属此人的行数
+    pub lines: u32,
+    pub percent: f64,
+}
+
+/// 目录的归属信息
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryOwnership {
+    /// 相对仓库根目录，根目录下的文件为 "."
+    pub path: String,
+    /// CODEOWNERS 声明的负责人
+    pub owners: Vec<String>,
+    /// blame 行数最多的贡献者（按行数降序）
+    pub contributors: Vec<OwnerContributor>,
+    pub file_count: u32,
+    /// 参与 blame 的文件数（每个目录抽样）
+    pub sampled_files: u32,
+}
+
+/// 代码归属图
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeOwnersReport {
+    /// 找到的 CODEOWNERS 文件（相对路径）
+    pub codeowners_file: Option<String>,
+    pub rules: Vec<CodeOwnerRule>,
+    pub directories: Vec<DirectoryOwnership>,
+}
+
+/// 一条远程地址改写（预览或执行结果）
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteRewrite {
+    pub project_path: String,
+    pub remote: String,
+    /// 是否为 pushurl（否则是 url）
+    pub push: bool,
+    pub old_url: String,
+    pub new_url: String,
+    /// 预览时为 false
+    pub applied: bool,
+    pub error: Option<String>,
+}
+
+/// 仓库的写操作队列
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GitQueueState {
+    pub path: String,
+    /// 正在执行的操作
+    pub running: Option<String>,
+    /// 排队中的操作，按先后顺序（第 N 个即排在第 N 位）
+    pub waiting: Vec<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, specta::Type)]
 pub struct GitRepo {
     pub path: String,
     pub name: String,
+    /// "normal" | "worktree" | "bare"
+    #[serde(default)]
+    pub kind: String,
+}
+
+/// scan_directory 的过滤选项
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanOptions {
+    /// 只收录相对扫描目录匹配这些模式的仓库（gitignore 风格，如 "work/**"）
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// 跳过匹配这些模式的目录，如 "node_modules"、"archive/**"
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    #[serde(default)]
+    pub ignore_case: bool,
+    /// 进入符号链接指向的目录
+    #[serde(default = "default_true")]
+    pub follow_symlinks: bool,
+    /// 找到这么多仓库后停止扫描
+    pub max_repos: Option<u32>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: Vec::new(),
+            ignore_case: false,
+            follow_symlinks: true,
+            max_repos: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHookInfo {
+    /// hook 名，如 "pre-commit"
+    pub name: String,
+    /// 是否存在脚本（启用或停用都算）
+    pub installed: bool,
+    pub enabled: bool,
+    /// 由模板库安装时记录的模板 id
+    pub template_id: Option<String>,
+}
+
+/// Git hook 脚本模板（保存在应用数据目录）
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHookTemplate {
+    pub id: String,
+    pub name: String,
+    /// 目标 hook 名
+    pub hook: String,
+    #[serde(default)]
+    pub description: String,
+    pub content: String,
+    #[serde(default)]
+    pub builtin: bool,
+}
+
+/// 比较结果（目录/文件比较与版本比较共用）
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffResult {
+    pub files: Vec<FileDiff>,
+    pub added: u32,
+    pub removed: u32,
+    /// 修改 + 重命名
+    pub changed: u32,
+    pub insertions: u32,
+    pub deletions: u32,
+    /// 行数超过上限时后续文件只保留文件级信息（hunks 为空）
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FileDiff {
+    /// "added" | "removed" | "modified" | "renamed"
+    pub status: String,
+    /// 相对比较根目录的路径（新增文件为 None）
+    pub old_path: Option<String>,
+    /// 删除文件为 None
+    pub new_path: Option<String>,
+    pub binary: bool,
+    pub insertions: u32,
+    pub deletions: u32,
+    pub hunks: Vec<DiffHunk>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffHunk {
+    /// 原始 `@@ -a,b +c,d @@ ...` 行
+    pub header: String,
+    pub old_start: u32,
+    pub old_lines: u32,
+    pub new_start: u32,
+    pub new_lines: u32,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffLine {
+    /// "context" | "add" | "remove"
+    pub kind: String,
+    pub content: String,
+    pub old_line: Option<u32>,
+    pub new_line: Option<u32>,
+    /// 词级差异片段，仅 diff_text 的 word 模式填充
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segments: Option<Vec<DiffSegment>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffSegment {
+    /// "equal" | "add" | "remove"
+    pub kind: String,
+    pub text: String,
 }
 
 #[derive(Clone, serde::Serialize, specta::Type)]
@@ -107,27 +498,32 @@ pub struct GitCloneProgress {
 }
 
 /// 执行 `git -C <path> <args>` 并返回 stdout（trim 后），失败返回 stderr
-pub(super) fn run_git_command(path: &str, args: &[&str]) -> AppResult<String> {
-    #[cfg(target_os = "windows")]
-    let output = Command::new("git")
-        .args(["-C", path])
+pub(crate) fn run_git_command(path: &str, args: &[&str]) -> AppResult<String> {
+    let output = git_in(path)
         .args(args)
-        .creation_flags(CREATE_NO_WINDOW)
         .output()
         .map_err(|e| crate::error::AppError::from(e.to_string()))?;
 
-    #[cfg(not(target_os = "windows"))]
-    let output = Command::new("git")
-        .args(["-C", path])
-        .args(args)
-        .output()
-        .map_err(|e| crate::error::AppError::from(e.to_string()))?;
+    if output.status.success() {
+        Ok(decode_text(&output.stdout).trim().to_string())
+    } else {
+        Err(crate::error::AppError::from(
+            decode_text(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
+/// 与 run_git_command 相同，但子进程登记到 op，可被 cancel_operation 或超时结束
+pub(super) fn run_git_operation(path: &str, args: &[&str], op: &Operation) -> AppResult<String> {
+    let mut cmd = git_in(path);
+    cmd.args(args);
 
+    let output = op.output(&mut cmd)?;
     if output.status.success() {
-        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        Ok(decode_text(&output.stdout).trim().to_string())
     } else {
         Err(crate::error::AppError::from(
-            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            decode_text(&output.stderr).trim().to_string(),
         ))
     }
 }
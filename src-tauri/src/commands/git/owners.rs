@@ -0,0 +1,212 @@
+// 代码归属：CODEOWNERS 声明 + blame 统计的主要贡献者，按目录汇总
+//
+// CODEOWNERS 按 GitHub / GitLab 的位置查找（根目录、.github/、.gitlab/、docs/），
+// 模式语义同 gitignore，最后一条匹配的规则生效；GitLab 的 [Section] 行忽略。
+// blame 成本较高：每个目录按文件大小抽样 MAX_BLAME_FILES_PER_DIR 个文件，跳过过大的文件。
+
+use crate::error::{AppError, AppResult};
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use super::{
+    run_git_command, CodeOwnerRule, CodeOwnersReport, DirectoryOwnership, OwnerContributor,
+};
+
+/// 每个目录参与 blame 的文件数上限
+const MAX_BLAME_FILES_PER_DIR: usize = 15;
+
+/// 超过此大小的文件不做 blame（多为生成文件 / 数据文件）
+const MAX_BLAME_FILE_SIZE: u64 = 512 * 1024;
+
+/// 每个目录返回的贡献者数量
+const TOP_CONTRIBUTORS: usize = 5;
+
+const CODEOWNERS_PATHS: &[&str] = &[
+    "CODEOWNERS",
+    ".github/CODEOWNERS",
+    ".gitlab/CODEOWNERS",
+    "docs/CODEOWNERS",
+];
+
+fn parse_codeowners(content: &str) -> Vec<CodeOwnerRule> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with('[') {
+                return None;
+            }
+            let mut parts = line.split_whitespace();
+            let pattern = parts.next()?.to_string();
+            let owners = parts
+                .take_while(|p| !p.starts_with('#'))
+                .map(String::from)
+                .collect();
+            Some(CodeOwnerRule {
+                pattern,
+                owners,
+                line: index as u32 + 1,
+            })
+        })
+        .collect()
+}
+
+/// gitignore 风格的模式转为正则
+fn pattern_regex(pattern: &str) -> Option<Regex> {
+    let trimmed = pattern.trim_end_matches('/');
+    // 含中间斜杠或以斜杠开头的模式相对仓库根目录
+    let anchored = trimmed.starts_with('/') || trimmed.trim_start_matches('/').contains('/');
+    let body = trimmed.trim_start_matches('/');
+
+    let mut re = String::new();
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    re.push_str("(?:.*/)?");
+                } else {
+                    re.push_str(".*");
+                }
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    let prefix = if anchored { "^" } else { "(?:^|/)" };
+    Regex::new(&format!("{}{}(?:/.*)?$", prefix, re)).ok()
+}
+
+/// 目录适用的负责人：用目录下的一个虚拟文件路径匹配，最后一条匹配的规则生效
+fn owners_for_dir(dir: &str, rules: &[(Option<Regex>, &CodeOwnerRule)]) -> Vec<String> {
+    let probe = if dir == "." {
+        "__file__".to_string()
+    } else {
+        format!("{}/__file__", dir)
+    };
+    rules
+        .iter()
+        .rev()
+        .find(|(re, _)| re.as_ref().map(|r| r.is_match(&probe)).unwrap_or(false))
+        .map(|(_, rule)| rule.owners.clone())
+        .unwrap_or_default()
+}
+
+/// 文件所属目录（取前 depth 级），根目录文件为 "."
+fn dir_key(file: &str, depth: usize) -> String {
+    let segments: Vec<&str> = file.split('/').collect();
+    if segments.len() <= 1 {
+        return ".".to_string();
+    }
+    segments[..(segments.len() - 1).min(depth)].join("/")
+}
+
+/// blame 一个文件，累加 (name, email) -> 行数
+fn blame_file(path: &str, file: &str, counts: &mut HashMap<(String, String), u32>) {
+    let Ok(output) = run_git_command(
+        path,
+        &["blame", "--line-porcelain", "-w", "HEAD", "--", file],
+    ) else {
+        return;
+    };
+    let mut author = String::new();
+    for line in output.lines() {
+        if let Some(name) = line.strip_prefix("author ") {
+            author = name.to_string();
+        } else if let Some(mail) = line.strip_prefix("author-mail ") {
+            let email = mail.trim_matches(|c| c == '<' || c == '>').to_string();
+            *counts
+                .entry((std::mem::take(&mut author), email))
+                .or_insert(0) += 1;
+        }
+    }
+}
+
+fn build_report(path: &str, depth: usize) -> AppResult<CodeOwnersReport> {
+    let root = Path::new(path);
+    let codeowners_file = CODEOWNERS_PATHS
+        .iter()
+        .find(|p| root.join(p).is_file())
+        .map(|p| p.to_string());
+    let rules = match &codeowners_file {
+        Some(file) => parse_codeowners(&std::fs::read_to_string(root.join(file))?),
+        None => Vec::new(),
+    };
+    let compiled: Vec<(Option<Regex>, &CodeOwnerRule)> = rules
+        .iter()
+        .map(|r| (pattern_regex(&r.pattern), r))
+        .collect();
+
+    let files = run_git_command(path, &["ls-files", "-z"])?;
+    let mut by_dir: BTreeMap<String, Vec<(String, u64)>> = BTreeMap::new();
+    for file in files.split('\0').filter(|l| !l.is_empty()) {
+        let size = std::fs::metadata(root.join(file))
+            .map(|m| m.len())
+            .unwrap_or(0);
+        by_dir
+            .entry(dir_key(file, depth))
+            .or_default()
+            .push((file.to_string(), size));
+    }
+
+    let mut directories = Vec::new();
+    for (dir, mut files) in by_dir {
+        files.sort_by_key(|f| std::cmp::Reverse(f.1));
+        let sample: Vec<&String> = files
+            .iter()
+            .filter(|(_, size)| *size > 0 && *size <= MAX_BLAME_FILE_SIZE)
+            .take(MAX_BLAME_FILES_PER_DIR)
+            .map(|(f, _)| f)
+            .collect();
+
+        let mut counts = HashMap::new();
+        for file in &sample {
+            blame_file(path, file, &mut counts);
+        }
+        let total: u32 = counts.values().sum();
+        let mut contributors: Vec<OwnerContributor> = counts
+            .into_iter()
+            .map(|((name, email), lines)| OwnerContributor {
+                name,
+                email,
+                lines,
+                percent: if total > 0 {
+                    lines as f64 * 100.0 / total as f64
+                } else {
+                    0.0
+                },
+            })
+            .collect();
+        contributors.sort_by_key(|c| std::cmp::Reverse(c.lines));
+        contributors.truncate(TOP_CONTRIBUTORS);
+
+        directories.push(DirectoryOwnership {
+            owners: owners_for_dir(&dir, &compiled),
+            path: dir,
+            contributors,
+            file_count: files.len() as u32,
+            sampled_files: sample.len() as u32,
+        });
+    }
+
+    Ok(CodeOwnersReport {
+        codeowners_file,
+        rules,
+        directories,
+    })
+}
+
+/// 代码归属图；depth 为汇总的目录层级（默认 1，即顶层目录）
+#[tauri::command]
+#[specta::specta]
+pub async fn get_code_owners(path: String, depth: Option<u32>) -> AppResult<CodeOwnersReport> {
+    let depth = depth.unwrap_or(1).clamp(1, 4) as usize;
+    tokio::task::spawn_blocking(move || build_report(&path, depth))
+        .await
+        .map_err(|e| AppError::internal(e.to_string()))?
+}
@@ -0,0 +1,264 @@
+// 同一仓库的 git 写操作排队执行
+//
+// 状态刷新、提交、fetch 同时触发时，多个 git 进程争抢 .git/index.lock 会报
+// "Unable to create index.lock: File exists"。写操作（暂存、提交、切换分支、拉取推送等）
+// 先取得该仓库的锁再执行，按先来后到排队；只读命令不排队，git status 带 --no-optional-locks
+// 不去刷新索引，也就不会占用 index.lock。
+// 队列变化时广播 `git-queue-changed`（GitQueueState），前端据此显示"排队中（第 N 位）"，
+// 也可以用 get_git_queue 主动查询。仓库的队列在没有执行中 / 排队中的操作后从表中移除。
+
+use super::GitQueueState;
+use crate::error::AppResult;
+use once_cell::sync::{Lazy, OnceCell};
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter};
+
+static APP: OnceCell<AppHandle> = OnceCell::new();
+
+static QUEUES: Lazy<Mutex<HashMap<String, Arc<RepoQueue>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static NEXT_TICKET: AtomicU32 = AtomicU32::new(1);
+
+#[derive(Default)]
+struct RepoQueue {
+    lock: Arc<tokio::sync::Mutex<()>>,
+    running: Mutex<Option<String>>,
+    /// (排队号, 操作名)，按排队顺序
+    waiting: Mutex<Vec<(u32, String)>>,
+}
+
+pub fn init_repo_queue(app: &AppHandle) {
+    let _ = APP.set(app.clone());
+}
+
+/// 同一仓库的不同写法（末尾分隔符、大小写、符号链接）归为同一个队列
+async fn repo_key(path: &str) -> String {
+    let key = match tokio::fs::canonicalize(crate::path_compat::native_path(path)).await {
+        Ok(p) => crate::path_compat::display_path(&p.to_string_lossy()),
+        Err(_) => path.trim_end_matches(['/', '\\']).to_string(),
+    };
+    if cfg!(target_os = "windows") {
+        key.to_lowercase()
+    } else {
+        key
+    }
+}
+
+/// 对某个仓库队列的引用；释放时若表里的那份已是唯一引用（没有执行中或排队中的操作），
+/// 就把它从 QUEUES 移除，避免表随打开过的仓库无限增长
+struct QueueHandle {
+    key: String,
+    queue: Option<Arc<RepoQueue>>,
+}
+
+impl QueueHandle {
+    fn get_or_create(key: String) -> Self {
+        let queue = QUEUES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(key.clone())
+            .or_default()
+            .clone();
+        Self {
+            key,
+            queue: Some(queue),
+        }
+    }
+
+    fn get(key: String) -> Option<Self> {
+        let queue = QUEUES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&key)
+            .cloned()?;
+        Some(Self {
+            key,
+            queue: Some(queue),
+        })
+    }
+}
+
+impl Deref for QueueHandle {
+    type Target = RepoQueue;
+
+    fn deref(&self) -> &RepoQueue {
+        self.queue.as_deref().expect("queue is only taken on drop")
+    }
+}
+
+impl Drop for QueueHandle {
+    fn drop(&mut self) {
+        // 持有表锁时判断，期间不会有新的引用被取出
+        let mut queues = QUEUES.lock().unwrap_or_else(|e| e.into_inner());
+        drop(self.queue.take());
+        if queues
+            .get(&self.key)
+            .is_some_and(|q| Arc::strong_count(q) == 1)
+        {
+            queues.remove(&self.key);
+        }
+    }
+}
+
+impl RepoQueue {
+    fn snapshot(&self, path: &str) -> GitQueueState {
+        GitQueueState {
+            path: path.to_string(),
+            running: self
+                .running
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone(),
+            waiting: self
+                .waiting
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .iter()
+                .map(|(_, label)| label.clone())
+                .collect(),
+        }
+    }
+
+    fn broadcast(&self, path: &str) {
+        if let Some(app) = APP.get() {
+            let _ = app.emit("git-queue-changed", self.snapshot(path));
+        }
+    }
+
+    fn remove_ticket(&self, ticket: u32) {
+        self.waiting
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|(t, _)| *t != ticket);
+    }
+}
+
+/// 排队中的登记；命令在等待时被丢弃也会移出队列
+struct Ticket<'a> {
+    queue: &'a RepoQueue,
+    path: &'a str,
+    id: u32,
+}
+
+impl Drop for Ticket<'_> {
+    fn drop(&mut self) {
+        self.queue.remove_ticket(self.id);
+        self.queue.broadcast(self.path);
+    }
+}
+
+/// 持有期间独占仓库的写操作，析构时交给下一个
+pub(crate) struct RepoGuard {
+    // 先释放锁再释放队列引用，队列只在空闲时才会被移除
+    _lock: tokio::sync::OwnedMutexGuard<()>,
+    queue: QueueHandle,
+    path: String,
+}
+
+impl Drop for RepoGuard {
+    fn drop(&mut self) {
+        *self.queue.running.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        self.queue.broadcast(&self.path);
+    }
+}
+
+/// 排队取得仓库的写锁；label 是显示给用户的操作名
+pub(crate) async fn lock_repo(path: &str, label: &str) -> RepoGuard {
+    let queue = QueueHandle::get_or_create(repo_key(path).await);
+    let id = NEXT_TICKET.fetch_add(1, Ordering::Relaxed);
+    queue
+        .waiting
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push((id, label.to_string()));
+    queue.broadcast(path);
+
+    let lock = {
+        let ticket = Ticket {
+            queue: &queue,
+            path,
+            id,
+        };
+        let lock = queue.lock.clone().lock_owned().await;
+        *queue.running.lock().unwrap_or_else(|e| e.into_inner()) = Some(label.to_string());
+        drop(ticket);
+        lock
+    };
+    RepoGuard {
+        _lock: lock,
+        queue,
+        path: path.to_string(),
+    }
+}
+
+/// 查询仓库当前执行中与排队中的写操作
+#[tauri::command]
+#[specta::specta]
+pub async fn get_git_queue(path: String) -> AppResult<GitQueueState> {
+    let queue = QueueHandle::get(repo_key(&path).await);
+    Ok(match queue {
+        Some(queue) => queue.snapshot(&path),
+        None => GitQueueState {
+            path,
+            running: None,
+            waiting: Vec::new(),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn has_queue(key: &str) -> bool {
+        QUEUES
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains_key(key)
+    }
+
+    #[tokio::test]
+    async fn test_idle_queue_is_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        let key = repo_key(&path).await;
+
+        let first = lock_repo(&path, "提交").await;
+        let waiting = tokio::spawn({
+            let path = path.clone();
+            async move {
+                let _guard = lock_repo(&path, "推送").await;
+            }
+        });
+        while get_git_queue(path.clone())
+            .await
+            .unwrap()
+            .waiting
+            .is_empty()
+        {
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        drop(first);
+        // 还有排队者时不能移除
+        assert!(has_queue(&key));
+        waiting.await.unwrap();
+        assert!(!has_queue(&key));
+
+        let guard = lock_repo(&format!("{}/", path), "拉取").await;
+        assert!(has_queue(&key));
+        assert_eq!(
+            get_git_queue(path.clone())
+                .await
+                .unwrap()
+                .running
+                .as_deref(),
+            Some("拉取")
+        );
+        drop(guard);
+        assert!(!has_queue(&key));
+    }
+}
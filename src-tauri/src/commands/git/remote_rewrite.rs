@@ -0,0 +1,299 @@
+// 远程地址改写：SSH ⇄ HTTPS 互转、批量替换主机名（如 GitLab 域名迁移）
+//
+// 两个命令都先算出改写计划，apply = false 时只返回预览；
+// apply = true 时逐条执行 `git remote set-url [--push]`，单条失败不影响其它。
+// 支持的地址形式：`git@host:group/repo.git`、`ssh://[user@]host[:port]/path`、
+// `http(s)://[user@]host[:port]/path`，其它形式（本地路径、git:// 等）原样跳过。
+
+use crate::error::{AppError, AppResult};
+
+use super::{lock_repo, run_git_command, RemoteRewrite};
+
+#[derive(Debug, Clone, PartialEq)]
+enum UrlKind {
+    /// scp 风格：`user@host:path`
+    Scp,
+    Ssh,
+    Http,
+    Https,
+}
+
+#[derive(Debug, Clone)]
+struct RemoteUrl {
+    kind: UrlKind,
+    user: Option<String>,
+    host: String,
+    port: Option<String>,
+    /// 不带开头 `/` 的仓库路径
+    path: String,
+}
+
+impl RemoteUrl {
+    fn parse(url: &str) -> Option<Self> {
+        let url = url.trim();
+        if let Some((scheme, rest)) = url.split_once("://") {
+            let kind = match scheme.to_ascii_lowercase().as_str() {
+                "ssh" | "git+ssh" => UrlKind::Ssh,
+                "http" => UrlKind::Http,
+                "https" => UrlKind::Https,
+                _ => return None,
+            };
+            let (authority, path) = rest.split_once('/')?;
+            let (user, host_port) = match authority.rsplit_once('@') {
+                Some((u, h)) => (Some(u.to_string()), h),
+                None => (None, authority),
+            };
+            let (host, port) = match host_port.split_once(':') {
+                Some((h, p)) => (h, Some(p.to_string())),
+                None => (host_port, None),
+            };
+            if host.is_empty() {
+                return None;
+            }
+            return Some(Self {
+                kind,
+                user,
+                host: host.to_string(),
+                port,
+                path: path.to_string(),
+            });
+        }
+
+        // scp 风格：冒号必须出现在第一个 `/` 之前，否则是本地路径
+        let (left, path) = url.split_once(':')?;
+        if left.contains('/') || path.starts_with("//") {
+            return None;
+        }
+        // Windows 盘符（C:\repo）
+        if left.len() == 1 && !url.contains('@') {
+            return None;
+        }
+        let (user, host) = match left.rsplit_once('@') {
+            Some((u, h)) => (Some(u.to_string()), h),
+            None => (None, left),
+        };
+        if host.is_empty() || path.is_empty() {
+            return None;
+        }
+        Some(Self {
+            kind: UrlKind::Scp,
+            user,
+            host: host.to_string(),
+            port: None,
+            path: path.trim_start_matches('/').to_string(),
+        })
+    }
+
+    /// 与协议、用户名、端口无关的仓库标识：小写的 `host/path`，去掉结尾的 .git
+    fn identity(&self) -> String {
+        let path = self.path.trim_end_matches('/');
+        let path = path.strip_suffix(".git").unwrap_or(path);
+        format!("{}/{}", self.host, path).to_lowercase()
+    }
+
+    fn is_ssh(&self) -> bool {
+        matches!(self.kind, UrlKind::Scp | UrlKind::Ssh)
+    }
+
+    fn render(&self) -> String {
+        let user = self
+            .user
+            .as_ref()
+            .map(|u| format!("{}@", u))
+            .unwrap_or_default();
+        let port = self
+            .port
+            .as_ref()
+            .map(|p| format!(":{}", p))
+            .unwrap_or_default();
+        match self.kind {
+            UrlKind::Scp => format!("{}{}:{}", user, self.host, self.path),
+            UrlKind::Ssh => format!("ssh://{}{}{}/{}", user, self.host, port, self.path),
+            UrlKind::Http => format!("http://{}{}{}/{}", user, self.host, port, self.path),
+            UrlKind::Https => format!("https://{}{}{}/{}", user, self.host, port, self.path),
+        }
+    }
+
+    /// 转为目标协议（http 也会升级为 https）；已是目标协议时返回 None
+    fn convert(&self, protocol: &str) -> Option<Self> {
+        match protocol {
+            "ssh" if !self.is_ssh() => Some(Self {
+                // HTTPS 的端口对 SSH 没有意义，统一用 scp 风格
+                kind: UrlKind::Scp,
+                user: Some("git".to_string()),
+                host: self.host.clone(),
+                port: None,
+                path: self.path.clone(),
+            }),
+            "https" if self.kind != UrlKind::Https => Some(Self {
+                kind: UrlKind::Https,
+                // SSH 用户名（通常是 git）不带入 HTTPS，由凭据管理器处理
+                user: None,
+                host: self.host.clone(),
+                port: if self.is_ssh() {
+                    None
+                } else {
+                    self.port.clone()
+                },
+                path: self.path.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// 读取仓库所有远程的 url / pushurl：(远程名, 是否 pushurl, 地址)
+fn list_remote_urls(path: &str) -> Vec<(String, bool, String)> {
+    // 没有任何远程时 git config 退出码为 1，按空列表处理
+    let output = run_git_command(
+        path,
+        &["config", "--get-regexp", r"^remote\..*\.(url|pushurl)$"],
+    )
+    .unwrap_or_default();
+
+    output
+        .lines()
+        .filter_map(|line| {
+            let (key, url) = line.split_once(' ')?;
+            let key = key.strip_prefix("remote.")?;
+            if let Some(name) = key.strip_suffix(".pushurl") {
+                Some((name.to_string(), true, url.to_string()))
+            } else {
+                key.strip_suffix(".url")
+                    .map(|name| (name.to_string(), false, url.to_string()))
+            }
+        })
+        .collect()
+}
+
+/// 仓库的主远程地址：优先 origin，否则取第一个远程
+pub(crate) fn primary_remote_url(path: &str) -> Option<String> {
+    let urls: Vec<(String, String)> = list_remote_urls(path)
+        .into_iter()
+        .filter(|(_, is_push, _)| !is_push)
+        .map(|(name, _, url)| (name, url))
+        .collect();
+    urls.iter()
+        .find(|(name, _)| name == "origin")
+        .or_else(|| urls.first())
+        .map(|(_, url)| url.clone())
+}
+
+/// 同一仓库不同写法（SSH / HTTPS、带不带 .git）得到相同的标识；无法解析时返回 None
+pub(crate) fn remote_identity(url: &str) -> Option<String> {
+    RemoteUrl::parse(url).map(|u| u.identity())
+}
+
+/// 转义为 git config 使用的正则，精确匹配旧地址
+fn escape_value_regex(value: &str) -> String {
+    let mut out = String::from("^");
+    for c in value.chars() {
+        if "\\.^$*+?()[]{}|".contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out.push('$');
+    out
+}
+
+/// 按改写函数生成计划，apply 时执行（执行期间持有仓库写锁）
+async fn rewrite_remotes(
+    paths: &[String],
+    remote: Option<&str>,
+    apply: bool,
+    rewrite: impl Fn(&str) -> Option<String>,
+) -> Vec<RemoteRewrite> {
+    let mut results = Vec::new();
+    for path in paths {
+        let _guard = if apply {
+            Some(lock_repo(path, "修改远程地址").await)
+        } else {
+            None
+        };
+        for (name, push, old_url) in list_remote_urls(path) {
+            if remote.is_some_and(|r| r != name) {
+                continue;
+            }
+            let Some(new_url) = rewrite(&old_url) else {
+                continue;
+            };
+            if new_url == old_url {
+                continue;
+            }
+
+            let mut item = RemoteRewrite {
+                project_path: path.clone(),
+                remote: name.clone(),
+                push,
+                old_url: old_url.clone(),
+                new_url: new_url.clone(),
+                applied: false,
+                error: None,
+            };
+            if apply {
+                let pattern = escape_value_regex(&old_url);
+                let mut args = vec!["remote", "set-url"];
+                if push {
+                    args.push("--push");
+                }
+                args.extend([name.as_str(), new_url.as_str(), pattern.as_str()]);
+                match run_git_command(path, &args) {
+                    Ok(_) => item.applied = true,
+                    Err(e) => item.error = Some(e.to_string()),
+                }
+            }
+            results.push(item);
+        }
+    }
+    results
+}
+
+/// 在 SSH 与 HTTPS 之间转换远程地址；remote 为空时处理所有远程
+#[tauri::command]
+#[specta::specta]
+pub async fn convert_remote_protocol(
+    paths: Vec<String>,
+    remote: Option<String>,
+    protocol: String,
+    apply: bool,
+) -> AppResult<Vec<RemoteRewrite>> {
+    if protocol != "ssh" && protocol != "https" {
+        return Err(AppError::invalid(format!("不支持的协议: {}", protocol)));
+    }
+    Ok(rewrite_remotes(&paths, remote.as_deref(), apply, |url| {
+        RemoteUrl::parse(url)?
+            .convert(&protocol)
+            .map(|u| u.render())
+    })
+    .await)
+}
+
+/// 批量替换远程地址的主机名（不区分大小写），协议、用户与路径保持不变
+#[tauri::command]
+#[specta::specta]
+pub async fn rewrite_remote_host(
+    paths: Vec<String>,
+    from_host: String,
+    to_host: String,
+    apply: bool,
+) -> AppResult<Vec<RemoteRewrite>> {
+    let from_host = from_host.trim().to_string();
+    let to_host = to_host.trim().to_string();
+    if from_host.is_empty() || to_host.is_empty() {
+        return Err(AppError::invalid("主机名不能为空"));
+    }
+    if to_host.contains(['/', '@', ':', ' ']) {
+        return Err(AppError::invalid(format!("无效的主机名: {}", to_host)));
+    }
+
+    Ok(rewrite_remotes(&paths, None, apply, |url| {
+        let mut parsed = RemoteUrl::parse(url)?;
+        if !parsed.host.eq_ignore_ascii_case(&from_host) {
+            return None;
+        }
+        parsed.host = to_host.clone();
+        Some(parsed.render())
+    })
+    .await)
+}
@@ -1,15 +1,11 @@
 // 远程仓库与同步：remotes / push / pull / fetch / sync_to_remote
 
+use crate::commands::operations::Operation;
 use crate::error::AppResult;
-use std::process::Command;
+use crate::path_compat::{decode_text, git_command};
+use std::collections::HashMap;
 
-#[cfg(target_os = "windows")]
-use std::os::windows::process::CommandExt;
-
-use super::{run_git_command, RemoteInfo};
-
-#[cfg(target_os = "windows")]
-use super::CREATE_NO_WINDOW;
+use super::{lock_repo, run_git_command, run_git_operation, RemoteInfo, SyncBranchPreview};
 
 #[tauri::command]
 #[specta::specta]
@@ -47,6 +43,7 @@ pub async fn get_remotes(path: String) -> AppResult<Vec<RemoteInfo>> {
 #[tauri::command]
 #[specta::specta]
 pub async fn add_remote(path: String, name: String, url: String) -> AppResult<()> {
+    let _guard = lock_repo(&path, "添加远程").await;
     run_git_command(&path, &["remote", "add", &name, &url])?;
     Ok(())
 }
@@ -55,23 +52,15 @@ pub async fn add_remote(path: String, name: String, url: String) -> AppResult<()
 #[specta::specta]
 pub async fn verify_remote_url(url: String) -> AppResult<()> {
     // 使用 git ls-remote 验证远程仓库 URL 是否有效 (hide console window on Windows)
-    #[cfg(target_os = "windows")]
-    let output = Command::new("git")
-        .args(&["ls-remote", "--exit-code", &url])
-        .creation_flags(CREATE_NO_WINDOW)
-        .output()
-        .map_err(|e| crate::error::AppError::from(format!("执行 git 命令失败: {}", e)))?;
-
-    #[cfg(not(target_os = "windows"))]
-    let output = Command::new("git")
-        .args(&["ls-remote", "--exit-code", &url])
+    let output = git_command()
+        .args(["ls-remote", "--exit-code", &url])
         .output()
         .map_err(|e| crate::error::AppError::from(format!("执行 git 命令失败: {}", e)))?;
 
     if output.status.success() {
         Ok(())
     } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stderr = decode_text(&output.stderr);
         Err(crate::error::AppError::from(format!(
             "无法连接到远程仓库: {}",
             stderr.trim()
@@ -82,6 +71,7 @@ pub async fn verify_remote_url(url: String) -> AppResult<()> {
 #[tauri::command]
 #[specta::specta]
 pub async fn remove_remote(path: String, name: String) -> AppResult<()> {
+    let _guard = lock_repo(&path, "删除远程").await;
     run_git_command(&path, &["remote", "remove", &name])?;
     Ok(())
 }
@@ -93,27 +83,104 @@ pub async fn git_push(
     remote: String,
     branch: String,
     force: bool,
+    op_id: Option<String>,
+    timeout_secs: Option<u64>,
 ) -> AppResult<String> {
+    let _guard = lock_repo(&path, "推送").await;
+    let op = Operation::begin(op_id, "推送", timeout_secs)?;
     let mut args = vec!["push", &remote, &branch];
     if force {
         args.push("--force");
     }
-    run_git_command(&path, &args)
+    run_git_operation(&path, &args, &op)
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn git_pull(path: String, remote: String, branch: String) -> AppResult<String> {
-    run_git_command(&path, &["pull", &remote, &branch])
+pub async fn git_pull(
+    path: String,
+    remote: String,
+    branch: String,
+    op_id: Option<String>,
+    timeout_secs: Option<u64>,
+) -> AppResult<String> {
+    let _guard = lock_repo(&path, "拉取").await;
+    let op = Operation::begin(op_id, "拉取", timeout_secs)?;
+    run_git_operation(&path, &["pull", &remote, &branch], &op)
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn git_fetch(path: String, remote: Option<String>) -> AppResult<String> {
+pub async fn git_fetch(
+    path: String,
+    remote: Option<String>,
+    op_id: Option<String>,
+    timeout_secs: Option<u64>,
+) -> AppResult<String> {
+    let _guard = lock_repo(&path, "获取远程更新").await;
+    let op = Operation::begin(op_id, "获取远程更新", timeout_secs)?;
     match remote {
-        Some(r) => run_git_command(&path, &["fetch", &r]),
-        None => run_git_command(&path, &["fetch", "--all"]),
+        Some(r) => run_git_operation(&path, &["fetch", &r], &op),
+        None => run_git_operation(&path, &["fetch", "--all"], &op),
+    }
+}
+
+/// 源远程的全部分支（默认分支排在最前，新仓库需要先推默认分支）与默认分支名
+fn source_branches(path: &str, source_remote: &str) -> AppResult<(Vec<String>, Option<String>)> {
+    // Get the default branch of source remote (HEAD points to)
+    let default_branch = run_git_command(
+        path,
+        &[
+            "symbolic-ref",
+            &format!("refs/remotes/{}/HEAD", source_remote),
+        ],
+    )
+    .ok()
+    .and_then(|output| {
+        // Output is like: refs/remotes/origin/main（分支名本身可能带 /）
+        output
+            .trim()
+            .strip_prefix(&format!("refs/remotes/{}/", source_remote))
+            .map(|s| s.to_string())
+    });
+
+    // Get all branches from source remote (excluding HEAD)
+    let branches_output = run_git_command(path, &["branch", "-r"])?;
+    let mut branches: Vec<String> = branches_output
+        .lines()
+        .filter_map(|line| {
+            let branch = line.trim();
+            if branch.starts_with(&format!("{}/", source_remote)) && !branch.contains("HEAD") {
+                Some(
+                    branch
+                        .trim_start_matches(&format!("{}/", source_remote))
+                        .to_string(),
+                )
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    if branches.is_empty() {
+        return Err(crate::error::AppError::from(
+            "No branches found to sync".to_string(),
+        ));
+    }
+
+    // Sort branches to push default branch first (important for new repos)
+    if let Some(ref default_br) = default_branch {
+        branches.sort_by(|a, b| {
+            if a == default_br {
+                std::cmp::Ordering::Less
+            } else if b == default_br {
+                std::cmp::Ordering::Greater
+            } else {
+                a.cmp(b)
+            }
+        });
     }
+    Ok((branches, default_branch))
 }
 
 #[tauri::command]
@@ -125,60 +192,12 @@ pub async fn sync_to_remote(
     sync_all_branches: bool,
     force: bool,
 ) -> AppResult<String> {
+    let _guard = lock_repo(&path, "同步到远程").await;
     // First, fetch all branches from source remote to ensure we have latest refs
     run_git_command(&path, &["fetch", &source_remote, "--prune"])?;
 
     if sync_all_branches {
-        // Get the default branch of source remote (HEAD points to)
-        let default_branch = run_git_command(
-            &path,
-            &[
-                "symbolic-ref",
-                &format!("refs/remotes/{}/HEAD", source_remote),
-            ],
-        )
-        .ok()
-        .and_then(|output| {
-            // Output is like: refs/remotes/origin/main
-            output.trim().split('/').last().map(|s| s.to_string())
-        });
-
-        // Get all branches from source remote (excluding HEAD)
-        let branches_output = run_git_command(&path, &["branch", "-r"])?;
-        let mut branches: Vec<String> = branches_output
-            .lines()
-            .filter_map(|line| {
-                let branch = line.trim();
-                if branch.starts_with(&format!("{}/", source_remote)) && !branch.contains("HEAD") {
-                    Some(
-                        branch
-                            .trim_start_matches(&format!("{}/", source_remote))
-                            .to_string(),
-                    )
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        if branches.is_empty() {
-            return Err(crate::error::AppError::from(
-                "No branches found to sync".to_string(),
-            ));
-        }
-
-        // Sort branches to push default branch first (important for new repos)
-        if let Some(ref default_br) = default_branch {
-            branches.sort_by(|a, b| {
-                if a == default_br {
-                    std::cmp::Ordering::Less
-                } else if b == default_br {
-                    std::cmp::Ordering::Greater
-                } else {
-                    a.cmp(b)
-                }
-            });
-        }
+        let (branches, default_branch) = source_branches(&path, &source_remote)?;
 
         // Push each branch using remote tracking ref
         // Use: refs/remotes/origin/branch:refs/heads/branch
@@ -227,3 +246,137 @@ pub async fn sync_to_remote(
         ))
     }
 }
+
+/// 目标远程上各分支的提交：git ls-remote --heads，不修改本地引用
+fn target_heads(path: &str, target_remote: &str) -> AppResult<HashMap<String, String>> {
+    let output = run_git_command(path, &["ls-remote", "--heads", target_remote])?;
+    Ok(output
+        .lines()
+        .filter_map(|line| {
+            let (sha, name) = line.split_once('\t')?;
+            let branch = name.trim().strip_prefix("refs/heads/")?;
+            Some((branch.to_string(), sha.trim().to_string()))
+        })
+        .collect())
+}
+
+fn count_commits(path: &str, range: &str) -> Option<u32> {
+    run_git_command(path, &["rev-list", "--count", range])
+        .ok()
+        .and_then(|v| v.parse().ok())
+}
+
+fn preview_branch(
+    path: &str,
+    branch: &str,
+    source_sha: String,
+    target_sha: Option<&String>,
+    force: bool,
+    is_default: bool,
+) -> SyncBranchPreview {
+    let mut preview = SyncBranchPreview {
+        branch: branch.to_string(),
+        action: "create".to_string(),
+        source_sha: source_sha.clone(),
+        target_sha: target_sha.cloned(),
+        ahead: None,
+        discarded: None,
+        range: None,
+        requires_force: false,
+        is_default,
+    };
+    let Some(target_sha) = target_sha else {
+        return preview;
+    };
+    if *target_sha == source_sha {
+        preview.action = "upToDate".to_string();
+        preview.ahead = Some(0);
+        return preview;
+    }
+
+    let range = format!("{}..{}", target_sha, source_sha);
+    // 本地没有目标提交对象（目标独有的提交未拉取过）时，只能判定为非快进
+    let target_known = run_git_command(
+        path,
+        &["cat-file", "-e", &format!("{}^{{commit}}", target_sha)],
+    )
+    .is_ok();
+    let fast_forward = target_known
+        && run_git_command(
+            path,
+            &["merge-base", "--is-ancestor", target_sha, &source_sha],
+        )
+        .is_ok();
+    if fast_forward {
+        preview.action = "fastForward".to_string();
+        preview.ahead = count_commits(path, &range);
+    } else {
+        preview.action = "forceUpdate".to_string();
+        preview.requires_force = !force;
+        if target_known {
+            preview.ahead = count_commits(path, &range);
+            preview.discarded = count_commits(path, &format!("{}..{}", source_sha, target_sha));
+        }
+    }
+    preview.range = Some(range);
+    preview
+}
+
+/// sync_to_remote 的预演：不推送，逐个分支返回会新建、快进还是强制覆盖（附提交范围）
+#[tauri::command]
+#[specta::specta]
+pub async fn preview_sync_to_remote(
+    path: String,
+    source_remote: String,
+    target_remote: String,
+    sync_all_branches: bool,
+    force: bool,
+) -> AppResult<Vec<SyncBranchPreview>> {
+    // 全部分支模式会先 fetch 源远程，与其他写操作一样排队
+    let _guard = if sync_all_branches {
+        Some(lock_repo(&path, "获取远程更新").await)
+    } else {
+        None
+    };
+    tokio::task::spawn_blocking(move || {
+        let heads = target_heads(&path, &target_remote)?;
+        if !sync_all_branches {
+            // 与 sync_to_remote 一致：推送当前本地分支
+            let branch = run_git_command(&path, &["rev-parse", "--abbrev-ref", "HEAD"])?;
+            let source_sha = run_git_command(&path, &["rev-parse", &branch])?;
+            return Ok(vec![preview_branch(
+                &path,
+                &branch,
+                source_sha,
+                heads.get(&branch),
+                force,
+                false,
+            )]);
+        }
+
+        run_git_command(&path, &["fetch", &source_remote, "--prune"])?;
+        let (branches, default_branch) = source_branches(&path, &source_remote)?;
+        branches
+            .iter()
+            .map(|branch| {
+                let source_sha = run_git_command(
+                    &path,
+                    &[
+                        "rev-parse",
+                        &format!("refs/remotes/{}/{}", source_remote, branch),
+                    ],
+                )?;
+                Ok(preview_branch(
+                    &path,
+                    branch,
+                    source_sha,
+                    heads.get(branch),
+                    force,
+                    default_branch.as_deref() == Some(branch.as_str()),
+                ))
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| crate::error::AppError::internal(e.to_string()))?
+}
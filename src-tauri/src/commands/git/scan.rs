@@ -1,55 +1,213 @@
 // 仓库扫描与初始化：scan_directory / is_git_repo / git_init
+//
+// 扫描识别三类仓库：带 .git 目录的普通仓库、.git 为 gitdir 文件的工作树、裸仓库。
 
-use super::{run_git_command, GitRepo};
-use crate::error::AppResult;
+use super::{run_git_command, GitRepo, ScanOptions};
+use crate::error::{AppError, AppResult};
+use regex::{Regex, RegexBuilder};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
+/// 扫描过程中的共享状态
+struct ScanContext {
+    root: PathBuf,
+    include: Vec<Regex>,
+    exclude: Vec<Regex>,
+    follow_symlinks: bool,
+    max_repos: usize,
+    /// 已进入的目录（规范化路径），跟随符号链接时避免重复与环
+    visited: HashSet<PathBuf>,
+}
+
+impl ScanContext {
+    /// 相对扫描目录的路径（统一为 /），扫描目录本身用目录名
+    fn relative(&self, path: &Path) -> String {
+        match path.strip_prefix(&self.root) {
+            Ok(rel) if !rel.as_os_str().is_empty() => rel.to_string_lossy().replace('\\', "/"),
+            _ => path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn excluded(&self, path: &Path) -> bool {
+        let rel = self.relative(path);
+        self.exclude.iter().any(|re| re.is_match(&rel))
+    }
+
+    fn push(&self, repos: &mut Vec<GitRepo>, path: &Path, kind: &str) {
+        if !self.include.is_empty() {
+            let rel = self.relative(path);
+            if !self.include.iter().any(|re| re.is_match(&rel)) {
+                return;
+            }
+        }
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().trim_end_matches(".git").to_string())
+            .filter(|n| !n.is_empty())
+            .unwrap_or_else(|| "Unknown".to_string());
+        repos.push(GitRepo {
+            path: path.to_string_lossy().to_string(),
+            name,
+            kind: kind.to_string(),
+        });
+    }
+}
+
+/// gitignore 风格的模式转为正则：不含斜杠的模式匹配任意一级目录名
+fn pattern_regex(pattern: &str, ignore_case: bool) -> AppResult<Regex> {
+    let trimmed = pattern.trim().trim_end_matches('/');
+    let anchored = trimmed.starts_with('/') || trimmed.trim_start_matches('/').contains('/');
+    let body = trimmed.trim_start_matches('/');
+
+    let mut re = String::new();
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    re.push_str("(?:.*/)?");
+                } else {
+                    re.push_str(".*");
+                }
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    let prefix = if anchored { "^" } else { "(?:^|/)" };
+    RegexBuilder::new(&format!("{}{}(?:/.*)?$", prefix, re))
+        .case_insensitive(ignore_case)
+        .build()
+        .map_err(|e| AppError::invalid(format!("无效的匹配模式 {}: {}", pattern, e)))
+}
+
+/// 编译一组 gitignore 风格模式，忽略空行；目录同步的过滤规则也用它
+pub(crate) fn compile_patterns(patterns: &[String], ignore_case: bool) -> AppResult<Vec<Regex>> {
+    patterns
+        .iter()
+        .filter(|p| !p.trim().is_empty())
+        .map(|p| pattern_regex(p, ignore_case))
+        .collect()
+}
+
+/// .git 为目录时是普通仓库；为文件（gitdir: ...）时指向工作树或子模块的 git 目录
+fn dot_git_kind(dot_git: &Path) -> Option<&'static str> {
+    if dot_git.is_dir() {
+        return Some("normal");
+    }
+    let content = std::fs::read_to_string(dot_git).ok()?;
+    let gitdir = content
+        .trim()
+        .strip_prefix("gitdir:")?
+        .trim()
+        .replace('\\', "/");
+    Some(if gitdir.contains("/worktrees/") {
+        "worktree"
+    } else {
+        "normal"
+    })
+}
+
+/// 裸仓库：目录下直接有 HEAD、objects、refs
+fn is_bare_repo(dir: &Path) -> bool {
+    dir.join("HEAD").is_file() && dir.join("objects").is_dir() && dir.join("refs").is_dir()
+}
+
+/// 扫描目录下的 Git 仓库；options 为空时沿用默认行为（跟随符号链接、不过滤）
 #[tauri::command]
 #[specta::specta]
-pub async fn scan_directory(path: String, depth: Option<u32>) -> AppResult<Vec<GitRepo>> {
+pub async fn scan_directory(
+    path: String,
+    depth: Option<u32>,
+    options: Option<ScanOptions>,
+) -> AppResult<Vec<GitRepo>> {
+    let options = options.unwrap_or_default();
+    let root = PathBuf::from(&path);
+    let mut ctx = ScanContext {
+        include: compile_patterns(&options.include, options.ignore_case)?,
+        exclude: compile_patterns(&options.exclude, options.ignore_case)?,
+        follow_symlinks: options.follow_symlinks,
+        max_repos: options.max_repos.map(|n| n as usize).unwrap_or(usize::MAX),
+        visited: HashSet::new(),
+        root: root.clone(),
+    };
+    if let Ok(canonical) = root.canonicalize() {
+        ctx.visited.insert(canonical);
+    }
+
     let mut repos = Vec::new();
     let scan_depth = depth.unwrap_or(3);
-    scan_for_repos(&path, &mut repos, scan_depth)?;
+    if is_bare_repo(&root) {
+        ctx.push(&mut repos, &root, "bare");
+        return Ok(repos);
+    }
+    scan_for_repos(&root, &mut ctx, &mut repos, scan_depth)?;
     Ok(repos)
 }
 
-fn scan_for_repos(path: &str, repos: &mut Vec<GitRepo>, depth: u32) -> AppResult<()> {
-    if depth == 0 {
+fn scan_for_repos(
+    path: &Path,
+    ctx: &mut ScanContext,
+    repos: &mut Vec<GitRepo>,
+    depth: u32,
+) -> AppResult<()> {
+    if depth == 0 || repos.len() >= ctx.max_repos {
         return Ok(());
     }
 
-    let entries =
-        std::fs::read_dir(path).map_err(|e| crate::error::AppError::from(e.to_string()))?;
+    let mut entries: Vec<(std::fs::DirEntry, bool)> = std::fs::read_dir(path)
+        .map_err(|e| AppError::from(e.to_string()))?
+        .flatten()
+        .map(|entry| {
+            let is_symlink = entry.file_type().map(|t| t.is_symlink()).unwrap_or(false);
+            (entry, is_symlink)
+        })
+        .collect();
+    // 符号链接排在后面，同一目录优先按真实路径收录
+    entries.sort_by_key(|(_, is_symlink)| *is_symlink);
 
-    for entry in entries.flatten() {
+    for (entry, is_symlink) in entries {
+        if repos.len() >= ctx.max_repos {
+            break;
+        }
         let entry_path = entry.path();
-        if entry_path.is_dir() {
-            let Some(file_name) = entry_path.file_name() else {
-                continue;
-            };
-            let dir_name = file_name.to_string_lossy().to_string();
-
-            // Skip hidden directories except .git
-            if dir_name.starts_with('.') && dir_name != ".git" {
-                continue;
+        if is_symlink && !ctx.follow_symlinks {
+            continue;
+        }
+        let dir_name = entry.file_name().to_string_lossy().to_string();
+
+        if dir_name == ".git" {
+            // Found a git repo (or worktree), add the parent directory
+            if let Some(kind) = dot_git_kind(&entry_path) {
+                ctx.push(repos, path, kind);
             }
+            continue;
+        }
 
-            if dir_name == ".git" {
-                // Found a git repo, add the parent directory
-                if let Some(parent) = entry_path.parent() {
-                    let repo_name = parent
-                        .file_name()
-                        .map(|n| n.to_string_lossy().to_string())
-                        .unwrap_or_else(|| "Unknown".to_string());
-                    repos.push(GitRepo {
-                        path: parent.to_string_lossy().to_string(),
-                        name: repo_name,
-                    });
+        // Skip hidden directories
+        if !entry_path.is_dir() || dir_name.starts_with('.') || ctx.excluded(&entry_path) {
+            continue;
+        }
+        if ctx.follow_symlinks {
+            if let Ok(canonical) = entry_path.canonicalize() {
+                if !ctx.visited.insert(canonical) {
+                    continue;
                 }
-            } else {
-                // Continue scanning subdirectories
-                scan_for_repos(&entry_path.to_string_lossy(), repos, depth - 1)?;
             }
         }
+
+        if is_bare_repo(&entry_path) {
+            ctx.push(repos, &entry_path, "bare");
+        } else {
+            // Continue scanning subdirectories
+            scan_for_repos(&entry_path, ctx, repos, depth - 1)?;
+        }
     }
 
     Ok(())
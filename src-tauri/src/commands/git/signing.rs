@@ -0,0 +1,335 @@
+// 提交签名配置助手：检测本机可用的 GPG / SSH 签名密钥，按仓库或全局写入签名配置，
+// 并在临时仓库中做一次测试提交验证签名是否可用。
+//
+// SSH 签名需要 git 2.34+；验证 SSH 签名依赖 gpg.ssh.allowedSignersFile，
+// 测试提交时在临时目录生成一份只含当前密钥的 allowed_signers。
+
+use crate::error::{AppError, AppResult};
+use std::path::Path;
+use std::process::Command;
+
+use super::{lock_repo, run_git_command, SigningConfig, SigningKey, SigningTestResult};
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// 测试提交使用的兜底身份（未配置 user.name / user.email 时）
+const TEST_USER_NAME: &str = "CodeShelf";
+const TEST_USER_EMAIL: &str = "signing-test@codeshelf.local";
+
+/// 全局配置不依赖具体仓库，在临时目录下执行 git config --global；
+/// 读取时同样要带 --global，否则临时目录恰好位于某个仓库内时会读到该仓库的配置
+fn global_dir() -> String {
+    std::env::temp_dir().to_string_lossy().to_string()
+}
+
+/// (执行目录, 作用域参数)
+fn scope_args(path: Option<&str>) -> (String, &'static str) {
+    match path {
+        Some(p) => (p.to_string(), "--local"),
+        None => (global_dir(), "--global"),
+    }
+}
+
+fn config_get(dir: &str, args: &[&str]) -> Option<String> {
+    run_git_command(dir, args).ok().filter(|v| !v.is_empty())
+}
+
+/// 读取签名相关配置：path 为空时只读全局配置，否则读取仓库中生效的配置
+fn config_read(path: Option<&str>, key: &str) -> Option<String> {
+    match path {
+        Some(p) => config_get(p, &["config", key]),
+        None => config_get(&global_dir(), &["config", "--global", key]),
+    }
+}
+
+fn config_bool(path: Option<&str>, key: &str) -> bool {
+    let value = match path {
+        Some(p) => config_get(p, &["config", "--type=bool", key]),
+        None => config_get(&global_dir(), &["config", "--global", "--type=bool", key]),
+    };
+    value.map(|v| v == "true").unwrap_or(false)
+}
+
+/// 解析 gpg --list-secret-keys --with-colons 输出，跳过已吊销 / 已过期的密钥
+fn parse_gpg_keys(output: &str) -> Vec<SigningKey> {
+    let mut keys: Vec<SigningKey> = Vec::new();
+    // 当前记录是否属于主密钥（fpr / uid 只取主密钥后的第一条）
+    let mut in_primary = false;
+    for line in output.lines() {
+        let fields: Vec<&str> = line.split(':').collect();
+        match fields.first().copied() {
+            Some("sec") => {
+                let validity = fields.get(1).copied().unwrap_or("");
+                in_primary = !matches!(validity, "r" | "e" | "d");
+                if !in_primary {
+                    continue;
+                }
+                let expires = fields
+                    .get(6)
+                    .and_then(|v| v.parse::<i64>().ok())
+                    .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+                    .map(|dt| dt.format("%Y-%m-%d").to_string());
+                keys.push(SigningKey {
+                    format: "openpgp".to_string(),
+                    key: fields.get(4).copied().unwrap_or("").to_string(),
+                    label: None,
+                    fingerprint: None,
+                    expires,
+                });
+            }
+            Some("ssb") => in_primary = false,
+            Some("fpr") if in_primary => {
+                if let Some(key) = keys.last_mut().filter(|k| k.fingerprint.is_none()) {
+                    key.fingerprint = fields.get(9).map(|v| v.to_string());
+                }
+            }
+            Some("uid") if in_primary => {
+                if let Some(key) = keys.last_mut().filter(|k| k.label.is_none()) {
+                    key.label = fields.get(9).map(|v| v.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    keys.retain(|k| !k.key.is_empty());
+    keys
+}
+
+fn list_gpg_keys() -> Vec<SigningKey> {
+    let program = config_read(None, "gpg.program").unwrap_or_else(|| "gpg".to_string());
+    let mut cmd = Command::new(program);
+    cmd.args([
+        "--list-secret-keys",
+        "--with-colons",
+        "--keyid-format",
+        "long",
+    ]);
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+    match cmd.output() {
+        Ok(output) if output.status.success() => {
+            parse_gpg_keys(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn read_config(path: Option<&str>) -> SigningConfig {
+    let scope = match path {
+        Some(p)
+            if config_get(p, &["config", "--local", "user.signingkey"]).is_some()
+                || config_get(p, &["config", "--local", "commit.gpgsign"]).is_some() =>
+        {
+            "local"
+        }
+        _ => "global",
+    };
+    SigningConfig {
+        scope: scope.to_string(),
+        format: config_read(path, "gpg.format").unwrap_or_else(|| "openpgp".to_string()),
+        signing_key: config_read(path, "user.signingkey"),
+        sign_commits: config_bool(path, "commit.gpgsign"),
+        sign_tags: config_bool(path, "tag.gpgsign"),
+        allowed_signers_file: config_read(path, "gpg.ssh.allowedSignersFile"),
+    }
+}
+
+/// SSH 签名密钥可以是公钥文件路径（支持 ~/），也可以是 "key::ssh-ed25519 ..." 字面量
+fn ssh_public_key(key: &str) -> AppResult<String> {
+    if let Some(literal) = key.strip_prefix("key::") {
+        return Ok(literal.trim().to_string());
+    }
+    if key.starts_with("ssh-") || key.starts_with("ecdsa-") {
+        return Ok(key.trim().to_string());
+    }
+    let path = match key.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+        None => std::path::PathBuf::from(key),
+    };
+    std::fs::read_to_string(&path)
+        .map(|content| content.trim().to_string())
+        .map_err(|e| AppError::invalid(format!("无法读取 SSH 公钥 {}: {}", key, e)))
+}
+
+/// git verify-commit 把签名信息写在 stderr，这里直接取 stderr
+fn verify_commit(repo: &str) -> (bool, String) {
+    match crate::path_compat::git_in(repo)
+        .args(["verify-commit", "HEAD"])
+        .output()
+    {
+        Ok(output) => (
+            output.status.success(),
+            crate::path_compat::decode_text(&output.stderr)
+                .trim()
+                .to_string(),
+        ),
+        Err(e) => (false, e.to_string()),
+    }
+}
+
+/// 在 dir/repo 中按给定配置做一次空提交并验证
+fn signing_test_in(
+    dir: &Path,
+    config: &SigningConfig,
+    key: &str,
+    name: &str,
+    email: &str,
+) -> AppResult<SigningTestResult> {
+    let repo = dir.join("repo");
+    std::fs::create_dir_all(&repo)?;
+    let repo = repo.to_string_lossy().to_string();
+    run_git_command(&repo, &["init", "-q"])?;
+
+    let mut settings = vec![
+        ("user.name", name.to_string()),
+        ("user.email", email.to_string()),
+        ("gpg.format", config.format.clone()),
+        ("user.signingkey", key.to_string()),
+    ];
+    if config.format == "ssh" {
+        // 只信任当前密钥，用于 verify-commit
+        let public_key = ssh_public_key(key)?;
+        let signers = dir.join("allowed_signers");
+        std::fs::write(&signers, format!("{} {}\n", email, public_key))?;
+        settings.push((
+            "gpg.ssh.allowedSignersFile",
+            signers.to_string_lossy().to_string(),
+        ));
+    }
+    for (k, v) in &settings {
+        run_git_command(&repo, &["config", "--local", k, v])?;
+    }
+
+    if let Err(e) = run_git_command(
+        &repo,
+        &[
+            "commit",
+            "-q",
+            "-S",
+            "--allow-empty",
+            "-m",
+            "CodeShelf signing test",
+        ],
+    ) {
+        return Ok(SigningTestResult {
+            signed: false,
+            verified: false,
+            output: e.to_string(),
+        });
+    }
+    // %G? 为 N 表示没有签名
+    let status = run_git_command(&repo, &["log", "-1", "--format=%G?"]).unwrap_or_default();
+    let (verified, output) = verify_commit(&repo);
+    Ok(SigningTestResult {
+        signed: !status.is_empty() && status != "N",
+        verified,
+        output,
+    })
+}
+
+/// 在临时目录中测试签名，结束后清理
+fn run_signing_test(
+    config: &SigningConfig,
+    name: &str,
+    email: &str,
+) -> AppResult<SigningTestResult> {
+    let key = config
+        .signing_key
+        .as_deref()
+        .ok_or_else(|| AppError::invalid("未配置签名密钥（user.signingkey）"))?;
+    let dir = std::env::temp_dir().join(format!(
+        "codeshelf-signing-test-{}",
+        crate::commands::toolbox::generate_id()
+    ));
+    std::fs::create_dir_all(&dir)?;
+    let result = signing_test_in(&dir, config, key, name, email);
+    let _ = std::fs::remove_dir_all(&dir);
+    result
+}
+
+/// 检测本机可用的签名密钥：GPG 私钥 + ~/.ssh 下带公钥的 SSH 密钥
+#[tauri::command]
+#[specta::specta]
+pub async fn list_signing_keys() -> AppResult<Vec<SigningKey>> {
+    let mut keys = tokio::task::spawn_blocking(list_gpg_keys)
+        .await
+        .map_err(|e| AppError::internal(e.to_string()))?;
+    let ssh_keys = crate::commands::toolbox::ssh_keys::list_ssh_keys()
+        .await
+        .unwrap_or_default();
+    keys.extend(ssh_keys.into_iter().filter_map(|k| {
+        Some(SigningKey {
+            format: "ssh".to_string(),
+            key: k.public_key_path?,
+            label: k.comment,
+            fingerprint: k.fingerprint,
+            expires: None,
+        })
+    }));
+    Ok(keys)
+}
+
+/// 查看签名配置；path 为空时查看全局配置
+#[tauri::command]
+#[specta::specta]
+pub async fn get_signing_config(path: Option<String>) -> AppResult<SigningConfig> {
+    Ok(read_config(path.as_deref()))
+}
+
+/// 写入签名配置：path 不为空时写入仓库配置，否则写入全局配置
+#[tauri::command]
+#[specta::specta]
+pub async fn set_signing_config(
+    path: Option<String>,
+    format: String,
+    signing_key: String,
+    sign_commits: bool,
+    sign_tags: Option<bool>,
+) -> AppResult<SigningConfig> {
+    if !matches!(format.as_str(), "openpgp" | "ssh" | "x509") {
+        return Err(AppError::invalid(format!("不支持的签名格式: {}", format)));
+    }
+    let signing_key = signing_key.trim();
+    if signing_key.is_empty() {
+        return Err(AppError::invalid("签名密钥不能为空"));
+    }
+    let (dir, scope) = scope_args(path.as_deref());
+    let sign_commits = sign_commits.to_string();
+    let mut settings = vec![
+        ("gpg.format", format.as_str()),
+        ("user.signingkey", signing_key),
+        ("commit.gpgsign", sign_commits.as_str()),
+    ];
+    let sign_tags = sign_tags.map(|v| v.to_string());
+    if let Some(v) = sign_tags.as_deref() {
+        settings.push(("tag.gpgsign", v));
+    }
+    let _guard = match path.as_deref() {
+        Some(p) => Some(lock_repo(p, "设置提交签名").await),
+        None => None,
+    };
+    for (key, value) in settings {
+        run_git_command(&dir, &["config", scope, key, value])?;
+    }
+    Ok(read_config(path.as_deref()))
+}
+
+/// 在临时仓库中用当前生效的签名配置做一次测试提交，验证签名是否可用
+#[tauri::command]
+#[specta::specta]
+pub async fn test_commit_signing(path: Option<String>) -> AppResult<SigningTestResult> {
+    tokio::task::spawn_blocking(move || {
+        let config = read_config(path.as_deref());
+        let name =
+            config_read(path.as_deref(), "user.name").unwrap_or_else(|| TEST_USER_NAME.to_string());
+        let email = config_read(path.as_deref(), "user.email")
+            .unwrap_or_else(|| TEST_USER_EMAIL.to_string());
+        run_signing_test(&config, &name, &email)
+    })
+    .await
+    .map_err(|e| AppError::internal(e.to_string()))?
+}
@@ -1,16 +1,21 @@
 // 暂存/还原/stash/commit/revert/cherry-pick
 
-use super::{is_system_junk_file, run_git_command};
+use super::{is_system_junk_file, lock_repo, run_git_command};
 use crate::error::AppResult;
 
 #[tauri::command]
 #[specta::specta]
 pub async fn git_add(path: String, files: Vec<String>) -> AppResult<String> {
+    let _guard = lock_repo(&path, "暂存").await;
+    add_files(&path, files)
+}
+
+fn add_files(path: &str, files: Vec<String>) -> AppResult<String> {
     if files.is_empty() {
         // Add all changes while keeping macOS Finder metadata out of commits,
         // even when the target project has not configured its own .gitignore.
         run_git_command(
-            &path,
+            path,
             &[
                 "add",
                 "-A",
@@ -31,7 +36,7 @@ pub async fn git_add(path: String, files: Vec<String>) -> AppResult<String> {
         } else {
             let mut args = vec!["add", "--"];
             args.extend(files_to_add.iter().map(|s| s.as_str()));
-            run_git_command(&path, &args)
+            run_git_command(path, &args)
         }
     }
 }
@@ -39,6 +44,7 @@ pub async fn git_add(path: String, files: Vec<String>) -> AppResult<String> {
 #[tauri::command]
 #[specta::specta]
 pub async fn git_unstage(path: String, files: Vec<String>) -> AppResult<String> {
+    let _guard = lock_repo(&path, "取消暂存").await;
     if files.is_empty() {
         run_git_command(&path, &["reset", "HEAD"])
     } else {
@@ -61,6 +67,7 @@ pub async fn git_discard_files(
         ));
     }
 
+    let _guard = lock_repo(&path, "丢弃改动").await;
     if include_untracked {
         let mut args = vec!["clean", "-f", "--"];
         args.extend(files.iter().map(|s| s.as_str()));
@@ -78,39 +85,64 @@ pub async fn git_stash_push(path: String, message: Option<String>) -> AppResult<
     let label = message
         .filter(|m| !m.trim().is_empty())
         .unwrap_or_else(|| "CodeShelf stash".to_string());
+    let _guard = lock_repo(&path, "储藏").await;
     run_git_command(&path, &["stash", "push", "-u", "-m", &label])
 }
 
 #[tauri::command]
 #[specta::specta]
 pub async fn git_stash_pop(path: String) -> AppResult<String> {
+    let _guard = lock_repo(&path, "弹出储藏").await;
     run_git_command(&path, &["stash", "pop"])
 }
 
 #[tauri::command]
 #[specta::specta]
 pub async fn git_stash_apply(path: String) -> AppResult<String> {
+    let _guard = lock_repo(&path, "应用储藏").await;
     run_git_command(&path, &["stash", "apply"])
 }
 
 #[tauri::command]
 #[specta::specta]
 pub async fn git_revert_commit(path: String, commit_hash: String) -> AppResult<String> {
+    let _guard = lock_repo(&path, "回滚提交").await;
     run_git_command(&path, &["revert", "--no-edit", &commit_hash])
 }
 
 #[tauri::command]
 #[specta::specta]
 pub async fn git_cherry_pick(path: String, commit_hash: String) -> AppResult<String> {
+    let _guard = lock_repo(&path, "拣选提交").await;
     run_git_command(&path, &["cherry-pick", &commit_hash])
 }
 
+/// 提交前的身份检查；allow 为 true 表示用户已确认，跳过检查
+async fn ensure_identity(path: &str, allow: Option<bool>) -> AppResult<()> {
+    if allow.unwrap_or(false) {
+        return Ok(());
+    }
+    match super::identity::identity_mismatch(path).await {
+        Some(reason) => Err(crate::error::AppError::invalid(format!(
+            "提交身份可能不正确：{}",
+            reason
+        ))),
+        None => Ok(()),
+    }
+}
+
 #[tauri::command]
 #[specta::specta]
-pub async fn git_commit(path: String, message: String) -> AppResult<String> {
+pub async fn git_commit(
+    path: String,
+    message: String,
+    allow_identity_mismatch: Option<bool>,
+) -> AppResult<String> {
     if message.trim().is_empty() {
         return Err(crate::error::AppError::from("提交信息不能为空".to_string()));
     }
+    ensure_identity(&path, allow_identity_mismatch).await?;
+    let _guard = lock_repo(&path, "提交").await;
     run_git_command(&path, &["commit", "-m", &message])
 }
 
@@ -120,14 +152,17 @@ pub async fn git_add_and_commit(
     path: String,
     files: Vec<String>,
     message: String,
+    allow_identity_mismatch: Option<bool>,
 ) -> AppResult<String> {
     if message.trim().is_empty() {
         return Err(crate::error::AppError::from("提交信息不能为空".to_string()));
     }
 
-    // First add files
-    git_add(path.clone(), files).await?;
+    // 身份不符时在暂存前就拒绝，避免留下半完成的状态
+    ensure_identity(&path, allow_identity_mismatch).await?;
 
-    // Then commit
-    git_commit(path, message).await
+    // 暂存与提交在同一次排队中完成，中间不会插入其它写操作
+    let _guard = lock_repo(&path, "提交").await;
+    add_files(&path, files)?;
+    run_git_command(&path, &["commit", "-m", &message])
 }
@@ -1,19 +1,35 @@
 // 工作区状态与冲突处理：get_git_status / 冲突相关命令
 
 use super::{
-    is_system_junk_file, run_git_command, unquote_git_path, ConflictFileContent, GitStatus,
+    is_system_junk_file, lock_repo, run_git_command, unquote_git_path, ConflictFileContent,
+    GitStatus, RepoOperation,
 };
-use crate::error::AppResult;
+use crate::error::{AppError, AppResult};
+use std::path::{Path, PathBuf};
 
 #[tauri::command]
 #[specta::specta]
 pub async fn get_git_status(path: String) -> AppResult<GitStatus> {
     // Get current branch
-    let branch = run_git_command(&path, &["rev-parse", "--abbrev-ref", "HEAD"])
+    let mut branch = run_git_command(&path, &["rev-parse", "--abbrev-ref", "HEAD"])
         .unwrap_or_else(|_| "unknown".to_string());
+    let detached = branch == "HEAD";
+    let operation = detect_operation(&path);
+    if detached {
+        // 变基过程中 HEAD 是游离的，显示原分支名；否则显示短哈希
+        branch = operation
+            .as_ref()
+            .and_then(|op| op.head_name.clone())
+            .or_else(|| run_git_command(&path, &["rev-parse", "--short", "HEAD"]).ok())
+            .unwrap_or(branch);
+    }
 
     // Get status with -uall to show all untracked files recursively
-    let status_output = run_git_command(&path, &["status", "--porcelain", "-uall"])?;
+    // 不刷新索引，避免与排队中的写操作争抢 index.lock
+    let status_output = run_git_command(
+        &path,
+        &["--no-optional-locks", "status", "--porcelain", "-uall"],
+    )?;
 
     let mut staged = Vec::new();
     let mut unstaged = Vec::new();
@@ -72,9 +88,101 @@ pub async fn get_git_status(path: String) -> AppResult<GitStatus> {
         conflicted,
         ahead,
         behind,
+        lfs_missing: super::lfs::lfs_missing_count(&path),
+        detached,
+        operation,
+    })
+}
+
+fn git_dir(path: &str) -> Option<PathBuf> {
+    let dir = run_git_command(path, &["rev-parse", "--git-dir"]).ok()?;
+    let dir = PathBuf::from(dir);
+    Some(if dir.is_absolute() {
+        dir
+    } else {
+        Path::new(path).join(dir)
     })
 }
 
+fn read_state_file(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn read_state_number(path: &Path) -> Option<u32> {
+    read_state_file(path).and_then(|v| v.parse().ok())
+}
+
+fn ref_name(path: &str, sha: &str) -> Option<String> {
+    run_git_command(path, &["name-rev", "--name-only", "--no-undefined", sha])
+        .ok()
+        .filter(|v| !v.is_empty())
+}
+
+fn operation(kind: &str, path: &str, target: Option<String>) -> RepoOperation {
+    RepoOperation {
+        kind: kind.to_string(),
+        target_name: target.as_deref().and_then(|sha| ref_name(path, sha)),
+        target,
+        head_name: None,
+        step: None,
+        total: None,
+    }
+}
+
+/// 从 .git 下的状态文件识别进行中的操作
+fn detect_operation(path: &str) -> Option<RepoOperation> {
+    let dir = git_dir(path)?;
+
+    // rebase -i / rebase --merge（默认后端）
+    let merge_dir = dir.join("rebase-merge");
+    if merge_dir.is_dir() {
+        let mut op = operation("rebase", path, read_state_file(&merge_dir.join("onto")));
+        op.head_name = read_state_file(&merge_dir.join("head-name"))
+            .map(|v| v.trim_start_matches("refs/heads/").to_string());
+        op.step = read_state_number(&merge_dir.join("msgnum"));
+        op.total = read_state_number(&merge_dir.join("end"));
+        return Some(op);
+    }
+    // rebase --apply 与 git am 共用 rebase-apply，am 会留下 applying 标记
+    let apply_dir = dir.join("rebase-apply");
+    if apply_dir.is_dir() {
+        let kind = if apply_dir.join("applying").exists() {
+            "am"
+        } else {
+            "rebase"
+        };
+        let mut op = operation(kind, path, read_state_file(&apply_dir.join("onto")));
+        op.head_name = read_state_file(&apply_dir.join("head-name"))
+            .map(|v| v.trim_start_matches("refs/heads/").to_string());
+        op.step = read_state_number(&apply_dir.join("next"));
+        op.total = read_state_number(&apply_dir.join("last"));
+        return Some(op);
+    }
+
+    let heads = [
+        ("MERGE_HEAD", "merge"),
+        ("CHERRY_PICK_HEAD", "cherryPick"),
+        ("REVERT_HEAD", "revert"),
+    ];
+    for (file, kind) in heads {
+        if let Some(content) = read_state_file(&dir.join(file)) {
+            // 八爪鱼合并时 MERGE_HEAD 有多行，取第一个
+            let target = content.lines().next().map(|l| l.trim().to_string());
+            return Some(operation(kind, path, target));
+        }
+    }
+
+    if dir.join("BISECT_LOG").exists() {
+        let mut op = operation("bisect", path, None);
+        op.head_name = read_state_file(&dir.join("BISECT_START"));
+        return Some(op);
+    }
+    None
+}
+
 fn get_ahead_behind(path: &str) -> (u32, u32) {
     let output = run_git_command(
         path,
@@ -119,6 +227,7 @@ pub async fn git_checkout_conflict_version(
     file: String,
     version: String,
 ) -> AppResult<String> {
+    let _guard = lock_repo(&path, "解决冲突").await;
     match version.as_str() {
         "ours" => run_git_command(&path, &["checkout", "--ours", "--", &file])?,
         "theirs" => run_git_command(&path, &["checkout", "--theirs", "--", &file])?,
@@ -134,5 +243,23 @@ pub async fn git_checkout_conflict_version(
 #[tauri::command]
 #[specta::specta]
 pub async fn git_mark_resolved(path: String, file: String) -> AppResult<String> {
+    let _guard = lock_repo(&path, "标记已解决").await;
     run_git_command(&path, &["add", "--", &file])
 }
+
+/// 中止进行中的操作；kind 与 RepoOperation.kind 一致
+#[tauri::command]
+#[specta::specta]
+pub async fn abort_operation(path: String, kind: String) -> AppResult<String> {
+    let args: &[&str] = match kind.as_str() {
+        "merge" => &["merge", "--abort"],
+        "rebase" => &["rebase", "--abort"],
+        "am" => &["am", "--abort"],
+        "cherryPick" => &["cherry-pick", "--abort"],
+        "revert" => &["revert", "--abort"],
+        "bisect" => &["bisect", "reset"],
+        other => return Err(AppError::invalid(format!("不支持的操作类型: {}", other))),
+    };
+    let _guard = lock_repo(&path, "中止操作").await;
+    run_git_command(&path, args)
+}
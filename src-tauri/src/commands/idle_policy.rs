@@ -0,0 +1,309 @@
+// 空闲资源自动停止 - 后台巡检任务按设置中的 idle_policy 回收长时间闲置的资源
+//
+// - 静态服务：连续 server_idle_minutes 分钟没有请求后停止
+// - 转发规则：连续 forward_idle_minutes 分钟没有连接后停止
+// - 下载：按流量计费的网络下暂停进行中的下载，切回不计费网络（或关闭该选项）后恢复由这里暂停的任务
+// - Netcat 会话：netcat_idle_hours 小时没有收发数据后关闭
+// exempt_ids 中的资源不受影响。每次自动停止都会推送一条通知。
+// 计费网络检测：Windows 读连接配置的 NetworkCostType，Linux 读 NetworkManager 的 Metered 属性，
+// macOS 没有可用接口，视为不计费。结果缓存 CACHE_TTL。
+
+use super::settings::{load_app_settings, push_notification};
+use super::toolbox::netcat::{self, NetcatState, SessionStatus};
+use super::toolbox::{downloader, forwarder, server};
+use crate::error::AppResult;
+use crate::storage::IdlePolicySettings;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+/// 巡检间隔
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct IdlePolicyStatus {
+    /// 当前网络是否按流量计费
+    pub metered: bool,
+    /// 因计费网络被暂停、等待自动恢复的下载任务 id
+    pub paused_downloads: Vec<String>,
+}
+
+/// (读取时间, 是否计费)
+static METERED_CACHE: Lazy<Mutex<Option<(Instant, bool)>>> = Lazy::new(|| Mutex::new(None));
+
+/// 由巡检任务暂停的下载
+static PAUSED_DOWNLOADS: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+#[cfg(target_os = "windows")]
+fn read_metered() -> bool {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+    let script = "$null = [Windows.Networking.Connectivity.NetworkInformation,Windows.Networking.Connectivity,ContentType=WindowsRuntime]; \
+        $p = [Windows.Networking.Connectivity.NetworkInformation]::GetInternetConnectionProfile(); \
+        if ($p) { $p.GetConnectionCost().NetworkCostType }";
+    let Ok(output) = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", script])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+    else {
+        return false;
+    };
+    // Unrestricted / Unknown 不计费，Fixed / Variable 计费
+    matches!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        "Fixed" | "Variable"
+    )
+}
+
+#[cfg(target_os = "macos")]
+fn read_metered() -> bool {
+    false
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn read_metered() -> bool {
+    let Ok(output) = std::process::Command::new("busctl")
+        .args([
+            "get-property",
+            "org.freedesktop.NetworkManager",
+            "/org/freedesktop/NetworkManager",
+            "org.freedesktop.NetworkManager",
+            "Metered",
+        ])
+        .output()
+    else {
+        return false;
+    };
+    // 输出形如 "u 4"：1 = yes，3 = guess-yes
+    matches!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        "u 1" | "u 3"
+    )
+}
+
+fn is_metered() -> bool {
+    if let Ok(cache) = METERED_CACHE.lock() {
+        if let Some((at, metered)) = *cache {
+            if at.elapsed() < CACHE_TTL {
+                return metered;
+            }
+        }
+    }
+    let metered = read_metered();
+    if let Ok(mut cache) = METERED_CACHE.lock() {
+        *cache = Some((Instant::now(), metered));
+    }
+    metered
+}
+
+async fn metered() -> bool {
+    tokio::task::spawn_blocking(is_metered)
+        .await
+        .unwrap_or(false)
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// None 或 0 表示不启用
+fn enabled(value: Option<u32>) -> Option<u32> {
+    value.filter(|v| *v > 0)
+}
+
+async fn stop_idle_servers(app: &AppHandle, policy: &IdlePolicySettings) {
+    let Some(minutes) = enabled(policy.server_idle_minutes) else {
+        return;
+    };
+    let idle = server::idle_servers(Duration::from_secs(minutes as u64 * 60)).await;
+    for (id, name) in idle {
+        if policy.exempt_ids.contains(&id) {
+            continue;
+        }
+        log::info!("静态服务 {} 空闲超过 {} 分钟，自动停止", name, minutes);
+        if let Err(e) = server::stop_server(id).await {
+            log::warn!("自动停止静态服务失败: {}", e);
+            continue;
+        }
+        push_notification(
+            app,
+            "server",
+            "info",
+            "静态服务已自动停止",
+            &format!("「{}」已有 {} 分钟没有请求", name, minutes),
+        )
+        .await;
+    }
+}
+
+async fn stop_idle_forwards(app: &AppHandle, policy: &IdlePolicySettings) {
+    let Some(minutes) = enabled(policy.forward_idle_minutes) else {
+        return;
+    };
+    let idle = forwarder::idle_forward_rules(Duration::from_secs(minutes as u64 * 60)).await;
+    for (id, name) in idle {
+        if policy.exempt_ids.contains(&id) {
+            continue;
+        }
+        log::info!("转发规则 {} 空闲超过 {} 分钟，自动停止", name, minutes);
+        if let Err(e) = forwarder::stop_forwarding(id).await {
+            log::warn!("自动停止转发失败: {}", e);
+            continue;
+        }
+        push_notification(
+            app,
+            "server",
+            "info",
+            "端口转发已自动停止",
+            &format!("「{}」已有 {} 分钟没有连接", name, minutes),
+        )
+        .await;
+    }
+}
+
+fn paused_downloads() -> Vec<String> {
+    PAUSED_DOWNLOADS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .cloned()
+        .collect()
+}
+
+async fn apply_metered_policy(app: &AppHandle, policy: &IdlePolicySettings) {
+    let paused = paused_downloads();
+    if !policy.pause_downloads_on_metered && paused.is_empty() {
+        return;
+    }
+    let Ok(tasks) = downloader::get_download_tasks().await else {
+        return;
+    };
+
+    if policy.pause_downloads_on_metered && metered().await {
+        let mut names = Vec::new();
+        for task in tasks {
+            if task.status != "downloading" || policy.exempt_ids.contains(&task.id) {
+                continue;
+            }
+            if downloader::pause_download(task.id.clone()).await.is_ok() {
+                PAUSED_DOWNLOADS
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(task.id);
+                names.push(task.file_name);
+            }
+        }
+        if !names.is_empty() {
+            log::info!("当前为计费网络，暂停 {} 个下载", names.len());
+            push_notification(
+                app,
+                "download",
+                "info",
+                "计费网络，下载已暂停",
+                &format!("{}，切回不计费网络后自动恢复", names.join("、")),
+            )
+            .await;
+        }
+        return;
+    }
+
+    // 不再计费（或关闭了该选项）：恢复仍处于暂停状态的任务，用户已手动处理的不再管
+    for id in paused {
+        let still_paused = tasks.iter().any(|t| t.id == id && t.status == "paused");
+        if still_paused {
+            if let Err(e) = downloader::resume_download(id.clone()).await {
+                log::warn!("自动恢复下载失败: {}", e);
+            }
+        }
+        PAUSED_DOWNLOADS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&id);
+    }
+}
+
+async fn close_idle_netcat_sessions(app: &AppHandle, policy: &IdlePolicySettings) {
+    let Some(hours) = enabled(policy.netcat_idle_hours) else {
+        return;
+    };
+    let Some(state) = app.try_state::<NetcatState>() else {
+        return;
+    };
+    let limit = hours as u64 * 3600 * 1000;
+    let now = now_millis();
+
+    let mut idle = Vec::new();
+    {
+        let sessions = state.sessions.read().await;
+        for (id, session) in sessions.iter() {
+            let guard = session.read().await;
+            let s = &guard.session;
+            if !matches!(
+                s.status,
+                SessionStatus::Connecting | SessionStatus::Connected | SessionStatus::Listening
+            ) || policy.exempt_ids.contains(id)
+            {
+                continue;
+            }
+            let last = s.last_activity.or(s.connected_at).unwrap_or(s.created_at);
+            if now.saturating_sub(last) >= limit {
+                idle.push((id.clone(), s.name.clone()));
+            }
+        }
+    }
+
+    for (id, name) in idle {
+        log::info!("Netcat 会话 {} 空闲超过 {} 小时，自动关闭", name, hours);
+        if let Err(e) = netcat::netcat_stop_session(state.clone(), id).await {
+            log::warn!("自动关闭 Netcat 会话失败: {}", e);
+            continue;
+        }
+        push_notification(
+            app,
+            "server",
+            "info",
+            "Netcat 会话已自动关闭",
+            &format!("「{}」已有 {} 小时没有收发数据", name, hours),
+        )
+        .await;
+    }
+}
+
+/// 启动后台巡检任务；每轮重新读取设置，修改策略后下一轮生效
+pub fn spawn_idle_janitor(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(SWEEP_INTERVAL).await;
+            let policy = load_app_settings()
+                .await
+                .map(|s| s.idle_policy)
+                .unwrap_or_default();
+            stop_idle_servers(&app, &policy).await;
+            stop_idle_forwards(&app, &policy).await;
+            apply_metered_policy(&app, &policy).await;
+            close_idle_netcat_sessions(&app, &policy).await;
+        }
+    });
+}
+
+/// 获取计费网络状态与因此暂停的下载
+#[tauri::command]
+#[specta::specta]
+pub async fn get_idle_policy_status() -> AppResult<IdlePolicyStatus> {
+    let mut paused_downloads = paused_downloads();
+    paused_downloads.sort();
+    Ok(IdlePolicyStatus {
+        metered: metered().await,
+        paused_downloads,
+    })
+}
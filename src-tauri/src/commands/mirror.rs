@@ -0,0 +1,407 @@
+//! 仓库镜像 / 备份任务
+//!
+//! 两种模式：
+//! - push：`git push --mirror <target>`，target 中的 `{name}` 替换为项目目录名；
+//! - bundle：`git bundle create <dir>/<name>-<时间>.bundle --all`，按 keep_bundles 清理旧文件，
+//!   只清理文件名完全符合 `<name>-YYYYmmdd-HHMMSS.bundle` 的备份。
+//!
+//! 调度器每分钟检查一次到期任务，失败时写入通知。
+
+use crate::error::AppResult;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::{AppHandle, Emitter};
+
+use crate::commands::git::run_git_command;
+use crate::commands::settings::push_notification;
+use crate::storage::persisted_store::backup_invalid_file;
+use crate::storage::{current_iso_time, generate_id, get_storage_config};
+
+/// 每个任务保留的运行历史条数
+const MAX_HISTORY: usize = 50;
+
+/// 调度器检查间隔
+const TICK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 正在执行的任务 id，防止调度器与手动触发重入
+static RUNNING_JOBS: Lazy<std::sync::Mutex<HashSet<String>>> =
+    Lazy::new(|| std::sync::Mutex::new(HashSet::new()));
+
+// ========== 数据模型 ==========
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MirrorProjectResult {
+    pub path: String,
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MirrorRun {
+    pub started_at: String,
+    pub finished_at: String,
+    pub status: String, // "success" | "failure" | "partial"
+    pub results: Vec<MirrorProjectResult>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MirrorJob {
+    pub id: String,
+    pub name: String,
+    pub mode: String, // "push" | "bundle"
+    /// push 模式为远程 URL（支持 `{name}` 占位），bundle 模式为备份目录
+    pub target: String,
+    pub project_paths: Vec<String>,
+    /// 执行间隔（分钟），0 表示仅手动执行
+    #[serde(default)]
+    pub interval_minutes: u32,
+    /// bundle 模式下每个项目保留的备份份数
+    #[serde(default = "default_keep_bundles")]
+    pub keep_bundles: u32,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// 低电量省电模式下仍然执行
+    #[serde(default)]
+    pub allow_on_battery: bool,
+    #[serde(default)]
+    pub history: Vec<MirrorRun>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+fn default_keep_bundles() -> u32 {
+    5
+}
+
+fn default_true() -> bool {
+    true
+}
+
+// ========== 存储 ==========
+
+fn load_jobs() -> AppResult<Vec<MirrorJob>> {
+    let config = get_storage_config()?;
+    let path = config.mirror_jobs_file();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path)
+        .map_err(|e| crate::error::AppError::from(format!("读取镜像任务失败: {}", e)))?;
+    match serde_json::from_str(&content) {
+        Ok(jobs) => Ok(jobs),
+        Err(e) => {
+            log::error!("解析镜像任务失败: {}", e);
+            // 备份后移走原文件：下次保存不会覆盖原有任务，之后的读取也不会重复备份
+            if backup_invalid_file(&path, "镜像任务").is_some() {
+                let _ = fs::remove_file(&path);
+            }
+            Ok(Vec::new())
+        }
+    }
+}
+
+fn save_jobs(jobs: &[MirrorJob]) -> AppResult<()> {
+    let config = get_storage_config()?;
+    config.ensure_dirs()?;
+    let content = serde_json::to_string_pretty(jobs)
+        .map_err(|e| crate::error::AppError::from(format!("序列化镜像任务失败: {}", e)))?;
+    fs::write(config.mirror_jobs_file(), content)
+        .map_err(|e| crate::error::AppError::from(format!("保存镜像任务失败: {}", e)))
+}
+
+fn validate_job(job: &MirrorJob) -> AppResult<()> {
+    if job.name.trim().is_empty() {
+        return Err("name 不能为空".into());
+    }
+    if job.target.trim().is_empty() {
+        return Err("target 不能为空".into());
+    }
+    if job.project_paths.is_empty() {
+        return Err("至少选择一个项目".into());
+    }
+    match job.mode.as_str() {
+        "push" => {
+            if job.project_paths.len() > 1 && !job.target.contains("{name}") {
+                return Err("多个项目推送到同一远程时，target 需包含 {name} 占位".into());
+            }
+        }
+        "bundle" => {}
+        other => {
+            return Err(crate::error::AppError::from(format!(
+                "未知镜像模式: {}",
+                other
+            )))
+        }
+    }
+    Ok(())
+}
+
+// ========== 执行 ==========
+
+fn project_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "repo".to_string())
+}
+
+fn mirror_push(path: &str, target: &str) -> AppResult<String> {
+    let url = target.replace("{name}", &project_name(path));
+    run_git_command(path, &["push", "--mirror", &url])?;
+    Ok(format!("已推送到 {}", url))
+}
+
+/// 文件名是否为 `<name>-YYYYmmdd-HHMMSS.bundle`
+fn is_own_bundle(file_name: &str, name: &str) -> bool {
+    let Some(stamp) = file_name
+        .strip_prefix(name)
+        .and_then(|s| s.strip_prefix('-'))
+        .and_then(|s| s.strip_suffix(".bundle"))
+    else {
+        return false;
+    };
+    chrono::NaiveDateTime::parse_from_str(stamp, "%Y%m%d-%H%M%S").is_ok()
+}
+
+fn mirror_bundle(path: &str, dir: &str, keep: u32) -> AppResult<String> {
+    let dir = Path::new(dir);
+    fs::create_dir_all(dir)
+        .map_err(|e| crate::error::AppError::from(format!("创建备份目录失败: {}", e)))?;
+
+    let name = project_name(path);
+    let file = dir.join(format!(
+        "{}-{}.bundle",
+        name,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    let file_str = file.to_string_lossy().to_string();
+    run_git_command(path, &["bundle", "create", &file_str, "--all"])?;
+
+    // 清理旧备份：文件名带时间戳，按名称排序即按时间排序。
+    // 只认完整的 `<name>-<时间>.bundle`，避免误删 `<name>-web-<时间>.bundle` 这类其他项目的备份
+    let mut bundles: Vec<_> = fs::read_dir(dir)
+        .map_err(|e| crate::error::AppError::from(format!("读取备份目录失败: {}", e)))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|s| s.to_str())
+                .map(|s| is_own_bundle(s, &name))
+                .unwrap_or(false)
+        })
+        .collect();
+    bundles.sort();
+    let keep = keep.max(1) as usize;
+    if bundles.len() > keep {
+        for old in &bundles[..bundles.len() - keep] {
+            let _ = fs::remove_file(old);
+        }
+    }
+
+    Ok(format!("已生成 {}", file_str))
+}
+
+async fn execute_job(app: &AppHandle, id: &str) -> AppResult<MirrorRun> {
+    {
+        let mut running = RUNNING_JOBS.lock().map_err(|e| e.to_string())?;
+        if !running.insert(id.to_string()) {
+            return Err("任务正在执行中".into());
+        }
+    }
+    let result = execute_job_inner(app, id).await;
+    if let Ok(mut running) = RUNNING_JOBS.lock() {
+        running.remove(id);
+    }
+    result
+}
+
+async fn execute_job_inner(app: &AppHandle, id: &str) -> AppResult<MirrorRun> {
+    let job = load_jobs()?
+        .into_iter()
+        .find(|j| j.id == id)
+        .ok_or("镜像任务不存在")?;
+
+    let started_at = current_iso_time();
+    let mut results = Vec::new();
+    for path in &job.project_paths {
+        let path_c = path.clone();
+        let mode = job.mode.clone();
+        let target = job.target.clone();
+        let keep = job.keep_bundles;
+        let outcome = tokio::task::spawn_blocking(move || match mode.as_str() {
+            "bundle" => mirror_bundle(&path_c, &target, keep),
+            _ => mirror_push(&path_c, &target),
+        })
+        .await
+        .map_err(|e| crate::error::AppError::from(format!("任务执行失败: {}", e)))?;
+
+        results.push(match outcome {
+            Ok(message) => MirrorProjectResult {
+                path: path.clone(),
+                success: true,
+                message,
+            },
+            Err(e) => MirrorProjectResult {
+                path: path.clone(),
+                success: false,
+                message: e.to_string(),
+            },
+        });
+    }
+
+    let failed = results.iter().filter(|r| !r.success).count();
+    let status = if failed == 0 {
+        "success"
+    } else if failed == results.len() {
+        "failure"
+    } else {
+        "partial"
+    };
+    let run = MirrorRun {
+        started_at,
+        finished_at: current_iso_time(),
+        status: status.to_string(),
+        results,
+    };
+
+    // 重新读取最新列表再写回，避免覆盖执行期间的编辑
+    let mut jobs = load_jobs()?;
+    if let Some(latest) = jobs.iter_mut().find(|j| j.id == id) {
+        latest.history.insert(0, run.clone());
+        latest.history.truncate(MAX_HISTORY);
+    }
+    save_jobs(&jobs)?;
+
+    if failed > 0 {
+        let failed_names: Vec<String> = run
+            .results
+            .iter()
+            .filter(|r| !r.success)
+            .map(|r| project_name(&r.path))
+            .collect();
+        push_notification(
+            app,
+            "git",
+            "error",
+            &format!("镜像任务「{}」失败", job.name),
+            &format!("{} 个项目失败: {}", failed, failed_names.join(", ")),
+        )
+        .await;
+    }
+
+    let _ = app.emit("mirror-run-changed", json!({"id": id, "status": status}));
+    Ok(run)
+}
+
+fn is_due(job: &MirrorJob, now: DateTime<Utc>) -> bool {
+    if !job.enabled || job.interval_minutes == 0 {
+        return false;
+    }
+    let Some(last) = job.history.first() else {
+        return true;
+    };
+    match DateTime::parse_from_rfc3339(&last.started_at) {
+        Ok(t) => {
+            now.signed_duration_since(t.with_timezone(&Utc))
+                .num_minutes()
+                >= job.interval_minutes as i64
+        }
+        Err(_) => true,
+    }
+}
+
+/// 启动镜像调度器：每分钟扫描一次到期任务
+pub fn spawn_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(TICK_INTERVAL).await;
+            let now = Utc::now();
+            let due: Vec<MirrorJob> = load_jobs()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|j| is_due(j, now))
+                .collect();
+            for job in due {
+                // 低电量时推迟，下个周期仍然到期，恢复供电后补跑
+                if super::power::should_defer(&job.name, job.allow_on_battery).await {
+                    continue;
+                }
+                let id = job.id;
+                if let Err(e) = execute_job(&app, &id).await {
+                    log::warn!("镜像任务 {} 执行失败: {}", id, e);
+                }
+            }
+        }
+    });
+}
+
+// ========== Tauri 命令 ==========
+
+#[tauri::command]
+#[specta::specta]
+pub async fn mirror_job_list() -> AppResult<Vec<MirrorJob>> {
+    load_jobs()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn mirror_job_save(job: MirrorJob) -> AppResult<MirrorJob> {
+    let mut job = job;
+    validate_job(&job)?;
+    let mut jobs = load_jobs()?;
+    job.updated_at = current_iso_time();
+    if job.id.trim().is_empty() {
+        job.id = generate_id();
+        job.created_at = job.updated_at.clone();
+        job.history = Vec::new();
+        jobs.push(job.clone());
+    } else if let Some(existing) = jobs.iter_mut().find(|j| j.id == job.id) {
+        // 历史记录由后端维护，保存配置时保留
+        job.history = std::mem::take(&mut existing.history);
+        job.created_at = existing.created_at.clone();
+        *existing = job.clone();
+    } else {
+        return Err("镜像任务不存在".into());
+    }
+    save_jobs(&jobs)?;
+    Ok(job)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn mirror_job_delete(id: String) -> AppResult<()> {
+    let mut jobs = load_jobs()?;
+    jobs.retain(|j| j.id != id);
+    save_jobs(&jobs)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn mirror_job_set_enabled(id: String, enabled: bool) -> AppResult<MirrorJob> {
+    let mut jobs = load_jobs()?;
+    let job = jobs
+        .iter_mut()
+        .find(|j| j.id == id)
+        .ok_or("镜像任务不存在")?;
+    job.enabled = enabled;
+    job.updated_at = current_iso_time();
+    let updated = job.clone();
+    save_jobs(&jobs)?;
+    Ok(updated)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn mirror_job_run_now(app: AppHandle, id: String) -> AppResult<MirrorRun> {
+    execute_job(&app, &id).await
+}
@@ -1,16 +1,40 @@
 pub mod api_chat;
+pub mod app_profiles;
+pub mod bulk;
 pub mod chat;
 pub mod chat_bridge;
+pub mod commit_index;
+pub mod compliance;
+pub mod confirm;
+pub mod dashboard;
+pub mod deploy;
+pub mod divergence;
+pub mod docs_preview;
+pub mod doctor;
 pub mod extras;
 pub mod git;
+pub mod idle_policy;
+pub mod mirror;
+pub mod operations;
+pub mod path_roots;
+pub mod power;
 pub mod project;
+pub mod project_links;
+pub mod project_relocate;
+pub mod project_tasks;
 pub mod resume;
 pub mod resume_node_agent;
 pub mod resume_docx;
+pub mod runtime_overview;
+pub mod scratchpad;
 pub mod settings;
 pub mod stats;
 pub mod storage_admin;
 pub mod system;
+pub mod terminal;
 pub mod toolbox;
+pub mod updater;
+pub mod usage_stats;
 pub mod tools;
 pub mod workflows;
+pub mod workspace;
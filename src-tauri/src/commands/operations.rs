@@ -0,0 +1,225 @@
+// 可取消的长耗时操作
+//
+// fetch / pull / push 这类网络操作遇到无响应的远程时会一直挂住。
+// 命令开始时用前端传入的 op_id 登记一个 Operation，子进程通过 Operation::output 启动并登记 pid；
+// cancel_operation(op_id) 置取消标志并结束子进程树，超时（timeout_secs）同样会结束子进程。
+// Operation 析构时自动从登记表移除。
+
+use crate::error::{AppError, AppResult};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::Read;
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// 等待子进程时检查取消标志的间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationInfo {
+    pub id: String,
+    pub label: String,
+    pub timeout_secs: Option<u64>,
+    pub started_at: String,
+}
+
+struct OperationState {
+    info: OperationInfo,
+    cancelled: AtomicBool,
+    pids: Mutex<Vec<u32>>,
+}
+
+static OPERATIONS: Lazy<Mutex<HashMap<String, Arc<OperationState>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn operations() -> std::sync::MutexGuard<'static, HashMap<String, Arc<OperationState>>> {
+    OPERATIONS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 结束进程及其子进程（git 会再拉起 ssh / remote-https 等子进程）
+pub(crate) fn kill_process_tree(pid: u32) {
+    #[cfg(target_os = "windows")]
+    {
+        let _ = Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/T", "/F"])
+            .creation_flags(CREATE_NO_WINDOW)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = Command::new("pkill")
+            .args(["-9", "-P", &pid.to_string()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+        let _ = Command::new("kill")
+            .args(["-9", &pid.to_string()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+}
+
+/// 一次登记中的操作，析构时移出登记表
+pub struct Operation {
+    state: Arc<OperationState>,
+    started: Instant,
+    timeout: Option<Duration>,
+}
+
+impl Operation {
+    /// 登记操作；op_id 为空时生成一个（此时前端无法取消，只有超时生效）
+    pub fn begin(op_id: Option<String>, label: &str, timeout_secs: Option<u64>) -> AppResult<Self> {
+        let id = op_id
+            .filter(|id| !id.trim().is_empty())
+            .unwrap_or_else(crate::storage::generate_id);
+        let timeout_secs = timeout_secs.filter(|secs| *secs > 0);
+        let state = Arc::new(OperationState {
+            info: OperationInfo {
+                id: id.clone(),
+                label: label.to_string(),
+                timeout_secs,
+                started_at: crate::storage::current_iso_time(),
+            },
+            cancelled: AtomicBool::new(false),
+            pids: Mutex::new(Vec::new()),
+        });
+        let mut operations = operations();
+        if operations.contains_key(&id) {
+            return Err(AppError::invalid(format!("操作 {} 正在进行中", id)));
+        }
+        operations.insert(id, state.clone());
+        Ok(Self {
+            state,
+            started: Instant::now(),
+            timeout: timeout_secs.map(Duration::from_secs),
+        })
+    }
+
+    /// 登记的操作 id（未传 op_id 时为生成的 id）
+    pub fn id(&self) -> &str {
+        &self.state.info.id
+    }
+
+    fn timed_out(&self) -> bool {
+        self.timeout.is_some_and(|t| self.started.elapsed() >= t)
+    }
+
+    /// 已取消或超时时返回错误，供分多步执行的操作在步骤之间检查
+    pub fn check(&self) -> AppResult<()> {
+        if self.state.cancelled.load(Ordering::SeqCst) {
+            return Err(AppError::other(format!("{}已取消", self.state.info.label)));
+        }
+        if self.timed_out() {
+            return Err(AppError::other(format!(
+                "{}超时（{} 秒）",
+                self.state.info.label,
+                self.timeout.map(|t| t.as_secs()).unwrap_or_default()
+            )));
+        }
+        Ok(())
+    }
+
+    /// 启动子进程并等待结束，期间响应取消与超时；stdout / stderr 全部收集
+    pub fn output(&self, cmd: &mut Command) -> AppResult<Output> {
+        self.check()?;
+        let mut child = cmd
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let pid = child.id();
+        self.lock_pids().push(pid);
+
+        // 两个管道分别在线程里读，避免输出过多时子进程写满管道阻塞
+        let stdout = child.stdout.take().map(spawn_pipe_reader);
+        let stderr = child.stderr.take().map(spawn_pipe_reader);
+
+        let result = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break Ok(status),
+                Ok(None) => {}
+                Err(e) => break Err(AppError::from(e)),
+            }
+            if let Err(e) = self.check() {
+                kill_process_tree(pid);
+                let _ = child.wait();
+                break Err(e);
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        };
+        self.lock_pids().retain(|p| *p != pid);
+
+        let collect = |reader: Option<std::thread::JoinHandle<Vec<u8>>>| {
+            reader.and_then(|h| h.join().ok()).unwrap_or_default()
+        };
+        let (stdout, stderr) = (collect(stdout), collect(stderr));
+        Ok(Output {
+            status: result?,
+            stdout,
+            stderr,
+        })
+    }
+
+    fn lock_pids(&self) -> std::sync::MutexGuard<'_, Vec<u32>> {
+        self.state.pids.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Drop for Operation {
+    fn drop(&mut self) {
+        let mut operations = operations();
+        if operations
+            .get(&self.state.info.id)
+            .is_some_and(|s| Arc::ptr_eq(s, &self.state))
+        {
+            operations.remove(&self.state.info.id);
+        }
+    }
+}
+
+fn spawn_pipe_reader<R: Read + Send + 'static>(mut pipe: R) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        buf
+    })
+}
+
+/// 取消进行中的操作：置取消标志并结束其子进程
+#[tauri::command]
+#[specta::specta]
+pub async fn cancel_operation(op_id: String) -> AppResult<()> {
+    let state = operations()
+        .get(&op_id)
+        .cloned()
+        .ok_or_else(|| AppError::invalid("操作不存在或已结束"))?;
+    state.cancelled.store(true, Ordering::SeqCst);
+    let pids = state.pids.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    for pid in pids {
+        kill_process_tree(pid);
+    }
+    Ok(())
+}
+
+/// 进行中的操作（按开始时间排序）
+#[tauri::command]
+#[specta::specta]
+pub async fn list_operations() -> AppResult<Vec<OperationInfo>> {
+    let mut list: Vec<OperationInfo> = operations().values().map(|s| s.info.clone()).collect();
+    list.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+    Ok(list)
+}
@@ -0,0 +1,345 @@
+// 路径根目录 - 命名的基础目录（如 "work" -> D:\code），让项目路径可以随根目录整体迁移
+//
+// 项目落在某个根目录下时记录 root_id 与相对路径（统一用 / 分隔），
+// projects.path 仍保存解析后的绝对路径，其它读取项目路径的地方不受影响。
+// 根目录移动（换盘符、换机器、同步到另一台电脑）后调用 remap_path_root，
+// 按新根目录 + 相对路径重算所有项目的 path。
+// 按路径缓存的统计 / 提交索引不会迁移，下次访问时按新路径重建。
+
+use crate::error::{AppError, AppResult};
+use crate::storage::db::pool;
+use crate::storage::{current_iso_time, generate_id};
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PathRoot {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    /// 绑定在该根目录下的项目数
+    pub project_count: u32,
+    /// 根目录在本机是否存在
+    pub exists: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RemappedProject {
+    pub id: String,
+    pub name: String,
+    pub old_path: String,
+    pub new_path: String,
+    /// 新路径在本机是否存在
+    pub exists: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RemapReport {
+    pub root: PathRoot,
+    pub projects: Vec<RemappedProject>,
+    /// 为 true 时只预览，未写入
+    pub dry_run: bool,
+}
+
+// ============ helpers ============
+
+type RootRow = (
+    String, // id
+    String, // name
+    String, // path
+    String, // created_at
+    String, // updated_at
+    i64,    // project_count
+);
+
+const ROOT_SELECT: &str = "SELECT r.id, r.name, r.path, r.created_at, r.updated_at, \
+     (SELECT COUNT(*) FROM projects p WHERE p.root_id = r.id) FROM path_roots r";
+
+fn root_from_row(row: RootRow) -> PathRoot {
+    let (id, name, path, created_at, updated_at, project_count) = row;
+    PathRoot {
+        exists: Path::new(&path).is_dir(),
+        id,
+        name,
+        path,
+        project_count: project_count.max(0) as u32,
+        created_at,
+        updated_at,
+    }
+}
+
+async fn fetch_root(id: &str) -> AppResult<PathRoot> {
+    let row: Option<RootRow> = sqlx::query_as(&format!("{} WHERE r.id = ?", ROOT_SELECT))
+        .bind(id)
+        .fetch_optional(pool())
+        .await
+        .map_err(|e| AppError::from(format!("查询路径根目录失败: {}", e)))?;
+    row.map(root_from_row)
+        .ok_or_else(|| AppError::from("路径根目录不存在".to_string()))
+}
+
+/// 规范化根目录路径：必须是绝对路径，去掉末尾分隔符
+fn normalize_root(path: &str) -> AppResult<String> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        return Err(AppError::invalid("根目录路径不能为空"));
+    }
+    let buf: PathBuf = Path::new(trimmed).components().collect();
+    if !buf.is_absolute() {
+        return Err(AppError::invalid(format!(
+            "根目录必须是绝对路径: {}",
+            trimmed
+        )));
+    }
+    Ok(buf.to_string_lossy().to_string())
+}
+
+/// path 位于 root 下时返回用 / 连接的相对路径；与 root 相同时为空串
+fn relative_to(root: &str, path: &str) -> Option<String> {
+    let rel = Path::new(path).strip_prefix(Path::new(root)).ok()?;
+    let mut parts = Vec::new();
+    for component in rel.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_string_lossy().to_string()),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    Some(parts.join("/"))
+}
+
+fn resolve(root: &str, relative: &str) -> String {
+    let mut buf = PathBuf::from(root);
+    buf.extend(relative.split('/').filter(|p| !p.is_empty()));
+    buf.to_string_lossy().to_string()
+}
+
+/// 找出包含 path 的最深的根目录，返回 (root_id, 相对路径)
+async fn match_root(path: &str) -> AppResult<Option<(String, String)>> {
+    let roots: Vec<(String, String)> = sqlx::query_as("SELECT id, path FROM path_roots")
+        .fetch_all(pool())
+        .await
+        .map_err(|e| AppError::from(format!("查询路径根目录失败: {}", e)))?;
+    Ok(roots
+        .into_iter()
+        .filter_map(|(id, root)| relative_to(&root, path).map(|rel| (root.len(), id, rel)))
+        .max_by_key(|(len, _, _)| *len)
+        .map(|(_, id, rel)| (id, rel)))
+}
+
+async fn set_binding(project_id: &str, binding: Option<&(String, String)>) -> AppResult<()> {
+    sqlx::query("UPDATE projects SET root_id = ?, relative_path = ? WHERE id = ?")
+        .bind(binding.map(|(id, _)| id))
+        .bind(binding.map(|(_, rel)| rel))
+        .bind(project_id)
+        .execute(pool())
+        .await
+        .map_err(|e| AppError::from(format!("更新项目根目录失败: {}", e)))?;
+    Ok(())
+}
+
+/// 按当前绝对路径重新计算所有项目的根目录绑定
+async fn rebind_all() -> AppResult<()> {
+    let projects: Vec<(String, String)> = sqlx::query_as("SELECT id, path FROM projects")
+        .fetch_all(pool())
+        .await
+        .map_err(|e| AppError::from(format!("查询项目失败: {}", e)))?;
+    for (id, path) in projects {
+        let binding = match_root(&path).await?;
+        set_binding(&id, binding.as_ref()).await?;
+    }
+    Ok(())
+}
+
+/// 新建 / 导入项目后调用：落在某个根目录下时记录绑定，返回 root_id
+pub(crate) async fn bind_project(project_id: &str, path: &str) -> Option<String> {
+    let binding = match match_root(path).await {
+        Ok(binding) => binding,
+        Err(e) => {
+            log::warn!("匹配项目根目录失败: {}", e);
+            return None;
+        }
+    };
+    if let Err(e) = set_binding(project_id, binding.as_ref()).await {
+        log::warn!("{}", e);
+        return None;
+    }
+    binding.map(|(id, _)| id)
+}
+
+// ============ commands ============
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_path_roots() -> AppResult<Vec<PathRoot>> {
+    let rows: Vec<RootRow> = sqlx::query_as(&format!("{} ORDER BY r.name", ROOT_SELECT))
+        .fetch_all(pool())
+        .await
+        .map_err(|e| AppError::from(format!("查询路径根目录失败: {}", e)))?;
+    Ok(rows.into_iter().map(root_from_row).collect())
+}
+
+/// 添加根目录，已有项目中落在其下的会自动绑定（嵌套根目录取最深的一个）
+#[tauri::command]
+#[specta::specta]
+pub async fn add_path_root(name: String, path: String) -> AppResult<PathRoot> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::invalid("根目录名称不能为空"));
+    }
+    let path = normalize_root(&path)?;
+
+    let id = generate_id();
+    let now = current_iso_time();
+    sqlx::query(
+        "INSERT INTO path_roots (id, name, path, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(&id)
+    .bind(&name)
+    .bind(&path)
+    .bind(&now)
+    .bind(&now)
+    .execute(pool())
+    .await
+    .map_err(|e| {
+        if e.to_string().contains("UNIQUE") {
+            AppError::invalid(format!("根目录名称已存在: {}", name))
+        } else {
+            AppError::from(format!("添加路径根目录失败: {}", e))
+        }
+    })?;
+
+    rebind_all().await?;
+    fetch_root(&id).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn rename_path_root(id: String, name: String) -> AppResult<PathRoot> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::invalid("根目录名称不能为空"));
+    }
+    let result = sqlx::query("UPDATE path_roots SET name = ?, updated_at = ? WHERE id = ?")
+        .bind(&name)
+        .bind(current_iso_time())
+        .bind(&id)
+        .execute(pool())
+        .await
+        .map_err(|e| {
+            if e.to_string().contains("UNIQUE") {
+                AppError::invalid(format!("根目录名称已存在: {}", name))
+            } else {
+                AppError::from(format!("重命名路径根目录失败: {}", e))
+            }
+        })?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::from("路径根目录不存在".to_string()));
+    }
+    fetch_root(&id).await
+}
+
+/// 删除根目录；项目保留当前绝对路径，改绑到其它包含它的根目录（如有）
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_path_root(id: String) -> AppResult<()> {
+    let result = sqlx::query("DELETE FROM path_roots WHERE id = ?")
+        .bind(&id)
+        .execute(pool())
+        .await
+        .map_err(|e| AppError::from(format!("删除路径根目录失败: {}", e)))?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::from("路径根目录不存在".to_string()));
+    }
+    rebind_all().await
+}
+
+/// 根目录移动后重算其下项目的路径。dry_run 为 true 时只返回预览
+#[tauri::command]
+#[specta::specta]
+pub async fn remap_path_root(
+    id: String,
+    new_path: String,
+    dry_run: Option<bool>,
+) -> AppResult<RemapReport> {
+    let dry_run = dry_run.unwrap_or(false);
+    let new_root = normalize_root(&new_path)?;
+    let root = fetch_root(&id).await?;
+
+    let rows: Vec<(String, String, String, Option<String>)> = sqlx::query_as(
+        "SELECT id, name, path, relative_path FROM projects WHERE root_id = ? ORDER BY name",
+    )
+    .bind(&id)
+    .fetch_all(pool())
+    .await
+    .map_err(|e| AppError::from(format!("查询项目失败: {}", e)))?;
+
+    let mut projects = Vec::with_capacity(rows.len());
+    for (project_id, name, old_path, relative) in rows {
+        let relative = relative
+            .or_else(|| relative_to(&root.path, &old_path))
+            .unwrap_or_default();
+        let new_path = resolve(&new_root, &relative);
+        projects.push(RemappedProject {
+            exists: Path::new(&new_path).exists(),
+            id: project_id,
+            name,
+            old_path,
+            new_path,
+        });
+    }
+
+    if dry_run {
+        let mut preview = root;
+        preview.path = new_root;
+        preview.exists = Path::new(&preview.path).is_dir();
+        return Ok(RemapReport {
+            root: preview,
+            projects,
+            dry_run,
+        });
+    }
+
+    let now = current_iso_time();
+    let mut tx = pool()
+        .begin()
+        .await
+        .map_err(|e| AppError::from(format!("开启事务失败: {}", e)))?;
+    sqlx::query("UPDATE path_roots SET path = ?, updated_at = ? WHERE id = ?")
+        .bind(&new_root)
+        .bind(&now)
+        .bind(&id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::from(format!("更新路径根目录失败: {}", e)))?;
+    for project in &projects {
+        sqlx::query("UPDATE projects SET path = ?, updated_at = ? WHERE id = ?")
+            .bind(&project.new_path)
+            .bind(&now)
+            .bind(&project.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::from(format!("更新项目路径失败: {}", e)))?;
+    }
+    tx.commit()
+        .await
+        .map_err(|e| AppError::from(format!("提交事务失败: {}", e)))?;
+    crate::git_executable::reload().await;
+
+    log::info!(
+        "路径根目录 {} 迁移到 {}，更新 {} 个项目",
+        root.name,
+        new_root,
+        projects.len()
+    );
+    Ok(RemapReport {
+        root: fetch_root(&id).await?,
+        projects,
+        dry_run,
+    })
+}
@@ -0,0 +1,162 @@
+// 电源感知 - 笔记本使用电池且电量低于阈值时，暂停较重的后台任务
+//
+// 受影响的后台任务：统计增量刷新（refresh_dirty_stats）、定时工作流、镜像任务。
+// 工作流 / 镜像任务可单独设置 allow_on_battery 不受限制。
+// 电源状态读取有一定开销（Windows 走系统 API，macOS 调 pmset），结果缓存 CACHE_TTL。
+
+use crate::error::AppResult;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerStatus {
+    /// 是否检测到电池（台式机为 false）
+    pub has_battery: bool,
+    /// 当前是否由电池供电
+    pub on_battery: bool,
+    /// 电量百分比，未知时为 None
+    pub battery_percent: Option<u8>,
+    /// 是否启用了低电量暂停
+    pub saver_enabled: bool,
+    pub saver_threshold: u8,
+    /// 后台任务当前是否被暂停
+    pub background_paused: bool,
+}
+
+/// (是否有电池, 是否电池供电, 电量)
+type Battery = (bool, bool, Option<u8>);
+
+/// (读取时间, 电池状态)
+static CACHE: Lazy<Mutex<Option<(Instant, Battery)>>> = Lazy::new(|| Mutex::new(None));
+
+#[cfg(target_os = "windows")]
+fn read_battery() -> Battery {
+    use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    let mut status = SYSTEM_POWER_STATUS::default();
+    if unsafe { GetSystemPowerStatus(&mut status) }.is_err() {
+        return (false, false, None);
+    }
+    // BatteryFlag 128 = 没有电池，255 = 未知
+    let has_battery = status.BatteryFlag != 128 && status.BatteryFlag != 255;
+    let percent = (status.BatteryLifePercent <= 100).then_some(status.BatteryLifePercent);
+    (
+        has_battery,
+        has_battery && status.ACLineStatus == 0,
+        percent,
+    )
+}
+
+#[cfg(target_os = "macos")]
+fn read_battery() -> Battery {
+    let Ok(output) = std::process::Command::new("pmset")
+        .args(["-g", "batt"])
+        .output()
+    else {
+        return (false, false, None);
+    };
+    let text = String::from_utf8_lossy(&output.stdout);
+    let on_battery = text.contains("'Battery Power'");
+    let percent = text
+        .split_whitespace()
+        .find_map(|w| w.trim_end_matches(';').strip_suffix('%'))
+        .and_then(|p| p.parse::<u8>().ok());
+    (percent.is_some(), on_battery, percent)
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn read_battery() -> Battery {
+    let Ok(entries) = std::fs::read_dir("/sys/class/power_supply") else {
+        return (false, false, None);
+    };
+    let read = |dir: &std::path::Path, name: &str| {
+        std::fs::read_to_string(dir.join(name))
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default()
+    };
+    let (mut has_battery, mut discharging, mut ac_online) = (false, false, false);
+    let mut percent = None;
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        match read(&dir, "type").as_str() {
+            "Battery" => {
+                has_battery = true;
+                discharging |= read(&dir, "status") == "Discharging";
+                percent = percent.or_else(|| read(&dir, "capacity").parse::<u8>().ok());
+            }
+            "Mains" => ac_online |= read(&dir, "online") == "1",
+            _ => {}
+        }
+    }
+    (
+        has_battery,
+        has_battery && discharging && !ac_online,
+        percent,
+    )
+}
+
+fn battery_state() -> Battery {
+    if let Ok(cache) = CACHE.lock() {
+        if let Some((at, battery)) = *cache {
+            if at.elapsed() < CACHE_TTL {
+                return battery;
+            }
+        }
+    }
+    let battery = read_battery();
+    if let Ok(mut cache) = CACHE.lock() {
+        *cache = Some((Instant::now(), battery));
+    }
+    battery
+}
+
+async fn current_status() -> PowerStatus {
+    let settings = super::settings::load_app_settings()
+        .await
+        .unwrap_or_default();
+    let (has_battery, on_battery, battery_percent) = tokio::task::spawn_blocking(battery_state)
+        .await
+        .unwrap_or((false, false, None));
+    let background_paused = settings.power_saver_enabled
+        && on_battery
+        && battery_percent
+            .map(|p| p < settings.power_saver_threshold)
+            .unwrap_or(false);
+    PowerStatus {
+        has_battery,
+        on_battery,
+        battery_percent,
+        saver_enabled: settings.power_saver_enabled,
+        saver_threshold: settings.power_saver_threshold,
+        background_paused,
+    }
+}
+
+/// 后台任务是否应当推迟；allow_on_battery 为任务自身的豁免开关
+pub async fn should_defer(job: &str, allow_on_battery: bool) -> bool {
+    if allow_on_battery {
+        return false;
+    }
+    let status = current_status().await;
+    if status.background_paused {
+        log::info!(
+            "电池电量 {}% 低于 {}%，推迟后台任务: {}",
+            status.battery_percent.unwrap_or(0),
+            status.saver_threshold,
+            job
+        );
+    }
+    status.background_paused
+}
+
+/// 获取电源状态
+#[tauri::command]
+#[specta::specta]
+pub async fn get_power_status() -> AppResult<PowerStatus> {
+    Ok(current_status().await)
+}
@@ -44,9 +44,15 @@ type ProjectRow = (
     Option<String>, // last_opened
     Option<String>, // editor_id
     Option<String>, // claude_env_name
+    Option<String>, // icon
+    Option<String>, // color
+    Option<String>, // description
+    Option<String>, // root_id
+    Option<String>, // git_executable
+    Option<String>, // stats_branch
 );
 
-const PROJECT_SELECT: &str = "SELECT id, name, path, is_favorite, created_at, updated_at, last_opened, editor_id, claude_env_name FROM projects";
+const PROJECT_SELECT: &str = "SELECT id, name, path, is_favorite, created_at, updated_at, last_opened, editor_id, claude_env_name, icon, color, description, root_id, git_executable, stats_branch FROM projects";
 
 fn project_from_row(row: ProjectRow, tags: Vec<String>, labels: Vec<String>) -> Project {
     let (
@@ -59,6 +65,12 @@ fn project_from_row(row: ProjectRow, tags: Vec<String>, labels: Vec<String>) ->
         last_opened,
         editor_id,
         claude_env_name,
+        icon,
+        color,
+        description,
+        root_id,
+        git_executable,
+        stats_branch,
     ) = row;
     Project {
         id,
@@ -72,11 +84,17 @@ fn project_from_row(row: ProjectRow, tags: Vec<String>, labels: Vec<String>) ->
         last_opened,
         editor_id,
         claude_env_name,
+        icon,
+        color,
+        description,
+        root_id,
+        git_executable,
+        stats_branch,
     }
 }
 
 /// 取一个项目的完整数据（含 tags / labels）
-async fn fetch_project_by_id(id: &str) -> AppResult<Option<Project>> {
+pub(crate) async fn fetch_project_by_id(id: &str) -> AppResult<Option<Project>> {
     let pool = pool();
     let row: Option<ProjectRow> = sqlx::query_as(&format!("{} WHERE id = ?", PROJECT_SELECT))
         .bind(id)
@@ -149,6 +167,18 @@ async fn fetch_all_projects() -> AppResult<Vec<Project>> {
         .collect())
 }
 
+/// 当前配置档案可见的项目
+async fn fetch_visible_projects() -> AppResult<Vec<Project>> {
+    let projects = fetch_all_projects().await?;
+    Ok(match super::app_profiles::visible_project_ids() {
+        Some(ids) => projects
+            .into_iter()
+            .filter(|p| ids.contains(&p.id))
+            .collect(),
+        None => projects,
+    })
+}
+
 async fn project_exists(id: &str) -> AppResult<bool> {
     let exists: Option<(i64,)> = sqlx::query_as("SELECT 1 FROM projects WHERE id = ?")
         .bind(id)
@@ -163,7 +193,7 @@ async fn project_exists(id: &str) -> AppResult<bool> {
 #[tauri::command]
 #[specta::specta]
 pub async fn get_projects() -> AppResult<Vec<Project>> {
-    fetch_all_projects().await
+    fetch_visible_projects().await
 }
 
 #[tauri::command]
@@ -231,6 +261,12 @@ pub async fn create_project(input: CreateProjectInput) -> AppResult<Project> {
     tx.commit()
         .await
         .map_err(|e| crate::error::AppError::from(format!("提交事务失败: {}", e)))?;
+    super::app_profiles::add_projects_to_active(std::slice::from_ref(&id)).await;
+    let root_id = super::path_roots::bind_project(&id, &input.path).await;
+    tauri::async_runtime::spawn(super::project_relocate::remember_remote(
+        id.clone(),
+        input.path.clone(),
+    ));
 
     Ok(Project {
         id,
@@ -244,6 +280,12 @@ pub async fn create_project(input: CreateProjectInput) -> AppResult<Project> {
         last_opened: None,
         editor_id: None,
         claude_env_name: None,
+        icon: None,
+        color: None,
+        description: None,
+        root_id,
+        git_executable: None,
+        stats_branch: None,
     })
 }
 
@@ -321,6 +363,9 @@ pub async fn update_project(input: UpdateProjectInput) -> AppResult<Project> {
     tx.commit()
         .await
         .map_err(|e| crate::error::AppError::from(format!("提交事务失败: {}", e)))?;
+    if input.name.is_some() {
+        crate::favorites_menu::refresh();
+    }
 
     fetch_project_by_id(&input.id)
         .await?
@@ -330,6 +375,7 @@ pub async fn update_project(input: UpdateProjectInput) -> AppResult<Project> {
 #[tauri::command]
 #[specta::specta]
 pub async fn delete_project(id: String) -> AppResult<()> {
+    let icon = cached_icon_of(&id).await;
     let result = sqlx::query("DELETE FROM projects WHERE id = ?")
         .bind(&id)
         .execute(pool())
@@ -338,16 +384,91 @@ pub async fn delete_project(id: String) -> AppResult<()> {
     if result.rows_affected() == 0 {
         return Err(crate::error::AppError::from("项目不存在".to_string()));
     }
+    remove_cached_icon(icon.as_deref());
+    crate::favorites_menu::refresh();
     Ok(())
 }
 
+/// 统计目录下的文件数与总大小，超过上限时停止（仅用于确认提示）
+fn summarize_dir(path: &std::path::Path, limit: usize) -> (usize, u64, bool) {
+    let mut files = 0usize;
+    let mut bytes = 0u64;
+    let mut stack = vec![path.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if files >= limit {
+                return (files, bytes, true);
+            }
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            if meta.is_dir() {
+                stack.push(entry.path());
+            } else {
+                files += 1;
+                bytes += meta.len();
+            }
+        }
+    }
+    (files, bytes, false)
+}
+
+/// 删除项目目录（物理删除）。需二次确认：不带 confirm_token 时只返回确认请求，
+/// 带有效令牌时执行删除并返回 None
 #[tauri::command]
 #[specta::specta]
-pub async fn delete_project_directory(id: String) -> AppResult<()> {
+pub async fn delete_project_directory(
+    id: String,
+    confirm_token: Option<String>,
+) -> AppResult<Option<super::confirm::ConfirmRequest>> {
     let project = fetch_project_by_id(&id)
         .await?
         .ok_or_else(|| crate::error::AppError::from("项目不存在".to_string()))?;
     let path = PathBuf::from(&project.path);
+    let resolved = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+
+    if resolved.parent().is_none() || dirs::home_dir().is_some_and(|home| home == resolved) {
+        return Err(crate::error::AppError::invalid(format!(
+            "拒绝删除系统根目录或用户主目录: {}",
+            resolved.display()
+        )));
+    }
+
+    let mut details = vec![
+        format!("项目：{}", project.name),
+        format!("目录：{}", resolved.display()),
+    ];
+    if resolved != path {
+        details.push(format!("（记录路径 {} 指向上述目录）", path.display()));
+    }
+    if path.exists() {
+        let scan_path = resolved.clone();
+        let (files, bytes, truncated) =
+            tokio::task::spawn_blocking(move || summarize_dir(&scan_path, 100_000))
+                .await
+                .unwrap_or((0, 0, false));
+        details.push(format!(
+            "{}{} 个文件，共 {}",
+            if truncated { "超过 " } else { "" },
+            files,
+            super::toolbox::format_bytes(bytes)
+        ));
+    } else {
+        details.push("目录已不存在，仅删除项目记录".to_string());
+    }
+
+    let plan = super::confirm::ConfirmPlan {
+        fingerprint: format!("{}|{}", id, resolved.display()),
+        summary: format!("将永久删除项目「{}」的整个目录，无法恢复", project.name),
+        details,
+    };
+    if let Some(request) = super::confirm::require("delete_project_directory", confirm_token, plan)?
+    {
+        return Ok(Some(request));
+    }
 
     if path.exists() {
         // 物理目录删除走阻塞线程，避免占住 tokio runtime
@@ -357,12 +478,15 @@ pub async fn delete_project_directory(id: String) -> AppResult<()> {
             .map_err(|e| crate::error::AppError::from(format!("删除目录失败: {}", e)))?;
     }
 
+    let icon = cached_icon_of(&id).await;
     sqlx::query("DELETE FROM projects WHERE id = ?")
         .bind(&id)
         .execute(pool())
         .await
         .map_err(|e| crate::error::AppError::from(format!("删除项目记录失败: {}", e)))?;
-    Ok(())
+    remove_cached_icon(icon.as_deref());
+    crate::favorites_menu::refresh();
+    Ok(None)
 }
 
 #[tauri::command]
@@ -384,6 +508,7 @@ pub async fn toggle_favorite(id: String) -> AppResult<Project> {
     if result.rows_affected() == 0 {
         return Err(crate::error::AppError::from("项目不存在".to_string()));
     }
+    crate::favorites_menu::refresh();
 
     fetch_project_by_id(&id)
         .await?
@@ -405,6 +530,7 @@ pub async fn update_last_opened(id: String) -> AppResult<Project> {
     if result.rows_affected() == 0 {
         return Err(crate::error::AppError::from("项目不存在".to_string()));
     }
+    crate::favorites_menu::refresh();
 
     fetch_project_by_id(&id)
         .await?
@@ -527,7 +653,9 @@ pub async fn batch_delete_projects(ids: Vec<String>) -> AppResult<()> {
         .await
         .map_err(|e| crate::error::AppError::from(format!("开启事务失败: {}", e)))?;
 
+    let mut icons = Vec::new();
     for id in &ids {
+        icons.push(cached_icon_of(id).await);
         sqlx::query("DELETE FROM projects WHERE id = ?")
             .bind(id)
             .execute(&mut *tx)
@@ -537,6 +665,10 @@ pub async fn batch_delete_projects(ids: Vec<String>) -> AppResult<()> {
     tx.commit()
         .await
         .map_err(|e| crate::error::AppError::from(format!("提交事务失败: {}", e)))?;
+    for icon in icons {
+        remove_cached_icon(icon.as_deref());
+    }
+    crate::favorites_menu::refresh();
     Ok(())
 }
 
@@ -608,6 +740,11 @@ pub async fn import_projects(new_projects: Vec<CreateProjectInput>) -> AppResult
         tx.commit()
             .await
             .map_err(|e| crate::error::AppError::from(format!("提交事务失败: {}", e)))?;
+        let root_id = super::path_roots::bind_project(&id, &input.path).await;
+        tauri::async_runtime::spawn(super::project_relocate::remember_remote(
+            id.clone(),
+            input.path.clone(),
+        ));
 
         imported.push(Project {
             id,
@@ -621,9 +758,17 @@ pub async fn import_projects(new_projects: Vec<CreateProjectInput>) -> AppResult
             last_opened: None,
             editor_id: None,
             claude_env_name: None,
+            icon: None,
+            color: None,
+            description: None,
+            root_id,
+            git_executable: None,
+            stats_branch: None,
         });
     }
 
+    let ids: Vec<String> = imported.iter().map(|p| p.id.clone()).collect();
+    super::app_profiles::add_projects_to_active(&ids).await;
     Ok(imported)
 }
 
@@ -631,7 +776,7 @@ pub async fn import_projects(new_projects: Vec<CreateProjectInput>) -> AppResult
 #[tauri::command]
 #[specta::specta]
 pub async fn reload_projects() -> AppResult<Vec<Project>> {
-    fetch_all_projects().await
+    fetch_visible_projects().await
 }
 
 #[tauri::command]
@@ -681,3 +826,214 @@ pub async fn set_project_claude_env(
         .await?
         .ok_or_else(|| crate::error::AppError::from("项目不存在".to_string()))
 }
+
+// ============ 外观：图标 / 强调色 / 简介 ============
+
+/// 图标文件上限（字节）
+const MAX_ICON_BYTES: u64 = 1024 * 1024;
+
+/// 简介长度上限（字符）
+const MAX_DESCRIPTION_CHARS: usize = 200;
+
+const ICON_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "svg", "ico"];
+
+/// 更新单个外观字段（column 只传本模块内的固定列名）
+async fn set_project_field(
+    id: &str,
+    column: &'static str,
+    value: Option<String>,
+) -> AppResult<Project> {
+    let now = current_iso_time();
+    let result = sqlx::query(&format!(
+        "UPDATE projects SET {} = ?, updated_at = ? WHERE id = ?",
+        column
+    ))
+    .bind(&value)
+    .bind(&now)
+    .bind(id)
+    .execute(pool())
+    .await
+    .map_err(|e| crate::error::AppError::from(format!("更新 {} 失败: {}", column, e)))?;
+
+    if result.rows_affected() == 0 {
+        return Err(crate::error::AppError::from("项目不存在".to_string()));
+    }
+
+    fetch_project_by_id(id)
+        .await?
+        .ok_or_else(|| crate::error::AppError::from("项目不存在".to_string()))
+}
+
+/// 删除缓存的图标文件（只删除图标缓存目录下的文件）
+fn remove_cached_icon(icon: Option<&str>) {
+    let (Some(icon), Ok(config)) = (icon, crate::storage::get_storage_config()) else {
+        return;
+    };
+    let path = PathBuf::from(icon);
+    if path.starts_with(config.project_icons_dir()) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+async fn cached_icon_of(id: &str) -> Option<String> {
+    sqlx::query_scalar::<_, Option<String>>("SELECT icon FROM projects WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool())
+        .await
+        .ok()
+        .flatten()
+        .flatten()
+}
+
+/// 设置项目图标：把选择的图片复制到数据目录缓存，原文件移动或删除后图标仍然可用
+#[tauri::command]
+#[specta::specta]
+pub async fn set_project_icon(id: String, source_path: String) -> AppResult<Project> {
+    if !project_exists(&id).await? {
+        return Err(crate::error::AppError::from("项目不存在".to_string()));
+    }
+    let source = PathBuf::from(&source_path);
+    let ext = source
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .filter(|e| ICON_EXTENSIONS.contains(&e.as_str()))
+        .ok_or_else(|| {
+            crate::error::AppError::invalid(format!(
+                "不支持的图标格式，可选：{}",
+                ICON_EXTENSIONS.join(", ")
+            ))
+        })?;
+    let meta = std::fs::metadata(&source)
+        .map_err(|e| crate::error::AppError::invalid(format!("无法读取图标文件: {}", e)))?;
+    if !meta.is_file() {
+        return Err(crate::error::AppError::invalid("图标路径不是文件"));
+    }
+    if meta.len() > MAX_ICON_BYTES {
+        return Err(crate::error::AppError::invalid(format!(
+            "图标文件过大（上限 {}）",
+            super::toolbox::format_bytes(MAX_ICON_BYTES)
+        )));
+    }
+
+    let dir = crate::storage::get_storage_config()?.project_icons_dir();
+    std::fs::create_dir_all(&dir)?;
+    // 文件名带时间戳，替换图标后前端不会拿到旧缓存
+    let target = dir.join(format!(
+        "{}-{}.{}",
+        crate::storage::config::sanitize_id(&id),
+        chrono::Utc::now().timestamp_millis(),
+        ext
+    ));
+    std::fs::copy(&source, &target)?;
+
+    let previous = cached_icon_of(&id).await;
+    let project = set_project_field(&id, "icon", Some(target.to_string_lossy().to_string())).await;
+    match &project {
+        Ok(_) => remove_cached_icon(previous.as_deref()),
+        Err(_) => {
+            let _ = std::fs::remove_file(&target);
+        }
+    }
+    project
+}
+
+/// 清除项目图标并删除缓存文件
+#[tauri::command]
+#[specta::specta]
+pub async fn clear_project_icon(id: String) -> AppResult<Project> {
+    let previous = cached_icon_of(&id).await;
+    let project = set_project_field(&id, "icon", None).await?;
+    remove_cached_icon(previous.as_deref());
+    Ok(project)
+}
+
+/// 设置强调色（#RRGGBB / #RGB）；传 None 或空字符串时清除
+#[tauri::command]
+#[specta::specta]
+pub async fn set_project_color(id: String, color: Option<String>) -> AppResult<Project> {
+    let color = color
+        .map(|c| c.trim().to_lowercase())
+        .filter(|c| !c.is_empty());
+    if let Some(c) = &color {
+        let hex = c.strip_prefix('#').unwrap_or("");
+        if !matches!(hex.len(), 3 | 6) || !hex.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            return Err(crate::error::AppError::invalid(format!(
+                "颜色格式不正确: {}（应为 #RRGGBB）",
+                c
+            )));
+        }
+    }
+    set_project_field(&id, "color", color).await
+}
+
+/// 设置项目简介；传 None 或空字符串时清除
+#[tauri::command]
+#[specta::specta]
+pub async fn set_project_description(
+    id: String,
+    description: Option<String>,
+) -> AppResult<Project> {
+    let description = description
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty());
+    if let Some(d) = &description {
+        if d.chars().count() > MAX_DESCRIPTION_CHARS {
+            return Err(crate::error::AppError::invalid(format!(
+                "简介不能超过 {} 个字符",
+                MAX_DESCRIPTION_CHARS
+            )));
+        }
+    }
+    set_project_field(&id, "description", description).await
+}
+
+/// 设置项目专用的 git 可执行文件；传 None 或空字符串时改用全局设置
+#[tauri::command]
+#[specta::specta]
+pub async fn set_project_git_executable(
+    id: String,
+    executable: Option<String>,
+) -> AppResult<Project> {
+    let executable = executable
+        .map(|e| e.trim().to_string())
+        .filter(|e| !e.is_empty());
+    if let Some(e) = &executable {
+        crate::git_executable::check(e)?;
+    }
+    let project = set_project_field(&id, "git_executable", executable).await?;
+    crate::git_executable::reload().await;
+    Ok(project)
+}
+
+/// 设置统计提交使用的分支："*" 为全部本地分支，None 或空字符串为当前检出分支。
+/// 修改后标记该项目的统计待刷新
+#[tauri::command]
+#[specta::specta]
+pub async fn set_project_stats_branch(id: String, branch: Option<String>) -> AppResult<Project> {
+    let branch = branch
+        .map(|b| b.trim().to_string())
+        .filter(|b| !b.is_empty());
+    if let Some(b) = branch
+        .as_deref()
+        .filter(|b| *b != super::stats::ALL_BRANCHES)
+    {
+        let project = fetch_project_by_id(&id)
+            .await?
+            .ok_or_else(|| crate::error::AppError::from("项目不存在".to_string()))?;
+        let exists = crate::path_compat::git_in(&project.path)
+            .args(["rev-parse", "--verify", "--quiet"])
+            .arg(format!("refs/heads/{}", b))
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+        if !exists {
+            return Err(crate::error::AppError::invalid(format!(
+                "本地分支不存在: {}",
+                b
+            )));
+        }
+    }
+    let project = set_project_field(&id, "stats_branch", branch).await?;
+    super::stats::mark_project_dirty(project.path.clone()).await?;
+    Ok(project)
+}
@@ -0,0 +1,211 @@
+// 项目快捷链接
+//
+// 每个项目可以固定若干常用地址（问题跟踪、预发环境、CI 面板等），
+// 存 SQLite project_links 表，随项目删除级联删除。
+// 只接受 http / https 地址，打开时走系统默认浏览器。
+
+use crate::error::AppResult;
+use serde::{Deserialize, Serialize};
+
+use crate::commands::project_tasks::project_path;
+use crate::storage::db::pool;
+use crate::storage::{current_iso_time, generate_id};
+
+const LINK_KINDS: &[&str] = &["issues", "staging", "production", "ci", "docs", "other"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectLink {
+    pub id: String,
+    pub project_id: String,
+    pub title: String,
+    pub url: String,
+    /// "issues" | "staging" | "production" | "ci" | "docs" | "other"
+    pub kind: String,
+    pub sort_order: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectLinkInput {
+    /// 为空时新建
+    pub id: Option<String>,
+    pub project_id: String,
+    pub title: String,
+    pub url: String,
+    pub kind: Option<String>,
+    pub sort_order: Option<i64>,
+}
+
+// ============ helpers ============
+
+type LinkRow = (
+    String, // id
+    String, // project_id
+    String, // title
+    String, // url
+    String, // kind
+    i64,    // sort_order
+    String, // created_at
+    String, // updated_at
+);
+
+const LINK_SELECT: &str =
+    "SELECT id, project_id, title, url, kind, sort_order, created_at, updated_at FROM project_links";
+
+fn link_from_row(row: LinkRow) -> ProjectLink {
+    let (id, project_id, title, url, kind, sort_order, created_at, updated_at) = row;
+    ProjectLink {
+        id,
+        project_id,
+        title,
+        url,
+        kind,
+        sort_order,
+        created_at,
+        updated_at,
+    }
+}
+
+async fn fetch_link(project_id: &str, id: &str) -> AppResult<ProjectLink> {
+    let row: Option<LinkRow> =
+        sqlx::query_as(&format!("{} WHERE id = ? AND project_id = ?", LINK_SELECT))
+            .bind(id)
+            .bind(project_id)
+            .fetch_optional(pool())
+            .await
+            .map_err(|e| crate::error::AppError::from(format!("查询项目链接失败: {}", e)))?;
+    row.map(link_from_row)
+        .ok_or_else(|| crate::error::AppError::from("项目链接不存在".to_string()))
+}
+
+fn validate_url(url: &str) -> AppResult<()> {
+    let host = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .ok_or_else(|| crate::error::AppError::invalid("链接只支持 http / https 地址"))?;
+    if host.is_empty() || host.starts_with('/') || url.chars().any(char::is_whitespace) {
+        return Err(crate::error::AppError::invalid(format!(
+            "链接地址不正确: {}",
+            url
+        )));
+    }
+    Ok(())
+}
+
+// ============ commands ============
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_project_links(project_id: String) -> AppResult<Vec<ProjectLink>> {
+    let rows: Vec<LinkRow> = sqlx::query_as(&format!(
+        "{} WHERE project_id = ? ORDER BY sort_order, created_at",
+        LINK_SELECT
+    ))
+    .bind(&project_id)
+    .fetch_all(pool())
+    .await
+    .map_err(|e| crate::error::AppError::from(format!("查询项目链接失败: {}", e)))?;
+    Ok(rows.into_iter().map(link_from_row).collect())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn save_project_link(input: ProjectLinkInput) -> AppResult<ProjectLink> {
+    let title = input.title.trim();
+    if title.is_empty() {
+        return Err(crate::error::AppError::invalid("链接名称不能为空"));
+    }
+    let url = input.url.trim();
+    validate_url(url)?;
+    let kind = input
+        .kind
+        .filter(|k| !k.trim().is_empty())
+        .unwrap_or_else(|| "other".to_string());
+    if !LINK_KINDS.contains(&kind.as_str()) {
+        return Err(crate::error::AppError::invalid(format!(
+            "不支持的链接类型: {}，可选：{}",
+            kind,
+            LINK_KINDS.join(", ")
+        )));
+    }
+    project_path(&input.project_id).await?;
+
+    let now = current_iso_time();
+    let id = match input.id {
+        Some(id) => {
+            let result = sqlx::query(
+                "UPDATE project_links SET title = ?, url = ?, kind = ?, sort_order = COALESCE(?, sort_order), updated_at = ? WHERE id = ? AND project_id = ?",
+            )
+            .bind(title)
+            .bind(url)
+            .bind(&kind)
+            .bind(input.sort_order)
+            .bind(&now)
+            .bind(&id)
+            .bind(&input.project_id)
+            .execute(pool())
+            .await
+            .map_err(|e| crate::error::AppError::from(format!("更新项目链接失败: {}", e)))?;
+            if result.rows_affected() == 0 {
+                return Err(crate::error::AppError::from("项目链接不存在".to_string()));
+            }
+            id
+        }
+        None => {
+            let id = generate_id();
+            let sort_order = match input.sort_order {
+                Some(v) => v,
+                None => sqlx::query_scalar::<_, i64>(
+                    "SELECT COALESCE(MAX(sort_order), -1) + 1 FROM project_links WHERE project_id = ?",
+                )
+                .bind(&input.project_id)
+                .fetch_one(pool())
+                .await
+                .map_err(|e| crate::error::AppError::from(format!("查询项目链接失败: {}", e)))?,
+            };
+            sqlx::query(
+                "INSERT INTO project_links (id, project_id, title, url, kind, sort_order, created_at, updated_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&id)
+            .bind(&input.project_id)
+            .bind(title)
+            .bind(url)
+            .bind(&kind)
+            .bind(sort_order)
+            .bind(&now)
+            .bind(&now)
+            .execute(pool())
+            .await
+            .map_err(|e| crate::error::AppError::from(format!("创建项目链接失败: {}", e)))?;
+            id
+        }
+    };
+
+    fetch_link(&input.project_id, &id).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_project_link(project_id: String, link_id: String) -> AppResult<()> {
+    sqlx::query("DELETE FROM project_links WHERE id = ? AND project_id = ?")
+        .bind(&link_id)
+        .bind(&project_id)
+        .execute(pool())
+        .await
+        .map_err(|e| crate::error::AppError::from(format!("删除项目链接失败: {}", e)))?;
+    Ok(())
+}
+
+/// 在默认浏览器中打开项目链接
+#[tauri::command]
+#[specta::specta]
+pub async fn open_project_link(project_id: String, link_id: String) -> AppResult<()> {
+    let link = fetch_link(&project_id, &link_id).await?;
+    // 入库时已校验，这里再检查一次，避免手改数据库后打开任意协议
+    validate_url(&link.url)?;
+    super::system::open_url(link.url).await
+}
@@ -0,0 +1,259 @@
+// 查找被移动 / 改名的项目目录
+//
+// 项目路径不存在时，在扫描目录（设置 scan_roots）和路径根目录下扫描 Git 仓库，
+// 按远程地址或仓库名匹配，给出候选的新路径；由用户确认后再写入，不自动改动。
+// 远程地址在项目目录还在时记入 projects.remote_url（新建 / 导入项目时、每次查找前刷新），
+// 目录已丢失且从未记录过远程的项目只能按名称匹配。
+
+use super::git::{primary_remote_url, remote_identity, scan_directory, ScanOptions};
+use super::project::fetch_project_by_id;
+use super::settings::load_app_settings;
+use crate::error::{AppError, AppResult};
+use crate::storage::db::pool;
+use crate::storage::{current_iso_time, Project};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RelocationCandidate {
+    pub path: String,
+    /// "remote" 远程地址一致 | "name" 仓库目录名与项目名或原目录名一致
+    pub matched_by: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectRelocation {
+    pub project_id: String,
+    pub name: String,
+    pub old_path: String,
+    /// 记录过的远程地址
+    pub remote_url: Option<String>,
+    /// 远程匹配在前
+    pub candidates: Vec<RelocationCandidate>,
+    /// 只有一个远程匹配、或没有远程匹配但只有一个名称匹配时给出建议路径
+    pub suggested_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RelocationInput {
+    pub project_id: String,
+    pub new_path: String,
+}
+
+fn dir_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+async fn save_remote(project_id: &str, remote_url: &str) {
+    if let Err(e) = sqlx::query("UPDATE projects SET remote_url = ? WHERE id = ?")
+        .bind(remote_url)
+        .bind(project_id)
+        .execute(pool())
+        .await
+    {
+        log::warn!("记录项目远程地址失败: {}", e);
+    }
+}
+
+/// 读取并记录项目当前的主远程地址；目录不存在或没有远程时保留原记录
+pub(crate) async fn remember_remote(project_id: String, path: String) {
+    let url = tokio::task::spawn_blocking(move || primary_remote_url(&path))
+        .await
+        .ok()
+        .flatten();
+    if let Some(url) = url {
+        save_remote(&project_id, &url).await;
+    }
+}
+
+/// 刷新仍然存在的项目的远程地址
+async fn refresh_remotes(projects: &[(String, String)]) {
+    for (id, path) in projects {
+        if Path::new(path).is_dir() {
+            remember_remote(id.clone(), path.clone()).await;
+        }
+    }
+}
+
+/// 扫描目录：调用方指定的优先，否则用设置中的 scan_roots 加上路径根目录
+async fn search_roots(roots: Option<Vec<String>>) -> AppResult<Vec<String>> {
+    let mut list = match roots {
+        Some(roots) => roots,
+        None => {
+            let mut list = load_app_settings().await?.scan_roots;
+            let path_roots: Vec<String> = sqlx::query_scalar("SELECT path FROM path_roots")
+                .fetch_all(pool())
+                .await
+                .map_err(|e| AppError::from(format!("查询路径根目录失败: {}", e)))?;
+            list.extend(path_roots);
+            list
+        }
+    };
+    let mut seen = HashSet::new();
+    list.retain(|r| !r.trim().is_empty() && Path::new(r).is_dir() && seen.insert(r.clone()));
+    Ok(list)
+}
+
+/// 在扫描目录中为路径已不存在的项目查找新位置，只返回建议，不修改数据
+#[tauri::command]
+#[specta::specta]
+pub async fn relocate_missing_projects(
+    roots: Option<Vec<String>>,
+) -> AppResult<Vec<ProjectRelocation>> {
+    let rows: Vec<(String, String, String, Option<String>)> =
+        sqlx::query_as("SELECT id, name, path, remote_url FROM projects ORDER BY name")
+            .fetch_all(pool())
+            .await
+            .map_err(|e| AppError::from(format!("查询项目失败: {}", e)))?;
+
+    let (missing, present): (Vec<_>, Vec<_>) = rows
+        .into_iter()
+        .partition(|(_, _, path, _)| !Path::new(path).exists());
+    if missing.is_empty() {
+        return Ok(Vec::new());
+    }
+    let present: Vec<(String, String)> = present
+        .into_iter()
+        .map(|(id, _, path, _)| (id, path))
+        .collect();
+    refresh_remotes(&present).await;
+
+    let roots = search_roots(roots).await?;
+    if roots.is_empty() {
+        return Err(AppError::invalid(
+            "没有可用的扫描目录，请先在设置中添加扫描目录或路径根目录",
+        ));
+    }
+
+    // 已登记为其它项目的仓库不作为候选
+    let registered: HashSet<String> = present.into_iter().map(|(_, path)| path).collect();
+    let depth = load_app_settings().await?.scan_depth.max(1);
+    let mut repos = Vec::new();
+    let mut seen = HashSet::new();
+    for root in &roots {
+        match scan_directory(root.clone(), Some(depth), Some(ScanOptions::default())).await {
+            Ok(found) => repos.extend(
+                found
+                    .into_iter()
+                    .filter(|r| !registered.contains(&r.path) && seen.insert(r.path.clone())),
+            ),
+            Err(e) => log::warn!("扫描目录 {} 失败: {}", root, e),
+        }
+    }
+
+    // 只有记录过远程的项目才需要读取候选仓库的远程
+    let need_remotes = missing.iter().any(|(_, _, _, url)| url.is_some());
+    let repo_paths: Vec<String> = repos.iter().map(|r| r.path.clone()).collect();
+    let identities: HashMap<String, String> = if need_remotes {
+        tokio::task::spawn_blocking(move || {
+            repo_paths
+                .into_iter()
+                .filter_map(|path| {
+                    let identity = primary_remote_url(&path).and_then(|u| remote_identity(&u))?;
+                    Some((path, identity))
+                })
+                .collect()
+        })
+        .await
+        .unwrap_or_default()
+    } else {
+        HashMap::new()
+    };
+
+    let mut result = Vec::with_capacity(missing.len());
+    for (project_id, name, old_path, remote_url) in missing {
+        let identity = remote_url.as_deref().and_then(remote_identity);
+        let names = [name.to_lowercase(), dir_name(&old_path)];
+
+        let mut by_remote = Vec::new();
+        let mut by_name = Vec::new();
+        for repo in &repos {
+            if identity.is_some() && identities.get(&repo.path) == identity.as_ref() {
+                by_remote.push(repo.path.clone());
+            } else if names.contains(&dir_name(&repo.path)) {
+                by_name.push(repo.path.clone());
+            }
+        }
+
+        let suggested_path = match (by_remote.as_slice(), by_name.as_slice()) {
+            ([only], _) => Some(only.clone()),
+            ([], [only]) => Some(only.clone()),
+            _ => None,
+        };
+        let candidates = by_remote
+            .into_iter()
+            .map(|path| RelocationCandidate {
+                path,
+                matched_by: "remote".to_string(),
+            })
+            .chain(by_name.into_iter().map(|path| RelocationCandidate {
+                path,
+                matched_by: "name".to_string(),
+            }))
+            .collect();
+
+        result.push(ProjectRelocation {
+            project_id,
+            name,
+            old_path,
+            remote_url,
+            candidates,
+            suggested_path,
+        });
+    }
+    Ok(result)
+}
+
+/// 写入确认后的新路径，并重新匹配路径根目录
+#[tauri::command]
+#[specta::specta]
+pub async fn apply_project_relocations(
+    relocations: Vec<RelocationInput>,
+) -> AppResult<Vec<Project>> {
+    let now = current_iso_time();
+    let mut updated = Vec::with_capacity(relocations.len());
+    for item in relocations {
+        if !Path::new(&item.new_path).is_dir() {
+            return Err(AppError::invalid(format!("目录不存在: {}", item.new_path)));
+        }
+        let taken: Option<String> =
+            sqlx::query_scalar("SELECT id FROM projects WHERE path = ? AND id != ?")
+                .bind(&item.new_path)
+                .bind(&item.project_id)
+                .fetch_optional(pool())
+                .await
+                .map_err(|e| AppError::from(format!("查询项目失败: {}", e)))?;
+        if taken.is_some() {
+            return Err(AppError::invalid(format!(
+                "该目录已是其它项目: {}",
+                item.new_path
+            )));
+        }
+
+        let result = sqlx::query("UPDATE projects SET path = ?, updated_at = ? WHERE id = ?")
+            .bind(&item.new_path)
+            .bind(&now)
+            .bind(&item.project_id)
+            .execute(pool())
+            .await
+            .map_err(|e| AppError::from(format!("更新项目路径失败: {}", e)))?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::from("项目不存在".to_string()));
+        }
+        super::path_roots::bind_project(&item.project_id, &item.new_path).await;
+        remember_remote(item.project_id.clone(), item.new_path.clone()).await;
+
+        if let Some(project) = fetch_project_by_id(&item.project_id).await? {
+            updated.push(project);
+        }
+    }
+    crate::git_executable::reload().await;
+    Ok(updated)
+}
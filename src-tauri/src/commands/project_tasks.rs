@@ -0,0 +1,476 @@
+// 项目固定命令（任务运行器）
+//
+// 每个项目可以保存若干命令（如 "dev server"、"unit tests"、"build release"），
+// 配置存 SQLite project_tasks 表，随项目删除级联删除。
+// 运行时通过 shell 启动，stdout/stderr 按行推送 `project-task-output`，
+// 退出时推送 `project-task-exited`；运行中的任务会在进程管理里标注出来。
+
+use crate::error::AppResult;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::Command;
+use tokio::sync::{Mutex, Notify};
+use tokio::time::Duration;
+
+use crate::storage::db::pool;
+use crate::storage::{current_iso_time, generate_id};
+
+/// 每个运行实例保留的输出行数（供窗口后打开时回看）
+const MAX_OUTPUT_LINES: usize = 500;
+
+/// 停止时等待进程自行退出的时间，超时后强杀
+const STOP_GRACE: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTask {
+    pub id: String,
+    pub project_id: String,
+    pub name: String,
+    pub command: String,
+    /// 工作目录，相对路径基于项目目录；为空则使用项目目录
+    pub cwd: Option<String>,
+    pub env: HashMap<String, String>,
+    pub sort_order: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTaskInput {
+    /// 为空时新建
+    pub id: Option<String>,
+    pub project_id: String,
+    pub name: String,
+    pub command: String,
+    pub cwd: Option<String>,
+    pub env: Option<HashMap<String, String>>,
+    pub sort_order: Option<i64>,
+}
+
+/// 运行中的任务实例
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTaskRun {
+    pub run_id: String,
+    pub task_id: String,
+    pub project_id: String,
+    pub name: String,
+    pub command: String,
+    pub pid: Option<u32>,
+    pub started_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTaskOutput {
+    pub run_id: String,
+    /// "stdout" | "stderr"
+    pub stream: String,
+    pub line: String,
+}
+
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTaskExit {
+    pub run_id: String,
+    pub task_id: String,
+    pub exit_code: Option<i32>,
+    pub stopped: bool,
+}
+
+struct RunningTask {
+    info: ProjectTaskRun,
+    output: Arc<std::sync::Mutex<VecDeque<ProjectTaskOutput>>>,
+    stop: Arc<Notify>,
+}
+
+static RUNNING: Lazy<Mutex<HashMap<String, RunningTask>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+// ============ helpers ============
+
+type TaskRow = (
+    String,         // id
+    String,         // project_id
+    String,         // name
+    String,         // command
+    Option<String>, // cwd
+    String,         // env (JSON)
+    i64,            // sort_order
+    String,         // created_at
+    String,         // updated_at
+);
+
+const TASK_SELECT: &str = "SELECT id, project_id, name, command, cwd, env, sort_order, created_at, updated_at FROM project_tasks";
+
+fn task_from_row(row: TaskRow) -> ProjectTask {
+    let (id, project_id, name, command, cwd, env, sort_order, created_at, updated_at) = row;
+    ProjectTask {
+        id,
+        project_id,
+        name,
+        command,
+        cwd,
+        env: serde_json::from_str(&env).unwrap_or_default(),
+        sort_order,
+        created_at,
+        updated_at,
+    }
+}
+
+async fn fetch_task(id: &str) -> AppResult<ProjectTask> {
+    let row: Option<TaskRow> = sqlx::query_as(&format!("{} WHERE id = ?", TASK_SELECT))
+        .bind(id)
+        .fetch_optional(pool())
+        .await
+        .map_err(|e| crate::error::AppError::from(format!("查询项目任务失败: {}", e)))?;
+    row.map(task_from_row)
+        .ok_or_else(|| crate::error::AppError::from("项目任务不存在".to_string()))
+}
+
+pub(crate) async fn project_path(project_id: &str) -> AppResult<String> {
+    let path: Option<String> = sqlx::query_scalar("SELECT path FROM projects WHERE id = ?")
+        .bind(project_id)
+        .fetch_optional(pool())
+        .await
+        .map_err(|e| crate::error::AppError::from(format!("查询项目失败: {}", e)))?;
+    path.ok_or_else(|| crate::error::AppError::from("项目不存在".to_string()))
+}
+
+pub(crate) fn shell_command(command: &str) -> Command {
+    #[cfg(target_family = "unix")]
+    {
+        let mut c = Command::new("/bin/sh");
+        c.arg("-c").arg(command);
+        // 独立进程组，停止时整组结束（npm run dev 之类会再起子进程）
+        c.process_group(0);
+        c
+    }
+    #[cfg(target_family = "windows")]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        let mut c = Command::new("cmd");
+        c.arg("/C").arg(command);
+        c.creation_flags(CREATE_NO_WINDOW);
+        c
+    }
+}
+
+/// 结束整个进程树
+pub(crate) fn kill_tree(pid: u32, force: bool) {
+    #[cfg(target_family = "unix")]
+    {
+        let signal = if force { "-KILL" } else { "-TERM" };
+        let _ = std::process::Command::new("kill")
+            .args([signal, "--", &format!("-{}", pid)])
+            .output();
+    }
+    #[cfg(target_family = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        let mut cmd = std::process::Command::new("taskkill");
+        cmd.creation_flags(CREATE_NO_WINDOW);
+        if force {
+            cmd.arg("/F");
+        }
+        let _ = cmd.args(["/T", "/PID", &pid.to_string()]).output();
+    }
+}
+
+fn pipe_output<R: AsyncRead + Unpin + Send + 'static>(
+    app: AppHandle,
+    run_id: String,
+    stream: &'static str,
+    reader: R,
+    buffer: Arc<std::sync::Mutex<VecDeque<ProjectTaskOutput>>>,
+) {
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(reader).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let item = ProjectTaskOutput {
+                run_id: run_id.clone(),
+                stream: stream.to_string(),
+                line,
+            };
+            if let Ok(mut buf) = buffer.lock() {
+                buf.push_back(item.clone());
+                while buf.len() > MAX_OUTPUT_LINES {
+                    buf.pop_front();
+                }
+            }
+            let _ = app.emit("project-task-output", item);
+        }
+    });
+}
+
+/// 运行中任务的 pid → "项目任务名"，供进程管理标注
+pub async fn running_task_pids() -> HashMap<u32, String> {
+    RUNNING
+        .lock()
+        .await
+        .values()
+        .filter_map(|t| t.info.pid.map(|pid| (pid, t.info.name.clone())))
+        .collect()
+}
+
+// ============ commands ============
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_project_tasks(project_id: String) -> AppResult<Vec<ProjectTask>> {
+    let rows: Vec<TaskRow> = sqlx::query_as(&format!(
+        "{} WHERE project_id = ? ORDER BY sort_order, created_at",
+        TASK_SELECT
+    ))
+    .bind(&project_id)
+    .fetch_all(pool())
+    .await
+    .map_err(|e| crate::error::AppError::from(format!("查询项目任务失败: {}", e)))?;
+    Ok(rows.into_iter().map(task_from_row).collect())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn save_project_task(input: ProjectTaskInput) -> AppResult<ProjectTask> {
+    if input.name.trim().is_empty() {
+        return Err(crate::error::AppError::invalid("任务名称不能为空"));
+    }
+    if input.command.trim().is_empty() {
+        return Err(crate::error::AppError::invalid("命令不能为空"));
+    }
+    project_path(&input.project_id).await?;
+
+    let now = current_iso_time();
+    let env = serde_json::to_string(&input.env.unwrap_or_default())
+        .map_err(|e| crate::error::AppError::from(format!("序列化环境变量失败: {}", e)))?;
+    let cwd = input.cwd.filter(|c| !c.trim().is_empty());
+
+    let id = match input.id {
+        Some(id) => {
+            let result = sqlx::query(
+                "UPDATE project_tasks SET name = ?, command = ?, cwd = ?, env = ?, sort_order = COALESCE(?, sort_order), updated_at = ? WHERE id = ? AND project_id = ?",
+            )
+            .bind(input.name.trim())
+            .bind(&input.command)
+            .bind(&cwd)
+            .bind(&env)
+            .bind(input.sort_order)
+            .bind(&now)
+            .bind(&id)
+            .bind(&input.project_id)
+            .execute(pool())
+            .await
+            .map_err(|e| crate::error::AppError::from(format!("更新项目任务失败: {}", e)))?;
+            if result.rows_affected() == 0 {
+                return Err(crate::error::AppError::from("项目任务不存在".to_string()));
+            }
+            id
+        }
+        None => {
+            let id = generate_id();
+            let sort_order = match input.sort_order {
+                Some(v) => v,
+                None => sqlx::query_scalar::<_, i64>(
+                    "SELECT COALESCE(MAX(sort_order), -1) + 1 FROM project_tasks WHERE project_id = ?",
+                )
+                .bind(&input.project_id)
+                .fetch_one(pool())
+                .await
+                .map_err(|e| crate::error::AppError::from(format!("查询项目任务失败: {}", e)))?,
+            };
+            sqlx::query(
+                "INSERT INTO project_tasks (id, project_id, name, command, cwd, env, sort_order, created_at, updated_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&id)
+            .bind(&input.project_id)
+            .bind(input.name.trim())
+            .bind(&input.command)
+            .bind(&cwd)
+            .bind(&env)
+            .bind(sort_order)
+            .bind(&now)
+            .bind(&now)
+            .execute(pool())
+            .await
+            .map_err(|e| crate::error::AppError::from(format!("创建项目任务失败: {}", e)))?;
+            id
+        }
+    };
+
+    fetch_task(&id).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_project_task(id: String) -> AppResult<()> {
+    sqlx::query("DELETE FROM project_tasks WHERE id = ?")
+        .bind(&id)
+        .execute(pool())
+        .await
+        .map_err(|e| crate::error::AppError::from(format!("删除项目任务失败: {}", e)))?;
+    Ok(())
+}
+
+/// 运行项目任务，返回运行实例（同一任务可同时运行多个实例）
+#[tauri::command]
+#[specta::specta]
+pub async fn run_project_task(app: AppHandle, task_id: String) -> AppResult<ProjectTaskRun> {
+    let task = fetch_task(&task_id).await?;
+    let base = PathBuf::from(project_path(&task.project_id).await?);
+    let cwd = match task.cwd.as_deref() {
+        Some(c) => base.join(c),
+        None => base,
+    };
+    if !cwd.is_dir() {
+        return Err(crate::error::AppError::invalid(format!(
+            "工作目录不存在: {}",
+            cwd.display()
+        )));
+    }
+
+    let mut cmd = shell_command(&task.command);
+    cmd.current_dir(&cwd)
+        .envs(&task.env)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| crate::error::AppError::from(format!("启动任务失败: {}", e)))?;
+
+    let run_id = generate_id();
+    let info = ProjectTaskRun {
+        run_id: run_id.clone(),
+        task_id: task.id.clone(),
+        project_id: task.project_id.clone(),
+        name: task.name.clone(),
+        command: task.command.clone(),
+        pid: child.id(),
+        started_at: current_iso_time(),
+    };
+    let output = Arc::new(std::sync::Mutex::new(VecDeque::new()));
+    let stop = Arc::new(Notify::new());
+
+    if let Some(stdout) = child.stdout.take() {
+        pipe_output(
+            app.clone(),
+            run_id.clone(),
+            "stdout",
+            stdout,
+            output.clone(),
+        );
+    }
+    if let Some(stderr) = child.stderr.take() {
+        pipe_output(
+            app.clone(),
+            run_id.clone(),
+            "stderr",
+            stderr,
+            output.clone(),
+        );
+    }
+
+    RUNNING.lock().await.insert(
+        run_id.clone(),
+        RunningTask {
+            info: info.clone(),
+            output,
+            stop: stop.clone(),
+        },
+    );
+    let _ = app.emit("project-task-started", &info);
+
+    let pid = info.pid;
+    let task_id = task.id;
+    tokio::spawn(async move {
+        let (status, stopped) = tokio::select! {
+            status = child.wait() => (status.ok(), false),
+            _ = stop.notified() => {
+                if let Some(pid) = pid {
+                    kill_tree(pid, false);
+                }
+                match tokio::time::timeout(STOP_GRACE, child.wait()).await {
+                    Ok(status) => (status.ok(), true),
+                    Err(_) => {
+                        if let Some(pid) = pid {
+                            kill_tree(pid, true);
+                        }
+                        let _ = child.start_kill();
+                        (child.wait().await.ok(), true)
+                    }
+                }
+            }
+        };
+
+        RUNNING.lock().await.remove(&run_id);
+        let _ = app.emit(
+            "project-task-exited",
+            ProjectTaskExit {
+                run_id,
+                task_id,
+                exit_code: status.and_then(|s| s.code()),
+                stopped,
+            },
+        );
+    });
+
+    Ok(info)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_project_task(run_id: String) -> AppResult<()> {
+    let running = RUNNING.lock().await;
+    let task = running
+        .get(&run_id)
+        .ok_or_else(|| crate::error::AppError::from("任务未在运行".to_string()))?;
+    task.stop.notify_one();
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_running_project_tasks(
+    project_id: Option<String>,
+) -> AppResult<Vec<ProjectTaskRun>> {
+    let running = RUNNING.lock().await;
+    let mut list: Vec<ProjectTaskRun> = running
+        .values()
+        .filter(|t| {
+            project_id
+                .as_deref()
+                .map(|p| t.info.project_id == p)
+                .unwrap_or(true)
+        })
+        .map(|t| t.info.clone())
+        .collect();
+    list.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+    Ok(list)
+}
+
+/// 获取运行实例最近的输出
+#[tauri::command]
+#[specta::specta]
+pub async fn get_project_task_output(run_id: String) -> AppResult<Vec<ProjectTaskOutput>> {
+    let running = RUNNING.lock().await;
+    let task = running
+        .get(&run_id)
+        .ok_or_else(|| crate::error::AppError::from("任务未在运行".to_string()))?;
+    let output = task
+        .output
+        .lock()
+        .map_err(|e| crate::error::AppError::internal(e.to_string()))?;
+    Ok(output.iter().cloned().collect())
+}
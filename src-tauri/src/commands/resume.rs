@@ -43,7 +43,7 @@ pub async fn llm_proxy_request(request: LlmProxyRequest) -> AppResult<LlmProxyRe
 
     let method = reqwest::Method::from_bytes(request.method.as_bytes())
         .map_err(|e| AppError::invalid(format!("模型请求 method 不合法: {}", e)))?;
-    let client = reqwest::Client::builder()
+    let client = crate::http_client::builder()
         .timeout(std::time::Duration::from_secs(300))
         .build()
         .map_err(|e| AppError::from(format!("创建模型请求客户端失败: {}", e)))?;
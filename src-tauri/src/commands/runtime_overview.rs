@@ -0,0 +1,256 @@
+// 运行概览 - 一次取回所有正在运行的东西，供状态栏和托盘提示使用
+//
+// 包括运行中的静态服务、转发规则、进行中的下载、已连接 / 监听中的 Netcat 会话，
+// 以及应用启动并管理的进程（项目任务、内置终端、文档预览开发服务器）。
+// 只带关键统计，详情仍由各模块自己的命令获取。
+
+use super::toolbox::netcat::{NetcatState, Protocol, SessionMode, SessionStatus};
+use super::toolbox::{self, format_bytes};
+use super::{docs_preview, project_tasks, terminal};
+use crate::error::AppResult;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeServer {
+    pub id: String,
+    pub name: String,
+    pub port: u16,
+    pub url_prefix: String,
+    pub root_dir: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeForward {
+    pub id: String,
+    pub name: String,
+    pub local_port: u16,
+    /// host:port，或 unix / pipe 目标的路径
+    pub target: String,
+    pub connections: u32,
+    pub bytes_in: u64,
+    pub bytes_out: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeDownload {
+    pub id: String,
+    pub file_name: String,
+    pub downloaded_size: u64,
+    /// 未知时为 0
+    pub total_size: u64,
+    /// 字节/秒
+    pub speed: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeNetcatSession {
+    pub id: String,
+    pub name: String,
+    pub protocol: Protocol,
+    pub mode: SessionMode,
+    pub host: String,
+    pub port: u16,
+    pub status: SessionStatus,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    /// 仅服务器模式
+    pub client_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeProcess {
+    /// 项目任务为 run_id，终端为会话 id，文档预览为项目路径
+    pub id: String,
+    /// "project_task" | "terminal" | "docs_preview"
+    pub kind: String,
+    pub name: String,
+    pub pid: Option<u32>,
+    pub started_at: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeOverview {
+    pub servers: Vec<RuntimeServer>,
+    pub forwards: Vec<RuntimeForward>,
+    pub downloads: Vec<RuntimeDownload>,
+    pub netcat_sessions: Vec<RuntimeNetcatSession>,
+    pub processes: Vec<RuntimeProcess>,
+    /// 所有下载的速度之和，字节/秒
+    pub download_speed: u64,
+}
+
+impl RuntimeOverview {
+    /// 每类一行的简短摘要，没有运行中的项目时为空
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if !self.servers.is_empty() {
+            lines.push(format!("{} 个服务运行中", self.servers.len()));
+        }
+        if !self.forwards.is_empty() {
+            let connections: u32 = self.forwards.iter().map(|f| f.connections).sum();
+            lines.push(format!(
+                "{} 条转发运行中（{} 个连接）",
+                self.forwards.len(),
+                connections
+            ));
+        }
+        if !self.downloads.is_empty() {
+            lines.push(format!(
+                "{} 个下载进行中（{}/s）",
+                self.downloads.len(),
+                format_bytes(self.download_speed)
+            ));
+        }
+        if !self.netcat_sessions.is_empty() {
+            lines.push(format!("{} 个 Netcat 会话", self.netcat_sessions.len()));
+        }
+        if !self.processes.is_empty() {
+            lines.push(format!("{} 个进程", self.processes.len()));
+        }
+        lines
+    }
+}
+
+/// 收集当前运行状态；单个来源失败时该类留空，不影响其它
+pub(crate) async fn collect(app: &AppHandle) -> RuntimeOverview {
+    let mut overview = RuntimeOverview::default();
+
+    if let Ok(list) = toolbox::server::get_servers().await {
+        overview.servers = list
+            .into_iter()
+            .filter(|s| s.status == "running")
+            .map(|s| RuntimeServer {
+                id: s.id,
+                name: s.name,
+                port: s.port,
+                url_prefix: s.url_prefix,
+                root_dir: s.root_dir,
+            })
+            .collect();
+        overview.servers.sort_by_key(|s| s.port);
+    }
+
+    if let Ok(list) = toolbox::forwarder::get_forward_rules().await {
+        overview.forwards = list
+            .into_iter()
+            .filter(|r| r.status == "running")
+            .map(|r| RuntimeForward {
+                target: match r.socket_path {
+                    Some(path) if r.target_kind != "tcp" => path,
+                    _ => format!("{}:{}", r.remote_host, r.remote_port),
+                },
+                id: r.id,
+                name: r.name,
+                local_port: r.local_port,
+                connections: r.connections,
+                bytes_in: r.bytes_in,
+                bytes_out: r.bytes_out,
+            })
+            .collect();
+        overview.forwards.sort_by_key(|r| r.local_port);
+    }
+
+    if let Ok(list) = toolbox::downloader::get_download_tasks().await {
+        let mut downloading: Vec<_> = list
+            .into_iter()
+            .filter(|t| t.status == "downloading")
+            .collect();
+        downloading.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        overview.downloads = downloading
+            .into_iter()
+            .map(|t| RuntimeDownload {
+                id: t.id,
+                file_name: t.file_name,
+                downloaded_size: t.downloaded_size,
+                total_size: t.total_size,
+                speed: t.speed,
+            })
+            .collect();
+        overview.download_speed = overview.downloads.iter().map(|d| d.speed).sum();
+    }
+
+    if let Some(state) = app.try_state::<NetcatState>() {
+        let sessions = state.sessions.read().await;
+        for session in sessions.values() {
+            let guard = session.read().await;
+            let s = &guard.session;
+            if matches!(
+                s.status,
+                SessionStatus::Connecting | SessionStatus::Connected | SessionStatus::Listening
+            ) {
+                overview.netcat_sessions.push(RuntimeNetcatSession {
+                    id: s.id.clone(),
+                    name: s.name.clone(),
+                    protocol: s.protocol,
+                    mode: s.mode,
+                    host: s.host.clone(),
+                    port: s.port,
+                    status: s.status,
+                    bytes_sent: s.bytes_sent,
+                    bytes_received: s.bytes_received,
+                    client_count: s.client_count,
+                });
+            }
+        }
+        overview.netcat_sessions.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    if let Ok(runs) = project_tasks::list_running_project_tasks(None).await {
+        overview
+            .processes
+            .extend(runs.into_iter().map(|r| RuntimeProcess {
+                id: r.run_id,
+                kind: "project_task".to_string(),
+                name: r.name,
+                pid: r.pid,
+                started_at: r.started_at,
+            }));
+    }
+    if let Ok(terminals) = terminal::list_terminals(None).await {
+        overview
+            .processes
+            .extend(terminals.into_iter().map(|t| RuntimeProcess {
+                id: t.id,
+                kind: "terminal".to_string(),
+                name: format!("{} ({})", t.shell, t.cwd),
+                pid: t.pid,
+                started_at: t.started_at,
+            }));
+    }
+    // static 模式的预览已计入静态服务
+    if let Ok(previews) = docs_preview::get_docs_previews().await {
+        overview
+            .processes
+            .extend(
+                previews
+                    .into_iter()
+                    .filter(|p| p.mode == "dev")
+                    .map(|p| RuntimeProcess {
+                        name: format!("{} 文档预览", p.kind),
+                        id: p.project_path,
+                        kind: "docs_preview".to_string(),
+                        pid: p.pid,
+                        started_at: p.started_at,
+                    }),
+            );
+    }
+    overview
+        .processes
+        .sort_by(|a, b| a.started_at.cmp(&b.started_at));
+
+    overview
+}
+
+/// 获取运行概览
+#[tauri::command]
+#[specta::specta]
+pub async fn get_runtime_overview(app: AppHandle) -> AppResult<RuntimeOverview> {
+    Ok(collect(&app).await)
+}
@@ -0,0 +1,442 @@
+//! 草稿本：任意数量的命名 Markdown 笔记，自动保存并保留历史版本，可全文搜索
+//!
+//! 存储在 data/scratchpad/<id>/ 下：note.md 为当前内容，meta.json 为名称与时间，
+//! versions/<毫秒时间戳>.md 为历史快照。自动保存时距上次快照超过 VERSION_INTERVAL
+//! 才把旧内容存为一个版本，避免每次按键都产生版本；每篇最多保留 MAX_VERSIONS 个。
+
+use crate::error::{AppError, AppResult};
+use crate::storage::{current_iso_time, generate_id, get_storage_config};
+use chrono::{TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 自动快照的最小间隔（秒）
+const VERSION_INTERVAL_SECS: i64 = 5 * 60;
+
+const MAX_VERSIONS: usize = 50;
+
+const MAX_NOTE_BYTES: usize = 5 * 1024 * 1024;
+
+const MAX_NAME_CHARS: usize = 100;
+
+/// 每篇笔记最多返回的命中行
+const MAX_LINES_PER_NOTE: usize = 20;
+
+const PREVIEW_CHARS: usize = 120;
+
+/// 串行化读-改-写，自动保存与手动操作可能同时到达
+static LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NoteMeta {
+    id: String,
+    name: String,
+    created_at: String,
+    updated_at: String,
+    /// 上次快照的毫秒时间戳
+    #[serde(default)]
+    last_version_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ScratchNote {
+    pub id: String,
+    pub name: String,
+    pub content: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ScratchNoteSummary {
+    pub id: String,
+    pub name: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub bytes: u64,
+    /// 第一行非空内容
+    pub preview: String,
+    pub version_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ScratchNoteVersion {
+    pub id: String,
+    pub saved_at: String,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ScratchSearchLine {
+    /// 从 1 开始
+    pub line: u32,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ScratchSearchResult {
+    pub id: String,
+    pub name: String,
+    pub updated_at: String,
+    pub name_matches: bool,
+    pub lines: Vec<ScratchSearchLine>,
+}
+
+// ========== 存储 ==========
+
+fn root_dir() -> AppResult<PathBuf> {
+    let dir = get_storage_config()?.scratchpad_dir();
+    fs::create_dir_all(&dir).map_err(|e| AppError::from(format!("创建目录失败: {}", e)))?;
+    Ok(dir)
+}
+
+fn note_dir(id: &str) -> AppResult<PathBuf> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(AppError::invalid(format!("无效的笔记 ID: {}", id)));
+    }
+    Ok(root_dir()?.join(id))
+}
+
+fn write_atomic(path: &Path, content: &str) -> AppResult<()> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, content)
+        .and_then(|_| fs::rename(&tmp, path))
+        .map_err(|e| AppError::from(format!("写入 {:?} 失败: {}", path, e)))
+}
+
+fn load_meta(dir: &Path) -> AppResult<NoteMeta> {
+    let text =
+        fs::read_to_string(dir.join("meta.json")).map_err(|_| AppError::invalid("笔记不存在"))?;
+    Ok(serde_json::from_str(&text)?)
+}
+
+fn save_meta(dir: &Path, meta: &NoteMeta) -> AppResult<()> {
+    write_atomic(&dir.join("meta.json"), &serde_json::to_string_pretty(meta)?)
+}
+
+fn read_content(dir: &Path) -> String {
+    fs::read_to_string(dir.join("note.md")).unwrap_or_default()
+}
+
+fn all_meta() -> AppResult<Vec<NoteMeta>> {
+    let mut notes = Vec::new();
+    for entry in fs::read_dir(root_dir()?)?.flatten() {
+        if let Ok(meta) = load_meta(&entry.path()) {
+            notes.push(meta);
+        }
+    }
+    notes.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+    Ok(notes)
+}
+
+fn validate_name(name: &str, exclude_id: Option<&str>) -> AppResult<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::invalid("名称不能为空"));
+    }
+    if name.chars().count() > MAX_NAME_CHARS {
+        return Err(AppError::invalid(format!(
+            "名称不能超过 {} 个字符",
+            MAX_NAME_CHARS
+        )));
+    }
+    let taken = all_meta()?
+        .iter()
+        .any(|m| Some(m.id.as_str()) != exclude_id && m.name.eq_ignore_ascii_case(name));
+    if taken {
+        return Err(AppError::invalid(format!("已存在同名笔记: {}", name)));
+    }
+    Ok(name.to_string())
+}
+
+fn check_size(content: &str) -> AppResult<()> {
+    if content.len() > MAX_NOTE_BYTES {
+        return Err(AppError::invalid(format!(
+            "内容过大（上限 {} MB）",
+            MAX_NOTE_BYTES / 1024 / 1024
+        )));
+    }
+    Ok(())
+}
+
+/// 版本文件，按时间升序
+fn version_files(dir: &Path) -> Vec<(i64, PathBuf)> {
+    let mut versions: Vec<(i64, PathBuf)> = fs::read_dir(dir.join("versions"))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| {
+                    let path = e.path();
+                    let stamp = path.file_stem()?.to_str()?.parse().ok()?;
+                    Some((stamp, path))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    versions.sort_by_key(|(stamp, _)| *stamp);
+    versions
+}
+
+/// 把当前内容存为一个版本并淘汰最旧的
+fn snapshot(dir: &Path, meta: &mut NoteMeta, content: &str) -> AppResult<()> {
+    let versions_dir = dir.join("versions");
+    fs::create_dir_all(&versions_dir)?;
+    // 同一毫秒内连续快照时顺延，避免覆盖
+    let mut now = Utc::now().timestamp_millis();
+    while versions_dir.join(format!("{}.md", now)).exists() {
+        now += 1;
+    }
+    fs::write(versions_dir.join(format!("{}.md", now)), content)?;
+    meta.last_version_at = Some(now);
+    let versions = version_files(dir);
+    let excess = versions.len().saturating_sub(MAX_VERSIONS);
+    for (_, path) in versions.into_iter().take(excess) {
+        let _ = fs::remove_file(path);
+    }
+    Ok(())
+}
+
+fn to_note(meta: NoteMeta, content: String) -> ScratchNote {
+    ScratchNote {
+        id: meta.id,
+        name: meta.name,
+        content,
+        created_at: meta.created_at,
+        updated_at: meta.updated_at,
+    }
+}
+
+fn stamp_to_iso(stamp: i64) -> String {
+    Utc.timestamp_millis_opt(stamp)
+        .single()
+        .map(|t| t.to_rfc3339())
+        .unwrap_or_default()
+}
+
+fn lock() -> std::sync::MutexGuard<'static, ()> {
+    LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// ========== 命令 ==========
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_scratch_notes() -> AppResult<Vec<ScratchNoteSummary>> {
+    let _guard = lock();
+    let root = root_dir()?;
+    Ok(all_meta()?
+        .into_iter()
+        .map(|meta| {
+            let dir = root.join(&meta.id);
+            let content = read_content(&dir);
+            let preview = content
+                .lines()
+                .map(str::trim)
+                .find(|l| !l.is_empty())
+                .unwrap_or("")
+                .chars()
+                .take(PREVIEW_CHARS)
+                .collect();
+            ScratchNoteSummary {
+                bytes: content.len() as u64,
+                preview,
+                version_count: version_files(&dir).len() as u32,
+                id: meta.id,
+                name: meta.name,
+                created_at: meta.created_at,
+                updated_at: meta.updated_at,
+            }
+        })
+        .collect())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_scratch_note(id: String) -> AppResult<ScratchNote> {
+    let _guard = lock();
+    let dir = note_dir(&id)?;
+    let meta = load_meta(&dir)?;
+    Ok(to_note(meta, read_content(&dir)))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn create_scratch_note(name: String, content: Option<String>) -> AppResult<ScratchNote> {
+    let _guard = lock();
+    let name = validate_name(&name, None)?;
+    let content = content.unwrap_or_default();
+    check_size(&content)?;
+    let id = generate_id();
+    let dir = note_dir(&id)?;
+    fs::create_dir_all(&dir)?;
+    let now = current_iso_time();
+    let meta = NoteMeta {
+        id,
+        name,
+        created_at: now.clone(),
+        updated_at: now,
+        last_version_at: None,
+    };
+    write_atomic(&dir.join("note.md"), &content)?;
+    save_meta(&dir, &meta)?;
+    Ok(to_note(meta, content))
+}
+
+/// 自动保存入口；内容未变化时不更新时间。force_version 为 true 时不论间隔都先保存一个版本
+#[tauri::command]
+#[specta::specta]
+pub async fn save_scratch_note(
+    id: String,
+    content: String,
+    force_version: Option<bool>,
+) -> AppResult<ScratchNote> {
+    let _guard = lock();
+    check_size(&content)?;
+    let dir = note_dir(&id)?;
+    let mut meta = load_meta(&dir)?;
+    let current = read_content(&dir);
+    if current == content {
+        return Ok(to_note(meta, content));
+    }
+    let due = meta.last_version_at.map_or(true, |t| {
+        Utc::now().timestamp_millis() - t >= VERSION_INTERVAL_SECS * 1000
+    });
+    if !current.is_empty() && (force_version.unwrap_or(false) || due) {
+        snapshot(&dir, &mut meta, &current)?;
+    }
+    write_atomic(&dir.join("note.md"), &content)?;
+    meta.updated_at = current_iso_time();
+    save_meta(&dir, &meta)?;
+    Ok(to_note(meta, content))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn rename_scratch_note(id: String, name: String) -> AppResult<ScratchNote> {
+    let _guard = lock();
+    let dir = note_dir(&id)?;
+    let mut meta = load_meta(&dir)?;
+    meta.name = validate_name(&name, Some(&id))?;
+    meta.updated_at = current_iso_time();
+    save_meta(&dir, &meta)?;
+    Ok(to_note(meta, read_content(&dir)))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_scratch_note(id: String) -> AppResult<()> {
+    let _guard = lock();
+    let dir = note_dir(&id)?;
+    if dir.exists() {
+        fs::remove_dir_all(&dir).map_err(|e| AppError::from(format!("删除失败: {}", e)))?;
+    }
+    Ok(())
+}
+
+/// 历史版本，最新的在前
+#[tauri::command]
+#[specta::specta]
+pub async fn list_scratch_note_versions(id: String) -> AppResult<Vec<ScratchNoteVersion>> {
+    let _guard = lock();
+    let dir = note_dir(&id)?;
+    load_meta(&dir)?;
+    Ok(version_files(&dir)
+        .into_iter()
+        .rev()
+        .map(|(stamp, path)| ScratchNoteVersion {
+            id: stamp.to_string(),
+            saved_at: stamp_to_iso(stamp),
+            bytes: fs::metadata(path).map_or(0, |m| m.len()),
+        })
+        .collect())
+}
+
+fn version_path(dir: &Path, version_id: &str) -> AppResult<PathBuf> {
+    let path = version_id
+        .parse::<i64>()
+        .ok()
+        .map(|stamp| dir.join("versions").join(format!("{}.md", stamp)))
+        .filter(|p| p.is_file());
+    path.ok_or_else(|| AppError::invalid(format!("版本不存在: {}", version_id)))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_scratch_note_version(id: String, version_id: String) -> AppResult<String> {
+    let _guard = lock();
+    let dir = note_dir(&id)?;
+    Ok(fs::read_to_string(version_path(&dir, &version_id)?)?)
+}
+
+/// 用历史版本覆盖当前内容；覆盖前先把当前内容存为一个版本，恢复本身可以撤销
+#[tauri::command]
+#[specta::specta]
+pub async fn restore_scratch_note_version(
+    id: String,
+    version_id: String,
+) -> AppResult<ScratchNote> {
+    let _guard = lock();
+    let dir = note_dir(&id)?;
+    let mut meta = load_meta(&dir)?;
+    let content = fs::read_to_string(version_path(&dir, &version_id)?)?;
+    let current = read_content(&dir);
+    if current != content {
+        if !current.is_empty() {
+            snapshot(&dir, &mut meta, &current)?;
+        }
+        write_atomic(&dir.join("note.md"), &content)?;
+        meta.updated_at = current_iso_time();
+        save_meta(&dir, &meta)?;
+    }
+    Ok(to_note(meta, content))
+}
+
+/// 全文搜索：按空白拆分关键词，忽略大小写，名称或内容包含全部关键词的笔记才返回
+#[tauri::command]
+#[specta::specta]
+pub async fn search_scratch_notes(query: String) -> AppResult<Vec<ScratchSearchResult>> {
+    let terms: Vec<String> = query.split_whitespace().map(|t| t.to_lowercase()).collect();
+    if terms.is_empty() {
+        return Ok(Vec::new());
+    }
+    let _guard = lock();
+    let root = root_dir()?;
+    let mut results = Vec::new();
+    for meta in all_meta()? {
+        let content = read_content(&root.join(&meta.id));
+        let name = meta.name.to_lowercase();
+        let haystack = format!("{}\n{}", name, content.to_lowercase());
+        if !terms.iter().all(|t| haystack.contains(t.as_str())) {
+            continue;
+        }
+        let lines = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| {
+                let line = line.to_lowercase();
+                terms.iter().any(|t| line.contains(t.as_str()))
+            })
+            .take(MAX_LINES_PER_NOTE)
+            .map(|(index, line)| ScratchSearchLine {
+                line: index as u32 + 1,
+                text: line.trim().chars().take(PREVIEW_CHARS * 2).collect(),
+            })
+            .collect();
+        results.push(ScratchSearchResult {
+            name_matches: terms.iter().any(|t| name.contains(t.as_str())),
+            id: meta.id,
+            name: meta.name,
+            updated_at: meta.updated_at,
+            lines,
+        });
+    }
+    Ok(results)
+}
@@ -7,8 +7,10 @@ use std::fs;
 use crate::error::AppResult;
 use crate::storage::{
     current_iso_time, generate_id, get_storage_config, AiProviderConfig, AppSettings, EditorConfig,
-    McpGatewayKey, Notification, TerminalConfig, UiState,
+    GitIdentityProfile, IdlePolicySettings, McpGatewayKey, Notification, NotificationAction,
+    NotificationRetention, NotificationRouting, ProxySettings, TerminalConfig, UiState,
 };
+use crate::storage::{documents, external_changes};
 
 // ============== 标签管理 ==============
 
@@ -47,8 +49,9 @@ pub async fn save_labels(labels: Vec<String>) -> AppResult<()> {
     let content = serde_json::to_string(&labels)
         .map_err(|e| crate::error::AppError::from(format!("序列化标签失败: {}", e)))?;
 
-    fs::write(config.labels_file(), content)
-        .map_err(|e| crate::error::AppError::from(format!("保存标签失败: {}", e)))?;
+    external_changes::write_checked("labels", || {
+        documents::write_atomic(&config.labels_file(), &content)
+    })?;
     Ok(())
 }
 
@@ -105,8 +108,9 @@ pub async fn save_categories(categories: Vec<String>) -> AppResult<()> {
     let content = serde_json::to_string(&categories)
         .map_err(|e| crate::error::AppError::from(format!("序列化分类失败: {}", e)))?;
 
-    fs::write(config.categories_file(), content)
-        .map_err(|e| crate::error::AppError::from(format!("保存分类失败: {}", e)))?;
+    external_changes::write_checked("categories", || {
+        documents::write_atomic(&config.categories_file(), &content)
+    })?;
     Ok(())
 }
 
@@ -143,30 +147,11 @@ pub struct EditorInput {
 #[tauri::command]
 #[specta::specta]
 pub async fn get_editors() -> AppResult<Vec<EditorConfig>> {
-    let config = get_storage_config()?;
-    let path = config.editors_file();
-
-    if !path.exists() {
-        return Ok(vec![]);
-    }
-
-    let content = fs::read_to_string(&path)
-        .map_err(|e| crate::error::AppError::from(format!("读取编辑器配置失败: {}", e)))?;
-
-    let editors: Vec<EditorConfig> = serde_json::from_str(&content).unwrap_or_default();
-    Ok(editors)
+    documents::load(&documents::EDITORS)
 }
 
 async fn save_editors(editors: &[EditorConfig]) -> AppResult<()> {
-    let config = get_storage_config()?;
-    config.ensure_dirs()?;
-
-    let content = serde_json::to_string(editors)
-        .map_err(|e| crate::error::AppError::from(format!("序列化编辑器配置失败: {}", e)))?;
-
-    fs::write(config.editors_file(), content)
-        .map_err(|e| crate::error::AppError::from(format!("保存编辑器配置失败: {}", e)))?;
-    Ok(())
+    documents::save(&documents::EDITORS, &editors)
 }
 
 #[tauri::command]
@@ -263,38 +248,19 @@ pub struct TerminalInput {
 #[tauri::command]
 #[specta::specta]
 pub async fn get_terminal_config() -> AppResult<TerminalConfig> {
-    let config = get_storage_config()?;
-    let path = config.terminal_file();
-
-    if !path.exists() {
-        return Ok(TerminalConfig::default());
-    }
-
-    let content = fs::read_to_string(&path)
-        .map_err(|e| crate::error::AppError::from(format!("读取终端配置失败: {}", e)))?;
-
-    let terminal: TerminalConfig = serde_json::from_str(&content).unwrap_or_default();
-    Ok(terminal)
+    documents::load(&documents::TERMINAL)
 }
 
 #[tauri::command]
 #[specta::specta]
 pub async fn save_terminal_config(input: TerminalInput) -> AppResult<()> {
-    let config = get_storage_config()?;
-    config.ensure_dirs()?;
-
     let terminal = TerminalConfig {
         terminal_type: input.terminal_type,
         custom_path: input.custom_path,
         terminal_path: input.terminal_path,
     };
 
-    let content = serde_json::to_string(&terminal)
-        .map_err(|e| crate::error::AppError::from(format!("序列化终端配置失败: {}", e)))?;
-
-    fs::write(config.terminal_file(), content)
-        .map_err(|e| crate::error::AppError::from(format!("保存终端配置失败: {}", e)))?;
-    Ok(())
+    documents::save(&documents::TERMINAL, &terminal)
 }
 
 // ============== 应用设置管理 ==============
@@ -317,32 +283,50 @@ pub struct AppSettingsInput {
     pub mcp_gateway_port: Option<u16>,
     pub mcp_gateway_keys: Option<Vec<McpGatewayKey>>,
     pub show_dock_icon: Option<bool>,
+    pub auto_resume_services: Option<bool>,
+    pub proxy: Option<ProxySettings>,
+    pub download_handoff_enabled: Option<bool>,
+    pub download_handoff_port: Option<u16>,
+    pub metrics_enabled: Option<bool>,
+    pub metrics_port: Option<u16>,
+    pub usage_stats_enabled: Option<bool>,
+    pub download_virus_scan: Option<bool>,
+    pub download_virus_action: Option<String>,
+    pub power_saver_enabled: Option<bool>,
+    pub power_saver_threshold: Option<u8>,
+    pub git_identities: Option<Vec<GitIdentityProfile>>,
+    pub git_identity_guard: Option<bool>,
+    /// 空串表示清除覆盖
+    pub git_executable: Option<String>,
+    pub tray_badge_source: Option<String>,
+    pub idle_policy: Option<IdlePolicySettings>,
+    pub scan_roots: Option<Vec<String>>,
+    pub notification_retention: Option<NotificationRetention>,
+    pub notification_routing: Option<NotificationRouting>,
+    pub stats_retention_days: Option<u32>,
 }
 
+/// 返回给前端的设置：去掉只读模式密码哈希
 #[tauri::command]
 #[specta::specta]
 pub async fn get_app_settings() -> AppResult<AppSettings> {
-    let config = get_storage_config()?;
-    let path = config.app_settings_file();
-
-    if !path.exists() {
-        return Ok(AppSettings::default());
-    }
-
-    let content = fs::read_to_string(&path)
-        .map_err(|e| crate::error::AppError::from(format!("读取应用设置失败: {}", e)))?;
-
-    let settings: AppSettings = serde_json::from_str(&content).unwrap_or_default();
+    let mut settings = load_app_settings().await?;
+    settings.read_only_password_hash = None;
     Ok(settings)
 }
 
+/// 读取完整设置（含密码哈希），供后端读改写使用
+pub async fn load_app_settings() -> AppResult<AppSettings> {
+    documents::load(&documents::APP_SETTINGS)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn save_app_settings(
     app: tauri::AppHandle,
     input: AppSettingsInput,
 ) -> AppResult<AppSettings> {
-    let mut settings = get_app_settings().await?;
+    let mut settings = load_app_settings().await?;
 
     if let Some(theme) = input.theme {
         settings.theme = theme;
@@ -394,21 +378,189 @@ pub async fn save_app_settings(
         #[cfg(target_os = "macos")]
         crate::app_setup::apply_dock_visibility(&app, v);
     }
+    if let Some(v) = input.auto_resume_services {
+        settings.auto_resume_services = v;
+    }
+    if let Some(v) = input.proxy {
+        crate::http_client::validate_proxy(&v)?;
+        settings.proxy = v;
+    }
+    if let Some(v) = input.download_handoff_enabled {
+        settings.download_handoff_enabled = v;
+    }
+    if let Some(v) = input.download_handoff_port {
+        settings.download_handoff_port = v;
+    }
+    if let Some(v) = input.metrics_enabled {
+        settings.metrics_enabled = v;
+    }
+    if let Some(v) = input.metrics_port {
+        if v == 0 {
+            return Err(crate::error::AppError::invalid("指标端点端口不能为 0"));
+        }
+        settings.metrics_port = v;
+    }
+    if let Some(v) = input.usage_stats_enabled {
+        settings.usage_stats_enabled = v;
+        super::usage_stats::set_enabled(v);
+    }
+    if let Some(v) = input.download_virus_scan {
+        settings.download_virus_scan = v;
+    }
+    if let Some(v) = input.download_virus_action {
+        if !matches!(v.as_str(), "none" | "quarantine" | "delete") {
+            return Err(crate::error::AppError::invalid(format!(
+                "无效的病毒处理方式: {}",
+                v
+            )));
+        }
+        settings.download_virus_action = v;
+    }
+    if let Some(v) = input.power_saver_enabled {
+        settings.power_saver_enabled = v;
+    }
+    if let Some(v) = input.power_saver_threshold {
+        settings.power_saver_threshold = v.min(100);
+    }
+    if let Some(v) = input.git_identities {
+        if let Some(p) = v
+            .iter()
+            .find(|p| p.user_name.trim().is_empty() || !p.email.contains('@'))
+        {
+            return Err(crate::error::AppError::invalid(format!(
+                "身份模板「{}」的用户名或邮箱无效",
+                p.name
+            )));
+        }
+        settings.git_identities = v;
+    }
+    if let Some(v) = input.git_identity_guard {
+        settings.git_identity_guard = v;
+    }
+    if let Some(v) = input.git_executable {
+        let v = v.trim().to_string();
+        if !v.is_empty() {
+            crate::git_executable::check(&v)?;
+        }
+        settings.git_executable = Some(v).filter(|v| !v.is_empty());
+    }
+    if let Some(v) = input.tray_badge_source {
+        if !crate::tray_badge::SOURCES.contains(&v.as_str()) {
+            return Err(crate::error::AppError::invalid(format!(
+                "无效的托盘角标来源: {}",
+                v
+            )));
+        }
+        crate::tray_badge::set_source(&v);
+        settings.tray_badge_source = v;
+    }
+    if let Some(v) = input.idle_policy {
+        settings.idle_policy = v;
+    }
+    if let Some(v) = input.scan_roots {
+        settings.scan_roots = v
+            .into_iter()
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty())
+            .collect();
+    }
+    if let Some(v) = input.notification_retention {
+        settings.notification_retention = v;
+    }
+    if let Some(v) = input.notification_routing {
+        crate::notification_routing::validate(&v)?;
+        settings.notification_routing = v;
+    }
+    if let Some(v) = input.stats_retention_days {
+        settings.stats_retention_days = v;
+    }
+    if settings.download_handoff_enabled && settings.download_handoff_token.is_none() {
+        settings.download_handoff_token = Some(crate::storage::secrets::new_token()?);
+    }
 
-    let config = get_storage_config()?;
-    config.ensure_dirs()?;
+    documents::save(&documents::APP_SETTINGS, &settings)?;
+    apply_app_settings(&app, &settings).await?;
 
-    let content = serde_json::to_string(&settings)
-        .map_err(|e| crate::error::AppError::from(format!("序列化应用设置失败: {}", e)))?;
+    // 与 get_app_settings 一样不把密码哈希交给前端
+    settings.read_only_password_hash = None;
+    Ok(settings)
+}
 
-    fs::write(config.app_settings_file(), content)
-        .map_err(|e| crate::error::AppError::from(format!("保存应用设置失败: {}", e)))?;
+/// 保存后让代理、聊天桥接、MCP Gateway、下载接力按新设置生效
+async fn apply_app_settings(app: &tauri::AppHandle, settings: &AppSettings) -> AppResult<()> {
+    crate::http_client::set_proxy(settings.proxy.clone());
+    crate::git_executable::reload().await;
 
     // 通知聊天桥接 poller 重新加载配置
-    super::chat_bridge::notify_reload(&app).await;
-    crate::mcp_gateway::apply_settings(&settings).await?;
+    super::chat_bridge::notify_reload(app).await;
+    crate::mcp_gateway::apply_settings(settings).await?;
+    super::toolbox::download_handoff::apply_settings(app, settings).await?;
+    super::toolbox::metrics::apply_settings(settings).await?;
+    Ok(())
+}
 
-    Ok(settings)
+/// 设置文件被外部修改后调用：重新读取并应用所有有运行时状态的设置项
+pub(crate) async fn reload_app_settings(app: &tauri::AppHandle) -> AppResult<()> {
+    let settings = load_app_settings().await?;
+    crate::tray_badge::set_source(&settings.tray_badge_source);
+    super::usage_stats::set_enabled(settings.usage_stats_enabled);
+    #[cfg(target_os = "macos")]
+    crate::app_setup::apply_dock_visibility(app, settings.show_dock_icon);
+    apply_app_settings(app, &settings).await
+}
+
+// ============== 只读模式 ==============
+
+#[derive(Debug, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadOnlyStatus {
+    pub enabled: bool,
+    pub has_password: bool,
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_read_only_status() -> AppResult<ReadOnlyStatus> {
+    let settings = load_app_settings().await?;
+    Ok(ReadOnlyStatus {
+        enabled: crate::read_only::is_enabled(),
+        has_password: settings.read_only_password_hash.is_some(),
+    })
+}
+
+/// 开关只读模式。设置过密码时，关闭只读或修改密码都要先验证 password；
+/// 开启时可通过 new_password 设置新密码（传空字符串清除密码）
+#[tauri::command]
+#[specta::specta]
+pub async fn set_read_only_mode(
+    enabled: bool,
+    password: Option<String>,
+    new_password: Option<String>,
+) -> AppResult<ReadOnlyStatus> {
+    let mut settings = load_app_settings().await?;
+
+    let needs_password = !enabled || new_password.is_some();
+    if needs_password {
+        if let Some(hash) = settings.read_only_password_hash.as_deref() {
+            crate::read_only::verify_password(hash, password.as_deref().unwrap_or(""))?;
+        }
+    }
+
+    if let Some(new_password) = new_password {
+        settings.read_only_password_hash = if new_password.is_empty() {
+            None
+        } else {
+            Some(crate::read_only::hash_password(&new_password)?)
+        };
+    }
+    settings.read_only_mode = enabled;
+    documents::save(&documents::APP_SETTINGS, &settings)?;
+    crate::read_only::set_enabled(enabled);
+
+    Ok(ReadOnlyStatus {
+        enabled,
+        has_password: settings.read_only_password_hash.is_some(),
+    })
 }
 
 // ============== UI 状态管理 ==============
@@ -416,6 +568,8 @@ pub async fn save_app_settings(
 #[derive(Debug, Serialize, Deserialize, specta::Type)]
 pub struct UiStateInput {
     pub recent_detail_project_ids: Option<Vec<String>>,
+    /// 空字符串表示回到工具列表
+    pub active_toolbox_tool: Option<String>,
 }
 
 #[tauri::command]
@@ -443,6 +597,9 @@ pub async fn save_ui_state(input: UiStateInput) -> AppResult<UiState> {
     if let Some(ids) = input.recent_detail_project_ids {
         ui_state.recent_detail_project_ids = ids;
     }
+    if let Some(tool) = input.active_toolbox_tool {
+        ui_state.active_toolbox_tool = Some(tool).filter(|t| !t.is_empty());
+    }
 
     let config = get_storage_config()?;
     config.ensure_dirs()?;
@@ -458,12 +615,25 @@ pub async fn save_ui_state(input: UiStateInput) -> AppResult<UiState> {
 
 // ============== 通知管理 ==============
 
+/// 通知严重程度
+const NOTIFICATION_SEVERITIES: &[&str] = &["info", "success", "warning", "error"];
+
+/// 通知操作类型
+const NOTIFICATION_ACTION_KINDS: &[&str] =
+    &["open_folder", "open_url", "retry_download", "navigate"];
+
 #[derive(Debug, Serialize, Deserialize, specta::Type)]
 pub struct NotificationInput {
+    /// 严重程度："info" | "success" | "warning" | "error"，其它值按 info 处理
     pub notification_type: String,
     pub title: String,
     #[serde(default)]
     pub message: String,
+    /// 来源类别，前端发起的通知留空
+    #[serde(default)]
+    pub category: String,
+    #[serde(default)]
+    pub actions: Vec<NotificationAction>,
 }
 
 #[tauri::command]
@@ -483,11 +653,45 @@ pub async fn get_notifications() -> AppResult<Vec<Notification>> {
     Ok(notifications)
 }
 
+/// 按保留策略删除旧通知；列表按新到旧排列
+fn apply_notification_retention(
+    notifications: &mut Vec<Notification>,
+    policy: &NotificationRetention,
+) {
+    if policy.max_age_days > 0 {
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(policy.max_age_days as i64);
+        notifications.retain(|n| {
+            (policy.keep_unread && !n.read)
+                || chrono::DateTime::parse_from_rfc3339(&n.created_at)
+                    .map_or(true, |t| t.with_timezone(&chrono::Utc) >= cutoff)
+        });
+    }
+
+    let max = policy.max_count.max(1) as usize;
+    if notifications.len() > max {
+        // 先从最旧的已读通知删起，仍超出时再删未读的
+        let mut excess = notifications.len() - max;
+        let mut index = notifications.len();
+        while excess > 0 && index > 0 {
+            index -= 1;
+            if notifications[index].read {
+                notifications.remove(index);
+                excess -= 1;
+            }
+        }
+        notifications.truncate(max);
+    }
+}
+
 async fn save_notifications_internal(notifications: &[Notification]) -> AppResult<()> {
     let config = get_storage_config()?;
     config.ensure_dirs()?;
 
-    let content = serde_json::to_string(notifications)
+    let mut notifications = notifications.to_vec();
+    let policy = load_app_settings().await?.notification_retention;
+    apply_notification_retention(&mut notifications, &policy);
+
+    let content = serde_json::to_string(&notifications)
         .map_err(|e| crate::error::AppError::from(format!("序列化通知失败: {}", e)))?;
 
     fs::write(config.notifications_file(), content)
@@ -498,23 +702,101 @@ async fn save_notifications_internal(notifications: &[Notification]) -> AppResul
 #[tauri::command]
 #[specta::specta]
 pub async fn add_notification(input: NotificationInput) -> AppResult<Vec<Notification>> {
+    if let Some(action) = input
+        .actions
+        .iter()
+        .find(|a| !NOTIFICATION_ACTION_KINDS.contains(&a.kind.as_str()))
+    {
+        return Err(crate::error::AppError::invalid(format!(
+            "无效的通知操作: {}",
+            action.kind
+        )));
+    }
+    if !input.category.is_empty()
+        && !crate::notification_routing::CATEGORIES.contains(&input.category.as_str())
+    {
+        return Err(crate::error::AppError::invalid(format!(
+            "无效的通知类别: {}",
+            input.category
+        )));
+    }
     let mut notifications = get_notifications().await?;
 
+    let notification_type = if NOTIFICATION_SEVERITIES.contains(&input.notification_type.as_str()) {
+        input.notification_type
+    } else {
+        "info".to_string()
+    };
     let notification = Notification {
         id: generate_id(),
-        notification_type: input.notification_type,
+        notification_type,
         title: input.title,
         message: input.message,
         created_at: current_iso_time(),
+        category: input.category,
+        read: false,
+        actions: input.actions,
     };
 
     notifications.insert(0, notification);
-    if notifications.len() > 100 {
-        notifications.truncate(100);
+    save_notifications_internal(&notifications).await?;
+    get_notifications().await
+}
+
+/// 后台任务发起的通知：按类别的路由规则与免打扰决定是否写入通知中心（广播 `notification-added`）、
+/// 弹出系统通知、计入托盘角标
+pub async fn push_notification(
+    app: &tauri::AppHandle,
+    category: &str,
+    notification_type: &str,
+    title: &str,
+    message: &str,
+) {
+    push_notification_with_actions(app, category, notification_type, title, message, Vec::new())
+        .await;
+}
+
+/// 同 push_notification，附带操作按钮（如重试下载、打开文件夹）
+pub async fn push_notification_with_actions(
+    app: &tauri::AppHandle,
+    category: &str,
+    notification_type: &str,
+    title: &str,
+    message: &str,
+    actions: Vec<NotificationAction>,
+) {
+    use tauri::Emitter;
+
+    let routing = load_app_settings()
+        .await
+        .map(|s| s.notification_routing)
+        .unwrap_or_default();
+    let delivery = crate::notification_routing::resolve(&routing, category, notification_type);
+    if delivery.os_toast {
+        crate::notification_routing::show_os_toast(app, title, message);
+    }
+    if delivery.tray_badge {
+        crate::tray_badge::note_notification();
+    }
+    if !delivery.in_app {
+        return;
     }
 
-    save_notifications_internal(&notifications).await?;
-    Ok(notifications)
+    let input = NotificationInput {
+        notification_type: notification_type.to_string(),
+        title: title.to_string(),
+        message: message.to_string(),
+        category: category.to_string(),
+        actions,
+    };
+    match add_notification(input).await {
+        Ok(list) => {
+            if let Some(first) = list.first() {
+                let _ = app.emit("notification-added", first);
+            }
+        }
+        Err(e) => log::warn!("写入通知失败: {}", e),
+    }
 }
 
 #[tauri::command]
@@ -538,6 +820,77 @@ pub async fn save_notifications(notifications: Vec<Notification>) -> AppResult<(
     save_notifications_internal(&notifications).await
 }
 
+/// 标记单条通知已读；read 为 false 时标记为未读
+#[tauri::command]
+#[specta::specta]
+pub async fn mark_notification_read(
+    id: String,
+    read: Option<bool>,
+) -> AppResult<Vec<Notification>> {
+    let mut notifications = get_notifications().await?;
+    let notification = notifications
+        .iter_mut()
+        .find(|n| n.id == id)
+        .ok_or_else(|| crate::error::AppError::from("通知不存在".to_string()))?;
+    notification.read = read.unwrap_or(true);
+    save_notifications_internal(&notifications).await?;
+    get_notifications().await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn mark_all_notifications_read() -> AppResult<Vec<Notification>> {
+    let mut notifications = get_notifications().await?;
+    for notification in notifications.iter_mut() {
+        notification.read = true;
+    }
+    save_notifications_internal(&notifications).await?;
+    crate::tray_badge::mark_seen();
+    get_notifications().await
+}
+
+/// 临时开启免打扰 minutes 分钟（如演示前）；None 或 0 时立即结束手动免打扰，时段规则不受影响
+#[tauri::command]
+#[specta::specta]
+pub async fn set_do_not_disturb(minutes: Option<u32>) -> AppResult<NotificationRouting> {
+    let mut settings = load_app_settings().await?;
+    settings.notification_routing.dnd_until = minutes.filter(|m| *m > 0).map(|m| {
+        (chrono::Utc::now() + chrono::Duration::minutes(m as i64))
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    });
+    documents::save(&documents::APP_SETTINGS, &settings)?;
+    Ok(settings.notification_routing)
+}
+
+/// 执行通知上的第 index 个操作并标记已读。
+/// navigate 由前端跳转，这里只返回操作本身
+#[tauri::command]
+#[specta::specta]
+pub async fn run_notification_action(id: String, index: u32) -> AppResult<NotificationAction> {
+    let notifications = get_notifications().await?;
+    let action = notifications
+        .iter()
+        .find(|n| n.id == id)
+        .ok_or_else(|| crate::error::AppError::from("通知不存在".to_string()))?
+        .actions
+        .get(index as usize)
+        .cloned()
+        .ok_or_else(|| crate::error::AppError::invalid("通知操作不存在"))?;
+
+    match action.kind.as_str() {
+        "open_folder" => {
+            super::system::open_in_explorer(action.target.clone()).await?;
+        }
+        "open_url" => super::system::open_url(action.target.clone()).await?,
+        "retry_download" => {
+            super::toolbox::downloader::resume_download(action.target.clone()).await?
+        }
+        _ => {}
+    }
+    mark_notification_read(id, Some(true)).await?;
+    Ok(action)
+}
+
 // ============== 应用快捷键管理 ==============
 
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
@@ -767,7 +1120,7 @@ const BUILTIN_CLAUDE_CONFIG_TEMPLATES: &str = r#"{
 
 /// 拉取远程模板目录。任何网络/HTTP 错误都返回 Err，由调用方静默回退。
 async fn fetch_remote_claude_config_templates() -> Result<String, reqwest::Error> {
-    let client = reqwest::Client::builder()
+    let client = crate::http_client::builder()
         .timeout(std::time::Duration::from_secs(10))
         .user_agent("codeshelf")
         .build()?;
@@ -13,13 +13,19 @@
 // 写路径：
 //   - refresh_xxx_stats 跑 git → 写 3 张明细表 → 重新聚合 dashboard → 写 stats_meta
 //
+// 统计的分支按项目的 stats_branch：为空时统计当前检出分支，"*" 统计全部本地分支，
+// 其它值统计该本地分支（set_project_stats_branch 设置）。
+//
+// 清理：启动和完整刷新时自动删除已移除项目的统计、早于 stats_retention_days 的按日记录；
+// compact_stats_cache 额外 VACUUM 并删除残留的 stats_cache.json
+//
 // 这些 struct 仍然保留，因为：
 //   1. command 签名要兼容
 //   2. v1_from_json 反序列化老 JSON 需要 PersistedStatsCache
 
 use crate::error::AppResult;
+use crate::path_compat::{decode_text, git_in};
 use std::collections::{HashMap, HashSet};
-use std::process::Command;
 
 use serde::{Deserialize, Serialize};
 use sqlx::Acquire;
@@ -27,12 +33,6 @@ use tokio::task;
 
 use crate::storage::db::pool;
 
-#[cfg(target_os = "windows")]
-use std::os::windows::process::CommandExt;
-
-#[cfg(target_os = "windows")]
-const CREATE_NO_WINDOW: u32 = 0x08000000;
-
 // ============== 公开数据结构 ==============
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, specta::Type)]
@@ -88,6 +88,60 @@ pub struct ProjectStatsCache {
     pub last_updated: i64,
 }
 
+/// 对比窗口内某个贡献者的提交数
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ContributorActivity {
+    pub name: String,
+    pub email: String,
+    pub commits: u32,
+}
+
+/// 单个项目在对比窗口内的活跃度
+#[derive(Debug, Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectActivity {
+    pub name: String,
+    pub path: String,
+    pub commits: u32,
+    pub contributor_count: u32,
+    /// 按提交数降序
+    pub contributors: Vec<ContributorActivity>,
+    /// 新增行数（不含二进制文件）
+    pub insertions: u32,
+    pub deletions: u32,
+    /// 改动过的文件数（去重）
+    pub files_changed: u32,
+    pub commits_by_date: Vec<DailyActivity>,
+    /// 读取失败（非 git 仓库等）时的错误信息
+    pub error: Option<String>,
+}
+
+/// 跨项目活跃度对比结果
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityComparison {
+    /// 窗口起止日期（含），YYYY-MM-DD
+    pub since: String,
+    pub until: String,
+    pub projects: Vec<ProjectActivity>,
+}
+
+/// 热力图导出结果
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HeatmapExport {
+    /// "csv" | "json" | "svg" | "png"
+    pub format: String,
+    /// 文本格式为文件内容，png 为 base64
+    pub content: String,
+    /// 指定了输出路径时写入的文件
+    pub path: Option<String>,
+    pub since: String,
+    pub until: String,
+    pub total_commits: u32,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
 pub struct ProjectInfo {
     pub id: Option<String>,
@@ -95,29 +149,22 @@ pub struct ProjectInfo {
     pub path: String,
 }
 
+/// stats_branch 取此值时统计全部本地分支
+pub(crate) const ALL_BRANCHES: &str = "*";
+
 // ============== 工具函数 ==============
 
 fn run_git_command(path: &str, args: &[&str]) -> AppResult<String> {
-    #[cfg(target_os = "windows")]
-    let output = Command::new("git")
-        .args(["-C", path])
-        .args(args)
-        .creation_flags(CREATE_NO_WINDOW)
-        .output()
-        .map_err(|e| crate::error::AppError::from(e.to_string()))?;
-
-    #[cfg(not(target_os = "windows"))]
-    let output = Command::new("git")
-        .args(["-C", path])
+    let output = git_in(path)
         .args(args)
         .output()
         .map_err(|e| crate::error::AppError::from(e.to_string()))?;
 
     if output.status.success() {
-        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        Ok(decode_text(&output.stdout).trim().to_string())
     } else {
         Err(crate::error::AppError::from(
-            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            decode_text(&output.stderr).trim().to_string(),
         ))
     }
 }
@@ -148,19 +195,31 @@ fn get_dates_in_last_week() -> Vec<String> {
     dates
 }
 
+/// git log 的起点：当前分支为 HEAD，全部本地分支为 --branches
+fn branch_rev(branch: Option<&str>) -> String {
+    match branch {
+        None => "HEAD".to_string(),
+        Some(ALL_BRANCHES) => "--branches".to_string(),
+        Some(name) => format!("refs/heads/{}", name),
+    }
+}
+
 fn get_project_commits(
     path: &str,
     limit: u32,
+    branch: Option<&str>,
 ) -> Vec<(String, String, String, String, String, String)> {
     let format = "%H|%h|%s|%an|%ae|%ai";
-    let output = run_git_command(
-        path,
-        &[
-            "log",
-            &format!("-{}", limit),
-            &format!("--format={}", format),
-        ],
-    );
+    let rev = branch_rev(branch);
+    let count = format!("-{}", limit);
+    let format = format!("--format={}", format);
+    let mut args = vec!["log", rev.as_str(), count.as_str(), format.as_str()];
+    // 项目是仓库子目录时只统计该目录下的提交：monorepo 子项目作为独立项目时统计才有意义。
+    // 仓库根目录不加 pathspec，否则会按路径简化历史，丢掉没有改动文件的合并提交
+    if is_repo_subdir(path) {
+        args.extend(["--", "."]);
+    }
+    let output = run_git_command(path, &args);
 
     match output {
         Ok(result) => result
@@ -185,11 +244,29 @@ fn get_project_commits(
     }
 }
 
-fn get_unpushed_count(path: &str) -> u32 {
-    let output = run_git_command(
-        path,
-        &["rev-list", "--left-right", "--count", "HEAD...@{upstream}"],
-    );
+/// 项目目录是否为仓库的子目录（git rev-parse --show-prefix 非空）
+fn is_repo_subdir(path: &str) -> bool {
+    run_git_command(path, &["rev-parse", "--show-prefix"])
+        .map(|prefix| !prefix.is_empty())
+        .unwrap_or(false)
+}
+
+fn get_unpushed_count(path: &str, branch: Option<&str>) -> u32 {
+    let range = match branch {
+        // 任何远程分支上都没有的本地提交
+        Some(ALL_BRANCHES) => {
+            return run_git_command(
+                path,
+                &["rev-list", "--count", "--branches", "--not", "--remotes"],
+            )
+            .ok()
+            .and_then(|n| n.parse().ok())
+            .unwrap_or(0);
+        }
+        Some(name) => format!("refs/heads/{0}...{0}@{{upstream}}", name),
+        None => "HEAD...@{upstream}".to_string(),
+    };
+    let output = run_git_command(path, &["rev-list", "--left-right", "--count", &range]);
 
     match output {
         Ok(result) => {
@@ -205,9 +282,10 @@ fn get_unpushed_count(path: &str) -> u32 {
 }
 
 /// 跑 git 收集一个项目的统计（spawn_blocking 调用）
-fn analyze_project(name: String, path: String) -> ProjectStatsCache {
-    let unpushed = get_unpushed_count(&path);
-    let commits = get_project_commits(&path, 365);
+fn analyze_project(name: String, path: String, branch: Option<String>) -> ProjectStatsCache {
+    let branch = branch.as_deref();
+    let unpushed = get_unpushed_count(&path, branch);
+    let commits = get_project_commits(&path, 365, branch);
 
     let mut commits_by_date: HashMap<String, u32> = HashMap::new();
     let mut recent_commits: Vec<RecentCommit> = Vec::new();
@@ -516,6 +594,42 @@ fn aggregate_dashboard(
     }
 }
 
+/// 各项目设置的统计分支（项目路径 → stats_branch），未设置的不在其中
+async fn stats_branches() -> HashMap<String, String> {
+    sqlx::query_as::<_, (String, String)>(
+        "SELECT path, stats_branch FROM projects WHERE stats_branch IS NOT NULL",
+    )
+    .fetch_all(pool())
+    .await
+    .unwrap_or_else(|e| {
+        log::warn!("读取项目统计分支失败: {}", e);
+        Vec::new()
+    })
+    .into_iter()
+    .collect()
+}
+
+/// 刷新单个项目的统计（批量操作使用），完成后清除脏标记
+pub(crate) async fn refresh_project_stats(name: String, path: String) -> AppResult<()> {
+    let analyze_path = path.clone();
+    let branch = stats_branches().await.remove(&path);
+    let stats = task::spawn_blocking(move || analyze_project(name, analyze_path, branch))
+        .await
+        .map_err(|e| crate::error::AppError::internal(e.to_string()))?;
+    write_project_stats(&path, &stats).await?;
+    clear_dirty(std::slice::from_ref(&path)).await
+}
+
+/// 按 sqlite 中现有的项目统计重新聚合 dashboard
+pub(crate) async fn rebuild_dashboard() -> AppResult<()> {
+    let (total,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM projects")
+        .fetch_one(pool())
+        .await
+        .map_err(|e| crate::error::AppError::from(format!("查询项目数量失败: {}", e)))?;
+    let all = read_all_project_stats().await?;
+    write_dashboard(&aggregate_dashboard(&all, total as u32)).await
+}
+
 // ============== Tauri 命令 ==============
 
 #[tauri::command]
@@ -569,6 +683,10 @@ pub async fn get_dashboard_stats() -> AppResult<CachedDashboardData> {
 #[tauri::command]
 #[specta::specta]
 pub async fn refresh_dirty_stats(projects: Vec<ProjectInfo>) -> AppResult<CachedDashboardData> {
+    // 后台增量刷新：低电量时先返回缓存，脏标记保留到下次
+    if super::power::should_defer("统计增量刷新", false).await {
+        return read_dashboard().await;
+    }
     let dirty_paths = read_dirty().await?;
 
     if dirty_paths.is_empty() {
@@ -586,11 +704,14 @@ pub async fn refresh_dirty_stats(projects: Vec<ProjectInfo>) -> AppResult<Cached
     }
 
     // 并行跑 git
+    let branches = stats_branches().await;
     let mut handles = Vec::new();
     for project in projects_to_update {
         let name = project.name.clone();
         let path = project.path.clone();
-        let handle = task::spawn_blocking(move || (path.clone(), analyze_project(name, path)));
+        let branch = branches.get(&path).cloned();
+        let handle =
+            task::spawn_blocking(move || (path.clone(), analyze_project(name, path, branch)));
         handles.push(handle);
     }
 
@@ -603,6 +724,8 @@ pub async fn refresh_dirty_stats(projects: Vec<ProjectInfo>) -> AppResult<Cached
         }
     }
     clear_dirty(&cleared_paths).await?;
+    // 同步增量更新跨项目提交索引（后台进行）
+    super::commit_index::schedule_index(cleared_paths);
 
     // 重新聚合 dashboard
     let all = read_all_project_stats().await?;
@@ -632,11 +755,14 @@ pub async fn refresh_dashboard_stats(projects: Vec<ProjectInfo>) -> AppResult<Ca
         return Ok(empty);
     }
 
+    let branches = stats_branches().await;
     let mut handles = Vec::new();
     for project in &projects {
         let name = project.name.clone();
         let path = project.path.clone();
-        let handle = task::spawn_blocking(move || (path.clone(), analyze_project(name, path)));
+        let branch = branches.get(&path).cloned();
+        let handle =
+            task::spawn_blocking(move || (path.clone(), analyze_project(name, path, branch)));
         handles.push(handle);
     }
 
@@ -648,6 +774,11 @@ pub async fn refresh_dashboard_stats(projects: Vec<ProjectInfo>) -> AppResult<Ca
         }
     }
     clear_dirty(&cleared_paths).await?;
+    // 同步增量更新跨项目提交索引（后台进行）
+    super::commit_index::schedule_index(cleared_paths);
+    if let Err(e) = prune_stats_cache().await {
+        log::warn!("{}", e);
+    }
 
     let all = read_all_project_stats().await?;
     let dashboard = aggregate_dashboard(&all, total_projects);
@@ -659,6 +790,9 @@ pub async fn refresh_dashboard_stats(projects: Vec<ProjectInfo>) -> AppResult<Ca
 #[tauri::command]
 #[specta::specta]
 pub async fn init_stats_cache(projects: Vec<ProjectInfo>) -> AppResult<CachedDashboardData> {
+    if let Err(e) = prune_stats_cache().await {
+        log::warn!("{}", e);
+    }
     let now = get_current_timestamp();
     let all = read_all_project_stats().await?;
 
@@ -745,3 +879,594 @@ pub async fn cleanup_stats_cache(current_project_paths: Vec<String>) -> AppResul
         .map_err(|e| crate::error::AppError::from(format!("提交事务失败: {}", e)))?;
     Ok(())
 }
+
+// ============== 缓存清理与占用 ==============
+
+/// 统计缓存占用
+#[derive(Debug, Serialize, Deserialize, Clone, Default, specta::Type)]
+pub struct StatsCacheSize {
+    pub project_count: u32,
+    /// 已不在项目列表中的项目
+    pub orphaned_projects: u32,
+    pub daily_rows: u32,
+    /// 早于保留天数的按日记录
+    pub expired_daily_rows: u32,
+    pub recent_commit_rows: u32,
+    /// 数据库文件（含 -wal / -shm）字节数，统计缓存与其它数据共用同一个库
+    pub database_bytes: u64,
+    /// 迁移到 sqlite 后残留的 stats_cache.json 字节数
+    pub legacy_file_bytes: u64,
+    pub retention_days: u32,
+}
+
+/// 压缩结果
+#[derive(Debug, Serialize, Deserialize, Clone, Default, specta::Type)]
+pub struct StatsCompactReport {
+    pub removed_projects: u32,
+    pub removed_daily_rows: u32,
+    pub removed_legacy_files: u32,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+}
+
+/// 保留窗口的起始日期；0 表示不清理
+fn retention_cutoff(days: u32) -> Option<String> {
+    if days == 0 {
+        return None;
+    }
+    let date = chrono::Local::now().date_naive() - chrono::Duration::days(days as i64);
+    Some(date.format("%Y-%m-%d").to_string())
+}
+
+fn stats_db_files() -> AppResult<Vec<std::path::PathBuf>> {
+    let db = crate::storage::get_storage_config()?.db_file();
+    let wal = db.with_extension("db-wal");
+    let shm = db.with_extension("db-shm");
+    Ok(vec![db, wal, shm])
+}
+
+fn legacy_stats_files() -> AppResult<Vec<std::path::PathBuf>> {
+    let data_dir = &crate::storage::get_storage_config()?.data_dir;
+    Ok(vec![
+        data_dir.join("stats_cache.json"),
+        data_dir.join("stats_cache.json.migrated"),
+    ])
+}
+
+fn total_size(paths: &[std::path::PathBuf]) -> u64 {
+    paths
+        .iter()
+        .filter_map(|p| std::fs::metadata(p).ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+async fn count(sql: &str, cutoff: Option<&str>) -> AppResult<u32> {
+    let mut query = sqlx::query_scalar::<_, i64>(sql);
+    if let Some(cutoff) = cutoff {
+        query = query.bind(cutoff);
+    }
+    let n = query
+        .fetch_one(pool())
+        .await
+        .map_err(|e| crate::error::AppError::from(format!("统计缓存占用失败: {}", e)))?;
+    Ok(n.max(0) as u32)
+}
+
+/// 删除已移除项目的统计与早于保留窗口的按日记录，返回 (删除的项目数, 删除的按日记录数)
+async fn prune_stats_cache() -> AppResult<(u32, u32)> {
+    let days = super::settings::load_app_settings()
+        .await?
+        .stats_retention_days;
+
+    let removed_projects = sqlx::query(
+        "DELETE FROM project_stats WHERE project_path NOT IN (SELECT path FROM projects)",
+    )
+    .execute(pool())
+    .await
+    .map_err(|e| crate::error::AppError::from(format!("清理 project_stats 失败: {}", e)))?
+    .rows_affected() as u32;
+    sqlx::query("DELETE FROM stats_dirty WHERE project_path NOT IN (SELECT path FROM projects)")
+        .execute(pool())
+        .await
+        .map_err(|e| crate::error::AppError::from(format!("清理 stats_dirty 失败: {}", e)))?;
+
+    let mut removed_rows = 0;
+    if let Some(cutoff) = retention_cutoff(days) {
+        removed_rows = sqlx::query("DELETE FROM project_stats_commits_by_date WHERE date < ?")
+            .bind(&cutoff)
+            .execute(pool())
+            .await
+            .map_err(|e| crate::error::AppError::from(format!("清理 commits_by_date 失败: {}", e)))?
+            .rows_affected() as u32;
+    }
+
+    if removed_projects > 0 || removed_rows > 0 {
+        log::info!(
+            "清理统计缓存：{} 个已移除项目，{} 条过期按日记录",
+            removed_projects,
+            removed_rows
+        );
+    }
+    Ok((removed_projects, removed_rows))
+}
+
+/// 统计缓存占用，供存储设置页展示
+#[tauri::command]
+#[specta::specta]
+pub async fn get_stats_cache_size() -> AppResult<StatsCacheSize> {
+    let retention_days = super::settings::load_app_settings()
+        .await?
+        .stats_retention_days;
+    let cutoff = retention_cutoff(retention_days);
+
+    Ok(StatsCacheSize {
+        project_count: count("SELECT COUNT(*) FROM project_stats", None).await?,
+        orphaned_projects: count(
+            "SELECT COUNT(*) FROM project_stats \
+             WHERE project_path NOT IN (SELECT path FROM projects)",
+            None,
+        )
+        .await?,
+        daily_rows: count("SELECT COUNT(*) FROM project_stats_commits_by_date", None).await?,
+        expired_daily_rows: match &cutoff {
+            Some(cutoff) => {
+                count(
+                    "SELECT COUNT(*) FROM project_stats_commits_by_date WHERE date < ?",
+                    Some(cutoff),
+                )
+                .await?
+            }
+            None => 0,
+        },
+        recent_commit_rows: count("SELECT COUNT(*) FROM project_stats_recent_commits", None)
+            .await?,
+        database_bytes: total_size(&stats_db_files()?),
+        legacy_file_bytes: total_size(&legacy_stats_files()?),
+        retention_days,
+    })
+}
+
+/// 清理统计缓存并压缩数据库（VACUUM），同时删除残留的 stats_cache.json
+#[tauri::command]
+#[specta::specta]
+pub async fn compact_stats_cache() -> AppResult<StatsCompactReport> {
+    let db_files = stats_db_files()?;
+    let legacy_files = legacy_stats_files()?;
+    let bytes_before = total_size(&db_files) + total_size(&legacy_files);
+
+    let (removed_projects, removed_daily_rows) = prune_stats_cache().await?;
+
+    let mut removed_legacy_files = 0;
+    for path in legacy_files.iter().filter(|p| p.exists()) {
+        match std::fs::remove_file(path) {
+            Ok(_) => removed_legacy_files += 1,
+            Err(e) => log::warn!("删除 {:?} 失败: {}", path, e),
+        }
+    }
+
+    sqlx::query("VACUUM")
+        .execute(pool())
+        .await
+        .map_err(|e| crate::error::AppError::from(format!("压缩数据库失败: {}", e)))?;
+    sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+        .execute(pool())
+        .await
+        .map_err(|e| crate::error::AppError::from(format!("压缩数据库失败: {}", e)))?;
+
+    let all = read_all_project_stats().await?;
+    let total_projects = count("SELECT COUNT(*) FROM projects", None).await?;
+    write_dashboard(&aggregate_dashboard(&all, total_projects)).await?;
+
+    Ok(StatsCompactReport {
+        removed_projects,
+        removed_daily_rows,
+        removed_legacy_files,
+        bytes_before,
+        bytes_after: total_size(&db_files),
+    })
+}
+
+// ============== 跨项目活跃度对比 ==============
+
+/// 对比窗口最长天数（约 20 年）
+const MAX_ACTIVITY_DAYS: i64 = 7300;
+
+/// 解析对比窗口："7d" / "30d" / "12w" / "6m" / "1y"，或 "2024-01-01..2024-03-31"
+fn parse_activity_range(range: &str) -> AppResult<(chrono::NaiveDate, chrono::NaiveDate)> {
+    let range = range.trim();
+    let today = chrono::Local::now().date_naive();
+    if let Some((since, until)) = range.split_once("..") {
+        let parse = |s: &str| {
+            chrono::NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d")
+                .map_err(|_| crate::error::AppError::invalid(format!("日期格式错误: {}", s)))
+        };
+        let (since, until) = (parse(since)?, parse(until)?);
+        if since > until {
+            return Err(crate::error::AppError::invalid("开始日期不能晚于结束日期"));
+        }
+        if (until - since).num_days() >= MAX_ACTIVITY_DAYS {
+            return Err(crate::error::AppError::invalid(format!(
+                "时间范围过大（最多 {} 天）",
+                MAX_ACTIVITY_DAYS
+            )));
+        }
+        return Ok((since, until));
+    }
+    let (count, unit) = match range.char_indices().last() {
+        Some((index, _)) => range.split_at(index),
+        None => ("", ""),
+    };
+    let count: i64 = count
+        .parse()
+        .ok()
+        .filter(|n| *n > 0)
+        .ok_or_else(|| crate::error::AppError::invalid(format!("无效的时间范围: {}", range)))?;
+    let unit_days = match unit {
+        "d" => 1,
+        "w" => 7,
+        "m" => 30,
+        "y" => 365,
+        _ => {
+            return Err(crate::error::AppError::invalid(format!(
+                "无效的时间范围: {}",
+                range
+            )))
+        }
+    };
+    let days = count
+        .checked_mul(unit_days)
+        .filter(|d| *d <= MAX_ACTIVITY_DAYS)
+        .ok_or_else(|| {
+            crate::error::AppError::invalid(format!(
+                "时间范围过大（最多 {} 天）",
+                MAX_ACTIVITY_DAYS
+            ))
+        })?;
+    let since = today
+        .checked_sub_days(chrono::Days::new((days - 1) as u64))
+        .ok_or_else(|| crate::error::AppError::invalid(format!("无效的时间范围: {}", range)))?;
+    Ok((since, today))
+}
+
+/// 跑 git log --numstat 统计一个项目在窗口内的提交、贡献者与改动量（spawn_blocking 调用）
+fn analyze_activity(path: String, since: &str, until: &str) -> ProjectActivity {
+    let name = std::path::Path::new(&path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.clone());
+    let output = run_git_command(
+        &path,
+        &[
+            "log",
+            "--no-merges",
+            &format!("--since={} 00:00:00", since),
+            &format!("--until={} 23:59:59", until),
+            "--date=short",
+            "--format=@@%an|%ae|%ad",
+            "--numstat",
+            "--",
+            ".",
+        ],
+    );
+    let output = match output {
+        Ok(o) => o,
+        Err(e) => {
+            return ProjectActivity {
+                name,
+                path,
+                error: Some(e.to_string()),
+                ..Default::default()
+            }
+        }
+    };
+
+    let mut activity = ProjectActivity {
+        name,
+        path,
+        ..Default::default()
+    };
+    let mut authors: HashMap<(String, String), u32> = HashMap::new();
+    let mut by_date: HashMap<String, u32> = HashMap::new();
+    let mut files: HashSet<String> = HashSet::new();
+    for line in output.lines() {
+        if let Some(header) = line.strip_prefix("@@") {
+            let parts: Vec<&str> = header.splitn(3, '|').collect();
+            if parts.len() < 3 {
+                continue;
+            }
+            activity.commits += 1;
+            *authors
+                .entry((parts[0].to_string(), parts[1].to_string()))
+                .or_insert(0) += 1;
+            *by_date.entry(parts[2].to_string()).or_insert(0) += 1;
+            continue;
+        }
+        let mut cols = line.splitn(3, '\t');
+        if let (Some(ins), Some(del), Some(file)) = (cols.next(), cols.next(), cols.next()) {
+            // 二进制文件为 "-"
+            activity.insertions += ins.parse::<u32>().unwrap_or(0);
+            activity.deletions += del.parse::<u32>().unwrap_or(0);
+            files.insert(file.to_string());
+        }
+    }
+
+    activity.files_changed = files.len() as u32;
+    activity.contributor_count = authors.len() as u32;
+    activity.contributors = authors
+        .into_iter()
+        .map(|((name, email), commits)| ContributorActivity {
+            name,
+            email,
+            commits,
+        })
+        .collect();
+    activity
+        .contributors
+        .sort_by(|a, b| b.commits.cmp(&a.commits).then(a.name.cmp(&b.name)));
+    activity.commits_by_date = by_date
+        .into_iter()
+        .map(|(date, count)| DailyActivity { date, count })
+        .collect();
+    activity.commits_by_date.sort_by(|a, b| a.date.cmp(&b.date));
+    activity
+}
+
+/// 对比多个项目在同一时间窗口内的提交数、贡献者与改动量
+#[tauri::command]
+#[specta::specta]
+pub async fn compare_projects_activity(
+    paths: Vec<String>,
+    range: String,
+) -> AppResult<ActivityComparison> {
+    if paths.is_empty() {
+        return Err(crate::error::AppError::invalid("请至少选择一个项目"));
+    }
+    let (since, until) = parse_activity_range(&range)?;
+    let since = since.format("%Y-%m-%d").to_string();
+    let until = until.format("%Y-%m-%d").to_string();
+
+    let mut handles = Vec::new();
+    for path in paths {
+        let (since, until) = (since.clone(), until.clone());
+        handles.push(task::spawn_blocking(move || {
+            analyze_activity(path, &since, &until)
+        }));
+    }
+    let mut projects = Vec::new();
+    for handle in handles {
+        if let Ok(activity) = handle.await {
+            projects.push(activity);
+        }
+    }
+
+    Ok(ActivityComparison {
+        since,
+        until,
+        projects,
+    })
+}
+
+// ============== 热力图导出 ==============
+
+/// GitHub 配色，0 级为无提交
+const HEATMAP_COLORS: [(u8, u8, u8); 5] = [
+    (0xeb, 0xed, 0xf0),
+    (0x9b, 0xe9, 0xa8),
+    (0x40, 0xc4, 0x63),
+    (0x30, 0xa1, 0x4e),
+    (0x21, 0x6e, 0x39),
+];
+
+/// 方格边长与间距（SVG 像素，PNG 按 2 倍绘制）
+const HEATMAP_CELL: u32 = 11;
+const HEATMAP_GAP: u32 = 3;
+
+/// 窗口内每天的提交数（无提交的日期补 0）
+fn heatmap_days(
+    data: &[DailyActivity],
+    since: chrono::NaiveDate,
+    until: chrono::NaiveDate,
+) -> Vec<(chrono::NaiveDate, u32)> {
+    let counts: HashMap<&str, u32> = data.iter().map(|d| (d.date.as_str(), d.count)).collect();
+    since
+        .iter_days()
+        .take_while(|d| *d <= until)
+        .map(|d| {
+            let key = d.format("%Y-%m-%d").to_string();
+            (d, counts.get(key.as_str()).copied().unwrap_or(0))
+        })
+        .collect()
+}
+
+fn heatmap_level(count: u32, max: u32) -> usize {
+    if count == 0 || max == 0 {
+        0
+    } else {
+        (count * 4).div_ceil(max).clamp(1, 4) as usize
+    }
+}
+
+/// 方格位置：(列 = 第几周, 行 = 周几，周日为 0)
+fn heatmap_cells(days: &[(chrono::NaiveDate, u32)]) -> Vec<(u32, u32, chrono::NaiveDate, u32)> {
+    use chrono::Datelike;
+    let Some((first, _)) = days.first() else {
+        return Vec::new();
+    };
+    let offset = first.weekday().num_days_from_sunday();
+    days.iter()
+        .enumerate()
+        .map(|(i, (date, count))| {
+            let slot = i as u32 + offset;
+            (slot / 7, slot % 7, *date, *count)
+        })
+        .collect()
+}
+
+fn render_heatmap_svg(days: &[(chrono::NaiveDate, u32)], total: u32) -> String {
+    use chrono::Datelike;
+    let step = HEATMAP_CELL + HEATMAP_GAP;
+    let (left, top) = (30, 20);
+    let cells = heatmap_cells(days);
+    let weeks = cells.last().map(|c| c.0 + 1).unwrap_or(0);
+    let max = days.iter().map(|d| d.1).max().unwrap_or(0);
+    let width = left + weeks * step + 10;
+    let height = top + 7 * step + 30;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" font-family=\"-apple-system, Segoe UI, Helvetica, Arial, sans-serif\" font-size=\"10\">\n",
+        width, height, width, height
+    );
+    svg.push_str("<rect width=\"100%\" height=\"100%\" fill=\"#ffffff\"/>\n");
+    // 月份标签：每月第一天所在的列
+    let mut last_month = 0;
+    for (week, _, date, _) in &cells {
+        if date.month() != last_month && date.day() <= 7 {
+            svg.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" fill=\"#57606a\">{}月</text>\n",
+                left + week * step,
+                top - 6,
+                date.month()
+            ));
+            last_month = date.month();
+        }
+    }
+    for (row, label) in [(1, "一"), (3, "三"), (5, "五")] {
+        svg.push_str(&format!(
+            "<text x=\"0\" y=\"{}\" fill=\"#57606a\">周{}</text>\n",
+            top + row * step + HEATMAP_CELL - 1,
+            label
+        ));
+    }
+    for (week, weekday, date, count) in &cells {
+        let (r, g, b) = HEATMAP_COLORS[heatmap_level(*count, max)];
+        svg.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"2\" fill=\"#{:02x}{:02x}{:02x}\"><title>{} · {} 次提交</title></rect>\n",
+            left + week * step,
+            top + weekday * step,
+            HEATMAP_CELL,
+            HEATMAP_CELL,
+            r,
+            g,
+            b,
+            date.format("%Y-%m-%d"),
+            count
+        ));
+    }
+    svg.push_str(&format!(
+        "<text x=\"{}\" y=\"{}\" fill=\"#24292f\">共 {} 次提交</text>\n",
+        left,
+        top + 7 * step + 18,
+        total
+    ));
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// PNG 只绘制方格（没有字体渲染），按 2 倍尺寸输出
+fn render_heatmap_png(days: &[(chrono::NaiveDate, u32)]) -> AppResult<Vec<u8>> {
+    let scale = 2;
+    let (cell, step, margin) = (
+        HEATMAP_CELL * scale,
+        (HEATMAP_CELL + HEATMAP_GAP) * scale,
+        10 * scale,
+    );
+    let cells = heatmap_cells(days);
+    let weeks = cells.last().map(|c| c.0 + 1).unwrap_or(1);
+    let max = days.iter().map(|d| d.1).max().unwrap_or(0);
+    let width = margin * 2 + weeks * step - HEATMAP_GAP * scale;
+    let height = margin * 2 + 7 * step - HEATMAP_GAP * scale;
+
+    let mut pixels = vec![255u8; (width * height * 4) as usize];
+    for (week, weekday, _, count) in &cells {
+        let (r, g, b) = HEATMAP_COLORS[heatmap_level(*count, max)];
+        let (x0, y0) = (margin + week * step, margin + weekday * step);
+        for y in y0..y0 + cell {
+            for x in x0..x0 + cell {
+                let i = ((y * width + x) * 4) as usize;
+                pixels[i..i + 4].copy_from_slice(&[r, g, b, 255]);
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut out, width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder
+            .write_header()
+            .map_err(|e| crate::error::AppError::internal(format!("PNG 编码失败: {}", e)))?;
+        writer
+            .write_image_data(&pixels)
+            .map_err(|e| crate::error::AppError::internal(format!("PNG 编码失败: {}", e)))?;
+    }
+    Ok(out)
+}
+
+/// 导出提交热力图（所有项目聚合）；range 同 compare_projects_activity，默认最近一年。
+/// output_path 不为空时同时写入文件
+#[tauri::command]
+#[specta::specta]
+pub async fn export_heatmap(
+    range: Option<String>,
+    format: String,
+    output_path: Option<String>,
+) -> AppResult<HeatmapExport> {
+    use base64::Engine;
+
+    let (since, until) = parse_activity_range(range.as_deref().unwrap_or("1y"))?;
+    let dashboard = read_dashboard().await?;
+    let days = heatmap_days(&dashboard.heatmap_data, since, until);
+    let total_commits: u32 = days.iter().map(|d| d.1).sum();
+
+    let bytes = match format.as_str() {
+        "csv" => {
+            let mut csv = String::from("date,count\n");
+            for (date, count) in &days {
+                csv.push_str(&format!("{},{}\n", date.format("%Y-%m-%d"), count));
+            }
+            csv.into_bytes()
+        }
+        "json" => {
+            let list: Vec<DailyActivity> = days
+                .iter()
+                .map(|(date, count)| DailyActivity {
+                    date: date.format("%Y-%m-%d").to_string(),
+                    count: *count,
+                })
+                .collect();
+            serde_json::to_vec_pretty(&list)?
+        }
+        "svg" => render_heatmap_svg(&days, total_commits).into_bytes(),
+        "png" => render_heatmap_png(&days)?,
+        other => {
+            return Err(crate::error::AppError::invalid(format!(
+                "不支持的导出格式: {}",
+                other
+            )))
+        }
+    };
+
+    let path = match output_path.filter(|p| !p.trim().is_empty()) {
+        Some(path) => {
+            std::fs::write(&path, &bytes)?;
+            Some(path)
+        }
+        None => None,
+    };
+    let content = if format == "png" {
+        base64::engine::general_purpose::STANDARD.encode(&bytes)
+    } else {
+        String::from_utf8_lossy(&bytes).to_string()
+    };
+
+    Ok(HeatmapExport {
+        format,
+        content,
+        path,
+        since: since.format("%Y-%m-%d").to_string(),
+        until: until.format("%Y-%m-%d").to_string(),
+        total_commits,
+    })
+}
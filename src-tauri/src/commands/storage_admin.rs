@@ -1,11 +1,14 @@
 // 数据备份管理 Tauri 命令。
 //
 // SQLite 迁移每次启动会自动备份 data_dir 到 ../backup_<ISO8601>/。
-// 这里暴露两个命令让前端管理备份：
+// 这里暴露的命令让前端管理备份与设置文件版本：
 //   - list_data_backups: 列出所有可用备份的时间戳
 //   - restore_from_backup: 标记下次启动时从指定备份恢复（写 flag 文件 + 提示重启）
+//   - check_settings_documents: dry-run 检查设置文件的版本与待执行迁移
+//   - get_settings_migration_log: 设置文件的升级 / 解析失败记录
 
 use crate::error::AppResult;
+use crate::storage::documents::{self, DocumentCheck, MigrationLogEntry};
 use crate::storage::get_storage_config;
 use crate::storage::migrations::{list_backup_timestamps, schedule_restore};
 
@@ -26,3 +29,15 @@ pub async fn restore_from_backup(timestamp: String) -> AppResult<String> {
         timestamp
     ))
 }
+
+#[tauri::command]
+#[specta::specta]
+pub async fn check_settings_documents() -> AppResult<Vec<DocumentCheck>> {
+    documents::check_all()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_settings_migration_log() -> AppResult<Vec<MigrationLogEntry>> {
+    documents::migration_log()
+}
@@ -1,5 +1,8 @@
 use crate::error::AppResult;
-use std::process::Command;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use crate::storage;
 use serde::Serialize;
@@ -15,43 +18,125 @@ const CREATE_NEW_CONSOLE: u32 = 0x00000010;
 #[cfg(target_os = "windows")]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
+/// 启动外部程序后观察的时长：这段时间内以非 0 退出视为启动失败
+const EARLY_EXIT_WINDOW: Duration = Duration::from_millis(1500);
+
+/// 警告中保留的 stderr 长度（字节）
+const MAX_STDERR_BYTES: usize = 4096;
+
+/// 外部程序启动后立即异常退出（路径错误、参数不支持等）时返回的警告
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchWarning {
+    pub program: String,
+    pub exit_code: Option<i32>,
+    pub stderr: String,
+    pub message: String,
+}
+
+/// 启动并观察 EARLY_EXIT_WINDOW：窗口内以非 0 退出时返回退出码与 stderr。
+/// 仍在运行的进程交给后台线程回收；capture_stderr 为 false 时不接管 stderr
+/// （在新控制台窗口中运行的 shell 需要把错误显示在自己的窗口里）
+async fn spawn_watched(
+    cmd: &mut Command,
+    program: &str,
+    capture_stderr: bool,
+) -> std::io::Result<Option<LaunchWarning>> {
+    cmd.stdin(Stdio::null());
+    if capture_stderr {
+        cmd.stderr(Stdio::piped());
+    }
+    let mut child = cmd.spawn()?;
+
+    // 持续读取 stderr，避免管道写满阻塞子进程；只保留开头一段
+    let collected = Arc::new(Mutex::new(Vec::new()));
+    if let Some(mut stderr) = child.stderr.take() {
+        let collected = collected.clone();
+        std::thread::spawn(move || {
+            let mut buf = [0u8; 4096];
+            while let Ok(n) = stderr.read(&mut buf) {
+                if n == 0 {
+                    break;
+                }
+                let mut collected = collected.lock().unwrap_or_else(|e| e.into_inner());
+                let room = MAX_STDERR_BYTES.saturating_sub(collected.len());
+                collected.extend_from_slice(&buf[..n.min(room)]);
+            }
+        });
+    }
+
+    let started = Instant::now();
+    while started.elapsed() < EARLY_EXIT_WINDOW {
+        if let Some(status) = child.try_wait()? {
+            if status.success() {
+                return Ok(None);
+            }
+            // 给读线程一点时间读完剩余输出
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            let stderr = {
+                let collected = collected.lock().unwrap_or_else(|e| e.into_inner());
+                String::from_utf8_lossy(&collected).trim().to_string()
+            };
+            let code = status.code();
+            log::warn!("{} 启动后立即退出（{:?}）: {}", program, code, stderr);
+            return Ok(Some(LaunchWarning {
+                program: program.to_string(),
+                exit_code: code,
+                message: match code {
+                    Some(code) => format!("{} 启动后立即退出，退出码 {}", program, code),
+                    None => format!("{} 启动后被终止", program),
+                },
+                stderr,
+            }));
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(None)
+}
+
 #[tauri::command]
 #[specta::specta]
-pub async fn open_in_explorer(path: String) -> AppResult<()> {
+pub async fn open_in_explorer(path: String) -> AppResult<Option<LaunchWarning>> {
     #[cfg(target_os = "windows")]
     {
+        // explorer 成功打开时退出码也常为 1，不做观察；它不识别正斜杠与 `\\?\` 前缀
         Command::new("explorer")
-            .arg(&path)
+            .arg(crate::path_compat::display_path(&path))
             .spawn()
             .map_err(|e| crate::error::AppError::from(format!("Failed to open explorer: {}", e)))?;
+        return Ok(None);
     }
 
     #[cfg(target_os = "macos")]
     {
-        Command::new("open")
-            .arg(&path)
-            .spawn()
-            .map_err(|e| crate::error::AppError::from(format!("Failed to open Finder: {}", e)))?;
+        return spawn_watched(Command::new("open").arg(&path), "open", true)
+            .await
+            .map_err(|e| crate::error::AppError::from(format!("Failed to open Finder: {}", e)));
     }
 
     #[cfg(target_os = "linux")]
     {
         // Try common file managers
-        let result = Command::new("xdg-open").arg(&path).spawn();
-
-        if result.is_err() {
-            Command::new("nautilus").arg(&path).spawn().map_err(|e| {
-                crate::error::AppError::from(format!("Failed to open file manager: {}", e))
-            })?;
+        match spawn_watched(Command::new("xdg-open").arg(&path), "xdg-open", true).await {
+            Ok(warning) => Ok(warning),
+            Err(_) => spawn_watched(Command::new("nautilus").arg(&path), "nautilus", true)
+                .await
+                .map_err(|e| {
+                    crate::error::AppError::from(format!("Failed to open file manager: {}", e))
+                }),
         }
     }
-
-    Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn open_in_editor(path: String, editor_path: Option<String>) -> AppResult<()> {
+pub async fn open_in_editor(
+    path: String,
+    editor_path: Option<String>,
+) -> AppResult<Option<LaunchWarning>> {
     let editor = editor_path.unwrap_or_else(|| {
         // Default to VS Code if no editor specified
         #[cfg(target_os = "windows")]
@@ -64,43 +149,170 @@ pub async fn open_in_editor(path: String, editor_path: Option<String>) -> AppRes
         return "code".to_string();
     });
 
+    let mut cmd = Command::new(&editor);
+    cmd.arg(&path);
+
     #[cfg(target_os = "macos")]
-    {
-        if editor.ends_with(".app") {
-            // macOS .app 应用包：用 open -a 启动
-            Command::new("open")
-                .args(["-a", &editor, &path])
-                .spawn()
-                .map_err(|e| {
-                    crate::error::AppError::from(format!(
-                        "Failed to open editor '{}': {}",
-                        editor, e
-                    ))
-                })?;
-        } else {
-            Command::new(&editor).arg(&path).spawn().map_err(|e| {
-                crate::error::AppError::from(format!("Failed to open editor '{}': {}", editor, e))
-            })?;
-        }
+    if editor.ends_with(".app") {
+        // macOS .app 应用包：用 open -a 启动
+        cmd = Command::new("open");
+        cmd.args(["-a", &editor, &path]);
     }
 
-    #[cfg(target_os = "windows")]
-    {
-        Command::new(&editor).arg(&path).spawn().map_err(|e| {
-            crate::error::AppError::from(format!("Failed to open editor '{}': {}", editor, e))
-        })?;
+    spawn_watched(&mut cmd, &editor, true).await.map_err(|e| {
+        crate::error::AppError::from(format!("Failed to open editor '{}': {}", editor, e))
+    })
+}
+
+/// 工具路径检查结果（设置里配置编辑器 / 终端路径时使用）
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolPathCheck {
+    pub valid: bool,
+    /// 实际使用的路径：绝对路径原样返回，命令名按 PATH 查找
+    pub resolved_path: Option<String>,
+    /// 不可用的原因
+    pub message: Option<String>,
+}
+
+/// 在 PATH 中查找命令（Windows 同时尝试 PATHEXT 扩展名）
+fn find_in_path(name: &str) -> Option<std::path::PathBuf> {
+    let path_var = std::env::var_os("PATH")?;
+    let exts: Vec<String> = if cfg!(target_os = "windows") {
+        std::env::var("PATHEXT")
+            .unwrap_or_else(|_| ".EXE;.CMD;.BAT;.COM".to_string())
+            .split(';')
+            .map(|e| e.to_lowercase())
+            .chain(std::iter::once(String::new()))
+            .collect()
+    } else {
+        vec![String::new()]
+    };
+    std::env::split_paths(&path_var).find_map(|dir| {
+        exts.iter()
+            .map(|ext| dir.join(format!("{}{}", name, ext)))
+            .find(|p| p.is_file())
+    })
+}
+
+#[cfg(unix)]
+fn is_executable(path: &std::path::Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path)
+        .map(|m| m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &std::path::Path) -> bool {
+    true
+}
+
+/// 检查编辑器 / 终端路径是否可用：文件存在且可执行，或是 PATH 中的命令，或是 macOS .app 应用包
+#[tauri::command]
+#[specta::specta]
+pub async fn validate_tool_path(path: String) -> AppResult<ToolPathCheck> {
+    let trimmed = path.trim().trim_matches('"');
+    let invalid = |message: String| ToolPathCheck {
+        valid: false,
+        resolved_path: None,
+        message: Some(message),
+    };
+    if trimmed.is_empty() {
+        return Ok(invalid("路径不能为空".to_string()));
     }
+    let candidate = std::path::Path::new(trimmed);
+    let is_bare_name = candidate.components().count() == 1 && !candidate.is_absolute();
 
-    #[cfg(target_os = "linux")]
-    {
-        Command::new(&editor).arg(&path).spawn().map_err(|e| {
-            crate::error::AppError::from(format!("Failed to open editor '{}': {}", editor, e))
-        })?;
+    let resolved = if is_bare_name {
+        match find_in_path(trimmed) {
+            Some(p) => p,
+            None => return Ok(invalid(format!("在 PATH 中找不到命令: {}", trimmed))),
+        }
+    } else {
+        candidate.to_path_buf()
+    };
+
+    if cfg!(target_os = "macos") && trimmed.ends_with(".app") {
+        return Ok(if resolved.is_dir() {
+            ToolPathCheck {
+                valid: true,
+                resolved_path: Some(resolved.to_string_lossy().to_string()),
+                message: None,
+            }
+        } else {
+            invalid(format!("应用不存在: {}", trimmed))
+        });
+    }
+    if !resolved.exists() {
+        return Ok(invalid(format!("文件不存在: {}", trimmed)));
+    }
+    if resolved.is_dir() {
+        return Ok(invalid("路径是目录，请选择可执行文件".to_string()));
     }
+    if !is_executable(&resolved) {
+        return Ok(invalid("文件没有执行权限".to_string()));
+    }
+    Ok(ToolPathCheck {
+        valid: true,
+        resolved_path: Some(resolved.to_string_lossy().to_string()),
+        message: None,
+    })
+}
 
-    Ok(())
+/// PowerShell 单引号字符串
+#[cfg(not(target_os = "macos"))]
+fn ps_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+/// POSIX shell 单引号字符串
+#[cfg(target_os = "macos")]
+fn sh_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+/// 进入目录（并执行命令）的 PowerShell 脚本
+#[cfg(not(target_os = "macos"))]
+fn ps_script(path: &str, command: Option<&str>) -> String {
+    let cd = format!("Set-Location -LiteralPath {}", ps_quote(path));
+    match command {
+        Some(cmd) => format!("{}; {}", cd, cmd),
+        None => cd,
+    }
+}
+
+/// macOS：通过 AppleScript 在 Terminal / iTerm 新窗口中进入目录并执行命令
+#[cfg(target_os = "macos")]
+async fn run_in_macos_terminal(
+    app: &str,
+    path: &str,
+    command: &str,
+) -> AppResult<Option<LaunchWarning>> {
+    let script = format!("cd {} && {}", sh_quote(path), command);
+    let escaped = script.replace('\\', "\\\\").replace('"', "\\\"");
+    let apple_script = if app == "iTerm" {
+        format!(
+            "tell application \"iTerm\"\nactivate\nset w to (create window with default profile)\ntell current session of w to write text \"{}\"\nend tell",
+            escaped
+        )
+    } else {
+        format!(
+            "tell application \"Terminal\"\nactivate\ndo script \"{}\"\nend tell",
+            escaped
+        )
+    };
+    spawn_watched(
+        Command::new("osascript").args(["-e", &apple_script]),
+        "osascript",
+        true,
+    )
+    .await
+    .map_err(|e| crate::error::AppError::from(e.to_string()))
 }
 
+/// 打开终端并进入目录；传入 command 时进入目录后执行该命令（终端保持打开）。
+/// 终端启动后立即异常退出时返回警告
 #[tauri::command]
 #[specta::specta]
 #[allow(unused_variables)]
@@ -109,47 +321,64 @@ pub async fn open_in_terminal(
     terminal_type: Option<String>,
     custom_path: Option<String>,
     terminal_path: Option<String>,
-) -> AppResult<()> {
+    command: Option<String>,
+) -> AppResult<Option<LaunchWarning>> {
     let term_type = terminal_type.unwrap_or_else(|| "default".to_string());
+    let command = command.filter(|c| !c.trim().is_empty());
+    if command.is_some() && term_type == "custom" {
+        return Err(crate::error::AppError::invalid(
+            "Running a command is not supported for custom terminals",
+        ));
+    }
+    #[allow(unused_mut)]
+    let mut warning = None;
 
+    // Windows 的 shell 在新控制台窗口中运行，错误输出留在它自己的窗口，不接管 stderr
     #[cfg(target_os = "windows")]
     {
         match term_type.as_str() {
             "powershell" => {
                 let ps_path = terminal_path.as_deref().unwrap_or("powershell");
                 // Use Set-Location with -LiteralPath for paths with special characters
-                let escaped_path = path.replace("'", "''");
-                Command::new(ps_path)
-                    .args([
-                        "-NoExit",
-                        "-Command",
-                        &format!("Set-Location -LiteralPath '{}'", escaped_path),
-                    ])
-                    .creation_flags(CREATE_NEW_CONSOLE)
-                    .spawn()
-                    .map_err(|e| crate::error::AppError::from(e.to_string()))?;
+                warning = spawn_watched(
+                    Command::new(ps_path)
+                        .args(["-NoExit", "-Command", &ps_script(&path, command.as_deref())])
+                        .creation_flags(CREATE_NEW_CONSOLE),
+                    ps_path,
+                    false,
+                )
+                .await
+                .map_err(|e| crate::error::AppError::from(e.to_string()))?;
             }
             "cmd" => {
                 let cmd_path = terminal_path.as_deref().unwrap_or("cmd");
-                // Use quotes around path for paths with spaces or special characters
-                Command::new(cmd_path)
-                    .args(["/k", &format!("cd /d \"{}\"", path)])
-                    .creation_flags(CREATE_NEW_CONSOLE)
-                    .spawn()
+                let mut cmd = Command::new(cmd_path);
+                match command.as_deref() {
+                    // cmd 自己解析 /k 之后的整行，原样传入避免 Rust 再加一层引号
+                    Some(c) => cmd.raw_arg(format!("/k cd /d \"{}\" && {}", path, c)),
+                    // Use quotes around path for paths with spaces or special characters
+                    None => cmd.args(["/k", &format!("cd /d \"{}\"", path)]),
+                };
+                warning = spawn_watched(cmd.creation_flags(CREATE_NEW_CONSOLE), cmd_path, false)
+                    .await
                     .map_err(|e| crate::error::AppError::from(e.to_string()))?;
             }
             "custom" => {
                 if let Some(custom) = custom_path {
-                    Command::new(&custom)
-                        .arg(&path)
-                        .creation_flags(CREATE_NEW_CONSOLE)
-                        .spawn()
-                        .map_err(|e| {
-                            crate::error::AppError::from(format!(
-                                "Failed to open custom terminal '{}': {}",
-                                custom, e
-                            ))
-                        })?;
+                    warning = spawn_watched(
+                        Command::new(&custom)
+                            .arg(&path)
+                            .creation_flags(CREATE_NEW_CONSOLE),
+                        &custom,
+                        false,
+                    )
+                    .await
+                    .map_err(|e| {
+                        crate::error::AppError::from(format!(
+                            "Failed to open custom terminal '{}': {}",
+                            custom, e
+                        ))
+                    })?;
                 } else {
                     return Err(crate::error::AppError::from(
                         "Custom terminal path not provided".to_string(),
@@ -159,19 +388,29 @@ pub async fn open_in_terminal(
             _ => {
                 // Default: Windows Terminal if available, otherwise PowerShell
                 let wt_path = terminal_path.as_deref().unwrap_or("wt");
-                let wt_result = Command::new(wt_path).args(["-d", &path]).spawn();
-
-                if wt_result.is_err() {
-                    let escaped_path = path.replace("'", "''");
-                    Command::new("powershell")
-                        .args([
-                            "-NoExit",
-                            "-Command",
-                            &format!("Set-Location -LiteralPath '{}'", escaped_path),
-                        ])
-                        .creation_flags(CREATE_NEW_CONSOLE)
-                        .spawn()
+                let mut wt = Command::new(wt_path);
+                wt.args(["-d", &path]);
+                if let Some(c) = command.as_deref() {
+                    // wt 把 `;` 当作子命令分隔符，需转义
+                    wt.args(["powershell", "-NoExit", "-Command", &c.replace(';', "\\;")]);
+                }
+                match spawn_watched(&mut wt, wt_path, true).await {
+                    Ok(w) => warning = w,
+                    Err(_) => {
+                        warning = spawn_watched(
+                            Command::new("powershell")
+                                .args([
+                                    "-NoExit",
+                                    "-Command",
+                                    &ps_script(&path, command.as_deref()),
+                                ])
+                                .creation_flags(CREATE_NEW_CONSOLE),
+                            "powershell",
+                            false,
+                        )
+                        .await
                         .map_err(|e| crate::error::AppError::from(e.to_string()))?;
+                    }
                 }
             }
         }
@@ -181,32 +420,35 @@ pub async fn open_in_terminal(
     {
         match term_type.as_str() {
             "iterm" => {
-                Command::new("open")
-                    .args(["-a", "iTerm", &path])
-                    .spawn()
-                    .map_err(|e| crate::error::AppError::from(e.to_string()))?;
+                warning = match command.as_deref() {
+                    Some(c) => run_in_macos_terminal("iTerm", &path, c).await?,
+                    None => spawn_watched(
+                        Command::new("open").args(["-a", "iTerm", &path]),
+                        "iTerm",
+                        true,
+                    )
+                    .await
+                    .map_err(|e| crate::error::AppError::from(e.to_string()))?,
+                };
             }
             "custom" => {
                 if let Some(custom) = custom_path {
-                    if custom.ends_with(".app") {
+                    let mut cmd = if custom.ends_with(".app") {
                         // macOS .app 应用包：用 open -a 启动（同 iTerm/Terminal 模式）
-                        Command::new("open")
-                            .args(["-a", &custom, &path])
-                            .spawn()
-                            .map_err(|e| {
-                                crate::error::AppError::from(format!(
-                                    "Failed to open custom terminal '{}': {}",
-                                    custom, e
-                                ))
-                            })?;
+                        let mut cmd = Command::new("open");
+                        cmd.args(["-a", &custom, &path]);
+                        cmd
                     } else {
-                        Command::new(&custom).arg(&path).spawn().map_err(|e| {
-                            crate::error::AppError::from(format!(
-                                "Failed to open custom terminal '{}': {}",
-                                custom, e
-                            ))
-                        })?;
-                    }
+                        let mut cmd = Command::new(&custom);
+                        cmd.arg(&path);
+                        cmd
+                    };
+                    warning = spawn_watched(&mut cmd, &custom, true).await.map_err(|e| {
+                        crate::error::AppError::from(format!(
+                            "Failed to open custom terminal '{}': {}",
+                            custom, e
+                        ))
+                    })?;
                 } else {
                     return Err(crate::error::AppError::from(
                         "Custom terminal path not provided".to_string(),
@@ -215,10 +457,16 @@ pub async fn open_in_terminal(
             }
             _ => {
                 // Default: Terminal.app
-                Command::new("open")
-                    .args(["-a", "Terminal", &path])
-                    .spawn()
-                    .map_err(|e| crate::error::AppError::from(e.to_string()))?;
+                warning = match command.as_deref() {
+                    Some(c) => run_in_macos_terminal("Terminal", &path, c).await?,
+                    None => spawn_watched(
+                        Command::new("open").args(["-a", "Terminal", &path]),
+                        "Terminal",
+                        true,
+                    )
+                    .await
+                    .map_err(|e| crate::error::AppError::from(e.to_string()))?,
+                };
             }
         }
     }
@@ -228,15 +476,15 @@ pub async fn open_in_terminal(
         match term_type.as_str() {
             "custom" => {
                 if let Some(custom) = custom_path {
-                    Command::new(&custom)
-                        .current_dir(&path)
-                        .spawn()
-                        .map_err(|e| {
-                            crate::error::AppError::from(format!(
-                                "Failed to open custom terminal '{}': {}",
-                                custom, e
-                            ))
-                        })?;
+                    warning =
+                        spawn_watched(Command::new(&custom).current_dir(&path), &custom, true)
+                            .await
+                            .map_err(|e| {
+                                crate::error::AppError::from(format!(
+                                    "Failed to open custom terminal '{}': {}",
+                                    custom, e
+                                ))
+                            })?;
                 } else {
                     return Err(crate::error::AppError::from(
                         "Custom terminal path not provided".to_string(),
@@ -246,64 +494,99 @@ pub async fn open_in_terminal(
             "powershell" => {
                 // WSL: try powershell.exe or use custom path
                 let ps_path = terminal_path.as_deref().unwrap_or("powershell.exe");
-                let result = Command::new(ps_path)
-                    .args(["-NoExit", "-Command", &format!("cd '{}'", path)])
-                    .spawn();
-                if result.is_err() {
+                let script = ps_script(&path, command.as_deref());
+                let result = spawn_watched(
+                    Command::new(ps_path).args(["-NoExit", "-Command", &script]),
+                    ps_path,
+                    false,
+                )
+                .await;
+                warning = match result {
+                    Ok(w) => w,
                     // Fallback: native powershell with original path
-                    Command::new("powershell")
-                        .args(["-NoExit", "-Command", &format!("cd '{}'", path)])
-                        .spawn()
-                        .map_err(|e| crate::error::AppError::from(e.to_string()))?;
-                }
+                    Err(_) => spawn_watched(
+                        Command::new("powershell").args(["-NoExit", "-Command", &script]),
+                        "powershell",
+                        false,
+                    )
+                    .await
+                    .map_err(|e| crate::error::AppError::from(e.to_string()))?,
+                };
             }
             "cmd" => {
                 // WSL: try cmd.exe or use custom path
                 let cmd_path = terminal_path.as_deref().unwrap_or("cmd.exe");
-                let result = Command::new(cmd_path)
-                    .args(["/k", &format!("cd /d {}", path)])
-                    .spawn();
-                if result.is_err() {
-                    Command::new("cmd")
-                        .args(["/k", &format!("cd /d {}", path)])
-                        .spawn()
-                        .map_err(|e| crate::error::AppError::from(e.to_string()))?;
-                }
+                // 路径加引号，含空格或 & 等字符时不被 cmd 拆开
+                let line = match command.as_deref() {
+                    Some(c) => format!("cd /d \"{}\" && {}", path, c),
+                    None => format!("cd /d \"{}\"", path),
+                };
+                let result =
+                    spawn_watched(Command::new(cmd_path).args(["/k", &line]), cmd_path, false)
+                        .await;
+                warning = match result {
+                    Ok(w) => w,
+                    Err(_) => spawn_watched(Command::new("cmd").args(["/k", &line]), "cmd", false)
+                        .await
+                        .map_err(|e| crate::error::AppError::from(e.to_string()))?,
+                };
             }
             _ => {
                 // Default: try Windows Terminal (WSL) with custom path, then common Linux terminals
+                // 带命令时 wt.exe 打开的是 Windows shell，直接用 Linux 终端
                 let wt_path = terminal_path.as_deref().unwrap_or("wt.exe");
-                let wt_result = Command::new(wt_path).args(["-d", &path]).spawn();
-
-                if wt_result.is_err() {
-                    let terminals = ["gnome-terminal", "konsole", "xterm", "xfce4-terminal"];
-                    let mut opened = false;
-
-                    for term in terminals {
-                        let result = match term {
-                            "gnome-terminal" => Command::new(term)
-                                .args(["--working-directory", &path])
-                                .spawn(),
-                            _ => Command::new(term).current_dir(&path).spawn(),
-                        };
-
-                        if result.is_ok() {
-                            opened = true;
-                            break;
-                        }
+                let wt_result = match command {
+                    Some(_) => Err(std::io::Error::from(std::io::ErrorKind::Unsupported)),
+                    None => {
+                        spawn_watched(Command::new(wt_path).args(["-d", &path]), wt_path, true)
+                            .await
                     }
+                };
+
+                match wt_result {
+                    Ok(w) => warning = w,
+                    Err(_) => {
+                        let terminals = ["gnome-terminal", "konsole", "xterm", "xfce4-terminal"];
+                        let mut opened = false;
+                        // 命令结束后进入交互 shell，保持窗口打开
+                        let script = command
+                            .as_deref()
+                            .map(|c| format!("{}; exec \"${{SHELL:-sh}}\"", c));
+
+                        for term in terminals {
+                            let mut cmd = Command::new(term);
+                            cmd.current_dir(&path);
+                            if term == "gnome-terminal" {
+                                cmd.args(["--working-directory", &path]);
+                            }
+                            if let Some(script) = script.as_deref() {
+                                let exec_flag = match term {
+                                    "gnome-terminal" => "--",
+                                    "xfce4-terminal" => "-x",
+                                    _ => "-e",
+                                };
+                                cmd.args([exec_flag, "sh", "-c", script]);
+                            }
+
+                            if let Ok(w) = spawn_watched(&mut cmd, term, true).await {
+                                warning = w;
+                                opened = true;
+                                break;
+                            }
+                        }
 
-                    if !opened {
-                        return Err(crate::error::AppError::from(
-                            "No supported terminal emulator found".to_string(),
-                        ));
+                        if !opened {
+                            return Err(crate::error::AppError::from(
+                                "No supported terminal emulator found".to_string(),
+                            ));
+                        }
                     }
                 }
             }
         }
     }
 
-    Ok(())
+    Ok(warning)
 }
 
 #[tauri::command]
@@ -610,9 +893,8 @@ fn test_default_terminal() -> AppResult<TerminalTestResult> {
 #[specta::specta]
 pub async fn read_readme(path: String) -> AppResult<String> {
     use std::fs;
-    use std::path::PathBuf;
 
-    let project_path = PathBuf::from(&path);
+    let project_path = crate::path_compat::native_path(&path);
 
     // Try different README file names
     let readme_names = vec![
@@ -627,9 +909,12 @@ pub async fn read_readme(path: String) -> AppResult<String> {
     for name in readme_names {
         let readme_path = project_path.join(name);
         if readme_path.exists() {
-            return fs::read_to_string(readme_path).map_err(|e| {
-                crate::error::AppError::from(format!("Failed to read README: {}", e))
-            });
+            // 兼容 GBK、UTF-16 等非 UTF-8 编码的 README
+            return fs::read(readme_path)
+                .map(|bytes| crate::path_compat::decode_text(&bytes))
+                .map_err(|e| {
+                    crate::error::AppError::from(format!("Failed to read README: {}", e))
+                });
         }
     }
 
@@ -641,21 +926,15 @@ pub async fn read_readme(path: String) -> AppResult<String> {
 #[tauri::command]
 #[specta::specta]
 pub async fn check_git_version() -> AppResult<String> {
-    #[cfg(target_os = "windows")]
-    let output = Command::new("git")
-        .arg("--version")
-        .creation_flags(CREATE_NO_WINDOW)
-        .output()
-        .map_err(|e| crate::error::AppError::from(format!("Git not found: {}", e)))?;
-
-    #[cfg(not(target_os = "windows"))]
-    let output = Command::new("git")
+    let output = crate::path_compat::git_command()
         .arg("--version")
         .output()
         .map_err(|e| crate::error::AppError::from(format!("Git not found: {}", e)))?;
 
     if output.status.success() {
-        let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        let version = crate::path_compat::decode_text(&output.stdout)
+            .trim()
+            .to_string();
         // Extract version number from "git version 2.x.x"
         let version = version.replace("git version ", "");
         Ok(version)
@@ -666,6 +945,13 @@ pub async fn check_git_version() -> AppResult<String> {
     }
 }
 
+/// 校验设置中填写的 git 可执行文件，返回其版本号
+#[tauri::command]
+#[specta::specta]
+pub async fn check_git_executable(executable: String) -> AppResult<String> {
+    crate::git_executable::check(&executable)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn check_node_version() -> AppResult<String> {
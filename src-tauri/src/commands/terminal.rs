@@ -0,0 +1,330 @@
+// 内嵌终端：基于 portable-pty 的伪终端会话，前端用 xterm 渲染，不必每次都打开外部终端窗口。
+//
+// - 会话可绑定项目（工作目录为项目目录），也可以直接指定目录
+// - 输出由读线程推送 `terminal-output`，按 UTF-8 边界切分，避免多字节字符被截断
+// - shell 退出时推送 `terminal-exited` 并移除会话
+// - 每个会话保留一段回滚输出，前端切换页面后重新挂载时可以补回
+
+use crate::error::{AppError, AppResult};
+use once_cell::sync::Lazy;
+use portable_pty::{native_pty_system, ChildKiller, CommandBuilder, MasterPty, PtySize};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter};
+
+use crate::commands::project_tasks::project_path;
+use crate::storage::{current_iso_time, generate_id};
+
+/// 每个会话保留的回滚输出（字节）
+const SCROLLBACK_BYTES: usize = 256 * 1024;
+
+/// 同时打开的终端数上限
+const MAX_SESSIONS: usize = 32;
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalSession {
+    pub id: String,
+    pub project_id: Option<String>,
+    pub cwd: String,
+    pub shell: String,
+    pub cols: u16,
+    pub rows: u16,
+    pub pid: Option<u32>,
+    pub started_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalOutput {
+    pub session_id: String,
+    pub data: String,
+}
+
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalExit {
+    pub session_id: String,
+    pub exit_code: Option<u32>,
+}
+
+struct PtySession {
+    info: TerminalSession,
+    master: Box<dyn MasterPty + Send>,
+    /// 单独加锁：写入可能因 shell 未及时读取而阻塞，不能占着 SESSIONS
+    writer: Arc<Mutex<Box<dyn Write + Send>>>,
+    killer: Box<dyn ChildKiller + Send + Sync>,
+    scrollback: Arc<Mutex<String>>,
+}
+
+static SESSIONS: Lazy<Mutex<HashMap<String, PtySession>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn sessions() -> std::sync::MutexGuard<'static, HashMap<String, PtySession>> {
+    SESSIONS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn default_shell() -> String {
+    #[cfg(target_os = "windows")]
+    {
+        "powershell.exe".to_string()
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        std::env::var("SHELL")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or_else(|| "/bin/bash".to_string())
+    }
+}
+
+fn pty_size(cols: u16, rows: u16) -> PtySize {
+    PtySize {
+        rows: rows.max(2),
+        cols: cols.max(10),
+        pixel_width: 0,
+        pixel_height: 0,
+    }
+}
+
+/// 追加回滚输出，超出上限时从头部按字符边界裁掉
+fn push_scrollback(buffer: &Mutex<String>, text: &str) {
+    let mut buffer = buffer.lock().unwrap_or_else(|e| e.into_inner());
+    buffer.push_str(text);
+    if buffer.len() > SCROLLBACK_BYTES {
+        let mut cut = buffer.len() - SCROLLBACK_BYTES;
+        while !buffer.is_char_boundary(cut) {
+            cut += 1;
+        }
+        buffer.drain(..cut);
+    }
+}
+
+/// 读线程：按 UTF-8 边界切分输出并推送，未完整的多字节字符留到下一次
+fn spawn_reader(
+    app: AppHandle,
+    session_id: String,
+    mut reader: Box<dyn Read + Send>,
+    scrollback: Arc<Mutex<String>>,
+) {
+    std::thread::spawn(move || {
+        let mut buf = [0u8; 8192];
+        let mut pending: Vec<u8> = Vec::new();
+        loop {
+            let n = match reader.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => n,
+            };
+            pending.extend_from_slice(&buf[..n]);
+            let valid = match std::str::from_utf8(&pending) {
+                Ok(_) => pending.len(),
+                // 非法字节直接按 lossy 输出；只有结尾不完整时才等待
+                Err(e) if e.error_len().is_some() => pending.len(),
+                Err(e) => e.valid_up_to(),
+            };
+            if valid == 0 {
+                continue;
+            }
+            let data = String::from_utf8_lossy(&pending[..valid]).to_string();
+            pending.drain(..valid);
+            push_scrollback(&scrollback, &data);
+            let _ = app.emit(
+                "terminal-output",
+                TerminalOutput {
+                    session_id: session_id.clone(),
+                    data,
+                },
+            );
+        }
+    });
+}
+
+/// 打开终端会话；指定 project_id 时在项目目录下打开，否则使用 cwd（默认主目录）
+#[tauri::command]
+#[specta::specta]
+pub async fn create_terminal(
+    app: AppHandle,
+    project_id: Option<String>,
+    cwd: Option<String>,
+    shell: Option<String>,
+    cols: u16,
+    rows: u16,
+) -> AppResult<TerminalSession> {
+    if sessions().len() >= MAX_SESSIONS {
+        return Err(AppError::invalid(format!(
+            "最多同时打开 {} 个终端",
+            MAX_SESSIONS
+        )));
+    }
+    let base = match project_id.as_deref() {
+        Some(id) => Some(PathBuf::from(project_path(id).await?)),
+        None => None,
+    };
+    let cwd = match (base, cwd.filter(|c| !c.trim().is_empty())) {
+        (Some(base), Some(sub)) => base.join(sub),
+        (Some(base), None) => base,
+        (None, Some(dir)) => PathBuf::from(dir),
+        (None, None) => dirs::home_dir().unwrap_or_else(|| PathBuf::from(".")),
+    };
+    if !cwd.is_dir() {
+        return Err(AppError::invalid(format!(
+            "目录不存在: {}",
+            cwd.to_string_lossy()
+        )));
+    }
+    let shell = shell
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or_else(default_shell);
+
+    let pair = native_pty_system()
+        .openpty(pty_size(cols, rows))
+        .map_err(|e| AppError::other(format!("创建伪终端失败: {}", e)))?;
+    let mut cmd = CommandBuilder::new(&shell);
+    cmd.cwd(&cwd);
+    cmd.env("TERM", "xterm-256color");
+    cmd.env("COLORTERM", "truecolor");
+    let mut child = pair
+        .slave
+        .spawn_command(cmd)
+        .map_err(|e| AppError::other(format!("启动 {} 失败: {}", shell, e)))?;
+    // 子进程已持有 slave，父进程这端关闭，shell 退出后读端才能收到 EOF
+    drop(pair.slave);
+
+    let reader = pair
+        .master
+        .try_clone_reader()
+        .map_err(|e| AppError::other(format!("读取终端输出失败: {}", e)))?;
+    let writer = pair
+        .master
+        .take_writer()
+        .map_err(|e| AppError::other(format!("打开终端输入失败: {}", e)))?;
+
+    let info = TerminalSession {
+        id: generate_id(),
+        project_id,
+        cwd: cwd.to_string_lossy().to_string(),
+        shell,
+        cols,
+        rows,
+        pid: child.process_id(),
+        started_at: current_iso_time(),
+    };
+    let scrollback = Arc::new(Mutex::new(String::new()));
+    spawn_reader(app.clone(), info.id.clone(), reader, scrollback.clone());
+
+    sessions().insert(
+        info.id.clone(),
+        PtySession {
+            info: info.clone(),
+            master: pair.master,
+            writer: Arc::new(Mutex::new(writer)),
+            killer: child.clone_killer(),
+            scrollback,
+        },
+    );
+
+    // 等待 shell 退出：推送退出事件并移除会话（释放 master，读线程随之结束）
+    let session_id = info.id.clone();
+    std::thread::spawn(move || {
+        let exit_code = child.wait().ok().map(|status| status.exit_code());
+        sessions().remove(&session_id);
+        let _ = app.emit(
+            "terminal-exited",
+            TerminalExit {
+                session_id,
+                exit_code,
+            },
+        );
+    });
+
+    Ok(info)
+}
+
+/// 向终端写入输入（按键、粘贴内容）
+#[tauri::command]
+#[specta::specta]
+pub async fn write_terminal(session_id: String, data: String) -> AppResult<()> {
+    let writer = sessions()
+        .get(&session_id)
+        .map(|session| session.writer.clone())
+        .ok_or_else(|| AppError::invalid("终端会话不存在"))?;
+    tokio::task::spawn_blocking(move || {
+        let mut writer = writer.lock().unwrap_or_else(|e| e.into_inner());
+        writer
+            .write_all(data.as_bytes())
+            .and_then(|_| writer.flush())
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("写入终端失败: {}", e)))?
+    .map_err(|e| AppError::other(format!("写入终端失败: {}", e)))
+}
+
+/// 调整终端尺寸（前端容器大小变化时调用）
+#[tauri::command]
+#[specta::specta]
+pub async fn resize_terminal(session_id: String, cols: u16, rows: u16) -> AppResult<()> {
+    let mut sessions = sessions();
+    let session = sessions
+        .get_mut(&session_id)
+        .ok_or_else(|| AppError::invalid("终端会话不存在"))?;
+    session
+        .master
+        .resize(pty_size(cols, rows))
+        .map_err(|e| AppError::other(format!("调整终端尺寸失败: {}", e)))?;
+    session.info.cols = cols;
+    session.info.rows = rows;
+    Ok(())
+}
+
+/// 关闭终端：结束 shell 进程，退出事件由等待线程推送
+#[tauri::command]
+#[specta::specta]
+pub async fn close_terminal(session_id: String) -> AppResult<()> {
+    let mut sessions = sessions();
+    let session = sessions
+        .get_mut(&session_id)
+        .ok_or_else(|| AppError::invalid("终端会话不存在"))?;
+    session
+        .killer
+        .kill()
+        .map_err(|e| AppError::other(format!("结束终端进程失败: {}", e)))
+}
+
+/// 打开中的终端；指定 project_id 时只返回该项目的终端
+#[tauri::command]
+#[specta::specta]
+pub async fn list_terminals(project_id: Option<String>) -> AppResult<Vec<TerminalSession>> {
+    let mut list: Vec<TerminalSession> = sessions()
+        .values()
+        .filter(|s| project_id.is_none() || s.info.project_id == project_id)
+        .map(|s| s.info.clone())
+        .collect();
+    list.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+    Ok(list)
+}
+
+/// 终端的回滚输出（前端重新挂载时补回）
+#[tauri::command]
+#[specta::specta]
+pub async fn get_terminal_scrollback(session_id: String) -> AppResult<String> {
+    let sessions = sessions();
+    let session = sessions
+        .get(&session_id)
+        .ok_or_else(|| AppError::invalid("终端会话不存在"))?;
+    let buffer = session
+        .scrollback
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    Ok(buffer)
+}
+
+/// 退出应用时结束所有终端进程
+pub fn close_all() {
+    for session in sessions().values_mut() {
+        let _ = session.killer.kill();
+    }
+}
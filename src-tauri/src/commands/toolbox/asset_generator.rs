@@ -0,0 +1,361 @@
+// 素材生成 - 二维码（PNG / SVG）、由图片生成整套 favicon、指定尺寸的占位图
+//
+// 全部在本地生成：PNG 结果以 base64 返回，SVG 直接返回文本；传入输出路径时同时写入文件。
+// 二维码只用 qrcode 计算模块矩阵，渲染与配色在这里完成；占位图 PNG 没有字体渲染，文字只在 SVG 中出现。
+
+use crate::error::{AppError, AppResult};
+use base64::Engine;
+use image::{imageops, ImageFormat, Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+use std::path::Path;
+
+/// 占位图的最大边长
+const MAX_PLACEHOLDER_SIZE: u32 = 8192;
+
+/// ICO 内嵌的尺寸
+const ICO_SIZES: &[u32] = &[16, 32, 48];
+
+/// 单独输出的 PNG 图标：(文件名, 边长)
+const FAVICON_PNGS: &[(&str, u32)] = &[
+    ("favicon-16x16.png", 16),
+    ("favicon-32x32.png", 32),
+    ("apple-touch-icon.png", 180),
+    ("android-chrome-192x192.png", 192),
+    ("android-chrome-512x512.png", 512),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedAsset {
+    /// "png" | "svg"
+    pub format: String,
+    /// svg 为文本，png 为 base64
+    pub content: String,
+    pub width: u32,
+    pub height: u32,
+    /// 指定了输出路径时写入的文件
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedFile {
+    pub path: String,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FaviconResult {
+    pub files: Vec<GeneratedFile>,
+    /// 放进 <head> 的 link 标签
+    pub html: String,
+}
+
+/// 解析 #rgb / #rrggbb / #rrggbbaa
+fn parse_color(value: &str) -> AppResult<[u8; 4]> {
+    let hex = value.trim().trim_start_matches('#');
+    let expanded: String = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 | 8 => hex.to_string(),
+        _ => return Err(AppError::invalid(format!("颜色格式无效: {}", value))),
+    };
+    let channel = |i: usize| {
+        u8::from_str_radix(&expanded[i..i + 2], 16)
+            .map_err(|_| AppError::invalid(format!("颜色格式无效: {}", value)))
+    };
+    let alpha = if expanded.len() == 8 {
+        channel(6)?
+    } else {
+        255
+    };
+    Ok([channel(0)?, channel(2)?, channel(4)?, alpha])
+}
+
+fn css_color(rgba: [u8; 4]) -> String {
+    if rgba[3] == 255 {
+        format!("#{:02x}{:02x}{:02x}", rgba[0], rgba[1], rgba[2])
+    } else {
+        format!(
+            "rgba({},{},{},{:.3})",
+            rgba[0],
+            rgba[1],
+            rgba[2],
+            rgba[3] as f32 / 255.0
+        )
+    }
+}
+
+fn encode_png(image: &RgbaImage) -> AppResult<Vec<u8>> {
+    let mut out = Cursor::new(Vec::new());
+    image
+        .write_to(&mut out, ImageFormat::Png)
+        .map_err(|e| AppError::internal(format!("PNG 编码失败: {}", e)))?;
+    Ok(out.into_inner())
+}
+
+fn finish_asset(
+    format: &str,
+    bytes: Vec<u8>,
+    width: u32,
+    height: u32,
+    output_path: Option<String>,
+) -> AppResult<GeneratedAsset> {
+    let path = match output_path.filter(|p| !p.trim().is_empty()) {
+        Some(path) => {
+            std::fs::write(&path, &bytes)?;
+            Some(path)
+        }
+        None => None,
+    };
+    let content = if format == "svg" {
+        String::from_utf8_lossy(&bytes).into_owned()
+    } else {
+        base64::engine::general_purpose::STANDARD.encode(&bytes)
+    };
+    Ok(GeneratedAsset {
+        format: format.to_string(),
+        content,
+        width,
+        height,
+        path,
+    })
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// 生成二维码；scale 为每个模块的像素数，margin 为静区模块数（标准建议 4）
+#[tauri::command]
+#[specta::specta]
+#[allow(clippy::too_many_arguments)]
+pub async fn generate_qr_code(
+    text: String,
+    format: String,
+    scale: Option<u32>,
+    margin: Option<u32>,
+    error_correction: Option<String>,
+    foreground: Option<String>,
+    background: Option<String>,
+    output_path: Option<String>,
+) -> AppResult<GeneratedAsset> {
+    if text.is_empty() {
+        return Err(AppError::invalid("二维码内容不能为空"));
+    }
+    let level = match error_correction.as_deref().unwrap_or("M") {
+        "L" | "l" => qrcode::EcLevel::L,
+        "M" | "m" => qrcode::EcLevel::M,
+        "Q" | "q" => qrcode::EcLevel::Q,
+        "H" | "h" => qrcode::EcLevel::H,
+        other => return Err(AppError::invalid(format!("无效的纠错等级: {}", other))),
+    };
+    let code = qrcode::QrCode::with_error_correction_level(text.as_bytes(), level)
+        .map_err(|e| AppError::invalid(format!("无法生成二维码: {}", e)))?;
+    let scale = scale.unwrap_or(8).clamp(1, 64);
+    let margin = margin.unwrap_or(4).min(16);
+    let fg = parse_color(foreground.as_deref().unwrap_or("#000000"))?;
+    let bg = parse_color(background.as_deref().unwrap_or("#ffffff"))?;
+
+    let modules = code.width() as u32;
+    let dark: Vec<bool> = code
+        .to_colors()
+        .into_iter()
+        .map(|c| c == qrcode::Color::Dark)
+        .collect();
+    let total = modules + margin * 2;
+
+    match format.as_str() {
+        "svg" => {
+            // 每个深色模块一段 path，viewBox 以模块为单位，缩放交给 width / height
+            let mut path = String::new();
+            for (i, _) in dark.iter().enumerate().filter(|(_, d)| **d) {
+                let (x, y) = (i as u32 % modules + margin, i as u32 / modules + margin);
+                path.push_str(&format!("M{} {}h1v1h-1z", x, y));
+            }
+            let size = total * scale;
+            let svg = format!(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size}\" height=\"{size}\" viewBox=\"0 0 {total} {total}\" shape-rendering=\"crispEdges\">\n<rect width=\"{total}\" height=\"{total}\" fill=\"{bg}\"/>\n<path d=\"{path}\" fill=\"{fg}\"/>\n</svg>\n",
+                size = size,
+                total = total,
+                bg = css_color(bg),
+                fg = css_color(fg),
+                path = path
+            );
+            finish_asset("svg", svg.into_bytes(), size, size, output_path)
+        }
+        "png" => {
+            let size = total * scale;
+            let image = RgbaImage::from_fn(size, size, |x, y| {
+                let (mx, my) = (x / scale, y / scale);
+                let inside = (margin..margin + modules).contains(&mx)
+                    && (margin..margin + modules).contains(&my);
+                if inside && dark[((my - margin) * modules + (mx - margin)) as usize] {
+                    Rgba(fg)
+                } else {
+                    Rgba(bg)
+                }
+            });
+            finish_asset("png", encode_png(&image)?, size, size, output_path)
+        }
+        other => Err(AppError::invalid(format!("不支持的格式: {}", other))),
+    }
+}
+
+/// 按比例缩放到 size 内并居中放到透明正方形画布上
+fn square_icon(source: &RgbaImage, size: u32) -> RgbaImage {
+    let resized = imageops::resize(
+        source,
+        (source.width() * size / source.width().max(source.height())).max(1),
+        (source.height() * size / source.width().max(source.height())).max(1),
+        imageops::FilterType::Lanczos3,
+    );
+    let mut canvas = RgbaImage::new(size, size);
+    let x = (size - resized.width()) / 2;
+    let y = (size - resized.height()) / 2;
+    imageops::overlay(&mut canvas, &resized, x as i64, y as i64);
+    canvas
+}
+
+/// 由一张图片生成 favicon.ico 与常用尺寸的 PNG 图标，写入 output_dir
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_favicons(
+    source_path: String,
+    output_dir: String,
+) -> AppResult<FaviconResult> {
+    let dir = Path::new(&output_dir);
+    if output_dir.trim().is_empty() {
+        return Err(AppError::invalid("请选择输出目录"));
+    }
+    std::fs::create_dir_all(dir)?;
+
+    let source = tokio::task::spawn_blocking(move || image::open(&source_path))
+        .await
+        .map_err(|e| AppError::internal(format!("读取图片失败: {}", e)))?
+        .map_err(|e| AppError::invalid(format!("无法读取图片: {}", e)))?
+        .into_rgba8();
+    if source.width() < 16 || source.height() < 16 {
+        return Err(AppError::invalid("图片太小，至少需要 16×16"));
+    }
+
+    let dir_owned = dir.to_path_buf();
+    let files = tokio::task::spawn_blocking(move || -> AppResult<Vec<GeneratedFile>> {
+        let mut files = Vec::new();
+        let mut write = |name: &str, bytes: &[u8]| -> AppResult<()> {
+            let path = dir_owned.join(name);
+            std::fs::write(&path, bytes)?;
+            files.push(GeneratedFile {
+                path: path.to_string_lossy().to_string(),
+                bytes: bytes.len() as u64,
+            });
+            Ok(())
+        };
+
+        let icons: Vec<RgbaImage> = ICO_SIZES
+            .iter()
+            .map(|size| square_icon(&source, *size))
+            .collect();
+        let frames = icons
+            .iter()
+            .map(|icon| {
+                image::codecs::ico::IcoFrame::as_png(
+                    icon.as_raw(),
+                    icon.width(),
+                    icon.height(),
+                    image::ExtendedColorType::Rgba8,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::internal(format!("ICO 编码失败: {}", e)))?;
+        let mut ico = Vec::new();
+        image::codecs::ico::IcoEncoder::new(&mut ico)
+            .encode_images(&frames)
+            .map_err(|e| AppError::internal(format!("ICO 编码失败: {}", e)))?;
+        write("favicon.ico", &ico)?;
+
+        for (name, size) in FAVICON_PNGS {
+            write(name, &encode_png(&square_icon(&source, *size))?)?;
+        }
+        Ok(files)
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("生成 favicon 失败: {}", e)))??;
+
+    let html = [
+        r#"<link rel="icon" href="/favicon.ico" sizes="any">"#,
+        r#"<link rel="icon" type="image/png" sizes="32x32" href="/favicon-32x32.png">"#,
+        r#"<link rel="icon" type="image/png" sizes="16x16" href="/favicon-16x16.png">"#,
+        r#"<link rel="apple-touch-icon" sizes="180x180" href="/apple-touch-icon.png">"#,
+    ]
+    .join("\n");
+    Ok(FaviconResult { files, html })
+}
+
+/// 生成占位图：背景色加对角线；SVG 中间显示 text（默认 "宽×高"）
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_placeholder_image(
+    width: u32,
+    height: u32,
+    format: String,
+    background: Option<String>,
+    foreground: Option<String>,
+    text: Option<String>,
+    output_path: Option<String>,
+) -> AppResult<GeneratedAsset> {
+    if width == 0 || height == 0 || width > MAX_PLACEHOLDER_SIZE || height > MAX_PLACEHOLDER_SIZE {
+        return Err(AppError::invalid(format!(
+            "尺寸需在 1-{} 之间",
+            MAX_PLACEHOLDER_SIZE
+        )));
+    }
+    let bg = parse_color(background.as_deref().unwrap_or("#cccccc"))?;
+    let fg = parse_color(foreground.as_deref().unwrap_or("#969696"))?;
+
+    match format.as_str() {
+        "svg" => {
+            let label = text
+                .filter(|t| !t.trim().is_empty())
+                .unwrap_or_else(|| format!("{}×{}", width, height));
+            let font_size = (width.min(height) / 6).max(8);
+            let svg = format!(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n<rect width=\"{w}\" height=\"{h}\" fill=\"{bg}\"/>\n<path d=\"M0 0L{w} {h}M{w} 0L0 {h}\" stroke=\"{fg}\" stroke-opacity=\"0.35\"/>\n<text x=\"50%\" y=\"50%\" fill=\"{fg}\" font-family=\"sans-serif\" font-size=\"{size}\" text-anchor=\"middle\" dominant-baseline=\"middle\">{label}</text>\n</svg>\n",
+                w = width,
+                h = height,
+                bg = css_color(bg),
+                fg = css_color(fg),
+                size = font_size,
+                label = xml_escape(&label)
+            );
+            finish_asset("svg", svg.into_bytes(), width, height, output_path)
+        }
+        "png" => {
+            let bytes = tokio::task::spawn_blocking(move || {
+                let thickness = (width.min(height) / 200).max(1) as f64;
+                let (w, h) = (width as f64, height as f64);
+                let len = (w * w + h * h).sqrt();
+                let image = RgbaImage::from_fn(width, height, |x, y| {
+                    let (px, py) = (x as f64 + 0.5, y as f64 + 0.5);
+                    // 到两条对角线的距离
+                    let d1 = (h * px - w * py).abs() / len;
+                    let d2 = (h * px + w * py - w * h).abs() / len;
+                    if d1 <= thickness / 2.0 + 0.5 || d2 <= thickness / 2.0 + 0.5 {
+                        Rgba(fg)
+                    } else {
+                        Rgba(bg)
+                    }
+                });
+                encode_png(&image)
+            })
+            .await
+            .map_err(|e| AppError::internal(format!("生成占位图失败: {}", e)))??;
+            finish_asset("png", bytes, width, height, output_path)
+        }
+        other => Err(AppError::invalid(format!("不支持的格式: {}", other))),
+    }
+}
@@ -0,0 +1,280 @@
+// 证书检查模块 - 连接 host:port 抓取 TLS 证书链，或解析本地 PEM / CRT / DER 文件
+//
+// 握手时接受任意证书（过期、自签名同样要能看到），只展示字段不判断信任链；
+// 主机名是否匹配、是否即将过期由这里单独计算。
+
+mod x509;
+
+use crate::error::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use sha2::Digest;
+use std::sync::Arc;
+use tokio::time::{timeout, Duration};
+use tokio_rustls::rustls;
+use tokio_rustls::rustls::client::danger::{
+    HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier,
+};
+use tokio_rustls::rustls::crypto::CryptoProvider;
+use tokio_rustls::rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use tokio_rustls::rustls::{DigitallySignedStruct, SignatureScheme};
+
+/// 默认提前告警天数
+const DEFAULT_WARN_DAYS: u32 = 30;
+
+const DEFAULT_TIMEOUT_MS: u64 = 10_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CertificateInfo {
+    pub subject: String,
+    pub issuer: String,
+    pub common_name: Option<String>,
+    /// DNS / IP / email / URI 形式的备用名称
+    pub sans: Vec<String>,
+    pub serial: String,
+    /// RFC 3339
+    pub not_before: String,
+    pub not_after: String,
+    /// 距到期的天数，已过期为负数
+    pub days_remaining: i64,
+    pub expired: bool,
+    /// 尚未生效
+    pub not_yet_valid: bool,
+    /// warn_days 内到期（未过期）
+    pub expiring_soon: bool,
+    pub self_signed: bool,
+    pub is_ca: bool,
+    pub signature_algorithm: String,
+    /// 如 "RSA 2048"、"EC P-256"
+    pub public_key: String,
+    pub sha256_fingerprint: String,
+    pub sha1_fingerprint: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TlsInspection {
+    pub host: String,
+    pub port: u16,
+    /// 如 "TLSv1_3"
+    pub protocol: Option<String>,
+    pub cipher_suite: Option<String>,
+    /// 叶子证书的 SAN（无 SAN 时退回 CN）是否匹配 host
+    pub hostname_matches: bool,
+    /// 服务端发送的证书链，叶子证书在前
+    pub chain: Vec<CertificateInfo>,
+}
+
+fn fingerprint(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+fn certificate_info(der: &[u8], warn_days: u32) -> AppResult<CertificateInfo> {
+    let cert = x509::parse_certificate(der)
+        .map_err(|e| AppError::invalid(format!("证书解析失败: {}", e)))?;
+    let now = chrono::Utc::now();
+    let days_remaining = (cert.not_after - now).num_days();
+    let expired = cert.not_after < now;
+    Ok(CertificateInfo {
+        self_signed: cert.subject_raw == cert.issuer_raw,
+        subject: cert.subject,
+        issuer: cert.issuer,
+        common_name: cert.common_name,
+        sans: cert.sans,
+        serial: cert.serial,
+        not_before: cert.not_before.to_rfc3339(),
+        not_after: cert.not_after.to_rfc3339(),
+        days_remaining,
+        expired,
+        not_yet_valid: cert.not_before > now,
+        expiring_soon: !expired && days_remaining < warn_days as i64,
+        is_ca: cert.is_ca,
+        signature_algorithm: cert.signature_algorithm,
+        public_key: cert.public_key,
+        sha256_fingerprint: fingerprint(&sha2::Sha256::digest(der)),
+        sha1_fingerprint: fingerprint(&sha1::Sha1::digest(der)),
+    })
+}
+
+/// 通配符只匹配一级子域（*.example.com 不匹配 example.com 和 a.b.example.com）
+fn name_matches(pattern: &str, host: &str) -> bool {
+    let pattern = pattern.trim_end_matches('.').to_lowercase();
+    let host = host.trim_end_matches('.').to_lowercase();
+    match pattern.strip_prefix("*.") {
+        Some(suffix) => host
+            .split_once('.')
+            .is_some_and(|(label, rest)| !label.is_empty() && rest == suffix),
+        None => pattern == host,
+    }
+}
+
+fn hostname_matches(leaf: &CertificateInfo, host: &str) -> bool {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let names: Vec<&str> = leaf
+        .sans
+        .iter()
+        .filter(|n| !n.starts_with("email:") && !n.starts_with("URI:"))
+        .map(|n| n.as_str())
+        .collect();
+    if names.is_empty() {
+        return leaf
+            .common_name
+            .as_deref()
+            .is_some_and(|cn| name_matches(cn, host));
+    }
+    names.iter().any(|n| name_matches(n, host))
+}
+
+/// 接受任意证书，只保留握手签名校验（否则连证书都拿不到）
+#[derive(Debug)]
+struct AcceptAnyCertificate(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AcceptAnyCertificate {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+/// 连接 host:port 完成 TLS 握手，返回证书链与协商信息
+#[tauri::command]
+#[specta::specta]
+pub async fn inspect_tls_certificates(
+    host: String,
+    port: u16,
+    server_name: Option<String>,
+    warn_days: Option<u32>,
+    timeout_ms: Option<u64>,
+) -> AppResult<TlsInspection> {
+    let host = host.trim().to_string();
+    if host.is_empty() {
+        return Err(AppError::invalid("主机不能为空"));
+    }
+    // SNI 默认用 host，连 IP 时可以另外指定
+    let sni = server_name
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| host.clone());
+    let sni_name = ServerName::try_from(sni.trim_start_matches('[').trim_end_matches(']'))
+        .map_err(|_| AppError::invalid(format!("无效的服务器名称: {}", sni)))?
+        .to_owned();
+    let warn_days = warn_days.unwrap_or(DEFAULT_WARN_DAYS);
+    let limit = Duration::from_millis(timeout_ms.filter(|t| *t > 0).unwrap_or(DEFAULT_TIMEOUT_MS));
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let config = rustls::ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(|e| AppError::internal(format!("TLS 配置失败: {}", e)))?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(AcceptAnyCertificate(provider)))
+        .with_no_client_auth();
+    let connector = tokio_rustls::TlsConnector::from(Arc::new(config));
+
+    let addr = if host.contains(':') && !host.starts_with('[') {
+        format!("[{}]:{}", host, port)
+    } else {
+        format!("{}:{}", host, port)
+    };
+    let stream = timeout(limit, async {
+        let tcp = tokio::net::TcpStream::connect(&addr)
+            .await
+            .map_err(|e| AppError::other(format!("连接 {} 失败: {}", addr, e)))?;
+        connector
+            .connect(sni_name, tcp)
+            .await
+            .map_err(|e| AppError::other(format!("TLS 握手失败: {}", e)))
+    })
+    .await
+    .map_err(|_| AppError::other(format!("连接 {} 超时", addr)))??;
+
+    let (_, connection) = stream.get_ref();
+    let protocol = connection.protocol_version().map(|v| format!("{:?}", v));
+    let cipher_suite = connection
+        .negotiated_cipher_suite()
+        .map(|s| format!("{:?}", s.suite()));
+    let chain = connection
+        .peer_certificates()
+        .unwrap_or_default()
+        .iter()
+        .map(|c| certificate_info(c.as_ref(), warn_days))
+        .collect::<AppResult<Vec<_>>>()?;
+    if chain.is_empty() {
+        return Err(AppError::other("服务端未返回证书"));
+    }
+
+    Ok(TlsInspection {
+        hostname_matches: hostname_matches(&chain[0], &sni),
+        host,
+        port,
+        protocol,
+        cipher_suite,
+        chain,
+    })
+}
+
+/// 解析本地证书文件：PEM（可含多张证书）或单张 DER
+#[tauri::command]
+#[specta::specta]
+pub async fn parse_certificate_file(
+    path: String,
+    warn_days: Option<u32>,
+) -> AppResult<Vec<CertificateInfo>> {
+    let data = tokio::fs::read(&path)
+        .await
+        .map_err(|e| AppError::invalid(format!("无法读取文件 {}: {}", path, e)))?;
+    let warn_days = warn_days.unwrap_or(DEFAULT_WARN_DAYS);
+    let ders = match std::str::from_utf8(&data) {
+        Ok(text) if text.contains("-----BEGIN") => {
+            x509::pem_certificates(text).map_err(AppError::invalid)?
+        }
+        _ => vec![data],
+    };
+    if ders.is_empty() {
+        return Err(AppError::invalid("文件中没有证书（只支持 CERTIFICATE 块）"));
+    }
+    ders.iter()
+        .map(|der| certificate_info(der, warn_days))
+        .collect()
+}
@@ -0,0 +1,541 @@
+// 最小 X.509 解析：只读取展示需要的字段（主体 / 颁发者 / 有效期 / SAN / 公钥 / 签名算法），
+// 不做签名校验。DER 读取器只支持定长编码，证书本身就要求 DER。
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+const TAG_BOOLEAN: u8 = 0x01;
+const TAG_INTEGER: u8 = 0x02;
+const TAG_BIT_STRING: u8 = 0x03;
+const TAG_OCTET_STRING: u8 = 0x04;
+const TAG_OID: u8 = 0x06;
+const TAG_UTC_TIME: u8 = 0x17;
+const TAG_GENERALIZED_TIME: u8 = 0x18;
+const TAG_SEQUENCE: u8 = 0x30;
+const TAG_VERSION: u8 = 0xa0;
+const TAG_EXTENSIONS: u8 = 0xa3;
+
+const OID_SUBJECT_ALT_NAME: &str = "2.5.29.17";
+const OID_BASIC_CONSTRAINTS: &str = "2.5.29.19";
+const OID_RSA: &str = "1.2.840.113549.1.1.1";
+const OID_EC: &str = "1.2.840.10045.2.1";
+
+pub(super) struct Certificate {
+    pub subject: String,
+    pub issuer: String,
+    pub common_name: Option<String>,
+    /// 原始 DER，用于判断自签名
+    pub subject_raw: Vec<u8>,
+    pub issuer_raw: Vec<u8>,
+    pub serial: String,
+    pub not_before: DateTime<Utc>,
+    pub not_after: DateTime<Utc>,
+    pub sans: Vec<String>,
+    pub is_ca: bool,
+    pub signature_algorithm: String,
+    pub public_key: String,
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn peek_tag(&self) -> Option<u8> {
+        self.data.first().copied()
+    }
+
+    /// 读取一个 TLV，返回 (tag, 内容, 含头部的完整编码)
+    fn read_any(&mut self) -> Result<(u8, &'a [u8], &'a [u8]), String> {
+        let data = self.data;
+        let tag = *data.first().ok_or("DER 数据意外结束")?;
+        let first = *data.get(1).ok_or("DER 数据意外结束")?;
+        let (len, header) = if first < 0x80 {
+            (first as usize, 2)
+        } else {
+            let count = (first & 0x7f) as usize;
+            if count == 0 || count > 4 {
+                return Err("不支持的 DER 长度编码".to_string());
+            }
+            let bytes = data.get(2..2 + count).ok_or("DER 数据意外结束")?;
+            let len = bytes.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize);
+            (len, 2 + count)
+        };
+        let end = header.checked_add(len).ok_or("DER 长度溢出")?;
+        let full = data.get(..end).ok_or("DER 数据意外结束")?;
+        self.data = &data[end..];
+        Ok((tag, &full[header..], full))
+    }
+
+    fn read(&mut self, expected: u8) -> Result<&'a [u8], String> {
+        let (tag, content, _) = self.read_any()?;
+        if tag != expected {
+            return Err(format!(
+                "DER 结构不符：期望 0x{:02x}，实际 0x{:02x}",
+                expected, tag
+            ));
+        }
+        Ok(content)
+    }
+
+    fn read_sequence(&mut self) -> Result<Reader<'a>, String> {
+        self.read(TAG_SEQUENCE).map(Reader::new)
+    }
+}
+
+fn parse_oid(data: &[u8]) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    let mut value: u64 = 0;
+    for byte in data {
+        value = (value << 7) | (byte & 0x7f) as u64;
+        if byte & 0x80 == 0 {
+            if parts.is_empty() {
+                let first = (value / 40).min(2);
+                parts.push(first.to_string());
+                parts.push((value - first * 40).to_string());
+            } else {
+                parts.push(value.to_string());
+            }
+            value = 0;
+        }
+    }
+    parts.join(".")
+}
+
+fn attribute_name(oid: &str) -> String {
+    match oid {
+        "2.5.4.3" => "CN",
+        "2.5.4.5" => "serialNumber",
+        "2.5.4.6" => "C",
+        "2.5.4.7" => "L",
+        "2.5.4.8" => "ST",
+        "2.5.4.10" => "O",
+        "2.5.4.11" => "OU",
+        "1.2.840.113549.1.9.1" => "emailAddress",
+        "0.9.2342.19200300.100.1.25" => "DC",
+        _ => return oid.to_string(),
+    }
+    .to_string()
+}
+
+fn algorithm_name(oid: &str) -> String {
+    match oid {
+        "1.2.840.113549.1.1.5" => "sha1WithRSAEncryption",
+        "1.2.840.113549.1.1.11" => "sha256WithRSAEncryption",
+        "1.2.840.113549.1.1.12" => "sha384WithRSAEncryption",
+        "1.2.840.113549.1.1.13" => "sha512WithRSAEncryption",
+        "1.2.840.113549.1.1.10" => "RSASSA-PSS",
+        "1.2.840.10045.4.3.2" => "ecdsa-with-SHA256",
+        "1.2.840.10045.4.3.3" => "ecdsa-with-SHA384",
+        "1.2.840.10045.4.3.4" => "ecdsa-with-SHA512",
+        "1.3.101.112" => "Ed25519",
+        "1.3.101.113" => "Ed448",
+        _ => return oid.to_string(),
+    }
+    .to_string()
+}
+
+fn curve_name(oid: &str) -> String {
+    match oid {
+        "1.2.840.10045.3.1.7" => "P-256",
+        "1.3.132.0.34" => "P-384",
+        "1.3.132.0.35" => "P-521",
+        _ => return oid.to_string(),
+    }
+    .to_string()
+}
+
+/// 属性值按字符串解码（UTF8String / PrintableString / IA5String 等）；BMPString 按 UTF-16BE
+fn decode_string(tag: u8, data: &[u8]) -> String {
+    if tag == 0x1e {
+        let units: Vec<u16> = data
+            .chunks(2)
+            .map(|c| u16::from_be_bytes([c[0], *c.get(1).unwrap_or(&0)]))
+            .collect();
+        return String::from_utf16_lossy(&units);
+    }
+    String::from_utf8_lossy(data).into_owned()
+}
+
+/// Name 格式化为 "CN=example.com, O=Example"，顺序与证书内一致
+fn parse_name(data: &[u8]) -> Result<(String, Option<String>), String> {
+    let mut parts = Vec::new();
+    let mut common_name = None;
+    let mut rdns = Reader::new(data);
+    while !rdns.is_empty() {
+        let mut set = Reader::new(rdns.read(0x31)?);
+        while !set.is_empty() {
+            let mut attr = set.read_sequence()?;
+            let oid = parse_oid(attr.read(TAG_OID)?);
+            let (tag, value, _) = attr.read_any()?;
+            let value = decode_string(tag, value);
+            if oid == "2.5.4.3" {
+                common_name = Some(value.clone());
+            }
+            parts.push(format!("{}={}", attribute_name(&oid), value));
+        }
+    }
+    Ok((parts.join(", "), common_name))
+}
+
+/// 解析 UTCTime / GeneralizedTime。RFC 5280 要求以 Z 结尾，这里也接受 BER 允许的 ±hhmm
+/// 时区偏移，以及 GeneralizedTime 的小数秒（截断到秒）；不带时区的本地时间无法换算，直接拒绝
+fn parse_time(tag: u8, data: &[u8]) -> Result<DateTime<Utc>, String> {
+    let invalid = || format!("证书时间格式无效: {}", String::from_utf8_lossy(data));
+    let text = std::str::from_utf8(data)
+        .ok()
+        .filter(|t| t.is_ascii())
+        .ok_or_else(invalid)?;
+
+    let (body, offset_secs) = match text.strip_suffix('Z') {
+        Some(body) => (body, 0),
+        None => {
+            let split = text.len().checked_sub(5).ok_or_else(invalid)?;
+            let (body, zone) = text.split_at(split);
+            let sign = match zone.as_bytes()[0] {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return Err(invalid()),
+            };
+            let hours: i64 = zone[1..3].parse().map_err(|_| invalid())?;
+            let minutes: i64 = zone[3..5].parse().map_err(|_| invalid())?;
+            if !zone[1..].bytes().all(|b| b.is_ascii_digit()) || hours > 23 || minutes > 59 {
+                return Err(invalid());
+            }
+            (body, sign * (hours * 3600 + minutes * 60))
+        }
+    };
+
+    let body = match body.split_once(['.', ',']) {
+        None => body,
+        Some((whole, fraction))
+            if tag == TAG_GENERALIZED_TIME
+                && !fraction.is_empty()
+                && fraction.bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            whole
+        }
+        Some(_) => return Err(invalid()),
+    };
+    if !body.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+
+    let full = match (tag, body.len()) {
+        // UTCTime 两位年份：50 及以上为 19xx
+        (TAG_UTC_TIME, 10 | 12) => {
+            let year: u32 = body[..2].parse().map_err(|_| invalid())?;
+            format!("{}{}", if year >= 50 { "19" } else { "20" }, body)
+        }
+        (TAG_GENERALIZED_TIME, 12 | 14) => body.to_string(),
+        _ => return Err(invalid()),
+    };
+    // 秒可省略
+    let full = if full.len() == 12 {
+        format!("{}00", full)
+    } else {
+        full
+    };
+    NaiveDateTime::parse_from_str(&full, "%Y%m%d%H%M%S")
+        .map(|t| t.and_utc() - chrono::Duration::seconds(offset_secs))
+        .map_err(|_| invalid())
+}
+
+fn parse_san(data: &[u8]) -> Result<Vec<String>, String> {
+    let mut names = Vec::new();
+    let mut seq = Reader::new(data).read_sequence()?;
+    while !seq.is_empty() {
+        let (tag, value, _) = seq.read_any()?;
+        match tag {
+            0x81 => names.push(format!("email:{}", String::from_utf8_lossy(value))),
+            0x82 => names.push(String::from_utf8_lossy(value).into_owned()),
+            0x86 => names.push(format!("URI:{}", String::from_utf8_lossy(value))),
+            0x87 => match value.len() {
+                4 => names.push(
+                    std::net::Ipv4Addr::new(value[0], value[1], value[2], value[3]).to_string(),
+                ),
+                16 => {
+                    let mut octets = [0u8; 16];
+                    octets.copy_from_slice(value);
+                    names.push(std::net::Ipv6Addr::from(octets).to_string());
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    Ok(names)
+}
+
+fn parse_public_key(mut spki: Reader) -> Result<String, String> {
+    let mut algorithm = spki.read_sequence()?;
+    let oid = parse_oid(algorithm.read(TAG_OID)?);
+    let key = spki.read(TAG_BIT_STRING)?;
+    Ok(match oid.as_str() {
+        OID_RSA => {
+            // BIT STRING 首字节为未用位数，之后是 RSAPublicKey { modulus, exponent }
+            let modulus = Reader::new(key.get(1..).unwrap_or_default())
+                .read_sequence()
+                .and_then(|mut k| k.read(TAG_INTEGER))?;
+            let bytes = modulus.iter().skip_while(|b| **b == 0).count();
+            format!("RSA {}", bytes * 8)
+        }
+        OID_EC => {
+            let curve = if algorithm.peek_tag() == Some(TAG_OID) {
+                curve_name(&parse_oid(algorithm.read(TAG_OID)?))
+            } else {
+                "未知曲线".to_string()
+            };
+            format!("EC {}", curve)
+        }
+        other => algorithm_name(other),
+    })
+}
+
+fn hex_serial(data: &[u8]) -> String {
+    let trimmed: &[u8] = match data {
+        [0, rest @ ..] if !rest.is_empty() => rest,
+        _ => data,
+    };
+    trimmed
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+pub(super) fn parse_certificate(der: &[u8]) -> Result<Certificate, String> {
+    let mut cert = Reader::new(der).read_sequence()?;
+    let mut tbs = cert.read_sequence()?;
+    let mut signature = cert.read_sequence()?;
+    let signature_algorithm = algorithm_name(&parse_oid(signature.read(TAG_OID)?));
+
+    if tbs.peek_tag() == Some(TAG_VERSION) {
+        tbs.read_any()?;
+    }
+    let serial = hex_serial(tbs.read(TAG_INTEGER)?);
+    tbs.read_sequence()?;
+    let (_, issuer_data, issuer_raw) = tbs.read_any()?;
+    let (issuer, _) = parse_name(issuer_data)?;
+    let mut validity = tbs.read_sequence()?;
+    let (tag, value, _) = validity.read_any()?;
+    let not_before = parse_time(tag, value)?;
+    let (tag, value, _) = validity.read_any()?;
+    let not_after = parse_time(tag, value)?;
+    let (_, subject_data, subject_raw) = tbs.read_any()?;
+    let (subject, common_name) = parse_name(subject_data)?;
+    let public_key = parse_public_key(tbs.read_sequence()?)?;
+
+    let mut sans = Vec::new();
+    let mut is_ca = false;
+    while !tbs.is_empty() {
+        let (tag, value, _) = tbs.read_any()?;
+        if tag != TAG_EXTENSIONS {
+            continue;
+        }
+        let mut extensions = Reader::new(value).read_sequence()?;
+        while !extensions.is_empty() {
+            let mut ext = extensions.read_sequence()?;
+            let oid = parse_oid(ext.read(TAG_OID)?);
+            if ext.peek_tag() == Some(TAG_BOOLEAN) {
+                ext.read_any()?;
+            }
+            let value = ext.read(TAG_OCTET_STRING)?;
+            match oid.as_str() {
+                OID_SUBJECT_ALT_NAME => sans = parse_san(value)?,
+                OID_BASIC_CONSTRAINTS => {
+                    let mut constraints = Reader::new(value).read_sequence()?;
+                    if constraints.peek_tag() == Some(TAG_BOOLEAN) {
+                        is_ca = constraints.read(TAG_BOOLEAN)?.first() == Some(&0xff);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(Certificate {
+        subject,
+        issuer,
+        common_name,
+        subject_raw: subject_raw.to_vec(),
+        issuer_raw: issuer_raw.to_vec(),
+        serial,
+        not_before,
+        not_after,
+        sans,
+        is_ca,
+        signature_algorithm,
+        public_key,
+    })
+}
+
+/// 从 PEM 文本中取出所有 CERTIFICATE 块的 DER
+pub(super) fn pem_certificates(text: &str) -> Result<Vec<Vec<u8>>, String> {
+    use base64::Engine;
+    const BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+    const END: &str = "-----END CERTIFICATE-----";
+    let mut result = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(BEGIN) {
+        let body_start = start + BEGIN.len();
+        let end = rest[body_start..]
+            .find(END)
+            .ok_or("PEM 缺少 END CERTIFICATE")?;
+        let body: String = rest[body_start..body_start + end]
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        let der = base64::engine::general_purpose::STANDARD
+            .decode(body)
+            .map_err(|e| format!("PEM 内容不是有效的 Base64: {}", e))?;
+        result.push(der);
+        rest = &rest[body_start + end + END.len()..];
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 自签名 EC 证书，有效期 1999-12-31 23:59:59 至 2049-12-31 23:59:59，两端都是 UTCTime
+    const UTC_CERT: &str = "-----BEGIN CERTIFICATE-----
+MIIBjTCCATOgAwIBAgIEEjSrzTAKBggqhkjOPQQDAjAzMRgwFgYDVQQDDA91dGMu
+ZXhhbXBsZS5jb20xFzAVBgNVBAoMDkNvZGVzaGVsZiBUZXN0MB4XDTk5MTIzMTIz
+NTk1OVoXDTQ5MTIzMTIzNTk1OVowMzEYMBYGA1UEAwwPdXRjLmV4YW1wbGUuY29t
+MRcwFQYDVQQKDA5Db2Rlc2hlbGYgVGVzdDBZMBMGByqGSM49AgEGCCqGSM49AwEH
+A0IABNkJx98JRMarW3t7Qm3F4swyciL+FmLOQbl4jDm1fMoF29R0KG6RbzalZAiK
+tOqL3k3L5PPVUNbSIjecUOls5vSjNTAzMCAGA1UdEQQZMBeCD3V0Yy5leGFtcGxl
+LmNvbYcEfwAAATAPBgNVHRMBAf8EBTADAQH/MAoGCCqGSM49BAMCA0gAMEUCIEGA
+sZYL/PdlyUU+BgN7Lv3oXMyTGH9aYxfTXL5iMaJxAiEAyWFcQDxCol6p45TXUE1W
+Z6m9lteaL9QmMaz1prXn4iA=
+-----END CERTIFICATE-----
+";
+
+    /// 自签名 EC 证书，notBefore 为 UTCTime 2024-01-01，notAfter 为 GeneralizedTime 2050-01-01 12:30:00
+    const GENERALIZED_CERT: &str = "-----BEGIN CERTIFICATE-----
+MIIBjDCCATKgAwIBAgIEEjSrzTAKBggqhkjOPQQDAjAzMRgwFgYDVQQDDA9nZW4u
+ZXhhbXBsZS5jb20xFzAVBgNVBAoMDkNvZGVzaGVsZiBUZXN0MCAXDTI0MDEwMTAw
+MDAwMFoYDzIwNTAwMTAxMTIzMDAwWjAzMRgwFgYDVQQDDA9nZW4uZXhhbXBsZS5j
+b20xFzAVBgNVBAoMDkNvZGVzaGVsZiBUZXN0MFkwEwYHKoZIzj0CAQYIKoZIzj0D
+AQcDQgAEhETuulRrC+tD4OYoE+Ip1GdyQ4hwXlWwATjG6QHgHY0lGC5j/x5AdoBc
+08XbIiXGZJbHPs+0hyqKb4lvGN1scqMyMDAwIAYDVR0RBBkwF4IPZ2VuLmV4YW1w
+bGUuY29thwR/AAABMAwGA1UdEwEB/wQCMAAwCgYIKoZIzj0EAwIDSAAwRQIhAIu5
+KDOqr+TBhE4g2PtYsaCpl7Zd/Nc/EZ5ErOTHYrxIAiBSmHmzBdz0tzWlAJxMDFM3
+eDPUBU8Y6TEPz8fyWlciBQ==
+-----END CERTIFICATE-----
+";
+
+    fn der(pem: &str) -> Vec<u8> {
+        pem_certificates(pem).unwrap().remove(0)
+    }
+
+    fn utc(text: &str) -> DateTime<Utc> {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn test_utc_time_century_window() {
+        let der = der(UTC_CERT);
+        // 外层 SEQUENCE 超过 127 字节，使用两字节长度的长格式
+        assert_eq!(&der[..2], &[TAG_SEQUENCE, 0x82]);
+        let cert = parse_certificate(&der).unwrap();
+        assert_eq!(cert.not_before, utc("1999-12-31T23:59:59Z"));
+        assert_eq!(cert.not_after, utc("2049-12-31T23:59:59Z"));
+        assert_eq!(cert.subject, "CN=utc.example.com, O=Codeshelf Test");
+        assert_eq!(cert.common_name.as_deref(), Some("utc.example.com"));
+        assert_eq!(cert.subject_raw, cert.issuer_raw);
+        assert_eq!(cert.serial, "12:34:AB:CD");
+        assert_eq!(cert.sans, vec!["utc.example.com", "127.0.0.1"]);
+        assert!(cert.is_ca);
+        assert_eq!(cert.public_key, "EC P-256");
+        assert_eq!(cert.signature_algorithm, "ecdsa-with-SHA256");
+    }
+
+    #[test]
+    fn test_generalized_time_after_2050() {
+        let cert = parse_certificate(&der(GENERALIZED_CERT)).unwrap();
+        assert_eq!(cert.not_before, utc("2024-01-01T00:00:00Z"));
+        assert_eq!(cert.not_after, utc("2050-01-01T12:30:00Z"));
+        assert!(!cert.is_ca);
+    }
+
+    #[test]
+    fn test_truncated_der_is_rejected() {
+        let der = der(UTC_CERT);
+        for len in 0..der.len() {
+            assert!(parse_certificate(&der[..len]).is_err(), "前缀长度 {}", len);
+        }
+    }
+
+    #[test]
+    fn test_garbage_der_is_rejected() {
+        assert!(parse_certificate(b"not a certificate").is_err());
+        // 长度字段声称 4GB
+        assert!(parse_certificate(&[TAG_SEQUENCE, 0x84, 0xff, 0xff, 0xff, 0xff]).is_err());
+        // 长度字节数超过 4
+        assert!(parse_certificate(&[TAG_SEQUENCE, 0x85, 0, 0, 0, 0, 1, 0]).is_err());
+        // 不定长编码
+        assert!(parse_certificate(&[TAG_SEQUENCE, 0x80, 0, 0]).is_err());
+        assert!(
+            pem_certificates("-----BEGIN CERTIFICATE-----\n!!!\n-----END CERTIFICATE-----")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_long_form_lengths() {
+        let mut one_byte = vec![TAG_OCTET_STRING, 0x81, 0x80];
+        one_byte.extend([0xaa; 0x80]);
+        one_byte.push(TAG_INTEGER);
+        let mut reader = Reader::new(&one_byte);
+        assert_eq!(reader.read(TAG_OCTET_STRING).unwrap().len(), 0x80);
+        assert_eq!(reader.peek_tag(), Some(TAG_INTEGER));
+
+        let mut two_bytes = vec![TAG_OCTET_STRING, 0x82, 0x01, 0x00];
+        two_bytes.extend([0xbb; 0x100]);
+        let mut reader = Reader::new(&two_bytes);
+        assert_eq!(reader.read(TAG_OCTET_STRING).unwrap().len(), 0x100);
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn test_time_offsets_and_fractions() {
+        let parse = |tag, text: &str| parse_time(tag, text.as_bytes());
+        assert_eq!(
+            parse(TAG_GENERALIZED_TIME, "20500101143000+0200").unwrap(),
+            utc("2050-01-01T12:30:00Z")
+        );
+        assert_eq!(
+            parse(TAG_UTC_TIME, "491231235959-0100").unwrap(),
+            utc("2050-01-01T00:59:59Z")
+        );
+        assert_eq!(
+            parse(TAG_GENERALIZED_TIME, "20500101123000.123Z").unwrap(),
+            utc("2050-01-01T12:30:00Z")
+        );
+        assert_eq!(
+            parse(TAG_UTC_TIME, "9912312359Z").unwrap(),
+            utc("1999-12-31T23:59:00Z")
+        );
+        // 小数秒只允许出现在 GeneralizedTime
+        assert!(parse(TAG_UTC_TIME, "491231235959.5Z").is_err());
+        assert!(parse(TAG_GENERALIZED_TIME, "20500101123000.Z").is_err());
+        // 缺少时区、非法偏移、长度不符
+        assert!(parse(TAG_GENERALIZED_TIME, "20500101123000").is_err());
+        assert!(parse(TAG_GENERALIZED_TIME, "20500101123000+2460").is_err());
+        assert!(parse(TAG_GENERALIZED_TIME, "20500101123000+0x00").is_err());
+        assert!(parse(TAG_UTC_TIME, "20500101123000Z").is_err());
+        assert!(parse(TAG_GENERALIZED_TIME, "20501301123000Z").is_err());
+        assert!(parse(TAG_UTC_TIME, "Z").is_err());
+        assert!(parse(TAG_UTC_TIME, "时间Z").is_err());
+    }
+}
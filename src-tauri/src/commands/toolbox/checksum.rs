@@ -0,0 +1,220 @@
+// 本地文件校验和 - 计算 MD5 / SHA-1 / SHA-256 / SHA-512 / CRC32，可与期望值比对
+//
+// 文件只读一遍，所有选中的算法同时更新；大文件（ISO 等）按 PROGRESS_INTERVAL 推送 `file-hash-progress`。
+// 传入 job_id 时登记为可取消操作，前端可用 cancel_operation(job_id) 中止。
+
+use crate::commands::operations::Operation;
+use crate::error::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use sha2::Digest;
+use std::io::Read;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// 支持的算法
+pub const HASH_ALGORITHMS: &[&str] = &["md5", "sha1", "sha256", "sha512", "crc32"];
+
+const READ_BUFFER: usize = 1024 * 1024;
+
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FileHash {
+    pub algorithm: String,
+    /// 小写十六进制
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FileHashResult {
+    pub path: String,
+    pub size: u64,
+    pub hashes: Vec<FileHash>,
+    /// 与期望值一致的算法；未传期望值或都不一致时为 None
+    pub matched_algorithm: Option<String>,
+    /// 未传期望值时为 None
+    pub matches: Option<bool>,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FileHashProgress {
+    pub job_id: String,
+    pub path: String,
+    pub processed: u64,
+    pub total: u64,
+}
+
+enum Hasher {
+    Md5(md5::Context),
+    Sha1(sha1::Sha1),
+    Sha256(sha2::Sha256),
+    Sha512(sha2::Sha512),
+    Crc32(crc32fast::Hasher),
+}
+
+impl Hasher {
+    fn new(algorithm: &str) -> Option<Self> {
+        Some(match algorithm {
+            "md5" => Self::Md5(md5::Context::new()),
+            "sha1" => Self::Sha1(sha1::Sha1::new()),
+            "sha256" => Self::Sha256(sha2::Sha256::new()),
+            "sha512" => Self::Sha512(sha2::Sha512::new()),
+            "crc32" => Self::Crc32(crc32fast::Hasher::new()),
+            _ => return None,
+        })
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Md5(h) => h.consume(data),
+            Self::Sha1(h) => h.update(data),
+            Self::Sha256(h) => h.update(data),
+            Self::Sha512(h) => h.update(data),
+            Self::Crc32(h) => h.update(data),
+        }
+    }
+
+    fn finish(self) -> String {
+        match self {
+            Self::Md5(h) => format!("{:x}", h.compute()),
+            Self::Sha1(h) => hex(&h.finalize()),
+            Self::Sha256(h) => hex(&h.finalize()),
+            Self::Sha512(h) => hex(&h.finalize()),
+            Self::Crc32(h) => format!("{:08x}", h.finalize()),
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// 期望值统一为小写十六进制；兼容 "sha256:abc…"、带空格 / 冒号分隔、CRC32 的 "0x" 前缀
+fn normalize_expected(expected: &str) -> String {
+    let value = expected.trim();
+    let value = value
+        .split_once(':')
+        .filter(|(prefix, _)| HASH_ALGORITHMS.contains(&prefix.to_lowercase().as_str()))
+        .map_or(value, |(_, v)| v);
+    let value = value.trim_start_matches("0x").trim_start_matches("0X");
+    value
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ':' && *c != '-')
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn hash_file(
+    app: &AppHandle,
+    path: &str,
+    algorithms: &[String],
+    job_id: &str,
+    op: &Operation,
+) -> AppResult<(u64, Vec<FileHash>)> {
+    let mut file = std::fs::File::open(path)
+        .map_err(|e| AppError::invalid(format!("无法打开文件 {}: {}", path, e)))?;
+    let total = file.metadata()?.len();
+    let mut hashers: Vec<(String, Hasher)> = algorithms
+        .iter()
+        .filter_map(|a| Hasher::new(a).map(|h| (a.clone(), h)))
+        .collect();
+
+    let mut buf = vec![0u8; READ_BUFFER];
+    let mut processed = 0u64;
+    let mut last_progress = Instant::now();
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        for (_, hasher) in hashers.iter_mut() {
+            hasher.update(&buf[..n]);
+        }
+        processed += n as u64;
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            op.check()?;
+            last_progress = Instant::now();
+            let _ = app.emit(
+                "file-hash-progress",
+                FileHashProgress {
+                    job_id: job_id.to_string(),
+                    path: path.to_string(),
+                    processed,
+                    total,
+                },
+            );
+        }
+    }
+
+    let hashes = hashers
+        .into_iter()
+        .map(|(algorithm, hasher)| FileHash {
+            algorithm,
+            value: hasher.finish(),
+        })
+        .collect();
+    Ok((processed, hashes))
+}
+
+/// 计算文件的校验和；algorithms 为空时计算全部算法，传入 expected 时比对结果
+#[tauri::command]
+#[specta::specta]
+pub async fn compute_file_hashes(
+    app: AppHandle,
+    path: String,
+    algorithms: Vec<String>,
+    expected: Option<String>,
+    job_id: Option<String>,
+) -> AppResult<FileHashResult> {
+    let mut selected: Vec<String> = Vec::new();
+    for algorithm in algorithms {
+        let algorithm = algorithm.trim().to_lowercase().replace('-', "");
+        if !HASH_ALGORITHMS.contains(&algorithm.as_str()) {
+            return Err(AppError::invalid(format!("不支持的算法: {}", algorithm)));
+        }
+        if !selected.contains(&algorithm) {
+            selected.push(algorithm);
+        }
+    }
+    if selected.is_empty() {
+        selected = HASH_ALGORITHMS.iter().map(|a| a.to_string()).collect();
+    }
+    if !std::path::Path::new(&path).is_file() {
+        return Err(AppError::invalid(format!("文件不存在: {}", path)));
+    }
+
+    let op = Operation::begin(job_id, "计算校验和", None)?;
+    let started = Instant::now();
+    let (size, hashes) = {
+        let path = path.clone();
+        tokio::task::spawn_blocking(move || {
+            let job_id = op.id().to_string();
+            hash_file(&app, &path, &selected, &job_id, &op)
+        })
+        .await
+        .map_err(|e| AppError::internal(format!("计算校验和失败: {}", e)))??
+    };
+
+    let expected = expected
+        .as_deref()
+        .map(normalize_expected)
+        .filter(|e| !e.is_empty());
+    let matched_algorithm = expected.as_ref().and_then(|e| {
+        hashes
+            .iter()
+            .find(|h| &h.value == e)
+            .map(|h| h.algorithm.clone())
+    });
+    Ok(FileHashResult {
+        path,
+        size,
+        matches: expected.map(|_| matched_algorithm.is_some()),
+        matched_algorithm,
+        hashes,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+    })
+}
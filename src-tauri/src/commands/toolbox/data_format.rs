@@ -0,0 +1,546 @@
+// 数据格式工具 - JSON / YAML / TOML 校验与互转、JSON 格式化 / 压缩、JSONPath 查询
+//
+// 大文件在 webview 里解析会卡死，这些操作都放在 Rust 侧，可直接传文件路径并把结果写到文件。
+// 互转以 serde_yaml::Value 为中间表示（保留键顺序）；JSON 格式化按词法逐字符重排，
+// 不经过反序列化，键顺序与数字字面量原样保留。
+
+use crate::error::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+/// JSONPath 查询返回的最大结果数
+const MAX_QUERY_RESULTS: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum DocumentFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+/// 解析错误位置，行列均从 1 开始
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentError {
+    pub message: String,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentValidation {
+    pub valid: bool,
+    pub error: Option<DocumentError>,
+}
+
+/// 传入 output_path 时结果写入文件，text 为 None
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentOutput {
+    pub text: Option<String>,
+    pub written_to: Option<String>,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonPathMatch {
+    /// 规范化路径，如 $.items[0]['display name']
+    pub path: String,
+    /// 匹配值的 JSON 文本
+    pub value: String,
+}
+
+impl DocumentError {
+    fn at(message: impl Into<String>, line: usize, column: usize) -> Self {
+        Self {
+            message: message.into(),
+            line: Some(line as u32),
+            column: Some(column as u32),
+        }
+    }
+
+    fn plain(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            line: None,
+            column: None,
+        }
+    }
+}
+
+impl From<DocumentError> for AppError {
+    fn from(e: DocumentError) -> Self {
+        match (e.line, e.column) {
+            (Some(line), Some(column)) => {
+                AppError::invalid(format!("第 {} 行第 {} 列: {}", line, column, e.message))
+            }
+            _ => AppError::invalid(e.message),
+        }
+    }
+}
+
+/// 字节偏移换算为行列
+fn position(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+    (line, column)
+}
+
+fn toml_to_value(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::Number(i.into()),
+        toml::Value::Float(f) => Value::Number(f.into()),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        // 其他格式没有日期类型，按 TOML 原文输出字符串
+        toml::Value::Datetime(d) => Value::String(d.to_string()),
+        toml::Value::Array(items) => {
+            Value::Sequence(items.into_iter().map(toml_to_value).collect())
+        }
+        toml::Value::Table(table) => Value::Mapping(
+            table
+                .into_iter()
+                .map(|(k, v)| (Value::String(k), toml_to_value(v)))
+                .collect(),
+        ),
+    }
+}
+
+fn parse(format: DocumentFormat, text: &str) -> Result<Value, DocumentError> {
+    match format {
+        DocumentFormat::Json => serde_json::from_str::<Value>(text)
+            .map_err(|e| DocumentError::at(e.to_string(), e.line(), e.column())),
+        DocumentFormat::Yaml => {
+            serde_yaml::from_str::<Value>(text).map_err(|e| match e.location() {
+                Some(loc) => DocumentError::at(e.to_string(), loc.line(), loc.column()),
+                None => DocumentError::plain(e.to_string()),
+            })
+        }
+        DocumentFormat::Toml => text
+            .parse::<toml::Table>()
+            .map(|table| toml_to_value(toml::Value::Table(table)))
+            .map_err(|e| match e.span() {
+                Some(span) => {
+                    let (line, column) = position(text, span.start);
+                    DocumentError::at(e.message().to_string(), line, column)
+                }
+                None => DocumentError::plain(e.message().to_string()),
+            }),
+    }
+}
+
+fn render(format: DocumentFormat, value: &Value, indent: usize) -> AppResult<String> {
+    match format {
+        DocumentFormat::Json if indent == 0 => serde_json::to_string(value)
+            .map_err(|e| AppError::invalid(format!("无法转换为 JSON: {}", e))),
+        DocumentFormat::Json => {
+            let indent = " ".repeat(indent);
+            let mut out = Vec::new();
+            let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+            let mut serializer = serde_json::Serializer::with_formatter(&mut out, formatter);
+            value
+                .serialize(&mut serializer)
+                .map_err(|e| AppError::invalid(format!("无法转换为 JSON: {}", e)))?;
+            Ok(String::from_utf8_lossy(&out).into_owned())
+        }
+        DocumentFormat::Yaml => serde_yaml::to_string(value)
+            .map_err(|e| AppError::invalid(format!("无法转换为 YAML: {}", e))),
+        DocumentFormat::Toml => {
+            let toml_value = toml::Value::try_from(value)
+                .map_err(|e| AppError::invalid(format!("无法转换为 TOML: {}", e)))?;
+            if !toml_value.is_table() {
+                return Err(AppError::invalid("TOML 顶层必须是对象（键值表）"));
+            }
+            toml::to_string_pretty(&toml_value)
+                .map_err(|e| AppError::invalid(format!("无法转换为 TOML: {}", e)))
+        }
+    }
+}
+
+/// text 与 file_path 二选一；都传时以 text 为准
+async fn read_source(text: Option<String>, file_path: Option<String>) -> AppResult<String> {
+    if let Some(text) = text {
+        return Ok(text);
+    }
+    let path = file_path
+        .filter(|p| !p.trim().is_empty())
+        .ok_or_else(|| AppError::invalid("请提供文本或文件路径"))?;
+    let bytes = tokio::fs::read(&path)
+        .await
+        .map_err(|e| AppError::invalid(format!("无法读取文件 {}: {}", path, e)))?;
+    String::from_utf8(bytes)
+        .map_err(|_| AppError::invalid(format!("文件不是 UTF-8 文本: {}", path)))
+}
+
+async fn write_output(output: String, output_path: Option<String>) -> AppResult<DocumentOutput> {
+    let bytes = output.len() as u64;
+    match output_path.filter(|p| !p.trim().is_empty()) {
+        Some(path) => {
+            tokio::fs::write(&path, output).await?;
+            Ok(DocumentOutput {
+                text: None,
+                written_to: Some(path),
+                bytes,
+            })
+        }
+        None => Ok(DocumentOutput {
+            text: Some(output),
+            written_to: None,
+            bytes,
+        }),
+    }
+}
+
+/// 按 JSON 词法重排空白；indent 为 None 时压缩成一行。调用前需已确认是合法 JSON
+fn reformat_json(text: &str, indent: Option<&str>) -> String {
+    let bytes = text.as_bytes();
+    let mut out: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let newline = |out: &mut Vec<u8>, depth: usize| {
+        if let Some(indent) = indent {
+            out.push(b'\n');
+            for _ in 0..depth {
+                out.extend_from_slice(indent.as_bytes());
+            }
+        }
+    };
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        i += 1;
+        if in_string {
+            out.push(b);
+            if escaped {
+                escaped = false;
+            } else if b == b'\\' {
+                escaped = true;
+            } else if b == b'"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match b {
+            b'"' => {
+                in_string = true;
+                out.push(b);
+            }
+            b'{' | b'[' => {
+                out.push(b);
+                // 空容器保持 {} / []
+                let close = if b == b'{' { b'}' } else { b']' };
+                let mut j = i;
+                while j < bytes.len() && bytes[j].is_ascii_whitespace() {
+                    j += 1;
+                }
+                if bytes.get(j) == Some(&close) {
+                    out.push(close);
+                    i = j + 1;
+                } else {
+                    depth += 1;
+                    newline(&mut out, depth);
+                }
+            }
+            b'}' | b']' => {
+                depth = depth.saturating_sub(1);
+                newline(&mut out, depth);
+                out.push(b);
+            }
+            b',' => {
+                out.push(b);
+                newline(&mut out, depth);
+            }
+            b':' => {
+                out.push(b);
+                if indent.is_some() {
+                    out.push(b' ');
+                }
+            }
+            b if b.is_ascii_whitespace() => {}
+            _ => out.push(b),
+        }
+    }
+    if indent.is_some() {
+        out.push(b'\n');
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+enum PathStep {
+    Key(String),
+    Index(i64),
+    Wildcard,
+    /// `..key` / `..*`，None 表示通配
+    Descendant(Option<String>),
+}
+
+fn parse_json_path(expression: &str) -> AppResult<Vec<PathStep>> {
+    let invalid = |msg: &str| AppError::invalid(format!("JSONPath 无效: {}", msg));
+    let expr = expression.trim();
+    let mut chars = expr
+        .strip_prefix('$')
+        .ok_or_else(|| invalid("必须以 $ 开头"))?
+        .chars()
+        .peekable();
+    let read_name = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+        let mut name = String::new();
+        while let Some(&c) = chars.peek() {
+            if c == '.' || c == '[' {
+                break;
+            }
+            name.push(c);
+            chars.next();
+        }
+        name
+    };
+
+    let mut steps = Vec::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '.' if chars.peek() == Some(&'.') => {
+                chars.next();
+                let name = read_name(&mut chars);
+                match name.as_str() {
+                    "" => return Err(invalid(".. 后缺少键名")),
+                    "*" => steps.push(PathStep::Descendant(None)),
+                    _ => steps.push(PathStep::Descendant(Some(name))),
+                }
+            }
+            '.' => {
+                let name = read_name(&mut chars);
+                match name.as_str() {
+                    "" => return Err(invalid(". 后缺少键名")),
+                    "*" => steps.push(PathStep::Wildcard),
+                    _ => steps.push(PathStep::Key(name)),
+                }
+            }
+            '[' => {
+                let mut inner = String::new();
+                let mut quote: Option<char> = None;
+                let mut closed = false;
+                while let Some(c) = chars.next() {
+                    match quote {
+                        Some(q) if c == '\\' => {
+                            if let Some(next) = chars.next() {
+                                if next != q && next != '\\' {
+                                    inner.push('\\');
+                                }
+                                inner.push(next);
+                            }
+                        }
+                        Some(q) if c == q => quote = None,
+                        Some(_) => inner.push(c),
+                        None if c == '\'' || c == '"' => {
+                            quote = Some(c);
+                            // 用前缀区分带引号的键与数字下标
+                            inner.push('\u{0}');
+                        }
+                        None if c == ']' => {
+                            closed = true;
+                            break;
+                        }
+                        None => inner.push(c),
+                    }
+                }
+                if !closed {
+                    return Err(invalid("缺少 ]"));
+                }
+                let inner = inner.trim();
+                if let Some(key) = inner.strip_prefix('\u{0}') {
+                    steps.push(PathStep::Key(key.to_string()));
+                } else if inner == "*" {
+                    steps.push(PathStep::Wildcard);
+                } else {
+                    let index = inner
+                        .parse::<i64>()
+                        .map_err(|_| invalid(&format!("无法识别的下标 [{}]", inner)))?;
+                    steps.push(PathStep::Index(index));
+                }
+            }
+            _ => return Err(invalid(&format!("意外的字符 '{}'", c))),
+        }
+    }
+    Ok(steps)
+}
+
+fn key_path(parent: &str, key: &Value) -> String {
+    let key = match key {
+        Value::String(s) => s.clone(),
+        other => serde_json::to_string(other).unwrap_or_default(),
+    };
+    let simple = !key.is_empty()
+        && !key.starts_with(|c: char| c.is_ascii_digit())
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+    if simple {
+        format!("{}.{}", parent, key)
+    } else {
+        format!(
+            "{}['{}']",
+            parent,
+            key.replace('\\', "\\\\").replace('\'', "\\'")
+        )
+    }
+}
+
+fn children<'a>(path: &str, value: &'a Value) -> Vec<(String, &'a Value)> {
+    match value {
+        Value::Sequence(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (format!("{}[{}]", path, i), v))
+            .collect(),
+        Value::Mapping(map) => map.iter().map(|(k, v)| (key_path(path, k), v)).collect(),
+        Value::Tagged(tagged) => children(path, &tagged.value),
+        _ => Vec::new(),
+    }
+}
+
+fn select<'a>(path: &str, value: &'a Value, step: &PathStep, out: &mut Vec<(String, &'a Value)>) {
+    let value = match value {
+        Value::Tagged(tagged) => &tagged.value,
+        v => v,
+    };
+    match step {
+        PathStep::Key(key) => {
+            if let Value::Mapping(map) = value {
+                if let Some((k, v)) = map.iter().find(|(k, _)| k.as_str() == Some(key)) {
+                    out.push((key_path(path, k), v));
+                }
+            }
+        }
+        PathStep::Index(index) => {
+            if let Value::Sequence(items) = value {
+                let i = if *index < 0 {
+                    items.len() as i64 + index
+                } else {
+                    *index
+                };
+                if let Some(v) = usize::try_from(i).ok().and_then(|i| items.get(i)) {
+                    out.push((format!("{}[{}]", path, i), v));
+                }
+            }
+        }
+        PathStep::Wildcard => out.extend(children(path, value)),
+        PathStep::Descendant(key) => {
+            // 深度优先、文档顺序遍历自身及所有后代
+            let mut stack = vec![(path.to_string(), value)];
+            while let Some((p, v)) = stack.pop() {
+                match key {
+                    Some(key) => select(&p, v, &PathStep::Key(key.clone()), out),
+                    None => out.extend(children(&p, v)),
+                }
+                stack.extend(children(&p, v).into_iter().rev());
+            }
+        }
+    }
+}
+
+/// 校验文档，返回错误位置
+#[tauri::command]
+#[specta::specta]
+pub async fn validate_document(
+    format: DocumentFormat,
+    text: Option<String>,
+    file_path: Option<String>,
+) -> AppResult<DocumentValidation> {
+    let source = read_source(text, file_path).await?;
+    let result = tokio::task::spawn_blocking(move || parse(format, &source).map(|_| ()))
+        .await
+        .map_err(|e| AppError::internal(format!("校验失败: {}", e)))?;
+    Ok(match result {
+        Ok(()) => DocumentValidation {
+            valid: true,
+            error: None,
+        },
+        Err(error) => DocumentValidation {
+            valid: false,
+            error: Some(error),
+        },
+    })
+}
+
+/// JSON / YAML / TOML 互转；indent 只影响 JSON 输出（默认 2，0 为压缩）
+#[tauri::command]
+#[specta::specta]
+pub async fn convert_document(
+    from: DocumentFormat,
+    to: DocumentFormat,
+    text: Option<String>,
+    file_path: Option<String>,
+    indent: Option<u32>,
+    output_path: Option<String>,
+) -> AppResult<DocumentOutput> {
+    let source = read_source(text, file_path).await?;
+    let indent = indent.unwrap_or(2) as usize;
+    let output = tokio::task::spawn_blocking(move || -> AppResult<String> {
+        let value = parse(from, &source)?;
+        render(to, &value, indent)
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("转换失败: {}", e)))??;
+    write_output(output, output_path).await
+}
+
+/// JSON 格式化（indent 为 0 时压缩），保留键顺序与数字原文
+#[tauri::command]
+#[specta::specta]
+pub async fn format_json(
+    text: Option<String>,
+    file_path: Option<String>,
+    indent: Option<u32>,
+    output_path: Option<String>,
+) -> AppResult<DocumentOutput> {
+    let source = read_source(text, file_path).await?;
+    let indent = indent.unwrap_or(2) as usize;
+    let output = tokio::task::spawn_blocking(move || -> AppResult<String> {
+        serde_json::from_str::<serde::de::IgnoredAny>(&source)
+            .map_err(|e| DocumentError::at(e.to_string(), e.line(), e.column()))?;
+        let indent = (indent > 0).then(|| " ".repeat(indent));
+        Ok(reformat_json(&source, indent.as_deref()))
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("格式化失败: {}", e)))??;
+    write_output(output, output_path).await
+}
+
+/// JSONPath 查询，支持 $ . [n] [-n] ['key'] * ..key ..*；YAML / TOML 文档同样可查
+#[tauri::command]
+#[specta::specta]
+pub async fn query_json_path(
+    format: DocumentFormat,
+    expression: String,
+    text: Option<String>,
+    file_path: Option<String>,
+) -> AppResult<Vec<JsonPathMatch>> {
+    let steps = parse_json_path(&expression)?;
+    let source = read_source(text, file_path).await?;
+    tokio::task::spawn_blocking(move || -> AppResult<Vec<JsonPathMatch>> {
+        let root = parse(format, &source)?;
+        let mut current = vec![("$".to_string(), &root)];
+        for step in &steps {
+            let mut next = Vec::new();
+            for (path, value) in &current {
+                select(path, value, step, &mut next);
+            }
+            current = next;
+        }
+        current
+            .into_iter()
+            .take(MAX_QUERY_RESULTS)
+            .map(|(path, value)| {
+                let value = serde_json::to_string_pretty(&value)
+                    .map_err(|e| AppError::invalid(format!("无法输出为 JSON: {}", e)))?;
+                Ok(JsonPathMatch { path, value })
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("查询失败: {}", e)))?
+}
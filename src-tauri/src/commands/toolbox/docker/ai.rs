@@ -101,7 +101,7 @@ pub(super) async fn generate_dockerfile_with_ai(
         "{}/chat/completions",
         provider.base_url.trim_end_matches('/')
     );
-    let client = reqwest::Client::builder()
+    let client = crate::http_client::builder()
         .timeout(Duration::from_secs(120))
         .build()
         .map_err(|e| crate::error::AppError::from(e.to_string()))?;
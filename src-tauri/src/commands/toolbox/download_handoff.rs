@@ -0,0 +1,418 @@
+// 浏览器下载交接 - 浏览器扩展或其他应用把 URL（含文件名、请求头）交给下载器
+//
+// 两个入口，都只在设置中启用后生效：
+//   - 链接：codeshelf-download://add?url=<编码后的 URL>&filename=<文件名>&header=<Name: Value>
+//     （header 可重复；Windows/Linux 启用时注册协议，macOS 由安装包声明）
+//   - 本地端点（仅监听 127.0.0.1）：
+//       GET  /health     探测是否在运行
+//       POST /downloads  { url, fileName?, saveDir?, headers? }，需带 X-CodeShelf-Token 头
+// 收到的任务推送 `download-handoff` 事件。
+//
+// 另有不受设置开关限制的批量入口 import_download_urls：粘贴多行 URL（可用 Tab 分隔文件名）一次加入下载队列。
+
+use super::downloader::start_download;
+use super::local_endpoint::LocalEndpoint;
+use super::DownloadConfig;
+use crate::commands::settings::load_app_settings;
+use crate::error::{AppError, AppResult};
+use crate::storage::{documents, secrets, AppSettings};
+use axum::{
+    extract::State,
+    http::{HeaderMap, HeaderValue, Method, StatusCode},
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::Path;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_deep_link::DeepLinkExt;
+use tower_http::cors::CorsLayer;
+
+pub const SCHEME: &str = "codeshelf-download";
+const TOKEN_HEADER: &str = "x-codeshelf-token";
+
+static ENDPOINT: Lazy<LocalEndpoint> = Lazy::new(|| LocalEndpoint::new("下载交接端点"));
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadHandoffStatus {
+    pub enabled: bool,
+    pub running: bool,
+    pub port: u16,
+    /// 端点地址（运行中才有）
+    pub endpoint: Option<String>,
+    pub token: Option<String>,
+    /// 系统是否已把 codeshelf-download:// 交给本应用
+    pub protocol_registered: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadHandoffEvent {
+    pub task_id: String,
+    pub url: String,
+    pub file_name: Option<String>,
+    /// "link" | "endpoint"
+    pub source: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HandoffRequest {
+    url: String,
+    file_name: Option<String>,
+    save_dir: Option<String>,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+}
+
+fn to_config(
+    url: String,
+    file_name: Option<String>,
+    save_dir: Option<String>,
+    headers: HashMap<String, String>,
+) -> AppResult<DownloadConfig> {
+    let parsed =
+        url::Url::parse(&url).map_err(|e| AppError::invalid(format!("无效的 URL: {}", e)))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(AppError::invalid("只支持 http/https 下载"));
+    }
+    // 只取文件名部分，防止通过 ../ 写到下载目录之外
+    let file_name = file_name
+        .as_deref()
+        .and_then(|n| Path::new(n.trim()).file_name())
+        .map(|n| n.to_string_lossy().to_string())
+        .filter(|n| !n.is_empty());
+
+    Ok(DownloadConfig {
+        url,
+        save_dir,
+        file_name,
+        max_retries: None,
+        headers: Some(headers),
+        mirrors: Vec::new(),
+        stall_timeout_secs: None,
+    })
+}
+
+fn parse_link(link: &str) -> AppResult<DownloadConfig> {
+    let parsed =
+        url::Url::parse(link).map_err(|e| AppError::invalid(format!("无效的下载链接: {}", e)))?;
+    if parsed.scheme() != SCHEME {
+        return Err(AppError::invalid(format!("不是 {}:// 链接", SCHEME)));
+    }
+
+    let mut url = None;
+    let mut file_name = None;
+    let mut headers = HashMap::new();
+    for (key, value) in parsed.query_pairs() {
+        match key.as_ref() {
+            "url" => url = Some(value.to_string()),
+            "filename" | "fileName" => file_name = Some(value.to_string()),
+            "header" => {
+                if let Some((name, v)) = value.split_once(':') {
+                    headers.insert(name.trim().to_string(), v.trim().to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    let url = url.ok_or("下载链接缺少 url 参数")?;
+    to_config(url, file_name, None, headers)
+}
+
+async fn enqueue(app: &AppHandle, config: DownloadConfig, source: &str) -> AppResult<String> {
+    let url = config.url.clone();
+    let file_name = config.file_name.clone();
+    let task_id = start_download(config).await?;
+    let _ = app.emit(
+        "download-handoff",
+        DownloadHandoffEvent {
+            task_id: task_id.clone(),
+            url,
+            file_name,
+            source: source.to_string(),
+        },
+    );
+    Ok(task_id)
+}
+
+/// 处理 codeshelf-download:// 链接（启动参数、单实例转发、系统 open-url 事件），其他参数忽略
+pub fn handle_links(app: &AppHandle, args: Vec<String>) {
+    let prefix = format!("{}:", SCHEME);
+    let links: Vec<String> = args
+        .into_iter()
+        .filter(|a| a.starts_with(&prefix))
+        .collect();
+    if links.is_empty() {
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let enabled = load_app_settings()
+            .await
+            .map(|s| s.download_handoff_enabled)
+            .unwrap_or(false);
+        if !enabled {
+            log::warn!("下载交接未启用，忽略 {} 个链接", links.len());
+            return;
+        }
+        for link in links {
+            let result = match parse_link(&link) {
+                Ok(config) => enqueue(&app, config, "link").await.map(|_| ()),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                log::warn!("处理下载链接失败: {}", e);
+            }
+        }
+    });
+}
+
+/// 启动时调用：监听系统 open-url 事件、处理启动参数中的链接，并按设置启动端点
+pub fn init(app: &AppHandle) {
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        handle_links(
+            &handle,
+            event.urls().iter().map(|u| u.to_string()).collect(),
+        );
+    });
+    handle_links(app, std::env::args().skip(1).collect());
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = match load_app_settings().await {
+            Ok(settings) => apply_settings(&app, &settings).await.map(|_| ()),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            log::error!("下载交接初始化失败: {}", e);
+        }
+    });
+}
+
+#[cfg(any(windows, target_os = "linux"))]
+fn protocol_registered(app: &AppHandle) -> bool {
+    app.deep_link().is_registered(SCHEME).unwrap_or(false)
+}
+
+/// macOS 的协议由安装包 Info.plist 声明，始终已注册
+#[cfg(not(any(windows, target_os = "linux")))]
+fn protocol_registered(_app: &AppHandle) -> bool {
+    true
+}
+
+#[cfg(any(windows, target_os = "linux"))]
+fn set_protocol_registered(app: &AppHandle, register: bool) {
+    if protocol_registered(app) == register {
+        return;
+    }
+    let result = if register {
+        app.deep_link().register(SCHEME)
+    } else {
+        app.deep_link().unregister(SCHEME)
+    };
+    if let Err(e) = result {
+        log::warn!("切换 {}:// 协议注册失败: {}", SCHEME, e);
+    }
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+fn set_protocol_registered(_app: &AppHandle, _register: bool) {}
+
+/// 按设置注册/注销协议并启停本地端点（保存设置后调用）
+pub async fn apply_settings(
+    app: &AppHandle,
+    settings: &AppSettings,
+) -> AppResult<DownloadHandoffStatus> {
+    set_protocol_registered(app, settings.download_handoff_enabled);
+    if settings.download_handoff_enabled {
+        ENDPOINT
+            .start(settings.download_handoff_port, router(app.clone()))
+            .await?;
+    } else {
+        ENDPOINT.stop().await;
+    }
+    Ok(status(app, settings).await)
+}
+
+async fn status(app: &AppHandle, settings: &AppSettings) -> DownloadHandoffStatus {
+    let running_port = ENDPOINT.running_port().await;
+    DownloadHandoffStatus {
+        enabled: settings.download_handoff_enabled,
+        running: running_port.is_some(),
+        port: settings.download_handoff_port,
+        endpoint: running_port.map(|p| format!("http://127.0.0.1:{}/downloads", p)),
+        token: settings.download_handoff_token.clone(),
+        protocol_registered: protocol_registered(app),
+    }
+}
+
+fn router(app: AppHandle) -> Router {
+    let cors = CorsLayer::new()
+        .allow_origin(HeaderValue::from_static("*"))
+        .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
+        .allow_headers(tower_http::cors::Any);
+
+    Router::new()
+        .route("/health", get(http_health))
+        .route("/downloads", post(http_add_download))
+        .layer(cors)
+        .with_state(app)
+}
+
+async fn http_health() -> impl IntoResponse {
+    Json(json!({ "ok": true, "app": "codeshelf" }))
+}
+
+async fn http_add_download(
+    State(app): State<AppHandle>,
+    headers: HeaderMap,
+    Json(req): Json<HandoffRequest>,
+) -> impl IntoResponse {
+    // 每次读取设置，重新生成令牌后立即生效
+    let expected = load_app_settings()
+        .await
+        .ok()
+        .filter(|s| s.download_handoff_enabled)
+        .and_then(|s| s.download_handoff_token);
+    let provided = headers.get(TOKEN_HEADER).and_then(|v| v.to_str().ok());
+    if expected.is_none() || provided != expected.as_deref() {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(json!({ "ok": false, "error": "令牌无效" })),
+        );
+    }
+
+    let result = match to_config(req.url, req.file_name, req.save_dir, req.headers) {
+        Ok(config) => enqueue(&app, config, "endpoint").await,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "ok": false, "error": e.to_string() })),
+            )
+        }
+    };
+    match result {
+        Ok(task_id) => (
+            StatusCode::OK,
+            Json(json!({ "ok": true, "taskId": task_id })),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "ok": false, "error": e.to_string() })),
+        ),
+    }
+}
+
+/// 批量导入中无效的一行
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadImportError {
+    /// 行号（从 1 开始）
+    pub line: u32,
+    pub text: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadImportResult {
+    /// 已创建的下载任务 id（按行顺序）
+    pub task_ids: Vec<String>,
+    pub errors: Vec<DownloadImportError>,
+    /// 与前面行重复而跳过的 URL 数
+    pub duplicates: u32,
+}
+
+/// 解析粘贴的 URL 列表：每行一个，可用 Tab 分隔指定文件名；空行和 # 开头的行忽略。
+/// 先整体校验，再把有效行逐个加入下载队列；无效行与加入队列失败的行逐条返回原因
+#[tauri::command]
+#[specta::specta]
+pub async fn import_download_urls(
+    text: String,
+    save_dir: Option<String>,
+) -> AppResult<DownloadImportResult> {
+    let mut configs = Vec::new();
+    let mut errors = Vec::new();
+    let mut seen: Vec<String> = Vec::new();
+    let mut duplicates = 0;
+    for (index, raw) in text.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (url, file_name) = match line.split_once('\t') {
+            Some((url, name)) => (url.trim(), Some(name.trim().to_string())),
+            None => (line, None),
+        };
+        if seen.iter().any(|u| u == url) {
+            duplicates += 1;
+            continue;
+        }
+        match to_config(url.to_string(), file_name, save_dir.clone(), HashMap::new()) {
+            Ok(config) => {
+                seen.push(url.to_string());
+                configs.push((index as u32 + 1, line.to_string(), config));
+            }
+            Err(e) => errors.push(DownloadImportError {
+                line: index as u32 + 1,
+                text: line.to_string(),
+                error: e.to_string(),
+            }),
+        }
+    }
+    if configs.is_empty() && errors.is_empty() {
+        return Err(AppError::invalid("没有可导入的 URL"));
+    }
+
+    let mut task_ids = Vec::with_capacity(configs.len());
+    for (line, text, config) in configs {
+        // 单个任务失败不影响其余行
+        match start_download(config).await {
+            Ok(id) => task_ids.push(id),
+            Err(e) => errors.push(DownloadImportError {
+                line,
+                text,
+                error: e.to_string(),
+            }),
+        }
+    }
+    errors.sort_by_key(|e| e.line);
+    log::info!(
+        "批量导入下载：{} 个任务，{} 行无效，{} 个重复",
+        task_ids.len(),
+        errors.len(),
+        duplicates
+    );
+    Ok(DownloadImportResult {
+        task_ids,
+        errors,
+        duplicates,
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_download_handoff_status(app: AppHandle) -> AppResult<DownloadHandoffStatus> {
+    let settings = load_app_settings().await?;
+    Ok(status(&app, &settings).await)
+}
+
+/// 重新生成端点令牌（浏览器扩展需同步更新）
+#[tauri::command]
+#[specta::specta]
+pub async fn regenerate_download_handoff_token() -> AppResult<String> {
+    let mut settings = load_app_settings().await?;
+    let token = secrets::new_token()?;
+    settings.download_handoff_token = Some(token.clone());
+
+    documents::save(&documents::APP_SETTINGS, &settings)?;
+    Ok(token)
+}
@@ -0,0 +1,231 @@
+// 下载历史统计 - 记录每次完成的下载（大小、耗时、平均速度、来源主机），按天 / 按主机汇总
+//
+// 只记录本次运行实际传输的字节：暂停后恢复的任务，耗时与速度按恢复后的这一段计算。
+// 历史最多保留 MAX_HISTORY 条，超出时丢弃最早的记录。
+
+use crate::error::AppResult;
+use crate::storage::config::StorageConfig;
+use crate::storage::PersistedStore;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+const MAX_HISTORY: usize = 2000;
+/// 判定慢主机：至少有这么多次下载
+const SLOW_HOST_MIN_DOWNLOADS: u32 = 3;
+/// 判定慢主机：平均速度低于全局平均的比例
+const SLOW_HOST_RATIO: f64 = 0.5;
+
+/// 一次完成的下载
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadHistoryEntry {
+    pub id: String,
+    pub task_id: String,
+    pub file_name: String,
+    pub url: String,
+    /// 实际下载所用源的主机名
+    pub host: String,
+    /// 文件大小（字节）
+    pub size: u64,
+    /// 本次传输的字节数
+    pub transferred: u64,
+    pub duration_ms: u64,
+    /// 平均速度（字节/秒）
+    pub average_speed: u64,
+    /// 完成时间（毫秒时间戳）
+    pub completed_at: i64,
+}
+
+/// 按天汇总
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadDailyStats {
+    /// YYYY-MM-DD（本地时间）
+    pub date: String,
+    pub downloads: u32,
+    pub bytes: u64,
+    pub average_speed: u64,
+}
+
+/// 按主机汇总
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadHostStats {
+    pub host: String,
+    pub downloads: u32,
+    pub bytes: u64,
+    pub average_speed: u64,
+    /// 多次下载平均速度明显低于整体水平
+    pub slow: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadHistoryStats {
+    pub total_downloads: u32,
+    pub total_bytes: u64,
+    pub average_speed: u64,
+    /// 按日期升序
+    pub daily: Vec<DownloadDailyStats>,
+    /// 按下载量降序
+    pub hosts: Vec<DownloadHostStats>,
+}
+
+static HISTORY: Lazy<PersistedStore<DownloadHistoryEntry>> = Lazy::new(|| {
+    PersistedStore::new(
+        "downloadHistory",
+        "下载历史",
+        StorageConfig::download_history_file,
+        |e| e.id.clone(),
+    )
+});
+
+/// 从 URL 提取主机名
+fn host_of(url: &str) -> String {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_string()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// 记录一次完成的下载（由下载器在任务完成时调用）
+pub(crate) async fn record(
+    task_id: &str,
+    file_name: &str,
+    url: &str,
+    size: u64,
+    transferred: u64,
+    duration_ms: u64,
+) {
+    HISTORY.ensure_loaded().await;
+    let entry = DownloadHistoryEntry {
+        id: super::generate_id(),
+        task_id: task_id.to_string(),
+        file_name: file_name.to_string(),
+        url: url.to_string(),
+        host: host_of(url),
+        size,
+        transferred,
+        duration_ms,
+        average_speed: speed(transferred, duration_ms),
+        completed_at: chrono::Utc::now().timestamp_millis(),
+    };
+    {
+        let mut history = HISTORY.lock().await;
+        history.insert(entry.id.clone(), entry);
+        if history.len() > MAX_HISTORY {
+            let mut entries: Vec<(i64, String)> = history
+                .values()
+                .map(|e| (e.completed_at, e.id.clone()))
+                .collect();
+            entries.sort();
+            let excess = history.len() - MAX_HISTORY;
+            for (_, id) in entries.into_iter().take(excess) {
+                history.remove(&id);
+            }
+        }
+    }
+    if let Err(e) = HISTORY.save().await {
+        log::error!("保存下载历史失败: {}", e);
+    }
+}
+
+fn speed(bytes: u64, duration_ms: u64) -> u64 {
+    bytes * 1000 / duration_ms.max(1)
+}
+
+/// 获取下载历史（按完成时间倒序）
+#[tauri::command]
+#[specta::specta]
+pub async fn get_download_history(limit: Option<usize>) -> AppResult<Vec<DownloadHistoryEntry>> {
+    HISTORY.ensure_loaded().await;
+    let mut entries: Vec<DownloadHistoryEntry> = HISTORY.lock().await.values().cloned().collect();
+    entries.sort_by_key(|e| std::cmp::Reverse(e.completed_at));
+    entries.truncate(limit.unwrap_or(200));
+    Ok(entries)
+}
+
+/// 汇总下载历史；days 为空时统计全部记录
+#[tauri::command]
+#[specta::specta]
+pub async fn get_download_history_stats(days: Option<u32>) -> AppResult<DownloadHistoryStats> {
+    HISTORY.ensure_loaded().await;
+    let since = days
+        .map(|d| chrono::Utc::now().timestamp_millis() - d as i64 * 86_400_000)
+        .unwrap_or(i64::MIN);
+    let history = HISTORY.lock().await;
+    let entries: Vec<&DownloadHistoryEntry> = history
+        .values()
+        .filter(|e| e.completed_at >= since)
+        .collect();
+
+    // (次数, 总字节, 传输字节, 耗时)
+    let mut daily: BTreeMap<String, (u32, u64, u64, u64)> = BTreeMap::new();
+    let mut hosts: HashMap<String, (u32, u64, u64, u64)> = HashMap::new();
+    let (mut total_transferred, mut total_duration) = (0u64, 0u64);
+    for e in &entries {
+        let date = chrono::DateTime::from_timestamp_millis(e.completed_at)
+            .map(|t| {
+                t.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d")
+                    .to_string()
+            })
+            .unwrap_or_default();
+        for slot in [
+            daily.entry(date).or_default(),
+            hosts.entry(e.host.clone()).or_default(),
+        ] {
+            slot.0 += 1;
+            slot.1 += e.size;
+            slot.2 += e.transferred;
+            slot.3 += e.duration_ms;
+        }
+        total_transferred += e.transferred;
+        total_duration += e.duration_ms;
+    }
+
+    let average_speed = speed(total_transferred, total_duration);
+    let mut hosts: Vec<DownloadHostStats> = hosts
+        .into_iter()
+        .map(|(host, (downloads, bytes, transferred, duration))| {
+            let host_speed = speed(transferred, duration);
+            DownloadHostStats {
+                host,
+                downloads,
+                bytes,
+                average_speed: host_speed,
+                slow: downloads >= SLOW_HOST_MIN_DOWNLOADS
+                    && (host_speed as f64) < average_speed as f64 * SLOW_HOST_RATIO,
+            }
+        })
+        .collect();
+    hosts.sort_by_key(|h| std::cmp::Reverse(h.bytes));
+
+    Ok(DownloadHistoryStats {
+        total_downloads: entries.len() as u32,
+        total_bytes: entries.iter().map(|e| e.size).sum(),
+        average_speed,
+        daily: daily
+            .into_iter()
+            .map(
+                |(date, (downloads, bytes, transferred, duration))| DownloadDailyStats {
+                    date,
+                    downloads,
+                    bytes,
+                    average_speed: speed(transferred, duration),
+                },
+            )
+            .collect(),
+        hosts,
+    })
+}
+
+/// 清空下载历史
+#[tauri::command]
+#[specta::specta]
+pub async fn clear_download_history() -> AppResult<()> {
+    HISTORY.ensure_loaded().await;
+    HISTORY.lock().await.clear();
+    HISTORY.save().await
+}
@@ -1,99 +1,118 @@
 // 文件下载模块 - 支持断点续传、重试机制、下载队列管理
+//
+// 配置了镜像时：开始前对所有源做一次小范围（Range）请求测速，按速度排序后从最快的源下载；
+// 下载中出错或超过停滞时长（默认 DEFAULT_STALL_TIMEOUT，可按任务调整）没有收到数据时切换到下一个源，
+// 已下载部分通过 Range 续传。凭据类请求头与托管平台令牌只发给原始地址，不发给其他来源的镜像。
 
-use super::{current_time, generate_id, DownloadConfig, DownloadTask};
+use super::{current_time, generate_id, DownloadConfig, DownloadScanResult, DownloadTask};
 use crate::error::AppResult;
-use crate::storage;
-use once_cell::sync::Lazy;
+use crate::storage::config::StorageConfig;
+use crate::storage::NotificationAction;
+use crate::storage::PersistedStore;
+use once_cell::sync::{Lazy, OnceCell};
 use std::collections::HashMap;
 use std::fs::{self, File, OpenOptions};
 use std::io::Write;
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use tauri::AppHandle;
 use tokio::sync::Mutex;
 use tokio::time::{sleep, Duration};
 
 /// 下载任务存储 - 延迟初始化
-static DOWNLOAD_TASKS: Lazy<Arc<Mutex<HashMap<String, DownloadTask>>>> =
-    Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
-
-/// 是否已从文件加载
-static TASKS_LOADED: Lazy<Arc<Mutex<bool>>> = Lazy::new(|| Arc::new(Mutex::new(false)));
+static DOWNLOAD_TASKS: Lazy<PersistedStore<DownloadTask>> = Lazy::new(|| {
+    PersistedStore::new(
+        "downloadTasks",
+        "下载任务",
+        StorageConfig::download_tasks_file,
+        |t: &DownloadTask| t.id.clone(),
+    )
+    .on_load(|t| {
+        // 重启后，下载中的任务变为暂停
+        if t.status == "downloading" {
+            t.status = "paused".to_string();
+        }
+    })
+});
+
+/// 镜像测速时读取的字节数
+const PROBE_BYTES: u64 = 64 * 1024;
+/// 单个源测速超时
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+/// 超过该时长没有收到数据视为停滞（任务未单独设置时）
+const DEFAULT_STALL_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// 凭据类请求头（小写），只发给与原始地址同源的下载源
+const CREDENTIAL_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "private-token",
+];
 
 /// 下载取消标志
-static DOWNLOAD_CANCELLED: Lazy<Arc<Mutex<HashMap<String, AtomicBool>>>> =
-    Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
+/// 用于发送下载完成 / 失败通知
+static APP: OnceCell<AppHandle> = OnceCell::new();
 
-/// 确保下载任务已从文件加载
-async fn ensure_tasks_loaded() {
-    let mut loaded = TASKS_LOADED.lock().await;
-    if !*loaded {
-        match load_tasks_from_file() {
-            Ok(tasks) => {
-                let mut tasks_map = DOWNLOAD_TASKS.lock().await;
-                *tasks_map = tasks;
-                *loaded = true;
-            }
-            Err(e) => {
-                log::warn!("加载下载任务失败，将在下次重试: {}", e);
-            }
-        }
-    }
+pub fn init(app: &AppHandle) {
+    let _ = APP.set(app.clone());
 }
 
-/// 从文件加载下载任务
-fn load_tasks_from_file() -> AppResult<HashMap<String, DownloadTask>> {
-    let config = storage::get_storage_config()?;
-    let path = config.download_tasks_file();
-
-    log::info!("加载下载任务: {:?}", path);
-
-    if !path.exists() {
-        log::info!("下载任务文件不存在，返回空列表");
-        return Ok(HashMap::new());
-    }
-
-    let content = fs::read_to_string(&path)
-        .map_err(|e| crate::error::AppError::from(format!("读取下载任务失败: {}", e)))?;
-
-    // 直接解析为任务数组
-    let tasks: Vec<DownloadTask> = serde_json::from_str(&content).unwrap_or_default();
+/// 下载结束时发送带操作的通知：完成可打开所在文件夹，失败可重试
+async fn notify_finished(task_id: &str) {
+    let (Some(app), Some(task)) = (APP.get(), task_snapshot(task_id).await) else {
+        return;
+    };
+    let folder = Path::new(&task.save_path)
+        .parent()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let (severity, title, message, action) = if task.status == "completed" {
+        (
+            "success",
+            "下载完成",
+            task.file_name.clone(),
+            NotificationAction {
+                kind: "open_folder".to_string(),
+                label: "打开文件夹".to_string(),
+                target: folder,
+            },
+        )
+    } else {
+        (
+            "error",
+            "下载失败",
+            format!("{}：{}", task.file_name, task.error.unwrap_or_default()),
+            NotificationAction {
+                kind: "retry_download".to_string(),
+                label: "重试".to_string(),
+                target: task.id.clone(),
+            },
+        )
+    };
+    crate::commands::settings::push_notification_with_actions(
+        app,
+        "download",
+        severity,
+        title,
+        &message,
+        vec![action],
+    )
+    .await;
+}
 
-    let result: HashMap<String, DownloadTask> = tasks
-        .into_iter()
-        .map(|mut t| {
-            // 重启后，下载中的任务变为暂停
-            if t.status == "downloading" {
-                t.status = "paused".to_string();
-            }
-            (t.id.clone(), t)
-        })
-        .collect();
+static DOWNLOAD_CANCELLED: Lazy<Arc<Mutex<HashMap<String, AtomicBool>>>> =
+    Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
 
-    log::info!("共加载 {} 个下载任务", result.len());
-    Ok(result)
+/// 确保下载任务已从文件加载
+pub(crate) async fn ensure_tasks_loaded() {
+    DOWNLOAD_TASKS.ensure_loaded().await;
 }
 
-/// 保存下载任务到文件
+/// 保存下载任务到文件（防抖合并写盘）
 async fn save_tasks_to_file() -> AppResult<()> {
-    let config = storage::get_storage_config()?;
-    config.ensure_dirs()?;
-
-    let tasks = DOWNLOAD_TASKS.lock().await;
-    let tasks_vec: Vec<&DownloadTask> = tasks.values().collect();
-
-    // 直接保存为任务数组
-    let content = serde_json::to_string(&tasks_vec)
-        .map_err(|e| crate::error::AppError::from(format!("序列化下载任务失败: {}", e)))?;
-
-    let path = config.download_tasks_file();
-    log::info!("保存下载任务到: {:?}", path);
-
-    fs::write(&path, content)
-        .map_err(|e| crate::error::AppError::from(format!("写入下载任务失败: {}", e)))?;
-
-    log::info!("下载任务保存成功，共 {} 个任务", tasks.len());
-    Ok(())
+    DOWNLOAD_TASKS.save().await
 }
 
 /// 默认下载目录
@@ -128,6 +147,14 @@ fn extract_filename(url: &str) -> String {
 #[tauri::command]
 #[specta::specta]
 pub async fn start_download(config: DownloadConfig) -> AppResult<String> {
+    enqueue_download(config, None).await
+}
+
+/// 创建并启动下载任务；auth_host 为令牌所属主机，每次请求时按主机读取令牌（见 release_assets）
+pub(crate) async fn enqueue_download(
+    config: DownloadConfig,
+    auth_host: Option<String>,
+) -> AppResult<String> {
     ensure_tasks_loaded().await;
 
     let task_id = generate_id();
@@ -138,6 +165,7 @@ pub async fn start_download(config: DownloadConfig) -> AppResult<String> {
         .file_name
         .unwrap_or_else(|| extract_filename(&config.url));
     let save_path = Path::new(&save_dir).join(&file_name);
+    let mirrors = normalize_mirrors(&config.url, config.mirrors);
 
     // 创建任务
     let task = DownloadTask {
@@ -152,6 +180,12 @@ pub async fn start_download(config: DownloadConfig) -> AppResult<String> {
         error: None,
         created_at: current_time(),
         updated_at: current_time(),
+        headers: config.headers.clone().unwrap_or_default(),
+        mirrors: mirrors.clone(),
+        active_url: None,
+        scan: None,
+        auth_host,
+        stall_timeout_secs: config.stall_timeout_secs,
     };
 
     // 保存任务
@@ -173,28 +207,170 @@ pub async fn start_download(config: DownloadConfig) -> AppResult<String> {
 
     // 启动下载任务
     let id = task_id.clone();
-    let url = config.url.clone();
+    let sources: Vec<String> = std::iter::once(config.url.clone()).chain(mirrors).collect();
     let path = save_path.to_string_lossy().to_string();
     let max_retries = config.max_retries.unwrap_or(3);
+    let headers = config.headers.unwrap_or_default();
 
     tokio::spawn(async move {
-        download_with_retry(&id, &url, &path, max_retries).await;
+        download_with_retry(&id, &sources, &path, &headers, max_retries).await;
     });
 
     Ok(task_id)
 }
 
-/// 带重试的下载
-async fn download_with_retry(task_id: &str, url: &str, save_path: &str, max_retries: u32) {
+/// 去掉空白、重复以及与主地址相同的镜像
+fn normalize_mirrors(url: &str, mirrors: Vec<String>) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+    for mirror in mirrors {
+        let mirror = mirror.trim().to_string();
+        if !mirror.is_empty() && mirror != url && !result.contains(&mirror) {
+            result.push(mirror);
+        }
+    }
+    result
+}
+
+/// 请求 url 时使用的请求头：与原始地址不同源（协议、主机、端口）的镜像去掉凭据类请求头
+fn headers_for_source(
+    url: &str,
+    original: &str,
+    headers: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let same_origin = match (reqwest::Url::parse(url), reqwest::Url::parse(original)) {
+        (Ok(a), Ok(b)) => a.origin() == b.origin(),
+        _ => false,
+    };
+    headers
+        .iter()
+        .filter(|(k, _)| {
+            same_origin || !CREDENTIAL_HEADERS.contains(&k.to_ascii_lowercase().as_str())
+        })
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect()
+}
+
+/// 测速：读取前 PROBE_BYTES 字节，返回字节/秒；失败或超时返回 None
+async fn probe_source(
+    client: &reqwest::Client,
+    url: &str,
+    headers: &HashMap<String, String>,
+) -> Option<f64> {
+    use futures::StreamExt;
+
+    let mut request = client
+        .get(url)
+        .header("Range", format!("bytes=0-{}", PROBE_BYTES - 1));
+    for (k, v) in headers {
+        request = request.header(k.as_str(), v.as_str());
+    }
+    let started = std::time::Instant::now();
+    let received = tokio::time::timeout(PROBE_TIMEOUT, async {
+        let response = request.send().await.ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        let mut stream = response.bytes_stream();
+        let mut received = 0u64;
+        while received < PROBE_BYTES {
+            match stream.next().await {
+                Some(Ok(chunk)) => received += chunk.len() as u64,
+                Some(Err(_)) => return None,
+                None => break,
+            }
+        }
+        Some(received)
+    })
+    .await
+    .ok()??;
+    Some(received as f64 / started.elapsed().as_secs_f64().max(0.001))
+}
+
+/// 并发测速，按速度从快到慢排序；测速失败的源排在最后并保持原顺序。sources[0] 为原始地址
+async fn rank_sources(sources: &[String], headers: &HashMap<String, String>) -> Vec<String> {
+    let Ok(client) = crate::http_client::builder().build() else {
+        return sources.to_vec();
+    };
+    let client = &client;
+    let speeds = futures::future::join_all(sources.iter().map(|url| {
+        let headers = headers_for_source(url, &sources[0], headers);
+        async move { probe_source(client, url, &headers).await }
+    }))
+    .await;
+
+    let mut ranked: Vec<(&String, Option<f64>)> = sources.iter().zip(speeds).collect();
+    ranked.sort_by(|a, b| match (a.1, b.1) {
+        (Some(x), Some(y)) => y.total_cmp(&x),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+    for (url, speed) in &ranked {
+        match speed {
+            Some(s) => log::info!("镜像测速 {}: {:.1} KB/s", url, s / 1024.0),
+            None => log::warn!("镜像测速失败: {}", url),
+        }
+    }
+    ranked.into_iter().map(|(url, _)| url.clone()).collect()
+}
+
+/// 记录当前使用的下载源
+async fn set_active_url(task_id: &str, url: &str) {
+    let mut tasks = DOWNLOAD_TASKS.lock().await;
+    if let Some(task) = tasks.get_mut(task_id) {
+        task.active_url = Some(url.to_string());
+    }
+}
+
+/// 带重试的下载；有多个源时先测速排序，失败后切换到下一个源
+async fn download_with_retry(
+    task_id: &str,
+    sources: &[String],
+    save_path: &str,
+    headers: &HashMap<String, String>,
+    max_retries: u32,
+) {
     let mut retries = 0;
+    let original = sources[0].clone();
+    let sources = if sources.len() > 1 {
+        update_task_status(task_id, "downloading", None).await;
+        rank_sources(sources, headers).await
+    } else {
+        sources.to_vec()
+    };
+    let mut index = 0;
+    let started = std::time::Instant::now();
+    let initial_size = task_snapshot(task_id)
+        .await
+        .map(|t| t.downloaded_size)
+        .unwrap_or(0);
 
     loop {
         // 更新状态为下载中
         update_task_status(task_id, "downloading", None).await;
 
-        match download_file(task_id, url, save_path).await {
+        let url = &sources[index % sources.len()];
+        if sources.len() > 1 {
+            set_active_url(task_id, url).await;
+        }
+
+        let headers = headers_for_source(url, &original, headers);
+        match download_file(task_id, url, save_path, &headers).await {
             Ok(_) => {
                 update_task_status(task_id, "completed", None).await;
+                if let Some(task) = task_snapshot(task_id).await {
+                    super::download_history::record(
+                        task_id,
+                        &task.file_name,
+                        url,
+                        task.total_size,
+                        task.downloaded_size.saturating_sub(initial_size),
+                        started.elapsed().as_millis() as u64,
+                    )
+                    .await;
+                }
+                auto_scan(task_id).await;
+                notify_finished(task_id).await;
                 return;
             }
             Err(e) => {
@@ -207,20 +383,36 @@ async fn download_with_retry(task_id: &str, url: &str, save_path: &str, max_retr
                 retries += 1;
                 if retries > max_retries {
                     update_task_status(task_id, "failed", Some(e.to_string())).await;
+                    notify_finished(task_id).await;
                     return;
                 }
 
-                // 指数退避重试
-                let delay = Duration::from_secs(2u64.pow(retries));
-                sleep(delay).await;
+                // 有镜像时立即切换到下一个源续传，否则指数退避重试
+                if sources.len() > 1 {
+                    index += 1;
+                    log::warn!(
+                        "下载源 {} 失败（{}），切换到 {}",
+                        url,
+                        e,
+                        sources[index % sources.len()]
+                    );
+                } else {
+                    let delay = Duration::from_secs(2u64.pow(retries));
+                    sleep(delay).await;
+                }
             }
         }
     }
 }
 
 /// 执行下载
-async fn download_file(task_id: &str, url: &str, save_path: &str) -> AppResult<()> {
-    let client = reqwest::Client::builder()
+async fn download_file(
+    task_id: &str,
+    url: &str,
+    save_path: &str,
+    headers: &HashMap<String, String>,
+) -> AppResult<()> {
+    let client = crate::http_client::builder()
         .timeout(Duration::from_secs(300))
         .build()
         .map_err(|e| crate::error::AppError::from(format!("创建 HTTP 客户端失败: {}", e)))?;
@@ -232,9 +424,30 @@ async fn download_file(task_id: &str, url: &str, save_path: &str) -> AppResult<(
         0
     };
 
+    // 托管平台令牌只在请求时读取，不写进任务的请求头；镜像地址不带令牌
+    let task = task_snapshot(task_id).await;
+    let mut headers = headers.clone();
+    if let Some(host) = task
+        .as_ref()
+        .filter(|t| t.url == url)
+        .and_then(|t| t.auth_host.as_deref())
+    {
+        headers.extend(super::release_assets::auth_headers_for_host(host).await);
+    }
+    let headers = &headers;
+    let stall_timeout = match task.and_then(|t| t.stall_timeout_secs) {
+        Some(0) => None,
+        Some(secs) => Some(Duration::from_secs(secs.into())),
+        None => Some(DEFAULT_STALL_TIMEOUT),
+    };
+
     // 先尝试 HEAD 请求获取文件大小
     let mut total_size = 0u64;
-    if let Ok(head_resp) = client.head(url).send().await {
+    let mut head = client.head(url);
+    for (k, v) in headers {
+        head = head.header(k.as_str(), v.as_str());
+    }
+    if let Ok(head_resp) = head.send().await {
         if head_resp.status().is_success() {
             total_size = head_resp.content_length().unwrap_or(0);
         }
@@ -250,6 +463,9 @@ async fn download_file(task_id: &str, url: &str, save_path: &str) -> AppResult<(
 
     // 构建请求，支持断点续传
     let mut request = client.get(url);
+    for (k, v) in headers {
+        request = request.header(k.as_str(), v.as_str());
+    }
     if existing_size > 0 {
         request = request.header("Range", format!("bytes={}-", existing_size));
     }
@@ -318,7 +534,19 @@ async fn download_file(task_id: &str, url: &str, save_path: &str) -> AppResult<(
     let mut stream = response.bytes_stream();
     use futures::StreamExt;
 
-    while let Some(chunk) = stream.next().await {
+    loop {
+        let next = match stall_timeout {
+            Some(limit) => tokio::time::timeout(limit, stream.next())
+                .await
+                .map_err(|_| {
+                    crate::error::AppError::from(format!("超过 {} 秒没有收到数据", limit.as_secs()))
+                })?,
+            None => stream.next().await,
+        };
+        let Some(chunk) = next else {
+            break;
+        };
+
         // 检查是否被取消
         if is_cancelled(task_id).await {
             return Err(crate::error::AppError::from("下载已取消".to_string()));
@@ -375,6 +603,61 @@ async fn download_file(task_id: &str, url: &str, save_path: &str) -> AppResult<(
     Ok(())
 }
 
+/// 按设置在下载完成后自动扫描
+async fn auto_scan(task_id: &str) {
+    let Ok(settings) = crate::commands::settings::load_app_settings().await else {
+        return;
+    };
+    if settings.download_virus_scan {
+        if let Err(e) = run_scan(task_id, &settings.download_virus_action).await {
+            log::error!("下载文件病毒扫描失败: {}", e);
+        }
+    }
+}
+
+/// 扫描任务文件并把结果写回任务
+async fn run_scan(task_id: &str, action: &str) -> AppResult<DownloadScanResult> {
+    let task = {
+        let mut tasks = DOWNLOAD_TASKS.lock().await;
+        let task = tasks
+            .get_mut(task_id)
+            .ok_or_else(|| crate::error::AppError::invalid(format!("任务不存在: {}", task_id)))?;
+        task.scan = Some(DownloadScanResult {
+            scanner: None,
+            verdict: "scanning".to_string(),
+            detail: None,
+            action: None,
+            scanned_at: current_time(),
+        });
+        task.clone()
+    };
+
+    let result = super::virus_scan::scan_task_file(&task, action).await;
+    {
+        let mut tasks = DOWNLOAD_TASKS.lock().await;
+        if let Some(task) = tasks.get_mut(task_id) {
+            task.scan = Some(result.clone());
+            task.updated_at = current_time();
+        }
+    }
+    save_tasks_to_file().await?;
+    Ok(result)
+}
+
+/// 手动扫描已完成的下载；发现威胁时按设置的处理方式隔离 / 删除
+#[tauri::command]
+#[specta::specta]
+pub async fn scan_download(task_id: String) -> AppResult<DownloadScanResult> {
+    ensure_tasks_loaded().await;
+    let settings = crate::commands::settings::load_app_settings().await?;
+    run_scan(&task_id, &settings.download_virus_action).await
+}
+
+/// 获取任务当前状态的副本
+async fn task_snapshot(task_id: &str) -> Option<DownloadTask> {
+    DOWNLOAD_TASKS.lock().await.get(task_id).cloned()
+}
+
 /// 检查是否被取消
 async fn is_cancelled(task_id: &str) -> bool {
     let flags = DOWNLOAD_CANCELLED.lock().await;
@@ -437,9 +720,10 @@ pub async fn resume_download(task_id: String) -> AppResult<()> {
     let task =
         task.ok_or_else(|| crate::error::AppError::from(format!("任务不存在: {}", task_id)))?;
 
-    if task.status != "paused" {
+    // 失败的任务从已下载的部分续传
+    if task.status != "paused" && task.status != "failed" {
         return Err(crate::error::AppError::from(
-            "任务未暂停，无法恢复".to_string(),
+            "任务未暂停或失败，无法恢复".to_string(),
         ));
     }
 
@@ -451,11 +735,14 @@ pub async fn resume_download(task_id: String) -> AppResult<()> {
 
     // 重新启动下载
     let id = task_id.clone();
-    let url = task.url.clone();
+    let sources: Vec<String> = std::iter::once(task.url.clone())
+        .chain(task.mirrors.clone())
+        .collect();
     let path = task.save_path.clone();
+    let headers = task.headers.clone();
 
     tokio::spawn(async move {
-        download_with_retry(&id, &url, &path, 3).await;
+        download_with_retry(&id, &sources, &path, &headers, 3).await;
     });
 
     Ok(())
@@ -514,6 +801,17 @@ pub async fn get_download_tasks() -> AppResult<Vec<DownloadTask>> {
     Ok(tasks.values().cloned().collect())
 }
 
+/// 正在下载的任务数（托盘角标用）
+pub(crate) async fn active_download_count() -> usize {
+    ensure_tasks_loaded().await;
+    DOWNLOAD_TASKS
+        .lock()
+        .await
+        .values()
+        .filter(|t| t.status == "downloading")
+        .count()
+}
+
 /// 获取单个下载任务
 #[tauri::command]
 #[specta::specta]
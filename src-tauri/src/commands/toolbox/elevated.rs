@@ -0,0 +1,107 @@
+// 提权操作命令 - 权限不足时以管理员身份执行单个操作（见 crate::elevation）
+//
+// - kill_process_elevated：普通 kill_process 因权限不足失败时使用，与强制终止一样需要二次确认
+// - 端口中继：低位端口（<1024）无法直接绑定时，服务改用高位端口，
+//   再由提权助手监听低位端口（仅回环地址）并转发过去；应用退出时中继随之结束
+
+use super::{current_time, ElevatedRelay};
+use crate::commands::confirm::{self, ConfirmRequest};
+use crate::elevation::{run_elevated, ElevatedHandle, ElevatedOp};
+use crate::error::{AppError, AppResult};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// 等待用户完成授权的最长时间
+const AUTH_TIMEOUT: Duration = Duration::from_secs(120);
+
+static RELAYS: Lazy<Mutex<HashMap<u16, (ElevatedRelay, ElevatedHandle)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 以管理员权限结束进程（会弹出系统授权对话框）。
+/// 不带 confirm_token 时只返回确认请求，带有效令牌时执行并返回 None
+#[tauri::command]
+#[specta::specta]
+pub async fn kill_process_elevated(
+    pid: u32,
+    force: Option<bool>,
+    confirm_token: Option<String>,
+) -> AppResult<Option<ConfirmRequest>> {
+    if pid == std::process::id() {
+        return Err(AppError::invalid("无法终止 CodeShelf 进程"));
+    }
+    let force = force.unwrap_or(false);
+    let plan = super::process::kill_plan(pid, force).await?;
+    if let Some(request) = confirm::require("kill_process_elevated", confirm_token, plan)? {
+        return Ok(Some(request));
+    }
+    let op = ElevatedOp::KillProcess { pid, force };
+    let (message, _) = run_elevated(&op, AUTH_TIMEOUT).await?;
+    log::info!("提权结束进程: {} ({})", pid, message);
+    Ok(None)
+}
+
+/// 启动提权端口中继：监听 listen_host:listen_port，转发到 127.0.0.1:target_port
+#[tauri::command]
+#[specta::specta]
+pub async fn start_elevated_port_relay(
+    listen_port: u16,
+    target_port: u16,
+    listen_host: Option<String>,
+) -> AppResult<ElevatedRelay> {
+    if listen_port == target_port {
+        return Err(AppError::invalid("监听端口与目标端口不能相同"));
+    }
+    if RELAYS.lock().await.contains_key(&listen_port) {
+        return Err(AppError::invalid(format!(
+            "端口 {} 已有提权中继",
+            listen_port
+        )));
+    }
+    let listen_host = listen_host
+        .filter(|h| !h.trim().is_empty())
+        .unwrap_or_else(|| "127.0.0.1".to_string());
+    let op = ElevatedOp::PortRelay {
+        listen_host: listen_host.clone(),
+        listen_port,
+        target_port,
+        parent_pid: std::process::id(),
+    };
+    let (message, handle) = run_elevated(&op, AUTH_TIMEOUT).await?;
+    log::info!("提权端口中继已启动: {}", message);
+
+    let relay = ElevatedRelay {
+        listen_host,
+        listen_port,
+        target_port,
+        started_at: current_time(),
+    };
+    RELAYS
+        .lock()
+        .await
+        .insert(listen_port, (relay.clone(), handle));
+    Ok(relay)
+}
+
+/// 停止提权端口中继
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_elevated_port_relay(listen_port: u16) -> AppResult<()> {
+    let (_, handle) = RELAYS
+        .lock()
+        .await
+        .remove(&listen_port)
+        .ok_or_else(|| AppError::invalid(format!("端口 {} 没有提权中继", listen_port)))?;
+    handle.stop()
+}
+
+/// 获取正在运行的提权端口中继
+#[tauri::command]
+#[specta::specta]
+pub async fn get_elevated_port_relays() -> AppResult<Vec<ElevatedRelay>> {
+    let relays = RELAYS.lock().await;
+    let mut list: Vec<ElevatedRelay> = relays.values().map(|(r, _)| r.clone()).collect();
+    list.sort_by_key(|r| r.listen_port);
+    Ok(list)
+}
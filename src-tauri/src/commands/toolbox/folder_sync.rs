@@ -0,0 +1,409 @@
+// 目录同步 - 单向镜像 source → destination，可预览差异、可删除目标中多余的文件
+//
+// 按 大小 + 修改时间 判断文件是否变化，复制后把源文件的修改时间写回目标，下次同步即可跳过。
+// 先写入同目录的临时文件再改名，网络盘中途断开不会留下半个文件。不跟随符号链接。
+// 过滤规则为 gitignore 风格；被排除的目标文件即使开启删除也会保留。
+// 执行期间推送 `folder-sync-progress`，传入 job_id 时可用 cancel_operation 中止。
+
+use crate::commands::git::compile_patterns;
+use crate::commands::operations::Operation;
+use crate::error::{AppError, AppResult};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+use tauri::{AppHandle, Emitter};
+
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// 修改时间容差：FAT / SMB 只保存到 2 秒精度
+const MTIME_TOLERANCE: Duration = Duration::from_secs(2);
+
+const TEMP_SUFFIX: &str = ".codeshelf-sync.tmp";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FolderSyncOptions {
+    /// 非空时只同步匹配的文件
+    pub include: Vec<String>,
+    /// 匹配的文件和目录都跳过
+    pub exclude: Vec<String>,
+    /// 删除目标中源目录没有的文件和目录
+    pub delete_extraneous: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderSyncEntry {
+    /// 相对路径，以 / 分隔；目录以 / 结尾
+    pub relative_path: String,
+    /// "create" | "update" | "delete"
+    pub action: String,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderSyncError {
+    pub relative_path: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderSyncReport {
+    pub dry_run: bool,
+    /// dry_run 时为将要执行的变更，否则为已成功执行的变更
+    pub entries: Vec<FolderSyncEntry>,
+    pub created: u32,
+    pub updated: u32,
+    pub deleted: u32,
+    pub unchanged: u32,
+    /// 需要（或已经）复制的字节数
+    pub bytes_copied: u64,
+    pub errors: Vec<FolderSyncError>,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderSyncProgress {
+    pub job_id: String,
+    /// "scan" | "copy" | "delete" | "done"
+    pub phase: String,
+    pub processed: u32,
+    pub total: u32,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    pub current: Option<String>,
+}
+
+struct FileMeta {
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+#[derive(Default)]
+struct Tree {
+    files: BTreeMap<String, FileMeta>,
+    dirs: Vec<String>,
+}
+
+struct Filter {
+    include: Vec<Regex>,
+    exclude: Vec<Regex>,
+}
+
+impl Filter {
+    fn new(options: &FolderSyncOptions) -> AppResult<Self> {
+        Ok(Self {
+            include: compile_patterns(&options.include, false)?,
+            exclude: compile_patterns(&options.exclude, false)?,
+        })
+    }
+
+    fn excluded(&self, relative: &str) -> bool {
+        self.exclude.iter().any(|re| re.is_match(relative))
+    }
+
+    fn accepts_file(&self, relative: &str) -> bool {
+        !self.excluded(relative)
+            && (self.include.is_empty() || self.include.iter().any(|re| re.is_match(relative)))
+    }
+}
+
+fn relative_of(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// 递归列出目录；被排除的目录整棵跳过，遗留的临时文件忽略
+fn scan(root: &Path, filter: &Filter, op: &Operation) -> AppResult<Tree> {
+    let mut tree = Tree::default();
+    if !root.exists() {
+        return Ok(tree);
+    }
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        op.check()?;
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            let path = entry.path();
+            let relative = relative_of(root, &path);
+            if file_type.is_dir() {
+                if !filter.excluded(&relative) {
+                    tree.dirs.push(relative);
+                    stack.push(path);
+                }
+            } else if file_type.is_file()
+                && !relative.ends_with(TEMP_SUFFIX)
+                && filter.accepts_file(&relative)
+            {
+                let meta = entry.metadata()?;
+                tree.files.insert(
+                    relative,
+                    FileMeta {
+                        size: meta.len(),
+                        modified: meta.modified().ok(),
+                    },
+                );
+            }
+        }
+    }
+    Ok(tree)
+}
+
+fn unchanged(source: &FileMeta, target: &FileMeta) -> bool {
+    if source.size != target.size {
+        return false;
+    }
+    match (source.modified, target.modified) {
+        (Some(a), Some(b)) => {
+            let diff = a.duration_since(b).or_else(|_| b.duration_since(a));
+            diff.is_ok_and(|d| d <= MTIME_TOLERANCE)
+        }
+        _ => false,
+    }
+}
+
+fn copy_via_temp(source: &Path, temp: &Path, target: &Path) -> std::io::Result<()> {
+    std::fs::copy(source, temp)?;
+    if let Ok(modified) = std::fs::metadata(source).and_then(|m| m.modified()) {
+        std::fs::File::options()
+            .write(true)
+            .open(temp)?
+            .set_modified(modified)?;
+    }
+    std::fs::rename(temp, target)
+}
+
+fn copy_file(source: &Path, target: &Path) -> AppResult<()> {
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut temp = target.as_os_str().to_os_string();
+    temp.push(TEMP_SUFFIX);
+    let temp = PathBuf::from(temp);
+    copy_via_temp(source, &temp, target).map_err(|e| {
+        let _ = std::fs::remove_file(&temp);
+        AppError::from(e)
+    })
+}
+
+fn count(entries: &[FolderSyncEntry], action: &str) -> u32 {
+    entries.iter().filter(|e| e.action == action).count() as u32
+}
+
+struct Reporter<'a> {
+    app: &'a AppHandle,
+    progress: FolderSyncProgress,
+    last_progress: Instant,
+}
+
+impl Reporter<'_> {
+    fn report(&mut self, phase: &str, current: &str, force: bool) {
+        if !force && self.last_progress.elapsed() < PROGRESS_INTERVAL {
+            return;
+        }
+        self.last_progress = Instant::now();
+        self.progress.phase = phase.to_string();
+        self.progress.current = Some(current.to_string());
+        let _ = self.app.emit("folder-sync-progress", &self.progress);
+    }
+}
+
+fn run(
+    app: &AppHandle,
+    op: &Operation,
+    source: &Path,
+    destination: &Path,
+    options: &FolderSyncOptions,
+    dry_run: bool,
+) -> AppResult<FolderSyncReport> {
+    let filter = Filter::new(options)?;
+    let mut reporter = Reporter {
+        app,
+        progress: FolderSyncProgress {
+            job_id: op.id().to_string(),
+            phase: "scan".to_string(),
+            processed: 0,
+            total: 0,
+            bytes_done: 0,
+            bytes_total: 0,
+            current: None,
+        },
+        last_progress: Instant::now(),
+    };
+    reporter.report("scan", "", true);
+    let from = scan(source, &filter, op)?;
+    let to = scan(destination, &filter, op)?;
+
+    let mut entries = Vec::new();
+    let mut unchanged_count = 0u32;
+    for (relative, meta) in &from.files {
+        let action = match to.files.get(relative) {
+            None => "create",
+            Some(existing) if !unchanged(meta, existing) => "update",
+            Some(_) => {
+                unchanged_count += 1;
+                continue;
+            }
+        };
+        entries.push(FolderSyncEntry {
+            relative_path: relative.clone(),
+            action: action.to_string(),
+            bytes: meta.size,
+        });
+    }
+    if options.delete_extraneous {
+        for (relative, meta) in &to.files {
+            if !from.files.contains_key(relative) {
+                entries.push(FolderSyncEntry {
+                    relative_path: relative.clone(),
+                    action: "delete".to_string(),
+                    bytes: meta.size,
+                });
+            }
+        }
+        // 目录按路径倒序，子目录先于父目录删除
+        let mut dirs: Vec<&String> = to.dirs.iter().filter(|d| !from.dirs.contains(d)).collect();
+        dirs.sort_by(|a, b| b.cmp(a));
+        for dir in dirs {
+            entries.push(FolderSyncEntry {
+                relative_path: format!("{}/", dir),
+                action: "delete".to_string(),
+                bytes: 0,
+            });
+        }
+    }
+
+    let bytes_total: u64 = entries
+        .iter()
+        .filter(|e| e.action != "delete")
+        .map(|e| e.bytes)
+        .sum();
+    if dry_run {
+        return Ok(FolderSyncReport {
+            dry_run,
+            created: count(&entries, "create"),
+            updated: count(&entries, "update"),
+            deleted: count(&entries, "delete"),
+            unchanged: unchanged_count,
+            bytes_copied: bytes_total,
+            entries,
+            errors: Vec::new(),
+            elapsed_ms: 0,
+        });
+    }
+
+    // 没有 include 时连空目录一起镜像
+    std::fs::create_dir_all(destination)?;
+    if filter.include.is_empty() {
+        for dir in from.dirs.iter().filter(|d| !to.dirs.contains(d)) {
+            std::fs::create_dir_all(destination.join(dir))?;
+        }
+    }
+    reporter.progress.total = entries.len() as u32;
+    reporter.progress.bytes_total = bytes_total;
+    let mut done = Vec::with_capacity(entries.len());
+    let mut errors = Vec::new();
+    for entry in entries {
+        op.check()?;
+        let target = destination.join(entry.relative_path.trim_end_matches('/'));
+        let result = if entry.action != "delete" {
+            reporter.report("copy", &entry.relative_path, false);
+            copy_file(&source.join(&entry.relative_path), &target)
+        } else if entry.relative_path.ends_with('/') {
+            reporter.report("delete", &entry.relative_path, false);
+            // 目录里还有被排除的文件时删不掉，保留即可，不算错误
+            match std::fs::remove_dir(&target) {
+                Ok(()) => Ok(()),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                Err(_) => {
+                    reporter.progress.processed += 1;
+                    continue;
+                }
+            }
+        } else {
+            reporter.report("delete", &entry.relative_path, false);
+            std::fs::remove_file(&target).map_err(AppError::from)
+        };
+        reporter.progress.processed += 1;
+        match result {
+            Ok(()) => {
+                if entry.action != "delete" {
+                    reporter.progress.bytes_done += entry.bytes;
+                }
+                done.push(entry);
+            }
+            Err(e) => errors.push(FolderSyncError {
+                relative_path: entry.relative_path,
+                message: e.to_string(),
+            }),
+        }
+    }
+    reporter.report("done", "", true);
+
+    Ok(FolderSyncReport {
+        dry_run,
+        created: count(&done, "create"),
+        updated: count(&done, "update"),
+        deleted: count(&done, "delete"),
+        unchanged: unchanged_count,
+        bytes_copied: reporter.progress.bytes_done,
+        entries: done,
+        errors,
+        elapsed_ms: 0,
+    })
+}
+
+/// 把 source 单向同步到 destination；dry_run 时只返回将要执行的变更
+#[tauri::command]
+#[specta::specta]
+pub async fn sync_folder(
+    app: AppHandle,
+    source: String,
+    destination: String,
+    options: Option<FolderSyncOptions>,
+    dry_run: bool,
+    job_id: Option<String>,
+) -> AppResult<FolderSyncReport> {
+    let source_path = Path::new(&source);
+    if !source_path.is_dir() {
+        return Err(AppError::invalid(format!("源目录不存在: {}", source)));
+    }
+    if destination.trim().is_empty() {
+        return Err(AppError::invalid("请选择目标目录"));
+    }
+    let source_path = source_path.canonicalize()?;
+    // 目标可能还不存在，用最近的已存在上级目录判断嵌套关系
+    let destination_path = PathBuf::from(&destination);
+    let resolved = destination_path
+        .ancestors()
+        .find_map(|p| {
+            p.canonicalize()
+                .ok()
+                .map(|base| base.join(destination_path.strip_prefix(p).unwrap_or(Path::new(""))))
+        })
+        .unwrap_or_else(|| destination_path.clone());
+    if resolved.starts_with(&source_path) || source_path.starts_with(&resolved) {
+        return Err(AppError::invalid("源目录与目标目录不能相同或互相包含"));
+    }
+    let options = options.unwrap_or_default();
+
+    let op = Operation::begin(job_id, "目录同步", None)?;
+    let started = Instant::now();
+    let mut report = tokio::task::spawn_blocking(move || {
+        run(&app, &op, &source_path, &resolved, &options, dry_run)
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("目录同步失败: {}", e)))??;
+    report.elapsed_ms = started.elapsed().as_millis() as u64;
+    Ok(report)
+}
@@ -1,142 +1,284 @@
 // 端口转发模块 - TCP 流量代理转发，支持连接管理和流量统计
-
-use super::{current_time, generate_id, ForwardRule, ForwardRuleInput, ForwardStats};
+//
+// 一条规则可以有多个目标（主目标 + backup_targets）：
+// - failover：按顺序优先连接健康的目标，主目标恢复后新连接自动回到主目标
+// - round_robin：在健康目标间轮询
+// 连接失败会把目标标记为不可用并立即尝试下一个；配置了健康检查间隔时，
+// 后台定期探测各目标，不可用的目标恢复后重新参与选择。
+//
+// 目标也可以是 Unix 域套接字 / Windows 命名管道（target_kind = unix / pipe），
+// 用于把 Docker socket、语言服务器 socket 等暴露为本机 TCP 端口；这类规则只监听 127.0.0.1。
+
+use super::pcap::{self, Transport};
+use super::port_conflict::{bind_or_conflict, bind_or_conflict_on};
+use super::{
+    current_time, generate_id, ForwardRule, ForwardRuleInput, ForwardStats, ForwardTarget,
+    ForwardTargetStatus,
+};
 use crate::error::AppResult;
-use crate::storage;
+use crate::storage::config::StorageConfig;
+use crate::storage::PersistedStore;
 use once_cell::sync::Lazy;
-use socket2::{Domain, Socket, Type};
 use std::collections::HashMap;
-use std::fs;
-use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
-use tokio::io::AsyncWriteExt;
+use tauri::AppHandle;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::{Mutex, Semaphore};
-use tokio::time::{timeout, Duration};
+use tokio::time::{timeout, Duration, Instant};
 
 /// 转发规则存储 - 延迟初始化
-static FORWARD_RULES: Lazy<Arc<Mutex<HashMap<String, ForwardRule>>>> =
-    Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
-
-/// 是否已从文件加载
-static RULES_LOADED: Lazy<Arc<Mutex<bool>>> = Lazy::new(|| Arc::new(Mutex::new(false)));
+static FORWARD_RULES: Lazy<PersistedStore<ForwardRule>> = Lazy::new(|| {
+    PersistedStore::new(
+        "forwardRules",
+        "转发规则",
+        StorageConfig::forward_rules_file,
+        |r: &ForwardRule| r.id.clone(),
+    )
+    .on_load(|r| {
+        // 重启后默认停止
+        r.status = "stopped".to_string();
+        r.connections = 0;
+        r.bytes_in = 0;
+        r.bytes_out = 0;
+    })
+});
 
 /// 转发控制器（用于停止转发）
 static FORWARD_CONTROLLERS: Lazy<Arc<Mutex<HashMap<String, Arc<ForwardController>>>>> =
     Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
 
 /// 确保转发规则已从文件加载
-async fn ensure_rules_loaded() {
-    let mut loaded = RULES_LOADED.lock().await;
-    if !*loaded {
-        match load_rules_from_file() {
-            Ok(rules) => {
-                let mut rules_map = FORWARD_RULES.lock().await;
-                *rules_map = rules;
-                *loaded = true; // 只有成功加载才设置为 true
-            }
-            Err(e) => {
-                log::warn!("加载转发规则失败，将在下次重试: {}", e);
-                // 不设置 loaded = true，允许下次重试
-            }
-        }
-    }
+pub(crate) async fn ensure_rules_loaded() {
+    FORWARD_RULES.ensure_loaded().await;
 }
 
-/// 从文件加载转发规则
-fn load_rules_from_file() -> AppResult<HashMap<String, ForwardRule>> {
-    let config = storage::get_storage_config()?;
-    let path = config.forward_rules_file();
+/// 丢弃已加载的转发规则，切换配置档案后从新位置读取
+pub(crate) async fn unload_rules() {
+    FORWARD_RULES.unload().await;
+}
 
-    log::info!("加载转发规则: {:?}", path);
+/// 保存转发规则到文件（防抖合并写盘）
+async fn save_rules_to_file() -> AppResult<()> {
+    FORWARD_RULES.save().await
+}
 
-    if !path.exists() {
-        log::info!("转发规则文件不存在，返回空列表");
-        return Ok(HashMap::new());
-    }
+/// 单个目标的运行时状态
+struct TargetState {
+    address: String,
+    healthy: AtomicBool,
+    last_error: std::sync::Mutex<Option<String>>,
+}
 
-    let content = fs::read_to_string(&path)
-        .map_err(|e| crate::error::AppError::from(format!("读取转发规则失败: {}", e)))?;
+/// 转发目标池：记录各目标健康状态，按策略给出连接尝试顺序
+struct TargetPool {
+    targets: Vec<TargetState>,
+    round_robin: bool,
+    next: AtomicUsize,
+}
 
-    // 直接解析为规则数组
-    let rules_arr: Vec<ForwardRule> = match serde_json::from_str(&content) {
-        Ok(arr) => arr,
-        Err(e) => {
-            log::error!(
-                "解析转发规则 JSON 失败: {}，内容: {}",
-                e,
-                &content[..content.len().min(200)]
-            );
-            Vec::new()
+impl TargetPool {
+    fn from_rule(rule: &ForwardRule) -> Self {
+        let primary = ForwardTarget {
+            host: rule.remote_host.clone(),
+            port: rule.remote_port,
+        };
+        let targets = std::iter::once(&primary)
+            .chain(rule.backup_targets.iter())
+            .map(|t| TargetState {
+                address: format!("{}:{}", t.host, t.port),
+                healthy: AtomicBool::new(true),
+                last_error: std::sync::Mutex::new(None),
+            })
+            .collect();
+        Self {
+            targets,
+            round_robin: rule.balance == "round_robin",
+            next: AtomicUsize::new(0),
         }
-    };
+    }
 
-    let mut rules = HashMap::new();
-    for mut rule in rules_arr {
-        // 重启后默认停止
-        rule.status = "stopped".to_string();
-        rule.connections = 0;
-        rule.bytes_in = 0;
-        rule.bytes_out = 0;
-        log::info!(
-            "加载转发规则: {} ({}:{} -> {}:{})",
-            rule.name,
-            "localhost",
-            rule.local_port,
-            rule.remote_host,
-            rule.remote_port
-        );
-        rules.insert(rule.id.clone(), rule);
+    /// 本次连接的尝试顺序：健康的目标在前（轮询时从下一个开始），不健康的兜底
+    fn candidates(&self) -> Vec<usize> {
+        let n = self.targets.len();
+        let start = if self.round_robin {
+            self.next.fetch_add(1, Ordering::Relaxed) % n
+        } else {
+            0
+        };
+        let order: Vec<usize> = (0..n).map(|i| (start + i) % n).collect();
+        let (mut healthy, unhealthy): (Vec<usize>, Vec<usize>) = order
+            .into_iter()
+            .partition(|i| self.targets[*i].healthy.load(Ordering::Relaxed));
+        healthy.extend(unhealthy);
+        healthy
     }
 
-    log::info!("共加载 {} 个转发规则", rules.len());
-    Ok(rules)
-}
+    fn mark(&self, index: usize, result: Result<(), String>) {
+        let target = &self.targets[index];
+        let was_healthy = target.healthy.swap(result.is_ok(), Ordering::Relaxed);
+        match &result {
+            Ok(()) if !was_healthy => log::info!("转发目标已恢复: {}", target.address),
+            Err(e) if was_healthy => log::warn!("转发目标不可用: {} ({})", target.address, e),
+            _ => {}
+        }
+        if let Ok(mut last) = target.last_error.lock() {
+            *last = result.err();
+        }
+    }
 
-/// 保存转发规则到文件
-async fn save_rules_to_file() -> AppResult<()> {
-    let config = storage::get_storage_config()?;
+    fn statuses(&self) -> Vec<ForwardTargetStatus> {
+        self.targets
+            .iter()
+            .map(|t| ForwardTargetStatus {
+                address: t.address.clone(),
+                healthy: t.healthy.load(Ordering::Relaxed),
+                last_error: t.last_error.lock().ok().and_then(|e| e.clone()),
+            })
+            .collect()
+    }
 
-    // 确保数据目录存在
-    config.ensure_dirs()?;
+    /// 按顺序尝试连接，返回第一个成功的连接
+    async fn connect(&self) -> AppResult<TcpStream> {
+        // 只有一个目标时保持原来的较长超时；多目标时快速失败切到下一个
+        let connect_timeout = if self.targets.len() > 1 {
+            Duration::from_secs(3)
+        } else {
+            Duration::from_secs(10)
+        };
+        let mut last_error = String::new();
+        for index in self.candidates() {
+            let address = &self.targets[index].address;
+            match timeout(connect_timeout, TcpStream::connect(address)).await {
+                Ok(Ok(stream)) => {
+                    self.mark(index, Ok(()));
+                    return Ok(stream);
+                }
+                Ok(Err(e)) => last_error = format!("连接 {} 失败: {}", address, e),
+                Err(_) => last_error = format!("连接 {} 超时", address),
+            }
+            self.mark(index, Err(last_error.clone()));
+        }
+        Err(crate::error::AppError::from(format!(
+            "所有转发目标均不可用（{}）",
+            last_error
+        )))
+    }
 
-    let rules = FORWARD_RULES.lock().await;
+    /// 主动探测所有目标
+    async fn check_all(&self) {
+        for (index, target) in self.targets.iter().enumerate() {
+            let result =
+                match timeout(Duration::from_secs(2), TcpStream::connect(&target.address)).await {
+                    Ok(Ok(_)) => Ok(()),
+                    Ok(Err(e)) => Err(e.to_string()),
+                    Err(_) => Err("健康检查超时".to_string()),
+                };
+            self.mark(index, result);
+        }
+    }
+}
 
-    // 直接序列化（serde 会自动用 camelCase）
-    let rules_data: Vec<&ForwardRule> = rules.values().collect();
+/// 转发上游连接（TCP / Unix 域套接字 / 命名管道）
+trait Upstream: AsyncRead + AsyncWrite + Unpin + Send {}
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Upstream for T {}
 
-    let content = serde_json::to_string(&rules_data)
-        .map_err(|e| crate::error::AppError::from(format!("序列化转发规则失败: {}", e)))?;
+/// 套接字类目标
+#[derive(Clone)]
+enum SocketTarget {
+    Unix(String),
+    Pipe(String),
+}
 
-    let path = config.forward_rules_file();
-    log::info!("保存转发规则到: {:?}", path);
+impl SocketTarget {
+    fn from_rule(rule: &ForwardRule) -> Option<Self> {
+        let path = rule.socket_path.clone().unwrap_or_default();
+        match rule.target_kind.as_str() {
+            "unix" => Some(Self::Unix(path)),
+            "pipe" => Some(Self::Pipe(path)),
+            _ => None,
+        }
+    }
 
-    fs::write(&path, content)
-        .map_err(|e| crate::error::AppError::from(format!("写入转发规则失败: {}", e)))?;
+    fn describe(&self) -> String {
+        match self {
+            Self::Unix(path) => format!("unix:{}", path),
+            Self::Pipe(path) => format!("pipe:{}", path),
+        }
+    }
 
-    log::info!("转发规则保存成功，共 {} 个规则", rules.len());
-    Ok(())
+    async fn connect(&self) -> AppResult<Box<dyn Upstream>> {
+        match self {
+            #[cfg(unix)]
+            Self::Unix(path) => {
+                let stream = tokio::net::UnixStream::connect(path).await.map_err(|e| {
+                    crate::error::AppError::from(format!("连接 {} 失败: {}", path, e))
+                })?;
+                Ok(Box::new(stream))
+            }
+            #[cfg(windows)]
+            Self::Pipe(path) => {
+                use tokio::net::windows::named_pipe::ClientOptions;
+                // 管道实例全忙（ERROR_PIPE_BUSY = 231）时稍等重试
+                for _ in 0..50 {
+                    match ClientOptions::new().open(path) {
+                        Ok(client) => return Ok(Box::new(client)),
+                        Err(e) if e.raw_os_error() == Some(231) => {
+                            tokio::time::sleep(Duration::from_millis(50)).await;
+                        }
+                        Err(e) => {
+                            return Err(crate::error::AppError::from(format!(
+                                "连接 {} 失败: {}",
+                                path, e
+                            )))
+                        }
+                    }
+                }
+                Err(crate::error::AppError::from(format!(
+                    "命名管道忙: {}",
+                    path
+                )))
+            }
+            other => Err(crate::error::AppError::invalid(format!(
+                "当前平台不支持该转发目标: {}",
+                other.describe()
+            ))),
+        }
+    }
 }
 
 /// 转发控制器
 struct ForwardController {
     /// 停止标志
     stop: AtomicBool,
+    /// 转发目标
+    targets: TargetPool,
+    /// 套接字类目标，存在时不使用 targets
+    socket_target: Option<SocketTarget>,
     /// 当前连接数
     connections: AtomicU32,
+    /// 启动以来接受的连接总数
+    connections_total: AtomicU64,
     /// 入站字节数
     bytes_in: AtomicU64,
     /// 出站字节数
     bytes_out: AtomicU64,
+    /// 最近一次连接建立或关闭的时间，用于空闲自动停止
+    last_active: std::sync::Mutex<Instant>,
 }
 
 impl ForwardController {
-    fn new() -> Self {
+    fn new(rule: &ForwardRule) -> Self {
         Self {
             stop: AtomicBool::new(false),
+            targets: TargetPool::from_rule(rule),
+            socket_target: SocketTarget::from_rule(rule),
             connections: AtomicU32::new(0),
+            connections_total: AtomicU64::new(0),
             bytes_in: AtomicU64::new(0),
             bytes_out: AtomicU64::new(0),
+            last_active: std::sync::Mutex::new(Instant::now()),
         }
     }
 
@@ -150,10 +292,30 @@ impl ForwardController {
 
     fn inc_connections(&self) {
         self.connections.fetch_add(1, Ordering::SeqCst);
+        self.connections_total.fetch_add(1, Ordering::Relaxed);
+        self.touch();
     }
 
     fn dec_connections(&self) {
         self.connections.fetch_sub(1, Ordering::SeqCst);
+        self.touch();
+    }
+
+    fn touch(&self) {
+        *self.last_active.lock().unwrap_or_else(|e| e.into_inner()) = Instant::now();
+    }
+
+    /// 没有连接时返回已空闲的时长
+    fn idle_for(&self) -> Option<Duration> {
+        if self.connections.load(Ordering::SeqCst) > 0 {
+            return None;
+        }
+        Some(
+            self.last_active
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .elapsed(),
+        )
     }
 
     fn add_bytes_in(&self, bytes: u64) {
@@ -183,10 +345,11 @@ pub async fn add_forward_rule(input: ForwardRuleInput) -> AppResult<ForwardRule>
     if input.local_port == 0 {
         return Err(crate::error::AppError::from("本地端口不能为 0".to_string()));
     }
-    if input.remote_port == 0 {
+    let is_socket = validate_target(&input)?;
+    if !is_socket && input.remote_port == 0 {
         return Err(crate::error::AppError::from("远程端口不能为 0".to_string()));
     }
-    if input.remote_host.is_empty() {
+    if !is_socket && input.remote_host.is_empty() {
         return Err(crate::error::AppError::from("远程主机不能为空".to_string()));
     }
 
@@ -211,6 +374,11 @@ pub async fn add_forward_rule(input: ForwardRuleInput) -> AppResult<ForwardRule>
         remote_host: input.remote_host,
         remote_port: input.remote_port,
         doc_path: input.doc_path,
+        target_kind: input.target_kind.unwrap_or_else(|| "tcp".to_string()),
+        socket_path: input.socket_path.filter(|p| !p.trim().is_empty()),
+        backup_targets: input.backup_targets.unwrap_or_default(),
+        balance: input.balance.unwrap_or_else(|| "failover".to_string()),
+        health_check_interval_secs: input.health_check_interval_secs.filter(|s| *s > 0),
         status: "stopped".to_string(),
         connections: 0,
         bytes_in: 0,
@@ -239,6 +407,46 @@ pub async fn add_forward_rule(input: ForwardRuleInput) -> AppResult<ForwardRule>
     Ok(rule)
 }
 
+/// 校验目标类型；套接字类目标要求路径非空，返回是否为套接字类目标
+fn validate_target(input: &ForwardRuleInput) -> AppResult<bool> {
+    match input.target_kind.as_deref().unwrap_or("tcp") {
+        "tcp" => {
+            validate_failover(input)?;
+            Ok(false)
+        }
+        "unix" | "pipe" => {
+            if input.socket_path.as_deref().unwrap_or("").trim().is_empty() {
+                return Err(crate::error::AppError::invalid("套接字路径不能为空"));
+            }
+            Ok(true)
+        }
+        other => Err(crate::error::AppError::invalid(format!(
+            "不支持的转发目标类型: {}",
+            other
+        ))),
+    }
+}
+
+/// 校验备用目标与策略
+fn validate_failover(input: &ForwardRuleInput) -> AppResult<()> {
+    if let Some(balance) = input.balance.as_deref() {
+        if balance != "failover" && balance != "round_robin" {
+            return Err(crate::error::AppError::invalid(format!(
+                "不支持的目标选择策略: {}",
+                balance
+            )));
+        }
+    }
+    for target in input.backup_targets.iter().flatten() {
+        if target.host.trim().is_empty() || target.port == 0 {
+            return Err(crate::error::AppError::invalid(
+                "备用目标的主机不能为空、端口不能为 0",
+            ));
+        }
+    }
+    Ok(())
+}
+
 /// 移除转发规则
 #[tauri::command]
 #[specta::specta]
@@ -247,6 +455,7 @@ pub async fn remove_forward_rule(rule_id: String) -> AppResult<()> {
 
     // 先停止转发
     let _ = stop_forwarding(rule_id.clone()).await;
+    pcap::finish(&rule_id);
 
     // 保存旧规则以便回滚
     let old_rule = {
@@ -280,7 +489,7 @@ pub async fn remove_forward_rule(rule_id: String) -> AppResult<()> {
 /// 启动转发
 #[tauri::command]
 #[specta::specta]
-pub async fn start_forwarding(rule_id: String) -> AppResult<()> {
+pub async fn start_forwarding(app: AppHandle, rule_id: String) -> AppResult<()> {
     ensure_rules_loaded().await;
 
     // 获取规则
@@ -296,8 +505,24 @@ pub async fn start_forwarding(rule_id: String) -> AppResult<()> {
         return Err(crate::error::AppError::from("转发已在运行中".to_string()));
     }
 
+    // 先同步绑定端口，占用时直接返回占用进程信息
+    // 套接字类目标（如 Docker socket）等同本机管理权限，只允许本机访问
+    let std_listener = if rule.target_kind == "tcp" {
+        bind_or_conflict(&app, rule.local_port, 128, "forwarder", &rule_id).await?
+    } else {
+        bind_or_conflict_on(
+            &app,
+            std::net::Ipv4Addr::LOCALHOST,
+            rule.local_port,
+            128,
+            "forwarder",
+            &rule_id,
+        )
+        .await?
+    };
+
     // 创建控制器
-    let controller = Arc::new(ForwardController::new());
+    let controller = Arc::new(ForwardController::new(&rule));
 
     // 保存控制器
     {
@@ -313,16 +538,29 @@ pub async fn start_forwarding(rule_id: String) -> AppResult<()> {
         }
     }
 
+    // 健康检查
+    let health_check = rule
+        .health_check_interval_secs
+        .filter(|s| *s > 0 && controller.socket_target.is_none());
+    if let Some(interval) = health_check {
+        let ctrl = controller.clone();
+        tokio::spawn(async move {
+            loop {
+                ctrl.targets.check_all().await;
+                tokio::time::sleep(Duration::from_secs(interval as u64)).await;
+                if ctrl.is_stopped() {
+                    break;
+                }
+            }
+        });
+    }
+
     // 启动转发任务
     let id = rule_id.clone();
     let local_port = rule.local_port;
-    let remote_host = rule.remote_host.clone();
-    let remote_port = rule.remote_port;
 
     tokio::spawn(async move {
-        if let Err(e) =
-            run_forward_server(&id, local_port, &remote_host, remote_port, controller).await
-        {
+        if let Err(e) = run_forward_server(&id, local_port, std_listener, controller).await {
             log::error!("转发服务错误: {}", e);
         }
 
@@ -340,58 +578,27 @@ pub async fn start_forwarding(rule_id: String) -> AppResult<()> {
 async fn run_forward_server(
     rule_id: &str,
     local_port: u16,
-    remote_host: &str,
-    remote_port: u16,
+    std_listener: std::net::TcpListener,
     controller: Arc<ForwardController>,
 ) -> AppResult<()> {
-    let addr: std::net::SocketAddr = format!("0.0.0.0:{}", local_port)
-        .parse()
-        .map_err(|e| crate::error::AppError::from(format!("解析地址失败: {}", e)))?;
-
-    // 使用 socket2 创建支持快速关闭的 socket
-    let socket = Socket::new(Domain::IPV4, Type::STREAM, None)
-        .map_err(|e| crate::error::AppError::from(format!("创建 socket 失败: {}", e)))?;
-
-    // 设置 SO_REUSEADDR，允许在 TIME_WAIT 状态时复用端口
-    socket
-        .set_reuse_address(true)
-        .map_err(|e| crate::error::AppError::from(format!("设置 SO_REUSEADDR 失败: {}", e)))?;
-
-    // 设置 SO_LINGER 为 0，使 socket 关闭时立即释放端口
-    socket
-        .set_linger(Some(std::time::Duration::from_secs(0)))
-        .map_err(|e| crate::error::AppError::from(format!("设置 SO_LINGER 失败: {}", e)))?;
-
-    // 设置非阻塞模式
-    socket
-        .set_nonblocking(true)
-        .map_err(|e| crate::error::AppError::from(format!("设置非阻塞模式失败: {}", e)))?;
-
-    // 绑定地址
-    socket
-        .bind(&addr.into())
-        .map_err(|e| crate::error::AppError::from(format!("绑定端口失败: {}", e)))?;
-
-    // 监听
-    socket
-        .listen(128)
-        .map_err(|e| crate::error::AppError::from(format!("监听端口失败: {}", e)))?;
-
-    // 转换为 tokio TcpListener
-    let std_listener: std::net::TcpListener = socket.into();
+    // 转换为 tokio TcpListener（端口已在 start_forwarding 中绑定）
     let listener = TcpListener::from_std(std_listener)
         .map_err(|e| crate::error::AppError::from(format!("创建 TcpListener 失败: {}", e)))?;
 
-    log::info!(
-        "转发服务启动: {} -> {}:{}",
-        local_port,
-        remote_host,
-        remote_port
-    );
+    let destination = match &controller.socket_target {
+        Some(socket) => socket.describe(),
+        None => controller
+            .targets
+            .targets
+            .iter()
+            .map(|t| t.address.as_str())
+            .collect::<Vec<_>>()
+            .join(", "),
+    };
+    log::info!("转发服务启动: {} -> {}", local_port, destination);
 
     // 连接数限制
     let semaphore = Arc::new(Semaphore::new(100));
-    let remote_addr = format!("{}:{}", remote_host, remote_port);
 
     loop {
         // 检查是否需要停止
@@ -410,7 +617,6 @@ async fn run_forward_server(
                     continue;
                 }
 
-                let remote = remote_addr.clone();
                 let ctrl = controller.clone();
                 let id = rule_id.to_string();
 
@@ -421,7 +627,7 @@ async fn run_forward_server(
                     // 更新连接数
                     update_rule_stats(&id).await;
 
-                    if let Err(e) = handle_connection(inbound, &remote, ctrl.clone()).await {
+                    if let Err(e) = handle_connection(&id, inbound, ctrl.clone()).await {
                         log::debug!("连接处理错误 {}: {}", peer_addr, e);
                     }
 
@@ -446,20 +652,19 @@ async fn run_forward_server(
 
 /// 处理单个连接
 async fn handle_connection(
+    rule_id: &str,
     mut inbound: TcpStream,
-    remote_addr: &str,
     controller: Arc<ForwardController>,
 ) -> AppResult<()> {
-    // 连接超时
-    let connect_timeout = Duration::from_secs(10);
-
-    let mut outbound = timeout(connect_timeout, TcpStream::connect(remote_addr))
-        .await
-        .map_err(|_| "连接超时".to_string())?
-        .map_err(|e| crate::error::AppError::from(format!("连接远程服务器失败: {}", e)))?;
+    let outbound: Box<dyn Upstream> = match &controller.socket_target {
+        Some(socket) => socket.connect().await?,
+        None => Box::new(controller.targets.connect().await?),
+    };
 
+    let client_addr = inbound.peer_addr()?;
+    let listen_addr = inbound.local_addr()?;
     let (mut ri, mut wi) = inbound.split();
-    let (mut ro, mut wo) = outbound.split();
+    let (mut ro, mut wo) = tokio::io::split(outbound);
 
     let ctrl1 = controller.clone();
     let ctrl2 = controller.clone();
@@ -484,6 +689,7 @@ async fn handle_connection(
                 Ok(Ok(0)) => break,
                 Ok(Ok(n)) => {
                     ctrl1.add_bytes_out(n as u64);
+                    pcap::record(rule_id, Transport::Tcp, client_addr, listen_addr, &buf[..n]);
                     if tokio::io::AsyncWriteExt::write_all(&mut wo, &buf[..n])
                         .await
                         .is_err()
@@ -515,6 +721,7 @@ async fn handle_connection(
                 Ok(Ok(0)) => break,
                 Ok(Ok(n)) => {
                     ctrl2.add_bytes_in(n as u64);
+                    pcap::record(rule_id, Transport::Tcp, listen_addr, client_addr, &buf[..n]);
                     if tokio::io::AsyncWriteExt::write_all(&mut wi, &buf[..n])
                         .await
                         .is_err()
@@ -551,6 +758,67 @@ async fn update_rule_stats(rule_id: &str) {
     }
 }
 
+/// 一条规则的指标（供 metrics 端点使用）
+pub(crate) struct ForwardMetrics {
+    pub id: String,
+    pub name: String,
+    pub local_port: u16,
+    pub running: bool,
+    pub connections: u32,
+    pub connections_total: u64,
+    pub bytes_in: u64,
+    pub bytes_out: u64,
+}
+
+/// 所有规则的指标，未运行的规则计数为 0
+pub(crate) async fn forward_metrics() -> Vec<ForwardMetrics> {
+    ensure_rules_loaded().await;
+    let stats: HashMap<String, (u32, u64, u64, u64)> = FORWARD_CONTROLLERS
+        .lock()
+        .await
+        .iter()
+        .map(|(id, c)| {
+            let (connections, bytes_in, bytes_out) = c.get_stats();
+            let total = c.connections_total.load(Ordering::Relaxed);
+            (id.clone(), (connections, total, bytes_in, bytes_out))
+        })
+        .collect();
+    let rules = FORWARD_RULES.lock().await;
+    rules
+        .values()
+        .map(|rule| {
+            let running = stats.get(&rule.id);
+            let (connections, connections_total, bytes_in, bytes_out) =
+                running.copied().unwrap_or_default();
+            ForwardMetrics {
+                id: rule.id.clone(),
+                name: rule.name.clone(),
+                local_port: rule.local_port,
+                running: running.is_some(),
+                connections,
+                connections_total,
+                bytes_in,
+                bytes_out,
+            }
+        })
+        .collect()
+}
+
+/// 运行中且无连接已超过 min_idle 的规则 (id, 名称)
+pub(crate) async fn idle_forward_rules(min_idle: Duration) -> Vec<(String, String)> {
+    let idle: Vec<String> = FORWARD_CONTROLLERS
+        .lock()
+        .await
+        .iter()
+        .filter(|(_, c)| c.idle_for().is_some_and(|d| d >= min_idle))
+        .map(|(id, _)| id.clone())
+        .collect();
+    let rules = FORWARD_RULES.lock().await;
+    idle.into_iter()
+        .filter_map(|id| rules.get(&id).map(|r| (id, r.name.clone())))
+        .collect()
+}
+
 /// 停止转发
 #[tauri::command]
 #[specta::specta]
@@ -630,16 +898,18 @@ pub async fn get_forward_rule(rule_id: String) -> AppResult<Option<ForwardRule>>
 #[specta::specta]
 pub async fn get_forward_stats(rule_id: String) -> AppResult<ForwardStats> {
     let controllers = FORWARD_CONTROLLERS.lock().await;
-    let (connections, bytes_in, bytes_out) = controllers
-        .get(&rule_id)
-        .map(|c| c.get_stats())
-        .unwrap_or((0, 0, 0));
+    let controller = controllers.get(&rule_id);
+    let (connections, bytes_in, bytes_out) = controller.map(|c| c.get_stats()).unwrap_or((0, 0, 0));
 
     Ok(ForwardStats {
         rule_id,
         connections,
         bytes_in,
         bytes_out,
+        targets: controller
+            .filter(|c| c.socket_target.is_none())
+            .map(|c| c.targets.statuses())
+            .unwrap_or_default(),
     })
 }
 
@@ -662,6 +932,8 @@ pub async fn update_forward_rule(
         .ok_or_else(|| crate::error::AppError::from(format!("规则不存在: {}", rule_id)))?;
     let old_rule = current.clone();
 
+    validate_target(&input)?;
+
     // 如果正在运行，先停止
     if current.status == "running" {
         stop_forwarding(rule_id.clone()).await?;
@@ -676,6 +948,11 @@ pub async fn update_forward_rule(
             rule.remote_host = input.remote_host;
             rule.remote_port = input.remote_port;
             rule.doc_path = input.doc_path;
+            rule.target_kind = input.target_kind.unwrap_or_else(|| "tcp".to_string());
+            rule.socket_path = input.socket_path.filter(|p| !p.trim().is_empty());
+            rule.backup_targets = input.backup_targets.unwrap_or_default();
+            rule.balance = input.balance.unwrap_or_else(|| "failover".to_string());
+            rule.health_check_interval_secs = input.health_check_interval_secs.filter(|s| *s > 0);
         }
     }
 
@@ -0,0 +1,400 @@
+// HTTP 压测 - 类似 wrk 的小型负载生成器，检查转发 / 静态服务背后的本地接口
+//
+// concurrency 个 worker 各自循环请求直到时长结束，每秒推送一次 `http-bench-progress`。
+// 延迟记录在对数分桶直方图里（1ms 内精确到微秒，之后相对误差 < 2%），长时间压测内存也不增长。
+// 传入 job_id 时登记为可取消操作，前端可用 cancel_operation(job_id) 提前结束并拿到已有结果。
+
+use crate::commands::operations::Operation;
+use crate::error::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+const MAX_CONCURRENCY: u32 = 512;
+
+const MAX_DURATION_SECS: u32 = 600;
+
+const DEFAULT_TIMEOUT_MS: u64 = 10_000;
+
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 报告中保留的不同错误信息条数
+const MAX_ERROR_KINDS: usize = 10;
+
+/// 1024µs 以下每微秒一个桶，之后每个 2 的幂区间再分 64 个桶
+const LINEAR_BUCKETS: u64 = 1024;
+const SUB_BUCKETS: u64 = 64;
+const BUCKET_COUNT: usize = (LINEAR_BUCKETS + 54 * SUB_BUCKETS) as usize;
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpBenchLatency {
+    pub min_ms: f64,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpBenchStatusCount {
+    pub status: u16,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpBenchErrorCount {
+    pub message: String,
+    pub count: u64,
+}
+
+/// 压测报告；进度事件与最终结果共用，finished 区分
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpBenchReport {
+    pub job_id: String,
+    pub url: String,
+    pub method: String,
+    pub concurrency: u32,
+    pub elapsed_ms: u64,
+    /// 收到响应的请求数（含 4xx / 5xx）
+    pub requests: u64,
+    /// 连接失败、超时等未收到响应的请求数
+    pub errors: u64,
+    /// 状态码 >= 400 的响应数
+    pub non_success: u64,
+    /// 整体平均每秒请求数
+    pub rps: f64,
+    /// 最近一个进度间隔内的每秒请求数
+    pub current_rps: f64,
+    pub bytes_received: u64,
+    /// 仅统计收到响应的请求；没有响应时为 None
+    pub latency: Option<HttpBenchLatency>,
+    pub status_counts: Vec<HttpBenchStatusCount>,
+    pub error_kinds: Vec<HttpBenchErrorCount>,
+    pub finished: bool,
+}
+
+fn bucket_index(micros: u64) -> usize {
+    if micros < LINEAR_BUCKETS {
+        return micros as usize;
+    }
+    let exp = 63 - micros.leading_zeros() as u64;
+    let mantissa = (micros >> (exp - 6)) - SUB_BUCKETS;
+    ((LINEAR_BUCKETS + (exp - 10) * SUB_BUCKETS + mantissa) as usize).min(BUCKET_COUNT - 1)
+}
+
+/// 桶的下界（微秒）
+fn bucket_value(index: usize) -> u64 {
+    let index = index as u64;
+    if index < LINEAR_BUCKETS {
+        return index;
+    }
+    let exp = (index - LINEAR_BUCKETS) / SUB_BUCKETS + 10;
+    let mantissa = (index - LINEAR_BUCKETS) % SUB_BUCKETS + SUB_BUCKETS;
+    mantissa << (exp - 6)
+}
+
+struct Recorder {
+    histogram: Vec<u64>,
+    requests: u64,
+    errors: u64,
+    non_success: u64,
+    bytes_received: u64,
+    total_micros: u64,
+    min_micros: u64,
+    max_micros: u64,
+    statuses: HashMap<u16, u64>,
+    error_kinds: Vec<HttpBenchErrorCount>,
+}
+
+impl Recorder {
+    fn new() -> Self {
+        Self {
+            histogram: vec![0; BUCKET_COUNT],
+            requests: 0,
+            errors: 0,
+            non_success: 0,
+            bytes_received: 0,
+            total_micros: 0,
+            min_micros: u64::MAX,
+            max_micros: 0,
+            statuses: HashMap::new(),
+            error_kinds: Vec::new(),
+        }
+    }
+
+    fn record_response(&mut self, status: u16, micros: u64, bytes: u64) {
+        self.requests += 1;
+        if status >= 400 {
+            self.non_success += 1;
+        }
+        self.bytes_received += bytes;
+        self.total_micros += micros;
+        self.min_micros = self.min_micros.min(micros);
+        self.max_micros = self.max_micros.max(micros);
+        self.histogram[bucket_index(micros)] += 1;
+        *self.statuses.entry(status).or_insert(0) += 1;
+    }
+
+    fn record_error(&mut self, message: String) {
+        self.errors += 1;
+        if let Some(kind) = self.error_kinds.iter_mut().find(|k| k.message == message) {
+            kind.count += 1;
+        } else if self.error_kinds.len() < MAX_ERROR_KINDS {
+            self.error_kinds
+                .push(HttpBenchErrorCount { message, count: 1 });
+        }
+    }
+
+    fn percentile(&self, p: f64) -> u64 {
+        let target = ((self.requests as f64) * p).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (index, count) in self.histogram.iter().enumerate() {
+            seen += count;
+            if seen >= target {
+                // 分桶下界可能略小于真实最小值
+                return bucket_value(index).clamp(self.min_micros, self.max_micros);
+            }
+        }
+        self.max_micros
+    }
+
+    fn latency(&self) -> Option<HttpBenchLatency> {
+        if self.requests == 0 {
+            return None;
+        }
+        let ms = |micros: u64| micros as f64 / 1000.0;
+        Some(HttpBenchLatency {
+            min_ms: ms(self.min_micros),
+            mean_ms: self.total_micros as f64 / self.requests as f64 / 1000.0,
+            p50_ms: ms(self.percentile(0.5)),
+            p90_ms: ms(self.percentile(0.9)),
+            p99_ms: ms(self.percentile(0.99)),
+            max_ms: ms(self.max_micros),
+        })
+    }
+}
+
+/// 错误信息去掉 URL，便于同类错误聚合
+fn error_message(e: reqwest::Error) -> String {
+    if e.is_timeout() {
+        "请求超时".to_string()
+    } else if e.is_connect() {
+        "连接失败".to_string()
+    } else {
+        e.without_url().to_string()
+    }
+}
+
+struct BenchRequest {
+    client: reqwest::Client,
+    method: reqwest::Method,
+    url: reqwest::Url,
+    headers: reqwest::header::HeaderMap,
+    body: Option<String>,
+}
+
+async fn worker(request: Arc<BenchRequest>, recorder: Arc<Mutex<Recorder>>, stop: Arc<AtomicBool>) {
+    while !stop.load(Ordering::Relaxed) {
+        let mut builder = request
+            .client
+            .request(request.method.clone(), request.url.clone())
+            .headers(request.headers.clone());
+        if let Some(body) = &request.body {
+            builder = builder.body(body.clone());
+        }
+        let started = Instant::now();
+        let result = match builder.send().await {
+            Ok(response) => {
+                let status = response.status().as_u16();
+                // 读完响应体才算一次完整请求
+                response
+                    .bytes()
+                    .await
+                    .map(|body| (status, body.len() as u64))
+            }
+            Err(e) => Err(e),
+        };
+        let micros = started.elapsed().as_micros() as u64;
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+        let mut recorder = recorder.lock().unwrap_or_else(|e| e.into_inner());
+        match result {
+            Ok((status, bytes)) => recorder.record_response(status, micros, bytes),
+            Err(e) => recorder.record_error(error_message(e)),
+        }
+    }
+}
+
+struct ReportContext<'a> {
+    job_id: &'a str,
+    url: &'a str,
+    method: &'a str,
+    concurrency: u32,
+}
+
+fn build_report(
+    ctx: &ReportContext,
+    recorder: &Recorder,
+    elapsed: Duration,
+    current_rps: f64,
+    finished: bool,
+) -> HttpBenchReport {
+    let secs = elapsed.as_secs_f64();
+    let mut status_counts: Vec<HttpBenchStatusCount> = recorder
+        .statuses
+        .iter()
+        .map(|(status, count)| HttpBenchStatusCount {
+            status: *status,
+            count: *count,
+        })
+        .collect();
+    status_counts.sort_by_key(|s| s.status);
+    HttpBenchReport {
+        job_id: ctx.job_id.to_string(),
+        url: ctx.url.to_string(),
+        method: ctx.method.to_string(),
+        concurrency: ctx.concurrency,
+        elapsed_ms: elapsed.as_millis() as u64,
+        requests: recorder.requests,
+        errors: recorder.errors,
+        non_success: recorder.non_success,
+        rps: if secs > 0.0 {
+            (recorder.requests + recorder.errors) as f64 / secs
+        } else {
+            0.0
+        },
+        current_rps,
+        bytes_received: recorder.bytes_received,
+        latency: recorder.latency(),
+        status_counts,
+        error_kinds: recorder.error_kinds.clone(),
+        finished,
+    }
+}
+
+/// 对 URL 做固定时长的并发压测，返回 RPS、延迟分位数与错误统计
+#[tauri::command]
+#[specta::specta]
+#[allow(clippy::too_many_arguments)]
+pub async fn benchmark_http(
+    app: AppHandle,
+    url: String,
+    concurrency: u32,
+    duration_secs: u32,
+    method: Option<String>,
+    body: Option<String>,
+    headers: Option<HashMap<String, String>>,
+    timeout_ms: Option<u64>,
+    job_id: Option<String>,
+) -> AppResult<HttpBenchReport> {
+    let parsed = reqwest::Url::parse(url.trim())
+        .map_err(|e| AppError::invalid(format!("URL 无效: {}", e)))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(AppError::invalid("仅支持 http / https 地址"));
+    }
+    if concurrency == 0 || concurrency > MAX_CONCURRENCY {
+        return Err(AppError::invalid(format!(
+            "并发数需在 1-{} 之间",
+            MAX_CONCURRENCY
+        )));
+    }
+    if duration_secs == 0 || duration_secs > MAX_DURATION_SECS {
+        return Err(AppError::invalid(format!(
+            "压测时长需在 1-{} 秒之间",
+            MAX_DURATION_SECS
+        )));
+    }
+    let method_name = method
+        .as_deref()
+        .map(|m| m.trim().to_uppercase())
+        .filter(|m| !m.is_empty())
+        .unwrap_or_else(|| "GET".to_string());
+    let http_method = reqwest::Method::from_bytes(method_name.as_bytes())
+        .map_err(|_| AppError::invalid(format!("不支持的请求方法: {}", method_name)))?;
+
+    let mut header_map = reqwest::header::HeaderMap::new();
+    for (name, value) in headers.unwrap_or_default() {
+        let header_name = reqwest::header::HeaderName::from_bytes(name.trim().as_bytes())
+            .map_err(|_| AppError::invalid(format!("请求头名称无效: {}", name)))?;
+        let header_value = reqwest::header::HeaderValue::from_str(value.trim())
+            .map_err(|_| AppError::invalid(format!("请求头 {} 的值无效", name)))?;
+        header_map.insert(header_name, header_value);
+    }
+
+    let client = crate::http_client::builder()
+        .timeout(Duration::from_millis(
+            timeout_ms.filter(|t| *t > 0).unwrap_or(DEFAULT_TIMEOUT_MS),
+        ))
+        .pool_max_idle_per_host(concurrency as usize)
+        .build()
+        .map_err(|e| AppError::other(format!("创建 HTTP 客户端失败: {}", e)))?;
+
+    let op = Operation::begin(job_id, "HTTP 压测", None)?;
+    let ctx = ReportContext {
+        job_id: op.id(),
+        url: parsed.as_str(),
+        method: &method_name,
+        concurrency,
+    };
+    let request = Arc::new(BenchRequest {
+        client,
+        method: http_method,
+        url: parsed.clone(),
+        headers: header_map,
+        body: body.filter(|b| !b.is_empty()),
+    });
+    let recorder = Arc::new(Mutex::new(Recorder::new()));
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let started = Instant::now();
+    let deadline = started + Duration::from_secs(duration_secs as u64);
+    let workers: Vec<_> = (0..concurrency)
+        .map(|_| tokio::spawn(worker(request.clone(), recorder.clone(), stop.clone())))
+        .collect();
+
+    let mut last_total = 0u64;
+    let mut last_tick = started;
+    let mut current_rps = 0.0;
+    loop {
+        let now = Instant::now();
+        if now >= deadline || op.check().is_err() {
+            break;
+        }
+        tokio::time::sleep(PROGRESS_INTERVAL.min(deadline - now)).await;
+
+        let report = {
+            let recorder = recorder.lock().unwrap_or_else(|e| e.into_inner());
+            let total = recorder.requests + recorder.errors;
+            let tick_secs = last_tick.elapsed().as_secs_f64();
+            if tick_secs > 0.0 {
+                current_rps = (total - last_total) as f64 / tick_secs;
+            }
+            last_total = total;
+            last_tick = Instant::now();
+            build_report(&ctx, &recorder, started.elapsed(), current_rps, false)
+        };
+        let _ = app.emit("http-bench-progress", &report);
+    }
+
+    // 时长到达后不再等待进行中的请求，直接丢弃
+    stop.store(true, Ordering::Relaxed);
+    for handle in workers {
+        handle.abort();
+    }
+    let elapsed = started.elapsed();
+    let report = {
+        let recorder = recorder.lock().unwrap_or_else(|e| e.into_inner());
+        build_report(&ctx, &recorder, elapsed, current_rps, true)
+    };
+    let _ = app.emit("http-bench-progress", &report);
+    Ok(report)
+}
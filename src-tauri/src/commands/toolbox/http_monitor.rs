@@ -0,0 +1,307 @@
+// HTTP 健康检查模块 - 定时请求 URL，校验状态码/响应体，统计可用率与延迟分位数（调度与状态记录见 monitor.rs）
+
+use super::monitor::{self, Monitor, UNKNOWN};
+use super::{
+    current_time, generate_id, HttpCheckRecord, HttpMonitor, HttpMonitorInput, HttpMonitorStats,
+};
+use crate::error::AppResult;
+use crate::storage::config::StorageConfig;
+use crate::storage::PersistedStore;
+use once_cell::sync::Lazy;
+use std::time::Instant;
+use tauri::AppHandle;
+use tokio::time::Duration;
+
+/// 监控项存储 - 延迟初始化
+static HTTP_MONITORS: Lazy<PersistedStore<HttpMonitor>> = Lazy::new(|| {
+    PersistedStore::new(
+        "httpMonitors",
+        "HTTP 监控",
+        StorageConfig::http_monitors_file,
+        |m: &HttpMonitor| m.id.clone(),
+    )
+    .on_load(|m: &mut HttpMonitor| {
+        // 重启后状态未知，等待首次检查
+        m.status = UNKNOWN.to_string();
+    })
+});
+
+/// 发起一次请求并按期望判定 up/down
+async fn check(monitor: &HttpMonitor) -> HttpCheckRecord {
+    let time = current_time();
+    let fail = |status_code: Option<u16>, latency_ms: Option<u64>, error: String| HttpCheckRecord {
+        time: time.clone(),
+        status: "down".to_string(),
+        status_code,
+        latency_ms,
+        error: Some(error),
+    };
+
+    let method = match reqwest::Method::from_bytes(monitor.method.to_uppercase().as_bytes()) {
+        Ok(m) => m,
+        Err(_) => return fail(None, None, format!("无效的请求方法: {}", monitor.method)),
+    };
+    let client = match crate::http_client::builder()
+        .timeout(Duration::from_millis(monitor.timeout_ms))
+        .build()
+    {
+        Ok(c) => c,
+        Err(e) => return fail(None, None, format!("创建 HTTP 客户端失败: {}", e)),
+    };
+
+    let start = Instant::now();
+    let response = match client.request(method, &monitor.url).send().await {
+        Ok(r) => r,
+        Err(e) => return fail(None, None, format!("请求失败: {}", e)),
+    };
+    let code = response.status().as_u16();
+
+    // 只有配置了 body 校验才读取响应体，延迟按读完计
+    let body = if monitor.body_contains.is_some() {
+        response.text().await.ok()
+    } else {
+        None
+    };
+    let latency = Some(start.elapsed().as_millis() as u64);
+
+    let status_ok = match monitor.expected_status {
+        Some(expected) => code == expected,
+        None => (200..300).contains(&code),
+    };
+    if !status_ok {
+        return fail(Some(code), latency, format!("状态码不符合预期: {}", code));
+    }
+    if let Some(ref needle) = monitor.body_contains {
+        if !body.unwrap_or_default().contains(needle.as_str()) {
+            return fail(Some(code), latency, format!("响应体未包含: {}", needle));
+        }
+    }
+
+    HttpCheckRecord {
+        time,
+        status: "up".to_string(),
+        status_code: Some(code),
+        latency_ms: latency,
+        error: None,
+    }
+}
+
+impl Monitor for HttpMonitor {
+    type Record = HttpCheckRecord;
+
+    const EVENT: &'static str = "http-monitor-updated";
+    /// 分位数统计基于这段窗口
+    const MAX_HISTORY: usize = 500;
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn interval_secs(&self) -> u64 {
+        self.interval_secs
+    }
+
+    fn status(&self) -> &str {
+        &self.status
+    }
+
+    async fn probe(&self) -> HttpCheckRecord {
+        check(self).await
+    }
+
+    fn record_summary(record: &HttpCheckRecord) -> (&str, &str) {
+        (&record.time, &record.status)
+    }
+
+    fn apply(&mut self, record: &HttpCheckRecord) {
+        self.status = record.status.clone();
+        self.last_error = record.error.clone();
+        self.last_checked = Some(record.time.clone());
+    }
+
+    fn set_last_change(&mut self, time: String) {
+        self.last_change = Some(time);
+    }
+
+    fn history_mut(&mut self) -> &mut Vec<HttpCheckRecord> {
+        &mut self.history
+    }
+
+    fn transition_notice(&self) -> (&'static str, String, String) {
+        if self.status == "up" {
+            ("success", format!("{} 已恢复", self.name), self.url.clone())
+        } else {
+            (
+                "error",
+                format!("{} 健康检查失败", self.name),
+                self.last_error.clone().unwrap_or_else(|| self.url.clone()),
+            )
+        }
+    }
+}
+
+/// 启动 HTTP 监控调度：按各自 interval 检查启用的监控项
+pub fn spawn_http_monitor(app: AppHandle) {
+    monitor::spawn_scheduler(app, &HTTP_MONITORS);
+}
+
+/// 最近邻法取分位数（输入需已排序）
+fn percentile(sorted: &[u64], p: f64) -> Option<u64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+fn validate_input(input: &HttpMonitorInput) -> AppResult<()> {
+    let parsed = url::Url::parse(input.url.trim())
+        .map_err(|e| crate::error::AppError::from(format!("无效的 URL: {}", e)))?;
+    if parsed.scheme() != "http" && parsed.scheme() != "https" {
+        return Err(crate::error::AppError::from(
+            "仅支持 http/https 地址".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// 添加 HTTP 监控
+#[tauri::command]
+#[specta::specta]
+pub async fn add_http_monitor(input: HttpMonitorInput) -> AppResult<HttpMonitor> {
+    HTTP_MONITORS.ensure_loaded().await;
+    validate_input(&input)?;
+
+    let monitor = HttpMonitor {
+        id: generate_id(),
+        name: if input.name.trim().is_empty() {
+            input.url.trim().to_string()
+        } else {
+            input.name
+        },
+        url: input.url.trim().to_string(),
+        method: input.method.unwrap_or_else(|| "GET".to_string()),
+        expected_status: input.expected_status,
+        body_contains: input.body_contains.filter(|s| !s.is_empty()),
+        interval_secs: input.interval_secs.unwrap_or(30).max(1),
+        timeout_ms: input.timeout_ms.unwrap_or(5000),
+        enabled: input.enabled.unwrap_or(true),
+        status: UNKNOWN.to_string(),
+        last_error: None,
+        last_checked: None,
+        last_change: None,
+        history: Vec::new(),
+        created_at: current_time(),
+    };
+
+    HTTP_MONITORS
+        .lock()
+        .await
+        .insert(monitor.id.clone(), monitor.clone());
+    HTTP_MONITORS.save().await?;
+    Ok(monitor)
+}
+
+/// 更新 HTTP 监控配置
+#[tauri::command]
+#[specta::specta]
+pub async fn update_http_monitor(id: String, input: HttpMonitorInput) -> AppResult<HttpMonitor> {
+    HTTP_MONITORS.ensure_loaded().await;
+    validate_input(&input)?;
+
+    let updated = {
+        let mut monitors = HTTP_MONITORS.lock().await;
+        let m = monitors.get_mut(&id).ok_or("监控项不存在")?;
+        let url_changed = m.url != input.url.trim();
+        if !input.name.trim().is_empty() {
+            m.name = input.name;
+        }
+        m.url = input.url.trim().to_string();
+        if let Some(method) = input.method {
+            m.method = method;
+        }
+        m.expected_status = input.expected_status;
+        m.body_contains = input.body_contains.filter(|s| !s.is_empty());
+        if let Some(interval) = input.interval_secs {
+            m.interval_secs = interval.max(1);
+        }
+        if let Some(t) = input.timeout_ms {
+            m.timeout_ms = t;
+        }
+        if let Some(enabled) = input.enabled {
+            m.enabled = enabled;
+        }
+        if url_changed {
+            m.status = UNKNOWN.to_string();
+            m.last_error = None;
+            m.history.clear();
+        }
+        m.clone()
+    };
+
+    HTTP_MONITORS.save().await?;
+    Ok(updated)
+}
+
+/// 删除 HTTP 监控
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_http_monitor(id: String) -> AppResult<()> {
+    HTTP_MONITORS.ensure_loaded().await;
+    HTTP_MONITORS.lock().await.remove(&id);
+    HTTP_MONITORS.save().await
+}
+
+/// 获取所有 HTTP 监控
+#[tauri::command]
+#[specta::specta]
+pub async fn get_http_monitors() -> AppResult<Vec<HttpMonitor>> {
+    HTTP_MONITORS.ensure_loaded().await;
+    let mut list: Vec<HttpMonitor> = HTTP_MONITORS.lock().await.values().cloned().collect();
+    list.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    Ok(list)
+}
+
+/// 立即检查一次
+#[tauri::command]
+#[specta::specta]
+pub async fn check_http_monitor_now(app: AppHandle, id: String) -> AppResult<HttpMonitor> {
+    HTTP_MONITORS.ensure_loaded().await;
+    monitor::run_check(&app, &HTTP_MONITORS, &id).await
+}
+
+/// 可用率与延迟分位数（基于保留的历史窗口）
+#[tauri::command]
+#[specta::specta]
+pub async fn get_http_monitor_stats(id: String) -> AppResult<HttpMonitorStats> {
+    HTTP_MONITORS.ensure_loaded().await;
+    let monitors = HTTP_MONITORS.lock().await;
+    let m = monitors.get(&id).ok_or("监控项不存在")?;
+
+    let checks = m.history.len();
+    let up = m.history.iter().filter(|r| r.status == "up").count();
+    let mut latencies: Vec<u64> = m
+        .history
+        .iter()
+        .filter(|r| r.status == "up")
+        .filter_map(|r| r.latency_ms)
+        .collect();
+    latencies.sort_unstable();
+
+    Ok(HttpMonitorStats {
+        monitor_id: id.clone(),
+        checks: checks as u32,
+        uptime_percent: if checks == 0 {
+            0.0
+        } else {
+            up as f64 * 100.0 / checks as f64
+        },
+        latency_p50: percentile(&latencies, 50.0),
+        latency_p90: percentile(&latencies, 90.0),
+        latency_p99: percentile(&latencies, 99.0),
+    })
+}
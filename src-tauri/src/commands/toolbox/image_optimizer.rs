@@ -0,0 +1,319 @@
+// 图片压缩 - 批量压缩目录中的 PNG / JPEG / WebP，结果写入另一个目录，原图不动
+//
+// PNG 用 oxipng 无损优化；JPEG 按 quality 重新编码（EXIF 方向先应用到像素上，ICC 配置随图保留，
+// 其余元数据不保留；CMYK JPEG 转 RGB 会偏色，直接输出原图）；WebP 只能无损重编码（image 不带
+// 有损编码器），quality 对它不起作用。压缩后没有变小的文件直接复制原图，
+// 保证输出目录是一份完整可用的副本。按文件推送 `image-optimize-progress`，传入 job_id 时可取消。
+
+use crate::commands::operations::Operation;
+use crate::error::{AppError, AppResult};
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::{DynamicImage, ImageDecoder, ImageEncoder, ImageFormat, ImageReader};
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tauri::{AppHandle, Emitter};
+
+const DEFAULT_JPEG_QUALITY: u8 = 80;
+
+/// oxipng 预设等级，0 最快，6 最慢
+const DEFAULT_PNG_LEVEL: u8 = 2;
+
+const MAX_PNG_LEVEL: u8 = 6;
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ImageOptimizeOptions {
+    /// JPEG 质量 1-100；只作用于 JPEG，WebP 始终无损重编码
+    pub jpeg_quality: u8,
+    /// oxipng 等级 0-6
+    pub png_level: u8,
+    /// 去掉 PNG 中不影响显示的元数据块
+    pub strip_metadata: bool,
+    /// 包含子目录，输出时保留相对路径
+    pub recursive: bool,
+}
+
+impl Default for ImageOptimizeOptions {
+    fn default() -> Self {
+        Self {
+            jpeg_quality: DEFAULT_JPEG_QUALITY,
+            png_level: DEFAULT_PNG_LEVEL,
+            strip_metadata: true,
+            recursive: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct OptimizedImage {
+    /// 相对源目录，以 / 分隔
+    pub relative_path: String,
+    /// "png" | "jpeg" | "webp"
+    pub format: String,
+    pub original_bytes: u64,
+    /// 失败时为 0
+    pub optimized_bytes: u64,
+    /// 压缩后没有变小，输出的是原图
+    pub kept_original: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageOptimizeReport {
+    pub output_dir: String,
+    pub files: Vec<OptimizedImage>,
+    /// 以下统计不含失败的文件
+    pub original_bytes: u64,
+    pub optimized_bytes: u64,
+    pub failed: u32,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageOptimizeProgress {
+    pub job_id: String,
+    pub relative_path: String,
+    pub processed: u32,
+    pub total: u32,
+}
+
+fn format_of(path: &Path) -> Option<ImageFormat> {
+    match ImageFormat::from_path(path).ok()? {
+        f @ (ImageFormat::Png | ImageFormat::Jpeg | ImageFormat::WebP) => Some(f),
+        _ => None,
+    }
+}
+
+fn format_name(format: ImageFormat) -> &'static str {
+    match format {
+        ImageFormat::Png => "png",
+        ImageFormat::Jpeg => "jpeg",
+        _ => "webp",
+    }
+}
+
+/// 列出支持的图片，跳过输出目录（输出目录在源目录内时）
+fn collect_images(
+    root: &Path,
+    output: &Path,
+    recursive: bool,
+) -> AppResult<Vec<(PathBuf, ImageFormat)>> {
+    let mut images = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            let path = entry.path();
+            if file_type.is_dir() {
+                if recursive && path != output {
+                    stack.push(path);
+                }
+            } else if file_type.is_file() {
+                if let Some(format) = format_of(&path) {
+                    images.push((path, format));
+                }
+            }
+        }
+    }
+    images.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(images)
+}
+
+/// JPEG 帧头（SOFn）里的颜色分量数，4 表示 CMYK / YCCK
+fn jpeg_components(data: &[u8]) -> Option<u8> {
+    let mut i = 2;
+    while i + 4 <= data.len() {
+        if data[i] != 0xFF {
+            return None;
+        }
+        let marker = data[i + 1];
+        if marker == 0xFF {
+            i += 1;
+            continue;
+        }
+        if matches!(marker, 0xC0..=0xCF) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+            // FF Cn | 长度(2) | 精度(1) | 高(2) | 宽(2) | 分量数(1)
+            return data.get(i + 9).copied();
+        }
+        i += 2 + u16::from_be_bytes([data[i + 2], data[i + 3]]) as usize;
+    }
+    None
+}
+
+/// 解码并应用 EXIF 方向，同时取出 ICC 配置
+fn decode(data: &[u8]) -> AppResult<(DynamicImage, Option<Vec<u8>>)> {
+    let mut decoder = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()?
+        .into_decoder()
+        .map_err(|e| AppError::invalid(format!("无法解码图片: {}", e)))?;
+    let orientation = decoder
+        .orientation()
+        .map_err(|e| AppError::invalid(format!("无法解码图片: {}", e)))?;
+    let icc = decoder
+        .icc_profile()
+        .map_err(|e| AppError::invalid(format!("无法读取 ICC 配置: {}", e)))?;
+    let mut image = DynamicImage::from_decoder(decoder)
+        .map_err(|e| AppError::invalid(format!("无法解码图片: {}", e)))?;
+    image.apply_orientation(orientation);
+    Ok((image, icc))
+}
+
+fn optimize(
+    data: &[u8],
+    format: ImageFormat,
+    options: &ImageOptimizeOptions,
+) -> AppResult<Vec<u8>> {
+    if format == ImageFormat::Png {
+        let mut png = oxipng::Options::from_preset(options.png_level.min(MAX_PNG_LEVEL));
+        if options.strip_metadata {
+            png.strip = oxipng::StripChunks::Safe;
+        }
+        return oxipng::optimize_from_memory(data, &png)
+            .map_err(|e| AppError::invalid(format!("PNG 优化失败: {}", e)));
+    }
+
+    // 解码器把 CMYK 粗略转成 RGB，重新编码会偏色；原样输出（大小相同，记为保留原图）
+    if format == ImageFormat::Jpeg && jpeg_components(data) == Some(4) {
+        return Ok(data.to_vec());
+    }
+
+    let (image, icc) = decode(data)?;
+    let mut out = Vec::new();
+    if format == ImageFormat::Jpeg {
+        let quality = options.jpeg_quality.clamp(1, 100);
+        let mut encoder = JpegEncoder::new_with_quality(&mut out, quality);
+        if let Some(icc) = icc {
+            encoder
+                .set_icc_profile(icc)
+                .map_err(|e| AppError::internal(format!("JPEG 写入 ICC 配置失败: {}", e)))?;
+        }
+        // 灰度图保持单通道，避免转成 RGB 后体积变大、ICC 配置与像素不匹配
+        let result = if image.color().has_color() {
+            image.to_rgb8().write_with_encoder(encoder)
+        } else {
+            image.to_luma8().write_with_encoder(encoder)
+        };
+        result.map_err(|e| AppError::internal(format!("JPEG 编码失败: {}", e)))?;
+    } else {
+        let mut encoder = WebPEncoder::new_lossless(&mut out);
+        if let Some(icc) = icc {
+            encoder
+                .set_icc_profile(icc)
+                .map_err(|e| AppError::internal(format!("WebP 写入 ICC 配置失败: {}", e)))?;
+        }
+        image
+            .write_with_encoder(encoder)
+            .map_err(|e| AppError::internal(format!("WebP 编码失败: {}", e)))?;
+    }
+    Ok(out)
+}
+
+fn optimize_file(
+    source: &Path,
+    target: &Path,
+    format: ImageFormat,
+    options: &ImageOptimizeOptions,
+) -> AppResult<(u64, u64, bool)> {
+    let data = std::fs::read(source)?;
+    let optimized = optimize(&data, format, options)?;
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let kept_original = optimized.len() >= data.len();
+    let written = if kept_original { &data } else { &optimized };
+    std::fs::write(target, written)?;
+    Ok((data.len() as u64, written.len() as u64, kept_original))
+}
+
+/// 批量压缩 source_dir 中的图片，写入 output_dir（需与源目录不同）
+#[tauri::command]
+#[specta::specta]
+pub async fn optimize_images(
+    app: AppHandle,
+    source_dir: String,
+    output_dir: String,
+    options: Option<ImageOptimizeOptions>,
+    job_id: Option<String>,
+) -> AppResult<ImageOptimizeReport> {
+    let source = Path::new(&source_dir);
+    if !source.is_dir() {
+        return Err(AppError::invalid(format!("目录不存在: {}", source_dir)));
+    }
+    if output_dir.trim().is_empty() {
+        return Err(AppError::invalid("请选择输出目录"));
+    }
+    std::fs::create_dir_all(&output_dir)?;
+    let source = source.canonicalize()?;
+    let output = Path::new(&output_dir).canonicalize()?;
+    if source == output {
+        return Err(AppError::invalid("输出目录不能与源目录相同"));
+    }
+    let options = options.unwrap_or_default();
+
+    let op = Operation::begin(job_id, "压缩图片", None)?;
+    let started = Instant::now();
+    let files = tokio::task::spawn_blocking(move || -> AppResult<Vec<OptimizedImage>> {
+        let images = collect_images(&source, &output, options.recursive)?;
+        let total = images.len() as u32;
+        let mut files = Vec::with_capacity(images.len());
+        for (i, (path, format)) in images.into_iter().enumerate() {
+            op.check()?;
+            let relative = path.strip_prefix(&source).unwrap_or(&path);
+            let relative_path = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            let _ = app.emit(
+                "image-optimize-progress",
+                ImageOptimizeProgress {
+                    job_id: op.id().to_string(),
+                    relative_path: relative_path.clone(),
+                    processed: i as u32,
+                    total,
+                },
+            );
+
+            let mut file = OptimizedImage {
+                relative_path,
+                format: format_name(format).to_string(),
+                original_bytes: 0,
+                optimized_bytes: 0,
+                kept_original: false,
+                error: None,
+            };
+            match optimize_file(&path, &output.join(relative), format, &options) {
+                Ok((original, optimized, kept_original)) => {
+                    file.original_bytes = original;
+                    file.optimized_bytes = optimized;
+                    file.kept_original = kept_original;
+                }
+                Err(e) => {
+                    file.original_bytes = std::fs::metadata(&path).map_or(0, |m| m.len());
+                    file.error = Some(e.to_string());
+                }
+            }
+            files.push(file);
+        }
+        Ok(files)
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("压缩图片失败: {}", e)))??;
+
+    let succeeded = files.iter().filter(|f| f.error.is_none());
+    Ok(ImageOptimizeReport {
+        output_dir,
+        original_bytes: succeeded.clone().map(|f| f.original_bytes).sum(),
+        optimized_bytes: succeeded.map(|f| f.optimized_bytes).sum(),
+        failed: files.iter().filter(|f| f.error.is_some()).count() as u32,
+        files,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+    })
+}
@@ -0,0 +1,90 @@
+// 本机 HTTP 端点 - 下载交接、指标端点共用的启停逻辑
+//
+// 只监听 127.0.0.1。同一端口且仍在运行时重复启动直接返回；换端口时先通知旧服务
+// 优雅退出再启动新的。各模块只提供路由与自己的状态信息。
+
+use crate::error::{AppError, AppResult};
+use axum::Router;
+use std::net::SocketAddr;
+use tokio::sync::{oneshot, Mutex};
+
+pub(crate) struct LocalEndpoint {
+    /// 日志与错误信息中的名称
+    label: &'static str,
+    running: Mutex<Option<Running>>,
+}
+
+struct Running {
+    port: u16,
+    shutdown: Option<oneshot::Sender<()>>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Running {
+    fn stop(mut self) {
+        if let Some(tx) = self.shutdown.take() {
+            let _ = tx.send(());
+        }
+        self.task.abort();
+    }
+}
+
+impl LocalEndpoint {
+    pub(crate) fn new(label: &'static str) -> Self {
+        Self {
+            label,
+            running: Mutex::new(None),
+        }
+    }
+
+    /// 在 127.0.0.1:port 上启动；已在同一端口运行时不做任何事
+    pub(crate) async fn start(&self, port: u16, router: Router) -> AppResult<()> {
+        let mut guard = self.running.lock().await;
+        if let Some(existing) = guard.as_ref() {
+            if existing.port == port && !existing.task.is_finished() {
+                return Ok(());
+            }
+        }
+        if let Some(old) = guard.take() {
+            old.stop();
+        }
+
+        let addr = SocketAddr::from(([127, 0, 0, 1], port));
+        let listener = tokio::net::TcpListener::bind(addr)
+            .await
+            .map_err(|e| AppError::from(format!("{}绑定 {} 失败: {}", self.label, addr, e)))?;
+        let (tx, rx) = oneshot::channel::<()>();
+        let label = self.label;
+        let task = tokio::spawn(async move {
+            let server = axum::serve(listener, router).with_graceful_shutdown(async {
+                let _ = rx.await;
+            });
+            if let Err(e) = server.await {
+                log::error!("{}异常退出: {}", label, e);
+            }
+        });
+
+        *guard = Some(Running {
+            port,
+            shutdown: Some(tx),
+            task,
+        });
+        Ok(())
+    }
+
+    pub(crate) async fn stop(&self) {
+        if let Some(running) = self.running.lock().await.take() {
+            running.stop();
+        }
+    }
+
+    /// 正在运行时返回监听端口
+    pub(crate) async fn running_port(&self) -> Option<u16> {
+        self.running
+            .lock()
+            .await
+            .as_ref()
+            .filter(|r| !r.task.is_finished())
+            .map(|r| r.port)
+    }
+}
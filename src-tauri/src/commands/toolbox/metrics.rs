@@ -0,0 +1,276 @@
+// Prometheus 指标端点
+//
+// 设置中启用后在 127.0.0.1:<metrics_port>/metrics 以 Prometheus 文本格式输出：
+//   - 端口转发：当前连接数、连接总数、入站 / 出站字节数
+//   - 静态服务：处理中的请求数、按状态码类别的请求数、响应字节数
+//   - 下载：各状态的任务数、已下载字节数、当前总速度
+// 计数器在规则 / 服务启动时从 0 开始，停止后归零，Prometheus 的 rate() 会按重置处理。
+
+use super::downloader::get_download_tasks;
+use super::forwarder::forward_metrics;
+use super::local_endpoint::LocalEndpoint;
+use super::server::server_metrics;
+use crate::commands::settings::load_app_settings;
+use crate::error::AppResult;
+use crate::storage::AppSettings;
+use axum::{
+    http::{header, StatusCode},
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+static ENDPOINT: Lazy<LocalEndpoint> = Lazy::new(|| LocalEndpoint::new("指标端点"));
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsStatus {
+    pub enabled: bool,
+    pub running: bool,
+    pub port: u16,
+    /// 抓取地址（运行中才有）
+    pub endpoint: Option<String>,
+}
+
+/// 启动时按设置开启端点
+pub fn init() {
+    tauri::async_runtime::spawn(async {
+        let result = match load_app_settings().await {
+            Ok(settings) => apply_settings(&settings).await.map(|_| ()),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            log::error!("指标端点初始化失败: {}", e);
+        }
+    });
+}
+
+pub async fn apply_settings(settings: &AppSettings) -> AppResult<MetricsStatus> {
+    if settings.metrics_enabled {
+        let router = Router::new().route("/metrics", get(http_metrics));
+        ENDPOINT.start(settings.metrics_port, router).await?;
+    } else {
+        ENDPOINT.stop().await;
+    }
+    Ok(status(settings).await)
+}
+
+async fn status(settings: &AppSettings) -> MetricsStatus {
+    let running_port = ENDPOINT.running_port().await;
+    MetricsStatus {
+        enabled: settings.metrics_enabled,
+        running: running_port.is_some(),
+        port: settings.metrics_port,
+        endpoint: running_port.map(|p| format!("http://127.0.0.1:{}/metrics", p)),
+    }
+}
+
+/// 指标端点状态
+#[tauri::command]
+#[specta::specta]
+pub async fn get_metrics_status() -> AppResult<MetricsStatus> {
+    let settings = load_app_settings().await?;
+    Ok(status(&settings).await)
+}
+
+async fn http_metrics() -> impl IntoResponse {
+    match render_metrics().await {
+        Ok(body) => (
+            StatusCode::OK,
+            [(
+                header::CONTENT_TYPE,
+                "text/plain; version=0.0.4; charset=utf-8",
+            )],
+            body,
+        )
+            .into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// 标签值转义：反斜杠、双引号、换行
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// 按指标名分组输出，同名指标只写一次 HELP / TYPE
+#[derive(Default)]
+struct Exposition {
+    families: BTreeMap<&'static str, (&'static str, &'static str, Vec<String>)>,
+}
+
+impl Exposition {
+    fn sample(
+        &mut self,
+        name: &'static str,
+        kind: &'static str,
+        help: &'static str,
+        labels: &[(&str, &str)],
+        value: impl std::fmt::Display,
+    ) {
+        let labels = labels
+            .iter()
+            .map(|(k, v)| format!("{}=\"{}\"", k, escape_label(v)))
+            .collect::<Vec<_>>()
+            .join(",");
+        let line = if labels.is_empty() {
+            format!("{} {}", name, value)
+        } else {
+            format!("{}{{{}}} {}", name, labels, value)
+        };
+        self.families
+            .entry(name)
+            .or_insert((kind, help, Vec::new()))
+            .2
+            .push(line);
+    }
+
+    fn render(self) -> String {
+        let mut out = String::new();
+        for (name, (kind, help, lines)) in self.families {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} {}", name, kind);
+            for line in lines {
+                out.push_str(&line);
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+async fn render_metrics() -> AppResult<String> {
+    let mut m = Exposition::default();
+
+    for rule in forward_metrics().await {
+        let port = rule.local_port.to_string();
+        let labels = [
+            ("rule", rule.id.as_str()),
+            ("name", rule.name.as_str()),
+            ("port", port.as_str()),
+        ];
+        m.sample(
+            "codeshelf_forward_up",
+            "gauge",
+            "Whether the forward rule is running",
+            &labels,
+            u8::from(rule.running),
+        );
+        m.sample(
+            "codeshelf_forward_connections",
+            "gauge",
+            "Open connections",
+            &labels,
+            rule.connections,
+        );
+        m.sample(
+            "codeshelf_forward_connections_total",
+            "counter",
+            "Accepted connections since the rule started",
+            &labels,
+            rule.connections_total,
+        );
+        m.sample(
+            "codeshelf_forward_received_bytes_total",
+            "counter",
+            "Bytes received from clients",
+            &labels,
+            rule.bytes_in,
+        );
+        m.sample(
+            "codeshelf_forward_sent_bytes_total",
+            "counter",
+            "Bytes sent to clients",
+            &labels,
+            rule.bytes_out,
+        );
+    }
+
+    for server in server_metrics().await {
+        let port = server.port.to_string();
+        let labels = [
+            ("server", server.id.as_str()),
+            ("name", server.name.as_str()),
+            ("port", port.as_str()),
+        ];
+        m.sample(
+            "codeshelf_server_up",
+            "gauge",
+            "Whether the static server is running",
+            &labels,
+            u8::from(server.running),
+        );
+        m.sample(
+            "codeshelf_server_active_requests",
+            "gauge",
+            "Requests being handled",
+            &labels,
+            server.active_requests,
+        );
+        for (i, count) in server.responses.iter().enumerate() {
+            let code = format!("{}xx", i + 1);
+            let mut with_code = labels.to_vec();
+            with_code.push(("code", code.as_str()));
+            m.sample(
+                "codeshelf_server_requests_total",
+                "counter",
+                "Completed requests by status class",
+                &with_code,
+                count,
+            );
+        }
+        m.sample(
+            "codeshelf_server_response_bytes_total",
+            "counter",
+            "Response body bytes (by Content-Length)",
+            &labels,
+            server.bytes_sent,
+        );
+    }
+
+    let tasks = get_download_tasks().await?;
+    let mut by_status: BTreeMap<&str, usize> =
+        ["pending", "downloading", "paused", "completed", "failed"]
+            .into_iter()
+            .map(|s| (s, 0))
+            .collect();
+    for task in &tasks {
+        *by_status.entry(task.status.as_str()).or_default() += 1;
+    }
+    for (status, count) in by_status {
+        m.sample(
+            "codeshelf_download_tasks",
+            "gauge",
+            "Download tasks by status",
+            &[("status", status)],
+            count,
+        );
+    }
+    m.sample(
+        "codeshelf_download_downloaded_bytes",
+        "gauge",
+        "Bytes downloaded across all tasks in the list",
+        &[],
+        tasks.iter().map(|t| t.downloaded_size).sum::<u64>(),
+    );
+    m.sample(
+        "codeshelf_download_speed_bytes",
+        "gauge",
+        "Current total download speed in bytes per second",
+        &[],
+        tasks
+            .iter()
+            .filter(|t| t.status == "downloading")
+            .map(|t| t.speed)
+            .sum::<u64>(),
+    );
+
+    Ok(m.render())
+}
@@ -1,19 +1,44 @@
-// 工具箱模块 - 包含端口扫描、文件下载、进程管理、端口转发、静态服务、Claude Code 配置功能
+// 工具箱模块 - 包含端口扫描、文件下载、文件校验和、进程管理、端口转发、静态服务、Claude Code 配置功能
 
+pub mod asset_generator;
+pub mod certs;
+pub mod checksum;
 pub mod claude_code;
 pub mod clipboard;
+pub mod data_format;
 pub mod docker;
+pub mod download_handoff;
+pub mod download_history;
 pub mod downloader;
+pub mod elevated;
+pub mod folder_sync;
 pub mod forwarder;
+pub mod http_bench;
+pub mod http_monitor;
+pub mod image_optimizer;
+mod local_endpoint;
+pub mod metrics;
+mod monitor;
 pub mod netcat;
 pub mod pairdrop;
+pub mod pcap;
+pub mod port_conflict;
+pub mod port_watch;
 pub mod process;
+pub mod regex_tester;
+pub mod release_assets;
+pub mod resource_alerts;
 pub mod scanner;
 pub mod server;
 pub mod shortcuts;
+pub mod ssh_keys;
 pub mod ssh_tunnel;
+mod syn_scan;
+pub mod text_diff;
+pub mod virus_scan;
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 // ============== 端口扫描相关结构 ==============
 
@@ -21,7 +46,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
 pub struct ScanConfig {
-    /// 目标 IP 地址
+    /// 目标 IP 地址，或 IPv4 网段（CIDR，如 192.168.1.0/24）
     pub target: String,
     /// 要扫描的端口列表，为空则使用默认常用端口
     pub ports: Option<Vec<u16>>,
@@ -33,6 +58,19 @@ pub struct ScanConfig {
     pub timeout_ms: Option<u64>,
     /// 并发数，默认 100
     pub concurrency: Option<usize>,
+    /// 扫描方式："connect"（默认，完整 TCP 连接）| "syn"（原始套接字半开扫描，需要权限，
+    /// 不可用时自动退回 connect）
+    #[serde(default)]
+    pub mode: Option<String>,
+}
+
+/// SYN 扫描是否可用
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanCapabilities {
+    pub syn_available: bool,
+    /// 不可用的原因（缺少权限 / 平台不支持）
+    pub syn_unavailable_reason: Option<String>,
 }
 
 /// 扫描结果
@@ -77,6 +115,38 @@ pub struct DownloadTask {
     pub created_at: String,
     #[serde(alias = "updated_at")]
     pub updated_at: String,
+    /// 附加请求头（Referer、Cookie 等，浏览器交接时携带），恢复下载时沿用
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// 备用镜像地址（不含 url 本身）
+    #[serde(default)]
+    pub mirrors: Vec<String>,
+    /// 当前正在使用的下载源，未使用镜像时为 None
+    #[serde(default)]
+    pub active_url: Option<String>,
+    /// 下载后病毒扫描结果
+    #[serde(default)]
+    pub scan: Option<DownloadScanResult>,
+    /// 需要代码托管平台令牌时记录令牌所属主机，令牌本身在发请求时再读取，不随任务保存
+    #[serde(default)]
+    pub auth_host: Option<String>,
+    /// 停滞判定秒数，None 为默认 20 秒，0 表示不检测
+    #[serde(default)]
+    pub stall_timeout_secs: Option<u32>,
+}
+
+/// 下载文件的病毒扫描结果
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadScanResult {
+    /// 使用的扫描器，如 "Windows Defender" / "ClamAV"
+    pub scanner: Option<String>,
+    /// "scanning" | "clean" | "infected" | "error" | "unavailable"
+    pub verdict: String,
+    pub detail: Option<String>,
+    /// 发现威胁后执行的动作（"deleted" / "quarantined: <路径>"）
+    pub action: Option<String>,
+    pub scanned_at: String,
 }
 
 /// 下载配置
@@ -87,6 +157,15 @@ pub struct DownloadConfig {
     pub save_dir: Option<String>,
     pub file_name: Option<String>,
     pub max_retries: Option<u32>,
+    #[serde(default)]
+    pub headers: Option<HashMap<String, String>>,
+    /// 备用镜像地址：下载前测速选择最快的源，下载中停滞时切换到下一个
+    #[serde(default)]
+    pub mirrors: Vec<String>,
+    /// 超过该秒数没有收到数据视为停滞（重试或切换源），默认 20，0 表示不检测；
+    /// 服务器本身很慢时可调大
+    #[serde(default)]
+    pub stall_timeout_secs: Option<u32>,
 }
 
 /// 下载进度
@@ -118,6 +197,32 @@ pub struct ProcessInfo {
     pub cpu: f32,    // 百分比
     pub working_dir: Option<String>,
     pub cmd: Option<String>,
+    /// 由项目任务启动时为任务名
+    #[serde(default)]
+    pub project_task: Option<String>,
+}
+
+/// 端口冲突（启动服务/转发时端口已被占用）
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PortConflict {
+    pub port: u16,
+    /// "server" | "forwarder"
+    pub target_kind: String,
+    pub target_id: String,
+    /// 占用端口的进程（可能查不到，例如权限不足）
+    pub processes: Vec<ProcessInfo>,
+}
+
+/// 提权端口中继
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ElevatedRelay {
+    pub listen_host: String,
+    pub listen_port: u16,
+    /// 转发到 127.0.0.1 的端口
+    pub target_port: u16,
+    pub started_at: String,
 }
 
 /// 进程查询过滤
@@ -146,6 +251,22 @@ pub struct ForwardRule {
     /// 文档路径，如 "doc.html" 或 "swagger-ui.html"，用于快速访问
     #[serde(alias = "doc_path")]
     pub doc_path: Option<String>,
+    /// 目标类型："tcp"（默认，remote_host:remote_port）| "unix"（Unix 域套接字）|
+    /// "pipe"（Windows 命名管道）。后两者只监听 127.0.0.1
+    #[serde(default = "default_forward_target_kind")]
+    pub target_kind: String,
+    /// target_kind 为 unix / pipe 时的路径，如 /var/run/docker.sock、\\.\pipe\docker_engine
+    #[serde(default)]
+    pub socket_path: Option<String>,
+    /// 备用目标，主目标（remote_host:remote_port）不可用时依次尝试
+    #[serde(default)]
+    pub backup_targets: Vec<ForwardTarget>,
+    /// 目标选择策略："failover"（默认，优先主目标）| "round_robin"（在健康目标间轮询）
+    #[serde(default = "default_forward_balance")]
+    pub balance: String,
+    /// 主动健康检查间隔（秒），None 时只在连接失败时标记不可用
+    #[serde(default)]
+    pub health_check_interval_secs: Option<u32>,
     #[serde(default = "default_stopped")]
     pub status: String, // "running", "stopped"
     #[serde(default)]
@@ -168,6 +289,42 @@ pub struct ForwardRuleInput {
     pub remote_port: u16,
     /// 文档路径，如 "doc.html" 或 "swagger-ui.html"
     pub doc_path: Option<String>,
+    #[serde(default)]
+    pub target_kind: Option<String>,
+    #[serde(default)]
+    pub socket_path: Option<String>,
+    #[serde(default)]
+    pub backup_targets: Option<Vec<ForwardTarget>>,
+    #[serde(default)]
+    pub balance: Option<String>,
+    #[serde(default)]
+    pub health_check_interval_secs: Option<u32>,
+}
+
+/// 转发目标
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ForwardTarget {
+    pub host: String,
+    pub port: u16,
+}
+
+fn default_forward_balance() -> String {
+    "failover".to_string()
+}
+
+fn default_forward_target_kind() -> String {
+    "tcp".to_string()
+}
+
+/// 转发目标的运行时健康状态
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ForwardTargetStatus {
+    pub address: String,
+    pub healthy: bool,
+    /// 最近一次检查或连接失败的原因
+    pub last_error: Option<String>,
 }
 
 /// 转发统计
@@ -178,6 +335,155 @@ pub struct ForwardStats {
     pub connections: u32,
     pub bytes_in: u64,
     pub bytes_out: u64,
+    /// 各目标健康状态（未运行时为空）
+    #[serde(default)]
+    pub targets: Vec<ForwardTargetStatus>,
+}
+
+// ============== 端口监控相关结构 ==============
+
+/// 端口监控项（host:port 定时探测）
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PortWatch {
+    pub id: String,
+    pub name: String,
+    pub host: String,
+    pub port: u16,
+    /// 探测间隔（秒）
+    #[serde(default = "default_watch_interval")]
+    pub interval_secs: u64,
+    /// 连接超时（毫秒）
+    #[serde(default = "default_watch_timeout")]
+    pub timeout_ms: u64,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_unknown")]
+    pub status: String, // "up", "down", "unknown"
+    #[serde(default)]
+    pub latency_ms: Option<u64>,
+    #[serde(default)]
+    pub last_checked: Option<String>,
+    /// 最近一次状态切换时间
+    #[serde(default)]
+    pub last_change: Option<String>,
+    #[serde(default)]
+    pub history: Vec<PortWatchRecord>,
+    pub created_at: String,
+}
+
+/// 单次探测记录
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PortWatchRecord {
+    pub time: String,
+    pub status: String,
+    pub latency_ms: Option<u64>,
+}
+
+/// 创建/编辑端口监控的输入
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PortWatchInput {
+    pub name: String,
+    pub host: String,
+    pub port: u16,
+    pub interval_secs: Option<u64>,
+    pub timeout_ms: Option<u64>,
+    pub enabled: Option<bool>,
+}
+
+fn default_watch_interval() -> u64 {
+    30
+}
+
+fn default_watch_timeout() -> u64 {
+    2000
+}
+
+fn default_unknown() -> String {
+    "unknown".to_string()
+}
+
+// ============== HTTP 健康检查相关结构 ==============
+
+/// HTTP(S) 健康检查监控项
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpMonitor {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    #[serde(default = "default_http_method")]
+    pub method: String,
+    /// 期望状态码，None 表示任意 2xx
+    #[serde(default)]
+    pub expected_status: Option<u16>,
+    /// 响应体需包含的子串
+    #[serde(default)]
+    pub body_contains: Option<String>,
+    #[serde(default = "default_watch_interval")]
+    pub interval_secs: u64,
+    #[serde(default = "default_http_timeout")]
+    pub timeout_ms: u64,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_unknown")]
+    pub status: String, // "up", "down", "unknown"
+    #[serde(default)]
+    pub last_error: Option<String>,
+    #[serde(default)]
+    pub last_checked: Option<String>,
+    #[serde(default)]
+    pub last_change: Option<String>,
+    #[serde(default)]
+    pub history: Vec<HttpCheckRecord>,
+    pub created_at: String,
+}
+
+/// 单次 HTTP 检查记录
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpCheckRecord {
+    pub time: String,
+    pub status: String,
+    pub status_code: Option<u16>,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// 创建/编辑 HTTP 监控的输入
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpMonitorInput {
+    pub name: String,
+    pub url: String,
+    pub method: Option<String>,
+    pub expected_status: Option<u16>,
+    pub body_contains: Option<String>,
+    pub interval_secs: Option<u64>,
+    pub timeout_ms: Option<u64>,
+    pub enabled: Option<bool>,
+}
+
+/// 基于历史记录的可用率与延迟分位数
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpMonitorStats {
+    pub monitor_id: String,
+    pub checks: u32,
+    pub uptime_percent: f64,
+    pub latency_p50: Option<u64>,
+    pub latency_p90: Option<u64>,
+    pub latency_p99: Option<u64>,
+}
+
+fn default_http_method() -> String {
+    "GET".to_string()
+}
+
+fn default_http_timeout() -> u64 {
+    5000
 }
 
 // ============== SSH 隧道相关结构 ==============
@@ -299,6 +605,60 @@ fn default_group() -> String {
     "默认分组".to_string()
 }
 
+// ============== SSH 密钥相关结构 ==============
+
+/// ~/.ssh 下的一对密钥
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SshKeyInfo {
+    /// 私钥文件名（如 id_ed25519）
+    pub name: String,
+    pub private_key_path: String,
+    /// 对应的 .pub 文件，不存在时为 None
+    pub public_key_path: Option<String>,
+    /// 密钥类型：ssh-ed25519 / ssh-rsa / ecdsa-sha2-nistp256 ...
+    pub key_type: Option<String>,
+    /// SHA256 指纹（ssh-keygen -lf）
+    pub fingerprint: Option<String>,
+    pub comment: Option<String>,
+    /// ssh config 中通过 IdentityFile 使用该密钥的 Host
+    pub hosts: Vec<String>,
+}
+
+/// ssh config 中的一个 Host 块
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SshHostEntry {
+    pub alias: String,
+    pub host_name: Option<String>,
+    pub user: Option<String>,
+    pub port: Option<u16>,
+    pub identity_files: Vec<String>,
+}
+
+/// 写入 ssh config 的 Host 映射
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SshHostEntryInput {
+    pub alias: String,
+    pub host_name: Option<String>,
+    pub user: Option<String>,
+    pub port: Option<u16>,
+    /// 使用的私钥文件名（~/.ssh 下）
+    pub key_name: String,
+}
+
+/// ssh -T 认证测试结果
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SshAuthTestResult {
+    pub success: bool,
+    /// 服务端返回的认证用户名（GitHub / GitLab 欢迎语中提取）
+    pub account: Option<String>,
+    pub output: String,
+    pub duration_ms: u64,
+}
+
 // ============== 静态服务相关结构 ==============
 
 /// 服务配置
@@ -322,8 +682,20 @@ pub struct ServerConfig {
     pub index_page: Option<String>,
     /// 多个代理规则
     pub proxies: Vec<ProxyConfig>,
+    /// 同时处理的最大请求数，超出返回 503；None 不限制
+    #[serde(default)]
+    pub max_concurrent_requests: Option<u32>,
+    /// 同时保持的最大连接数（含 keep-alive 空闲连接），超出的连接收到 503 后被关闭；None 不限制
+    #[serde(default)]
+    pub max_connections: Option<u32>,
+    /// 每个客户端 IP 每秒允许的请求数（可突发 2 倍），超出返回 429；None 不限制
+    #[serde(default)]
+    pub rate_limit_per_ip: Option<u32>,
     #[serde(default = "default_stopped")]
-    pub status: String, // "running", "stopped"
+    pub status: String, // "running", "stopped", "error"
+    /// status 为 "error" 时的原因，如根目录被删除 / 卸载
+    #[serde(default)]
+    pub error: Option<String>,
     #[serde(alias = "created_at")]
     pub created_at: String,
 }
@@ -334,6 +706,18 @@ pub struct ServerConfig {
 pub struct ProxyConfig {
     pub prefix: String,
     pub target: String,
+    /// 转发时去掉匹配的前缀（/api/users → /users）；关闭时原样保留
+    #[serde(default = "default_true")]
+    pub strip_prefix: bool,
+    /// 转发前在路径前追加的前缀（如 /v1）
+    #[serde(default)]
+    pub add_prefix: Option<String>,
+    /// 转发请求时设置的请求头，同名时覆盖客户端发来的值
+    #[serde(default)]
+    pub request_headers: HashMap<String, String>,
+    /// 写入代理响应的响应头
+    #[serde(default)]
+    pub response_headers: HashMap<String, String>,
 }
 
 /// 创建服务的输入
@@ -352,6 +736,26 @@ pub struct ServerConfigInput {
     pub index_page: Option<String>,
     /// 多个代理规则
     pub proxies: Option<Vec<ProxyConfig>>,
+    #[serde(default)]
+    pub max_concurrent_requests: Option<u32>,
+    #[serde(default)]
+    pub max_connections: Option<u32>,
+    #[serde(default)]
+    pub rate_limit_per_ip: Option<u32>,
+}
+
+/// 导出的服务配置包，可在其它机器导入
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerConfigBundle {
+    /// 固定为 "codeshelf-server"
+    pub format: String,
+    pub version: u32,
+    pub exported_at: String,
+    pub server: ServerConfigInput,
+    /// 等价的 nginx 配置，仅供参考，导入时忽略
+    #[serde(default)]
+    pub nginx: Option<String>,
 }
 
 /// 服务访问日志
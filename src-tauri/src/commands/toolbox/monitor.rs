@@ -0,0 +1,130 @@
+// 定时监控公共部分 - 端口监控（port_watch）与 HTTP 健康检查（http_monitor）共用
+//
+// 监控项存于 PersistedStore，重启后状态复位为 unknown；调度循环每秒检查一次，
+// 按各自 interval 并发执行到期的探测。探测结果写入历史（保留最近 MAX_HISTORY 条），
+// 每次探测推送更新事件，状态在 up/down 之间切换时发通知并落盘（首次探测只记录）。
+// 各模块只实现探测本身与通知文案。
+
+use crate::commands::settings::push_notification;
+use crate::error::AppResult;
+use crate::storage::PersistedStore;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::Instant;
+use tauri::{AppHandle, Emitter};
+use tokio::time::Duration;
+
+/// 调度循环间隔
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+pub(crate) const UNKNOWN: &str = "unknown";
+
+/// 一类监控项：提供状态字段的访问与一次探测
+pub(crate) trait Monitor:
+    Clone + Serialize + DeserializeOwned + Send + Sync + 'static
+{
+    /// 单次探测记录
+    type Record: Send;
+
+    /// 每次探测后推送的事件名
+    const EVENT: &'static str;
+    /// 每个监控项保留的记录条数
+    const MAX_HISTORY: usize;
+
+    fn id(&self) -> &str;
+    fn enabled(&self) -> bool;
+    fn interval_secs(&self) -> u64;
+    /// "up" | "down" | "unknown"
+    fn status(&self) -> &str;
+
+    /// 执行一次探测
+    fn probe(&self) -> impl Future<Output = Self::Record> + Send;
+
+    /// 记录的 (时间, 状态)
+    fn record_summary(record: &Self::Record) -> (&str, &str);
+
+    /// 写入探测结果：状态、检查时间以及延迟、错误等各自的字段
+    fn apply(&mut self, record: &Self::Record);
+
+    /// 最近一次状态切换时间
+    fn set_last_change(&mut self, time: String);
+
+    fn history_mut(&mut self) -> &mut Vec<Self::Record>;
+
+    /// 状态切换时的通知：(级别, 标题, 正文)
+    fn transition_notice(&self) -> (&'static str, String, String);
+}
+
+/// 执行一次探测并更新状态；状态切换时通知并落盘
+pub(crate) async fn run_check<M: Monitor>(
+    app: &AppHandle,
+    store: &'static PersistedStore<M>,
+    id: &str,
+) -> AppResult<M> {
+    let snapshot = store.lock().await.get(id).cloned().ok_or("监控项不存在")?;
+
+    let record = snapshot.probe().await;
+
+    let (updated, transition) = {
+        let mut items = store.lock().await;
+        let m = items.get_mut(id).ok_or("监控项不存在")?;
+        let previous = m.status().to_string();
+        m.apply(&record);
+        let (time, status) = M::record_summary(&record);
+        let changed = previous != status;
+        if changed {
+            m.set_last_change(time.to_string());
+        }
+        let history = m.history_mut();
+        history.push(record);
+        if history.len() > M::MAX_HISTORY {
+            let overflow = history.len() - M::MAX_HISTORY;
+            history.drain(..overflow);
+        }
+        // 首次探测（unknown → x）只记录，不算切换
+        (m.clone(), changed && previous != UNKNOWN)
+    };
+
+    let _ = app.emit(M::EVENT, &updated);
+
+    if transition {
+        let (kind, title, body) = updated.transition_notice();
+        push_notification(app, "monitor", kind, &title, &body).await;
+        store.save().await?;
+    }
+
+    Ok(updated)
+}
+
+/// 启动调度：按各自 interval 探测启用的监控项
+pub(crate) fn spawn_scheduler<M: Monitor>(app: AppHandle, store: &'static PersistedStore<M>) {
+    tauri::async_runtime::spawn(async move {
+        let mut next_due: HashMap<String, Instant> = HashMap::new();
+        loop {
+            tokio::time::sleep(TICK_INTERVAL).await;
+            store.ensure_loaded().await;
+
+            let now = Instant::now();
+            let due: Vec<(String, u64)> = {
+                let items = store.lock().await;
+                next_due.retain(|id, _| items.contains_key(id));
+                items
+                    .values()
+                    .filter(|m| m.enabled())
+                    .filter(|m| next_due.get(m.id()).map(|t| now >= *t).unwrap_or(true))
+                    .map(|m| (m.id().to_string(), m.interval_secs().max(1)))
+                    .collect()
+            };
+
+            for (id, interval) in due {
+                next_due.insert(id.clone(), now + Duration::from_secs(interval));
+                let app = app.clone();
+                tokio::spawn(async move {
+                    let _ = run_check(&app, store, &id).await;
+                });
+            }
+        }
+    });
+}
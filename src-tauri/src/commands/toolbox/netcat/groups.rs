@@ -0,0 +1,173 @@
+// Netcat 会话分组 - 批量启停、组内广播发送与汇总统计
+//
+// 分组只是会话上的一个名字（group 字段），随会话配置持久化；
+// 批量操作逐个会话执行，单个失败不影响其他会话，结果逐条返回。
+
+use super::{
+    normalize_group, send_message_internal, start_session_internal, stop_session_internal,
+    DataFormat, NetcatGroupOpResult, NetcatGroupStats, NetcatState, SendMessageInput,
+    SessionStatus,
+};
+use crate::error::AppResult;
+use std::collections::BTreeMap;
+use tauri::{AppHandle, State};
+
+/// 组内会话 (id, 名称)，按名称排序
+async fn group_members(state: &NetcatState, group: &str) -> AppResult<Vec<(String, String)>> {
+    let sessions = state.sessions.read().await;
+    let mut members = Vec::new();
+    for (id, session_state) in sessions.iter() {
+        let s = session_state.read().await;
+        if s.session.group.as_deref() == Some(group) {
+            members.push((id.clone(), s.session.name.clone()));
+        }
+    }
+    if members.is_empty() {
+        return Err(crate::error::AppError::invalid(format!(
+            "分组 {} 中没有会话",
+            group
+        )));
+    }
+    members.sort_by(|a, b| a.1.cmp(&b.1));
+    Ok(members)
+}
+
+fn op_result(session_id: String, name: String, result: AppResult<()>) -> NetcatGroupOpResult {
+    NetcatGroupOpResult {
+        session_id,
+        name,
+        success: result.is_ok(),
+        error: result.err().map(|e| e.to_string()),
+    }
+}
+
+/// 设置会话所属分组，group 为空时移出分组
+#[tauri::command]
+#[specta::specta]
+pub async fn netcat_set_session_group(
+    state: State<'_, NetcatState>,
+    session_ids: Vec<String>,
+    group: Option<String>,
+) -> AppResult<()> {
+    let group = normalize_group(group);
+    {
+        let sessions = state.sessions.read().await;
+        for id in &session_ids {
+            let session_state = sessions.get(id).ok_or("会话不存在")?;
+            session_state.write().await.session.group = group.clone();
+        }
+    }
+    state.save_sessions().await
+}
+
+/// 启动分组内所有会话
+#[tauri::command]
+#[specta::specta]
+pub async fn netcat_start_group(
+    app: AppHandle,
+    state: State<'_, NetcatState>,
+    group: String,
+) -> AppResult<Vec<NetcatGroupOpResult>> {
+    let mut results = Vec::new();
+    for (id, name) in group_members(&state, &group).await? {
+        let result = start_session_internal(&app, &state, id.clone()).await;
+        results.push(op_result(id, name, result));
+    }
+    log::info!("Netcat 分组启动: {} ({} 个会话)", group, results.len());
+    Ok(results)
+}
+
+/// 停止分组内所有会话
+#[tauri::command]
+#[specta::specta]
+pub async fn netcat_stop_group(
+    state: State<'_, NetcatState>,
+    group: String,
+) -> AppResult<Vec<NetcatGroupOpResult>> {
+    let mut results = Vec::new();
+    for (id, name) in group_members(&state, &group).await? {
+        let result = stop_session_internal(&state, &id).await;
+        results.push(op_result(id, name, result));
+    }
+    log::info!("Netcat 分组停止: {} ({} 个会话)", group, results.len());
+    Ok(results)
+}
+
+/// 向分组内所有已连接的会话发送同一条数据；服务器模式会话广播给其全部客户端
+#[tauri::command]
+#[specta::specta]
+pub async fn netcat_send_to_group(
+    app: AppHandle,
+    state: State<'_, NetcatState>,
+    group: String,
+    data: String,
+    format: DataFormat,
+) -> AppResult<Vec<NetcatGroupOpResult>> {
+    let mut results = Vec::new();
+    for (id, name) in group_members(&state, &group).await? {
+        let status = {
+            let sessions = state.sessions.read().await;
+            match sessions.get(&id) {
+                Some(s) => s.read().await.session.status,
+                None => continue,
+            }
+        };
+        let result = if matches!(status, SessionStatus::Connected | SessionStatus::Listening) {
+            let input = SendMessageInput {
+                session_id: id.clone(),
+                data: data.clone(),
+                format,
+                target_client: None,
+                broadcast: Some(true),
+                payload_id: None,
+                render: Some(true),
+            };
+            send_message_internal(&app, &state, input).await.map(|_| ())
+        } else {
+            Err(crate::error::AppError::invalid("会话未连接"))
+        };
+        results.push(op_result(id, name, result));
+    }
+    Ok(results)
+}
+
+/// 获取各分组的汇总统计
+#[tauri::command]
+#[specta::specta]
+pub async fn netcat_get_group_stats(
+    state: State<'_, NetcatState>,
+) -> AppResult<Vec<NetcatGroupStats>> {
+    let sessions = state.sessions.read().await;
+    let mut groups: BTreeMap<String, NetcatGroupStats> = BTreeMap::new();
+    for session_state in sessions.values() {
+        let s = session_state.read().await;
+        let Some(group) = s.session.group.clone() else {
+            continue;
+        };
+        let stats = groups
+            .entry(group.clone())
+            .or_insert_with(|| NetcatGroupStats {
+                group,
+                session_count: 0,
+                active_count: 0,
+                error_count: 0,
+                client_count: 0,
+                bytes_sent: 0,
+                bytes_received: 0,
+                message_count: 0,
+                last_activity: None,
+            });
+        stats.session_count += 1;
+        match s.session.status {
+            SessionStatus::Connected | SessionStatus::Listening => stats.active_count += 1,
+            SessionStatus::Error => stats.error_count += 1,
+            _ => {}
+        }
+        stats.client_count += s.session.client_count;
+        stats.bytes_sent += s.session.bytes_sent;
+        stats.bytes_received += s.session.bytes_received;
+        stats.message_count += s.session.message_count;
+        stats.last_activity = stats.last_activity.max(s.session.last_activity);
+    }
+    Ok(groups.into_values().collect())
+}
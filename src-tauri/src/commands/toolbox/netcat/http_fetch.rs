@@ -0,0 +1,330 @@
+// Netcat HTTP 请求 - 自动发送的 HTTP 数据源，同时可作为接口调试工具
+//
+// - 支持任意方法、自定义请求头 / 请求体、TLS 证书校验开关
+// - 重定向手动逐跳处理（reqwest 自动跟随时拿不到中间跳），返回完整重定向链
+// - 耗时阶段：DNS 用 lookup_host 单独计时，再把解析结果交给 reqwest 避免重复解析；
+//   TCP 建连用一次单独的探测连接计时；首字节为发出请求到收到响应头
+// - 请求不走代理（探测连接与实际请求都直连目标，计时才一致）
+// - 响应体边读边截断，超过 MAX_BODY_BYTES 后不再继续读取
+
+use super::{HttpFetchConfig, HttpFetchResponse, HttpHeader, HttpRedirectHop, HttpTiming};
+use crate::error::{AppError, AppResult};
+use reqwest::{Method, StatusCode, Url};
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+/// 返回给前端的响应体上限，超出部分截断且不再读取
+const MAX_BODY_BYTES: usize = 5 * 1024 * 1024;
+
+const DEFAULT_TIMEOUT_MS: u64 = 10_000;
+const DEFAULT_MAX_REDIRECTS: u32 = 10;
+
+/// 跨主机重定向时不再携带的请求头
+const SENSITIVE_HEADERS: &[&str] = &["authorization", "cookie", "proxy-authorization"];
+
+fn describe_error(e: &reqwest::Error) -> String {
+    if e.is_connect() {
+        format!("连接失败 (目标服务器可能未启动或防火墙阻止): {}", e)
+    } else if e.is_timeout() {
+        format!("请求超时: {}", e)
+    } else if e.is_request() {
+        format!("请求错误: {}", e)
+    } else {
+        format!("HTTP 请求失败: {}", e)
+    }
+}
+
+/// 解析目标主机，返回 (地址, 耗时)；主机本身是 IP 时不计 DNS
+async fn resolve(url: &Url) -> AppResult<(Option<SocketAddr>, u64)> {
+    let Some(host) = url.host_str() else {
+        return Ok((None, 0));
+    };
+    let port = url.port_or_known_default().unwrap_or(80);
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if let Ok(ip) = host.parse::<std::net::IpAddr>() {
+        return Ok((Some(SocketAddr::new(ip, port)), 0));
+    }
+    let started = Instant::now();
+    let addr = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| AppError::other(format!("DNS 解析失败 ({}): {}", host, e)))?
+        .next();
+    Ok((addr, started.elapsed().as_millis() as u64))
+}
+
+/// 探测 TCP 建连耗时；失败时返回 None，真正的错误由后续请求报告
+async fn probe_connect(addr: SocketAddr, timeout: Duration) -> Option<u64> {
+    let started = Instant::now();
+    tokio::time::timeout(timeout, tokio::net::TcpStream::connect(addr))
+        .await
+        .ok()?
+        .ok()?;
+    Some(started.elapsed().as_millis() as u64)
+}
+
+/// 按 RFC 9110：303 一律改 GET；301 / 302 的 POST 改 GET；307 / 308 保持原方法与请求体
+fn redirect_method(status: StatusCode, method: &Method) -> Option<Method> {
+    match status.as_u16() {
+        303 if *method != Method::HEAD => Some(Method::GET),
+        301 | 302 if *method == Method::POST => Some(Method::GET),
+        _ => None,
+    }
+}
+
+fn version_label(version: reqwest::Version) -> String {
+    match version {
+        reqwest::Version::HTTP_09 => "HTTP/0.9",
+        reqwest::Version::HTTP_10 => "HTTP/1.0",
+        reqwest::Version::HTTP_11 => "HTTP/1.1",
+        reqwest::Version::HTTP_2 => "HTTP/2",
+        reqwest::Version::HTTP_3 => "HTTP/3",
+        _ => "HTTP",
+    }
+    .to_string()
+}
+
+/// HTTP 请求：返回状态、响应头、耗时阶段与重定向链
+#[tauri::command]
+#[specta::specta]
+pub async fn netcat_fetch_http(config: HttpFetchConfig) -> AppResult<HttpFetchResponse> {
+    let method_name = config
+        .method
+        .as_deref()
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .unwrap_or("GET")
+        .to_uppercase();
+    let mut method = Method::from_bytes(method_name.as_bytes())
+        .map_err(|_| AppError::invalid(format!("不支持的 HTTP 方法: {}", method_name)))?;
+    let mut url =
+        Url::parse(config.url.trim()).map_err(|e| AppError::invalid(format!("URL 无效: {}", e)))?;
+    let mut headers: Vec<(String, String)> =
+        config.headers.unwrap_or_default().into_iter().collect();
+    let mut body = config.body;
+    let verify_tls = config.verify_tls.unwrap_or(false);
+    let follow = config.follow_redirects.unwrap_or(true);
+    let max_redirects = config.max_redirects.unwrap_or(DEFAULT_MAX_REDIRECTS);
+    let timeout = Duration::from_millis(config.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS).max(100));
+
+    log::info!("Netcat HTTP 请求: url={}, method={}", url, method);
+
+    let started = Instant::now();
+    let mut redirects: Vec<HttpRedirectHop> = Vec::new();
+    loop {
+        let remaining = timeout
+            .checked_sub(started.elapsed())
+            .filter(|d| !d.is_zero())
+            .ok_or_else(|| AppError::other("请求超时"))?;
+        let hop_started = Instant::now();
+
+        let (addr, dns_ms) = resolve(&url).await?;
+        let connect_ms = match addr {
+            Some(addr) => probe_connect(addr, remaining)
+                .await
+                .map(|ms| dns_ms + ms)
+                .unwrap_or(dns_ms),
+            None => dns_ms,
+        };
+
+        // 调试工具直连目标，不走代理：计时与解析到的地址都对应真实目标
+        let mut builder = reqwest::Client::builder()
+            .no_proxy()
+            .redirect(reqwest::redirect::Policy::none())
+            .timeout(remaining)
+            .connect_timeout(Duration::from_secs(5))
+            .user_agent("CodeShelf-Netcat/1.0")
+            .danger_accept_invalid_certs(!verify_tls);
+        if let (Some(host), Some(addr)) = (url.host_str(), addr) {
+            builder = builder.resolve(host, addr);
+        }
+        let client = builder
+            .build()
+            .map_err(|e| AppError::from(format!("创建 HTTP 客户端失败: {}", e)))?;
+
+        let mut request = client.request(method.clone(), url.clone());
+        for (key, value) in &headers {
+            request = request.header(key, value);
+        }
+        if let Some(body) = &body {
+            request = request.body(body.clone());
+        }
+
+        let mut response = request.send().await.map_err(|e| {
+            let detail = describe_error(&e);
+            log::error!("Netcat HTTP 请求失败: {}", detail);
+            detail
+        })?;
+        let ttfb_ms = hop_started.elapsed().as_millis() as u64;
+        let status = response.status();
+
+        let location = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from);
+        if let (true, true, Some(location)) = (follow, status.is_redirection(), location) {
+            if redirects.len() as u32 >= max_redirects {
+                return Err(AppError::other(format!(
+                    "重定向次数超过 {} 次",
+                    max_redirects
+                )));
+            }
+            let next = url
+                .join(&location)
+                .map_err(|e| AppError::other(format!("重定向地址无效 ({}): {}", location, e)))?;
+            redirects.push(HttpRedirectHop {
+                url: url.to_string(),
+                status: status.as_u16(),
+                location: next.to_string(),
+                duration_ms: ttfb_ms,
+            });
+            if let Some(next_method) = redirect_method(status, &method) {
+                method = next_method;
+                body = None;
+                headers.retain(|(k, _)| {
+                    !k.eq_ignore_ascii_case("content-type")
+                        && !k.eq_ignore_ascii_case("content-length")
+                });
+            }
+            if next.host_str() != url.host_str() {
+                headers.retain(|(k, _)| !SENSITIVE_HEADERS.contains(&k.to_lowercase().as_str()));
+            }
+            url = next;
+            continue;
+        }
+
+        let version = version_label(response.version());
+        let remote_addr = response.remote_addr().map(|a| a.to_string());
+        let response_headers: Vec<HttpHeader> = response
+            .headers()
+            .iter()
+            .map(|(name, value)| HttpHeader {
+                name: name.to_string(),
+                value: String::from_utf8_lossy(value.as_bytes()).to_string(),
+            })
+            .collect();
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("")
+            .to_string();
+
+        let declared_size = response.content_length();
+        let mut bytes: Vec<u8> = Vec::new();
+        let mut body_truncated = false;
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| AppError::from(format!("读取响应失败: {}", e)))?
+        {
+            let room = MAX_BODY_BYTES - bytes.len();
+            if chunk.len() > room {
+                bytes.extend_from_slice(&chunk[..room]);
+                body_truncated = true;
+                break;
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        // 截断时实际大小未知，有 Content-Length 时以它为准
+        let body_size = match declared_size {
+            Some(size) if body_truncated => size,
+            _ => bytes.len() as u64,
+        };
+        let text = String::from_utf8_lossy(&bytes).to_string();
+
+        log::info!(
+            "Netcat HTTP 响应: {} {} bytes, content-type={}",
+            status,
+            body_size,
+            content_type
+        );
+
+        // 如果是 JSON 并且指定了路径，则提取
+        let is_json =
+            content_type.contains("application/json") || content_type.contains("text/json");
+        let extracted = match config.json_path.as_deref().map(str::trim) {
+            Some(path) if is_json && !path.is_empty() && !body_truncated => {
+                Some(extract_json_path(&text, path)?)
+            }
+            _ => None,
+        };
+
+        return Ok(HttpFetchResponse {
+            status: status.as_u16(),
+            status_text: status.canonical_reason().unwrap_or("").to_string(),
+            version,
+            headers: response_headers,
+            body: text,
+            body_size,
+            body_truncated,
+            extracted,
+            final_url: url.to_string(),
+            remote_addr,
+            redirects,
+            timing: HttpTiming {
+                dns_ms,
+                connect_ms,
+                ttfb_ms,
+                total_ms: hop_started.elapsed().as_millis() as u64,
+            },
+            total_ms: started.elapsed().as_millis() as u64,
+        });
+    }
+}
+
+/// 从 JSON 中提取指定路径的值
+fn extract_json_path(json_str: &str, path: &str) -> AppResult<String> {
+    let json: serde_json::Value = serde_json::from_str(json_str)
+        .map_err(|e| AppError::from(format!("JSON 解析失败: {}", e)))?;
+
+    // 支持多路径: "data.name,data.id"
+    let paths: Vec<&str> = path.split(',').map(|p| p.trim()).collect();
+    let mut results: Vec<String> = Vec::new();
+
+    for single_path in paths {
+        if let Some(value) = get_json_value(&json, single_path) {
+            let str_value = match value {
+                serde_json::Value::String(s) => s.clone(),
+                serde_json::Value::Null => "null".to_string(),
+                other => other.to_string(),
+            };
+            results.push(str_value);
+        }
+    }
+
+    if results.is_empty() {
+        Ok(json_str.to_string())
+    } else {
+        Ok(results.join(" "))
+    }
+}
+
+/// 获取 JSON 路径对应的值
+fn get_json_value<'a>(json: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    let mut current = json;
+
+    // 解析路径: "data.items[0].value"
+    let parts: Vec<&str> = path.split('.').collect();
+
+    for part in parts {
+        // 检查是否有数组索引
+        if let Some(bracket_pos) = part.find('[') {
+            let key = &part[..bracket_pos];
+            let index_str = &part[bracket_pos + 1..part.len() - 1];
+
+            // 先获取对象属性
+            if !key.is_empty() {
+                current = current.get(key)?;
+            }
+
+            // 然后获取数组元素
+            let index: usize = index_str.parse().ok()?;
+            current = current.get(index)?;
+        } else {
+            current = current.get(part)?;
+        }
+    }
+
+    Some(current)
+}
@@ -1,15 +1,32 @@
 // Netcat 模块 - Tauri 命令导出
-
+//
+// 另含基于同一套会话 / 消息模型的协议专用客户端：MQTT（mqtt.rs）、Modbus TCP 主站（modbus.rs）
+
+mod groups;
+mod http_fetch;
+mod modbus;
+mod mqtt;
+mod payloads;
+mod share;
 mod tcp_client;
 mod tcp_server;
+mod traffic_log;
 mod types;
 mod udp;
 
+pub use groups::*;
+pub use http_fetch::*;
+pub use modbus::*;
+pub use mqtt::*;
+pub use payloads::*;
+pub use share::*;
+pub use traffic_log::*;
 pub use types::*;
 
 use super::generate_id;
 use crate::error::AppResult;
 use crate::storage::get_storage_config;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::{AppHandle, Emitter, State};
@@ -44,36 +61,67 @@ impl NetcatState {
             crate::error::AppError::from(format!("解析 Netcat 会话文件失败: {}", e))
         })?;
 
+        let mut saved_messages = Self::take_saved_messages();
         let mut sessions = self.sessions.write().await;
         for cfg in configs {
-            let session = NetcatSession {
-                id: cfg.id.clone(),
-                name: cfg.name,
-                protocol: cfg.protocol,
-                mode: cfg.mode,
-                host: cfg.host,
-                port: cfg.port,
-                status: SessionStatus::Disconnected,
-                auto_reconnect: cfg.auto_reconnect,
-                timeout_ms: cfg.timeout_ms,
-                created_at: cfg.created_at,
-                connected_at: None,
-                last_activity: None,
-                bytes_sent: 0,
-                bytes_received: 0,
-                message_count: 0,
-                error_message: None,
-                local_addr: None,
-                client_count: 0,
-                auto_send: cfg.auto_send,
-            };
-            let session_state = Arc::new(RwLock::new(SessionState::new(session)));
-            sessions.insert(cfg.id, session_state);
+            // 启动预加载与前端 netcat_init 都会调用，已存在（可能正在运行）的会话不覆盖
+            if sessions.contains_key(&cfg.id) {
+                continue;
+            }
+            traffic_log::configure(&cfg.id, cfg.log_mode, cfg.log_payload);
+            let id = cfg.id.clone();
+            let session = NetcatSession::from(cfg);
+            let mut state = SessionState::new(session);
+            if let Some(messages) = saved_messages.remove(&id) {
+                let mut messages: VecDeque<NetcatMessage> = messages.into();
+                let limit = state.session.history_limit;
+                if messages.len() > limit {
+                    messages.drain(..messages.len() - limit);
+                }
+                state.session.message_count = messages.len() as u64;
+                state.messages = messages;
+            }
+            sessions.insert(id, Arc::new(RwLock::new(state)));
         }
 
         Ok(())
     }
 
+    /// 读取退出时保存的消息历史（读完即删，避免下次启动重复恢复旧消息）
+    fn take_saved_messages() -> HashMap<String, Vec<NetcatMessage>> {
+        let Ok(config) = get_storage_config() else {
+            return HashMap::new();
+        };
+        let path = config.netcat_messages_file();
+        let messages = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        let _ = std::fs::remove_file(&path);
+        messages
+    }
+
+    /// 保存所有会话的消息历史（退出时调用）
+    pub async fn save_messages(&self) -> AppResult<()> {
+        let config = get_storage_config()?;
+        config.ensure_dirs()?;
+
+        let sessions = self.sessions.read().await;
+        let mut all: HashMap<String, Vec<NetcatMessage>> = HashMap::new();
+        for (id, session_state) in sessions.iter() {
+            let s = session_state.read().await;
+            if !s.messages.is_empty() {
+                all.insert(id.clone(), s.messages.iter().cloned().collect());
+            }
+        }
+
+        let content = serde_json::to_string(&all)
+            .map_err(|e| crate::error::AppError::from(format!("序列化 Netcat 消息失败: {}", e)))?;
+        std::fs::write(config.netcat_messages_file(), content)
+            .map_err(|e| crate::error::AppError::from(format!("保存 Netcat 消息失败: {}", e)))?;
+        Ok(())
+    }
+
     /// 保存会话配置到文件
     pub async fn save_sessions(&self) -> AppResult<()> {
         let config = get_storage_config()?;
@@ -84,18 +132,7 @@ impl NetcatState {
 
         for session_state in sessions.values() {
             let s = session_state.read().await;
-            configs.push(NetcatSessionConfig {
-                id: s.session.id.clone(),
-                name: s.session.name.clone(),
-                protocol: s.session.protocol,
-                mode: s.session.mode,
-                host: s.session.host.clone(),
-                port: s.session.port,
-                auto_reconnect: s.session.auto_reconnect,
-                timeout_ms: s.session.timeout_ms,
-                created_at: s.session.created_at,
-                auto_send: s.session.auto_send.clone(),
-            });
+            configs.push(NetcatSessionConfig::from(&s.session));
         }
 
         let content = serde_json::to_string_pretty(&configs)
@@ -169,7 +206,16 @@ pub async fn netcat_create_session(
         local_addr: None,
         client_count: 0,
         auto_send: AutoSendConfig::default(),
+        group: normalize_group(input.group),
+        log_mode: TrafficLogMode::default(),
+        log_payload: false,
+        history_limit: DEFAULT_HISTORY_LIMIT,
+        overflow_policy: OverflowPolicy::default(),
+        capture_stopped: false,
+        send_rate: 0.0,
+        recv_rate: 0.0,
     };
+    traffic_log::configure(&session_id, session.log_mode, session.log_payload);
 
     let session_state = Arc::new(RwLock::new(SessionState::new(session.clone())));
 
@@ -193,6 +239,15 @@ pub async fn netcat_start_session(
     app: AppHandle,
     state: State<'_, NetcatState>,
     session_id: String,
+) -> AppResult<()> {
+    start_session_internal(&app, &state, session_id).await
+}
+
+/// 内部启动会话逻辑（可复用）
+async fn start_session_internal(
+    app: &AppHandle,
+    state: &NetcatState,
+    session_id: String,
 ) -> AppResult<()> {
     let session_state = {
         let sessions = state.sessions.read().await;
@@ -362,8 +417,7 @@ async fn stop_session_internal(state: &NetcatState, session_id: &str) -> AppResu
         let mut s = session_state.write().await;
         s.session.status = SessionStatus::Disconnected;
         s.session.error_message = None;
-        s.clients.clear();
-        s.session.client_count = 0;
+        s.clear_clients();
     }
 
     // 等待资源释放
@@ -378,6 +432,7 @@ async fn stop_session_internal(state: &NetcatState, session_id: &str) -> AppResu
 #[tauri::command]
 #[specta::specta]
 pub async fn netcat_remove_session(
+    app: AppHandle,
     state: State<'_, NetcatState>,
     session_id: String,
 ) -> AppResult<()> {
@@ -386,10 +441,20 @@ pub async fn netcat_remove_session(
 
     // 移除
     state.sessions.write().await.remove(&session_id);
+    super::pcap::finish(&session_id);
+    traffic_log::remove(&session_id);
 
     // 保存到文件
     state.save_sessions().await?;
 
+    // 通知该会话的独立窗口自行关闭
+    crate::tool_windows::emit_to_resource(
+        &app,
+        &session_id,
+        "tool-window-resource-removed",
+        session_id.clone(),
+    );
+
     Ok(())
 }
 
@@ -425,7 +490,16 @@ pub async fn netcat_send_message(
     state: State<'_, NetcatState>,
     input: SendMessageInput,
 ) -> AppResult<NetcatMessage> {
-    log::info!(
+    send_message_internal(&app, &state, input).await
+}
+
+/// 内部发送消息逻辑（可复用）
+async fn send_message_internal(
+    app: &AppHandle,
+    state: &NetcatState,
+    input: SendMessageInput,
+) -> AppResult<NetcatMessage> {
+    log::debug!(
         "Netcat 发送消息: session={}, size={}, format={:?}, target_client={:?}, broadcast={:?}",
         input.session_id,
         input.data.len(),
@@ -444,6 +518,16 @@ pub async fn netcat_send_message(
         "会话不存在".to_string()
     })?;
 
+    // 报文库 / 模板变量渲染
+    let mut input = input;
+    if let Some(payload_id) = input.payload_id.as_deref() {
+        let payload = payloads::get_payload(payload_id).await?;
+        input.data = payloads::render_template(&payload.data);
+        input.format = payload.format;
+    } else if input.render.unwrap_or(false) {
+        input.data = payloads::render_template(&input.data);
+    }
+
     // 解析数据
     let data = parse_input_data(&input.data, input.format)?;
     log::debug!("Netcat 解析后数据大小: {} bytes", data.len());
@@ -453,7 +537,7 @@ pub async fn netcat_send_message(
         (s.session.protocol, s.session.mode)
     };
 
-    log::info!("Netcat 发送: protocol={:?}, mode={:?}", protocol, mode);
+    log::debug!("Netcat 发送: protocol={:?}, mode={:?}", protocol, mode);
 
     let resolved_tcp_target_client = if protocol == Protocol::Tcp
         && mode == SessionMode::Server
@@ -473,18 +557,46 @@ pub async fn netcat_send_message(
         None
     };
 
+    // UDP 服务器模式：target_client 可以是虚拟客户端 id，也可以直接写对端地址
+    let resolved_udp_targets: Vec<(Option<String>, String)> =
+        if protocol == Protocol::Udp && mode == SessionMode::Server {
+            let s = session_state.read().await;
+            if input.broadcast.unwrap_or(false) {
+                s.clients
+                    .values()
+                    .map(|client| (Some(client.id.clone()), client.addr.clone()))
+                    .collect()
+            } else {
+                let target = input.target_client.as_deref().unwrap_or("").trim();
+                if target.is_empty() {
+                    Vec::new()
+                } else {
+                    match s
+                        .clients
+                        .values()
+                        .find(|client| client.id == target || client.addr == target)
+                    {
+                        Some(client) => vec![(Some(client.id.clone()), client.addr.clone())],
+                        None => vec![(None, target.to_string())],
+                    }
+                }
+            }
+        } else {
+            Vec::new()
+        };
+
     // 根据协议和模式发送
     match (protocol, mode) {
         (Protocol::Tcp, SessionMode::Client) => {
-            log::info!("Netcat TCP 客户端模式发送");
+            log::debug!("Netcat TCP 客户端模式发送");
             tcp_client::send_tcp_client_data(&input.session_id, data.clone()).await?;
         }
         (Protocol::Tcp, SessionMode::Server) => {
             if input.broadcast.unwrap_or(false) {
-                log::info!("Netcat TCP 服务器模式广播");
+                log::debug!("Netcat TCP 服务器模式广播");
                 tcp_server::broadcast_to_clients(&input.session_id, data.clone()).await?;
             } else if let Some(ref client_id) = resolved_tcp_target_client {
-                log::info!("Netcat TCP 服务器模式发送到客户端: {}", client_id);
+                log::debug!("Netcat TCP 服务器模式发送到客户端: {}", client_id);
                 tcp_server::send_to_client(&input.session_id, client_id, data.clone()).await?;
             } else {
                 log::error!("Netcat TCP 服务器模式: 未指定目标客户端或广播");
@@ -493,11 +605,22 @@ pub async fn netcat_send_message(
                 ));
             }
         }
-        (Protocol::Udp, _) => {
-            log::info!("Netcat UDP 模式发送");
+        (Protocol::Udp, SessionMode::Client) => {
+            log::debug!("Netcat UDP 客户端模式发送");
             let target = input.target_client.clone();
             udp::send_udp_data(&input.session_id, data.clone(), target).await?;
         }
+        (Protocol::Udp, SessionMode::Server) => {
+            if resolved_udp_targets.is_empty() {
+                return Err(crate::error::AppError::from(
+                    "服务器模式需要指定目标客户端或广播".to_string(),
+                ));
+            }
+            for (_, addr) in &resolved_udp_targets {
+                log::debug!("Netcat UDP 服务器模式发送到: {}", addr);
+                udp::send_udp_data(&input.session_id, data.clone(), Some(addr.clone())).await?;
+            }
+        }
     }
 
     if protocol == Protocol::Tcp && mode == SessionMode::Server {
@@ -509,8 +632,8 @@ pub async fn netcat_send_message(
                 .map(|client_id| vec![client_id.clone()])
         };
         mirror_tcp_server_send_to_local_clients(
-            &app,
-            state.inner(),
+            app,
+            state,
             &input.session_id,
             mirror_targets.as_deref(),
             &data,
@@ -519,7 +642,14 @@ pub async fn netcat_send_message(
     }
 
     // 尝试获取 client_addr（如果指定了目标客户端）
-    let message_client_id = resolved_tcp_target_client.or_else(|| input.target_client.clone());
+    let resolved_udp_target_client = if input.broadcast.unwrap_or(false) {
+        None
+    } else {
+        resolved_udp_targets.first().and_then(|(id, _)| id.clone())
+    };
+    let message_client_id = resolved_tcp_target_client
+        .or(resolved_udp_target_client)
+        .or_else(|| input.target_client.clone());
 
     let client_addr = if let Some(ref cid) = message_client_id {
         let s = session_state.read().await;
@@ -552,16 +682,17 @@ pub async fn netcat_send_message(
     // 保存到会话
     {
         let mut s = session_state.write().await;
-        s.messages.push(message.clone());
-        s.session.message_count += 1;
+        s.record_message(app, message.clone());
         s.session.last_activity = Some(now);
-
-        if s.messages.len() > 1000 {
-            s.messages.remove(0);
-        }
     }
 
-    log::info!("Netcat 消息发送成功");
+    traffic_log::log_traffic(
+        &message.session_id,
+        "发送",
+        message.size,
+        message.client_addr.as_deref(),
+        &message.data,
+    );
     Ok(message)
 }
 
@@ -668,12 +799,10 @@ async fn mirror_tcp_server_send_to_local_clients(
             };
 
             session.session.bytes_received += data.len() as u64;
-            session.session.message_count += 1;
+            session.record_throughput(MessageDirection::Received, None, data.len());
             session.session.last_activity = Some(now);
-            session.messages.push(message.clone());
-
-            if session.messages.len() > 1000 {
-                session.messages.remove(0);
+            if !session.record_message(app, message.clone()) {
+                continue;
             }
 
             (session.session.id.clone(), message)
@@ -705,7 +834,7 @@ pub async fn netcat_get_sessions(state: State<'_, NetcatState>) -> AppResult<Vec
 
     for session_state in sessions.values() {
         let s = session_state.read().await;
-        result.push(s.session.clone());
+        result.push(s.session_snapshot());
     }
 
     Ok(result)
@@ -721,10 +850,10 @@ pub async fn netcat_get_session(
     let sessions = state.sessions.read().await;
     let session_state = sessions.get(&session_id).ok_or("会话不存在")?;
     let s = session_state.read().await;
-    Ok(s.session.clone())
+    Ok(s.session_snapshot())
 }
 
-/// 获取会话消息
+/// 获取会话消息（最新在前），可按方向、客户端、时间范围和内容筛选后再分页
 #[tauri::command]
 #[specta::specta]
 pub async fn netcat_get_messages(
@@ -732,7 +861,25 @@ pub async fn netcat_get_messages(
     session_id: String,
     limit: Option<usize>,
     offset: Option<usize>,
+    filter: Option<NetcatMessageFilter>,
 ) -> AppResult<Vec<NetcatMessage>> {
+    let filter = filter.unwrap_or_default();
+    let text = filter
+        .text
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase);
+    let hex = match filter.hex.as_deref().map(str::trim) {
+        Some(h) if !h.is_empty() => Some(parse_input_data(h, DataFormat::Hex)?),
+        _ => None,
+    };
+    let client = filter
+        .client
+        .as_deref()
+        .map(str::trim)
+        .filter(|c| !c.is_empty());
+
     let sessions = state.sessions.read().await;
     let session_state = sessions.get(&session_id).ok_or("会话不存在")?;
     let s = session_state.read().await;
@@ -744,6 +891,22 @@ pub async fn netcat_get_messages(
         .messages
         .iter()
         .rev()
+        .filter(|m| filter.direction.map_or(true, |d| m.direction == d))
+        .filter(|m| filter.since.map_or(true, |t| m.timestamp >= t))
+        .filter(|m| filter.until.map_or(true, |t| m.timestamp <= t))
+        .filter(|m| {
+            client.map_or(true, |c| {
+                m.client_id.as_deref() == Some(c) || m.client_addr.as_deref() == Some(c)
+            })
+        })
+        .filter(|m| {
+            text.as_deref()
+                .map_or(true, |t| m.data.to_lowercase().contains(t))
+        })
+        .filter(|m| {
+            hex.as_deref()
+                .map_or(true, |h| message_contains_bytes(m, h))
+        })
         .skip(offset)
         .take(limit)
         .cloned()
@@ -752,6 +915,29 @@ pub async fn netcat_get_messages(
     Ok(messages)
 }
 
+/// 消息内容是否包含指定字节序列。
+/// 收到的非 UTF-8 数据以 "48 65 ..." 文本保存，所以文本消息同时按原文和十六进制两种方式匹配
+fn message_contains_bytes(message: &NetcatMessage, pattern: &[u8]) -> bool {
+    let contains = |bytes: &[u8]| bytes.windows(pattern.len()).any(|w| w == pattern);
+    match message.format {
+        DataFormat::Text => {
+            contains(message.data.as_bytes())
+                || (is_hex_display(&message.data)
+                    && parse_input_data(&message.data, DataFormat::Hex)
+                        .is_ok_and(|bytes| contains(&bytes)))
+        }
+        format => parse_input_data(&message.data, format).is_ok_and(|bytes| contains(&bytes)),
+    }
+}
+
+/// 是否为 bytes_to_display_string 生成的十六进制文本（"48 65 6C"）
+fn is_hex_display(data: &str) -> bool {
+    !data.is_empty()
+        && data
+            .split(' ')
+            .all(|b| b.len() == 2 && b.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
 /// 获取连接的客户端
 #[tauri::command]
 #[specta::specta]
@@ -763,7 +949,7 @@ pub async fn netcat_get_clients(
     let session_state = sessions.get(&session_id).ok_or("会话不存在")?;
     let s = session_state.read().await;
 
-    Ok(s.clients.values().cloned().collect())
+    Ok(s.clients_snapshot())
 }
 
 /// 清空会话消息
@@ -777,9 +963,44 @@ pub async fn netcat_clear_messages(
     let session_state = sessions.get(&session_id).ok_or("会话不存在")?;
     let mut s = session_state.write().await;
     s.messages.clear();
+    s.session.capture_stopped = false;
     Ok(())
 }
 
+/// 设置会话的消息条数上限与溢出策略；上限调小时按策略立即裁剪已有消息
+#[tauri::command]
+#[specta::specta]
+pub async fn netcat_set_history_limit(
+    state: State<'_, NetcatState>,
+    session_id: String,
+    limit: usize,
+    policy: OverflowPolicy,
+) -> AppResult<NetcatSession> {
+    if !(1..=MAX_HISTORY_LIMIT).contains(&limit) {
+        return Err(crate::error::AppError::invalid(format!(
+            "消息条数上限应在 1 - {} 之间",
+            MAX_HISTORY_LIMIT
+        )));
+    }
+    let session = {
+        let sessions = state.sessions.read().await;
+        let session_state = sessions.get(&session_id).ok_or("会话不存在")?;
+        let mut s = session_state.write().await;
+        s.session.history_limit = limit;
+        s.session.overflow_policy = policy;
+        if s.messages.len() > limit {
+            // 两种策略都只保留最新的 limit 条，stopCapturing 同时进入停止状态
+            let excess = s.messages.len() - limit;
+            s.messages.drain(..excess);
+        }
+        s.session.capture_stopped =
+            policy == OverflowPolicy::StopCapturing && s.messages.len() >= limit;
+        s.session.clone()
+    };
+    state.save_sessions().await?;
+    Ok(session)
+}
+
 /// 断开指定客户端（仅服务器模式）
 #[tauri::command]
 #[specta::specta]
@@ -795,205 +1016,12 @@ pub async fn netcat_disconnect_client(
     let sessions = state.sessions.read().await;
     if let Some(session_state) = sessions.get(&session_id) {
         let mut s = session_state.write().await;
-        s.clients.remove(&client_id);
-        s.session.client_count = s.clients.len() as u32;
+        s.remove_client(&client_id);
     }
 
     Ok(())
 }
 
-/// HTTP 请求配置
-#[derive(Debug, Clone, serde::Deserialize, specta::Type)]
-#[serde(rename_all = "camelCase")]
-pub struct HttpFetchConfig {
-    pub url: String,
-    #[serde(default)]
-    pub method: Option<String>,
-    #[serde(default)]
-    pub headers: Option<std::collections::HashMap<String, String>>,
-    #[serde(default)]
-    pub body: Option<String>,
-    #[serde(default)]
-    pub json_path: Option<String>,
-}
-
-/// HTTP 获取数据（用于自动发送的 HTTP 模式）
-#[tauri::command]
-#[specta::specta]
-pub async fn netcat_fetch_http(config: HttpFetchConfig) -> AppResult<String> {
-    use reqwest::{Client, Method};
-    use std::time::Duration;
-
-    log::info!(
-        "Netcat HTTP 请求: url={}, method={:?}",
-        config.url,
-        config.method
-    );
-
-    // 创建 HTTP 客户端，配置更宽松的选项
-    let client = Client::builder()
-        .timeout(Duration::from_secs(10))
-        .connect_timeout(Duration::from_secs(5))
-        .no_proxy() // 禁用系统代理
-        .user_agent("CodeShelf-Netcat/1.0")
-        .danger_accept_invalid_certs(true) // 接受无效证书（用于本地测试）
-        .build()
-        .map_err(|e| crate::error::AppError::from(format!("创建 HTTP 客户端失败: {}", e)))?;
-
-    // 解析 HTTP 方法
-    let method = match config
-        .method
-        .as_deref()
-        .unwrap_or("GET")
-        .to_uppercase()
-        .as_str()
-    {
-        "GET" => Method::GET,
-        "POST" => Method::POST,
-        "PUT" => Method::PUT,
-        "DELETE" => Method::DELETE,
-        "PATCH" => Method::PATCH,
-        "HEAD" => Method::HEAD,
-        other => {
-            return Err(crate::error::AppError::from(format!(
-                "不支持的 HTTP 方法: {}",
-                other
-            )))
-        }
-    };
-
-    let mut request = client.request(method, &config.url);
-
-    // 添加自定义头
-    if let Some(headers) = config.headers {
-        for (key, value) in headers {
-            request = request.header(&key, &value);
-        }
-    }
-
-    // 添加请求体
-    if let Some(body) = config.body {
-        request = request.body(body);
-    }
-
-    let response = request.send().await.map_err(|e| {
-        // 提供更详细的错误信息
-        let err_detail = if e.is_connect() {
-            format!("连接失败 (目标服务器可能未启动或防火墙阻止): {}", e)
-        } else if e.is_timeout() {
-            format!("请求超时: {}", e)
-        } else if e.is_request() {
-            format!("请求错误: {}", e)
-        } else {
-            format!("HTTP 请求失败: {}", e)
-        };
-        log::error!("Netcat HTTP 请求失败: {}", err_detail);
-        err_detail
-    })?;
-
-    let status = response.status();
-    log::info!("Netcat HTTP 响应状态: {}", status);
-
-    if !status.is_success() {
-        return Err(crate::error::AppError::from(format!(
-            "HTTP 请求失败: {} {}",
-            status.as_u16(),
-            status.canonical_reason().unwrap_or("")
-        )));
-    }
-
-    let content_type = response
-        .headers()
-        .get("content-type")
-        .and_then(|v| v.to_str().ok())
-        .unwrap_or("")
-        .to_string();
-
-    let text = response
-        .text()
-        .await
-        .map_err(|e| crate::error::AppError::from(format!("读取响应失败: {}", e)))?;
-
-    log::info!(
-        "Netcat HTTP 响应: {} bytes, content-type={}, preview={}",
-        text.len(),
-        content_type,
-        &text[..text.len().min(100)]
-    );
-
-    // 如果是 JSON 并且指定了路径，则提取
-    if content_type.contains("application/json") || content_type.contains("text/json") {
-        if let Some(path) = config.json_path {
-            if !path.trim().is_empty() {
-                let result = extract_json_path(&text, &path)?;
-                log::info!(
-                    "Netcat HTTP JSON 提取结果: {}",
-                    &result[..result.len().min(100)]
-                );
-                return Ok(result);
-            }
-        }
-    }
-
-    Ok(text)
-}
-
-/// 从 JSON 中提取指定路径的值
-fn extract_json_path(json_str: &str, path: &str) -> AppResult<String> {
-    let json: serde_json::Value = serde_json::from_str(json_str)
-        .map_err(|e| crate::error::AppError::from(format!("JSON 解析失败: {}", e)))?;
-
-    // 支持多路径: "data.name,data.id"
-    let paths: Vec<&str> = path.split(',').map(|p| p.trim()).collect();
-    let mut results: Vec<String> = Vec::new();
-
-    for single_path in paths {
-        if let Some(value) = get_json_value(&json, single_path) {
-            let str_value = match value {
-                serde_json::Value::String(s) => s.clone(),
-                serde_json::Value::Null => "null".to_string(),
-                other => other.to_string(),
-            };
-            results.push(str_value);
-        }
-    }
-
-    if results.is_empty() {
-        Ok(json_str.to_string())
-    } else {
-        Ok(results.join(" "))
-    }
-}
-
-/// 获取 JSON 路径对应的值
-fn get_json_value<'a>(json: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
-    let mut current = json;
-
-    // 解析路径: "data.items[0].value"
-    let parts: Vec<&str> = path.split('.').collect();
-
-    for part in parts {
-        // 检查是否有数组索引
-        if let Some(bracket_pos) = part.find('[') {
-            let key = &part[..bracket_pos];
-            let index_str = &part[bracket_pos + 1..part.len() - 1];
-
-            // 先获取对象属性
-            if !key.is_empty() {
-                current = current.get(key)?;
-            }
-
-            // 然后获取数组元素
-            let index: usize = index_str.parse().ok()?;
-            current = current.get(index)?;
-        } else {
-            current = current.get(part)?;
-        }
-    }
-
-    Some(current)
-}
-
 // ============== 辅助函数 ==============
 
 /// 解析输入数据
@@ -1046,6 +1074,13 @@ fn bytes_to_display_string(data: &[u8]) -> String {
     }
 }
 
+/// 分组名去除首尾空白，空串视为未分组
+fn normalize_group(group: Option<String>) -> Option<String> {
+    group
+        .map(|g| g.trim().to_string())
+        .filter(|g| !g.is_empty())
+}
+
 /// 获取当前时间戳
 fn current_timestamp() -> u64 {
     SystemTime::now()
@@ -0,0 +1,221 @@
+// Modbus TCP 主站 - 读写线圈 / 寄存器，每次请求单独建立连接
+//
+// 支持的功能码：
+//   01 读线圈  02 读离散输入  03 读保持寄存器  04 读输入寄存器
+//   05 写单个线圈  06 写单个寄存器  15 写多个线圈  16 写多个寄存器
+// 返回解析后的数值与原始请求 / 响应报文（十六进制），便于对照设备文档排查。
+
+use crate::error::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+static TRANSACTION_ID: AtomicU16 = AtomicU16::new(1);
+
+/// Modbus 请求
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ModbusRequest {
+    pub host: String,
+    /// 默认 502
+    pub port: Option<u16>,
+    /// 从站地址，默认 1
+    pub unit_id: Option<u8>,
+    /// 功能码：1 / 2 / 3 / 4 / 5 / 6 / 15 / 16
+    pub function: u8,
+    /// 起始地址（0 基）
+    pub address: u16,
+    /// 读取数量（读功能码使用）
+    pub quantity: Option<u16>,
+    /// 写入的值：线圈用 0 / 1，寄存器用 0-65535
+    #[serde(default)]
+    pub values: Vec<u16>,
+    pub timeout_ms: Option<u64>,
+}
+
+/// Modbus 响应
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ModbusResponse {
+    pub function: u8,
+    /// 读取到的值（线圈为 0 / 1）；写操作返回设备回显的值
+    pub values: Vec<u16>,
+    pub request_hex: String,
+    pub response_hex: String,
+    pub elapsed_ms: u64,
+}
+
+fn to_hex(data: &[u8]) -> String {
+    data.iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn exception_message(code: u8) -> &'static str {
+    match code {
+        1 => "非法功能码",
+        2 => "非法数据地址",
+        3 => "非法数据值",
+        4 => "从站设备故障",
+        5 => "已确认，处理中",
+        6 => "从站设备忙",
+        10 => "网关路径不可用",
+        11 => "网关目标设备无响应",
+        _ => "未知异常",
+    }
+}
+
+/// 构造 PDU（功能码 + 数据）
+fn build_pdu(req: &ModbusRequest) -> AppResult<Vec<u8>> {
+    let mut pdu = vec![req.function];
+    pdu.extend_from_slice(&req.address.to_be_bytes());
+    match req.function {
+        1..=4 => {
+            let quantity = req.quantity.unwrap_or(1);
+            let max = if req.function <= 2 { 2000 } else { 125 };
+            if quantity == 0 || quantity > max {
+                return Err(AppError::invalid(format!("读取数量需在 1-{} 之间", max)));
+            }
+            pdu.extend_from_slice(&quantity.to_be_bytes());
+        }
+        5 => {
+            let value = *req
+                .values
+                .first()
+                .ok_or_else(|| AppError::invalid("缺少写入值"))?;
+            pdu.extend_from_slice(if value != 0 {
+                &[0xFF, 0x00]
+            } else {
+                &[0x00, 0x00]
+            });
+        }
+        6 => {
+            let value = *req
+                .values
+                .first()
+                .ok_or_else(|| AppError::invalid("缺少写入值"))?;
+            pdu.extend_from_slice(&value.to_be_bytes());
+        }
+        15 => {
+            let count = req.values.len();
+            if count == 0 || count > 1968 {
+                return Err(AppError::invalid("写入线圈数量需在 1-1968 之间"));
+            }
+            let mut bytes = vec![0u8; count.div_ceil(8)];
+            for (i, v) in req.values.iter().enumerate() {
+                if *v != 0 {
+                    bytes[i / 8] |= 1 << (i % 8);
+                }
+            }
+            pdu.extend_from_slice(&(count as u16).to_be_bytes());
+            pdu.push(bytes.len() as u8);
+            pdu.extend_from_slice(&bytes);
+        }
+        16 => {
+            let count = req.values.len();
+            if count == 0 || count > 123 {
+                return Err(AppError::invalid("写入寄存器数量需在 1-123 之间"));
+            }
+            pdu.extend_from_slice(&(count as u16).to_be_bytes());
+            pdu.push((count * 2) as u8);
+            for v in &req.values {
+                pdu.extend_from_slice(&v.to_be_bytes());
+            }
+        }
+        f => return Err(AppError::invalid(format!("不支持的功能码: {}", f))),
+    }
+    Ok(pdu)
+}
+
+/// 解析响应 PDU
+fn parse_pdu(req: &ModbusRequest, pdu: &[u8]) -> AppResult<Vec<u16>> {
+    let function = *pdu
+        .first()
+        .ok_or_else(|| AppError::from("响应为空".to_string()))?;
+    if function == req.function | 0x80 {
+        let code = pdu.get(1).copied().unwrap_or(0);
+        return Err(AppError::from(format!(
+            "设备返回异常 {:02X}: {}",
+            code,
+            exception_message(code)
+        )));
+    }
+    if function != req.function {
+        return Err(AppError::from(format!("响应功能码不匹配: {}", function)));
+    }
+    let short = || AppError::from("响应长度不足".to_string());
+    match function {
+        1 | 2 => {
+            let count = *pdu.get(1).ok_or_else(short)? as usize;
+            let bytes = pdu.get(2..2 + count).ok_or_else(short)?;
+            let quantity = req.quantity.unwrap_or(1) as usize;
+            Ok((0..quantity)
+                .map(|i| ((bytes.get(i / 8).copied().unwrap_or(0) >> (i % 8)) & 1) as u16)
+                .collect())
+        }
+        3 | 4 => {
+            let count = *pdu.get(1).ok_or_else(short)? as usize;
+            let bytes = pdu.get(2..2 + count).ok_or_else(short)?;
+            Ok(bytes
+                .chunks_exact(2)
+                .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                .collect())
+        }
+        // 写操作回显：地址 + 值 / 数量
+        _ => {
+            let echo = pdu.get(3..5).ok_or_else(short)?;
+            Ok(vec![u16::from_be_bytes([echo[0], echo[1]])])
+        }
+    }
+}
+
+/// 发送一次 Modbus TCP 请求
+#[tauri::command]
+#[specta::specta]
+pub async fn modbus_request(request: ModbusRequest) -> AppResult<ModbusResponse> {
+    let pdu = build_pdu(&request)?;
+    let transaction_id = TRANSACTION_ID.fetch_add(1, Ordering::Relaxed);
+
+    // MBAP 头：事务号 + 协议号 0 + 长度（单元号 + PDU）+ 单元号
+    let mut frame = Vec::with_capacity(7 + pdu.len());
+    frame.extend_from_slice(&transaction_id.to_be_bytes());
+    frame.extend_from_slice(&[0, 0]);
+    frame.extend_from_slice(&((pdu.len() + 1) as u16).to_be_bytes());
+    frame.push(request.unit_id.unwrap_or(1));
+    frame.extend_from_slice(&pdu);
+
+    let addr = format!("{}:{}", request.host, request.port.unwrap_or(502));
+    let timeout = Duration::from_millis(request.timeout_ms.unwrap_or(3000));
+    let started = Instant::now();
+
+    let response = tokio::time::timeout(timeout, async {
+        let mut stream = TcpStream::connect(&addr)
+            .await
+            .map_err(|e| AppError::from(format!("连接 {} 失败: {}", addr, e)))?;
+        stream.write_all(&frame).await?;
+
+        let mut header = [0u8; 7];
+        stream.read_exact(&mut header).await?;
+        let length = u16::from_be_bytes([header[4], header[5]]) as usize;
+        if u16::from_be_bytes([header[0], header[1]]) != transaction_id || length < 2 {
+            return Err(AppError::from("响应 MBAP 头无效".to_string()));
+        }
+        let mut body = vec![0u8; length - 1];
+        stream.read_exact(&mut body).await?;
+        Ok::<_, AppError>([header.as_slice(), body.as_slice()].concat())
+    })
+    .await
+    .map_err(|_| AppError::from(format!("请求 {} 超时", addr)))??;
+
+    let values = parse_pdu(&request, &response[7..])?;
+    Ok(ModbusResponse {
+        function: request.function,
+        values,
+        request_hex: to_hex(&frame),
+        response_hex: to_hex(&response),
+        elapsed_ms: started.elapsed().as_millis() as u64,
+    })
+}
@@ -0,0 +1,589 @@
+// MQTT 快速客户端 - 基于 MQTT 3.1.1 的最小实现：连接、订阅 / 取消订阅、发布、消息历史
+//
+// - 只支持明文 TCP（mqtt://），不支持 TLS / WebSocket
+// - 订阅与发布的 QoS 限制为 0 / 1；收到 QoS 1 消息自动回 PUBACK
+// - 会话只保存在内存中，不持久化；断开后需重新连接、重新订阅
+// - 收到的消息通过 "mqtt-event" 事件实时推送给前端
+
+use super::{
+    bytes_to_display_string, current_timestamp, parse_input_data, DataFormat, MessageDirection,
+    SessionStatus,
+};
+use crate::commands::toolbox::generate_id;
+use crate::error::{AppError, AppResult};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::net::TcpStream;
+use tokio::sync::{oneshot, Mutex, RwLock};
+
+/// 每个会话保留的消息条数
+const MAX_MESSAGES: usize = 1000;
+/// 等待 CONNACK / SUBACK / PUBACK 的超时
+const ACK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// MQTT 连接配置
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MqttConnectInput {
+    pub host: String,
+    pub port: u16,
+    pub name: Option<String>,
+    /// 为空时自动生成
+    pub client_id: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// 心跳间隔（秒），默认 60
+    pub keep_alive_secs: Option<u16>,
+    /// 默认 true
+    pub clean_session: Option<bool>,
+    pub timeout_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MqttSession {
+    pub id: String,
+    pub name: String,
+    pub host: String,
+    pub port: u16,
+    pub client_id: String,
+    pub status: SessionStatus,
+    pub error_message: Option<String>,
+    pub connected_at: u64,
+    /// 已订阅主题 -> QoS
+    pub subscriptions: HashMap<String, u8>,
+    pub messages_sent: u64,
+    pub messages_received: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MqttMessage {
+    pub id: String,
+    pub session_id: String,
+    pub direction: MessageDirection,
+    pub topic: String,
+    /// UTF-8 文本，非文本内容显示为十六进制
+    pub payload: String,
+    pub size: usize,
+    pub qos: u8,
+    pub retain: bool,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(tag = "type")]
+pub enum MqttEvent {
+    #[serde(rename = "statusChanged")]
+    StatusChanged {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        status: SessionStatus,
+        error: Option<String>,
+    },
+    #[serde(rename = "messageReceived")]
+    MessageReceived {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        message: MqttMessage,
+    },
+}
+
+struct MqttClient {
+    session: RwLock<MqttSession>,
+    messages: RwLock<VecDeque<MqttMessage>>,
+    writer: Mutex<OwnedWriteHalf>,
+    /// 等待 SUBACK / UNSUBACK / PUBACK 的请求：packet id -> 回包内容
+    pending: std::sync::Mutex<HashMap<u16, oneshot::Sender<Vec<u8>>>>,
+    next_packet_id: AtomicU16,
+    stopped: AtomicBool,
+}
+
+static CLIENTS: Lazy<RwLock<HashMap<String, Arc<MqttClient>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+// ============== 报文编解码 ==============
+
+fn put_str(buf: &mut Vec<u8>, s: &str) {
+    buf.extend_from_slice(&(s.len() as u16).to_be_bytes());
+    buf.extend_from_slice(s.as_bytes());
+}
+
+/// 固定头 + 剩余长度（变长编码）+ 内容
+fn packet(header: u8, body: &[u8]) -> Vec<u8> {
+    let mut out = vec![header];
+    let mut len = body.len();
+    loop {
+        let mut byte = (len % 128) as u8;
+        len /= 128;
+        if len > 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if len == 0 {
+            break;
+        }
+    }
+    out.extend_from_slice(body);
+    out
+}
+
+/// 读取一个完整报文，返回 (固定头, 内容)
+async fn read_packet<R: AsyncRead + Unpin>(reader: &mut R) -> std::io::Result<(u8, Vec<u8>)> {
+    let header = reader.read_u8().await?;
+    let mut len = 0usize;
+    let mut shift = 0;
+    loop {
+        let byte = reader.read_u8().await?;
+        len |= ((byte & 0x7f) as usize) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+        if shift > 21 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "剩余长度编码无效",
+            ));
+        }
+    }
+    let mut body = vec![0u8; len];
+    reader.read_exact(&mut body).await?;
+    Ok((header, body))
+}
+
+fn connack_error(code: u8) -> &'static str {
+    match code {
+        1 => "协议版本不被支持",
+        2 => "客户端 ID 被拒绝",
+        3 => "服务不可用",
+        4 => "用户名或密码错误",
+        5 => "未授权",
+        _ => "连接被拒绝",
+    }
+}
+
+fn validate_qos(qos: Option<u8>) -> AppResult<u8> {
+    match qos.unwrap_or(0) {
+        q @ (0 | 1) => Ok(q),
+        q => Err(AppError::invalid(format!("仅支持 QoS 0 / 1，收到 {}", q))),
+    }
+}
+
+impl MqttClient {
+    fn packet_id(&self) -> u16 {
+        // packet id 不能为 0
+        loop {
+            let id = self.next_packet_id.fetch_add(1, Ordering::Relaxed);
+            if id != 0 {
+                return id;
+            }
+        }
+    }
+
+    async fn send(&self, data: &[u8]) -> AppResult<()> {
+        let mut writer = self.writer.lock().await;
+        writer
+            .write_all(data)
+            .await
+            .map_err(|e| AppError::from(format!("MQTT 发送失败: {}", e)))
+    }
+
+    /// 发送带 packet id 的请求并等待对应的 ACK
+    async fn request(&self, packet_id: u16, data: &[u8]) -> AppResult<Vec<u8>> {
+        let (tx, rx) = oneshot::channel();
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.insert(packet_id, tx);
+        }
+        self.send(data).await?;
+        let result = tokio::time::timeout(ACK_TIMEOUT, rx).await;
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.remove(&packet_id);
+        }
+        match result {
+            Ok(Ok(body)) => Ok(body),
+            Ok(Err(_)) => Err(AppError::from("MQTT 连接已断开".to_string())),
+            Err(_) => Err(AppError::from("等待服务器确认超时".to_string())),
+        }
+    }
+
+    async fn push_message(&self, message: MqttMessage) {
+        let mut messages = self.messages.write().await;
+        messages.push_back(message);
+        while messages.len() > MAX_MESSAGES {
+            messages.pop_front();
+        }
+    }
+}
+
+async fn get_client(session_id: &str) -> AppResult<Arc<MqttClient>> {
+    CLIENTS
+        .read()
+        .await
+        .get(session_id)
+        .cloned()
+        .ok_or_else(|| AppError::invalid("MQTT 会话不存在"))
+}
+
+async fn set_status(
+    app: &AppHandle,
+    client: &MqttClient,
+    status: SessionStatus,
+    error: Option<String>,
+) {
+    let session_id = {
+        let mut session = client.session.write().await;
+        session.status = status;
+        session.error_message = error.clone();
+        session.id.clone()
+    };
+    let _ = app.emit(
+        "mqtt-event",
+        MqttEvent::StatusChanged {
+            session_id,
+            status,
+            error,
+        },
+    );
+}
+
+/// 读取循环：分发 ACK、处理收到的 PUBLISH
+async fn read_loop<R: AsyncRead + Unpin>(app: AppHandle, client: Arc<MqttClient>, mut reader: R) {
+    let error = loop {
+        let (header, body) = match read_packet(&mut reader).await {
+            Ok(p) => p,
+            Err(e) => break Some(format!("连接断开: {}", e)),
+        };
+        match header >> 4 {
+            // PUBLISH
+            3 => {
+                let qos = (header >> 1) & 0x03;
+                let retain = header & 0x01 == 1;
+                if body.len() < 2 {
+                    continue;
+                }
+                let topic_len = u16::from_be_bytes([body[0], body[1]]) as usize;
+                let mut offset = 2 + topic_len;
+                if body.len() < offset {
+                    continue;
+                }
+                let topic = String::from_utf8_lossy(&body[2..offset]).to_string();
+                if qos > 0 && body.len() >= offset + 2 {
+                    let pid = [body[offset], body[offset + 1]];
+                    offset += 2;
+                    // QoS 1 回 PUBACK；QoS 2 按协议回 PUBREC（订阅时最高只请求 QoS 1，正常不会出现）
+                    let ack = if qos == 1 { 0x40 } else { 0x50 };
+                    let _ = client.send(&packet(ack, &pid)).await;
+                }
+                let payload = &body[offset.min(body.len())..];
+                let (session_id, message) = {
+                    let mut session = client.session.write().await;
+                    session.messages_received += 1;
+                    let message = MqttMessage {
+                        id: generate_id(),
+                        session_id: session.id.clone(),
+                        direction: MessageDirection::Received,
+                        topic,
+                        payload: bytes_to_display_string(payload),
+                        size: payload.len(),
+                        qos,
+                        retain,
+                        timestamp: current_timestamp(),
+                    };
+                    (session.id.clone(), message)
+                };
+                client.push_message(message.clone()).await;
+                let _ = app.emit(
+                    "mqtt-event",
+                    MqttEvent::MessageReceived {
+                        session_id,
+                        message,
+                    },
+                );
+            }
+            // PUBACK / SUBACK / UNSUBACK
+            4 | 9 | 11 if body.len() >= 2 => {
+                let pid = u16::from_be_bytes([body[0], body[1]]);
+                let waiter = client.pending.lock().ok().and_then(|mut p| p.remove(&pid));
+                if let Some(tx) = waiter {
+                    let _ = tx.send(body);
+                }
+            }
+            // PUBREL（QoS 2 第二步）回 PUBCOMP
+            6 if body.len() >= 2 => {
+                let _ = client.send(&packet(0x70, &body[..2])).await;
+            }
+            // PINGRESP 等忽略
+            _ => {}
+        }
+    };
+
+    if !client.stopped.load(Ordering::SeqCst) {
+        log::warn!("MQTT 连接断开: {:?}", error);
+        set_status(&app, &client, SessionStatus::Error, error).await;
+    }
+}
+
+// ============== 命令 ==============
+
+/// 连接 MQTT 服务器
+#[tauri::command]
+#[specta::specta]
+pub async fn mqtt_connect(app: AppHandle, input: MqttConnectInput) -> AppResult<MqttSession> {
+    if input.host.trim().is_empty() || input.port == 0 {
+        return Err(AppError::invalid("主机不能为空、端口不能为 0"));
+    }
+    let client_id = input
+        .client_id
+        .filter(|c| !c.trim().is_empty())
+        .unwrap_or_else(|| format!("codeshelf-{}", generate_id()));
+    let keep_alive = input.keep_alive_secs.unwrap_or(60);
+    let timeout = Duration::from_millis(input.timeout_ms.unwrap_or(5000));
+
+    let addr = format!("{}:{}", input.host, input.port);
+    let stream = tokio::time::timeout(timeout, TcpStream::connect(&addr))
+        .await
+        .map_err(|_| AppError::from(format!("连接 {} 超时", addr)))?
+        .map_err(|e| AppError::from(format!("连接 {} 失败: {}", addr, e)))?;
+    let (mut reader, mut writer) = stream.into_split();
+
+    // CONNECT
+    let mut body = Vec::new();
+    put_str(&mut body, "MQTT");
+    body.push(4); // 协议级别 3.1.1
+    let username = input.username.filter(|u| !u.is_empty());
+    let password = input.password.filter(|p| !p.is_empty());
+    let mut flags = 0u8;
+    if input.clean_session.unwrap_or(true) {
+        flags |= 0x02;
+    }
+    if username.is_some() {
+        flags |= 0x80;
+    }
+    if password.is_some() {
+        flags |= 0x40;
+    }
+    body.push(flags);
+    body.extend_from_slice(&keep_alive.to_be_bytes());
+    put_str(&mut body, &client_id);
+    if let Some(u) = &username {
+        put_str(&mut body, u);
+    }
+    if let Some(p) = &password {
+        put_str(&mut body, p);
+    }
+    writer.write_all(&packet(0x10, &body)).await?;
+
+    // CONNACK
+    let (header, ack) = tokio::time::timeout(timeout, read_packet(&mut reader))
+        .await
+        .map_err(|_| AppError::from("等待 CONNACK 超时".to_string()))??;
+    if header >> 4 != 2 || ack.len() < 2 {
+        return Err(AppError::from("服务器返回了无效的 CONNACK".to_string()));
+    }
+    if ack[1] != 0 {
+        return Err(AppError::from(format!(
+            "MQTT 连接被拒绝: {}",
+            connack_error(ack[1])
+        )));
+    }
+
+    let id = generate_id();
+    let session = MqttSession {
+        id: id.clone(),
+        name: input.name.unwrap_or_else(|| format!("MQTT {}", addr)),
+        host: input.host,
+        port: input.port,
+        client_id,
+        status: SessionStatus::Connected,
+        error_message: None,
+        connected_at: current_timestamp(),
+        subscriptions: HashMap::new(),
+        messages_sent: 0,
+        messages_received: 0,
+    };
+    let client = Arc::new(MqttClient {
+        session: RwLock::new(session.clone()),
+        messages: RwLock::new(VecDeque::new()),
+        writer: Mutex::new(writer),
+        pending: std::sync::Mutex::new(HashMap::new()),
+        next_packet_id: AtomicU16::new(1),
+        stopped: AtomicBool::new(false),
+    });
+    CLIENTS.write().await.insert(id.clone(), client.clone());
+
+    tokio::spawn(read_loop(app, client.clone(), reader));
+
+    // 心跳
+    if keep_alive > 0 {
+        let client = client.clone();
+        tokio::spawn(async move {
+            let interval = Duration::from_secs((keep_alive as u64 / 2).max(1));
+            loop {
+                tokio::time::sleep(interval).await;
+                if client.stopped.load(Ordering::SeqCst)
+                    || client.send(&packet(0xC0, &[])).await.is_err()
+                {
+                    break;
+                }
+            }
+        });
+    }
+
+    log::info!("MQTT 已连接: {} ({})", addr, id);
+    Ok(session)
+}
+
+/// 断开并移除 MQTT 会话
+#[tauri::command]
+#[specta::specta]
+pub async fn mqtt_disconnect(session_id: String) -> AppResult<()> {
+    let client = CLIENTS
+        .write()
+        .await
+        .remove(&session_id)
+        .ok_or_else(|| AppError::invalid("MQTT 会话不存在"))?;
+    client.stopped.store(true, Ordering::SeqCst);
+    let _ = client.send(&packet(0xE0, &[])).await;
+    let _ = client.writer.lock().await.shutdown().await;
+    Ok(())
+}
+
+/// 订阅主题（支持 + / # 通配符）
+#[tauri::command]
+#[specta::specta]
+pub async fn mqtt_subscribe(session_id: String, topic: String, qos: Option<u8>) -> AppResult<u8> {
+    let qos = validate_qos(qos)?;
+    if topic.is_empty() {
+        return Err(AppError::invalid("主题不能为空"));
+    }
+    let client = get_client(&session_id).await?;
+    let pid = client.packet_id();
+    let mut body = pid.to_be_bytes().to_vec();
+    put_str(&mut body, &topic);
+    body.push(qos);
+    let ack = client.request(pid, &packet(0x82, &body)).await?;
+    let granted = ack.get(2).copied().unwrap_or(0x80);
+    if granted == 0x80 {
+        return Err(AppError::from(format!("服务器拒绝订阅: {}", topic)));
+    }
+    client
+        .session
+        .write()
+        .await
+        .subscriptions
+        .insert(topic, granted);
+    Ok(granted)
+}
+
+/// 取消订阅
+#[tauri::command]
+#[specta::specta]
+pub async fn mqtt_unsubscribe(session_id: String, topic: String) -> AppResult<()> {
+    let client = get_client(&session_id).await?;
+    let pid = client.packet_id();
+    let mut body = pid.to_be_bytes().to_vec();
+    put_str(&mut body, &topic);
+    client.request(pid, &packet(0xA2, &body)).await?;
+    client.session.write().await.subscriptions.remove(&topic);
+    Ok(())
+}
+
+/// 发布消息；QoS 1 时等待 PUBACK
+#[tauri::command]
+#[specta::specta]
+pub async fn mqtt_publish(
+    session_id: String,
+    topic: String,
+    payload: String,
+    format: DataFormat,
+    qos: Option<u8>,
+    retain: Option<bool>,
+) -> AppResult<MqttMessage> {
+    let qos = validate_qos(qos)?;
+    if topic.is_empty() || topic.contains(['+', '#']) {
+        return Err(AppError::invalid("发布主题不能为空，且不能包含通配符"));
+    }
+    let data = parse_input_data(&payload, format)?;
+    let retain = retain.unwrap_or(false);
+    let client = get_client(&session_id).await?;
+
+    let mut body = Vec::new();
+    put_str(&mut body, &topic);
+    let header = 0x30 | (qos << 1) | retain as u8;
+    if qos == 1 {
+        let pid = client.packet_id();
+        body.extend_from_slice(&pid.to_be_bytes());
+        body.extend_from_slice(&data);
+        client.request(pid, &packet(header, &body)).await?;
+    } else {
+        body.extend_from_slice(&data);
+        client.send(&packet(header, &body)).await?;
+    }
+
+    let message = MqttMessage {
+        id: generate_id(),
+        session_id: session_id.clone(),
+        direction: MessageDirection::Sent,
+        topic,
+        payload: bytes_to_display_string(&data),
+        size: data.len(),
+        qos,
+        retain,
+        timestamp: current_timestamp(),
+    };
+    client.session.write().await.messages_sent += 1;
+    client.push_message(message.clone()).await;
+    Ok(message)
+}
+
+/// 获取所有 MQTT 会话
+#[tauri::command]
+#[specta::specta]
+pub async fn mqtt_get_sessions() -> AppResult<Vec<MqttSession>> {
+    let clients = CLIENTS.read().await;
+    let mut sessions = Vec::new();
+    for client in clients.values() {
+        sessions.push(client.session.read().await.clone());
+    }
+    sessions.sort_by_key(|s| s.connected_at);
+    Ok(sessions)
+}
+
+/// 获取消息历史（最新的 limit 条，默认全部），可按主题过滤
+#[tauri::command]
+#[specta::specta]
+pub async fn mqtt_get_messages(
+    session_id: String,
+    topic: Option<String>,
+    limit: Option<usize>,
+) -> AppResult<Vec<MqttMessage>> {
+    let client = get_client(&session_id).await?;
+    let messages = client.messages.read().await;
+    let filtered: Vec<&MqttMessage> = messages
+        .iter()
+        .filter(|m| topic.as_deref().map_or(true, |t| m.topic == t))
+        .collect();
+    let skip = filtered.len().saturating_sub(limit.unwrap_or(usize::MAX));
+    Ok(filtered.into_iter().skip(skip).cloned().collect())
+}
+
+/// 清空消息历史
+#[tauri::command]
+#[specta::specta]
+pub async fn mqtt_clear_messages(session_id: String) -> AppResult<()> {
+    get_client(&session_id)
+        .await?
+        .messages
+        .write()
+        .await
+        .clear();
+    Ok(())
+}
@@ -0,0 +1,229 @@
+// Netcat 常用报文库 - 跨会话共享的命名报文（text / hex / base64），支持模板变量
+//
+// 发送时 SendMessageInput.payload_id 指定报文，或 render = true 时对输入内容渲染变量。
+// 变量与前端自动发送模板一致：
+//   {{timestamp}} {{datetime}} {{date}} {{time}} {{uuid}} {{seq}}
+//   {{random:1-100}} {{float:0-1}} {{choice:a,b,c}}
+// hex / base64 报文先渲染文本再解码，变量需要自行保证替换后仍是合法编码。
+
+use super::{current_timestamp, NetcatPayload, NetcatPayloadInput};
+use crate::commands::toolbox::generate_id;
+use crate::error::{AppError, AppResult};
+use crate::storage::config::StorageConfig;
+use crate::storage::PersistedStore;
+use once_cell::sync::Lazy;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static PAYLOADS: Lazy<PersistedStore<NetcatPayload>> = Lazy::new(|| {
+    PersistedStore::new(
+        "netcatPayloads",
+        "Netcat 报文库",
+        StorageConfig::netcat_payloads_file,
+        |p| p.id.clone(),
+    )
+});
+
+/// {{seq}} 计数器（进程内递增）
+static SEQ: AtomicU64 = AtomicU64::new(0);
+
+static VARIABLE: Lazy<regex::Regex> =
+    Lazy::new(|| regex::Regex::new(r"\{\{(\w+)(?::([^}]+))?\}\}").expect("valid regex"));
+
+/// 按 id 获取报文
+pub(super) async fn get_payload(id: &str) -> AppResult<NetcatPayload> {
+    PAYLOADS.ensure_loaded().await;
+    PAYLOADS
+        .lock()
+        .await
+        .get(id)
+        .cloned()
+        .ok_or_else(|| AppError::invalid(format!("报文不存在: {}", id)))
+}
+
+/// 报文库全部报文（会话导出时附带）
+pub(super) async fn all_payloads() -> Vec<NetcatPayload> {
+    PAYLOADS.ensure_loaded().await;
+    PAYLOADS.lock().await.values().cloned().collect()
+}
+
+/// 合并导入的报文：同名报文视为已存在并跳过，其余分配新 id；返回 (导入数, 跳过数)
+pub(super) async fn import_payloads(imported: Vec<NetcatPayload>) -> AppResult<(u32, u32)> {
+    PAYLOADS.ensure_loaded().await;
+    let (mut added, mut skipped) = (0, 0);
+    {
+        let mut payloads = PAYLOADS.lock().await;
+        for payload in imported {
+            let name = payload.name.trim().to_string();
+            if name.is_empty() || payloads.values().any(|p| p.name == name) {
+                skipped += 1;
+                continue;
+            }
+            // 连续生成的时间戳 id 在低精度时钟下可能重复，重复时重新生成
+            let mut id = generate_id();
+            while payloads.contains_key(&id) {
+                id = generate_id();
+            }
+            let payload = NetcatPayload {
+                id,
+                name,
+                ..payload
+            };
+            payloads.insert(payload.id.clone(), payload);
+            added += 1;
+        }
+    }
+    if added > 0 {
+        PAYLOADS.save().await?;
+    }
+    Ok((added, skipped))
+}
+
+/// 渲染模板变量，未知变量原样保留
+pub(super) fn render_template(template: &str) -> String {
+    if !template.contains("{{") {
+        return template.to_string();
+    }
+    VARIABLE
+        .replace_all(template, |caps: &regex::Captures| {
+            let param = caps.get(2).map(|m| m.as_str());
+            match &caps[1] {
+                "timestamp" => current_timestamp().to_string(),
+                "datetime" => chrono::Utc::now().to_rfc3339(),
+                "date" => chrono::Local::now().format("%Y-%m-%d").to_string(),
+                "time" => chrono::Local::now().format("%H:%M:%S").to_string(),
+                "uuid" => uuid_v4(),
+                "seq" => (SEQ.fetch_add(1, Ordering::Relaxed) + 1).to_string(),
+                "random" => {
+                    let (min, max) = parse_range(param.unwrap_or("1-100"), 1.0, 100.0);
+                    let (min, max) = (min as i64, (max as i64).max(min as i64));
+                    (min + (random_u64() % (max - min + 1) as u64) as i64).to_string()
+                }
+                "float" => {
+                    let (min, max) = parse_range(param.unwrap_or("0-1"), 0.0, 1.0);
+                    let unit = (random_u64() >> 11) as f64 / (1u64 << 53) as f64;
+                    format!("{:.2}", min + unit * (max - min))
+                }
+                "choice" => {
+                    let choices: Vec<&str> = param.unwrap_or("a,b,c").split(',').collect();
+                    choices[(random_u64() % choices.len() as u64) as usize].to_string()
+                }
+                _ => caps[0].to_string(),
+            }
+        })
+        .into_owned()
+}
+
+fn parse_range(param: &str, default_min: f64, default_max: f64) -> (f64, f64) {
+    let mut parts = param.splitn(2, '-');
+    let min = parts
+        .next()
+        .and_then(|s| s.trim().parse().ok())
+        .unwrap_or(default_min);
+    let max = parts
+        .next()
+        .and_then(|s| s.trim().parse().ok())
+        .unwrap_or(default_max);
+    (min, max)
+}
+
+fn random_u64() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(SEQ.load(Ordering::Relaxed));
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    hasher.finish()
+}
+
+fn uuid_v4() -> String {
+    let mut bytes = [0u8; 16];
+    bytes[..8].copy_from_slice(&random_u64().to_be_bytes());
+    bytes[8..].copy_from_slice(&random_u64().to_be_bytes());
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// 获取报文库
+#[tauri::command]
+#[specta::specta]
+pub async fn netcat_get_payloads() -> AppResult<Vec<NetcatPayload>> {
+    PAYLOADS.ensure_loaded().await;
+    let mut payloads: Vec<NetcatPayload> = PAYLOADS.lock().await.values().cloned().collect();
+    payloads.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(payloads)
+}
+
+/// 新建（id 为空）或更新报文
+#[tauri::command]
+#[specta::specta]
+pub async fn netcat_save_payload(
+    id: Option<String>,
+    input: NetcatPayloadInput,
+) -> AppResult<NetcatPayload> {
+    if input.name.trim().is_empty() {
+        return Err(AppError::invalid("报文名称不能为空"));
+    }
+    PAYLOADS.ensure_loaded().await;
+    let now = current_timestamp();
+    let payload = {
+        let mut payloads = PAYLOADS.lock().await;
+        match id {
+            Some(id) => {
+                let existing = payloads
+                    .get_mut(&id)
+                    .ok_or_else(|| AppError::invalid(format!("报文不存在: {}", id)))?;
+                existing.name = input.name.trim().to_string();
+                existing.data = input.data;
+                existing.format = input.format;
+                existing.description = input.description;
+                existing.updated_at = now;
+                existing.clone()
+            }
+            None => {
+                let payload = NetcatPayload {
+                    id: generate_id(),
+                    name: input.name.trim().to_string(),
+                    data: input.data,
+                    format: input.format,
+                    description: input.description,
+                    created_at: now,
+                    updated_at: now,
+                };
+                payloads.insert(payload.id.clone(), payload.clone());
+                payload
+            }
+        }
+    };
+    PAYLOADS.save().await?;
+    Ok(payload)
+}
+
+/// 删除报文
+#[tauri::command]
+#[specta::specta]
+pub async fn netcat_delete_payload(id: String) -> AppResult<()> {
+    PAYLOADS.ensure_loaded().await;
+    PAYLOADS.lock().await.remove(&id);
+    PAYLOADS.save().await
+}
+
+/// 预览渲染结果（不发送）
+#[tauri::command]
+#[specta::specta]
+pub async fn netcat_render_payload(template: String) -> AppResult<String> {
+    Ok(render_template(&template))
+}
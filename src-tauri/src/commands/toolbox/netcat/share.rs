@@ -0,0 +1,122 @@
+// Netcat 会话导入导出 - 把选中会话的配置（可附带报文库）写成 JSON 文件，
+// 在另一台机器导入，便于团队共享一套设备 / 服务连接配置。
+//
+// 导出文件不含消息历史和运行状态；导入的会话分配新 id，报文按名称去重合并。
+
+use super::payloads::{all_payloads, import_payloads};
+use super::{
+    current_timestamp, normalize_group, traffic_log, NetcatExportFile, NetcatImportResult,
+    NetcatSession, NetcatSessionConfig, NetcatState, SessionState,
+};
+use crate::commands::toolbox::generate_id;
+use crate::error::{AppError, AppResult};
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::RwLock;
+
+/// 当前导出文件格式版本，导入时拒绝更高版本
+const EXPORT_VERSION: u32 = 1;
+
+/// 导出选中的会话到 file_path；返回导出的会话数
+#[tauri::command]
+#[specta::specta]
+pub async fn netcat_export_sessions(
+    state: State<'_, NetcatState>,
+    session_ids: Vec<String>,
+    include_payloads: bool,
+    file_path: String,
+) -> AppResult<u32> {
+    let mut configs: Vec<NetcatSessionConfig> = Vec::new();
+    {
+        let sessions = state.sessions.read().await;
+        for id in &session_ids {
+            let session_state = sessions
+                .get(id)
+                .ok_or_else(|| AppError::invalid(format!("会话不存在: {}", id)))?;
+            configs.push((&session_state.read().await.session).into());
+        }
+    }
+    if configs.is_empty() {
+        return Err(AppError::invalid("请选择要导出的会话"));
+    }
+
+    let mut payloads = if include_payloads {
+        all_payloads().await
+    } else {
+        Vec::new()
+    };
+    payloads.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let count = configs.len() as u32;
+    let file = NetcatExportFile {
+        version: EXPORT_VERSION,
+        exported_at: current_timestamp(),
+        sessions: configs,
+        payloads,
+    };
+    let content = serde_json::to_string_pretty(&file)
+        .map_err(|e| AppError::internal(format!("序列化导出文件失败: {}", e)))?;
+    std::fs::write(&file_path, content)
+        .map_err(|e| AppError::other(format!("写入导出文件失败: {}", e)))?;
+    log::info!("Netcat 导出 {} 个会话到 {}", count, file_path);
+    Ok(count)
+}
+
+/// 从导出文件导入会话（新建，不覆盖已有会话）与报文
+#[tauri::command]
+#[specta::specta]
+pub async fn netcat_import_sessions(
+    state: State<'_, NetcatState>,
+    file_path: String,
+) -> AppResult<NetcatImportResult> {
+    let content = std::fs::read_to_string(&file_path)
+        .map_err(|e| AppError::other(format!("读取导入文件失败: {}", e)))?;
+    let file: NetcatExportFile = serde_json::from_str(&content)
+        .map_err(|e| AppError::invalid(format!("不是有效的 Netcat 会话导出文件: {}", e)))?;
+    if file.version > EXPORT_VERSION {
+        return Err(AppError::invalid(format!(
+            "导出文件版本 {} 高于当前支持的版本 {}，请升级应用后再导入",
+            file.version, EXPORT_VERSION
+        )));
+    }
+
+    let now = current_timestamp();
+    let mut imported: Vec<NetcatSession> = Vec::new();
+    {
+        let mut sessions = state.sessions.write().await;
+        for mut cfg in file.sessions {
+            // 连续生成的时间戳 id 在低精度时钟下可能重复，重复时重新生成
+            cfg.id = generate_id();
+            while sessions.contains_key(&cfg.id) {
+                cfg.id = generate_id();
+            }
+            cfg.created_at = now;
+            cfg.group = normalize_group(cfg.group);
+            // 自动发送需在本机确认后再开启
+            cfg.auto_send.enabled = false;
+            traffic_log::configure(&cfg.id, cfg.log_mode, cfg.log_payload);
+            let session = NetcatSession::from(cfg);
+            sessions.insert(
+                session.id.clone(),
+                Arc::new(RwLock::new(SessionState::new(session.clone()))),
+            );
+            imported.push(session);
+        }
+    }
+    if !imported.is_empty() {
+        state.save_sessions().await?;
+    }
+
+    let (payloads_imported, payloads_skipped) = import_payloads(file.payloads).await?;
+    log::info!(
+        "Netcat 从 {} 导入 {} 个会话、{} 条报文",
+        file_path,
+        imported.len(),
+        payloads_imported
+    );
+    Ok(NetcatImportResult {
+        sessions: imported,
+        payloads_imported,
+        payloads_skipped,
+    })
+}
@@ -2,6 +2,7 @@
 
 use super::types::*;
 use crate::commands::toolbox::generate_id;
+use crate::commands::toolbox::pcap::{self, Transport};
 use crate::error::AppResult;
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -80,6 +81,11 @@ pub async fn start_tcp_client(
         state.shutdown_tx = Some(shutdown_tx);
     }
 
+    // 抓包用的两端地址
+    let unknown = std::net::SocketAddr::from(([0, 0, 0, 0], 0));
+    let local = stream.local_addr().unwrap_or(unknown);
+    let peer = stream.peer_addr().unwrap_or(unknown);
+
     // 分割流
     let (mut reader, writer) = stream.into_split();
     let writer = Arc::new(RwLock::new(writer));
@@ -143,6 +149,7 @@ pub async fn start_tcp_client(
                 }
                 Ok(Ok(n)) => {
                     let data = buffer[..n].to_vec();
+                    pcap::record(&session_id_clone, Transport::Tcp, peer, local, &data);
                     handle_received_data(&app_clone, &session_state_clone, data, None).await;
                 }
                 Ok(Err(e)) => {
@@ -197,7 +204,7 @@ pub async fn start_tcp_client(
                 break;
             }
 
-            log::info!(
+            log::debug!(
                 "Netcat Client 从通道收到数据: {} bytes, 准备写入服务器",
                 data.len()
             );
@@ -217,14 +224,16 @@ pub async fn start_tcp_client(
                 break;
             }
 
-            log::info!(
+            log::debug!(
                 "Netcat Client 数据已写入并刷新到服务器: {} bytes",
                 data.len()
             );
+            pcap::record(&session_id_for_send, Transport::Tcp, local, peer, &data);
 
             // 更新统计
             let mut state = session_state_clone2.write().await;
             state.session.bytes_sent += data.len() as u64;
+            state.record_throughput(MessageDirection::Sent, None, data.len());
             state.session.last_activity = Some(current_timestamp());
         }
         log::info!("Netcat Client 发送任务结束: target={}", addr_clone);
@@ -240,7 +249,7 @@ pub async fn start_tcp_client(
 
 /// 发送数据到 TCP 客户端
 pub async fn send_tcp_client_data(session_id: &str, data: Vec<u8>) -> AppResult<()> {
-    log::info!(
+    log::debug!(
         "Netcat Client 发送数据: session={}, size={}",
         session_id,
         data.len()
@@ -257,7 +266,7 @@ pub async fn send_tcp_client_data(session_id: &str, data: Vec<u8>) -> AppResult<
     if let Some(tx) = senders.get(session_id) {
         match tx.send(data).await {
             Ok(_) => {
-                log::info!("Netcat Client 数据已发送到通道: session={}", session_id);
+                log::debug!("Netcat Client 数据已发送到通道: session={}", session_id);
                 Ok(())
             }
             Err(e) => {
@@ -304,22 +313,14 @@ async fn handle_received_data(
     let message_id = generate_id();
     let data_preview = bytes_to_display_string(&data);
 
-    // 安全截断预览（字符边界安全）
-    let preview_safe: String = data_preview.chars().take(50).collect();
-    log::info!(
-        "Netcat Client 收到数据: {} bytes, preview={}",
-        data.len(),
-        preview_safe
-    );
-
     // 使用超时来获取锁，避免死锁
     let lock_result =
         tokio::time::timeout(std::time::Duration::from_secs(5), session_state.write()).await;
 
-    let (session_id, message) = match lock_result {
+    let (session_id, message, saved) = match lock_result {
         Ok(mut state) => {
             state.session.bytes_received += data.len() as u64;
-            state.session.message_count += 1;
+            state.record_throughput(MessageDirection::Received, None, data.len());
             state.session.last_activity = Some(now);
 
             let server_addr = format!("{}:{}", state.session.host, state.session.port);
@@ -336,12 +337,8 @@ async fn handle_received_data(
                 client_addr: Some(server_addr),
             };
 
-            state.messages.push(message.clone());
-
-            // 限制消息历史
-            if state.messages.len() > 1000 {
-                state.messages.remove(0);
-            }
+            // 按会话的上限与溢出策略保存
+            let saved = state.record_message(app, message.clone());
 
             let sid = state.session.id.clone();
             let msg_count = state.session.message_count;
@@ -351,7 +348,7 @@ async fn handle_received_data(
                 msg_count
             );
 
-            (sid, message)
+            (sid, message, saved)
         }
         Err(_) => {
             log::error!("Netcat Client 获取写锁超时，跳过此消息: id={}", message_id);
@@ -359,6 +356,19 @@ async fn handle_received_data(
         }
     };
 
+    super::traffic_log::log_traffic(
+        &session_id,
+        "收到",
+        message.size,
+        message.client_addr.as_deref(),
+        &message.data,
+    );
+
+    // 已停止记录时不再推送
+    if !saved {
+        return;
+    }
+
     // 发送事件 - 在锁释放后
     let event = NetcatEvent::MessageReceived {
         session_id: session_id.clone(),
@@ -367,7 +377,7 @@ async fn handle_received_data(
 
     log::debug!("Netcat Client 准备发送事件: session={}", session_id);
     match app.emit("netcat-event", &event) {
-        Ok(_) => log::info!("Netcat Client 消息事件已发送: session={}", session_id),
+        Ok(_) => log::debug!("Netcat Client 消息事件已发送: session={}", session_id),
         Err(e) => log::error!(
             "Netcat Client 消息事件发送失败: {} (session={})",
             e,
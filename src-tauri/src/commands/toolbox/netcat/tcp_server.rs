@@ -2,6 +2,7 @@
 
 use super::types::*;
 use crate::commands::toolbox::generate_id;
+use crate::commands::toolbox::pcap::{self, Transport};
 use crate::error::AppResult;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -137,7 +138,7 @@ pub async fn start_tcp_server(
     {
         let mut state = session_state.write().await;
         state.session.status = SessionStatus::Disconnected;
-        state.clients.clear();
+        state.clear_clients();
     }
 
     SERVER_CLIENTS.write().await.remove(&session_id);
@@ -167,6 +168,8 @@ async fn handle_client_connection(
         last_activity: now,
         bytes_sent: 0,
         bytes_received: 0,
+        send_rate: 0.0,
+        recv_rate: 0.0,
     };
 
     // 添加到会话状态
@@ -185,6 +188,11 @@ async fn handle_client_connection(
         },
     );
 
+    // 抓包用的两端地址
+    let unknown = std::net::SocketAddr::from(([0, 0, 0, 0], 0));
+    let local = stream.local_addr().unwrap_or(unknown);
+    let peer = stream.peer_addr().unwrap_or(unknown);
+
     // 分割流
     let (mut reader, writer) = stream.into_split();
     let writer = Arc::new(RwLock::new(writer));
@@ -211,6 +219,7 @@ async fn handle_client_connection(
     let client_id_clone2 = client_id.clone();
     let client_addr_clone = client_addr.clone();
     let shutdown_flag_send = shutdown_flag.clone();
+    let session_id_for_send = session_id.clone();
 
     tokio::spawn(async move {
         log::info!("Netcat Server 发送任务启动: client={}", client_addr_clone);
@@ -227,7 +236,7 @@ async fn handle_client_connection(
                 break;
             }
 
-            log::info!(
+            log::debug!(
                 "Netcat Server 从通道收到数据: {} bytes, 准备写入客户端 {}",
                 request.data.len(),
                 client_addr_clone
@@ -257,12 +266,24 @@ async fn handle_client_connection(
             }
 
             let data_len = request.data.len();
-            log::info!("Netcat Server 数据已写入并刷新到客户端: {} bytes", data_len);
+            pcap::record(
+                &session_id_for_send,
+                Transport::Tcp,
+                local,
+                peer,
+                &request.data,
+            );
+            log::debug!("Netcat Server 数据已写入并刷新到客户端: {} bytes", data_len);
             let _ = request.result_tx.send(Ok(()));
 
             // 更新统计
             let mut state = session_state_clone2.write().await;
             state.session.bytes_sent += data_len as u64;
+            state.record_throughput(
+                MessageDirection::Sent,
+                Some(client_id_clone2.as_str()),
+                data_len,
+            );
             if let Some(client) = state.clients.get_mut(&client_id_clone2) {
                 client.bytes_sent += data_len as u64;
                 client.last_activity = current_timestamp();
@@ -316,7 +337,7 @@ async fn handle_client_connection(
                 }
                 Ok(Ok(n)) => {
                     message_count += 1;
-                    log::info!(
+                    log::debug!(
                         "Netcat Server [{}] 收到第{}条数据: {} bytes from {}",
                         client_id_clone,
                         message_count,
@@ -324,6 +345,7 @@ async fn handle_client_connection(
                         client_addr
                     );
                     let data = buffer[..n].to_vec();
+                    pcap::record(&session_id_clone, Transport::Tcp, peer, local, &data);
 
                     // 使用 spawn 来避免阻塞读取循环
                     let app_for_handle = app_clone.clone();
@@ -397,8 +419,7 @@ async fn handle_client_disconnect(
     // 从会话状态移除
     {
         let mut state = session_state.write().await;
-        state.clients.remove(client_id);
-        state.session.client_count = state.clients.len() as u32;
+        state.remove_client(client_id);
     }
 
     // 从全局存储移除
@@ -431,23 +452,14 @@ async fn handle_received_data(
     let message_id = generate_id();
     let data_preview = bytes_to_display_string(&data);
 
-    // 安全截断预览（字符边界安全）
-    let preview_safe: String = data_preview.chars().take(50).collect();
-    log::info!(
-        "Netcat Server handle_received_data: {} bytes, from={:?}, preview={}",
-        data.len(),
-        client_addr,
-        preview_safe
-    );
-
     // 使用超时来获取锁，避免死锁
     let lock_result =
         tokio::time::timeout(std::time::Duration::from_secs(5), session_state.write()).await;
 
-    let (session_id, message) = match lock_result {
+    let (session_id, message, saved) = match lock_result {
         Ok(mut state) => {
             state.session.bytes_received += data.len() as u64;
-            state.session.message_count += 1;
+            state.record_throughput(MessageDirection::Received, client_id.as_deref(), data.len());
             state.session.last_activity = Some(now);
 
             // 更新客户端统计
@@ -470,12 +482,8 @@ async fn handle_received_data(
                 client_addr,
             };
 
-            state.messages.push(message.clone());
-
-            // 限制消息历史
-            if state.messages.len() > 1000 {
-                state.messages.remove(0);
-            }
+            // 按会话的上限与溢出策略保存
+            let saved = state.record_message(app, message.clone());
 
             let sid = state.session.id.clone();
             let msg_count = state.session.message_count;
@@ -485,7 +493,7 @@ async fn handle_received_data(
                 msg_count
             );
 
-            (sid, message)
+            (sid, message, saved)
         }
         Err(_) => {
             log::error!("Netcat Server 获取写锁超时，跳过此消息: id={}", message_id);
@@ -493,6 +501,19 @@ async fn handle_received_data(
         }
     };
 
+    super::traffic_log::log_traffic(
+        &session_id,
+        "收到",
+        message.size,
+        message.client_addr.as_deref(),
+        &message.data,
+    );
+
+    // 已停止记录时不再推送
+    if !saved {
+        return;
+    }
+
     // 发送事件 - 在锁释放后
     let event = NetcatEvent::MessageReceived {
         session_id: session_id.clone(),
@@ -501,7 +522,7 @@ async fn handle_received_data(
 
     log::debug!("Netcat Server 准备发送事件: session={}", session_id);
     match app.emit("netcat-event", &event) {
-        Ok(_) => log::info!("Netcat Server 消息事件已发送: session={}", session_id),
+        Ok(_) => log::debug!("Netcat Server 消息事件已发送: session={}", session_id),
         Err(e) => log::error!(
             "Netcat Server 消息事件发送失败: {} (session={})",
             e,
@@ -512,7 +533,7 @@ async fn handle_received_data(
 
 /// 发送数据到指定客户端
 pub async fn send_to_client(session_id: &str, client_id: &str, data: Vec<u8>) -> AppResult<()> {
-    log::info!(
+    log::debug!(
         "Netcat Server 发送数据到客户端: session={}, client={}, size={}",
         session_id,
         client_id,
@@ -559,7 +580,7 @@ pub async fn send_to_client(session_id: &str, client_id: &str, data: Vec<u8>) ->
 
 /// 广播数据到所有客户端
 pub async fn broadcast_to_clients(session_id: &str, data: Vec<u8>) -> AppResult<()> {
-    log::info!(
+    log::debug!(
         "Netcat Server 广播数据: session={}, size={}",
         session_id,
         data.len()
@@ -580,7 +601,7 @@ pub async fn broadcast_to_clients(session_id: &str, data: Vec<u8>) -> AppResult<
 
     let client_count = client_txs.len();
     if client_count > 0 {
-        log::info!("Netcat Server 广播到 {} 个客户端", client_count);
+        log::debug!("Netcat Server 广播到 {} 个客户端", client_count);
 
         if client_count == 0 {
             log::warn!("Netcat Server 没有已连接的客户端");
@@ -0,0 +1,177 @@
+// Netcat 流量日志 - 按会话控制收发数据写日志的频率
+//
+// 每个会话一个日志模式（log_mode）：
+//   - off:     不记录收发
+//   - sampled: 每秒最多 SAMPLED_LINES_PER_SEC 行，其余计入丢弃数（默认）
+//   - full:    每条都记录
+// 报文内容只在会话打开 log_payload 时以 debug 级别输出，避免高吞吐会话把日志文件撑大。
+
+use super::{NetcatLogStats, NetcatState, TrafficLogMode};
+use crate::error::AppResult;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::State;
+
+/// sampled 模式下每秒最多记录的行数
+const SAMPLED_LINES_PER_SEC: u32 = 5;
+
+/// 报文预览的字符数
+const PAYLOAD_PREVIEW_CHARS: usize = 80;
+
+struct SessionLog {
+    mode: TrafficLogMode,
+    log_payload: bool,
+    window_start: Instant,
+    window_lines: u32,
+    /// 当前窗口内被丢弃的行数，窗口切换时汇总输出一次
+    window_dropped: u64,
+    logged: u64,
+    dropped: u64,
+}
+
+impl SessionLog {
+    fn new(mode: TrafficLogMode, log_payload: bool) -> Self {
+        Self {
+            mode,
+            log_payload,
+            window_start: Instant::now(),
+            window_lines: 0,
+            window_dropped: 0,
+            logged: 0,
+            dropped: 0,
+        }
+    }
+}
+
+static LOGS: Lazy<Mutex<HashMap<String, SessionLog>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn logs() -> std::sync::MutexGuard<'static, HashMap<String, SessionLog>> {
+    LOGS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 会话创建 / 加载 / 修改日志设置时调用，已有计数保留
+pub(super) fn configure(session_id: &str, mode: TrafficLogMode, log_payload: bool) {
+    logs()
+        .entry(session_id.to_string())
+        .and_modify(|log| {
+            log.mode = mode;
+            log.log_payload = log_payload;
+        })
+        .or_insert_with(|| SessionLog::new(mode, log_payload));
+}
+
+pub(super) fn remove(session_id: &str) {
+    logs().remove(session_id);
+}
+
+/// 记录一次收发；direction 如 "收到" / "发送"，peer 为对端地址，preview 为报文的显示文本
+pub(super) fn log_traffic(
+    session_id: &str,
+    direction: &str,
+    size: usize,
+    peer: Option<&str>,
+    preview: &str,
+) {
+    let (log_payload, summary) = {
+        let mut logs = logs();
+        let log = logs
+            .entry(session_id.to_string())
+            .or_insert_with(|| SessionLog::new(TrafficLogMode::default(), false));
+        let mut summary = None;
+        let allowed = match log.mode {
+            TrafficLogMode::Off => false,
+            TrafficLogMode::Full => true,
+            TrafficLogMode::Sampled => {
+                if log.window_start.elapsed() >= Duration::from_secs(1) {
+                    if log.window_dropped > 0 {
+                        summary = Some(log.window_dropped);
+                    }
+                    log.window_start = Instant::now();
+                    log.window_lines = 0;
+                    log.window_dropped = 0;
+                }
+                log.window_lines < SAMPLED_LINES_PER_SEC
+            }
+        };
+        if !allowed {
+            log.dropped += 1;
+            log.window_dropped += 1;
+            return;
+        }
+        log.logged += 1;
+        log.window_lines += 1;
+        (log.log_payload, summary)
+    };
+
+    if let Some(skipped) = summary {
+        log::info!(
+            "Netcat [{}] 上一秒省略了 {} 条流量日志",
+            session_id,
+            skipped
+        );
+    }
+    match peer {
+        Some(peer) => log::info!(
+            "Netcat [{}] {} {} bytes ({})",
+            session_id,
+            direction,
+            size,
+            peer
+        ),
+        None => log::info!("Netcat [{}] {} {} bytes", session_id, direction, size),
+    }
+    if log_payload {
+        let preview: String = preview.chars().take(PAYLOAD_PREVIEW_CHARS).collect();
+        log::debug!("Netcat [{}] 内容: {:?}", session_id, preview);
+    }
+}
+
+/// 设置会话的流量日志模式；log_payload 为空时保持不变
+#[tauri::command]
+#[specta::specta]
+pub async fn netcat_set_log_mode(
+    state: State<'_, NetcatState>,
+    session_id: String,
+    mode: TrafficLogMode,
+    log_payload: Option<bool>,
+) -> AppResult<()> {
+    {
+        let sessions = state.sessions.read().await;
+        let session_state = sessions.get(&session_id).ok_or("会话不存在")?;
+        let mut s = session_state.write().await;
+        s.session.log_mode = mode;
+        if let Some(v) = log_payload {
+            s.session.log_payload = v;
+        }
+        configure(&session_id, mode, s.session.log_payload);
+    }
+    state.save_sessions().await
+}
+
+/// 会话的流量日志计数
+#[tauri::command]
+#[specta::specta]
+pub async fn netcat_get_log_stats(
+    state: State<'_, NetcatState>,
+    session_id: String,
+) -> AppResult<NetcatLogStats> {
+    let (mode, log_payload) = {
+        let sessions = state.sessions.read().await;
+        let session_state = sessions.get(&session_id).ok_or("会话不存在")?;
+        let s = session_state.read().await;
+        (s.session.log_mode, s.session.log_payload)
+    };
+    let (logged_lines, dropped_lines) = logs()
+        .get(&session_id)
+        .map(|log| (log.logged, log.dropped))
+        .unwrap_or_default();
+    Ok(NetcatLogStats {
+        session_id,
+        mode,
+        log_payload,
+        logged_lines,
+        dropped_lines,
+    })
+}
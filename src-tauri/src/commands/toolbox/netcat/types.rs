@@ -1,8 +1,10 @@
 // Netcat 工具类型定义
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
 use tokio::sync::{mpsc, RwLock};
 
 /// 协议类型
@@ -57,6 +59,40 @@ impl Default for AutoSendMode {
     }
 }
 
+/// 收发流量的日志模式
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum TrafficLogMode {
+    /// 不记录
+    Off,
+    /// 按频率抽样记录
+    #[default]
+    Sampled,
+    /// 每条都记录
+    Full,
+}
+
+/// 消息历史达到上限后的处理方式
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum OverflowPolicy {
+    /// 丢弃最早的消息
+    #[default]
+    DropOldest,
+    /// 停止记录新消息，直到清空历史
+    StopCapturing,
+}
+
+/// 默认保留的消息条数
+pub const DEFAULT_HISTORY_LIMIT: usize = 1000;
+
+/// 消息条数上限的允许范围
+pub const MAX_HISTORY_LIMIT: usize = 100_000;
+
+fn default_history_limit() -> usize {
+    DEFAULT_HISTORY_LIMIT
+}
+
 /// 自动发送配置
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
@@ -133,6 +169,9 @@ pub struct NetcatSessionInput {
     pub name: Option<String>,
     pub auto_reconnect: Option<bool>,
     pub timeout_ms: Option<u64>,
+    /// 所属分组
+    #[serde(default)]
+    pub group: Option<String>,
 }
 
 /// 会话配置（持久化存储）
@@ -151,6 +190,21 @@ pub struct NetcatSessionConfig {
     /// 自动发送配置
     #[serde(default)]
     pub auto_send: AutoSendConfig,
+    /// 所属分组
+    #[serde(default)]
+    pub group: Option<String>,
+    /// 流量日志模式
+    #[serde(default)]
+    pub log_mode: TrafficLogMode,
+    /// 是否以 debug 级别输出报文内容
+    #[serde(default)]
+    pub log_payload: bool,
+    /// 保留的消息条数上限
+    #[serde(default = "default_history_limit")]
+    pub history_limit: usize,
+    /// 达到上限后的处理方式
+    #[serde(default)]
+    pub overflow_policy: OverflowPolicy,
 }
 
 /// 会话配置
@@ -180,6 +234,87 @@ pub struct NetcatSession {
     /// 自动发送配置
     #[serde(default)]
     pub auto_send: AutoSendConfig,
+    /// 所属分组（如 "设备农场"），用于批量启停与广播
+    #[serde(default)]
+    pub group: Option<String>,
+    /// 流量日志模式
+    #[serde(default)]
+    pub log_mode: TrafficLogMode,
+    /// 是否以 debug 级别输出报文内容
+    #[serde(default)]
+    pub log_payload: bool,
+    /// 保留的消息条数上限
+    #[serde(default = "default_history_limit")]
+    pub history_limit: usize,
+    /// 达到上限后的处理方式
+    #[serde(default)]
+    pub overflow_policy: OverflowPolicy,
+    /// 因达到上限（stopCapturing）已停止记录消息，清空历史后恢复
+    #[serde(default)]
+    pub capture_stopped: bool,
+    /// 最近 10 秒的平均发送速率（字节/秒），查询时计算
+    #[serde(default)]
+    pub send_rate: f64,
+    /// 最近 10 秒的平均接收速率（字节/秒）
+    #[serde(default)]
+    pub recv_rate: f64,
+}
+
+impl From<NetcatSessionConfig> for NetcatSession {
+    /// 由持久化配置还原会话（未连接状态，计数清零）
+    fn from(cfg: NetcatSessionConfig) -> Self {
+        Self {
+            id: cfg.id,
+            name: cfg.name,
+            protocol: cfg.protocol,
+            mode: cfg.mode,
+            host: cfg.host,
+            port: cfg.port,
+            status: SessionStatus::Disconnected,
+            auto_reconnect: cfg.auto_reconnect,
+            timeout_ms: cfg.timeout_ms,
+            created_at: cfg.created_at,
+            connected_at: None,
+            last_activity: None,
+            bytes_sent: 0,
+            bytes_received: 0,
+            message_count: 0,
+            error_message: None,
+            local_addr: None,
+            client_count: 0,
+            auto_send: cfg.auto_send,
+            group: cfg.group,
+            log_mode: cfg.log_mode,
+            log_payload: cfg.log_payload,
+            history_limit: cfg.history_limit.clamp(1, MAX_HISTORY_LIMIT),
+            overflow_policy: cfg.overflow_policy,
+            capture_stopped: false,
+            send_rate: 0.0,
+            recv_rate: 0.0,
+        }
+    }
+}
+
+impl From<&NetcatSession> for NetcatSessionConfig {
+    fn from(session: &NetcatSession) -> Self {
+        Self {
+            id: session.id.clone(),
+            name: session.name.clone(),
+            protocol: session.protocol,
+            mode: session.mode,
+            host: session.host.clone(),
+            port: session.port,
+            auto_reconnect: session.auto_reconnect,
+            timeout_ms: session.timeout_ms,
+            created_at: session.created_at,
+            auto_send: session.auto_send.clone(),
+            group: session.group.clone(),
+            log_mode: session.log_mode,
+            log_payload: session.log_payload,
+            history_limit: session.history_limit,
+            overflow_policy: session.overflow_policy,
+        }
+    }
 }
 
 /// 发送消息的输入
@@ -193,6 +328,99 @@ pub struct SendMessageInput {
     pub target_client: Option<String>,
     /// 是否广播给所有客户端（仅服务器模式）
     pub broadcast: Option<bool>,
+    /// 使用报文库中的报文（忽略 data / format）
+    #[serde(default)]
+    pub payload_id: Option<String>,
+    /// 发送前渲染 {{timestamp}} 等模板变量（使用报文库报文时总是渲染）
+    #[serde(default)]
+    pub render: Option<bool>,
+}
+
+/// 分组批量操作中单个会话的结果
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NetcatGroupOpResult {
+    pub session_id: String,
+    pub name: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// 分组汇总统计
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NetcatGroupStats {
+    pub group: String,
+    pub session_count: u32,
+    /// 已连接 / 监听中的会话数
+    pub active_count: u32,
+    pub error_count: u32,
+    pub client_count: u32,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub message_count: u64,
+    pub last_activity: Option<u64>,
+}
+
+/// 会话流量日志计数
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NetcatLogStats {
+    pub session_id: String,
+    pub mode: TrafficLogMode,
+    pub log_payload: bool,
+    /// 已写入日志的收发行数
+    pub logged_lines: u64,
+    /// 因关闭或抽样被丢弃的行数
+    pub dropped_lines: u64,
+}
+
+/// 会话导出文件
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NetcatExportFile {
+    /// 文件格式版本
+    pub version: u32,
+    pub exported_at: u64,
+    pub sessions: Vec<NetcatSessionConfig>,
+    /// 导出时选择了附带报文库才有内容
+    #[serde(default)]
+    pub payloads: Vec<NetcatPayload>,
+}
+
+/// 会话导入结果
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NetcatImportResult {
+    pub sessions: Vec<NetcatSession>,
+    pub payloads_imported: u32,
+    /// 报文库中已有同名报文而跳过的数量
+    pub payloads_skipped: u32,
+}
+
+/// 报文库中的报文
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NetcatPayload {
+    pub id: String,
+    pub name: String,
+    /// 报文内容，可包含 {{timestamp}} 等模板变量
+    pub data: String,
+    pub format: DataFormat,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+/// 新建 / 更新报文的输入
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NetcatPayloadInput {
+    pub name: String,
+    pub data: String,
+    pub format: DataFormat,
+    pub description: Option<String>,
 }
 
 /// 消息记录
@@ -211,6 +439,29 @@ pub struct NetcatMessage {
     pub client_addr: Option<String>,
 }
 
+/// 消息筛选条件（netcat_get_messages），各条件同时满足
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NetcatMessageFilter {
+    #[serde(default)]
+    pub direction: Option<MessageDirection>,
+    /// 客户端 ID 或地址
+    #[serde(default)]
+    pub client: Option<String>,
+    /// 起始时间戳（毫秒，含）
+    #[serde(default)]
+    pub since: Option<u64>,
+    /// 截止时间戳（毫秒，含）
+    #[serde(default)]
+    pub until: Option<u64>,
+    /// 内容包含的文本（不区分大小写）
+    #[serde(default)]
+    pub text: Option<String>,
+    /// 内容包含的字节序列，十六进制如 "48 65" / "0x4865"
+    #[serde(default)]
+    pub hex: Option<String>,
+}
+
 /// 消息方向
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, specta::Type)]
 #[serde(rename_all = "lowercase")]
@@ -229,6 +480,68 @@ pub struct ConnectedClient {
     pub last_activity: u64,
     pub bytes_sent: u64,
     pub bytes_received: u64,
+    /// 最近 10 秒的平均发送速率（字节/秒）
+    #[serde(default)]
+    pub send_rate: f64,
+    /// 最近 10 秒的平均接收速率（字节/秒）
+    #[serde(default)]
+    pub recv_rate: f64,
+}
+
+/// 吞吐量统计的时间窗口
+const RATE_WINDOW: Duration = Duration::from_secs(10);
+
+/// 滑动窗口吞吐量：按秒分桶累计收发字节，只保留最近 RATE_WINDOW 内的桶
+#[derive(Debug, Default)]
+pub struct RateMeter {
+    /// (桶开始时间, 发送字节, 接收字节)
+    buckets: VecDeque<(Instant, u64, u64)>,
+    /// 首次记录的时间；统计不足一个窗口时按实际时长求平均
+    started: Option<Instant>,
+}
+
+impl RateMeter {
+    pub fn record(&mut self, direction: MessageDirection, bytes: usize) {
+        let now = Instant::now();
+        self.started.get_or_insert(now);
+        let (sent, received) = match direction {
+            MessageDirection::Sent => (bytes as u64, 0),
+            MessageDirection::Received => (0, bytes as u64),
+        };
+        match self.buckets.back_mut() {
+            Some((start, s, r)) if now.duration_since(*start) < Duration::from_secs(1) => {
+                *s += sent;
+                *r += received;
+            }
+            _ => self.buckets.push_back((now, sent, received)),
+        }
+        while self
+            .buckets
+            .front()
+            .is_some_and(|(start, _, _)| now.duration_since(*start) > RATE_WINDOW)
+        {
+            self.buckets.pop_front();
+        }
+    }
+
+    /// (发送, 接收) 字节/秒
+    pub fn rates(&self) -> (f64, f64) {
+        let Some(started) = self.started else {
+            return (0.0, 0.0);
+        };
+        let now = Instant::now();
+        let (sent, received) = self
+            .buckets
+            .iter()
+            .filter(|(start, _, _)| now.duration_since(*start) <= RATE_WINDOW)
+            .fold((0, 0), |(s, r), (_, bs, br)| (s + bs, r + br));
+        let secs = now
+            .duration_since(started)
+            .min(RATE_WINDOW)
+            .as_secs_f64()
+            .max(1.0);
+        (sent as f64 / secs, received as f64 / secs)
+    }
 }
 
 /// 会话事件（用于前端实时更新）
@@ -261,28 +574,146 @@ pub enum NetcatEvent {
         #[serde(rename = "clientId")]
         client_id: String,
     },
+    /// 消息历史达到上限，已停止记录新消息
+    #[serde(rename = "captureStopped")]
+    CaptureStopped {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        limit: usize,
+    },
 }
 
 /// 内部会话状态
 pub struct SessionState {
     pub session: NetcatSession,
-    pub messages: Vec<NetcatMessage>,
+    pub messages: VecDeque<NetcatMessage>,
     pub clients: HashMap<String, ConnectedClient>,
     pub shutdown_tx: Option<mpsc::Sender<()>>,
     /// 主任务句柄，用于强制终止
     pub task_handle: Option<tokio::task::AbortHandle>,
+    /// 会话整体吞吐量
+    pub rate: RateMeter,
+    /// 各客户端吞吐量（服务器模式），键为客户端 ID
+    pub client_rates: HashMap<String, RateMeter>,
 }
 
 impl SessionState {
     pub fn new(session: NetcatSession) -> Self {
         Self {
             session,
-            messages: Vec::new(),
+            messages: VecDeque::new(),
             clients: HashMap::new(),
             shutdown_tx: None,
             task_handle: None,
+            rate: RateMeter::default(),
+            client_rates: HashMap::new(),
+        }
+    }
+
+    /// 在读写循环中记录收发字节；client_id 为服务器模式下的对端客户端
+    pub fn record_throughput(
+        &mut self,
+        direction: MessageDirection,
+        client_id: Option<&str>,
+        bytes: usize,
+    ) {
+        self.rate.record(direction, bytes);
+        if let Some(id) = client_id.filter(|id| self.clients.contains_key(*id)) {
+            self.client_rates
+                .entry(id.to_string())
+                .or_default()
+                .record(direction, bytes);
+        }
+    }
+
+    /// 移除客户端及其吞吐量统计
+    pub fn remove_client(&mut self, client_id: &str) {
+        self.clients.remove(client_id);
+        self.client_rates.remove(client_id);
+        self.session.client_count = self.clients.len() as u32;
+    }
+
+    /// 清空客户端（会话停止时）
+    pub fn clear_clients(&mut self) {
+        self.clients.clear();
+        self.client_rates.clear();
+        self.session.client_count = 0;
+    }
+
+    /// 带当前速率的会话信息
+    pub fn session_snapshot(&self) -> NetcatSession {
+        let (send_rate, recv_rate) = self.rate.rates();
+        NetcatSession {
+            send_rate,
+            recv_rate,
+            ..self.session.clone()
         }
     }
+
+    /// 带当前速率的客户端列表
+    pub fn clients_snapshot(&self) -> Vec<ConnectedClient> {
+        self.clients
+            .values()
+            .map(|client| {
+                let (send_rate, recv_rate) = self
+                    .client_rates
+                    .get(&client.id)
+                    .map(|m| m.rates())
+                    .unwrap_or_default();
+                ConnectedClient {
+                    send_rate,
+                    recv_rate,
+                    ..client.clone()
+                }
+            })
+            .collect()
+    }
+
+    /// 记录一条消息，按会话的上限与溢出策略处理；返回是否已保存
+    pub fn push_message(&mut self, message: NetcatMessage) -> bool {
+        let limit = self.session.history_limit.max(1);
+        if self.messages.len() >= limit {
+            match self.session.overflow_policy {
+                OverflowPolicy::DropOldest => {
+                    while self.messages.len() >= limit {
+                        self.messages.pop_front();
+                    }
+                }
+                OverflowPolicy::StopCapturing => {
+                    if !self.session.capture_stopped {
+                        self.session.capture_stopped = true;
+                        log::warn!(
+                            "Netcat [{}] 消息历史已达上限 {} 条，停止记录新消息",
+                            self.session.id,
+                            limit
+                        );
+                    }
+                    return false;
+                }
+            }
+        }
+        self.messages.push_back(message);
+        true
+    }
+
+    /// 保存一条消息并计数；本条触发停止记录时通知前端。返回是否已保存，未保存的消息不应再推送
+    pub fn record_message(&mut self, app: &AppHandle, message: NetcatMessage) -> bool {
+        let was_stopped = self.session.capture_stopped;
+        if self.push_message(message) {
+            self.session.message_count += 1;
+            return true;
+        }
+        if !was_stopped {
+            let _ = app.emit(
+                "netcat-event",
+                NetcatEvent::CaptureStopped {
+                    session_id: self.session.id.clone(),
+                    limit: self.session.history_limit,
+                },
+            );
+        }
+        false
+    }
 }
 
 /// 全局会话管理器
@@ -292,3 +723,89 @@ pub type SessionManager = Arc<RwLock<HashMap<String, Arc<RwLock<SessionState>>>>
 pub fn create_session_manager() -> SessionManager {
     Arc::new(RwLock::new(HashMap::new()))
 }
+
+// ============== HTTP 请求 ==============
+
+/// HTTP 请求配置
+#[derive(Debug, Clone, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpFetchConfig {
+    pub url: String,
+    /// 任意 HTTP 方法，默认 GET
+    #[serde(default)]
+    pub method: Option<String>,
+    #[serde(default)]
+    pub headers: Option<HashMap<String, String>>,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub json_path: Option<String>,
+    /// 校验 TLS 证书，默认 false（本地调试常用自签名证书）
+    #[serde(default)]
+    pub verify_tls: Option<bool>,
+    /// 跟随重定向，默认 true
+    #[serde(default)]
+    pub follow_redirects: Option<bool>,
+    /// 最多跟随的重定向次数，默认 10
+    #[serde(default)]
+    pub max_redirects: Option<u32>,
+    /// 整个请求的超时（毫秒），默认 10000
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+/// 响应头（按原始顺序，允许重复）
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpHeader {
+    pub name: String,
+    pub value: String,
+}
+
+/// 重定向链中的一跳
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpRedirectHop {
+    pub url: String,
+    pub status: u16,
+    pub location: String,
+    pub duration_ms: u64,
+}
+
+/// 最后一跳的耗时阶段（毫秒，均从该跳开始计时的累计值，同 curl 的 time_*）
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpTiming {
+    /// DNS 解析完成
+    pub dns_ms: u64,
+    /// TCP 连接建立（单独的探测连接测得）
+    pub connect_ms: u64,
+    /// 收到响应头（首字节）
+    pub ttfb_ms: u64,
+    /// 响应体读取完成
+    pub total_ms: u64,
+}
+
+/// HTTP 请求的完整响应
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpFetchResponse {
+    pub status: u16,
+    pub status_text: String,
+    /// "HTTP/1.1" / "HTTP/2" ...
+    pub version: String,
+    pub headers: Vec<HttpHeader>,
+    pub body: String,
+    /// 响应体字节数（截断前）
+    pub body_size: u64,
+    /// 响应体超过上限被截断
+    pub body_truncated: bool,
+    /// 指定 jsonPath 且响应为 JSON 时的提取结果
+    pub extracted: Option<String>,
+    pub final_url: String,
+    pub remote_addr: Option<String>,
+    pub redirects: Vec<HttpRedirectHop>,
+    pub timing: HttpTiming,
+    /// 含重定向在内的总耗时
+    pub total_ms: u64,
+}
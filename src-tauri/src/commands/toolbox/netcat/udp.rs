@@ -2,6 +2,7 @@
 
 use super::types::*;
 use crate::commands::toolbox::generate_id;
+use crate::commands::toolbox::pcap::{self, Transport};
 use crate::error::AppResult;
 use std::collections::HashMap;
 use std::net::SocketAddr;
@@ -121,6 +122,10 @@ pub async fn start_udp_session(
     let socket_send = socket.clone();
     let session_state_send = session_state.clone();
     let shutdown_flag_send = shutdown_flag.clone();
+    let session_id_send = session_id.clone();
+    let local = socket
+        .local_addr()
+        .unwrap_or_else(|_| SocketAddr::from(([0, 0, 0, 0], 0)));
 
     tokio::spawn(async move {
         while let Some((data, addr)) = send_rx.recv().await {
@@ -146,11 +151,25 @@ pub async fn start_udp_session(
                 eprintln!("UDP 发送失败: {}", e);
                 continue;
             }
+            if let Some(dest) = addr.or(target_addr) {
+                pcap::record(&session_id_send, Transport::Udp, local, dest, &data);
+            }
 
             // 更新统计
+            let now = current_timestamp();
             let mut state = session_state_send.write().await;
             state.session.bytes_sent += data.len() as u64;
-            state.session.last_activity = Some(current_timestamp());
+            state.session.last_activity = Some(now);
+            let target_id = addr.map(|target| udp_client_id(&target.to_string()));
+            state.record_throughput(MessageDirection::Sent, target_id.as_deref(), data.len());
+
+            // 服务器模式：同步更新对端虚拟客户端的统计
+            if let Some(target_id) = target_id {
+                if let Some(client) = state.clients.get_mut(&target_id) {
+                    client.bytes_sent += data.len() as u64;
+                    client.last_activity = now;
+                }
+            }
         }
     });
 
@@ -179,6 +198,7 @@ pub async fn start_udp_session(
             match recv_result {
                 Ok(Ok((n, addr))) => {
                     let data = buffer[..n].to_vec();
+                    pcap::record(&session_id_clone, Transport::Udp, addr, local, &data);
                     handle_received_data(
                         &app_clone,
                         &session_state_clone,
@@ -274,6 +294,11 @@ pub async fn shutdown_udp_session(session_id: &str) {
     UDP_SHUTDOWN_FLAGS.write().await.remove(session_id);
 }
 
+/// UDP 无连接，服务器模式下按对端地址生成稳定的虚拟客户端 id
+pub fn udp_client_id(addr: &str) -> String {
+    format!("udp-{}", addr.replace([':', '.'], "-"))
+}
+
 /// 处理接收到的数据
 async fn handle_received_data(
     app: &AppHandle,
@@ -285,15 +310,14 @@ async fn handle_received_data(
     let now = current_timestamp();
     let message_id = generate_id();
 
-    let (session_id, message) = {
+    let (session_id, message, saved) = {
         let mut state = session_state.write().await;
         state.session.bytes_received += data.len() as u64;
-        state.session.message_count += 1;
         state.session.last_activity = Some(now);
 
         // 服务器模式下跟踪客户端
         let client_id = if mode == SessionMode::Server {
-            let client_id = format!("udp-{}", from_addr.replace([':', '.'], "-"));
+            let client_id = udp_client_id(&from_addr);
             if !state.clients.contains_key(&client_id) {
                 let client = ConnectedClient {
                     id: client_id.clone(),
@@ -302,6 +326,8 @@ async fn handle_received_data(
                     last_activity: now,
                     bytes_sent: 0,
                     bytes_received: data.len() as u64,
+                    send_rate: 0.0,
+                    recv_rate: 0.0,
                 };
                 state.clients.insert(client_id.clone(), client.clone());
                 state.session.client_count = state.clients.len() as u32;
@@ -322,6 +348,7 @@ async fn handle_received_data(
         } else {
             None
         };
+        state.record_throughput(MessageDirection::Received, client_id.as_deref(), data.len());
 
         let message = NetcatMessage {
             id: message_id,
@@ -335,16 +362,25 @@ async fn handle_received_data(
             client_addr: Some(from_addr),
         };
 
-        state.messages.push(message.clone());
+        // 按会话的上限与溢出策略保存
+        let saved = state.record_message(app, message.clone());
 
-        // 限制消息历史
-        if state.messages.len() > 1000 {
-            state.messages.remove(0);
-        }
-
-        (state.session.id.clone(), message)
+        (state.session.id.clone(), message, saved)
     };
 
+    super::traffic_log::log_traffic(
+        &session_id,
+        "收到",
+        message.size,
+        message.client_addr.as_deref(),
+        &message.data,
+    );
+
+    // 已停止记录时不再推送
+    if !saved {
+        return;
+    }
+
     // 发送事件
     let _ = app.emit(
         "netcat-event",
@@ -0,0 +1,324 @@
+// 流量抓包导出：把 Netcat 会话 / 端口转发经过的原始字节写成 pcapng 文件，可直接用 Wireshark 打开分析。
+//
+// 数据是在应用层读写处截获的，并非真实网卡报文：每次读写合成一个 IP + TCP/UDP 报文
+// （链路类型 LINKTYPE_RAW），TCP 按方向累加序列号，Wireshark 的「追踪流」可以正常重组。
+// - 校验和只填 IPv4 头部，TCP/UDP 校验和留 0（Wireshark 默认不校验）
+// - 端口转发只记录客户端与本地监听端口之间的一侧
+// - 单个文件超过 MAX_CAPTURE_BYTES 后停止写入并标记 truncated
+
+use crate::error::{AppError, AppResult};
+use crate::storage::get_storage_config;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 单个抓包文件的大小上限
+const MAX_CAPTURE_BYTES: u64 = 1024 * 1024 * 1024;
+/// 单个合成报文的最大负载（IPv4 总长度字段为 16 位）
+const MAX_SEGMENT: usize = 65000;
+const LINKTYPE_RAW: u16 = 101;
+
+/// 传输层协议
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transport {
+    Tcp,
+    Udp,
+}
+
+/// 抓包状态
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TrafficCapture {
+    /// 会话 ID / 转发规则 ID
+    pub resource_id: String,
+    /// "netcat" | "forward"
+    pub kind: String,
+    pub path: String,
+    pub packets: u64,
+    pub bytes: u64,
+    pub truncated: bool,
+    pub started_at: String,
+}
+
+struct Capture {
+    info: TrafficCapture,
+    writer: BufWriter<File>,
+    written: u64,
+    /// (源, 目的) -> 下一个序列号
+    seq: HashMap<(SocketAddr, SocketAddr), u32>,
+}
+
+static CAPTURES: Lazy<Mutex<HashMap<String, Capture>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+/// 正在进行的抓包数量，为 0 时 record 直接返回，不加锁
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+/// 记录一段数据；该资源没有在抓包时什么也不做
+pub fn record(
+    resource_id: &str,
+    transport: Transport,
+    src: SocketAddr,
+    dst: SocketAddr,
+    data: &[u8],
+) {
+    if ACTIVE.load(Ordering::Relaxed) == 0 || data.is_empty() {
+        return;
+    }
+    let Ok(mut captures) = CAPTURES.lock() else {
+        return;
+    };
+    let Some(capture) = captures.get_mut(resource_id) else {
+        return;
+    };
+    if capture.info.truncated {
+        return;
+    }
+    for chunk in data.chunks(MAX_SEGMENT) {
+        if let Err(e) = capture.write_segment(transport, src, dst, chunk) {
+            log::warn!("写入抓包文件失败: {}", e);
+            capture.info.truncated = true;
+            return;
+        }
+    }
+}
+
+/// 资源被删除时结束抓包
+pub fn finish(resource_id: &str) {
+    let _ = stop(resource_id);
+}
+
+fn stop(resource_id: &str) -> AppResult<TrafficCapture> {
+    let mut captures = CAPTURES
+        .lock()
+        .map_err(|_| AppError::internal("抓包状态锁失败"))?;
+    let mut capture = captures
+        .remove(resource_id)
+        .ok_or_else(|| AppError::invalid("该会话 / 规则没有在抓包"))?;
+    ACTIVE.store(captures.len(), Ordering::Relaxed);
+    capture.writer.flush()?;
+    log::info!(
+        "抓包结束: {} ({} 个报文, {} 字节)",
+        capture.info.path,
+        capture.info.packets,
+        capture.info.bytes
+    );
+    Ok(capture.info)
+}
+
+impl Capture {
+    fn create(resource_id: String, kind: String, path: PathBuf) -> AppResult<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut writer = BufWriter::new(File::create(&path)?);
+
+        // Section Header Block
+        let mut shb = Vec::new();
+        shb.extend_from_slice(&0x1A2B3C4Du32.to_le_bytes());
+        shb.extend_from_slice(&1u16.to_le_bytes());
+        shb.extend_from_slice(&0u16.to_le_bytes());
+        shb.extend_from_slice(&(-1i64).to_le_bytes());
+        write_block(&mut writer, 0x0A0D0D0A, &shb)?;
+
+        // Interface Description Block（时间戳默认微秒精度）
+        let mut idb = Vec::new();
+        idb.extend_from_slice(&LINKTYPE_RAW.to_le_bytes());
+        idb.extend_from_slice(&0u16.to_le_bytes());
+        idb.extend_from_slice(&0u32.to_le_bytes());
+        write_block(&mut writer, 1, &idb)?;
+
+        Ok(Self {
+            info: TrafficCapture {
+                resource_id,
+                kind,
+                path: path.to_string_lossy().to_string(),
+                packets: 0,
+                bytes: 0,
+                truncated: false,
+                started_at: super::current_time(),
+            },
+            writer,
+            written: 0,
+            seq: HashMap::new(),
+        })
+    }
+
+    fn write_segment(
+        &mut self,
+        transport: Transport,
+        src: SocketAddr,
+        dst: SocketAddr,
+        payload: &[u8],
+    ) -> std::io::Result<()> {
+        let seq = *self.seq.entry((src, dst)).or_insert(1);
+        let ack = *self.seq.get(&(dst, src)).unwrap_or(&1);
+        self.seq
+            .insert((src, dst), seq.wrapping_add(payload.len() as u32));
+
+        let packet = build_packet(transport, src, dst, seq, ack, payload);
+        if self.written + packet.len() as u64 > MAX_CAPTURE_BYTES {
+            self.info.truncated = true;
+            log::warn!("抓包文件超过大小上限，停止写入: {}", self.info.path);
+            return Ok(());
+        }
+
+        let micros = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_micros() as u64;
+        let mut epb = Vec::with_capacity(20 + packet.len() + 3);
+        epb.extend_from_slice(&0u32.to_le_bytes());
+        epb.extend_from_slice(&((micros >> 32) as u32).to_le_bytes());
+        epb.extend_from_slice(&(micros as u32).to_le_bytes());
+        epb.extend_from_slice(&(packet.len() as u32).to_le_bytes());
+        epb.extend_from_slice(&(packet.len() as u32).to_le_bytes());
+        epb.extend_from_slice(&packet);
+        epb.resize(epb.len().div_ceil(4) * 4, 0);
+        write_block(&mut self.writer, 6, &epb)?;
+
+        self.written += packet.len() as u64;
+        self.info.packets += 1;
+        self.info.bytes += payload.len() as u64;
+        Ok(())
+    }
+}
+
+/// 写一个 pcapng 块：类型 + 总长度 + 内容 + 总长度
+fn write_block(writer: &mut impl Write, block_type: u32, body: &[u8]) -> std::io::Result<()> {
+    let total = (12 + body.len()) as u32;
+    writer.write_all(&block_type.to_le_bytes())?;
+    writer.write_all(&total.to_le_bytes())?;
+    writer.write_all(body)?;
+    writer.write_all(&total.to_le_bytes())
+}
+
+/// 合成 IP + TCP/UDP 报文；两端地址族不同时统一按 IPv6（v4 映射地址）处理
+fn build_packet(
+    transport: Transport,
+    src: SocketAddr,
+    dst: SocketAddr,
+    seq: u32,
+    ack: u32,
+    payload: &[u8],
+) -> Vec<u8> {
+    let mut l4 = Vec::with_capacity(20 + payload.len());
+    l4.extend_from_slice(&src.port().to_be_bytes());
+    l4.extend_from_slice(&dst.port().to_be_bytes());
+    let protocol = match transport {
+        Transport::Tcp => {
+            l4.extend_from_slice(&seq.to_be_bytes());
+            l4.extend_from_slice(&ack.to_be_bytes());
+            l4.push(5 << 4); // 数据偏移：5 个 32 位字
+            l4.push(0x18); // PSH | ACK
+            l4.extend_from_slice(&u16::MAX.to_be_bytes()); // 窗口
+            l4.extend_from_slice(&[0, 0, 0, 0]); // 校验和 + 紧急指针
+            6u8
+        }
+        Transport::Udp => {
+            l4.extend_from_slice(&((8 + payload.len()) as u16).to_be_bytes());
+            l4.extend_from_slice(&[0, 0]);
+            17u8
+        }
+    };
+    l4.extend_from_slice(payload);
+
+    let mut packet = Vec::with_capacity(40 + l4.len());
+    match (src.ip(), dst.ip()) {
+        (IpAddr::V4(s), IpAddr::V4(d)) => {
+            packet.push(0x45);
+            packet.push(0);
+            packet.extend_from_slice(&((20 + l4.len()) as u16).to_be_bytes());
+            packet.extend_from_slice(&[0, 0, 0x40, 0]); // id + DF
+            packet.push(64);
+            packet.push(protocol);
+            packet.extend_from_slice(&[0, 0]);
+            packet.extend_from_slice(&s.octets());
+            packet.extend_from_slice(&d.octets());
+            let csum = ipv4_checksum(&packet);
+            packet[10..12].copy_from_slice(&csum.to_be_bytes());
+        }
+        (s, d) => {
+            let to_v6 = |ip: IpAddr| match ip {
+                IpAddr::V4(v4) => v4.to_ipv6_mapped(),
+                IpAddr::V6(v6) => v6,
+            };
+            packet.extend_from_slice(&[0x60, 0, 0, 0]);
+            packet.extend_from_slice(&(l4.len() as u16).to_be_bytes());
+            packet.push(protocol);
+            packet.push(64);
+            packet.extend_from_slice(&to_v6(s).octets());
+            packet.extend_from_slice(&to_v6(d).octets());
+        }
+    }
+    packet.extend_from_slice(&l4);
+    packet
+}
+
+fn ipv4_checksum(header: &[u8]) -> u16 {
+    let mut sum: u32 = header
+        .chunks(2)
+        .map(|c| u16::from_be_bytes([c[0], c[1]]) as u32)
+        .sum();
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+/// 开始抓包；path 为空时保存到数据目录下的 captures 目录
+#[tauri::command]
+#[specta::specta]
+pub async fn start_traffic_capture(
+    kind: String,
+    resource_id: String,
+    path: Option<String>,
+) -> AppResult<TrafficCapture> {
+    if kind != "netcat" && kind != "forward" {
+        return Err(AppError::invalid(format!("不支持的抓包对象: {}", kind)));
+    }
+    let path = match path.filter(|p| !p.trim().is_empty()) {
+        Some(p) => PathBuf::from(p),
+        None => get_storage_config()?.captures_dir().join(format!(
+            "{}-{}-{}.pcapng",
+            kind,
+            resource_id,
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        )),
+    };
+
+    let mut captures = CAPTURES
+        .lock()
+        .map_err(|_| AppError::internal("抓包状态锁失败"))?;
+    if captures.contains_key(&resource_id) {
+        return Err(AppError::invalid("该会话 / 规则已在抓包"));
+    }
+    let capture = Capture::create(resource_id.clone(), kind, path)?;
+    let info = capture.info.clone();
+    captures.insert(resource_id, capture);
+    ACTIVE.store(captures.len(), Ordering::Relaxed);
+    log::info!("开始抓包: {}", info.path);
+    Ok(info)
+}
+
+/// 停止抓包并写盘，返回最终统计
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_traffic_capture(resource_id: String) -> AppResult<TrafficCapture> {
+    stop(&resource_id)
+}
+
+/// 获取正在进行的抓包
+#[tauri::command]
+#[specta::specta]
+pub async fn get_traffic_captures() -> AppResult<Vec<TrafficCapture>> {
+    let captures = CAPTURES
+        .lock()
+        .map_err(|_| AppError::internal("抓包状态锁失败"))?;
+    Ok(captures.values().map(|c| c.info.clone()).collect())
+}
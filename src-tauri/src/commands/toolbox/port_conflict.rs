@@ -0,0 +1,161 @@
+// 端口冲突处理 - 静态服务/端口转发启动时先同步绑定端口，
+// 端口被占用（AddrInUse）时查出占用进程，推送 `port-conflict` 并返回可读的错误；
+// 前端可调用 kill_and_retry 结束占用进程后重新启动（与 kill_process 一样需二次确认）。
+
+use super::process::{get_port_processes, kill_plan, terminate_process};
+use super::PortConflict;
+use crate::commands::confirm::{self, ConfirmRequest};
+use crate::error::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use socket2::{Domain, Socket, Type};
+use std::io::ErrorKind;
+use std::net::Ipv4Addr;
+use tauri::{AppHandle, Emitter};
+use tokio::time::{sleep, Duration};
+
+/// 结束占用进程后等待端口释放的最长时间
+const RELEASE_WAIT: Duration = Duration::from_secs(3);
+
+/// 创建 ip:port 的监听 socket（SO_LINGER=0 + 非阻塞；非 Windows 加 SO_REUSEADDR）
+fn bind_listener(ip: Ipv4Addr, port: u16, backlog: i32) -> std::io::Result<std::net::TcpListener> {
+    let addr = std::net::SocketAddr::from((ip, port));
+    let socket = Socket::new(Domain::IPV4, Type::STREAM, None)?;
+    // 允许在 TIME_WAIT 状态时复用端口。Windows 上 SO_REUSEADDR 允许抢占正在监听的端口，
+    // 绑定不会报 AddrInUse，冲突检测就失效了，所以不设置
+    #[cfg(not(windows))]
+    socket.set_reuse_address(true)?;
+    // 关闭时立即释放端口（发送 RST 而非 FIN）
+    socket.set_linger(Some(Duration::from_secs(0)))?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(backlog)?;
+    Ok(socket.into())
+}
+
+/// 绑定 0.0.0.0:port；被占用时返回包含占用进程的错误，并推送 `port-conflict`
+pub(crate) async fn bind_or_conflict(
+    app: &AppHandle,
+    port: u16,
+    backlog: i32,
+    target_kind: &str,
+    target_id: &str,
+) -> AppResult<std::net::TcpListener> {
+    bind_or_conflict_on(
+        app,
+        Ipv4Addr::UNSPECIFIED,
+        port,
+        backlog,
+        target_kind,
+        target_id,
+    )
+    .await
+}
+
+/// 同 bind_or_conflict，但绑定到指定地址（如只监听 127.0.0.1）
+pub(crate) async fn bind_or_conflict_on(
+    app: &AppHandle,
+    ip: Ipv4Addr,
+    port: u16,
+    backlog: i32,
+    target_kind: &str,
+    target_id: &str,
+) -> AppResult<std::net::TcpListener> {
+    match bind_listener(ip, port, backlog) {
+        Ok(listener) => Ok(listener),
+        Err(e) if e.kind() == ErrorKind::AddrInUse => {
+            let processes = get_port_processes(port).await.unwrap_or_default();
+            let occupants = processes
+                .iter()
+                .map(|p| format!("{} (PID {})", p.name, p.pid))
+                .collect::<Vec<_>>()
+                .join("、");
+
+            let _ = app.emit(
+                "port-conflict",
+                PortConflict {
+                    port,
+                    target_kind: target_kind.to_string(),
+                    target_id: target_id.to_string(),
+                    processes,
+                },
+            );
+
+            Err(if occupants.is_empty() {
+                AppError::invalid(format!("端口 {} 已被占用", port))
+            } else {
+                AppError::invalid(format!("端口 {} 已被 {} 占用", port, occupants))
+            })
+        }
+        Err(e) if e.kind() == ErrorKind::PermissionDenied && port < 1024 => {
+            Err(AppError::invalid(format!(
+                "绑定端口 {} 需要管理员权限，可改用高位端口并启动提权端口中继",
+                port
+            )))
+        }
+        Err(e) => Err(AppError::invalid(format!("绑定端口失败: {}", e))),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct KillAndRetryResult {
+    /// 不为空时尚未执行，用户确认后带上其中的 token 再调用一次
+    pub confirm: Option<ConfirmRequest>,
+    /// 重新启动后的服务 URL（转发为 None）
+    pub url: Option<String>,
+}
+
+/// 结束占用端口的进程并重新启动服务/转发。
+/// 只允许结束当前确实占用该端口的进程；不带 confirm_token 时只返回确认请求。
+#[tauri::command]
+#[specta::specta]
+pub async fn kill_and_retry(
+    app: AppHandle,
+    target_kind: String,
+    target_id: String,
+    port: u16,
+    pid: u32,
+    force: Option<bool>,
+    confirm_token: Option<String>,
+) -> AppResult<KillAndRetryResult> {
+    let occupying = get_port_processes(port).await?;
+    if !occupying.iter().any(|p| p.pid == pid) {
+        return Err(AppError::invalid(format!(
+            "进程 {} 未占用端口 {}",
+            pid, port
+        )));
+    }
+
+    let force = force.unwrap_or(false);
+    let plan = kill_plan(pid, force).await?;
+    if let Some(request) = confirm::require("kill_and_retry", confirm_token, plan)? {
+        return Ok(KillAndRetryResult {
+            confirm: Some(request),
+            url: None,
+        });
+    }
+    terminate_process(pid, force).await?;
+
+    // 等端口释放
+    let deadline = tokio::time::Instant::now() + RELEASE_WAIT;
+    loop {
+        let still = get_port_processes(port)
+            .await
+            .map(|list| list.iter().any(|p| p.pid == pid))
+            .unwrap_or(false);
+        if !still || tokio::time::Instant::now() >= deadline {
+            break;
+        }
+        sleep(Duration::from_millis(200)).await;
+    }
+
+    let url = match target_kind.as_str() {
+        "server" => Some(super::server::start_server(app, target_id).await?),
+        "forwarder" => {
+            super::forwarder::start_forwarding(app, target_id).await?;
+            None
+        }
+        other => return Err(AppError::invalid(format!("未知的目标类型: {}", other))),
+    };
+    Ok(KillAndRetryResult { confirm: None, url })
+}
@@ -0,0 +1,211 @@
+// 端口监控模块 - 定时探测 host:port，记录状态历史，up/down 切换时发通知（调度与状态记录见 monitor.rs）
+
+use super::monitor::{self, Monitor, UNKNOWN};
+use super::{current_time, generate_id, PortWatch, PortWatchInput, PortWatchRecord};
+use crate::error::AppResult;
+use crate::storage::config::StorageConfig;
+use crate::storage::PersistedStore;
+use once_cell::sync::Lazy;
+use std::time::Instant;
+use tauri::AppHandle;
+use tokio::net::TcpStream;
+use tokio::time::{timeout, Duration};
+
+/// 监控项存储 - 延迟初始化
+static PORT_WATCHES: Lazy<PersistedStore<PortWatch>> = Lazy::new(|| {
+    PersistedStore::new(
+        "portWatches",
+        "端口监控",
+        StorageConfig::port_watches_file,
+        |w: &PortWatch| w.id.clone(),
+    )
+    .on_load(|w: &mut PortWatch| {
+        // 重启后状态未知，等待首次探测
+        w.status = UNKNOWN.to_string();
+        w.latency_ms = None;
+    })
+});
+
+impl Monitor for PortWatch {
+    type Record = PortWatchRecord;
+
+    const EVENT: &'static str = "port-watch-updated";
+    const MAX_HISTORY: usize = 100;
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn interval_secs(&self) -> u64 {
+        self.interval_secs
+    }
+
+    fn status(&self) -> &str {
+        &self.status
+    }
+
+    /// TCP 探测
+    async fn probe(&self) -> PortWatchRecord {
+        let start = Instant::now();
+        let connected = timeout(
+            Duration::from_millis(self.timeout_ms),
+            TcpStream::connect((self.host.as_str(), self.port)),
+        )
+        .await;
+        let (status, latency_ms) = match connected {
+            Ok(Ok(_)) => ("up", Some(start.elapsed().as_millis() as u64)),
+            _ => ("down", None),
+        };
+        PortWatchRecord {
+            time: current_time(),
+            status: status.to_string(),
+            latency_ms,
+        }
+    }
+
+    fn record_summary(record: &PortWatchRecord) -> (&str, &str) {
+        (&record.time, &record.status)
+    }
+
+    fn apply(&mut self, record: &PortWatchRecord) {
+        self.status = record.status.clone();
+        self.latency_ms = record.latency_ms;
+        self.last_checked = Some(record.time.clone());
+    }
+
+    fn set_last_change(&mut self, time: String) {
+        self.last_change = Some(time);
+    }
+
+    fn history_mut(&mut self) -> &mut Vec<PortWatchRecord> {
+        &mut self.history
+    }
+
+    fn transition_notice(&self) -> (&'static str, String, String) {
+        let target = format!("{}:{}", self.host, self.port);
+        if self.status == "up" {
+            ("success", format!("{} 已恢复", self.name), target)
+        } else {
+            ("error", format!("{} 不可达", self.name), target)
+        }
+    }
+}
+
+/// 启动端口监控调度：按各自 interval 轮询启用的监控项
+pub fn spawn_port_watcher(app: AppHandle) {
+    monitor::spawn_scheduler(app, &PORT_WATCHES);
+}
+
+fn validate_input(input: &PortWatchInput) -> AppResult<()> {
+    if input.host.trim().is_empty() {
+        return Err(crate::error::AppError::from("主机不能为空".to_string()));
+    }
+    if input.port == 0 {
+        return Err(crate::error::AppError::from("端口不能为 0".to_string()));
+    }
+    Ok(())
+}
+
+/// 添加端口监控
+#[tauri::command]
+#[specta::specta]
+pub async fn add_port_watch(input: PortWatchInput) -> AppResult<PortWatch> {
+    PORT_WATCHES.ensure_loaded().await;
+    validate_input(&input)?;
+
+    let watch = PortWatch {
+        id: generate_id(),
+        name: if input.name.trim().is_empty() {
+            format!("{}:{}", input.host, input.port)
+        } else {
+            input.name
+        },
+        host: input.host.trim().to_string(),
+        port: input.port,
+        interval_secs: input.interval_secs.unwrap_or(30).max(1),
+        timeout_ms: input.timeout_ms.unwrap_or(2000),
+        enabled: input.enabled.unwrap_or(true),
+        status: UNKNOWN.to_string(),
+        latency_ms: None,
+        last_checked: None,
+        last_change: None,
+        history: Vec::new(),
+        created_at: current_time(),
+    };
+
+    PORT_WATCHES
+        .lock()
+        .await
+        .insert(watch.id.clone(), watch.clone());
+    PORT_WATCHES.save().await?;
+    Ok(watch)
+}
+
+/// 更新端口监控配置（保留历史）
+#[tauri::command]
+#[specta::specta]
+pub async fn update_port_watch(id: String, input: PortWatchInput) -> AppResult<PortWatch> {
+    PORT_WATCHES.ensure_loaded().await;
+    validate_input(&input)?;
+
+    let updated = {
+        let mut watches = PORT_WATCHES.lock().await;
+        let w = watches.get_mut(&id).ok_or("监控项不存在")?;
+        let target_changed = w.host != input.host.trim() || w.port != input.port;
+        if !input.name.trim().is_empty() {
+            w.name = input.name;
+        }
+        w.host = input.host.trim().to_string();
+        w.port = input.port;
+        if let Some(interval) = input.interval_secs {
+            w.interval_secs = interval.max(1);
+        }
+        if let Some(t) = input.timeout_ms {
+            w.timeout_ms = t;
+        }
+        if let Some(enabled) = input.enabled {
+            w.enabled = enabled;
+        }
+        // 目标变了，旧历史没有意义
+        if target_changed {
+            w.status = UNKNOWN.to_string();
+            w.latency_ms = None;
+            w.history.clear();
+        }
+        w.clone()
+    };
+
+    PORT_WATCHES.save().await?;
+    Ok(updated)
+}
+
+/// 删除端口监控
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_port_watch(id: String) -> AppResult<()> {
+    PORT_WATCHES.ensure_loaded().await;
+    PORT_WATCHES.lock().await.remove(&id);
+    PORT_WATCHES.save().await
+}
+
+/// 获取所有端口监控
+#[tauri::command]
+#[specta::specta]
+pub async fn get_port_watches() -> AppResult<Vec<PortWatch>> {
+    PORT_WATCHES.ensure_loaded().await;
+    let mut list: Vec<PortWatch> = PORT_WATCHES.lock().await.values().cloned().collect();
+    list.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    Ok(list)
+}
+
+/// 立即探测一次
+#[tauri::command]
+#[specta::specta]
+pub async fn check_port_watch_now(app: AppHandle, id: String) -> AppResult<PortWatch> {
+    PORT_WATCHES.ensure_loaded().await;
+    monitor::run_check(&app, &PORT_WATCHES, &id).await
+}
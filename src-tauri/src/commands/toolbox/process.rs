@@ -2,7 +2,10 @@
 
 use super::{ProcessFilter, ProcessInfo};
 use crate::error::AppResult;
-use std::collections::HashMap;
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use sysinfo::{Pid, ProcessStatus, System};
 
 #[cfg(target_os = "windows")]
@@ -39,6 +42,7 @@ pub async fn get_processes(filter: Option<ProcessFilter>) -> AppResult<Vec<Proce
                     }
                 }
             }
+            tag_project_tasks(&system, &mut processes).await;
             return Ok(processes);
         }
     }
@@ -69,6 +73,8 @@ pub async fn get_processes(filter: Option<ProcessFilter>) -> AppResult<Vec<Proce
         processes.push(info);
     }
 
+    tag_project_tasks(&system, &mut processes).await;
+
     // 按 PID 排序
     processes.sort_by_key(|p| p.pid);
 
@@ -100,6 +106,25 @@ fn build_process_info(
                 .collect::<Vec<_>>()
                 .join(" "),
         ),
+        project_task: None,
+    }
+}
+
+/// 标注由项目任务启动的进程（任务 shell 本身及其直接子进程）
+async fn tag_project_tasks(system: &System, processes: &mut [ProcessInfo]) {
+    let task_pids = crate::commands::project_tasks::running_task_pids().await;
+    if task_pids.is_empty() {
+        return;
+    }
+    for p in processes.iter_mut() {
+        let parent = system
+            .process(Pid::from_u32(p.pid))
+            .and_then(|proc| proc.parent())
+            .map(|pid| pid.as_u32());
+        p.project_task = task_pids
+            .get(&p.pid)
+            .or_else(|| parent.and_then(|pid| task_pids.get(&pid)))
+            .cloned();
     }
 }
 
@@ -278,19 +303,90 @@ pub async fn get_port_processes(port: u16) -> AppResult<Vec<ProcessInfo>> {
     .await
 }
 
-/// 终止进程
+/// 终止进程的确认计划：指纹包含启动时间，确认期间 PID 被复用会被识别
+pub(crate) async fn kill_plan(
+    pid: u32,
+    force: bool,
+) -> AppResult<crate::commands::confirm::ConfirmPlan> {
+    // 枚举全部进程较慢，放到阻塞线程
+    tokio::task::spawn_blocking(move || kill_plan_blocking(pid, force))
+        .await
+        .map_err(|e| crate::error::AppError::internal(format!("读取进程信息失败: {}", e)))?
+}
+
+fn kill_plan_blocking(pid: u32, force: bool) -> AppResult<crate::commands::confirm::ConfirmPlan> {
+    let mut system = System::new_all();
+    system.refresh_all();
+    let proc = system
+        .process(Pid::from_u32(pid))
+        .ok_or_else(|| crate::error::AppError::invalid(format!("进程 {} 不存在", pid)))?;
+
+    let name = proc.name().to_string();
+    let mut details = vec![format!("{} (PID {})", name, pid)];
+    if let Some(exe) = proc.exe() {
+        details.push(format!("程序：{}", exe.display()));
+    }
+    let cmd = proc.cmd().join(" ");
+    if !cmd.is_empty() {
+        details.push(format!("命令行：{}", cmd));
+    }
+    let children: Vec<String> = system
+        .processes()
+        .values()
+        .filter(|p| p.parent() == Some(Pid::from_u32(pid)))
+        .map(|p| format!("{} (PID {})", p.name(), p.pid().as_u32()))
+        .collect();
+    if !children.is_empty() {
+        details.push(format!("子进程将失去父进程：{}", children.join("、")));
+    }
+
+    Ok(crate::commands::confirm::ConfirmPlan {
+        fingerprint: format!("{}|{}|{}", pid, name, proc.start_time()),
+        summary: if force {
+            format!("将强制结束进程 {}，未保存的数据会丢失", name)
+        } else {
+            format!("将结束进程 {}", name)
+        },
+        details,
+    })
+}
+
+/// 终止进程。force 时需二次确认：不带 confirm_token 时只返回确认请求，
+/// 带有效令牌时执行并返回 None；普通终止直接执行
 #[tauri::command]
 #[specta::specta]
-pub async fn kill_process(pid: u32, force: Option<bool>) -> AppResult<()> {
+pub async fn kill_process(
+    pid: u32,
+    force: Option<bool>,
+    confirm_token: Option<String>,
+) -> AppResult<Option<crate::commands::confirm::ConfirmRequest>> {
+    ensure_not_self(pid)?;
+    let force = force.unwrap_or(false);
+    if force {
+        let plan = kill_plan(pid, true).await?;
+        if let Some(request) =
+            crate::commands::confirm::require("kill_process", confirm_token, plan)?
+        {
+            return Ok(Some(request));
+        }
+    }
+    terminate_process(pid, force).await?;
+    Ok(None)
+}
+
+fn ensure_not_self(pid: u32) -> AppResult<()> {
     // 获取当前进程 PID，防止用户意外结束 CodeShelf 自身
-    let current_pid = std::process::id();
-    if pid == current_pid {
+    if pid == std::process::id() {
         return Err(crate::error::AppError::from(
             "无法终止 CodeShelf 进程。如需停止内部服务，请使用本地服务页面的停止按钮。".to_string(),
         ));
     }
+    Ok(())
+}
 
-    let force = force.unwrap_or(false);
+/// 实际执行终止（内部调用方已自行校验目标时使用）
+pub(crate) async fn terminate_process(pid: u32, force: bool) -> AppResult<()> {
+    ensure_not_self(pid)?;
 
     #[cfg(target_os = "windows")]
     {
@@ -621,3 +717,94 @@ async fn get_port_occupation_macos() -> AppResult<Vec<PortOccupation>> {
     results.dedup_by(|a, b| a.port == b.port && a.protocol == b.protocol);
     Ok(results)
 }
+
+// ============== 端口占用实时监控 ==============
+
+/// 端口占用变化（相邻两次快照的差异）
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PortOccupationDiff {
+    pub added: Vec<PortOccupation>,
+    pub removed: Vec<PortOccupation>,
+}
+
+/// 当前监控任务的停止标志
+static PORT_MONITOR_STOP: Lazy<std::sync::Mutex<Option<Arc<AtomicBool>>>> =
+    Lazy::new(|| std::sync::Mutex::new(None));
+
+fn occupation_key(o: &PortOccupation) -> (u16, String, u32, String) {
+    (o.port, o.protocol.clone(), o.pid, o.local_addr.clone())
+}
+
+fn diff_occupation(old: &[PortOccupation], new: &[PortOccupation]) -> PortOccupationDiff {
+    let old_keys: HashSet<_> = old.iter().map(occupation_key).collect();
+    let new_keys: HashSet<_> = new.iter().map(occupation_key).collect();
+    PortOccupationDiff {
+        added: new
+            .iter()
+            .filter(|o| !old_keys.contains(&occupation_key(o)))
+            .cloned()
+            .collect(),
+        removed: old
+            .iter()
+            .filter(|o| !new_keys.contains(&occupation_key(o)))
+            .cloned()
+            .collect(),
+    }
+}
+
+/// 启动端口占用监控：按间隔取快照，有变化时推送 `port-occupation-changed`。
+/// 返回初始快照；重复调用会替换掉旧的监控任务（用于调整间隔）。
+#[tauri::command]
+#[specta::specta]
+pub async fn start_port_monitor(
+    app: tauri::AppHandle,
+    interval_secs: Option<u64>,
+) -> AppResult<Vec<PortOccupation>> {
+    use tauri::Emitter;
+
+    let interval = std::time::Duration::from_secs(interval_secs.unwrap_or(3).max(1));
+    let stop = Arc::new(AtomicBool::new(false));
+    if let Ok(mut current) = PORT_MONITOR_STOP.lock() {
+        if let Some(old) = current.replace(stop.clone()) {
+            old.store(true, Ordering::SeqCst);
+        }
+    }
+
+    let initial = get_local_port_occupation().await?;
+    let mut last = initial.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(interval).await;
+            if stop.load(Ordering::SeqCst) {
+                break;
+            }
+            let snapshot = match get_local_port_occupation().await {
+                Ok(s) => s,
+                Err(e) => {
+                    log::warn!("端口占用快照失败: {}", e);
+                    continue;
+                }
+            };
+            let diff = diff_occupation(&last, &snapshot);
+            if !diff.added.is_empty() || !diff.removed.is_empty() {
+                let _ = app.emit("port-occupation-changed", diff);
+            }
+            last = snapshot;
+        }
+    });
+
+    Ok(initial)
+}
+
+/// 停止端口占用监控
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_port_monitor() -> AppResult<()> {
+    if let Ok(mut current) = PORT_MONITOR_STOP.lock() {
+        if let Some(stop) = current.take() {
+            stop.store(true, Ordering::SeqCst);
+        }
+    }
+    Ok(())
+}
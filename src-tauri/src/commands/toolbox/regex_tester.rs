@@ -0,0 +1,228 @@
+// 正则测试 - 用 Rust regex 引擎对样例文本求值，返回匹配位置、捕获组与耗时
+//
+// regex 引擎保证线性时间，不存在回溯爆炸；剩下的风险是超大的编译产物和超长文本，
+// 这里限制编译大小，并在逐个匹配之间检查耗时，超时后返回已找到的部分结果。
+// 位置统一为 UTF-16 下标，前端可直接用于 String.slice 与高亮。
+
+use crate::error::{AppError, AppResult};
+use regex::RegexBuilder;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+const DEFAULT_MAX_MATCHES: u32 = 1000;
+
+const MAX_MATCHES_LIMIT: u32 = 10_000;
+
+const DEFAULT_TIMEOUT_MS: u64 = 2000;
+
+const MAX_TEXT_BYTES: usize = 10 * 1024 * 1024;
+
+/// 编译后程序与 DFA 缓存的上限
+const COMPILE_SIZE_LIMIT: usize = 10 * 1024 * 1024;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RegexFlags {
+    /// i
+    pub case_insensitive: bool,
+    /// m：^ $ 匹配行首行尾
+    pub multi_line: bool,
+    /// s：. 匹配换行
+    pub dot_matches_new_line: bool,
+    /// x：忽略空白并允许 # 注释
+    pub ignore_whitespace: bool,
+    /// U：默认非贪婪
+    pub swap_greed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RegexGroup {
+    /// 组序号，从 1 开始
+    pub index: u32,
+    pub name: Option<String>,
+    pub start: u32,
+    pub end: u32,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RegexMatch {
+    pub start: u32,
+    pub end: u32,
+    pub text: String,
+    /// 捕获组（不含组 0）；未参与匹配的组为 None
+    pub groups: Vec<Option<RegexGroup>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RegexTestResult {
+    pub matches: Vec<RegexMatch>,
+    /// 捕获组名称（不含组 0），无名组为 None
+    pub group_names: Vec<Option<String>>,
+    /// 达到 max_matches 后停止
+    pub truncated: bool,
+    /// 超时后停止，matches 为超时前的结果
+    pub timed_out: bool,
+    /// 传入 replacement 且未中断时的替换结果
+    pub replaced: Option<String>,
+    pub compile_ms: f64,
+    pub match_ms: f64,
+}
+
+/// 逐步把字节下标换算为 UTF-16 下标；调用方保证 byte 单调不减
+struct Utf16Cursor<'a> {
+    text: &'a str,
+    byte: usize,
+    utf16: usize,
+}
+
+impl<'a> Utf16Cursor<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            byte: 0,
+            utf16: 0,
+        }
+    }
+
+    fn advance(&mut self, byte: usize) -> u32 {
+        self.utf16 += self.text[self.byte..byte].encode_utf16().count();
+        self.byte = byte;
+        self.utf16 as u32
+    }
+
+    /// 从当前位置换算，不移动游标
+    fn peek(&self, byte: usize) -> u32 {
+        (self.utf16 + self.text[self.byte..byte].encode_utf16().count()) as u32
+    }
+}
+
+fn run(
+    pattern: &str,
+    text: &str,
+    flags: &RegexFlags,
+    replacement: Option<&str>,
+    max_matches: u32,
+    timeout: Duration,
+) -> AppResult<RegexTestResult> {
+    let started = Instant::now();
+    let regex = RegexBuilder::new(pattern)
+        .case_insensitive(flags.case_insensitive)
+        .multi_line(flags.multi_line)
+        .dot_matches_new_line(flags.dot_matches_new_line)
+        .ignore_whitespace(flags.ignore_whitespace)
+        .swap_greed(flags.swap_greed)
+        .size_limit(COMPILE_SIZE_LIMIT)
+        .dfa_size_limit(COMPILE_SIZE_LIMIT)
+        .build()
+        .map_err(|e| AppError::invalid(format!("正则表达式无效: {}", e)))?;
+    let compile_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+    let group_names: Vec<Option<String>> = regex
+        .capture_names()
+        .skip(1)
+        .map(|n| n.map(|s| s.to_string()))
+        .collect();
+    let started = Instant::now();
+    let mut cursor = Utf16Cursor::new(text);
+    let mut matches = Vec::new();
+    let mut truncated = false;
+    let mut timed_out = false;
+    for caps in regex.captures_iter(text) {
+        if matches.len() as u32 >= max_matches {
+            truncated = true;
+            break;
+        }
+        if started.elapsed() >= timeout {
+            timed_out = true;
+            break;
+        }
+        let whole = caps.get(0).expect("group 0 always participates");
+        let start = cursor.advance(whole.start());
+        let groups = caps
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(index, group)| {
+                group.map(|g| RegexGroup {
+                    index: index as u32,
+                    name: group_names[index - 1].clone(),
+                    start: cursor.peek(g.start()),
+                    end: cursor.peek(g.end()),
+                    text: g.as_str().to_string(),
+                })
+            })
+            .collect();
+        matches.push(RegexMatch {
+            start,
+            end: cursor.peek(whole.end()),
+            text: whole.as_str().to_string(),
+            groups,
+        });
+    }
+
+    let replaced = match replacement {
+        Some(rep) if !timed_out => Some(regex.replace_all(text, rep).into_owned()),
+        _ => None,
+    };
+    Ok(RegexTestResult {
+        matches,
+        group_names,
+        truncated,
+        timed_out,
+        replaced,
+        compile_ms,
+        match_ms: started.elapsed().as_secs_f64() * 1000.0,
+    })
+}
+
+/// 用 Rust regex 语法测试正则；replacement 支持 $1 / ${name} 引用
+#[tauri::command]
+#[specta::specta]
+pub async fn test_regex(
+    pattern: String,
+    text: String,
+    flags: Option<RegexFlags>,
+    replacement: Option<String>,
+    max_matches: Option<u32>,
+    timeout_ms: Option<u64>,
+) -> AppResult<RegexTestResult> {
+    if pattern.is_empty() {
+        return Err(AppError::invalid("正则表达式不能为空"));
+    }
+    if text.len() > MAX_TEXT_BYTES {
+        return Err(AppError::invalid(format!(
+            "测试文本过大（上限 {} MB）",
+            MAX_TEXT_BYTES / 1024 / 1024
+        )));
+    }
+    let flags = flags.unwrap_or_default();
+    let max_matches = max_matches
+        .filter(|m| *m > 0)
+        .unwrap_or(DEFAULT_MAX_MATCHES)
+        .min(MAX_MATCHES_LIMIT);
+    let timeout =
+        Duration::from_millis(timeout_ms.filter(|t| *t > 0).unwrap_or(DEFAULT_TIMEOUT_MS));
+
+    // 单次匹配无法中断，外层再加一道超时，兜底返回错误而不是让前端一直等
+    let task = tokio::task::spawn_blocking(move || {
+        run(
+            &pattern,
+            &text,
+            &flags,
+            replacement.as_deref(),
+            max_matches,
+            timeout,
+        )
+    });
+    match tokio::time::timeout(timeout * 2, task).await {
+        Ok(result) => result.map_err(|e| AppError::internal(format!("正则测试失败: {}", e)))?,
+        Err(_) => Err(AppError::other(format!(
+            "正则求值超时（{} 毫秒）",
+            timeout.as_millis()
+        ))),
+    }
+}
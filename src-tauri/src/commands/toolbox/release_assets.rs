@@ -0,0 +1,459 @@
+// Release 附件下载 - 解析 GitHub / GitLab 的 Release 页面地址，通过 API 列出附件，
+// 选中的附件交给下载器，下载器请求时再按主机读取令牌、带上对应平台的鉴权头。
+//
+// - 访问令牌按主机保存（hosting_tokens.json），同时支持 GitHub Enterprise / 自建 GitLab
+// - GitHub 私有仓库的附件必须走 API 地址（Accept: application/octet-stream），
+//   之后会重定向到对象存储，reqwest 跨主机重定向时会去掉 Authorization
+// - GitLab 的附件链接可能指向外部站点，只有与 GitLab 同主机的链接才带令牌
+
+use super::downloader::enqueue_download;
+use super::DownloadConfig;
+use crate::error::{AppError, AppResult};
+use crate::storage::config::StorageConfig;
+use crate::storage::PersistedStore;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+
+const API_TIMEOUT: Duration = Duration::from_secs(20);
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HostingToken {
+    /// 主机名，如 "github.com"、"gitlab.example.com"
+    pub host: String,
+    /// "github" | "gitlab"
+    pub provider: String,
+    pub token: String,
+}
+
+/// 返回给前端的令牌信息（令牌本身打码）
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HostingTokenInfo {
+    pub host: String,
+    pub provider: String,
+    pub masked_token: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseAsset {
+    pub name: String,
+    /// GitLab 的外部链接没有大小
+    pub size: Option<u64>,
+    pub content_type: Option<String>,
+    /// 实际下载地址（私有仓库为 API 地址）
+    pub download_url: String,
+    /// 下载时是否需要带令牌
+    pub requires_auth: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseInfo {
+    pub provider: String,
+    pub host: String,
+    /// 仓库路径，如 "owner/repo"、"group/sub/project"
+    pub repository: String,
+    pub tag: String,
+    pub name: Option<String>,
+    pub published_at: Option<String>,
+    pub assets: Vec<ReleaseAsset>,
+    /// 是否使用了已保存的令牌
+    pub authenticated: bool,
+}
+
+static TOKENS: Lazy<PersistedStore<HostingToken>> = Lazy::new(|| {
+    PersistedStore::new(
+        "hostingTokens",
+        "代码托管令牌",
+        StorageConfig::hosting_tokens_file,
+        |t| t.host.clone(),
+    )
+});
+
+/// 解析后的 Release 地址
+struct ReleaseUrl {
+    provider: &'static str,
+    scheme: String,
+    host: String,
+    repository: String,
+    /// None 表示最新版本
+    tag: Option<String>,
+}
+
+impl ReleaseUrl {
+    fn api_base(&self) -> String {
+        match (self.provider, self.host.as_str()) {
+            ("github", "github.com") => "https://api.github.com".to_string(),
+            ("github", _) => format!("{}://{}/api/v3", self.scheme, self.host),
+            _ => format!("{}://{}/api/v4", self.scheme, self.host),
+        }
+    }
+}
+
+fn mask_token(token: &str) -> String {
+    let chars: Vec<char> = token.chars().collect();
+    if chars.len() <= 8 {
+        return "*".repeat(chars.len());
+    }
+    format!(
+        "{}****{}",
+        chars[..4].iter().collect::<String>(),
+        chars[chars.len() - 4..].iter().collect::<String>()
+    )
+}
+
+/// 令牌按 "主机[:端口]" 保存
+fn host_key(url: &url::Url) -> Option<String> {
+    let host = url.host_str()?.to_lowercase();
+    Some(match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host,
+    })
+}
+
+/// 解析 Release 页面地址：
+///   GitHub：https://github.com/{owner}/{repo}/releases[/tag/{tag} | /latest]
+///   GitLab：https://gitlab.com/{group}/{project}/-/releases[/{tag}]
+fn parse_release_url(url: &str, saved_provider: Option<&str>) -> AppResult<ReleaseUrl> {
+    let parsed = url::Url::parse(url.trim())
+        .map_err(|e| AppError::invalid(format!("Release 地址无效: {}", e)))?;
+    let host = host_key(&parsed).ok_or_else(|| AppError::invalid("Release 地址缺少主机名"))?;
+    let segments: Vec<String> = parsed
+        .path_segments()
+        .map(|s| {
+            s.filter(|p| !p.is_empty())
+                .map(|p| {
+                    urlencoding::decode(p)
+                        .map(|v| v.to_string())
+                        .unwrap_or_else(|_| p.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let gitlab_pos = segments
+        .windows(2)
+        .position(|w| w[0] == "-" && w[1] == "releases");
+    let provider = match (saved_provider, gitlab_pos) {
+        (Some("gitlab"), _) | (None, Some(_)) => "gitlab",
+        _ if host == "gitlab.com" => "gitlab",
+        _ => "github",
+    };
+
+    let (repository, tag) = if provider == "gitlab" {
+        let pos = gitlab_pos.ok_or_else(|| {
+            AppError::invalid(
+                "GitLab Release 地址应形如 https://gitlab.com/group/project/-/releases/v1.0",
+            )
+        })?;
+        (segments[..pos].join("/"), segments.get(pos + 2).cloned())
+    } else {
+        if segments.len() < 3 || segments[2] != "releases" {
+            return Err(AppError::invalid(
+                "GitHub Release 地址应形如 https://github.com/owner/repo/releases/tag/v1.0",
+            ));
+        }
+        let tag = match segments.get(3).map(String::as_str) {
+            Some("tag") => segments.get(4).cloned(),
+            _ => None,
+        };
+        (format!("{}/{}", segments[0], segments[1]), tag)
+    };
+    if repository.is_empty() {
+        return Err(AppError::invalid("Release 地址缺少仓库路径"));
+    }
+    Ok(ReleaseUrl {
+        provider,
+        scheme: parsed.scheme().to_string(),
+        host,
+        repository,
+        tag,
+    })
+}
+
+/// 平台对应的鉴权头
+fn auth_headers(provider: &str, token: &str) -> Vec<(String, String)> {
+    if provider == "gitlab" {
+        vec![("PRIVATE-TOKEN".to_string(), token.to_string())]
+    } else {
+        vec![("Authorization".to_string(), format!("Bearer {}", token))]
+    }
+}
+
+async fn token_for(host: &str) -> Option<HostingToken> {
+    TOKENS.ensure_loaded().await;
+    TOKENS.lock().await.get(host).cloned()
+}
+
+/// 下载器发请求时按主机取鉴权头；令牌已删除时返回空
+pub(crate) async fn auth_headers_for_host(host: &str) -> Vec<(String, String)> {
+    token_for(host)
+        .await
+        .map(|t| auth_headers(&t.provider, &t.token))
+        .unwrap_or_default()
+}
+
+async fn fetch_json(url: &str, headers: &[(String, String)]) -> AppResult<Value> {
+    let client = crate::http_client::builder()
+        .timeout(API_TIMEOUT)
+        .user_agent("CodeShelf")
+        .build()
+        .map_err(|e| AppError::other(format!("创建 HTTP 客户端失败: {}", e)))?;
+    let mut request = client.get(url);
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| AppError::other(format!("请求 {} 失败: {}", url, e)))?;
+    let status = response.status();
+    if !status.is_success() {
+        let hint = match status.as_u16() {
+            401 | 403 => "，请检查令牌是否有效、是否有仓库读取权限",
+            404 => "，仓库或版本不存在（私有仓库需要先保存令牌）",
+            _ => "",
+        };
+        return Err(AppError::other(format!("API 返回 {}{}", status, hint)));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| AppError::other(format!("解析 API 响应失败: {}", e)))
+}
+
+fn github_assets(release: &Value, authenticated: bool) -> Vec<ReleaseAsset> {
+    release["assets"]
+        .as_array()
+        .map(|assets| {
+            assets
+                .iter()
+                .filter_map(|a| {
+                    let browser_url = a["browser_download_url"].as_str()?;
+                    // 带令牌时走 API 地址，私有仓库的浏览器地址无法用令牌下载
+                    let download_url = if authenticated {
+                        a["url"].as_str().unwrap_or(browser_url)
+                    } else {
+                        browser_url
+                    };
+                    Some(ReleaseAsset {
+                        name: a["name"].as_str()?.to_string(),
+                        size: a["size"].as_u64(),
+                        content_type: a["content_type"].as_str().map(String::from),
+                        download_url: download_url.to_string(),
+                        requires_auth: authenticated,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn gitlab_assets(release: &Value, host: &str, authenticated: bool) -> Vec<ReleaseAsset> {
+    let same_host = |url: &str| {
+        url::Url::parse(url)
+            .ok()
+            .and_then(|u| host_key(&u))
+            .is_some_and(|h| h == host)
+    };
+    let links = release["assets"]["links"].as_array().into_iter().flatten();
+    let sources = release["assets"]["sources"]
+        .as_array()
+        .into_iter()
+        .flatten();
+    links
+        .filter_map(|l| {
+            let url = l["direct_asset_url"].as_str().or(l["url"].as_str())?;
+            Some((l["name"].as_str()?.to_string(), url.to_string()))
+        })
+        .chain(sources.filter_map(|s| {
+            let format = s["format"].as_str()?;
+            Some((format!("source.{}", format), s["url"].as_str()?.to_string()))
+        }))
+        .map(|(name, url)| ReleaseAsset {
+            name,
+            size: None,
+            content_type: None,
+            requires_auth: authenticated && same_host(&url),
+            download_url: url,
+        })
+        .collect()
+}
+
+async fn load_release(url: &str) -> AppResult<(ReleaseInfo, Option<HostingToken>)> {
+    // 自建实例的平台类型以保存令牌时填写的为准
+    let token = match url::Url::parse(url.trim()).ok().and_then(|u| host_key(&u)) {
+        Some(host) => token_for(&host).await,
+        None => None,
+    };
+    let release_url = parse_release_url(url, token.as_ref().map(|t| t.provider.as_str()))?;
+    let headers = token
+        .as_ref()
+        .map(|t| auth_headers(release_url.provider, &t.token))
+        .unwrap_or_default();
+
+    let api = release_url.api_base();
+    let release = if release_url.provider == "gitlab" {
+        let project = urlencoding::encode(&release_url.repository);
+        let endpoint = match &release_url.tag {
+            Some(tag) => format!(
+                "{}/projects/{}/releases/{}",
+                api,
+                project,
+                urlencoding::encode(tag)
+            ),
+            None => format!("{}/projects/{}/releases/permalink/latest", api, project),
+        };
+        fetch_json(&endpoint, &headers).await?
+    } else {
+        let endpoint = match &release_url.tag {
+            Some(tag) => format!(
+                "{}/repos/{}/releases/tags/{}",
+                api,
+                release_url.repository,
+                urlencoding::encode(tag)
+            ),
+            None => format!("{}/repos/{}/releases/latest", api, release_url.repository),
+        };
+        let mut github_headers = headers.clone();
+        github_headers.push((
+            "Accept".to_string(),
+            "application/vnd.github+json".to_string(),
+        ));
+        fetch_json(&endpoint, &github_headers).await?
+    };
+
+    let authenticated = token.is_some();
+    let assets = if release_url.provider == "gitlab" {
+        gitlab_assets(&release, &release_url.host, authenticated)
+    } else {
+        github_assets(&release, authenticated)
+    };
+    let info = ReleaseInfo {
+        provider: release_url.provider.to_string(),
+        host: release_url.host.clone(),
+        repository: release_url.repository.clone(),
+        tag: release["tag_name"]
+            .as_str()
+            .map(String::from)
+            .or(release_url.tag.clone())
+            .unwrap_or_default(),
+        name: release["name"].as_str().map(String::from),
+        published_at: release["published_at"]
+            .as_str()
+            .or(release["released_at"].as_str())
+            .map(String::from),
+        assets,
+        authenticated,
+    };
+    Ok((info, token))
+}
+
+/// 已保存的代码托管令牌
+#[tauri::command]
+#[specta::specta]
+pub async fn list_hosting_tokens() -> AppResult<Vec<HostingTokenInfo>> {
+    TOKENS.ensure_loaded().await;
+    let mut list: Vec<HostingTokenInfo> = TOKENS
+        .lock()
+        .await
+        .values()
+        .map(|t| HostingTokenInfo {
+            host: t.host.clone(),
+            provider: t.provider.clone(),
+            masked_token: mask_token(&t.token),
+        })
+        .collect();
+    list.sort_by(|a, b| a.host.cmp(&b.host));
+    Ok(list)
+}
+
+/// 保存主机的访问令牌（同一主机覆盖）
+#[tauri::command]
+#[specta::specta]
+pub async fn save_hosting_token(host: String, provider: String, token: String) -> AppResult<()> {
+    let host = host
+        .trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_end_matches('/')
+        .to_lowercase();
+    if host.is_empty() || host.contains('/') {
+        return Err(AppError::invalid("请填写主机名，如 github.com"));
+    }
+    if !matches!(provider.as_str(), "github" | "gitlab") {
+        return Err(AppError::invalid(format!("不支持的平台: {}", provider)));
+    }
+    let token = token.trim().to_string();
+    if token.is_empty() {
+        return Err(AppError::invalid("令牌不能为空"));
+    }
+    TOKENS.ensure_loaded().await;
+    TOKENS.lock().await.insert(
+        host.clone(),
+        HostingToken {
+            host,
+            provider,
+            token,
+        },
+    );
+    TOKENS.save().await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_hosting_token(host: String) -> AppResult<()> {
+    TOKENS.ensure_loaded().await;
+    TOKENS.lock().await.remove(&host);
+    TOKENS.save().await
+}
+
+/// 列出 Release 的附件；地址不带版本号时取最新版本
+#[tauri::command]
+#[specta::specta]
+pub async fn list_release_assets(url: String) -> AppResult<ReleaseInfo> {
+    load_release(&url).await.map(|(info, _)| info)
+}
+
+/// 下载 Release 附件：重新查询一次附件列表，按名称找到附件后交给下载器
+#[tauri::command]
+#[specta::specta]
+pub async fn download_release_asset(
+    url: String,
+    asset_name: String,
+    save_dir: Option<String>,
+) -> AppResult<String> {
+    let (info, token) = load_release(&url).await?;
+    let asset = info
+        .assets
+        .iter()
+        .find(|a| a.name == asset_name)
+        .ok_or_else(|| AppError::invalid(format!("Release 中没有附件: {}", asset_name)))?;
+
+    let mut headers: HashMap<String, String> = HashMap::new();
+    if info.provider == "github" {
+        // API 地址默认返回附件元数据，需要声明下载二进制内容
+        headers.insert("Accept".to_string(), "application/octet-stream".to_string());
+    }
+    // 只记录令牌所属主机，令牌由下载器在每次请求时读取，避免明文写进下载任务文件
+    let auth_host = token.filter(|_| asset.requires_auth).map(|t| t.host);
+
+    enqueue_download(
+        DownloadConfig {
+            url: asset.download_url.clone(),
+            save_dir,
+            file_name: Some(asset.name.clone()),
+            max_retries: None,
+            headers: Some(headers),
+            mirrors: Vec::new(),
+            stall_timeout_secs: None,
+        },
+        auth_host,
+    )
+    .await
+}
@@ -0,0 +1,318 @@
+// 系统资源告警 - 后台定时采样 CPU / 内存 / 磁盘剩余空间，超过阈值时推送通知、可选闪烁托盘图标
+//
+// - 每条规则监控一个指标：cpu（%）、memory（%）、disk（指定挂载点剩余 GB，低于阈值告警）
+// - 超过阈值持续 sustain_secs 秒才触发，避免瞬时尖峰刷屏；恢复正常后再通知一次
+// - 采样间隔 SAMPLE_INTERVAL，没有启用的规则时跳过采样
+
+use super::generate_id;
+use crate::commands::settings::push_notification;
+use crate::error::{AppError, AppResult};
+use crate::storage::config::StorageConfig;
+use crate::storage::PersistedStore;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Instant;
+use sysinfo::{Disks, System};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Mutex;
+use tokio::time::Duration;
+
+/// 采样间隔
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// 告警规则
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceAlertRule {
+    pub id: String,
+    pub name: String,
+    /// "cpu" | "memory" | "disk"
+    pub metric: String,
+    /// cpu / memory 为百分比（超过告警）；disk 为剩余 GB（低于告警）
+    pub threshold: f64,
+    /// disk 规则的挂载点（如 "/"、"C:\\"），为空时检查所有磁盘
+    #[serde(default)]
+    pub disk_path: Option<String>,
+    /// 持续超过阈值多少秒才告警
+    #[serde(default)]
+    pub sustain_secs: u64,
+    /// 告警时闪烁托盘图标
+    #[serde(default)]
+    pub flash_tray: bool,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceAlertRuleInput {
+    pub name: String,
+    pub metric: String,
+    pub threshold: f64,
+    pub disk_path: Option<String>,
+    pub sustain_secs: Option<u64>,
+    pub flash_tray: Option<bool>,
+    pub enabled: Option<bool>,
+}
+
+/// 正在触发的告警
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceAlert {
+    pub rule_id: String,
+    pub name: String,
+    pub metric: String,
+    /// 当前值（cpu / memory 为百分比，disk 为剩余 GB）
+    pub value: f64,
+    pub threshold: f64,
+    /// 触发的磁盘挂载点（disk 规则）
+    pub target: Option<String>,
+    pub since: String,
+}
+
+static RULES: Lazy<PersistedStore<ResourceAlertRule>> = Lazy::new(|| {
+    PersistedStore::new(
+        "resourceAlerts",
+        "资源告警规则",
+        StorageConfig::resource_alerts_file,
+        |r| r.id.clone(),
+    )
+});
+
+/// 规则 id -> 正在触发的告警
+static ACTIVE: Lazy<Mutex<HashMap<String, ResourceAlert>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 一次采样的结果
+struct Sample {
+    cpu: f64,
+    memory: f64,
+    /// (挂载点, 剩余 GB)
+    disks: Vec<(String, f64)>,
+}
+
+fn take_sample(system: &mut System, disks: &mut Disks) -> Sample {
+    system.refresh_cpu_usage();
+    system.refresh_memory();
+    disks.refresh_list();
+    let memory = if system.total_memory() > 0 {
+        system.used_memory() as f64 * 100.0 / system.total_memory() as f64
+    } else {
+        0.0
+    };
+    Sample {
+        cpu: system.global_cpu_info().cpu_usage() as f64,
+        memory,
+        disks: disks
+            .list()
+            .iter()
+            .map(|d| {
+                (
+                    d.mount_point().to_string_lossy().to_string(),
+                    d.available_space() as f64 / 1024.0 / 1024.0 / 1024.0,
+                )
+            })
+            .collect(),
+    }
+}
+
+/// 规则是否越过阈值，返回 (当前值, 目标)
+fn evaluate(rule: &ResourceAlertRule, sample: &Sample) -> Option<(f64, Option<String>)> {
+    match rule.metric.as_str() {
+        "cpu" => (sample.cpu >= rule.threshold).then_some((sample.cpu, None)),
+        "memory" => (sample.memory >= rule.threshold).then_some((sample.memory, None)),
+        "disk" => sample
+            .disks
+            .iter()
+            .filter(|(mount, _)| {
+                rule.disk_path
+                    .as_deref()
+                    .map(|p| p.trim().is_empty() || mount == p.trim())
+                    .unwrap_or(true)
+            })
+            .filter(|(_, free)| *free < rule.threshold)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(mount, free)| (*free, Some(mount.clone()))),
+        _ => None,
+    }
+}
+
+fn describe(alert: &ResourceAlert) -> String {
+    match alert.metric.as_str() {
+        "disk" => format!(
+            "{} 剩余 {:.1} GB（阈值 {:.1} GB）",
+            alert.target.as_deref().unwrap_or("磁盘"),
+            alert.value,
+            alert.threshold
+        ),
+        "cpu" => format!(
+            "CPU 使用率 {:.0}%（阈值 {:.0}%）",
+            alert.value, alert.threshold
+        ),
+        _ => format!(
+            "内存使用率 {:.0}%（阈值 {:.0}%）",
+            alert.value, alert.threshold
+        ),
+    }
+}
+
+/// 用一次采样结果更新告警状态；pending 记录每条规则首次越过阈值的时间
+async fn check_rules(app: &AppHandle, sample: &Sample, pending: &mut HashMap<String, Instant>) {
+    let rules: Vec<ResourceAlertRule> = RULES
+        .lock()
+        .await
+        .values()
+        .filter(|r| r.enabled)
+        .cloned()
+        .collect();
+    pending.retain(|id, _| rules.iter().any(|r| &r.id == id));
+
+    let mut active = ACTIVE.lock().await;
+    active.retain(|id, _| rules.iter().any(|r| &r.id == id));
+
+    for rule in &rules {
+        match evaluate(rule, sample) {
+            Some((value, target)) => {
+                if let Some(alert) = active.get_mut(&rule.id) {
+                    alert.value = value;
+                    alert.target = target;
+                    continue;
+                }
+                let first = *pending.entry(rule.id.clone()).or_insert_with(Instant::now);
+                if first.elapsed().as_secs() < rule.sustain_secs {
+                    continue;
+                }
+                let alert = ResourceAlert {
+                    rule_id: rule.id.clone(),
+                    name: rule.name.clone(),
+                    metric: rule.metric.clone(),
+                    value,
+                    threshold: rule.threshold,
+                    target,
+                    since: super::current_time(),
+                };
+                let message = describe(&alert);
+                log::warn!("资源告警 {}: {}", rule.name, message);
+                push_notification(
+                    app,
+                    "monitor",
+                    "warning",
+                    &format!("{} 告警", rule.name),
+                    &message,
+                )
+                .await;
+                if rule.flash_tray {
+                    crate::app_setup::flash_tray(app, &format!("CodeShelf - {}", message));
+                }
+                let _ = app.emit("resource-alert", &alert);
+                active.insert(rule.id.clone(), alert);
+            }
+            None => {
+                pending.remove(&rule.id);
+                if let Some(alert) = active.remove(&rule.id) {
+                    push_notification(
+                        app,
+                        "monitor",
+                        "success",
+                        &format!("{} 已恢复", alert.name),
+                        &format!("告警开始于 {}", alert.since),
+                    )
+                    .await;
+                    let _ = app.emit("resource-alert-cleared", &alert.rule_id);
+                }
+            }
+        }
+    }
+}
+
+/// 启动后台采样
+pub fn spawn_resource_monitor(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut system = System::new();
+        let mut disks = Disks::new();
+        let mut pending: HashMap<String, Instant> = HashMap::new();
+        loop {
+            tokio::time::sleep(SAMPLE_INTERVAL).await;
+            RULES.ensure_loaded().await;
+            let has_rules = RULES.lock().await.values().any(|r| r.enabled);
+            if !has_rules {
+                pending.clear();
+                ACTIVE.lock().await.clear();
+                continue;
+            }
+            let sample = take_sample(&mut system, &mut disks);
+            check_rules(&app, &sample, &mut pending).await;
+        }
+    });
+}
+
+fn validate_input(input: &ResourceAlertRuleInput) -> AppResult<()> {
+    if input.name.trim().is_empty() {
+        return Err(AppError::invalid("规则名称不能为空"));
+    }
+    match input.metric.as_str() {
+        "cpu" | "memory" if !(0.0..=100.0).contains(&input.threshold) => {
+            Err(AppError::invalid("百分比阈值需在 0-100 之间"))
+        }
+        "cpu" | "memory" => Ok(()),
+        "disk" if input.threshold < 0.0 => Err(AppError::invalid("磁盘阈值不能为负数")),
+        "disk" => Ok(()),
+        other => Err(AppError::invalid(format!("不支持的指标: {}", other))),
+    }
+}
+
+/// 获取告警规则
+#[tauri::command]
+#[specta::specta]
+pub async fn get_resource_alert_rules() -> AppResult<Vec<ResourceAlertRule>> {
+    RULES.ensure_loaded().await;
+    let mut rules: Vec<ResourceAlertRule> = RULES.lock().await.values().cloned().collect();
+    rules.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(rules)
+}
+
+/// 新建（id 为空）或更新告警规则
+#[tauri::command]
+#[specta::specta]
+pub async fn save_resource_alert_rule(
+    id: Option<String>,
+    input: ResourceAlertRuleInput,
+) -> AppResult<ResourceAlertRule> {
+    validate_input(&input)?;
+    RULES.ensure_loaded().await;
+    let rule = ResourceAlertRule {
+        id: id.unwrap_or_else(generate_id),
+        name: input.name.trim().to_string(),
+        metric: input.metric,
+        threshold: input.threshold,
+        disk_path: input.disk_path.filter(|p| !p.trim().is_empty()),
+        sustain_secs: input.sustain_secs.unwrap_or(30),
+        flash_tray: input.flash_tray.unwrap_or(false),
+        enabled: input.enabled.unwrap_or(true),
+    };
+    RULES.lock().await.insert(rule.id.clone(), rule.clone());
+    // 规则变化后重新判断
+    ACTIVE.lock().await.remove(&rule.id);
+    RULES.save().await?;
+    Ok(rule)
+}
+
+/// 删除告警规则
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_resource_alert_rule(id: String) -> AppResult<()> {
+    RULES.ensure_loaded().await;
+    RULES.lock().await.remove(&id);
+    ACTIVE.lock().await.remove(&id);
+    RULES.save().await
+}
+
+/// 获取当前正在触发的告警
+#[tauri::command]
+#[specta::specta]
+pub async fn get_current_alerts() -> AppResult<Vec<ResourceAlert>> {
+    let active = ACTIVE.lock().await;
+    let mut alerts: Vec<ResourceAlert> = active.values().cloned().collect();
+    alerts.sort_by(|a, b| a.since.cmp(&b.since));
+    Ok(alerts)
+}
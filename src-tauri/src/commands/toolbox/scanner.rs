@@ -1,8 +1,8 @@
 // 端口扫描模块 - 支持并发扫描、超时控制、进度回调
 
-use super::{common_ports, port_service_name, ScanConfig, ScanResult};
-use crate::error::AppResult;
-use std::net::{IpAddr, SocketAddr};
+use super::{common_ports, port_service_name, syn_scan, ScanCapabilities, ScanConfig, ScanResult};
+use crate::error::{AppError, AppResult};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
@@ -14,6 +14,9 @@ use tokio::time::timeout;
 /// 全局扫描取消标志
 static SCAN_CANCELLED: AtomicBool = AtomicBool::new(false);
 
+/// 网段扫描最多展开的主机数（/20）
+const MAX_HOSTS: u32 = 4096;
+
 /// 扫描端口
 #[tauri::command]
 #[specta::specta]
@@ -21,9 +24,8 @@ pub async fn scan_ports(config: ScanConfig) -> AppResult<Vec<ScanResult>> {
     // 重置取消标志
     SCAN_CANCELLED.store(false, Ordering::SeqCst);
 
-    // 解析目标 IP
-    let target_ip = IpAddr::from_str(&config.target)
-        .map_err(|_| crate::error::AppError::from(format!("无效的 IP 地址: {}", config.target)))?;
+    // 解析目标 IP / 网段
+    let targets = parse_targets(&config.target)?;
 
     // 确定要扫描的端口
     let ports = determine_ports(&config);
@@ -32,12 +34,74 @@ pub async fn scan_ports(config: ScanConfig) -> AppResult<Vec<ScanResult>> {
     let timeout_ms = config.timeout_ms.unwrap_or(3000);
     let concurrency = config.concurrency.unwrap_or(100);
 
+    if config.mode.as_deref() == Some("syn") {
+        let ipv4_only = targets.iter().all(|ip| ip.is_ipv4());
+        match syn_scan::available() {
+            Ok(()) if ipv4_only => {
+                return syn_scan::scan(targets, ports, timeout_ms, &SCAN_CANCELLED).await;
+            }
+            Ok(()) => log::info!("SYN 扫描仅支持 IPv4，改用 connect 扫描"),
+            Err(reason) => log::info!("{}，改用 connect 扫描", reason),
+        }
+    }
+
     // 执行并发扫描
-    let results = concurrent_scan(target_ip, ports, timeout_ms, concurrency).await?;
+    let results = concurrent_scan(targets, ports, timeout_ms, concurrency).await?;
 
     Ok(results)
 }
 
+/// 检测 SYN 扫描是否可用（权限 / 平台），供前端决定是否提供该选项
+#[tauri::command]
+#[specta::specta]
+pub async fn get_scan_capabilities() -> AppResult<ScanCapabilities> {
+    let syn = syn_scan::available();
+    Ok(ScanCapabilities {
+        syn_available: syn.is_ok(),
+        syn_unavailable_reason: syn.err(),
+    })
+}
+
+/// 解析扫描目标：单个 IP 或 IPv4 CIDR 网段（/31、/32 之外跳过网络地址与广播地址）
+fn parse_targets(target: &str) -> AppResult<Vec<IpAddr>> {
+    let target = target.trim();
+    let Some((base, prefix)) = target.split_once('/') else {
+        let ip = IpAddr::from_str(target)
+            .map_err(|_| AppError::from(format!("无效的 IP 地址: {}", target)))?;
+        return Ok(vec![ip]);
+    };
+
+    let base = Ipv4Addr::from_str(base)
+        .map_err(|_| AppError::from(format!("无效的网段（仅支持 IPv4）: {}", target)))?;
+    let prefix: u32 = prefix
+        .parse()
+        .ok()
+        .filter(|p| *p <= 32)
+        .ok_or_else(|| AppError::from(format!("无效的网段前缀: {}", target)))?;
+    let size = 1u64 << (32 - prefix);
+    if size > MAX_HOSTS as u64 {
+        return Err(AppError::invalid(format!(
+            "网段过大（最多 {} 个地址，即 /20）",
+            MAX_HOSTS
+        )));
+    }
+
+    let mask = if prefix == 0 {
+        0
+    } else {
+        u32::MAX << (32 - prefix)
+    };
+    let network = u32::from(base) & mask;
+    let (first, last) = if prefix >= 31 {
+        (network, network + size as u32 - 1)
+    } else {
+        (network + 1, network + size as u32 - 2)
+    };
+    Ok((first..=last)
+        .map(|n| IpAddr::V4(Ipv4Addr::from(n)))
+        .collect())
+}
+
 /// 停止扫描
 #[tauri::command]
 #[specta::specta]
@@ -75,7 +139,7 @@ fn determine_ports(config: &ScanConfig) -> Vec<u16> {
 
 /// 并发扫描端口
 async fn concurrent_scan(
-    target: IpAddr,
+    targets: Vec<IpAddr>,
     ports: Vec<u16>,
     timeout_ms: u64,
     concurrency: usize,
@@ -84,69 +148,71 @@ async fn concurrent_scan(
     let _total = ports.len();
     let scanned = Arc::new(AtomicU32::new(0));
 
-    // 使用信号量控制并发
-    let semaphore = Arc::new(tokio::sync::Semaphore::new(concurrency));
-    let mut handles = Vec::new();
-
-    for port in ports {
-        // 检查是否被取消
-        if SCAN_CANCELLED.load(Ordering::SeqCst) {
-            break;
-        }
-
-        let sem = semaphore.clone();
-        let results = results.clone();
-        let scanned = scanned.clone();
-        let timeout_duration = Duration::from_millis(timeout_ms);
-
-        let handle = tokio::spawn(async move {
-            // 获取信号量许可
-            let _permit = sem.acquire().await.ok()?;
+    // 使用信号量控制并发：先取得许可再创建任务，大网段扫描时不会一次性堆积所有任务
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(concurrency.max(1)));
+    let mut tasks = tokio::task::JoinSet::new();
 
+    'scan: for &target in &targets {
+        for &port in &ports {
             // 检查是否被取消
             if SCAN_CANCELLED.load(Ordering::SeqCst) {
-                return None;
+                break 'scan;
             }
 
-            // 扫描端口
-            let addr = SocketAddr::new(target, port);
-            let is_open = match timeout(timeout_duration, TcpStream::connect(addr)).await {
-                Ok(Ok(_)) => true,
-                _ => false,
+            let Ok(permit) = semaphore.clone().acquire_owned().await else {
+                break 'scan;
             };
-
-            // 更新进度
-            scanned.fetch_add(1, Ordering::SeqCst);
-
-            // 只记录开放的端口
-            if is_open {
-                let result = ScanResult {
-                    ip: target.to_string(),
-                    port,
-                    status: "open".to_string(),
-                    service: port_service_name(port).map(|s| s.to_string()),
+            // 回收已完成的任务
+            while tasks.try_join_next().is_some() {}
+
+            let results = results.clone();
+            let scanned = scanned.clone();
+            let timeout_duration = Duration::from_millis(timeout_ms);
+
+            tasks.spawn(async move {
+                let _permit = permit;
+
+                // 检查是否被取消
+                if SCAN_CANCELLED.load(Ordering::SeqCst) {
+                    return None;
+                }
+
+                // 扫描端口
+                let addr = SocketAddr::new(target, port);
+                let is_open = match timeout(timeout_duration, TcpStream::connect(addr)).await {
+                    Ok(Ok(_)) => true,
+                    _ => false,
                 };
-                results.lock().await.push(result.clone());
-                Some(result)
-            } else {
-                None
-            }
-        });
 
-        handles.push(handle);
+                // 更新进度
+                scanned.fetch_add(1, Ordering::SeqCst);
+
+                // 只记录开放的端口
+                if is_open {
+                    let result = ScanResult {
+                        ip: target.to_string(),
+                        port,
+                        status: "open".to_string(),
+                        service: port_service_name(port).map(|s| s.to_string()),
+                    };
+                    results.lock().await.push(result.clone());
+                    Some(result)
+                } else {
+                    None
+                }
+            });
+        }
     }
 
     // 等待所有任务完成
-    for handle in handles {
-        let _ = handle.await;
-    }
+    while tasks.join_next().await.is_some() {}
 
     // 返回结果
     let final_results = results.lock().await.clone();
 
-    // 按端口号排序
+    // 按地址、端口号排序
     let mut sorted_results = final_results;
-    sorted_results.sort_by_key(|r| r.port);
+    sorted_results.sort_by_key(|r| (IpAddr::from_str(&r.ip).ok(), r.port));
 
     Ok(sorted_results)
 }
@@ -194,6 +260,7 @@ pub async fn scan_local_dev_ports() -> AppResult<Vec<ScanResult>> {
         port_end: None,
         timeout_ms: Some(1000),
         concurrency: Some(50),
+        mode: None,
     };
 
     scan_ports(config).await
@@ -218,4 +285,40 @@ mod tests {
         assert_eq!(port_service_name(22), Some("SSH"));
         assert_eq!(port_service_name(0), None);
     }
+
+    #[test]
+    fn test_parse_single_ip() {
+        let ip: IpAddr = "192.168.1.10".parse().unwrap();
+        assert_eq!(parse_targets(" 192.168.1.10 ").unwrap(), vec![ip]);
+        let ip: IpAddr = "::1".parse().unwrap();
+        assert_eq!(parse_targets("::1").unwrap(), vec![ip]);
+        assert!(parse_targets("example.com").is_err());
+    }
+
+    #[test]
+    fn test_parse_cidr_skips_network_and_broadcast() {
+        let targets = parse_targets("10.0.0.5/30").unwrap();
+        let expected: Vec<IpAddr> = vec!["10.0.0.5".parse().unwrap(), "10.0.0.6".parse().unwrap()];
+        assert_eq!(targets, expected);
+        assert_eq!(parse_targets("10.0.0.0/24").unwrap().len(), 254);
+    }
+
+    #[test]
+    fn test_parse_cidr_31_and_32() {
+        assert_eq!(parse_targets("10.0.0.1/31").unwrap().len(), 2);
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        assert_eq!(parse_targets("10.0.0.1/32").unwrap(), vec![ip]);
+    }
+
+    #[test]
+    fn test_parse_cidr_limits() {
+        assert_eq!(
+            parse_targets("10.0.0.0/20").unwrap().len(),
+            MAX_HOSTS as usize - 2
+        );
+        assert!(parse_targets("10.0.0.0/19").is_err());
+        assert!(parse_targets("10.0.0.0/33").is_err());
+        assert!(parse_targets("10.0.0.0/x").is_err());
+        assert!(parse_targets("::1/128").is_err());
+    }
 }
@@ -0,0 +1,79 @@
+// 服务配置导出 / 导入
+//
+// 配置包是一个 JSON 文件：服务配置（含代理规则与注入的请求头 / 响应头）以创建服务的输入形式保存，
+// 附带一份等价 nginx 配置供参考。导入时按输入新建服务，id、状态与创建时间重新生成；
+// 根目录在本机不存在时可以用 root_dir 另行指定。
+
+use crate::error::{AppError, AppResult};
+
+use super::super::{current_time, ServerConfig, ServerConfigBundle, ServerConfigInput};
+use super::nginx::server_nginx_config;
+use super::{create_server, ensure_servers_loaded, SERVERS};
+
+const BUNDLE_FORMAT: &str = "codeshelf-server";
+const BUNDLE_VERSION: u32 = 1;
+
+/// 导出服务配置包（格式化的 JSON）
+#[tauri::command]
+#[specta::specta]
+pub async fn export_server_config(server_id: String) -> AppResult<String> {
+    ensure_servers_loaded().await;
+
+    let server = {
+        let servers = SERVERS.lock().await;
+        servers.get(&server_id).cloned()
+    }
+    .ok_or_else(|| AppError::from(format!("服务不存在: {}", server_id)))?;
+
+    let bundle = ServerConfigBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        version: BUNDLE_VERSION,
+        exported_at: current_time(),
+        server: ServerConfigInput {
+            name: server.name.clone(),
+            port: server.port,
+            root_dir: server.root_dir.clone(),
+            cors: Some(server.cors),
+            gzip: Some(server.gzip),
+            cache_control: server.cache_control.clone(),
+            url_prefix: Some(server.url_prefix.clone()),
+            index_page: server.index_page.clone(),
+            proxies: Some(server.proxies.clone()),
+            max_concurrent_requests: server.max_concurrent_requests,
+            max_connections: server.max_connections,
+            rate_limit_per_ip: server.rate_limit_per_ip,
+        },
+        nginx: Some(server_nginx_config(server)),
+    };
+    serde_json::to_string_pretty(&bundle)
+        .map_err(|e| AppError::internal(format!("序列化配置包失败: {}", e)))
+}
+
+/// 导入服务配置包，新建一个停止状态的服务；root_dir 覆盖包中的根目录
+#[tauri::command]
+#[specta::specta]
+pub async fn import_server_config(
+    bundle: String,
+    root_dir: Option<String>,
+) -> AppResult<ServerConfig> {
+    let bundle: ServerConfigBundle = serde_json::from_str(&bundle)
+        .map_err(|e| AppError::invalid(format!("配置包格式无效: {}", e)))?;
+    if bundle.format != BUNDLE_FORMAT {
+        return Err(AppError::invalid(format!(
+            "不是服务配置包: {}",
+            bundle.format
+        )));
+    }
+    if bundle.version > BUNDLE_VERSION {
+        return Err(AppError::invalid(format!(
+            "配置包版本 {} 高于当前支持的版本 {}，请升级后再导入",
+            bundle.version, BUNDLE_VERSION
+        )));
+    }
+
+    let mut input = bundle.server;
+    if let Some(root_dir) = root_dir.filter(|d| !d.trim().is_empty()) {
+        input.root_dir = root_dir;
+    }
+    create_server(input).await
+}
@@ -1,11 +1,13 @@
 // 静态服务 CRUD：create / stop / remove / get / update
 
 use crate::error::AppResult;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use tauri::AppHandle;
 
-use super::super::{current_time, generate_id, ServerConfig, ServerConfigInput};
-use super::runtime::run_server;
+use super::super::port_conflict::bind_or_conflict;
+use super::super::{current_time, generate_id, ProxyConfig, ServerConfig, ServerConfigInput};
+use super::runtime::{mark_server_error, run_server, watch_root_dir};
 use super::{
     ensure_servers_loaded, save_servers_to_file, ServerController, SERVERS, SERVER_CONTROLLERS,
 };
@@ -23,6 +25,9 @@ pub async fn create_server(input: ServerConfigInput) -> AppResult<ServerConfig>
     if input.root_dir.is_empty() {
         return Err(crate::error::AppError::from("根目录不能为空".to_string()));
     }
+    if let Some(proxies) = &input.proxies {
+        validate_proxies(proxies)?;
+    }
 
     // 检查目录是否存在
     let root_path = PathBuf::from(&input.root_dir);
@@ -82,7 +87,11 @@ pub async fn create_server(input: ServerConfigInput) -> AppResult<ServerConfig>
         url_prefix,
         index_page,
         proxies: input.proxies.unwrap_or_default(),
+        max_concurrent_requests: input.max_concurrent_requests,
+        max_connections: input.max_connections,
+        rate_limit_per_ip: input.rate_limit_per_ip,
         status: "stopped".to_string(),
+        error: None,
         created_at: current_time(),
     };
 
@@ -131,6 +140,7 @@ pub async fn stop_server(server_id: String) -> AppResult<()> {
         let mut servers = SERVERS.lock().await;
         if let Some(server) = servers.get_mut(&server_id) {
             server.status = "stopped".to_string();
+            server.error = None;
             log::info!("服务状态已更新为停止");
         }
     }
@@ -154,7 +164,7 @@ pub async fn stop_server(server_id: String) -> AppResult<()> {
 /// 启动服务
 #[tauri::command]
 #[specta::specta]
-pub async fn start_server(server_id: String) -> AppResult<String> {
+pub async fn start_server(app: AppHandle, server_id: String) -> AppResult<String> {
     ensure_servers_loaded().await;
 
     // 获取配置
@@ -170,6 +180,16 @@ pub async fn start_server(server_id: String) -> AppResult<String> {
         return Err(crate::error::AppError::from("服务已在运行中".to_string()));
     }
 
+    // 自动恢复 / 文档预览也走这里：根目录可能在上次运行后被删除或卸载
+    if !Path::new(&config.root_dir).is_dir() {
+        let reason = format!("根目录不存在: {}", config.root_dir);
+        mark_server_error(&server_id, &reason).await;
+        return Err(crate::error::AppError::invalid(reason));
+    }
+
+    // 先同步绑定端口，占用时直接返回占用进程信息
+    let std_listener = bind_or_conflict(&app, config.port, 1024, "server", &server_id).await?;
+
     // 创建控制器
     let controller = Arc::new(ServerController::new());
 
@@ -184,9 +204,17 @@ pub async fn start_server(server_id: String) -> AppResult<String> {
         let mut servers = SERVERS.lock().await;
         if let Some(s) = servers.get_mut(&server_id) {
             s.status = "running".to_string();
+            s.error = None;
         }
     }
 
+    watch_root_dir(
+        app.clone(),
+        server_id.clone(),
+        config.root_dir.clone(),
+        controller.clone(),
+    );
+
     let id = server_id.clone();
     let port = config.port;
     let url_prefix = config.url_prefix.clone();
@@ -194,7 +222,7 @@ pub async fn start_server(server_id: String) -> AppResult<String> {
 
     // 启动服务
     tokio::spawn(async move {
-        let result = run_server(&id, config, controller).await;
+        let result = run_server(&id, config, std_listener, controller).await;
 
         match result {
             Ok(()) => {
@@ -205,16 +233,16 @@ pub async fn start_server(server_id: String) -> AppResult<String> {
             }
         }
 
-        // 更新状态（使用 try_lock 避免死锁）
+        // 更新状态（使用 try_lock 避免死锁）；根目录监视已标记为 error 的保留错误状态
         if let Ok(mut servers) = SERVERS.try_lock() {
-            if let Some(s) = servers.get_mut(&id) {
+            if let Some(s) = servers.get_mut(&id).filter(|s| s.status != "error") {
                 s.status = "stopped".to_string();
             }
         } else {
             // 如果获取锁失败，延迟重试
             tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
             let mut servers = SERVERS.lock().await;
-            if let Some(s) = servers.get_mut(&id) {
+            if let Some(s) = servers.get_mut(&id).filter(|s| s.status != "error") {
                 s.status = "stopped".to_string();
             }
         }
@@ -307,6 +335,28 @@ pub async fn get_server(server_id: String) -> AppResult<Option<ServerConfig>> {
     Ok(servers.get(&server_id).cloned())
 }
 
+/// 校验代理规则中注入的请求头 / 响应头
+pub(super) fn validate_proxies(proxies: &[ProxyConfig]) -> AppResult<()> {
+    use axum::http::header::{HeaderName, HeaderValue};
+    for proxy in proxies {
+        for (name, value) in proxy.request_headers.iter().chain(&proxy.response_headers) {
+            HeaderName::from_bytes(name.as_bytes()).map_err(|_| {
+                crate::error::AppError::invalid(format!(
+                    "代理 {} 的头名称无效: {}",
+                    proxy.prefix, name
+                ))
+            })?;
+            HeaderValue::from_str(value).map_err(|_| {
+                crate::error::AppError::invalid(format!(
+                    "代理 {} 的头 {} 的值无效",
+                    proxy.prefix, name
+                ))
+            })?;
+        }
+    }
+    Ok(())
+}
+
 /// 更新服务配置
 #[tauri::command]
 #[specta::specta]
@@ -322,6 +372,9 @@ pub async fn update_server(server_id: String, input: ServerConfigInput) -> AppRe
     let current = current
         .ok_or_else(|| crate::error::AppError::from(format!("服务不存在: {}", server_id)))?;
     let old_config = current.clone();
+    if let Some(proxies) = &input.proxies {
+        validate_proxies(proxies)?;
+    }
 
     // 如果正在运行，先停止
     if current.status == "running" {
@@ -366,6 +419,9 @@ pub async fn update_server(server_id: String, input: ServerConfigInput) -> AppRe
             server.url_prefix = url_prefix;
             server.index_page = index_page;
             server.proxies = input.proxies.unwrap_or_default();
+            server.max_concurrent_requests = input.max_concurrent_requests;
+            server.max_connections = input.max_connections;
+            server.rate_limit_per_ip = input.rate_limit_per_ip;
         }
     }
 
@@ -0,0 +1,147 @@
+// 静态服务访问限制：最大连接数 + 最大并发请求数 + 每 IP 限速，防止误暴露到局域网 / 公网后被刷爆
+//
+// - 连接：每个连接建立时取一个许可，随连接的 service 一起释放（keep-alive 空闲连接同样占用）；
+//   取不到许可的连接，其请求一律返回 503 并带 Connection: close，由客户端断开
+// - 并发：信号量，拿不到许可直接返回 503（不排队，避免积压占满内存）
+// - 限速：每个客户端 IP 一个令牌桶，每秒补充 rate 个、最多攒 2 倍突发，超出返回 429
+// 以 axum 中间件（tower Layer）挂在整个 Router 外层，静态文件与 API 代理都受限制。
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Extension,
+};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use super::super::ServerConfig;
+
+/// 令牌桶表超过该数量时清理长时间未访问的 IP
+const MAX_TRACKED_IPS: usize = 10_000;
+const BUCKET_IDLE: Duration = Duration::from_secs(60);
+
+struct Bucket {
+    tokens: f64,
+    last: Instant,
+}
+
+/// 连接许可，作为请求扩展挂在连接的 service 上；rejected 表示连接数已满
+#[derive(Clone)]
+pub(super) struct ConnectionSlot {
+    rejected: bool,
+    _permit: Option<Arc<OwnedSemaphorePermit>>,
+}
+
+pub(super) struct Limiter {
+    connections: Option<Arc<Semaphore>>,
+    concurrency: Option<Arc<Semaphore>>,
+    rate_per_ip: Option<u32>,
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+}
+
+impl Limiter {
+    /// 配置未设置任何限制时返回 None，不挂中间件
+    pub(super) fn from_config(config: &ServerConfig) -> Option<Arc<Self>> {
+        let connections = config.max_connections.filter(|n| *n > 0);
+        let max = config.max_concurrent_requests.filter(|n| *n > 0);
+        let rate = config.rate_limit_per_ip.filter(|n| *n > 0);
+        if connections.is_none() && max.is_none() && rate.is_none() {
+            return None;
+        }
+        Some(Arc::new(Self {
+            connections: connections.map(|n| Arc::new(Semaphore::new(n as usize))),
+            concurrency: max.map(|n| Arc::new(Semaphore::new(n as usize))),
+            rate_per_ip: rate,
+            buckets: Mutex::new(HashMap::new()),
+        }))
+    }
+
+    /// 新连接建立时调用；未限制连接数时返回 None
+    pub(super) fn acquire_connection(&self) -> Option<ConnectionSlot> {
+        let semaphore = self.connections.as_ref()?;
+        let permit = semaphore.clone().try_acquire_owned().ok();
+        Some(ConnectionSlot {
+            rejected: permit.is_none(),
+            _permit: permit.map(Arc::new),
+        })
+    }
+
+    fn allow(&self, ip: IpAddr) -> bool {
+        let Some(rate) = self.rate_per_ip else {
+            return true;
+        };
+        let Ok(mut buckets) = self.buckets.lock() else {
+            return true;
+        };
+        let now = Instant::now();
+        if buckets.len() > MAX_TRACKED_IPS {
+            buckets.retain(|_, b| now.duration_since(b.last) < BUCKET_IDLE);
+        }
+
+        let capacity = rate as f64 * 2.0;
+        let bucket = buckets.entry(ip).or_insert(Bucket {
+            tokens: capacity,
+            last: now,
+        });
+        let elapsed = now.duration_since(bucket.last).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate as f64).min(capacity);
+        bucket.last = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+pub(super) async fn limit(
+    State(limiter): State<Arc<Limiter>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Extension(slot): Extension<Option<ConnectionSlot>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if slot.is_some_and(|slot| slot.rejected) {
+        log::debug!("静态服务连接数已满，拒绝 {}", addr);
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::RETRY_AFTER, "1"), (header::CONNECTION, "close")],
+            "Too Many Connections",
+        )
+            .into_response();
+    }
+
+    if !limiter.allow(addr.ip()) {
+        log::debug!("静态服务限速：{} 请求过于频繁", addr.ip());
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, "1")],
+            "Too Many Requests",
+        )
+            .into_response();
+    }
+
+    // 许可在处理完成（响应头返回）时释放
+    let _permit = match &limiter.concurrency {
+        Some(semaphore) => match semaphore.clone().try_acquire_owned() {
+            Ok(permit) => Some(permit),
+            Err(_) => {
+                return (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    [(header::RETRY_AFTER, "1")],
+                    "Server Busy",
+                )
+                    .into_response();
+            }
+        },
+        None => None,
+    };
+
+    next.run(request).await
+}
@@ -2,131 +2,183 @@
 //
 // 子模块：
 // - crud:    CRUD 命令（create/stop/remove/get/get_servers/update）
-// - runtime: start_server 与底层 axum 运行/代理处理
+// - runtime: start_server 与底层 axum 运行/代理处理，运行期间监视根目录是否还在
 // - nginx:   生成等价 nginx 配置
+// - bundle:  服务配置导出 / 导入（JSON 配置包）
+// - limits:  并发请求数与每 IP 限速中间件
 
 use super::ServerConfig;
 use crate::error::AppResult;
-use crate::storage;
+use crate::storage::config::StorageConfig;
+use crate::storage::PersistedStore;
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
-use std::fs;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 
+mod bundle;
 mod crud;
+mod limits;
 mod nginx;
 mod runtime;
 
+pub use bundle::*;
 pub use crud::*;
 pub use nginx::*;
 
 /// 服务配置存储 - 延迟初始化，首次访问时从文件加载
-pub(super) static SERVERS: Lazy<Arc<Mutex<HashMap<String, ServerConfig>>>> =
-    Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
-
-/// 是否已从文件加载
-pub(super) static SERVERS_LOADED: Lazy<Arc<Mutex<bool>>> =
-    Lazy::new(|| Arc::new(Mutex::new(false)));
+pub(super) static SERVERS: Lazy<PersistedStore<ServerConfig>> = Lazy::new(|| {
+    PersistedStore::new(
+        "servers",
+        "服务配置",
+        StorageConfig::server_configs_file,
+        |s: &ServerConfig| s.id.clone(),
+    )
+    .on_load(|s| {
+        // 重启后默认停止
+        s.status = "stopped".to_string();
+        s.error = None;
+    })
+});
 
 /// 服务控制器
 pub(super) static SERVER_CONTROLLERS: Lazy<Arc<Mutex<HashMap<String, Arc<ServerController>>>>> =
     Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
 
 /// 确保服务配置已从文件加载
-pub(super) async fn ensure_servers_loaded() {
-    let mut loaded = SERVERS_LOADED.lock().await;
-    if !*loaded {
-        match load_servers_from_file() {
-            Ok(servers) => {
-                let mut servers_map = SERVERS.lock().await;
-                *servers_map = servers;
-                *loaded = true; // 只有成功加载才设置为 true
-            }
-            Err(e) => {
-                log::warn!("加载服务配置失败，将在下次重试: {}", e);
-                // 不设置 loaded = true，允许下次重试
-            }
-        }
-    }
+pub(crate) async fn ensure_servers_loaded() {
+    SERVERS.ensure_loaded().await;
 }
 
-/// 从文件加载服务配置
-fn load_servers_from_file() -> AppResult<HashMap<String, ServerConfig>> {
-    let config = storage::get_storage_config()?;
-    let path = config.server_configs_file();
-
-    log::info!("加载服务配置: {:?}", path);
-
-    if !path.exists() {
-        log::info!("服务配置文件不存在，返回空列表");
-        return Ok(HashMap::new());
-    }
-
-    let content = fs::read_to_string(&path)
-        .map_err(|e| crate::error::AppError::from(format!("读取服务配置失败: {}", e)))?;
-
-    // 直接解析为服务配置数组
-    let servers_arr: Vec<ServerConfig> = match serde_json::from_str(&content) {
-        Ok(arr) => arr,
-        Err(e) => {
-            log::error!(
-                "解析服务配置 JSON 失败: {}，内容: {}",
-                e,
-                &content[..content.len().min(200)]
-            );
-            Vec::new()
-        }
-    };
-
-    let mut servers = HashMap::new();
-    for mut server in servers_arr {
-        // 重启后默认停止
-        server.status = "stopped".to_string();
-        log::info!("加载服务: {} (端口 {})", server.name, server.port);
-        servers.insert(server.id.clone(), server);
-    }
-
-    log::info!("共加载 {} 个服务配置", servers.len());
-    Ok(servers)
+/// 丢弃已加载的服务配置，切换配置档案后从新位置读取
+pub(crate) async fn unload_servers() {
+    SERVERS.unload().await;
 }
 
-/// 保存服务配置到文件
-pub(super) async fn save_servers_to_file() -> AppResult<()> {
-    let config = storage::get_storage_config()?;
+/// 正在运行的服务数（托盘角标用）
+pub(crate) async fn running_server_count() -> usize {
+    SERVER_CONTROLLERS.lock().await.len()
+}
 
-    // 确保数据目录存在
-    config.ensure_dirs()?;
+/// 一个服务的指标（供 metrics 端点使用）
+pub(crate) struct ServerMetrics {
+    pub id: String,
+    pub name: String,
+    pub port: u16,
+    pub running: bool,
+    pub active_requests: usize,
+    /// 按状态码类别 1xx..5xx 的请求数
+    pub responses: [u64; 5],
+    pub bytes_sent: u64,
+}
 
+/// 所有服务的指标，未运行的服务计数为 0
+pub(crate) async fn server_metrics() -> Vec<ServerMetrics> {
+    ensure_servers_loaded().await;
+    let controllers: HashMap<String, Arc<ServerController>> =
+        SERVER_CONTROLLERS.lock().await.clone();
     let servers = SERVERS.lock().await;
+    servers
+        .values()
+        .map(|server| {
+            let controller = controllers.get(&server.id);
+            ServerMetrics {
+                id: server.id.clone(),
+                name: server.name.clone(),
+                port: server.port,
+                running: controller.is_some(),
+                active_requests: controller
+                    .map(|c| c.active.load(Ordering::SeqCst))
+                    .unwrap_or(0),
+                responses: controller
+                    .map(|c| c.responses.each_ref().map(|n| n.load(Ordering::Relaxed)))
+                    .unwrap_or_default(),
+                bytes_sent: controller
+                    .map(|c| c.bytes_sent.load(Ordering::Relaxed))
+                    .unwrap_or(0),
+            }
+        })
+        .collect()
+}
 
-    // 直接序列化（serde 会自动用 camelCase）
-    let servers_data: Vec<&ServerConfig> = servers.values().collect();
-
-    let content = serde_json::to_string(&servers_data)
-        .map_err(|e| crate::error::AppError::from(format!("序列化服务配置失败: {}", e)))?;
-
-    let path = config.server_configs_file();
-    log::info!("保存服务配置到: {:?}", path);
-
-    fs::write(&path, content)
-        .map_err(|e| crate::error::AppError::from(format!("写入服务配置失败: {}", e)))?;
+/// 运行中且无请求已超过 min_idle 的服务 (id, 名称)
+pub(crate) async fn idle_servers(min_idle: Duration) -> Vec<(String, String)> {
+    let idle: Vec<String> = SERVER_CONTROLLERS
+        .lock()
+        .await
+        .iter()
+        .filter(|(_, c)| c.idle_for().is_some_and(|d| d >= min_idle))
+        .map(|(id, _)| id.clone())
+        .collect();
+    let servers = SERVERS.lock().await;
+    idle.into_iter()
+        .filter_map(|id| servers.get(&id).map(|s| (id, s.name.clone())))
+        .collect()
+}
 
-    log::info!("服务配置保存成功，共 {} 个服务", servers.len());
-    Ok(())
+/// 保存服务配置到文件（防抖合并写盘）
+pub(super) async fn save_servers_to_file() -> AppResult<()> {
+    SERVERS.save().await
 }
 
 /// 服务控制器
 pub(super) struct ServerController {
     stop: AtomicBool,
+    /// 处理中的请求数
+    active: AtomicUsize,
+    /// 按状态码类别（1xx..5xx）统计的已完成请求数
+    responses: [AtomicU64; 5],
+    /// 响应体字节数（按 Content-Length 统计）
+    bytes_sent: AtomicU64,
+    /// 最近一次请求开始或结束的时间，用于空闲自动停止
+    last_active: std::sync::Mutex<Instant>,
 }
 
 impl ServerController {
     pub(super) fn new() -> Self {
         Self {
             stop: AtomicBool::new(false),
+            active: AtomicUsize::new(0),
+            responses: Default::default(),
+            bytes_sent: AtomicU64::new(0),
+            last_active: std::sync::Mutex::new(Instant::now()),
+        }
+    }
+
+    pub(super) fn begin_request(&self) {
+        self.active.fetch_add(1, Ordering::SeqCst);
+        self.touch();
+    }
+
+    pub(super) fn end_request(&self) {
+        self.active.fetch_sub(1, Ordering::SeqCst);
+        self.touch();
+    }
+
+    /// 记录一次完成的响应
+    pub(super) fn record_response(&self, status: u16, bytes: u64) {
+        let class = (status / 100).clamp(1, 5) as usize - 1;
+        self.responses[class].fetch_add(1, Ordering::Relaxed);
+        self.bytes_sent.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    fn touch(&self) {
+        *self.last_active.lock().unwrap_or_else(|e| e.into_inner()) = Instant::now();
+    }
+
+    /// 没有处理中的请求时返回已空闲的时长
+    fn idle_for(&self) -> Option<Duration> {
+        if self.active.load(Ordering::SeqCst) > 0 {
+            return None;
         }
+        Some(
+            self.last_active
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .elapsed(),
+        )
     }
 
     pub(super) fn is_stopped(&self) -> bool {
@@ -1,8 +1,9 @@
 // 生成等价 nginx 配置
 
-use super::super::{NginxConfigOptions, ProxyConfig};
+use super::super::{NginxConfigOptions, ProxyConfig, ServerConfig};
 use super::{ensure_servers_loaded, SERVERS};
 use crate::error::AppResult;
+use std::collections::HashMap;
 
 fn escape_nginx_string(value: &str) -> String {
     value.replace('\\', "\\\\").replace('"', "\\\"")
@@ -30,9 +31,34 @@ fn nginx_index_fallback(prefix: &str, index_page: Option<&str>) -> String {
     }
 }
 
-fn nginx_proxy_target(target: &str) -> String {
-    let trimmed = target.trim().trim_end_matches('/');
-    format!("{}/", trimmed)
+/// proxy_pass 的地址：带 URI 时 nginx 用它替换 location 匹配的部分，
+/// 去前缀时替换为追加前缀，保留前缀时替换为追加前缀 + 原前缀
+fn nginx_proxy_target(proxy: &ProxyConfig) -> String {
+    let trimmed = proxy.target.trim().trim_end_matches('/');
+    let mut segments: Vec<&str> = Vec::new();
+    if let Some(add) = proxy
+        .add_prefix
+        .as_deref()
+        .map(|p| p.trim_matches('/'))
+        .filter(|p| !p.is_empty())
+    {
+        segments.push(add);
+    }
+    if !proxy.strip_prefix {
+        segments.push(proxy.prefix.trim_matches('/'));
+    }
+    if segments.is_empty() {
+        format!("{}/", trimmed)
+    } else {
+        format!("{}/{}/", trimmed, segments.join("/"))
+    }
+}
+
+/// 按名称排序，生成的配置稳定
+fn sorted_headers(headers: &HashMap<String, String>) -> Vec<(&String, &String)> {
+    let mut headers: Vec<_> = headers.iter().collect();
+    headers.sort();
+    headers
 }
 
 fn push_nginx_line(out: &mut String, indent: usize, line: &str) {
@@ -56,16 +82,7 @@ fn push_cors_headers(out: &mut String, indent: &str) {
     out.push_str(&format!("{indent}}}\n"));
 }
 
-fn push_proxy_location(out: &mut String, proxy: &ProxyConfig, cors: bool) {
-    let clean_prefix = proxy.prefix.trim_matches('/');
-    if clean_prefix.is_empty() || proxy.target.trim().is_empty() {
-        return;
-    }
-
-    let location = format!("/{}", clean_prefix);
-    let target = nginx_proxy_target(&proxy.target);
-    out.push('\n');
-    push_nginx_line(out, 4, &format!("location = {location} {{"));
+fn push_proxy_directives(out: &mut String, proxy: &ProxyConfig, target: &str, cors: bool) {
     push_nginx_line(out, 8, &format!("proxy_pass {target};"));
     push_nginx_line(out, 8, "proxy_http_version 1.1;");
     push_nginx_line(out, 8, "proxy_set_header Host $host;");
@@ -76,26 +93,49 @@ fn push_proxy_location(out: &mut String, proxy: &ProxyConfig, cors: bool) {
         "proxy_set_header X-Forwarded-For $proxy_add_x_forwarded_for;",
     );
     push_nginx_line(out, 8, "proxy_set_header X-Forwarded-Proto $scheme;");
+    for (name, value) in sorted_headers(&proxy.request_headers) {
+        push_nginx_line(
+            out,
+            8,
+            &format!(
+                "proxy_set_header {} \"{}\";",
+                name,
+                escape_nginx_string(value)
+            ),
+        );
+    }
     if cors {
         push_cors_headers(out, "        ");
     }
+    for (name, value) in sorted_headers(&proxy.response_headers) {
+        push_nginx_line(
+            out,
+            8,
+            &format!(
+                "add_header {} \"{}\" always;",
+                name,
+                escape_nginx_string(value)
+            ),
+        );
+    }
+}
+
+fn push_proxy_location(out: &mut String, proxy: &ProxyConfig, cors: bool) {
+    let clean_prefix = proxy.prefix.trim_matches('/');
+    if clean_prefix.is_empty() || proxy.target.trim().is_empty() {
+        return;
+    }
+
+    let location = format!("/{}", clean_prefix);
+    let target = nginx_proxy_target(proxy);
+    out.push('\n');
+    push_nginx_line(out, 4, &format!("location = {location} {{"));
+    push_proxy_directives(out, proxy, &target, cors);
     push_nginx_line(out, 4, "}");
 
     out.push('\n');
     push_nginx_line(out, 4, &format!("location {location}/ {{"));
-    push_nginx_line(out, 8, &format!("proxy_pass {target};"));
-    push_nginx_line(out, 8, "proxy_http_version 1.1;");
-    push_nginx_line(out, 8, "proxy_set_header Host $host;");
-    push_nginx_line(out, 8, "proxy_set_header X-Real-IP $remote_addr;");
-    push_nginx_line(
-        out,
-        8,
-        "proxy_set_header X-Forwarded-For $proxy_add_x_forwarded_for;",
-    );
-    push_nginx_line(out, 8, "proxy_set_header X-Forwarded-Proto $scheme;");
-    if cors {
-        push_cors_headers(out, "        ");
-    }
+    push_proxy_directives(out, proxy, &target, cors);
     push_nginx_line(out, 4, "}");
 }
 
@@ -200,7 +240,12 @@ pub async fn generate_nginx_config(server_id: String) -> AppResult<String> {
     }
     .ok_or_else(|| crate::error::AppError::from(format!("服务不存在: {}", server_id)))?;
 
-    Ok(build_nginx_config(NginxConfigOptions {
+    Ok(server_nginx_config(server))
+}
+
+/// 按服务配置生成 nginx 配置
+pub(super) fn server_nginx_config(server: ServerConfig) -> String {
+    build_nginx_config(NginxConfigOptions {
         service_name: server.name,
         listen_port: server.port,
         root_dir: server.root_dir,
@@ -211,5 +256,5 @@ pub async fn generate_nginx_config(server_id: String) -> AppResult<String> {
         proxies: Some(server.proxies),
         access_log: Some(true),
         error_log: Some(true),
-    }))
+    })
 }
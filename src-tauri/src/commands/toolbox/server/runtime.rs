@@ -1,18 +1,18 @@
-// 静态服务运行时：run_server / proxy_handler / 解码与 hop-by-hop 处理
+// 静态服务运行时：run_server / proxy_handler / 解码与 hop-by-hop 处理 / 根目录监视
 
 use crate::error::AppResult;
-use std::net::SocketAddr;
 use std::sync::Arc;
+use tauri::{AppHandle, Emitter};
 
 use axum::{
     body::Body,
-    extract::{Path, State},
+    extract::{ConnectInfo, Path, State},
     http::{header, HeaderMap, Method, Request, StatusCode},
     response::IntoResponse,
     routing::any,
-    Router,
+    Extension, Router,
 };
-use socket2::{Domain, Socket, Type};
+use tower::Layer;
 use tower_http::{
     compression::CompressionLayer,
     cors::{Any, CorsLayer},
@@ -20,18 +20,119 @@ use tower_http::{
 };
 
 use super::super::ServerConfig;
-use super::ServerController;
+use super::limits::{self, Limiter};
+use super::{save_servers_to_file, ServerController, SERVERS, SERVER_CONTROLLERS};
+
+/// 根目录检查间隔
+const ROOT_CHECK_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_secs(2);
+
+/// 把服务标记为错误状态并记录原因
+pub(super) async fn mark_server_error(server_id: &str, reason: &str) {
+    {
+        let mut servers = SERVERS.lock().await;
+        if let Some(s) = servers.get_mut(server_id) {
+            s.status = "error".to_string();
+            s.error = Some(reason.to_string());
+        }
+    }
+    if let Err(e) = save_servers_to_file().await {
+        log::error!("保存服务配置失败: {}", e);
+    }
+}
+
+/// 运行期间轮询根目录：被删除或卸载时停止服务、标记错误并通知，
+/// 否则请求只会静默返回 404
+pub(super) fn watch_root_dir(
+    app: AppHandle,
+    server_id: String,
+    root_dir: String,
+    controller: Arc<ServerController>,
+) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(ROOT_CHECK_INTERVAL).await;
+            if controller.is_stopped() {
+                return;
+            }
+            if std::path::Path::new(&root_dir).is_dir() {
+                continue;
+            }
+
+            let reason = format!("根目录已不存在（被删除或卸载）: {}", root_dir);
+            log::warn!("静态服务 {} 停止: {}", server_id, reason);
+            controller.stop();
+            SERVER_CONTROLLERS.lock().await.remove(&server_id);
+            mark_server_error(&server_id, &reason).await;
+
+            let name = SERVERS
+                .lock()
+                .await
+                .get(&server_id)
+                .map(|s| s.name.clone())
+                .unwrap_or_else(|| server_id.clone());
+            crate::commands::settings::push_notification(
+                &app,
+                "server",
+                "error",
+                &format!("静态服务 {} 已停止", name),
+                &reason,
+            )
+            .await;
+            let _ = app.emit(
+                "server-error",
+                serde_json::json!({ "serverId": server_id, "error": reason }),
+            );
+            return;
+        }
+    });
+}
 
 /// 代理状态
 #[derive(Clone)]
 struct ProxyState {
     target: String,
+    /// 匹配的前缀（不含首尾 /）
+    prefix: String,
+    strip_prefix: bool,
+    /// 追加的前缀（不含首尾 /）
+    add_prefix: String,
+    request_headers: Vec<(String, String)>,
+    response_headers: Vec<(String, String)>,
+}
+
+/// 处理中的请求；客户端中途断开、处理被取消时也会在 drop 时计数减一
+struct ActiveRequest(Arc<ServerController>);
+
+impl Drop for ActiveRequest {
+    fn drop(&mut self) {
+        self.0.end_request();
+    }
+}
+
+/// 记录请求活动，供空闲自动停止判断
+async fn track_activity(
+    State(controller): State<Arc<ServerController>>,
+    request: Request<Body>,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    controller.begin_request();
+    let _active = ActiveRequest(controller.clone());
+    let response = next.run(request).await;
+    let bytes = response
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+    controller.record_response(response.status().as_u16(), bytes);
+    response
 }
 
 /// 运行服务
 pub(super) async fn run_server(
     _server_id: &str,
     config: ServerConfig,
+    std_listener: std::net::TcpListener,
     controller: Arc<ServerController>,
 ) -> AppResult<()> {
     // 创建静态文件服务
@@ -50,13 +151,31 @@ pub(super) async fn run_server(
     // 添加多个 API 代理规则
     // API 代理同时在根路径和 URL 前缀路径下生效，以便前端可以使用相对路径
     for proxy in &config.proxies {
+        // 确保前缀格式正确（以 / 开头，不以 / 结尾）
+        let clean_prefix = proxy.prefix.trim_matches('/');
+
         let proxy_state = ProxyState {
             target: proxy.target.clone(),
+            prefix: clean_prefix.to_string(),
+            strip_prefix: proxy.strip_prefix,
+            add_prefix: proxy
+                .add_prefix
+                .as_deref()
+                .unwrap_or_default()
+                .trim_matches('/')
+                .to_string(),
+            request_headers: proxy
+                .request_headers
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            response_headers: proxy
+                .response_headers
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
         };
 
-        // 确保前缀格式正确（以 / 开头，不以 / 结尾）
-        let clean_prefix = proxy.prefix.trim_matches('/');
-
         // 1. 首先在根路径注册代理（全局生效）
         let root_route_path = if clean_prefix.is_empty() {
             "/*path".to_string()
@@ -146,8 +265,20 @@ pub(super) async fn run_server(
         app = app.layer(CompressionLayer::new());
     }
 
-    // 绑定地址
-    let addr = SocketAddr::from(([0, 0, 0, 0], config.port));
+    // 并发与限速放在最外层，被拒绝的请求不进入后续处理
+    let limiter = Limiter::from_config(&config);
+    if let Some(limiter) = &limiter {
+        app = app.layer(axum::middleware::from_fn_with_state(
+            limiter.clone(),
+            limits::limit,
+        ));
+    }
+
+    // 被拒绝的请求也算活动，空闲计时放在限速之外
+    app = app.layer(axum::middleware::from_fn_with_state(
+        controller.clone(),
+        track_activity,
+    ));
 
     log::info!(
         "静态服务启动: http://127.0.0.1:{}{}",
@@ -160,42 +291,19 @@ pub(super) async fn run_server(
     );
     log::info!("根目录: {}", config.root_dir);
 
-    // 使用 socket2 创建支持 SO_REUSEADDR 的 socket
-    let socket = Socket::new(Domain::IPV4, Type::STREAM, None)
-        .map_err(|e| crate::error::AppError::from(format!("创建 socket 失败: {}", e)))?;
-
-    // 设置 SO_REUSEADDR，允许在 TIME_WAIT 状态时复用端口
-    socket
-        .set_reuse_address(true)
-        .map_err(|e| crate::error::AppError::from(format!("设置 SO_REUSEADDR 失败: {}", e)))?;
-
-    // 设置 SO_LINGER 为 0，使 socket 关闭时立即释放端口（发送 RST 而非 FIN）
-    socket
-        .set_linger(Some(std::time::Duration::from_secs(0)))
-        .map_err(|e| crate::error::AppError::from(format!("设置 SO_LINGER 失败: {}", e)))?;
-
-    // 设置非阻塞模式
-    socket
-        .set_nonblocking(true)
-        .map_err(|e| crate::error::AppError::from(format!("设置非阻塞模式失败: {}", e)))?;
-
-    // 绑定地址
-    socket
-        .bind(&addr.into())
-        .map_err(|e| crate::error::AppError::from(format!("绑定端口失败: {}", e)))?;
-
-    // 监听
-    socket
-        .listen(1024)
-        .map_err(|e| crate::error::AppError::from(format!("监听端口失败: {}", e)))?;
-
-    // 转换为 tokio TcpListener
-    let std_listener: std::net::TcpListener = socket.into();
+    // 转换为 tokio TcpListener（端口已在 start_server 中绑定）
     let listener = tokio::net::TcpListener::from_std(std_listener)
         .map_err(|e| crate::error::AppError::from(format!("创建 TcpListener 失败: {}", e)))?;
 
     // 使用 axum::serve 并添加 graceful shutdown
-    let server = axum::serve(listener, app);
+    // 每个连接挂上客户端地址（限速按 IP 计算）与连接许可，许可随连接关闭释放
+    let make_service = tower::service_fn(move |stream: axum::serve::IncomingStream<'_>| {
+        let remote = stream.remote_addr();
+        let slot = limiter.as_ref().and_then(|l| l.acquire_connection());
+        let service = Extension(ConnectInfo(remote)).layer(Extension(slot).layer(app.clone()));
+        async move { Ok::<_, std::convert::Infallible>(service) }
+    });
+    let server = axum::serve(listener, make_service);
 
     // 创建 shutdown 信号
     let ctrl = controller.clone();
@@ -230,17 +338,13 @@ async fn proxy_handler(
     let uri = req.uri().clone();
     let headers = req.headers().clone();
 
-    // 构建目标 URL
+    // 构建目标 URL：追加前缀 + （不去前缀时）匹配的前缀 + 剩余路径
     let query = uri.query().map(|q| format!("?{}", q)).unwrap_or_default();
-    let target_path = if path.is_empty() {
-        if query.is_empty() {
-            "/".to_string()
-        } else {
-            format!("/{}", query.trim_start_matches('?'))
-        }
-    } else {
-        format!("/{}{}", path, query)
-    };
+    let target_path = format!(
+        "/{}{}",
+        rewrite_path(&state, path.trim_start_matches('/')),
+        query
+    );
 
     // 解析目标地址 (格式: http://host:port 或 http://host:port/path)
     let target = state.target.trim_end_matches('/');
@@ -291,15 +395,26 @@ async fn proxy_handler(
     let mut raw_request = format!("{} {} HTTP/1.1\r\n", method, full_path);
     raw_request.push_str(&format!("Host: {}\r\n", target_addr));
 
-    // 复制请求头（跳过 host、content-length 和 hop-by-hop 头）
+    // 复制请求头（跳过 host、content-length、hop-by-hop 头和要覆盖的头）
     for (name, value) in headers.iter() {
         let name_str = name.as_str().to_lowercase();
-        if name_str != "host" && name_str != "content-length" && !is_hop_by_hop_header(&name_str) {
+        let overridden = state
+            .request_headers
+            .iter()
+            .any(|(k, _)| k.eq_ignore_ascii_case(&name_str));
+        if name_str != "host"
+            && name_str != "content-length"
+            && !is_hop_by_hop_header(&name_str)
+            && !overridden
+        {
             if let Ok(v) = value.to_str() {
                 raw_request.push_str(&format!("{}: {}\r\n", name, v));
             }
         }
     }
+    for (name, value) in &state.request_headers {
+        raw_request.push_str(&format!("{}: {}\r\n", name, value));
+    }
 
     // 设置 Content-Length（POST/PUT/PATCH 必须有）
     if !body_bytes.is_empty()
@@ -406,12 +521,37 @@ async fn proxy_handler(
         header::HeaderValue::from_static("*"),
     );
 
+    // 注入配置的响应头（已在保存时校验）
+    for (name, value) in &state.response_headers {
+        if let (Ok(n), Ok(v)) = (
+            header::HeaderName::from_bytes(name.as_bytes()),
+            header::HeaderValue::from_str(value),
+        ) {
+            response_headers.insert(n, v);
+        }
+    }
+
     // 移除 transfer-encoding 头（因为我们已经解码了 chunked）
     response_headers.remove("transfer-encoding");
 
     (status, response_headers, body).into_response()
 }
 
+/// 按代理规则改写转发路径（不含开头的 /）
+fn rewrite_path(state: &ProxyState, rest: &str) -> String {
+    let mut segments: Vec<&str> = Vec::new();
+    if !state.add_prefix.is_empty() {
+        segments.push(&state.add_prefix);
+    }
+    if !state.strip_prefix && !state.prefix.is_empty() {
+        segments.push(&state.prefix);
+    }
+    if !rest.is_empty() {
+        segments.push(rest);
+    }
+    segments.join("/")
+}
+
 /// 解码 chunked 传输编码
 fn decode_chunked(data: &[u8]) -> Vec<u8> {
     let mut result = Vec::new();
@@ -0,0 +1,646 @@
+// SSH 密钥管理 - 新机器上配置 Git / 服务器访问的常用操作
+//
+// - 列出 ~/.ssh 下的密钥（类型、指纹、注释，以及 ssh config 中哪些 Host 在用）
+// - ssh-keygen 生成 ed25519 密钥、读取公钥供前端复制；口令通过 SSH_ASKPASS 由本程序回传，不出现在命令行参数里
+// - ssh -T 测试认证（GitHub / GitLab / Gitee 认证成功时退出码非 0，按欢迎语判断）
+// - 编辑 ~/.ssh/config 的 Host 块，把密钥映射到远程主机；写入前备份为 config.bak
+//
+// 依赖系统自带的 OpenSSH 客户端（ssh、ssh-keygen）。
+
+use super::{SshAuthTestResult, SshHostEntry, SshHostEntryInput, SshKeyInfo};
+use crate::error::{AppError, AppResult};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// ssh -T 的最长等待时间
+const AUTH_TEST_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// upsert 时由本模块管理的配置项，其余配置项保留
+const MANAGED_OPTIONS: &[&str] = &["hostname", "user", "port", "identityfile", "identitiesonly"];
+
+/// 以 askpass 身份启动时的环境变量：标记与口令
+const ASKPASS_FLAG_ENV: &str = "CODESHELF_SSH_ASKPASS";
+const ASKPASS_SECRET_ENV: &str = "CODESHELF_SSH_ASKPASS_SECRET";
+
+/// ~/.ssh 下不是密钥的常见文件
+const NON_KEY_FILES: &[&str] = &[
+    "config",
+    "config.bak",
+    "known_hosts",
+    "known_hosts.old",
+    "authorized_keys",
+    "environment",
+];
+
+fn ssh_dir() -> AppResult<PathBuf> {
+    dirs::home_dir()
+        .map(|home| home.join(".ssh"))
+        .ok_or_else(|| AppError::internal("无法获取用户主目录"))
+}
+
+fn ssh_config_path() -> AppResult<PathBuf> {
+    Ok(ssh_dir()?.join("config"))
+}
+
+/// 密钥名只允许是 ~/.ssh 下的文件名
+fn key_path(name: &str) -> AppResult<PathBuf> {
+    let name = name.trim();
+    if name.is_empty()
+        || name.starts_with('.')
+        || name.contains(['/', '\\'])
+        || name.contains(char::is_control)
+        || name.ends_with(".pub")
+    {
+        return Err(AppError::invalid(format!("无效的密钥名: {}", name)));
+    }
+    Ok(ssh_dir()?.join(name))
+}
+
+/// 写入 ssh config 的值不能换行，否则可以注入额外的配置项
+fn ensure_single_line(label: &str, value: &str) -> AppResult<()> {
+    if value.contains(char::is_control) {
+        return Err(AppError::invalid(format!(
+            "{}不能包含换行或控制字符",
+            label
+        )));
+    }
+    Ok(())
+}
+
+/// ssh-keygen 通过 SSH_ASKPASS 调用本程序时，输出口令后退出；普通启动返回 false
+pub fn handle_askpass() -> bool {
+    if std::env::var_os(ASKPASS_FLAG_ENV).is_none() {
+        return false;
+    }
+    println!("{}", std::env::var(ASKPASS_SECRET_ENV).unwrap_or_default());
+    std::process::exit(0);
+}
+
+/// 私钥对应的 .pub 路径
+fn public_key_of(private_key: &Path) -> PathBuf {
+    let mut name = private_key.as_os_str().to_os_string();
+    name.push(".pub");
+    PathBuf::from(name)
+}
+
+fn command(program: &str) -> Command {
+    #[allow(unused_mut)]
+    let mut cmd = Command::new(program);
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+    cmd
+}
+
+/// 展开 IdentityFile 中的 ~ 与 %d（主目录）
+fn expand_home(value: &str) -> PathBuf {
+    let home = dirs::home_dir().unwrap_or_default();
+    let value = value
+        .trim_matches('"')
+        .replace("%d", &home.to_string_lossy());
+    match value
+        .strip_prefix("~/")
+        .or_else(|| value.strip_prefix("~\\"))
+    {
+        Some(rest) => home.join(rest),
+        None => PathBuf::from(value),
+    }
+}
+
+/// 配置行拆成 (小写关键字, 值)，支持 "Key value" 与 "Key=value"
+fn split_option(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let idx = line.find(|c: char| c.is_whitespace() || c == '=')?;
+    let key = line[..idx].to_lowercase();
+    let value = line[idx..]
+        .trim_start_matches(|c: char| c.is_whitespace() || c == '=')
+        .trim()
+        .to_string();
+    Some((key, value))
+}
+
+/// Host / Match 行开始一个新块
+fn is_block_start(line: &str) -> bool {
+    matches!(
+        split_option(line).map(|(k, _)| k).as_deref(),
+        Some("host") | Some("match")
+    )
+}
+
+/// 解析 ssh config 中的 Host 块（跳过通配符 Host 与 Match 块）
+fn parse_host_entries(content: &str) -> Vec<SshHostEntry> {
+    let mut entries: Vec<SshHostEntry> = Vec::new();
+    // 当前块对应的 entries 下标范围
+    let mut current: Vec<usize> = Vec::new();
+    for line in content.lines() {
+        let Some((key, value)) = split_option(line) else {
+            continue;
+        };
+        match key.as_str() {
+            "host" => {
+                current.clear();
+                for alias in value.split_whitespace() {
+                    if alias.contains(['*', '?', '!']) {
+                        continue;
+                    }
+                    current.push(entries.len());
+                    entries.push(SshHostEntry {
+                        alias: alias.to_string(),
+                        host_name: None,
+                        user: None,
+                        port: None,
+                        identity_files: Vec::new(),
+                    });
+                }
+            }
+            "match" => current.clear(),
+            _ => {
+                for &i in &current {
+                    let entry = &mut entries[i];
+                    match key.as_str() {
+                        "hostname" => entry.host_name = Some(value.clone()),
+                        "user" => entry.user = Some(value.clone()),
+                        "port" => entry.port = value.parse().ok(),
+                        "identityfile" => entry.identity_files.push(value.clone()),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+    entries
+}
+
+fn read_config() -> AppResult<String> {
+    let path = ssh_config_path()?;
+    if !path.exists() {
+        return Ok(String::new());
+    }
+    Ok(std::fs::read_to_string(path)?)
+}
+
+/// 写入 ssh config，先把旧文件备份为 config.bak
+fn write_config(content: &str) -> AppResult<()> {
+    let dir = ssh_dir()?;
+    ensure_ssh_dir(&dir)?;
+    let path = dir.join("config");
+    if path.exists() {
+        std::fs::copy(&path, dir.join("config.bak"))?;
+    }
+    std::fs::write(&path, content)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(())
+}
+
+fn ensure_ssh_dir(dir: &Path) -> AppResult<()> {
+    if !dir.exists() {
+        std::fs::create_dir_all(dir)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))?;
+        }
+    }
+    Ok(())
+}
+
+/// 找到包含 alias 的 Host 块（exact 时要求 Host 行只有这一个别名）：返回 (起始行, 结束行（不含）)
+fn find_host_block(lines: &[&str], alias: &str, exact: bool) -> Option<(usize, usize)> {
+    let start = lines.iter().position(|line| match split_option(line) {
+        Some((k, v)) if k == "host" => {
+            let mut aliases = v.split_whitespace();
+            if exact {
+                aliases.next() == Some(alias) && aliases.next().is_none()
+            } else {
+                aliases.any(|a| a == alias)
+            }
+        }
+        _ => false,
+    })?;
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| is_block_start(line))
+        .map(|i| start + 1 + i)
+        .unwrap_or(lines.len());
+    Some((start, end))
+}
+
+/// 新增或更新 Host 块；已有块中非本模块管理的配置项保留。
+/// 别名位于多别名的 Host 行时先把它移出，再单独写一个块
+fn upsert_host_block(content: &str, input: &SshHostEntryInput, identity_file: &str) -> String {
+    let shared = {
+        let lines: Vec<&str> = content.lines().collect();
+        find_host_block(&lines, &input.alias, true).is_none()
+            && find_host_block(&lines, &input.alias, false).is_some()
+    };
+    let content = if shared {
+        remove_host_block(content, &input.alias).unwrap_or_else(|| content.to_string())
+    } else {
+        content.to_string()
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    let mut block = vec![format!("Host {}", input.alias)];
+    let mut options = Vec::new();
+    if let Some(host_name) = input.host_name.as_deref().filter(|v| !v.trim().is_empty()) {
+        options.push(format!("    HostName {}", host_name.trim()));
+    }
+    if let Some(user) = input.user.as_deref().filter(|v| !v.trim().is_empty()) {
+        options.push(format!("    User {}", user.trim()));
+    }
+    if let Some(port) = input.port.filter(|p| *p != 22) {
+        options.push(format!("    Port {}", port));
+    }
+    options.push(format!("    IdentityFile {}", identity_file));
+    options.push("    IdentitiesOnly yes".to_string());
+
+    let mut out: Vec<String> = Vec::new();
+    match find_host_block(&lines, &input.alias, true) {
+        Some((start, end)) => {
+            let kept = lines[start + 1..end]
+                .iter()
+                .filter(|line| {
+                    !matches!(split_option(line), Some((k, _)) if MANAGED_OPTIONS.contains(&k.as_str()))
+                })
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>();
+            let trailing_blank = kept
+                .iter()
+                .rev()
+                .take_while(|l| l.trim().is_empty())
+                .count();
+            block.extend(options);
+            block.extend(kept[..kept.len() - trailing_blank].iter().cloned());
+
+            out.extend(lines[..start].iter().map(|l| l.to_string()));
+            out.extend(block);
+            if end < lines.len() {
+                out.push(String::new());
+            }
+            out.extend(lines[end..].iter().map(|l| l.to_string()));
+        }
+        None => {
+            block.extend(options);
+            // ssh 按先匹配先生效，放在 "Host *" 默认块之前
+            let wildcard = lines.iter().position(
+                |line| matches!(split_option(line), Some((k, v)) if k == "host" && v.trim() == "*"),
+            );
+            match wildcard {
+                Some(pos) => {
+                    out.extend(lines[..pos].iter().map(|l| l.to_string()));
+                    out.extend(block);
+                    out.push(String::new());
+                    out.extend(lines[pos..].iter().map(|l| l.to_string()));
+                }
+                None => {
+                    out.extend(lines.iter().map(|l| l.to_string()));
+                    if out.last().map(|l| !l.trim().is_empty()).unwrap_or(false) {
+                        out.push(String::new());
+                    }
+                    out.extend(block);
+                }
+            }
+        }
+    }
+    let mut result = out.join("\n");
+    result.push('\n');
+    result
+}
+
+/// 删除 Host 块；Host 行有多个别名时只移除该别名
+fn remove_host_block(content: &str, alias: &str) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let (start, mut end) = find_host_block(&lines, alias, false)?;
+    let host_value = split_option(lines[start])
+        .map(|(_, v)| v)
+        .unwrap_or_default();
+    let aliases: Vec<&str> = host_value
+        .split_whitespace()
+        .filter(|a| *a != alias)
+        .collect();
+
+    let mut out: Vec<String> = lines[..start].iter().map(|l| l.to_string()).collect();
+    if aliases.is_empty() {
+        // 连同块后的空行一起删除
+        while end < lines.len() && lines[end].trim().is_empty() {
+            end += 1;
+        }
+    } else {
+        out.push(format!("Host {}", aliases.join(" ")));
+        out.extend(lines[start + 1..end].iter().map(|l| l.to_string()));
+    }
+    out.extend(lines[end..].iter().map(|l| l.to_string()));
+    let mut result = out.join("\n");
+    result.push('\n');
+    Some(result)
+}
+
+/// ssh-keygen -lf 输出 "256 SHA256:xxx comment (ED25519)"，取指纹
+fn fingerprint(public_key: &Path) -> Option<String> {
+    let output = command("ssh-keygen")
+        .arg("-lf")
+        .arg(public_key)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .nth(1)
+        .map(String::from)
+}
+
+fn is_private_key(path: &Path) -> bool {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| content.lines().next().map(|l| l.to_string()))
+        .map(|first| first.starts_with("-----BEGIN") && first.contains("PRIVATE KEY"))
+        .unwrap_or(false)
+}
+
+fn read_key(private_key: &Path, entries: &[SshHostEntry]) -> SshKeyInfo {
+    let name = private_key
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let public_key = public_key_of(private_key);
+    let public_key = public_key.is_file().then_some(public_key);
+
+    let (key_type, comment) = public_key
+        .as_ref()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .map(|content| {
+            let mut parts = content.trim().splitn(3, ' ');
+            let key_type = parts.next().map(String::from);
+            let comment = parts.nth(1).map(|c| c.trim().to_string());
+            (key_type, comment.filter(|c| !c.is_empty()))
+        })
+        .unwrap_or((None, None));
+
+    let hosts = entries
+        .iter()
+        .filter(|e| {
+            e.identity_files
+                .iter()
+                .any(|f| expand_home(f) == private_key)
+        })
+        .map(|e| e.alias.clone())
+        .collect();
+
+    SshKeyInfo {
+        name,
+        private_key_path: private_key.to_string_lossy().to_string(),
+        public_key_path: public_key.as_ref().map(|p| p.to_string_lossy().to_string()),
+        key_type,
+        fingerprint: public_key.as_deref().and_then(fingerprint),
+        comment,
+        hosts,
+    }
+}
+
+fn list_keys() -> AppResult<Vec<SshKeyInfo>> {
+    let dir = ssh_dir()?;
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let entries = parse_host_entries(&read_config()?);
+    let mut keys = Vec::new();
+    for entry in std::fs::read_dir(&dir)?.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !path.is_file() || name.ends_with(".pub") || NON_KEY_FILES.contains(&name.as_str()) {
+            continue;
+        }
+        if public_key_of(&path).is_file() || is_private_key(&path) {
+            keys.push(read_key(&path, &entries));
+        }
+    }
+    keys.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(keys)
+}
+
+/// 从欢迎语中提取账号：GitHub / Gitee "Hi xxx!"、GitLab "Welcome to GitLab, @xxx!"、
+/// Bitbucket "logged in as xxx"
+fn parse_account(output: &str) -> Option<String> {
+    let re = Regex::new(r"(?:Hi |Welcome to GitLab, @?)([^!\s]+)!|logged in as (\S+)").ok()?;
+    let caps = re.captures(output)?;
+    caps.get(1)
+        .or_else(|| caps.get(2))
+        .map(|m| m.as_str().trim_end_matches('.').to_string())
+}
+
+fn auth_succeeded(exit_ok: bool, output: &str) -> bool {
+    if output.contains("Permission denied") {
+        return false;
+    }
+    exit_ok
+        || output.contains("successfully authenticated")
+        || output.contains("Welcome to GitLab")
+        || output.contains("logged in as")
+}
+
+/// 列出 ~/.ssh 下的密钥
+#[tauri::command]
+#[specta::specta]
+pub async fn list_ssh_keys() -> AppResult<Vec<SshKeyInfo>> {
+    tokio::task::spawn_blocking(list_keys)
+        .await
+        .map_err(|e| AppError::internal(e.to_string()))?
+}
+
+/// 生成 ed25519 密钥；name 默认 id_ed25519，已存在时拒绝覆盖
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_ssh_key(
+    name: Option<String>,
+    comment: Option<String>,
+    passphrase: Option<String>,
+) -> AppResult<SshKeyInfo> {
+    let path = key_path(name.as_deref().unwrap_or("id_ed25519"))?;
+    if path.exists() || public_key_of(&path).exists() {
+        return Err(AppError::invalid(format!(
+            "密钥已存在: {}",
+            path.to_string_lossy()
+        )));
+    }
+    ensure_ssh_dir(path.parent().unwrap_or(Path::new(".")))?;
+
+    let mut cmd = command("ssh-keygen");
+    cmd.args(["-t", "ed25519", "-q"]);
+    // 不指定注释时 ssh-keygen 默认使用 user@host
+    if let Some(comment) = comment.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+        cmd.args(["-C", comment]);
+    }
+    match passphrase.filter(|p| !p.is_empty()) {
+        // 不带 -N 且 stdin 不是终端时，ssh-keygen 通过 SSH_ASKPASS 读取口令（两次确认都由本程序回答）
+        Some(passphrase) => {
+            cmd.env("SSH_ASKPASS", std::env::current_exe()?)
+                .env("SSH_ASKPASS_REQUIRE", "force")
+                .env(ASKPASS_FLAG_ENV, "1")
+                .env(ASKPASS_SECRET_ENV, passphrase);
+            // OpenSSH 8.4 之前没有 SSH_ASKPASS_REQUIRE，需要 DISPLAY 才会使用 askpass
+            if std::env::var_os("DISPLAY").is_none() {
+                cmd.env("DISPLAY", ":0");
+            }
+        }
+        None => {
+            cmd.args(["-N", ""]);
+        }
+    }
+    let output = cmd
+        .arg("-f")
+        .arg(&path)
+        .stdin(std::process::Stdio::null())
+        .output()
+        .map_err(|e| {
+            AppError::other(format!(
+                "无法运行 ssh-keygen（是否已安装 OpenSSH？）: {}",
+                e
+            ))
+        })?;
+    if !output.status.success() {
+        return Err(AppError::other(format!(
+            "生成密钥失败: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let entries = parse_host_entries(&read_config()?);
+    Ok(read_key(&path, &entries))
+}
+
+/// 读取公钥内容（供前端复制）
+#[tauri::command]
+#[specta::specta]
+pub async fn get_ssh_public_key(name: String) -> AppResult<String> {
+    let path = key_path(&name)?;
+    let public_key = public_key_of(&path);
+    if public_key.is_file() {
+        return Ok(std::fs::read_to_string(public_key)?.trim().to_string());
+    }
+    // 没有 .pub 时从私钥导出（有口令的私钥会失败）
+    let output = command("ssh-keygen")
+        .args(["-y", "-P", "", "-f"])
+        .arg(&path)
+        .output()?;
+    if !output.status.success() {
+        return Err(AppError::other(format!(
+            "无法导出公钥: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// ssh -T 测试认证；host 可以是 "git@github.com" 或 ssh config 中的别名，
+/// 指定 key_name 时只使用该密钥
+#[tauri::command]
+#[specta::specta]
+pub async fn test_ssh_auth(host: String, key_name: Option<String>) -> AppResult<SshAuthTestResult> {
+    let host = host.trim().to_string();
+    if host.is_empty() || host.starts_with('-') {
+        return Err(AppError::invalid("主机地址不能为空"));
+    }
+    let mut cmd = tokio::process::Command::new("ssh");
+    cmd.args([
+        "-T",
+        "-o",
+        "BatchMode=yes",
+        "-o",
+        "ConnectTimeout=10",
+        "-o",
+        "StrictHostKeyChecking=accept-new",
+    ]);
+    if let Some(name) = key_name.as_deref().filter(|n| !n.trim().is_empty()) {
+        cmd.arg("-i")
+            .arg(key_path(name)?)
+            .args(["-o", "IdentitiesOnly=yes"]);
+    }
+    cmd.arg(&host)
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true);
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+
+    let started = Instant::now();
+    let output = tokio::time::timeout(AUTH_TEST_TIMEOUT, cmd.output())
+        .await
+        .map_err(|_| AppError::other(format!("连接 {} 超时", host)))?
+        .map_err(|e| AppError::other(format!("无法运行 ssh（是否已安装 OpenSSH？）: {}", e)))?;
+
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+    .trim()
+    .to_string();
+    let success = auth_succeeded(output.status.success(), &text);
+    Ok(SshAuthTestResult {
+        success,
+        account: if success { parse_account(&text) } else { None },
+        output: text,
+        duration_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+/// ssh config 中的 Host 列表（含 HostName / User / IdentityFile）
+#[tauri::command]
+#[specta::specta]
+pub async fn list_ssh_host_entries() -> AppResult<Vec<SshHostEntry>> {
+    Ok(parse_host_entries(&read_config()?))
+}
+
+/// 把密钥映射到远程主机：新增或更新 ssh config 中的 Host 块
+#[tauri::command]
+#[specta::specta]
+pub async fn save_ssh_host_entry(input: SshHostEntryInput) -> AppResult<SshHostEntry> {
+    let alias = input.alias.trim();
+    if alias.is_empty() || alias.contains(char::is_whitespace) || alias.contains(['*', '?', '!']) {
+        return Err(AppError::invalid(
+            "Host 别名不能为空，且不能包含空格或通配符",
+        ));
+    }
+    ensure_single_line("HostName", input.host_name.as_deref().unwrap_or(""))?;
+    ensure_single_line("User", input.user.as_deref().unwrap_or(""))?;
+    ensure_single_line("密钥名", &input.key_name)?;
+    let key = key_path(&input.key_name)?;
+    if !key.is_file() {
+        return Err(AppError::invalid(format!("密钥不存在: {}", input.key_name)));
+    }
+    let input = SshHostEntryInput {
+        alias: alias.to_string(),
+        ..input
+    };
+    let identity_file = format!("~/.ssh/{}", input.key_name.trim());
+    let content = upsert_host_block(&read_config()?, &input, &identity_file);
+    write_config(&content)?;
+
+    parse_host_entries(&content)
+        .into_iter()
+        .find(|e| e.alias == input.alias)
+        .ok_or_else(|| AppError::internal("写入 ssh config 后未找到 Host"))
+}
+
+/// 从 ssh config 中删除 Host
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_ssh_host_entry(alias: String) -> AppResult<()> {
+    let content = read_config()?;
+    let updated = remove_host_block(&content, alias.trim())
+        .ok_or_else(|| AppError::invalid(format!("ssh config 中没有 Host {}", alias)))?;
+    write_config(&updated)
+}
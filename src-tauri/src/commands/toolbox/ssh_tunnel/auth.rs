@@ -63,6 +63,43 @@ pub(super) fn list_host_aliases_from_config() -> Vec<String> {
 /// 连接 SSH 并完成认证，返回 client handle
 pub(super) async fn connect_and_authenticate(
     tunnel: &SshTunnel,
+) -> AppResult<client::Handle<SshClient>> {
+    connect_ssh(
+        &tunnel.ssh_host,
+        tunnel.ssh_port,
+        &tunnel.ssh_user,
+        &tunnel.auth,
+    )
+    .await
+}
+
+/// 按主机 / 端口 / 用户与认证方式连接 SSH（隧道用，不校验 host key）；
+/// SshConfig 认证时三者从 ~/.ssh/config 读取
+pub(super) async fn connect_ssh(
+    ssh_host: &str,
+    ssh_port: u16,
+    ssh_user: &str,
+    auth: &SshAuthMethod,
+) -> AppResult<client::Handle<SshClient>> {
+    connect_ssh_with(ssh_host, ssh_port, ssh_user, auth, false).await
+}
+
+/// 同 connect_ssh，但要求服务器 host key 已记录在 ~/.ssh/known_hosts 中且一致（部署用）
+pub(crate) async fn connect_ssh_verified(
+    ssh_host: &str,
+    ssh_port: u16,
+    ssh_user: &str,
+    auth: &SshAuthMethod,
+) -> AppResult<client::Handle<SshClient>> {
+    connect_ssh_with(ssh_host, ssh_port, ssh_user, auth, true).await
+}
+
+async fn connect_ssh_with(
+    ssh_host: &str,
+    ssh_port: u16,
+    ssh_user: &str,
+    auth: &SshAuthMethod,
+    verify_host_key: bool,
 ) -> AppResult<client::Handle<SshClient>> {
     let config = Arc::new(client::Config {
         // None = 不做 inactivity 检测；保活由 keepalive_interval 负责。
@@ -75,14 +112,9 @@ pub(super) async fn connect_and_authenticate(
         ..<_>::default()
     });
 
-    let (effective_user, effective_host, effective_port, identity_files) = match &tunnel.auth {
+    let (effective_user, effective_host, effective_port, identity_files) = match auth {
         SshAuthMethod::SshConfig { host_alias } => resolve_ssh_config(host_alias)?,
-        _ => (
-            tunnel.ssh_user.clone(),
-            tunnel.ssh_host.clone(),
-            tunnel.ssh_port,
-            vec![],
-        ),
+        _ => (ssh_user.to_string(), ssh_host.to_string(), ssh_port, vec![]),
     };
 
     if effective_user.is_empty() {
@@ -101,11 +133,26 @@ pub(super) async fn connect_and_authenticate(
         effective_port
     );
 
-    let mut session = client::connect(config, (effective_host.as_str(), effective_port), SshClient)
+    let handler = SshClient {
+        known_host: verify_host_key.then(|| (effective_host.clone(), effective_port)),
+    };
+    let mut session = client::connect(config, (effective_host.as_str(), effective_port), handler)
         .await
-        .map_err(|e| crate::error::AppError::from(format!("SSH 连接失败: {}", e)))?;
+        .map_err(|e| match e {
+            russh::Error::UnknownKey => crate::error::AppError::from(format!(
+                "主机 {}:{} 不在 ~/.ssh/known_hosts 中，请先用 ssh 手动连接一次并确认主机指纹",
+                effective_host, effective_port
+            )),
+            russh::Error::Keys(russh::keys::Error::KeyChanged { line }) => {
+                crate::error::AppError::from(format!(
+                    "主机 {}:{} 的 host key 与 ~/.ssh/known_hosts 第 {} 行记录不一致，已拒绝连接",
+                    effective_host, effective_port, line
+                ))
+            }
+            e => crate::error::AppError::from(format!("SSH 连接失败: {}", e)),
+        })?;
 
-    let success = match &tunnel.auth {
+    let success = match auth {
         SshAuthMethod::Password { password } => session
             .authenticate_password(&effective_user, password)
             .await
@@ -23,6 +23,7 @@ mod commands;
 mod port_test;
 mod runtime;
 
+pub(crate) use auth::connect_ssh_verified;
 pub use commands::*;
 pub use port_test::*;
 
@@ -113,17 +114,28 @@ impl SshTunnelController {
     }
 }
 
-/// russh 客户端 handler - 接受任意 host key（首版不校验 known_hosts）
-pub(super) struct SshClient;
+/// russh 客户端 handler。
+/// `known_host` 为 None 时接受任意 host key（隧道沿用首版行为）；
+/// 为 Some((host, port)) 时按 ~/.ssh/known_hosts 校验，未记录或不一致都拒绝连接。
+pub(crate) struct SshClient {
+    pub(crate) known_host: Option<(String, u16)>,
+}
 
 impl client::Handler for SshClient {
     type Error = russh::Error;
 
     async fn check_server_key(
         &mut self,
-        _server_public_key: &russh::keys::ssh_key::PublicKey,
+        server_public_key: &russh::keys::ssh_key::PublicKey,
     ) -> Result<bool, Self::Error> {
-        Ok(true)
+        match &self.known_host {
+            None => Ok(true),
+            Some((host, port)) => Ok(russh::keys::check_known_hosts(
+                host,
+                *port,
+                server_public_key,
+            )?),
+        }
     }
 }
 
@@ -134,7 +146,7 @@ pub(super) type SharedHandle = Arc<Mutex<Option<Arc<client::Handle<SshClient>>>>
 
 // ============== 持久化 ==============
 
-pub(super) async fn ensure_tunnels_loaded() {
+pub(crate) async fn ensure_tunnels_loaded() {
     let mut loaded = TUNNELS_LOADED.lock().await;
     if !*loaded {
         match load_tunnels_from_file() {
@@ -0,0 +1,255 @@
+// TCP SYN 扫描（半开扫描）：用原始套接字直接发 SYN，收到 SYN/ACK 判为开放、RST 判为关闭，
+// 不建立完整连接，也不必为每个端口等待连接超时，对大量被过滤的端口 / 整个网段快得多。
+//
+// - 仅支持 Linux + IPv4：Windows 客户端系统禁止原始套接字发送 TCP，
+//   macOS/BSD 的原始套接字收不到 TCP 报文（需要 BPF），这些平台直接报告不可用
+// - 需要 root 或 CAP_NET_RAW；无权限时 available() 返回原因，由调用方退回 connect 扫描
+// - 内核收到 SYN/ACK 后发现没有对应连接会自动回 RST，连接不会真正建立
+
+use super::ScanResult;
+use crate::error::AppResult;
+use std::net::IpAddr;
+use std::sync::atomic::AtomicBool;
+
+/// 检测当前环境能否进行 SYN 扫描，不能时返回原因
+pub fn available() -> Result<(), String> {
+    imp::available()
+}
+
+/// 对 targets × ports 做 SYN 扫描，只返回开放的端口；
+/// wait_ms 为最后一个 SYN 发出后等待回应的时间
+pub async fn scan(
+    targets: Vec<IpAddr>,
+    ports: Vec<u16>,
+    wait_ms: u64,
+    cancelled: &'static AtomicBool,
+) -> AppResult<Vec<ScanResult>> {
+    tokio::task::spawn_blocking(move || imp::scan(&targets, &ports, wait_ms, cancelled))
+        .await
+        .map_err(|e| crate::error::AppError::internal(format!("SYN 扫描任务失败: {}", e)))?
+}
+
+#[cfg(target_os = "linux")]
+mod imp {
+    use super::super::port_service_name;
+    use super::ScanResult;
+    use crate::error::{AppError, AppResult};
+    use socket2::{Domain, Protocol, SockAddr, Socket, Type};
+    use std::collections::HashSet;
+    use std::io::{ErrorKind, Read};
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::{Duration, Instant};
+
+    const TCP_SYN: u8 = 0x02;
+    const TCP_RST: u8 = 0x04;
+    const TCP_ACK: u8 = 0x10;
+
+    /// 每发出这么多个 SYN 处理一次回包，避免接收缓冲区溢出丢包
+    const SEND_BATCH: usize = 256;
+
+    fn open_socket() -> std::io::Result<Socket> {
+        Socket::new(Domain::IPV4, Type::RAW, Some(Protocol::TCP))
+    }
+
+    pub fn available() -> Result<(), String> {
+        match open_socket() {
+            Ok(_) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::PermissionDenied => {
+                Err("SYN 扫描需要 root 权限或 CAP_NET_RAW".to_string())
+            }
+            Err(e) => Err(format!("无法创建原始套接字: {}", e)),
+        }
+    }
+
+    /// 通过 UDP connect 让内核选路，得到访问目标时使用的本机地址
+    fn source_addr(target: Ipv4Addr) -> AppResult<Ipv4Addr> {
+        let socket = UdpSocket::bind("0.0.0.0:0")?;
+        socket.connect((target, 80))?;
+        match socket.local_addr()?.ip() {
+            IpAddr::V4(ip) => Ok(ip),
+            IpAddr::V6(_) => Err(AppError::internal("无法确定本机 IPv4 地址")),
+        }
+    }
+
+    fn checksum(data: &[u8], mut sum: u32) -> u16 {
+        for chunk in data.chunks(2) {
+            let word = if chunk.len() == 2 {
+                u16::from_be_bytes([chunk[0], chunk[1]])
+            } else {
+                u16::from_be_bytes([chunk[0], 0])
+            };
+            sum += word as u32;
+        }
+        while sum >> 16 != 0 {
+            sum = (sum & 0xffff) + (sum >> 16);
+        }
+        !(sum as u16)
+    }
+
+    /// 20 字节 TCP SYN 头（IP 头由内核填写）
+    fn syn_packet(
+        src: Ipv4Addr,
+        dst: Ipv4Addr,
+        src_port: u16,
+        dst_port: u16,
+        seq: u32,
+    ) -> [u8; 20] {
+        let mut tcp = [0u8; 20];
+        tcp[0..2].copy_from_slice(&src_port.to_be_bytes());
+        tcp[2..4].copy_from_slice(&dst_port.to_be_bytes());
+        tcp[4..8].copy_from_slice(&seq.to_be_bytes());
+        tcp[12] = 5 << 4; // 数据偏移：5 个 32 位字
+        tcp[13] = TCP_SYN;
+        tcp[14..16].copy_from_slice(&1024u16.to_be_bytes()); // 窗口
+
+        // 伪首部：源地址 + 目的地址 + 协议 + TCP 长度
+        let mut sum = 0u32;
+        for octets in [src.octets(), dst.octets()] {
+            sum += u16::from_be_bytes([octets[0], octets[1]]) as u32;
+            sum += u16::from_be_bytes([octets[2], octets[3]]) as u32;
+        }
+        sum += 6 + tcp.len() as u32;
+        let csum = checksum(&tcp, sum);
+        tcp[16..18].copy_from_slice(&csum.to_be_bytes());
+        tcp
+    }
+
+    /// 读取回包并把开放端口记入 open，最多占用 budget 时间
+    /// （原始套接字会收到本机所有 TCP 报文，流量大时不能一直读下去）
+    fn drain_replies(
+        socket: &mut Socket,
+        src_port: u16,
+        targets: &HashSet<Ipv4Addr>,
+        open: &mut HashSet<(Ipv4Addr, u16)>,
+        budget: Duration,
+    ) {
+        let mut buf = [0u8; 1500];
+        let until = Instant::now() + budget;
+        while Instant::now() < until {
+            let n = match socket.read(&mut buf) {
+                Ok(n) => n,
+                Err(_) => return, // 超时 / WouldBlock：暂时没有回包
+            };
+            let packet = &buf[..n];
+            if packet.len() < 20 || packet[0] >> 4 != 4 {
+                continue;
+            }
+            let ihl = ((packet[0] & 0x0f) as usize) * 4;
+            if packet.len() < ihl + 14 {
+                continue;
+            }
+            let from = Ipv4Addr::new(packet[12], packet[13], packet[14], packet[15]);
+            let tcp = &packet[ihl..];
+            let from_port = u16::from_be_bytes([tcp[0], tcp[1]]);
+            let to_port = u16::from_be_bytes([tcp[2], tcp[3]]);
+            let flags = tcp[13];
+            if to_port != src_port || !targets.contains(&from) {
+                continue;
+            }
+            if flags & (TCP_SYN | TCP_ACK) == (TCP_SYN | TCP_ACK) && flags & TCP_RST == 0 {
+                open.insert((from, from_port));
+            }
+        }
+    }
+
+    pub fn scan(
+        targets: &[IpAddr],
+        ports: &[u16],
+        wait_ms: u64,
+        cancelled: &AtomicBool,
+    ) -> AppResult<Vec<ScanResult>> {
+        let targets: Vec<Ipv4Addr> = targets
+            .iter()
+            .filter_map(|ip| match ip {
+                IpAddr::V4(v4) => Some(*v4),
+                IpAddr::V6(_) => None,
+            })
+            .collect();
+        let Some(first) = targets.first() else {
+            return Err(AppError::invalid("SYN 扫描仅支持 IPv4 目标"));
+        };
+
+        let mut socket = open_socket()?;
+        socket.set_read_timeout(Some(Duration::from_millis(20)))?;
+        let src = source_addr(*first)?;
+        // 固定源端口用于识别回包；取进程 id 混入高位端口，降低与其他扫描冲突的概率
+        let src_port = 40000 + (std::process::id() % 20000) as u16;
+        let seq = std::process::id().wrapping_mul(2654435761);
+
+        let target_set: HashSet<Ipv4Addr> = targets.iter().copied().collect();
+        let mut open = HashSet::new();
+        let mut sent = 0usize;
+
+        'send: for ip in &targets {
+            let dest = SockAddr::from(SocketAddr::new(IpAddr::V4(*ip), 0));
+            for &port in ports {
+                if cancelled.load(Ordering::SeqCst) {
+                    break 'send;
+                }
+                let packet = syn_packet(src, *ip, src_port, port, seq);
+                if let Err(e) = socket.send_to(&packet, &dest) {
+                    // 发送缓冲区满时稍等再试一次，仍失败则跳过该端口
+                    if e.kind() == ErrorKind::WouldBlock {
+                        std::thread::sleep(Duration::from_millis(5));
+                        let _ = socket.send_to(&packet, &dest);
+                    }
+                }
+                sent += 1;
+                if sent % SEND_BATCH == 0 {
+                    drain_replies(
+                        &mut socket,
+                        src_port,
+                        &target_set,
+                        &mut open,
+                        Duration::from_millis(20),
+                    );
+                }
+            }
+        }
+
+        let deadline = Instant::now() + Duration::from_millis(wait_ms);
+        while Instant::now() < deadline && !cancelled.load(Ordering::SeqCst) {
+            drain_replies(
+                &mut socket,
+                src_port,
+                &target_set,
+                &mut open,
+                Duration::from_millis(100),
+            );
+        }
+
+        let mut results: Vec<ScanResult> = open
+            .into_iter()
+            .map(|(ip, port)| ScanResult {
+                ip: ip.to_string(),
+                port,
+                status: "open".to_string(),
+                service: port_service_name(port).map(|s| s.to_string()),
+            })
+            .collect();
+        results.sort_by_key(|r| (r.ip.parse::<Ipv4Addr>().ok(), r.port));
+        Ok(results)
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod imp {
+    use super::ScanResult;
+    use crate::error::{AppError, AppResult};
+    use std::net::IpAddr;
+    use std::sync::atomic::AtomicBool;
+
+    pub fn available() -> Result<(), String> {
+        Err("当前平台不支持 SYN 扫描（仅支持 Linux）".to_string())
+    }
+
+    pub fn scan(
+        _targets: &[IpAddr],
+        _ports: &[u16],
+        _wait_ms: u64,
+        _cancelled: &AtomicBool,
+    ) -> AppResult<Vec<ScanResult>> {
+        Err(AppError::invalid(available().unwrap_err()))
+    }
+}
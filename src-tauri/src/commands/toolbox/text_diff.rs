@@ -0,0 +1,521 @@
+// 文本比较与补丁：任意两段文本的 diff（行级 / 词级）以及把 unified diff 应用回文本
+//
+// diff_text 在阻塞线程里把两段文本写入临时目录（tempfile 创建，结束即删除）后复用 diff_paths 的 `git diff --no-index` 与解析逻辑；
+// 词级模式在相邻的删除 / 新增行块之间再做一次按词 LCS，结果放在 DiffLine.segments。
+// apply_text_patch 纯 Rust 实现，hunk 位置对不上时在附近查找上下文（类似 patch 的偏移）。
+
+use crate::commands::git::{parse_range, parse_unified_diff, run_git_diff, DiffHunk, DiffSegment};
+use crate::error::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// 词级 LCS 的表格上限（删除块词数 × 新增块词数），超过时该块只保留行级差异
+const MAX_WORD_CELLS: usize = 1_000_000;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum TextDiffMode {
+    #[default]
+    Line,
+    Word,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TextDiffResult {
+    pub identical: bool,
+    pub insertions: u32,
+    pub deletions: u32,
+    pub hunks: Vec<DiffHunk>,
+    /// unified diff 文本，可直接保存为 .patch
+    pub patch: String,
+    /// 行数超过上限，hunks 不完整（patch 仍是完整的）
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyPatchResult {
+    pub text: String,
+    pub hunks_applied: u32,
+    /// 不在 hunk 头所写行号、靠查找上下文应用的 hunk 数
+    pub hunks_offset: u32,
+}
+
+/// 英文 / 数字连续视为一个词，空白连续视为一个词，其余字符（含中文）逐字切分
+fn tokenize(line: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut chars = line.char_indices().peekable();
+    while let Some((_, c)) = chars.next() {
+        let is_word = |ch: char| ch.is_ascii_alphanumeric() || ch == '_';
+        let same = |next: char| {
+            (is_word(c) && is_word(next)) || (c.is_whitespace() && next.is_whitespace())
+        };
+        match chars.peek() {
+            Some(&(_, next)) if same(next) => continue,
+            Some(&(j, _)) => {
+                tokens.push(&line[start..j]);
+                start = j;
+            }
+            None => tokens.push(&line[start..]),
+        }
+    }
+    tokens
+}
+
+/// 按行切词，行与行之间用 None 分隔
+fn block_tokens<'a>(lines: &[&'a str]) -> Vec<Option<&'a str>> {
+    let mut tokens = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 {
+            tokens.push(None);
+        }
+        tokens.extend(tokenize(line).into_iter().map(Some));
+    }
+    tokens
+}
+
+/// 返回两侧每个词是否属于公共子序列
+fn lcs_marks(a: &[Option<&str>], b: &[Option<&str>]) -> (Vec<bool>, Vec<bool>) {
+    let (n, m) = (a.len(), b.len());
+    let width = m + 1;
+    let mut table = vec![0u32; (n + 1) * width];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[i * width + j] = if a[i] == b[j] {
+                table[(i + 1) * width + j + 1] + 1
+            } else {
+                table[(i + 1) * width + j].max(table[i * width + j + 1])
+            };
+        }
+    }
+    let (mut keep_a, mut keep_b) = (vec![false; n], vec![false; m]);
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if a[i] == b[j] {
+            keep_a[i] = true;
+            keep_b[j] = true;
+            i += 1;
+            j += 1;
+        } else if table[(i + 1) * width + j] >= table[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    (keep_a, keep_b)
+}
+
+/// 把一侧的词按行拆回片段，相邻同类片段合并
+fn line_segments(tokens: &[Option<&str>], keep: &[bool], changed: &str) -> Vec<Vec<DiffSegment>> {
+    let mut lines = vec![Vec::new()];
+    for (token, kept) in tokens.iter().zip(keep) {
+        let Some(text) = token else {
+            lines.push(Vec::new());
+            continue;
+        };
+        let kind = if *kept { "equal" } else { changed };
+        let segments: &mut Vec<DiffSegment> = lines.last_mut().expect("at least one line");
+        match segments.last_mut() {
+            Some(last) if last.kind == kind => last.text.push_str(text),
+            _ => segments.push(DiffSegment {
+                kind: kind.to_string(),
+                text: text.to_string(),
+            }),
+        }
+    }
+    lines
+}
+
+/// 为 hunk 内每组「连续删除行 + 紧随的连续新增行」填充词级片段
+fn fill_word_segments(hunk: &mut DiffHunk) {
+    let mut i = 0;
+    while i < hunk.lines.len() {
+        if hunk.lines[i].kind != "remove" {
+            i += 1;
+            continue;
+        }
+        let removed_end = (i..hunk.lines.len())
+            .find(|&k| hunk.lines[k].kind != "remove")
+            .unwrap_or(hunk.lines.len());
+        let added_end = (removed_end..hunk.lines.len())
+            .find(|&k| hunk.lines[k].kind != "add")
+            .unwrap_or(hunk.lines.len());
+        if added_end > removed_end {
+            let removed: Vec<&str> = hunk.lines[i..removed_end]
+                .iter()
+                .map(|l| l.content.as_str())
+                .collect();
+            let added: Vec<&str> = hunk.lines[removed_end..added_end]
+                .iter()
+                .map(|l| l.content.as_str())
+                .collect();
+            let (a, b) = (block_tokens(&removed), block_tokens(&added));
+            if a.len().saturating_mul(b.len()) <= MAX_WORD_CELLS {
+                let (keep_a, keep_b) = lcs_marks(&a, &b);
+                let old_segments = line_segments(&a, &keep_a, "remove");
+                let new_segments = line_segments(&b, &keep_b, "add");
+                for (line, segments) in hunk.lines[i..removed_end].iter_mut().zip(old_segments) {
+                    line.segments = Some(segments);
+                }
+                for (line, segments) in hunk.lines[removed_end..added_end]
+                    .iter_mut()
+                    .zip(new_segments)
+                {
+                    line.segments = Some(segments);
+                }
+            }
+        }
+        i = added_end;
+    }
+}
+
+/// 去掉 git 输出的 `diff --git` / `index` 头，文件名换成传入的标签
+fn clean_patch(output: &str, label_a: &str, label_b: &str) -> String {
+    let mut patch = String::new();
+    let mut lines = output.lines().skip_while(|l| !l.starts_with("--- "));
+    if lines.next().is_none() {
+        return patch;
+    }
+    patch.push_str(&format!("--- {}\n", label_a));
+    let mut header_done = false;
+    for line in lines {
+        if !header_done && line.starts_with("+++ ") {
+            header_done = true;
+            patch.push_str(&format!("+++ {}\n", label_b));
+            continue;
+        }
+        patch.push_str(line);
+        patch.push('\n');
+    }
+    patch
+}
+
+/// 两段文本写成临时目录下的 a / b 后执行 git diff，输出中的路径即 a/a、b/b
+fn git_diff_files(
+    dir: &Path,
+    a: &str,
+    b: &str,
+    context_lines: u32,
+    ignore_whitespace: bool,
+) -> AppResult<String> {
+    std::fs::write(dir.join("a"), a)?;
+    std::fs::write(dir.join("b"), b)?;
+    let context = format!("-U{}", context_lines);
+    let mut args = vec![
+        "diff",
+        "--no-index",
+        "--no-color",
+        "--no-ext-diff",
+        context.as_str(),
+    ];
+    if ignore_whitespace {
+        args.push("-w");
+    }
+    args.extend(["--", "a", "b"]);
+    run_git_diff(Some(&dir.to_string_lossy()), &args)
+}
+
+/// 比较两段文本；mode 为 word 时额外给出行内的词级差异
+#[tauri::command]
+#[specta::specta]
+pub async fn diff_text(
+    a: String,
+    b: String,
+    mode: Option<TextDiffMode>,
+    context_lines: Option<u32>,
+    ignore_whitespace: Option<bool>,
+    label_a: Option<String>,
+    label_b: Option<String>,
+) -> AppResult<TextDiffResult> {
+    let output = tokio::task::spawn_blocking(move || -> AppResult<String> {
+        let dir = tempfile::Builder::new()
+            .prefix("codeshelf-diff-")
+            .tempdir()?;
+        git_diff_files(
+            dir.path(),
+            &a,
+            &b,
+            context_lines.unwrap_or(3),
+            ignore_whitespace.unwrap_or(false),
+        )
+    })
+    .await
+    .map_err(|e| AppError::internal(format!("文本比较失败: {}", e)))??;
+
+    let parsed = parse_unified_diff(&output, "a", "b");
+    let mut hunks = parsed
+        .files
+        .into_iter()
+        .next()
+        .map(|f| f.hunks)
+        .unwrap_or_default();
+    if mode.unwrap_or_default() == TextDiffMode::Word {
+        hunks.iter_mut().for_each(fill_word_segments);
+    }
+    let label = |l: Option<String>, default: &str| {
+        l.filter(|l| !l.trim().is_empty())
+            .unwrap_or_else(|| default.to_string())
+    };
+    Ok(TextDiffResult {
+        identical: output.trim().is_empty(),
+        insertions: parsed.insertions,
+        deletions: parsed.deletions,
+        patch: clean_patch(&output, &label(label_a, "a"), &label(label_b, "b")),
+        truncated: parsed.truncated,
+        hunks,
+    })
+}
+
+struct PatchHunk {
+    header: String,
+    old_start: usize,
+    old: Vec<String>,
+    new: Vec<String>,
+}
+
+/// 「\\ No newline at end of file」标记：分别表示旧 / 新文本末尾没有换行
+#[derive(Default)]
+struct MissingNewline {
+    old: bool,
+    new: bool,
+}
+
+/// 解析补丁中的 hunk；文件头与其他说明行忽略。reverse 时交换新旧两侧
+fn parse_patch(patch: &str, reverse: bool) -> AppResult<(Vec<PatchHunk>, MissingNewline)> {
+    let mut hunks: Vec<PatchHunk> = Vec::new();
+    let (mut old_rem, mut new_rem) = (0u32, 0u32);
+    let mut missing = MissingNewline::default();
+    let mut last_kind = ' ';
+    for line in patch.lines() {
+        if line.starts_with('\\') {
+            match last_kind {
+                '+' => missing.new = true,
+                '-' => missing.old = true,
+                _ => {
+                    missing.old = true;
+                    missing.new = true;
+                }
+            }
+            continue;
+        }
+        if let Some(hunk) = hunks.last_mut().filter(|_| old_rem > 0 || new_rem > 0) {
+            let (kind, content) = match line.chars().next() {
+                Some(c @ ('+' | '-' | ' ')) => (c, &line[1..]),
+                // 有些编辑器会去掉空上下文行的前导空格
+                None => (' ', ""),
+                Some(_) => return Err(AppError::invalid(format!("补丁格式错误: {}", line))),
+            };
+            match kind {
+                '+' => {
+                    new_rem = new_rem.saturating_sub(1);
+                    hunk.new.push(content.to_string());
+                }
+                '-' => {
+                    old_rem = old_rem.saturating_sub(1);
+                    hunk.old.push(content.to_string());
+                }
+                _ => {
+                    old_rem = old_rem.saturating_sub(1);
+                    new_rem = new_rem.saturating_sub(1);
+                    hunk.old.push(content.to_string());
+                    hunk.new.push(content.to_string());
+                }
+            }
+            last_kind = kind;
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("@@ ") {
+            let mut parts = rest.split_whitespace();
+            let (old_start, old_lines) = parse_range(parts.next().unwrap_or(""));
+            let (_, new_lines) = parse_range(parts.next().unwrap_or(""));
+            old_rem = old_lines;
+            new_rem = new_lines;
+            // 空的旧范围（纯新增）行号指向插入位置的前一行
+            let old_start = if old_lines == 0 {
+                old_start as usize
+            } else {
+                (old_start as usize).saturating_sub(1)
+            };
+            hunks.push(PatchHunk {
+                header: line.to_string(),
+                old_start,
+                old: Vec::new(),
+                new: Vec::new(),
+            });
+        }
+    }
+    if hunks.is_empty() {
+        return Err(AppError::invalid("补丁中没有可应用的 hunk"));
+    }
+    if reverse {
+        for hunk in hunks.iter_mut() {
+            std::mem::swap(&mut hunk.old, &mut hunk.new);
+        }
+        std::mem::swap(&mut missing.old, &mut missing.new);
+    }
+    Ok((hunks, missing))
+}
+
+fn lines_match(text: &[&str], expected: &[String]) -> bool {
+    text.len() == expected.len()
+        && text
+            .iter()
+            .zip(expected)
+            .all(|(a, b)| a.trim_end_matches('\r') == b.trim_end_matches('\r'))
+}
+
+/// 把 unified diff 应用到文本上；reverse 为 true 时撤销补丁
+#[tauri::command]
+#[specta::specta]
+pub async fn apply_text_patch(
+    original: String,
+    patch: String,
+    reverse: Option<bool>,
+) -> AppResult<ApplyPatchResult> {
+    let (hunks, missing_newline) = parse_patch(&patch, reverse.unwrap_or(false))?;
+    let had_newline = original.ends_with('\n');
+    let mut source: Vec<&str> = original.split('\n').collect();
+    if had_newline || original.is_empty() {
+        source.pop();
+    }
+
+    let mut result: Vec<String> = Vec::with_capacity(source.len());
+    let mut cursor = 0usize;
+    let mut delta: isize = 0;
+    let mut hunks_offset = 0u32;
+    for (index, hunk) in hunks.iter().enumerate() {
+        let expected = (hunk.old_start as isize + delta).max(cursor as isize) as usize;
+        let last = source.len().checked_sub(hunk.old.len());
+        // 从期望位置向两侧查找上下文，不回到已应用的区域之前
+        let position = last.and_then(|last| {
+            let max_distance = last
+                .saturating_sub(cursor)
+                .max(expected.saturating_sub(cursor));
+            (0..=max_distance).find_map(|d| {
+                [expected.checked_add(d), expected.checked_sub(d)]
+                    .into_iter()
+                    .flatten()
+                    .filter(|p| *p >= cursor && *p <= last)
+                    .find(|p| lines_match(&source[*p..*p + hunk.old.len()], &hunk.old))
+            })
+        });
+        let Some(position) = position else {
+            return Err(AppError::invalid(format!(
+                "第 {} 个 hunk 无法应用，上下文不匹配: {}",
+                index + 1,
+                hunk.header
+            )));
+        };
+        if position != expected {
+            hunks_offset += 1;
+        }
+        delta = position as isize - hunk.old_start as isize;
+        result.extend(source[cursor..position].iter().map(|l| l.to_string()));
+        result.extend(hunk.new.iter().cloned());
+        cursor = position + hunk.old.len();
+    }
+    result.extend(source[cursor..].iter().map(|l| l.to_string()));
+
+    let mut text = result.join("\n");
+    let trailing_newline = if missing_newline.new {
+        false
+    } else {
+        missing_newline.old || had_newline
+    };
+    if trailing_newline && !result.is_empty() {
+        text.push('\n');
+    }
+    Ok(ApplyPatchResult {
+        text,
+        hunks_applied: hunks.len() as u32,
+        hunks_offset,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = "one\ntwo\nthree\nfour\nfive\nsix\nseven\neight\nnine\nten\n";
+    const NEW: &str = "one\n2\nthree\nfour\nfive\nsix\nseven\neight\n9\nten\neleven\n";
+
+    async fn patch_of(a: &str, b: &str) -> String {
+        diff_text(a.into(), b.into(), None, Some(1), None, None, None)
+            .await
+            .unwrap()
+            .patch
+    }
+
+    async fn apply(original: &str, patch: &str, reverse: bool) -> AppResult<ApplyPatchResult> {
+        apply_text_patch(original.into(), patch.into(), Some(reverse)).await
+    }
+
+    #[tokio::test]
+    async fn test_round_trip() {
+        let patch = patch_of(OLD, NEW).await;
+        let applied = apply(OLD, &patch, false).await.unwrap();
+        assert_eq!(applied.text, NEW);
+        assert_eq!(applied.hunks_applied, 2);
+        assert_eq!(applied.hunks_offset, 0);
+        assert_eq!(apply(NEW, &patch, true).await.unwrap().text, OLD);
+    }
+
+    #[tokio::test]
+    async fn test_identical_text() {
+        let result = diff_text(OLD.into(), OLD.into(), None, None, None, None, None)
+            .await
+            .unwrap();
+        assert!(result.identical);
+        assert!(result.patch.is_empty());
+    }
+
+    #[test]
+    fn test_parse_patch_ranges() {
+        let patch = "--- a\n+++ b\n@@ -2,2 +2,3 @@\n two\n-three\n+3\n+3.5\n@@ -0,0 +1 @@\n+zero\n";
+        let (hunks, missing) = parse_patch(patch, false).unwrap();
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(hunks[0].old_start, 1);
+        assert_eq!(hunks[0].old, vec!["two", "three"]);
+        assert_eq!(hunks[0].new, vec!["two", "3", "3.5"]);
+        assert_eq!(hunks[1].old_start, 0);
+        assert!(hunks[1].old.is_empty());
+        assert!(!missing.old && !missing.new);
+        assert!(parse_patch("no hunks here", false).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_hunks_with_offset() {
+        let patch = patch_of(OLD, NEW).await;
+        // 前面多出两行，hunk 头的行号整体偏移
+        let shifted = format!("zero\nzero\n{}", OLD);
+        let applied = apply(&shifted, &patch, false).await.unwrap();
+        assert_eq!(applied.text, format!("zero\nzero\n{}", NEW));
+        assert_eq!(applied.hunks_applied, 2);
+        assert_eq!(applied.hunks_offset, 1);
+    }
+
+    #[tokio::test]
+    async fn test_missing_trailing_newline() {
+        let old = "alpha\nbeta";
+        let new = "alpha\nbeta\ngamma\n";
+        let patch = patch_of(old, new).await;
+        assert!(patch.contains("\\ No newline at end of file"));
+        assert_eq!(apply(old, &patch, false).await.unwrap().text, new);
+        assert_eq!(apply(new, &patch, true).await.unwrap().text, old);
+
+        let patch = patch_of(new, old).await;
+        assert_eq!(apply(new, &patch, false).await.unwrap().text, old);
+    }
+
+    #[tokio::test]
+    async fn test_mismatched_hunk_is_rejected() {
+        let patch = patch_of(OLD, NEW).await;
+        let edited = OLD.replace("nine", "NINE");
+        let err = apply(&edited, &patch, false).await.unwrap_err();
+        assert!(err.to_string().contains("第 2 个 hunk"), "{}", err);
+        // 已经应用过的补丁不能再正向应用一次
+        assert!(apply(NEW, &patch, false).await.is_err());
+    }
+}
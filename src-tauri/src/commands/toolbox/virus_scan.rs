@@ -0,0 +1,182 @@
+// 下载后病毒扫描 - 调用系统自带 / 已安装的扫描器检查下载完成的文件
+//
+// - Windows：Windows Defender（MpCmdRun.exe -Scan -ScanType 3 -File），退出码 2 表示发现威胁
+// - 其他平台：PATH 中的 clamscan，退出码 1 表示发现威胁
+// 扫描结果记录在下载任务的 scan 字段上；设置 download_virus_action 为 quarantine / delete 时，
+// 发现威胁后把文件移到数据目录下的 quarantine 目录或直接删除。
+
+use super::{DownloadScanResult, DownloadTask};
+use crate::error::{AppError, AppResult};
+use crate::storage::get_storage_config;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// 可用的扫描器：(名称, 可执行文件路径)
+fn find_scanner() -> Option<(&'static str, PathBuf)> {
+    #[cfg(target_os = "windows")]
+    {
+        let program_files =
+            std::env::var_os("ProgramFiles").unwrap_or_else(|| "C:\\Program Files".into());
+        let defender = Path::new(&program_files)
+            .join("Windows Defender")
+            .join("MpCmdRun.exe");
+        if defender.is_file() {
+            return Some(("Windows Defender", defender));
+        }
+    }
+
+    let name = if cfg!(target_os = "windows") {
+        "clamscan.exe"
+    } else {
+        "clamscan"
+    };
+    let path_var = std::env::var_os("PATH")?;
+    std::env::split_paths(&path_var)
+        .map(|dir| dir.join(name))
+        .find(|p| p.is_file())
+        .map(|p| ("ClamAV", p))
+}
+
+fn scan_blocking(file: &Path) -> DownloadScanResult {
+    let now = super::current_time();
+    let Some((scanner, exe)) = find_scanner() else {
+        return DownloadScanResult {
+            scanner: None,
+            verdict: "unavailable".to_string(),
+            detail: Some("未找到可用的病毒扫描器（Windows Defender / clamscan）".to_string()),
+            action: None,
+            scanned_at: now,
+        };
+    };
+
+    let mut cmd = Command::new(&exe);
+    if scanner == "Windows Defender" {
+        cmd.args(["-Scan", "-ScanType", "3", "-DisableRemediation", "-File"])
+            .arg(file);
+    } else {
+        cmd.arg("--no-summary").arg(file);
+    }
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+
+    let (verdict, detail) = match cmd.output() {
+        Ok(output) => {
+            let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            let threat_code = if scanner == "Windows Defender" { 2 } else { 1 };
+            match output.status.code() {
+                Some(0) => ("clean", None),
+                Some(code) if code == threat_code => ("infected", Some(threat_summary(&text))),
+                code => (
+                    "error",
+                    Some(format!(
+                        "扫描器退出码 {:?}: {}",
+                        code,
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    )),
+                ),
+            }
+        }
+        Err(e) => ("error", Some(format!("启动扫描器失败: {}", e))),
+    };
+
+    DownloadScanResult {
+        scanner: Some(scanner.to_string()),
+        verdict: verdict.to_string(),
+        detail,
+        action: None,
+        scanned_at: now,
+    }
+}
+
+/// 从扫描输出中提取威胁名称相关的行
+fn threat_summary(output: &str) -> String {
+    let lines: Vec<&str> = output
+        .lines()
+        .filter(|l| l.contains("FOUND") || l.contains("Threat") || l.contains("threat"))
+        .collect();
+    if lines.is_empty() {
+        "发现威胁".to_string()
+    } else {
+        lines.join("\n")
+    }
+}
+
+/// 按设置处理被判定为感染的文件，返回执行的动作
+fn apply_action(file: &Path, action: &str) -> AppResult<Option<String>> {
+    match action {
+        "delete" => {
+            std::fs::remove_file(file)?;
+            Ok(Some("deleted".to_string()))
+        }
+        "quarantine" => {
+            let dir = get_storage_config()?.quarantine_dir();
+            std::fs::create_dir_all(&dir)?;
+            let name = file
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "download".to_string());
+            let target = dir.join(format!(
+                "{}-{}.quarantine",
+                chrono::Local::now().format("%Y%m%d%H%M%S"),
+                name
+            ));
+            // 跨盘时 rename 会失败，退回复制后删除
+            if std::fs::rename(file, &target).is_err() {
+                std::fs::copy(file, &target)?;
+                std::fs::remove_file(file)?;
+            }
+            Ok(Some(format!("quarantined: {}", target.to_string_lossy())))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// 扫描下载任务的文件；action 为发现威胁后的处理方式（"none" / "quarantine" / "delete"）
+pub(crate) async fn scan_task_file(task: &DownloadTask, action: &str) -> DownloadScanResult {
+    let file = PathBuf::from(&task.save_path);
+    let action = action.to_string();
+    let file_name = task.file_name.clone();
+    tokio::task::spawn_blocking(move || {
+        if !file.is_file() {
+            return DownloadScanResult {
+                scanner: None,
+                verdict: "error".to_string(),
+                detail: Some("文件不存在".to_string()),
+                action: None,
+                scanned_at: super::current_time(),
+            };
+        }
+        let mut result = scan_blocking(&file);
+        if result.verdict == "infected" {
+            log::warn!("下载文件 {} 发现威胁: {:?}", file_name, result.detail);
+            match apply_action(&file, &action) {
+                Ok(taken) => result.action = taken,
+                Err(e) => result.action = Some(format!("failed: {}", e)),
+            }
+        }
+        result
+    })
+    .await
+    .unwrap_or_else(|e| DownloadScanResult {
+        scanner: None,
+        verdict: "error".to_string(),
+        detail: Some(e.to_string()),
+        action: None,
+        scanned_at: super::current_time(),
+    })
+}
+
+/// 当前可用的扫描器名称，没有时返回 None
+#[tauri::command]
+#[specta::specta]
+pub async fn get_virus_scanner() -> AppResult<Option<String>> {
+    tokio::task::spawn_blocking(|| find_scanner().map(|(name, _)| name.to_string()))
+        .await
+        .map_err(|e| AppError::internal(e.to_string()))
+}
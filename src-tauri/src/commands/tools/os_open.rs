@@ -1,17 +1,29 @@
 //! OpenPath / OpenInEditor / OpenTerminal / OpenUrl —— 桥接到 commands::system 的跨平台实现。
 
+use crate::commands::system::LaunchWarning;
 use crate::error::AppResult;
 use serde_json::Value;
 
 use super::ctx::expand_home;
 
+/// 程序启动后立即退出：把退出信息与 stderr 交给模型
+fn launch_failed(w: LaunchWarning) -> String {
+    if w.stderr.is_empty() {
+        w.message
+    } else {
+        format!("{}\n{}", w.message, w.stderr)
+    }
+}
+
 pub(super) async fn tool_open_path(args: &Value) -> AppResult<String> {
     let path = args
         .get("path")
         .and_then(|v| v.as_str())
         .ok_or("缺少 path")?;
     let path = expand_home(path);
-    crate::commands::system::open_in_explorer(path.clone()).await?;
+    if let Some(w) = crate::commands::system::open_in_explorer(path.clone()).await? {
+        return Err(launch_failed(w).into());
+    }
     Ok(format!("已在文件管理器中打开：{}", path))
 }
 
@@ -32,7 +44,9 @@ pub(super) async fn tool_open_in_editor(args: &Value) -> AppResult<String> {
             return Err("editor 参数包含危险字符".into());
         }
     }
-    crate::commands::system::open_in_editor(path.clone(), editor.clone()).await?;
+    if let Some(w) = crate::commands::system::open_in_editor(path.clone(), editor.clone()).await? {
+        return Err(launch_failed(w).into());
+    }
     Ok(format!(
         "已在编辑器打开：{}（{}）",
         path,
@@ -50,7 +64,11 @@ pub(super) async fn tool_open_terminal(args: &Value) -> AppResult<String> {
         .get("terminal")
         .and_then(|v| v.as_str())
         .map(|s| s.to_string());
-    crate::commands::system::open_in_terminal(path.clone(), terminal, None, None).await?;
+    if let Some(w) =
+        crate::commands::system::open_in_terminal(path.clone(), terminal, None, None, None).await?
+    {
+        return Err(launch_failed(w).into());
+    }
     Ok(format!("已在终端打开：{}", path))
 }
 
@@ -6,7 +6,7 @@
 //! - url:        必填，http/https
 //! - max_bytes:  最终输出上限字节数（默认 500_000，上限 2_000_000）；提取在截断之前进行
 //! - timeout_ms: 请求超时毫秒（默认 30_000，范围 1_000..=120_000）
-//! - proxy:      代理地址，如 "http://127.0.0.1:7890"（留空不走代理）
+//! - proxy:      代理地址，如 "http://127.0.0.1:7890"（留空使用全局代理设置）
 //! - headers:    自定义请求头对象
 //! - selector:   CSS 选择器，命中后只保留匹配元素（HTML 页面才生效）
 //! - extract_mode/extractMode: "text"(默认) | "html"，selector 命中后取文本还是原始 HTML
@@ -62,15 +62,18 @@ async fn run_web_fetch(args: &Value) -> AppResult<String> {
         .unwrap_or("text");
     let include_meta = args.get("meta").and_then(|v| v.as_bool()).unwrap_or(true);
 
-    let mut builder = reqwest::Client::builder()
-        .timeout(Duration::from_millis(timeout_ms))
-        .user_agent("codeshelf/0.1 (+https://github.com/)");
-    if let Some(p) = proxy {
-        let pr = reqwest::Proxy::all(p)
-            .map_err(|e| crate::error::AppError::from(format!("代理地址无效: {}", e)))?;
-        builder = builder.proxy(pr);
-    }
+    // 显式指定 proxy 时覆盖全局代理设置
+    let builder = match proxy {
+        Some(p) => {
+            let pr = reqwest::Proxy::all(p)
+                .map_err(|e| crate::error::AppError::from(format!("代理地址无效: {}", e)))?;
+            reqwest::Client::builder().proxy(pr)
+        }
+        None => crate::http_client::builder(),
+    };
     let client = builder
+        .timeout(Duration::from_millis(timeout_ms))
+        .user_agent("codeshelf/0.1 (+https://github.com/)")
         .build()
         .map_err(|e| crate::error::AppError::from(format!("客户端构建失败: {}", e)))?;
 
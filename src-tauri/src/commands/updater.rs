@@ -0,0 +1,332 @@
+// 应用更新：更新通道、手动检查、推迟与定时安装
+//
+// 通道决定 latest.json 的地址：stable 取 GitHub 最新正式版，beta 取滚动更新的 `beta` 发布。
+// 检查到的更新与下载好的安装包保存在内存中，安装时机可选：
+//   - install_update 立即安装并重启
+//   - schedule_update_install("onExit") 在下次退出时安装
+//   - schedule_update_install("at", at) 到指定时间优雅退出、安装并重启
+// defer_update 记录推迟的版本，到期前自动检查（manual = false）返回 deferred = true，前端不再弹出提示。
+
+use crate::commands::settings::load_app_settings;
+use crate::error::{AppError, AppResult};
+use crate::storage::{documents, UpdateDeferral};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+pub const UPDATE_CHANNELS: &[&str] = &["stable", "beta"];
+
+const STABLE_ENDPOINT: &str =
+    "https://github.com/en-o/codeshelf/releases/latest/download/latest.json";
+const BETA_ENDPOINT: &str = "https://github.com/en-o/codeshelf/releases/download/beta/latest.json";
+
+/// 检查结果
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCheck {
+    pub channel: String,
+    pub available: bool,
+    pub current_version: String,
+    pub version: Option<String>,
+    pub date: Option<String>,
+    /// 更新说明（latest.json 中的 notes）
+    pub notes: Option<String>,
+    /// 该版本已被推迟，自动检查时不提示
+    pub deferred: bool,
+}
+
+/// 已下载更新的安装安排
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSchedule {
+    pub version: String,
+    pub downloaded: bool,
+    /// "manual" | "onExit" | "at"
+    pub mode: String,
+    /// mode 为 "at" 时的安装时间（RFC3339）
+    pub at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DownloadProgress {
+    downloaded: f64,
+    total: Option<f64>,
+}
+
+struct Pending {
+    update: Update,
+    bytes: Option<Vec<u8>>,
+    schedule: UpdateSchedule,
+    timer: Option<tauri::async_runtime::JoinHandle<()>>,
+}
+
+static PENDING: Lazy<Mutex<Option<Pending>>> = Lazy::new(|| Mutex::new(None));
+
+/// 定时安装触发的退出需要在安装后重新启动
+static RESTART_AFTER_INSTALL: AtomicBool = AtomicBool::new(false);
+
+fn pending() -> std::sync::MutexGuard<'static, Option<Pending>> {
+    PENDING.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn endpoint(channel: &str) -> &'static str {
+    match channel {
+        "beta" => BETA_ENDPOINT,
+        _ => STABLE_ENDPOINT,
+    }
+}
+
+fn updater_error(e: tauri_plugin_updater::Error) -> AppError {
+    AppError::other(format!("更新失败: {}", e))
+}
+
+/// 当前更新通道
+#[tauri::command]
+#[specta::specta]
+pub async fn get_update_channel() -> AppResult<String> {
+    Ok(load_app_settings().await?.update_channel)
+}
+
+/// 切换更新通道；已检查或下载的更新作废
+#[tauri::command]
+#[specta::specta]
+pub async fn set_update_channel(channel: String) -> AppResult<String> {
+    if !UPDATE_CHANNELS.contains(&channel.as_str()) {
+        return Err(AppError::invalid(format!("无效的更新通道: {}", channel)));
+    }
+    let mut settings = load_app_settings().await?;
+    if settings.update_channel != channel {
+        settings.update_channel = channel.clone();
+        documents::save(&documents::APP_SETTINGS, &settings)?;
+        clear_pending();
+    }
+    Ok(channel)
+}
+
+fn clear_pending() {
+    if let Some(timer) = pending().take().and_then(|p| p.timer) {
+        timer.abort();
+    }
+}
+
+/// 按当前通道检查更新；manual 为 false（启动时的自动检查）时标出已推迟的版本
+#[tauri::command]
+#[specta::specta]
+pub async fn check_for_update(app: AppHandle, manual: Option<bool>) -> AppResult<UpdateCheck> {
+    let settings = load_app_settings().await?;
+    let channel = settings.update_channel.clone();
+    let url = url::Url::parse(endpoint(&channel))
+        .map_err(|e| AppError::internal(format!("更新地址无效: {}", e)))?;
+    let update = app
+        .updater_builder()
+        .endpoints(vec![url])
+        .map_err(updater_error)?
+        .build()
+        .map_err(updater_error)?
+        .check()
+        .await
+        .map_err(updater_error)?;
+
+    let current_version = app.package_info().version.to_string();
+    let Some(update) = update else {
+        clear_pending();
+        return Ok(UpdateCheck {
+            channel,
+            available: false,
+            current_version,
+            version: None,
+            date: None,
+            notes: None,
+            deferred: false,
+        });
+    };
+
+    let deferred = !manual.unwrap_or(false)
+        && settings.update_deferral.as_ref().is_some_and(|d| {
+            d.version == update.version
+                && DateTime::parse_from_rfc3339(&d.until).is_ok_and(|until| until > Utc::now())
+        });
+    let check = UpdateCheck {
+        channel,
+        available: true,
+        current_version,
+        version: Some(update.version.clone()),
+        date: update.date.map(|d| d.to_string()),
+        notes: update.body.clone(),
+        deferred,
+    };
+
+    let mut slot = pending();
+    let same_version = slot
+        .as_ref()
+        .is_some_and(|p| p.update.version == update.version);
+    if !same_version {
+        if let Some(timer) = slot.take().and_then(|p| p.timer) {
+            timer.abort();
+        }
+        *slot = Some(Pending {
+            schedule: UpdateSchedule {
+                version: update.version.clone(),
+                downloaded: false,
+                mode: "manual".to_string(),
+                at: None,
+            },
+            update,
+            bytes: None,
+            timer: None,
+        });
+    }
+    Ok(check)
+}
+
+/// 下载已检查到的更新，进度通过 `update-download-progress` 推送
+#[tauri::command]
+#[specta::specta]
+pub async fn download_update(app: AppHandle) -> AppResult<UpdateSchedule> {
+    let update = pending()
+        .as_ref()
+        .map(|p| p.update.clone())
+        .ok_or_else(|| AppError::invalid("没有可下载的更新，请先检查更新"))?;
+
+    let mut downloaded = 0u64;
+    let bytes = update
+        .download(
+            |chunk, total| {
+                downloaded += chunk as u64;
+                let _ = app.emit(
+                    "update-download-progress",
+                    DownloadProgress {
+                        downloaded: downloaded as f64,
+                        total: total.map(|t| t as f64),
+                    },
+                );
+            },
+            || {},
+        )
+        .await
+        .map_err(updater_error)?;
+
+    let mut slot = pending();
+    let pending = slot
+        .as_mut()
+        .filter(|p| p.update.version == update.version)
+        .ok_or_else(|| AppError::invalid("更新已作废，请重新检查"))?;
+    pending.bytes = Some(bytes);
+    pending.schedule.downloaded = true;
+    Ok(pending.schedule.clone())
+}
+
+/// 取出已下载的安装包并安装
+fn install_pending() -> AppResult<()> {
+    let (update, bytes) = {
+        let mut slot = pending();
+        let pending = slot
+            .as_mut()
+            .ok_or_else(|| AppError::invalid("没有待安装的更新"))?;
+        let bytes = pending
+            .bytes
+            .take()
+            .ok_or_else(|| AppError::invalid("更新尚未下载"))?;
+        (pending.update.clone(), bytes)
+    };
+    clear_pending();
+    update.install(bytes).map_err(updater_error)
+}
+
+/// 立即安装已下载的更新并重启
+#[tauri::command]
+#[specta::specta]
+pub async fn install_update(app: AppHandle) -> AppResult<()> {
+    install_pending()?;
+    app.restart();
+}
+
+/// 安排已下载更新的安装时机：mode 为 "manual" | "onExit" | "at"（需要 at，RFC3339）
+#[tauri::command]
+#[specta::specta]
+pub async fn schedule_update_install(
+    app: AppHandle,
+    mode: String,
+    at: Option<String>,
+) -> AppResult<UpdateSchedule> {
+    let delay = match mode.as_str() {
+        "manual" | "onExit" => None,
+        "at" => {
+            let at = at
+                .as_deref()
+                .ok_or_else(|| AppError::invalid("请指定安装时间"))?;
+            let at = DateTime::parse_from_rfc3339(at)
+                .map_err(|_| AppError::invalid(format!("无效的安装时间: {}", at)))?;
+            Some(
+                (at.with_timezone(&Utc) - Utc::now())
+                    .to_std()
+                    .unwrap_or_default(),
+            )
+        }
+        other => return Err(AppError::invalid(format!("无效的安装时机: {}", other))),
+    };
+
+    let mut slot = pending();
+    let pending = slot
+        .as_mut()
+        .filter(|p| p.bytes.is_some())
+        .ok_or_else(|| AppError::invalid("更新尚未下载"))?;
+    if let Some(timer) = pending.timer.take() {
+        timer.abort();
+    }
+    pending.schedule.mode = mode;
+    pending.schedule.at = if delay.is_some() { at } else { None };
+    if let Some(delay) = delay {
+        pending.timer = Some(tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(delay).await;
+            log::info!("到达计划的更新安装时间，退出并安装");
+            RESTART_AFTER_INSTALL.store(true, Ordering::SeqCst);
+            crate::shutdown::request_exit(&app);
+        }));
+    }
+    Ok(pending.schedule.clone())
+}
+
+/// 当前待安装的更新
+#[tauri::command]
+#[specta::specta]
+pub async fn get_update_schedule() -> AppResult<Option<UpdateSchedule>> {
+    Ok(pending().as_ref().map(|p| p.schedule.clone()))
+}
+
+/// 推迟某个版本 days 天；days 为 0 时取消推迟
+#[tauri::command]
+#[specta::specta]
+pub async fn defer_update(version: String, days: u32) -> AppResult<Option<UpdateDeferral>> {
+    let mut settings = load_app_settings().await?;
+    settings.update_deferral = (days > 0).then(|| UpdateDeferral {
+        version,
+        until: (Utc::now() + chrono::Duration::days(days as i64))
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+    });
+    documents::save(&documents::APP_SETTINGS, &settings)?;
+    Ok(settings.update_deferral)
+}
+
+/// 退出流程末尾调用：安排了退出时（或定时）安装的更新在此安装。
+/// 返回 true 表示需要重新启动应用
+pub(crate) fn install_on_exit() -> bool {
+    let scheduled = pending()
+        .as_ref()
+        .is_some_and(|p| p.bytes.is_some() && p.schedule.mode != "manual");
+    if !scheduled {
+        return false;
+    }
+    match install_pending() {
+        Ok(()) => RESTART_AFTER_INSTALL.load(Ordering::SeqCst),
+        Err(e) => {
+            log::warn!("退出时安装更新失败: {}", e);
+            false
+        }
+    }
+}
@@ -0,0 +1,223 @@
+// 本地使用统计：记录各命令（功能）的调用次数，帮助判断哪些工具真正在用。
+//
+// - 默认关闭，需在设置中开启（usage_stats_enabled）
+// - 只写入本地 SQLite 的 usage_stats 表（按天、按命令计数），不会发送到任何地方
+// - invoke 层每次调用只在内存里累加，后台每分钟合并写库一次，退出时再写一次
+
+use crate::error::AppResult;
+use crate::storage::db::pool;
+use crate::storage::{documents, AppSettings};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// 尚未写库的计数：(日期, 命令) -> 次数
+static PENDING: Lazy<Mutex<HashMap<(String, String), i64>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 命令名关键字 -> 功能分组（按顺序匹配第一个）
+const FEATURES: &[(&str, &str)] = &[
+    ("netcat", "Netcat 调试"),
+    ("mqtt", "MQTT 客户端"),
+    ("modbus", "Modbus 主站"),
+    ("docker", "Docker"),
+    ("pairdrop", "PairDrop 传输"),
+    ("ssh_tunnel", "SSH 隧道"),
+    ("forward", "端口转发"),
+    ("download", "下载器"),
+    ("server", "本地服务"),
+    ("port_watch", "端口监控"),
+    ("http_monitor", "HTTP 监控"),
+    ("process", "进程管理"),
+    ("kill", "进程管理"),
+    ("port", "端口扫描"),
+    ("clipboard", "剪贴板"),
+    ("claude", "Claude Code 配置"),
+    ("api_", "API 调试"),
+    ("chat", "AI 对话"),
+    ("resume", "简历"),
+    ("workflow", "工作流"),
+    ("mirror_job", "镜像同步"),
+    ("git", "Git"),
+    ("remote", "Git"),
+    ("branch", "Git"),
+    ("commit", "Git"),
+    ("project", "项目管理"),
+    ("shortcut", "快捷键"),
+    ("tool_window", "工具窗口"),
+    ("settings", "设置"),
+];
+
+fn feature_of(command: &str) -> &'static str {
+    FEATURES
+        .iter()
+        .find(|(keyword, _)| command.contains(keyword))
+        .map(|(_, feature)| *feature)
+        .unwrap_or("其他")
+}
+
+/// 启动时从设置载入开关，并启动定时写库任务
+pub fn init() {
+    let settings: AppSettings = documents::load(&documents::APP_SETTINGS).unwrap_or_default();
+    ENABLED.store(settings.usage_stats_enabled, Ordering::Relaxed);
+
+    tauri::async_runtime::spawn(async {
+        let mut ticker = tokio::time::interval(FLUSH_INTERVAL);
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            flush().await;
+        }
+    });
+}
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// invoke 层调用：开启时累加一次
+pub fn record(command: &str) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let day = chrono::Local::now().format("%Y-%m-%d").to_string();
+    if let Ok(mut pending) = PENDING.lock() {
+        *pending.entry((day, command.to_string())).or_insert(0) += 1;
+    }
+}
+
+/// 把内存计数合并进数据库
+pub async fn flush() {
+    let batch: Vec<((String, String), i64)> = match PENDING.lock() {
+        Ok(mut pending) => pending.drain().collect(),
+        Err(_) => return,
+    };
+    for ((day, command), count) in batch {
+        let result = sqlx::query(
+            "INSERT INTO usage_stats (day, command, count) VALUES (?, ?, ?)
+             ON CONFLICT(day, command) DO UPDATE SET count = count + excluded.count",
+        )
+        .bind(&day)
+        .bind(&command)
+        .bind(count)
+        .execute(pool())
+        .await;
+        if let Err(e) = result {
+            log::warn!("写入使用统计失败: {}", e);
+            return;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandUsage {
+    pub command: String,
+    pub feature: String,
+    pub count: u64,
+    pub last_used_day: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureUsage {
+    pub feature: String,
+    pub count: u64,
+    pub active_days: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageStats {
+    pub enabled: bool,
+    /// 统计区间起始日期（含），None 表示全部
+    pub since: Option<String>,
+    pub total_calls: u64,
+    /// 按调用次数降序
+    pub features: Vec<FeatureUsage>,
+    pub commands: Vec<CommandUsage>,
+}
+
+/// 查询使用统计；days 为空时统计全部记录
+#[tauri::command]
+#[specta::specta]
+pub async fn get_usage_stats(days: Option<u32>) -> AppResult<UsageStats> {
+    flush().await;
+
+    let since = days.map(|d| {
+        (chrono::Local::now() - chrono::Duration::days(d.saturating_sub(1) as i64))
+            .format("%Y-%m-%d")
+            .to_string()
+    });
+    let rows: Vec<(String, String, i64)> =
+        sqlx::query_as("SELECT day, command, count FROM usage_stats WHERE day >= ?")
+            .bind(since.clone().unwrap_or_default())
+            .fetch_all(pool())
+            .await?;
+
+    let mut commands: HashMap<String, CommandUsage> = HashMap::new();
+    let mut features: BTreeMap<&'static str, (u64, HashSet<String>)> = BTreeMap::new();
+    let mut total_calls = 0u64;
+    for (day, command, count) in rows {
+        let count = count.max(0) as u64;
+        total_calls += count;
+        let feature = feature_of(&command);
+
+        let entry = commands
+            .entry(command.clone())
+            .or_insert_with(|| CommandUsage {
+                command,
+                feature: feature.to_string(),
+                count: 0,
+                last_used_day: day.clone(),
+            });
+        entry.count += count;
+        if day > entry.last_used_day {
+            entry.last_used_day = day.clone();
+        }
+
+        let f = features.entry(feature).or_default();
+        f.0 += count;
+        f.1.insert(day);
+    }
+
+    let mut commands: Vec<CommandUsage> = commands.into_values().collect();
+    commands.sort_by(|a, b| b.count.cmp(&a.count).then(a.command.cmp(&b.command)));
+    let mut features: Vec<FeatureUsage> = features
+        .into_iter()
+        .map(|(feature, (count, days))| FeatureUsage {
+            feature: feature.to_string(),
+            count,
+            active_days: days.len() as u32,
+        })
+        .collect();
+    features.sort_by_key(|f| std::cmp::Reverse(f.count));
+
+    Ok(UsageStats {
+        enabled: ENABLED.load(Ordering::Relaxed),
+        since,
+        total_calls,
+        features,
+        commands,
+    })
+}
+
+/// 清空所有使用统计
+#[tauri::command]
+#[specta::specta]
+pub async fn clear_usage_stats() -> AppResult<()> {
+    if let Ok(mut pending) = PENDING.lock() {
+        pending.clear();
+    }
+    sqlx::query("DELETE FROM usage_stats")
+        .execute(pool())
+        .await?;
+    Ok(())
+}
@@ -48,6 +48,9 @@ pub struct Workflow {
     pub cron: String,
     #[serde(default = "default_true")]
     pub enabled: bool,
+    /// 低电量省电模式下仍然按计划执行
+    #[serde(default)]
+    pub allow_on_battery: bool,
     pub nodes: Vec<WorkflowNode>,
     #[serde(default)]
     pub last_run: Option<WorkflowRun>,
@@ -284,7 +287,7 @@ async fn run_node_llm(cfg: &Value, outputs: &HashMap<String, String>) -> AppResu
         "{}/chat/completions",
         provider.base_url.trim_end_matches('/')
     );
-    let client = reqwest::Client::builder()
+    let client = crate::http_client::builder()
         .timeout(Duration::from_secs(120))
         .build()
         .map_err(|e| crate::error::AppError::from(e.to_string()))?;
@@ -355,7 +358,7 @@ async fn run_node_webhook(cfg: &Value, outputs: &HashMap<String, String>) -> App
         .and_then(|v| v.as_str())
         .unwrap_or("");
     let text = render_template(body_template, outputs);
-    let client = reqwest::Client::builder()
+    let client = crate::http_client::builder()
         .timeout(Duration::from_secs(20))
         .build()
         .map_err(|e| crate::error::AppError::from(e.to_string()))?;
@@ -596,6 +599,7 @@ pub fn spawn_scheduler(app: AppHandle) -> SchedulerHandle {
                 .filter(|w| w.enabled && !w.cron.trim().is_empty())
             {
                 let id = wf.id.clone();
+                let allow_on_battery = wf.allow_on_battery;
                 let cron_expr = to_six_field(&wf.cron);
                 let Ok(schedule) = cron::Schedule::from_str(&cron_expr) else {
                     continue;
@@ -609,6 +613,10 @@ pub fn spawn_scheduler(app: AppHandle) -> SchedulerHandle {
                         };
                         let delta = (next - now).to_std().unwrap_or(Duration::from_secs(60));
                         tokio::time::sleep(delta).await;
+                        // 低电量时跳过本次计划执行
+                        if super::power::should_defer(&id, allow_on_battery).await {
+                            continue;
+                        }
                         let _ = execute_workflow(&app_inner, &id).await;
                     }
                 }));
@@ -718,6 +726,7 @@ pub async fn tool_create_workflow(args: &Value, app: &AppHandle) -> AppResult<St
         name,
         cron: cron_expr,
         enabled,
+        allow_on_battery: false,
         nodes,
         last_run: None,
         created_at: Utc::now().to_rfc3339(),
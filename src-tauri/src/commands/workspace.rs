@@ -0,0 +1,280 @@
+// Monorepo 子项目识别：pnpm / npm(yarn) workspaces、cargo workspace members
+//
+// 子项目目录可以直接作为项目添加：统计按目录过滤提交（git log -- .），
+// 项目任务的 cwd 也可以指向子项目目录。
+// 这里只做轻量解析（不引入 yaml/toml 依赖），覆盖常见写法：
+//   - pnpm-workspace.yaml 的 packages 列表
+//   - package.json 的 workspaces（数组或 { packages: [] }）
+//   - Cargo.toml [workspace] 下的 members / exclude 数组
+
+use crate::error::{AppError, AppResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceMember {
+    pub name: String,
+    /// 绝对路径
+    pub path: String,
+    /// 相对仓库根目录的路径（统一使用 `/`）
+    pub relative_path: String,
+    /// "pnpm" | "npm" | "cargo"
+    pub kind: String,
+}
+
+/// 去掉引号与行内注释
+fn clean_item(raw: &str) -> Option<String> {
+    let s = raw
+        .split('#')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .trim_end_matches(',');
+    let s = s.trim().trim_matches(|c| c == '"' || c == '\'').trim();
+    (!s.is_empty()).then(|| s.to_string())
+}
+
+/// pnpm-workspace.yaml：读取 `packages:` 下的 `- xxx` 项
+fn pnpm_patterns(root: &Path) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(root.join("pnpm-workspace.yaml")) else {
+        return Vec::new();
+    };
+    let mut patterns = Vec::new();
+    let mut in_packages = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if !line.starts_with(' ') && !line.starts_with('-') {
+            in_packages = trimmed.starts_with("packages:");
+            continue;
+        }
+        if in_packages {
+            if let Some(item) = trimmed.strip_prefix('-').and_then(clean_item) {
+                patterns.push(item);
+            }
+        }
+    }
+    patterns
+}
+
+/// package.json workspaces
+fn npm_patterns(root: &Path) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(root.join("package.json")) else {
+        return Vec::new();
+    };
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return Vec::new();
+    };
+    let ws = match json.get("workspaces") {
+        Some(serde_json::Value::Array(arr)) => arr.clone(),
+        Some(serde_json::Value::Object(obj)) => obj
+            .get("packages")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+    ws.iter()
+        .filter_map(|v| v.as_str().map(|s| s.to_string()))
+        .collect()
+}
+
+/// 从 `key = [ ... ]`（可跨行）中取出字符串数组
+fn toml_array(section: &str, key: &str) -> Vec<String> {
+    let Some(start) = section.lines().position(|l| {
+        l.trim_start()
+            .strip_prefix(key)
+            .map(|rest| rest.trim_start().starts_with('='))
+            .unwrap_or(false)
+    }) else {
+        return Vec::new();
+    };
+    let mut buf = String::new();
+    for line in section.lines().skip(start) {
+        buf.push_str(line.split('#').next().unwrap_or(""));
+        buf.push('\n');
+        if line.contains(']') {
+            break;
+        }
+    }
+    let inner = buf
+        .split_once('[')
+        .and_then(|(_, rest)| rest.split_once(']'))
+        .map(|(inner, _)| inner)
+        .unwrap_or("");
+    inner.split(',').filter_map(clean_item).collect()
+}
+
+/// Cargo.toml [workspace] members（返回 members 与 exclude）
+fn cargo_patterns(root: &Path) -> (Vec<String>, Vec<String>) {
+    let Ok(content) = fs::read_to_string(root.join("Cargo.toml")) else {
+        return (Vec::new(), Vec::new());
+    };
+    let mut section = String::new();
+    let mut in_workspace = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_workspace = trimmed == "[workspace]";
+            continue;
+        }
+        if in_workspace {
+            section.push_str(line);
+            section.push('\n');
+        }
+    }
+    (
+        toml_array(&section, "members"),
+        toml_array(&section, "exclude"),
+    )
+}
+
+/// 展开成员模式：支持字面路径、末尾 `/*`、末尾 `/**`
+fn expand_pattern(root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+    if let Some(base) = pattern.strip_suffix("/**") {
+        let mut out = Vec::new();
+        collect_dirs(&root.join(base), 4, &mut out);
+        return out;
+    }
+    if let Some(base) = pattern.strip_suffix("/*") {
+        return fs::read_dir(root.join(base))
+            .map(|rd| {
+                rd.filter_map(|e| e.ok())
+                    .map(|e| e.path())
+                    .filter(|p| p.is_dir())
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+    let dir = root.join(pattern);
+    if dir.is_dir() {
+        vec![dir]
+    } else {
+        Vec::new()
+    }
+}
+
+fn collect_dirs(dir: &Path, depth: u32, out: &mut Vec<PathBuf>) {
+    if depth == 0 {
+        return;
+    }
+    let Ok(rd) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in rd.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let name = entry.file_name();
+        if !path.is_dir() || name == "node_modules" || name.to_string_lossy().starts_with('.') {
+            continue;
+        }
+        out.push(path.clone());
+        collect_dirs(&path, depth - 1, out);
+    }
+}
+
+fn member_name(dir: &Path, kind: &str) -> String {
+    let fallback = || {
+        dir.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default()
+    };
+    if kind == "cargo" {
+        fs::read_to_string(dir.join("Cargo.toml"))
+            .ok()
+            .and_then(|c| {
+                c.lines()
+                    .find_map(|l| l.trim().strip_prefix("name").map(|r| r.to_string()))
+                    .and_then(|r| r.trim_start().strip_prefix('=').and_then(clean_item))
+            })
+            .unwrap_or_else(fallback)
+    } else {
+        fs::read_to_string(dir.join("package.json"))
+            .ok()
+            .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+            .and_then(|j| {
+                j.get("name")
+                    .and_then(|n| n.as_str())
+                    .map(|s| s.to_string())
+            })
+            .unwrap_or_else(fallback)
+    }
+}
+
+fn resolve_members(
+    root: &Path,
+    kind: &str,
+    manifest: &str,
+    patterns: &[String],
+    extra_excludes: &[String],
+    out: &mut BTreeMap<String, WorkspaceMember>,
+) {
+    let (excludes, includes): (Vec<&String>, Vec<&String>) =
+        patterns.iter().partition(|p| p.starts_with('!'));
+    let mut excluded: Vec<PathBuf> = excludes
+        .iter()
+        .flat_map(|p| expand_pattern(root, p.trim_start_matches('!')))
+        .collect();
+    excluded.extend(extra_excludes.iter().flat_map(|p| expand_pattern(root, p)));
+
+    for pattern in includes {
+        for dir in expand_pattern(root, pattern) {
+            if excluded.contains(&dir) || !dir.join(manifest).is_file() {
+                continue;
+            }
+            let relative_path = dir
+                .strip_prefix(root)
+                .unwrap_or(&dir)
+                .to_string_lossy()
+                .replace('\\', "/");
+            out.entry(relative_path.clone())
+                .or_insert_with(|| WorkspaceMember {
+                    name: member_name(&dir, kind),
+                    path: dir.to_string_lossy().to_string(),
+                    relative_path,
+                    kind: kind.to_string(),
+                });
+        }
+    }
+}
+
+/// 列出 monorepo 的子项目（不是 workspace 时返回空列表）
+#[tauri::command]
+#[specta::specta]
+pub async fn list_workspace_members(path: String) -> AppResult<Vec<WorkspaceMember>> {
+    let root = PathBuf::from(&path);
+    if !root.is_dir() {
+        return Err(AppError::invalid(format!("目录不存在: {}", path)));
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let mut members = BTreeMap::new();
+
+        let pnpm = pnpm_patterns(&root);
+        if !pnpm.is_empty() {
+            resolve_members(&root, "pnpm", "package.json", &pnpm, &[], &mut members);
+        } else {
+            let npm = npm_patterns(&root);
+            resolve_members(&root, "npm", "package.json", &npm, &[], &mut members);
+        }
+
+        let (cargo, cargo_exclude) = cargo_patterns(&root);
+        resolve_members(
+            &root,
+            "cargo",
+            "Cargo.toml",
+            &cargo,
+            &cargo_exclude,
+            &mut members,
+        );
+
+        members.into_values().collect()
+    })
+    .await
+    .map_err(|e| AppError::internal(e.to_string()))
+}
@@ -0,0 +1,436 @@
+// 提权操作代理：需要管理员权限的少数操作（结束其他用户的进程、绑定 1024 以下端口）
+// 不要求整个应用以管理员身份运行，而是以提权方式重新启动自身可执行文件，只执行一个限定的操作。
+//
+// 流程：
+// - 主进程把操作序列化为 base64(JSON)，通过 UAC（PowerShell Start-Process -Verb RunAs）/
+//   osascript（with administrator privileges）/ pkexec 启动 `<exe> --codeshelf-elevated <op> <结果文件>`
+// - 助手进程在 run() 最开始识别该参数，执行操作后把结果写入结果文件并退出，不会初始化 Tauri
+// - 端口中继是常驻操作：绑定成功后先写结果，然后一直转发到 127.0.0.1:target_port，
+//   直到出现 `<结果文件>.stop` 或主进程退出
+// 助手只接受 ElevatedOp 中定义的操作，不执行任意命令。
+// 结果文件放在当前用户数据目录下权限为 0700 的 elevated/ 中，助手以 O_EXCL|O_NOFOLLOW 创建文件，
+// 避免其他用户预先放置符号链接让提权进程覆盖任意文件。助手以 root 运行时文件归 root 所有，
+// 因此权限为 0644，由目录的 0700 限制其他用户访问，主进程才能读回结果。端口中继只允许监听回环地址。
+
+use crate::error::{AppError, AppResult};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 助手进程的命令行标记
+const HELPER_FLAG: &str = "--codeshelf-elevated";
+
+/// 端口中继同时转发的最大连接数
+const MAX_RELAY_CONNECTIONS: usize = 64;
+
+/// 可以提权执行的操作
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum ElevatedOp {
+    /// 结束进程
+    #[serde(rename_all = "camelCase")]
+    KillProcess { pid: u32, force: bool },
+    /// 监听低位端口并转发到本机 target_port
+    #[serde(rename_all = "camelCase")]
+    PortRelay {
+        listen_host: String,
+        listen_port: u16,
+        target_port: u16,
+        /// 主进程 PID，主进程退出后中继自动结束
+        parent_pid: u32,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct HelperResult {
+    success: bool,
+    message: String,
+}
+
+// ============== 助手进程 ==============
+
+/// 以助手模式启动时执行操作并退出进程；普通启动返回 false
+pub fn handle_helper_args() -> bool {
+    let args: Vec<String> = std::env::args().collect();
+    let Some(pos) = args.iter().position(|a| a == HELPER_FLAG) else {
+        return false;
+    };
+    let (Some(encoded), Some(result_path)) = (args.get(pos + 1), args.get(pos + 2)) else {
+        std::process::exit(2);
+    };
+    let result_path = PathBuf::from(result_path);
+
+    let op = URL_SAFE_NO_PAD
+        .decode(encoded)
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| serde_json::from_slice::<ElevatedOp>(&bytes).map_err(|e| e.to_string()));
+    let result = match op {
+        Ok(ElevatedOp::KillProcess { pid, force }) => helper_kill(pid, force),
+        Ok(ElevatedOp::PortRelay {
+            listen_host,
+            listen_port,
+            target_port,
+            parent_pid,
+        }) => helper_relay(
+            &listen_host,
+            listen_port,
+            target_port,
+            parent_pid,
+            &result_path,
+        ),
+        Err(e) => Err(format!("无效的提权操作: {}", e)),
+    };
+
+    let code = if result.is_ok() { 0 } else { 1 };
+    write_result(&result_path, result);
+    std::process::exit(code);
+}
+
+fn write_result(path: &Path, result: Result<String, String>) {
+    let result = match result {
+        Ok(message) => HelperResult {
+            success: true,
+            message,
+        },
+        Err(message) => HelperResult {
+            success: false,
+            message,
+        },
+    };
+    if let Ok(json) = serde_json::to_vec(&result) {
+        // 先写临时文件再 rename，避免主进程读到半截内容
+        let tmp = path.with_extension("tmp");
+        if create_new_file(&tmp, &json).is_ok() {
+            let _ = std::fs::rename(&tmp, path);
+        }
+    }
+}
+
+/// 只创建新文件且不跟随符号链接：文件已存在（含预先放好的链接）时失败。
+/// 权限 0644：助手以 root 创建时，普通权限的主进程仍可读取
+fn create_new_file(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.custom_flags(libc::O_NOFOLLOW).mode(0o644);
+    }
+    let mut file = options.open(path)?;
+    // 创建时的 mode 受 umask 影响，再按句柄显式设置一次
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o644))?;
+    }
+    file.write_all(content)
+}
+
+/// 中继监听地址只允许回环地址
+fn ensure_loopback(host: &str) -> Result<(), String> {
+    let loopback = host == "localhost" || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback());
+    if loopback {
+        Ok(())
+    } else {
+        Err(format!("提权中继只能监听回环地址，不支持 {}", host))
+    }
+}
+
+fn helper_kill(pid: u32, force: bool) -> Result<String, String> {
+    #[cfg(target_os = "windows")]
+    let output = {
+        use std::os::windows::process::CommandExt;
+        let mut cmd = std::process::Command::new("taskkill");
+        cmd.creation_flags(0x08000000);
+        if force {
+            cmd.arg("/F");
+        }
+        cmd.args(["/PID", &pid.to_string()]).output()
+    };
+    #[cfg(not(target_os = "windows"))]
+    let output = std::process::Command::new("kill")
+        .arg(if force { "-9" } else { "-15" })
+        .arg(pid.to_string())
+        .output();
+
+    match output {
+        Ok(o) if o.status.success() => Ok(format!("已结束进程 {}", pid)),
+        Ok(o) => Err(String::from_utf8_lossy(&o.stderr).trim().to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// 端口中继：绑定成功后写入结果，之后一直运行，只在停止 / 主进程退出时结束进程
+fn helper_relay(
+    host: &str,
+    listen_port: u16,
+    target_port: u16,
+    parent_pid: u32,
+    result_path: &Path,
+) -> Result<String, String> {
+    use std::net::{TcpListener, TcpStream};
+
+    ensure_loopback(host)?;
+    let listener = TcpListener::bind((host, listen_port))
+        .map_err(|e| format!("绑定 {}:{} 失败: {}", host, listen_port, e))?;
+    write_result(
+        result_path,
+        Ok(format!(
+            "{}:{} -> 127.0.0.1:{}",
+            host, listen_port, target_port
+        )),
+    );
+
+    let stop_file = stop_file_of(result_path);
+    std::thread::spawn(move || {
+        let mut system = sysinfo::System::new();
+        loop {
+            std::thread::sleep(Duration::from_secs(1));
+            let parent_alive = system.refresh_process(sysinfo::Pid::from_u32(parent_pid));
+            if stop_file.exists() || !parent_alive {
+                let _ = std::fs::remove_file(&stop_file);
+                std::process::exit(0);
+            }
+        }
+    });
+
+    let active = Arc::new(AtomicUsize::new(0));
+    for inbound in listener.incoming().flatten() {
+        if active.fetch_add(1, Ordering::SeqCst) >= MAX_RELAY_CONNECTIONS {
+            active.fetch_sub(1, Ordering::SeqCst);
+            drop(inbound);
+            continue;
+        }
+        let active = active.clone();
+        std::thread::spawn(move || {
+            let _guard = ConnectionGuard(active);
+            let Ok(outbound) = TcpStream::connect(("127.0.0.1", target_port)) else {
+                return;
+            };
+            let (Ok(mut in_read), Ok(mut out_write)) = (inbound.try_clone(), outbound.try_clone())
+            else {
+                return;
+            };
+            let (mut out_read, mut in_write) = (outbound, inbound);
+            let upstream = std::thread::spawn(move || {
+                let _ = std::io::copy(&mut in_read, &mut out_write);
+                let _ = out_write.shutdown(std::net::Shutdown::Write);
+            });
+            let _ = std::io::copy(&mut out_read, &mut in_write);
+            let _ = in_write.shutdown(std::net::Shutdown::Write);
+            let _ = upstream.join();
+        });
+    }
+    Err("监听已关闭".to_string())
+}
+
+/// 中继连接结束时释放计数
+struct ConnectionGuard(Arc<AtomicUsize>);
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// ============== 主进程 ==============
+
+/// 结果文件目录：当前用户数据目录下的 elevated/，Unix 上权限固定为 0700
+fn result_dir() -> AppResult<PathBuf> {
+    let dir = crate::storage::get_storage_config()?
+        .data_dir
+        .join("elevated");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+        std::fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(&dir)?;
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o700))?;
+    }
+    #[cfg(not(unix))]
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// 读取助手写回的结果；文件还不存在或内容不完整时返回 None
+fn read_result(path: &Path) -> Option<HelperResult> {
+    let content = std::fs::read(path).ok()?;
+    serde_json::from_slice(&content).ok()
+}
+
+fn stop_file_of(result_path: &Path) -> PathBuf {
+    result_path.with_extension("stop")
+}
+
+/// 提权运行的句柄，常驻操作（端口中继）通过 stop() 结束
+#[derive(Debug, Clone)]
+pub struct ElevatedHandle {
+    result_path: PathBuf,
+}
+
+impl ElevatedHandle {
+    /// 通知常驻助手退出
+    pub fn stop(&self) -> AppResult<()> {
+        std::fs::write(stop_file_of(&self.result_path), b"stop")?;
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn spawn_elevated(exe: &Path, args: &[String]) -> AppResult<tokio::process::Child> {
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+    let quote = |s: &str| format!("'{}'", s.replace('\'', "''"));
+    // Start-Process 按空格拼接参数，带空格的参数需要再包一层双引号
+    let arg_list = args
+        .iter()
+        .map(|a| quote(&format!("\"{}\"", a)))
+        .collect::<Vec<_>>()
+        .join(",");
+    let script = format!(
+        "Start-Process -FilePath {} -ArgumentList {} -Verb RunAs -WindowStyle Hidden -ErrorAction Stop",
+        quote(&exe.to_string_lossy()),
+        arg_list
+    );
+    tokio::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .creation_flags(CREATE_NO_WINDOW)
+        .spawn()
+        .map_err(|e| AppError::from(format!("启动提权进程失败: {}", e)))
+}
+
+#[cfg(target_os = "macos")]
+fn spawn_elevated(exe: &Path, args: &[String]) -> AppResult<tokio::process::Child> {
+    let quote = |s: &str| format!("'{}'", s.replace('\'', "'\\''"));
+    let command = std::iter::once(exe.to_string_lossy().to_string())
+        .chain(args.iter().cloned())
+        .map(|s| quote(&s))
+        .collect::<Vec<_>>()
+        .join(" ");
+    // 放到后台运行，常驻的端口中继不会阻塞 osascript
+    let shell = format!("{} > /dev/null 2>&1 &", command);
+    let script = format!(
+        "do shell script \"{}\" with administrator privileges",
+        shell.replace('\\', "\\\\").replace('"', "\\\"")
+    );
+    tokio::process::Command::new("osascript")
+        .args(["-e", &script])
+        .spawn()
+        .map_err(|e| AppError::from(format!("启动提权进程失败: {}", e)))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn spawn_elevated(exe: &Path, args: &[String]) -> AppResult<tokio::process::Child> {
+    tokio::process::Command::new("pkexec")
+        .arg(exe)
+        .args(args)
+        .spawn()
+        .map_err(|e| AppError::from(format!("启动 pkexec 失败（需要安装 polkit）: {}", e)))
+}
+
+/// 以管理员权限执行一个操作，等待助手写回结果（含用户在授权对话框中的等待时间）
+pub(crate) async fn run_elevated(
+    op: &ElevatedOp,
+    timeout: Duration,
+) -> AppResult<(String, ElevatedHandle)> {
+    if let ElevatedOp::PortRelay { listen_host, .. } = op {
+        ensure_loopback(listen_host).map_err(AppError::invalid)?;
+    }
+    let exe = std::env::current_exe()?;
+    let dir = result_dir()?;
+    let result_path = dir.join(format!("{}.json", crate::commands::toolbox::generate_id()));
+    let encoded = URL_SAFE_NO_PAD.encode(serde_json::to_vec(op)?);
+
+    let args = vec![
+        HELPER_FLAG.to_string(),
+        encoded,
+        result_path.to_string_lossy().to_string(),
+    ];
+    let mut child = spawn_elevated(&exe, &args)?;
+
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        if let Some(result) = read_result(&result_path) {
+            let _ = std::fs::remove_file(&result_path);
+            return if result.success {
+                Ok((result.message, ElevatedHandle { result_path }))
+            } else {
+                Err(AppError::from(result.message))
+            };
+        }
+        // 启动器非正常退出且没有结果：多数是用户取消了授权
+        if let Ok(Some(status)) = child.try_wait() {
+            if !status.success() && !result_path.exists() {
+                return Err(AppError::from("已取消管理员授权或提权失败".to_string()));
+            }
+        }
+        if tokio::time::Instant::now() >= deadline {
+            let _ = child.start_kill();
+            return Err(AppError::from("等待管理员授权超时".to_string()));
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_result_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("op.json");
+        write_result(&path, Ok("done".to_string()));
+
+        let result = read_result(&path).expect("结果文件应可读取");
+        assert!(result.success);
+        assert_eq!(result.message, "done");
+        assert!(!path.with_extension("tmp").exists());
+    }
+
+    #[test]
+    fn test_failure_result() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("op.json");
+        write_result(&path, Err("denied".to_string()));
+
+        let result = read_result(&path).unwrap();
+        assert!(!result.success);
+        assert_eq!(result.message, "denied");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_result_file_readable_by_owner_group_other() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("op.json");
+        write_result(&path, Ok("done".to_string()));
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+        // 以 root 写入时主进程要靠 other 位读取
+        assert_eq!(mode & 0o444, 0o444);
+        assert_eq!(mode & 0o022, 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_create_refuses_planted_symlink() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("target");
+        std::fs::write(&target, b"original").unwrap();
+        let link = dir.path().join("op.tmp");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        assert!(create_new_file(&link, b"evil").is_err());
+        assert_eq!(std::fs::read(&target).unwrap(), b"original");
+    }
+}
@@ -0,0 +1,278 @@
+// 收藏项目的系统集成：托盘「收藏项目」子菜单、Windows 任务栏跳转列表、macOS Dock 菜单。
+//
+// 三处都列出最近打开的前 MAX_FAVORITES 个收藏项目，点击即用项目绑定的编辑器打开。
+// 收藏 / 删除 / 重命名 / 打开项目后调用 refresh() 重建：
+//   - 托盘：重新生成菜单并 set_menu
+//   - Windows：跳转列表条目以 `--open-favorite <id>` 启动 exe，由单实例插件转交给已运行的实例
+//   - macOS：给 tao 的 NSApplicationDelegate 补上 applicationDockMenu:，菜单每次弹出时按缓存现建
+
+use crate::commands;
+use crate::error::AppResult;
+use crate::storage::db::pool;
+use once_cell::sync::OnceCell;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+/// 托盘菜单项 id 前缀，后接项目 id
+pub const MENU_ID_PREFIX: &str = "favorite:";
+
+/// 跳转列表启动参数
+const OPEN_FAVORITE_ARG: &str = "--open-favorite";
+
+/// 菜单中最多列出的收藏项目数
+const MAX_FAVORITES: i64 = 10;
+
+static APP: OnceCell<AppHandle> = OnceCell::new();
+
+/// 最近一次刷新得到的收藏列表（macOS Dock 菜单按需读取）
+static FAVORITES: Mutex<Vec<FavoriteEntry>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone)]
+pub struct FavoriteEntry {
+    pub id: String,
+    pub name: String,
+}
+
+/// 启动时调用：记录 AppHandle、挂 Dock 菜单、处理跳转列表带来的启动参数，并首次生成菜单
+pub fn init(app: &AppHandle) {
+    let _ = APP.set(app.clone());
+
+    #[cfg(target_os = "macos")]
+    {
+        let _ = app.run_on_main_thread(dock::install);
+    }
+
+    let args: Vec<String> = std::env::args().collect();
+    handle_args(app, &args);
+    refresh();
+}
+
+/// 收藏相关数据变化后调用；init 之前调用时忽略
+pub fn refresh() {
+    let Some(app) = APP.get().cloned() else {
+        return;
+    };
+    tauri::async_runtime::spawn(async move {
+        let favorites = match load_favorites().await {
+            Ok(list) => list,
+            Err(e) => {
+                log::warn!("读取收藏项目失败: {}", e);
+                return;
+            }
+        };
+        if let Ok(mut cache) = FAVORITES.lock() {
+            *cache = favorites.clone();
+        }
+
+        match crate::app_setup::build_tray_menu(&app, &favorites) {
+            Ok(menu) => {
+                if let Some(tray) = app.tray_by_id(crate::app_setup::TRAY_ID) {
+                    let _ = tray.set_menu(Some(menu));
+                }
+            }
+            Err(e) => log::warn!("重建托盘菜单失败: {}", e),
+        }
+
+        #[cfg(target_os = "windows")]
+        std::thread::spawn(move || {
+            if let Err(e) = jump_list::update(&favorites) {
+                log::warn!("更新跳转列表失败: {}", e);
+            }
+        });
+    });
+}
+
+async fn load_favorites() -> AppResult<Vec<FavoriteEntry>> {
+    let rows: Vec<(String, String)> = sqlx::query_as(
+        "SELECT id, name FROM projects WHERE is_favorite = 1
+         ORDER BY last_opened IS NULL, last_opened DESC, name COLLATE NOCASE
+         LIMIT ?",
+    )
+    .bind(MAX_FAVORITES)
+    .fetch_all(pool())
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(id, name)| FavoriteEntry { id, name })
+        .collect())
+}
+
+/// 处理命令行参数中的 `--open-favorite <id>`（首次启动与单实例转交共用）
+pub fn handle_args(app: &AppHandle, args: &[String]) {
+    if let Some(pos) = args.iter().position(|a| a == OPEN_FAVORITE_ARG) {
+        if let Some(id) = args.get(pos + 1) {
+            open_favorite(app, id);
+        }
+    }
+}
+
+/// 用项目绑定的编辑器（否则默认编辑器）打开收藏项目
+pub fn open_favorite(app: &AppHandle, id: &str) {
+    let app = app.clone();
+    let id = id.to_string();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = open_project_in_editor(&id).await {
+            log::warn!("打开收藏项目失败: {}", e);
+            commands::settings::push_notification(
+                &app,
+                "system",
+                "error",
+                "打开收藏项目失败",
+                &e.to_string(),
+            )
+            .await;
+        }
+    });
+}
+
+async fn open_project_in_editor(id: &str) -> AppResult<()> {
+    let row: Option<(String, Option<String>)> =
+        sqlx::query_as("SELECT path, editor_id FROM projects WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool())
+            .await?;
+    let (path, editor_id) = row.ok_or("项目不存在")?;
+
+    let editors = commands::settings::get_editors().await.unwrap_or_default();
+    let editor = editor_id
+        .and_then(|eid| editors.iter().find(|e| e.id == eid))
+        .or_else(|| editors.iter().find(|e| e.is_default))
+        .map(|e| e.path.clone());
+
+    if let Some(w) = commands::system::open_in_editor(path, editor).await? {
+        return Err(w.message.into());
+    }
+    // update_last_opened 内部会触发 refresh()
+    commands::project::update_last_opened(id.to_string()).await?;
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+mod jump_list {
+    use super::{FavoriteEntry, OPEN_FAVORITE_ARG};
+    use windows::core::{Interface, HSTRING, PCWSTR};
+    use windows::Win32::Storage::EnhancedStorage::PKEY_Title;
+    use windows::Win32::System::Com::StructuredStorage::PROPVARIANT;
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED,
+    };
+    use windows::Win32::UI::Shell::Common::{IObjectArray, IObjectCollection};
+    use windows::Win32::UI::Shell::PropertiesSystem::IPropertyStore;
+    use windows::Win32::UI::Shell::{
+        DestinationList, EnumerableObjectCollection, ICustomDestinationList, IShellLinkW, ShellLink,
+    };
+
+    /// 重写「收藏项目」分类；没有收藏时删除整个自定义列表
+    pub fn update(favorites: &[FavoriteEntry]) -> windows::core::Result<()> {
+        let exe = HSTRING::from(std::env::current_exe().unwrap_or_default().as_path());
+        unsafe {
+            let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+            let list: ICustomDestinationList =
+                CoCreateInstance(&DestinationList, None, CLSCTX_INPROC_SERVER)?;
+            if favorites.is_empty() {
+                return list.DeleteList(PCWSTR::null());
+            }
+
+            let mut slots = 0u32;
+            let _removed: IObjectArray = list.BeginList(&mut slots)?;
+            let collection: IObjectCollection =
+                CoCreateInstance(&EnumerableObjectCollection, None, CLSCTX_INPROC_SERVER)?;
+            for fav in favorites.iter().take(slots as usize) {
+                let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+                link.SetPath(&exe)?;
+                link.SetArguments(&HSTRING::from(format!(
+                    "{} \"{}\"",
+                    OPEN_FAVORITE_ARG, fav.id
+                )))?;
+                link.SetDescription(&HSTRING::from(fav.name.as_str()))?;
+                link.SetIconLocation(&exe, 0)?;
+
+                // 跳转列表显示的标题取自 PKEY_Title
+                let store: IPropertyStore = link.cast()?;
+                store.SetValue(&PKEY_Title, &PROPVARIANT::from(fav.name.as_str()))?;
+                store.Commit()?;
+                collection.AddObject(&link)?;
+            }
+
+            let items: IObjectArray = collection.cast()?;
+            list.AppendCategory(&HSTRING::from("收藏项目"), &items)?;
+            list.CommitList()
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod dock {
+    use super::FAVORITES;
+    use objc2::rc::Retained;
+    use objc2::runtime::{AnyClass, AnyObject, Imp, Sel};
+    use objc2::sel;
+    use objc2_app_kit::{NSApplication, NSMenu, NSMenuItem};
+    use objc2_foundation::{MainThreadMarker, NSString};
+
+    /// 给应用委托类动态加上 applicationDockMenu: 与点击回调（只在主线程调用）
+    pub fn install() {
+        let Some(mtm) = MainThreadMarker::new() else {
+            return;
+        };
+        let Some(delegate) = NSApplication::sharedApplication(mtm).delegate() else {
+            return;
+        };
+        let object: &AnyObject = AsRef::<AnyObject>::as_ref(&*delegate);
+        let class = object.class() as *const AnyClass as *mut AnyClass;
+
+        unsafe {
+            let dock_menu: unsafe extern "C-unwind" fn(
+                &AnyObject,
+                Sel,
+                *mut AnyObject,
+            ) -> *mut NSMenu = dock_menu;
+            let open_favorite: unsafe extern "C-unwind" fn(&AnyObject, Sel, *mut NSMenuItem) =
+                open_favorite;
+            let _ = objc2::ffi::class_addMethod(
+                class,
+                sel!(applicationDockMenu:),
+                std::mem::transmute::<_, Imp>(dock_menu),
+                c"@@:@".as_ptr(),
+            );
+            let _ = objc2::ffi::class_addMethod(
+                class,
+                sel!(codeshelfOpenFavorite:),
+                std::mem::transmute::<_, Imp>(open_favorite),
+                c"v@:@".as_ptr(),
+            );
+        }
+    }
+
+    unsafe extern "C-unwind" fn dock_menu(
+        this: &AnyObject,
+        _sel: Sel,
+        _sender: *mut AnyObject,
+    ) -> *mut NSMenu {
+        let mtm = MainThreadMarker::new_unchecked();
+        let menu = NSMenu::new(mtm);
+        let favorites = FAVORITES.lock().map(|f| f.clone()).unwrap_or_default();
+        for (index, fav) in favorites.iter().enumerate() {
+            let item = NSMenuItem::new(mtm);
+            item.setTitle(&NSString::from_str(&fav.name));
+            item.setTag(index as isize);
+            item.setTarget(Some(this));
+            item.setAction(Some(sel!(codeshelfOpenFavorite:)));
+            menu.addItem(&item);
+        }
+        Retained::autorelease_return(menu)
+    }
+
+    unsafe extern "C-unwind" fn open_favorite(_this: &AnyObject, _sel: Sel, item: *mut NSMenuItem) {
+        let Some(item) = item.as_ref() else {
+            return;
+        };
+        let id = FAVORITES
+            .lock()
+            .ok()
+            .and_then(|f| f.get(item.tag() as usize).map(|e| e.id.clone()));
+        if let (Some(app), Some(id)) = (super::APP.get(), id) {
+            super::open_favorite(app, &id);
+        }
+    }
+}
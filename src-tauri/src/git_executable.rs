@@ -0,0 +1,202 @@
+// git 可执行文件覆盖
+//
+// 设置中的 git_executable 是全局覆盖，项目上的 git_executable 优先于全局；都为空时使用 PATH 中的 git。
+// 值是一条命令行：可执行文件路径（含空格时用引号括起），或带包装程序的写法，如 `wsl git`、
+// `"D:\PortableGit\bin\git.exe"`。包装程序为 wsl 时，`-C` 的 Windows 路径转成 /mnt/<盘符>/... 形式；
+// git 输出中的路径仍是 WSL 内的路径。
+// 覆盖配置缓存在内存中，启动、保存设置、修改项目覆盖或项目路径时刷新，
+// run_git_command 等同步调用不查库。
+
+use crate::error::{AppError, AppResult};
+use crate::storage::db::pool;
+use once_cell::sync::Lazy;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::RwLock;
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// 解析后的 git 命令行
+#[derive(Debug, Clone)]
+pub struct GitExecutable {
+    program: String,
+    /// 包装程序之后、git 参数之前的部分，如 `wsl git` 中的 `git`
+    prefix_args: Vec<String>,
+}
+
+#[derive(Default)]
+struct Overrides {
+    global: Option<GitExecutable>,
+    /// (项目路径, 覆盖)
+    projects: Vec<(PathBuf, GitExecutable)>,
+}
+
+static OVERRIDES: Lazy<RwLock<Overrides>> = Lazy::new(|| RwLock::new(Overrides::default()));
+
+/// 按空白拆分命令行，双引号内的空白保留
+fn split_command_line(spec: &str) -> AppResult<Vec<String>> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut has_token = false;
+    for c in spec.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                has_token = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if has_token {
+                    parts.push(std::mem::take(&mut current));
+                    has_token = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_token = true;
+            }
+        }
+    }
+    if quoted {
+        return Err(AppError::invalid(format!("git 路径的引号不成对: {}", spec)));
+    }
+    if has_token {
+        parts.push(current);
+    }
+    Ok(parts)
+}
+
+impl GitExecutable {
+    /// 空串返回 None。整串是一个存在的文件时按路径处理（路径含空格也无需引号）
+    pub fn parse(spec: &str) -> AppResult<Option<Self>> {
+        let spec = spec.trim();
+        if spec.is_empty() {
+            return Ok(None);
+        }
+        if Path::new(spec).is_file() {
+            return Ok(Some(Self {
+                program: spec.to_string(),
+                prefix_args: Vec::new(),
+            }));
+        }
+        let mut parts = split_command_line(spec)?.into_iter();
+        let program = parts
+            .next()
+            .ok_or_else(|| AppError::invalid("git 路径不能为空"))?;
+        Ok(Some(Self {
+            program,
+            prefix_args: parts.collect(),
+        }))
+    }
+
+    fn is_wsl(&self) -> bool {
+        let name = Path::new(&self.program)
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        name == "wsl"
+    }
+
+    /// 新建子进程，已带上包装参数
+    pub fn command(&self) -> Command {
+        let mut cmd = Command::new(&self.program);
+        cmd.args(&self.prefix_args);
+        cmd
+    }
+
+    /// 传给 `-C` 的仓库路径
+    pub fn repo_path(&self, path: &str) -> String {
+        if self.is_wsl() {
+            to_wsl_path(path)
+        } else {
+            path.to_string()
+        }
+    }
+}
+
+/// `C:\code\app` → `/mnt/c/code/app`；不是盘符路径时只把反斜杠换成正斜杠
+fn to_wsl_path(path: &str) -> String {
+    let normalized = path.replace('\\', "/");
+    let mut chars = normalized.chars();
+    match (chars.next(), chars.next()) {
+        (Some(drive), Some(':')) if drive.is_ascii_alphabetic() => {
+            format!("/mnt/{}{}", drive.to_ascii_lowercase(), &normalized[2..])
+        }
+        _ => normalized,
+    }
+}
+
+/// 当前对 path 生效的覆盖：项目覆盖（取包含 path 的最深项目）优先，其次全局
+pub fn resolve(path: Option<&str>) -> Option<GitExecutable> {
+    let overrides = OVERRIDES.read().unwrap_or_else(|e| e.into_inner());
+    path.and_then(|path| {
+        let path = Path::new(path);
+        overrides
+            .projects
+            .iter()
+            .filter(|(root, _)| path.starts_with(root))
+            .max_by_key(|(root, _)| root.as_os_str().len())
+            .map(|(_, exe)| exe.clone())
+    })
+    .or_else(|| overrides.global.clone())
+}
+
+/// 执行 `<spec> --version` 验证可用，返回版本号
+pub fn check(spec: &str) -> AppResult<String> {
+    let exe = GitExecutable::parse(spec)?.ok_or_else(|| AppError::invalid("git 路径不能为空"))?;
+    let mut cmd = exe.command();
+    cmd.arg("--version");
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+    let output = cmd
+        .output()
+        .map_err(|e| AppError::invalid(format!("无法运行 {}: {}", spec.trim(), e)))?;
+    let stdout = crate::path_compat::decode_text(&output.stdout);
+    match stdout.trim().strip_prefix("git version ") {
+        Some(version) if output.status.success() => Ok(version.to_string()),
+        _ => Err(AppError::invalid(format!(
+            "{} 不是可用的 git: {}",
+            spec.trim(),
+            crate::path_compat::decode_text(&output.stderr).trim()
+        ))),
+    }
+}
+
+/// 重新读取全局设置与所有项目的覆盖
+pub async fn reload() {
+    let global = match crate::commands::settings::load_app_settings().await {
+        Ok(settings) => settings.git_executable,
+        Err(e) => {
+            log::warn!("读取 git 路径设置失败: {}", e);
+            None
+        }
+    };
+    let rows: Vec<(String, String)> = sqlx::query_as(
+        "SELECT path, git_executable FROM projects \
+         WHERE git_executable IS NOT NULL AND git_executable != ''",
+    )
+    .fetch_all(pool())
+    .await
+    .unwrap_or_else(|e| {
+        log::warn!("读取项目 git 路径失败: {}", e);
+        Vec::new()
+    });
+
+    let parse = |spec: &str| match GitExecutable::parse(spec) {
+        Ok(exe) => exe,
+        Err(e) => {
+            log::warn!("{}", e);
+            None
+        }
+    };
+    let global = global.as_deref().and_then(parse);
+    let projects = rows
+        .into_iter()
+        .filter_map(|(path, spec)| parse(&spec).map(|exe| (PathBuf::from(path), exe)))
+        .collect();
+    *OVERRIDES.write().unwrap_or_else(|e| e.into_inner()) = Overrides { global, projects };
+}
@@ -3,10 +3,13 @@
 // 通过 tauri-specta 注册：调试构建时会把命令签名导出为 src/bindings.ts，供前端类型安全调用。
 
 use crate::commands::{
-    api_chat, chat, chat_bridge, extras, git, project, resume, resume_node_agent, resume_docx,
-    settings, stats, storage_admin, system, toolbox, tools, workflows,
+    api_chat, app_profiles, bulk, chat, chat_bridge, commit_index, compliance, dashboard, deploy,
+    divergence, docs_preview, doctor, extras, git, idle_policy, mirror, operations, path_roots,
+    power, project, project_links, project_relocate, project_tasks, resume, resume_docx,
+    resume_node_agent, runtime_overview, scratchpad, settings, stats, storage_admin, system,
+    terminal, toolbox, tools, updater, usage_stats, workflows, workspace,
 };
-use crate::{keyboard_hook, mcp_gateway};
+use crate::{keyboard_hook, mcp_gateway, shutdown, startup, tool_windows};
 use tauri_specta::{collect_commands, Builder};
 
 pub fn make_builder() -> Builder<tauri::Wry> {
@@ -14,6 +17,7 @@ pub fn make_builder() -> Builder<tauri::Wry> {
         // Git
         git::scan_directory,
         git::get_git_status,
+        git::get_git_queue,
         git::get_commit_history,
         git::get_commit_detail,
         git::get_commit_files,
@@ -23,14 +27,20 @@ pub fn make_builder() -> Builder<tauri::Wry> {
         git::add_remote,
         git::verify_remote_url,
         git::remove_remote,
+        git::convert_remote_protocol,
+        git::rewrite_remote_host,
         git::git_push,
         git::git_pull,
         git::git_fetch,
         git::git_clone,
         git::cancel_git_clone,
+        operations::cancel_operation,
+        operations::list_operations,
         git::sync_to_remote,
+        git::preview_sync_to_remote,
         git::checkout_branch,
         git::create_branch,
+        git::compare_branches,
         git::git_add,
         git::git_unstage,
         git::git_discard_files,
@@ -42,10 +52,32 @@ pub fn make_builder() -> Builder<tauri::Wry> {
         git::get_conflict_file_content,
         git::git_checkout_conflict_version,
         git::git_mark_resolved,
+        git::abort_operation,
         git::git_commit,
         git::git_add_and_commit,
+        git::get_git_identity,
+        git::set_git_identity,
+        git::apply_git_identity_profile,
+        git::list_signing_keys,
+        git::get_signing_config,
+        git::set_signing_config,
+        git::test_commit_signing,
         git::is_git_repo,
         git::git_init,
+        git::list_git_hooks,
+        git::list_git_hook_templates,
+        git::save_git_hook_template,
+        git::delete_git_hook_template,
+        git::install_git_hook,
+        git::uninstall_git_hook,
+        git::set_git_hook_enabled,
+        git::diff_paths,
+        git::diff_revisions,
+        git::get_lfs_info,
+        git::git_lfs_pull,
+        git::git_lfs_fetch,
+        git::git_lfs_prune,
+        git::get_code_owners,
         // Project
         project::get_projects,
         project::create_project,
@@ -60,6 +92,63 @@ pub fn make_builder() -> Builder<tauri::Wry> {
         project::reload_projects,
         project::set_project_editor,
         project::set_project_claude_env,
+        project::set_project_icon,
+        project::clear_project_icon,
+        project::set_project_color,
+        project::set_project_description,
+        project::set_project_git_executable,
+        project::set_project_stats_branch,
+        // Path roots
+        path_roots::list_path_roots,
+        path_roots::add_path_root,
+        path_roots::rename_path_root,
+        path_roots::remove_path_root,
+        path_roots::remap_path_root,
+        // Moved projects
+        project_relocate::relocate_missing_projects,
+        project_relocate::apply_project_relocations,
+        // Bulk project operations
+        bulk::start_bulk_operation,
+        bulk::cancel_bulk_operation,
+        bulk::resume_bulk_operation,
+        bulk::list_bulk_operations,
+        bulk::get_bulk_operation,
+        bulk::delete_bulk_operation,
+        // Project tasks
+        project_tasks::list_project_tasks,
+        project_tasks::save_project_task,
+        project_tasks::delete_project_task,
+        project_tasks::run_project_task,
+        project_tasks::stop_project_task,
+        project_tasks::list_running_project_tasks,
+        project_tasks::get_project_task_output,
+        // Project links
+        project_links::list_project_links,
+        project_links::save_project_link,
+        project_links::delete_project_link,
+        project_links::open_project_link,
+        deploy::list_deploy_targets,
+        deploy::save_deploy_target,
+        deploy::delete_deploy_target,
+        deploy::run_deploy,
+        deploy::list_deploy_history,
+        // Compliance
+        compliance::scan_project_compliance,
+        compliance::get_project_compliance,
+        // Docs preview
+        docs_preview::detect_docs_generators,
+        docs_preview::start_docs_preview,
+        docs_preview::stop_docs_preview,
+        docs_preview::get_docs_previews,
+        // Embedded terminal
+        terminal::create_terminal,
+        terminal::write_terminal,
+        terminal::resize_terminal,
+        terminal::close_terminal,
+        terminal::list_terminals,
+        terminal::get_terminal_scrollback,
+        // Workspace members
+        workspace::list_workspace_members,
         // Stats
         stats::get_dashboard_stats,
         stats::refresh_dashboard_stats,
@@ -68,7 +157,28 @@ pub fn make_builder() -> Builder<tauri::Wry> {
         stats::mark_project_dirty,
         stats::mark_all_projects_dirty,
         stats::has_dirty_stats,
+        commit_index::update_commit_index,
+        commit_index::get_commit_index_status,
+        commit_index::search_all_commits,
         stats::cleanup_stats_cache,
+        stats::get_stats_cache_size,
+        stats::compact_stats_cache,
+        stats::compare_projects_activity,
+        stats::export_heatmap,
+        dashboard::get_dashboard_layout,
+        dashboard::save_dashboard_layout,
+        dashboard::get_dashboard_widget_data,
+        runtime_overview::get_runtime_overview,
+        idle_policy::get_idle_policy_status,
+        // 配置档案
+        app_profiles::list_app_profiles,
+        app_profiles::create_app_profile,
+        app_profiles::rename_app_profile,
+        app_profiles::set_app_profile_projects,
+        app_profiles::delete_app_profile,
+        app_profiles::switch_app_profile,
+        app_profiles::export_app_profile,
+        app_profiles::import_app_profile,
         // System
         system::open_in_explorer,
         system::open_in_editor,
@@ -76,18 +186,44 @@ pub fn make_builder() -> Builder<tauri::Wry> {
         system::open_url,
         system::read_readme,
         system::test_terminal,
+        system::validate_tool_path,
         system::check_git_version,
+        system::check_git_executable,
         system::check_node_version,
+        doctor::run_environment_doctor,
         system::get_app_paths,
         system::clear_logs,
         system::get_cursor_position,
         system::get_arch_status,
+        updater::get_update_channel,
+        updater::set_update_channel,
+        updater::check_for_update,
+        updater::download_update,
+        updater::install_update,
+        updater::schedule_update_install,
+        updater::get_update_schedule,
+        updater::defer_update,
+        power::get_power_status,
         // Toolbox - Scanner
         toolbox::scanner::scan_ports,
         toolbox::scanner::stop_scan,
         toolbox::scanner::get_common_ports,
         toolbox::scanner::check_port,
         toolbox::scanner::scan_local_dev_ports,
+        toolbox::scanner::get_scan_capabilities,
+        // Toolbox - Port Watch
+        toolbox::port_watch::add_port_watch,
+        toolbox::port_watch::update_port_watch,
+        toolbox::port_watch::remove_port_watch,
+        toolbox::port_watch::get_port_watches,
+        toolbox::port_watch::check_port_watch_now,
+        // Toolbox - HTTP Monitor
+        toolbox::http_monitor::add_http_monitor,
+        toolbox::http_monitor::update_http_monitor,
+        toolbox::http_monitor::remove_http_monitor,
+        toolbox::http_monitor::get_http_monitors,
+        toolbox::http_monitor::check_http_monitor_now,
+        toolbox::http_monitor::get_http_monitor_stats,
         // Toolbox - Downloader
         toolbox::downloader::start_download,
         toolbox::downloader::pause_download,
@@ -98,12 +234,53 @@ pub fn make_builder() -> Builder<tauri::Wry> {
         toolbox::downloader::clear_completed_downloads,
         toolbox::downloader::open_download_folder,
         toolbox::downloader::remove_download_task,
+        toolbox::downloader::scan_download,
+        toolbox::virus_scan::get_virus_scanner,
+        toolbox::download_history::get_download_history,
+        toolbox::download_history::get_download_history_stats,
+        toolbox::download_history::clear_download_history,
+        toolbox::download_handoff::get_download_handoff_status,
+        toolbox::download_handoff::regenerate_download_handoff_token,
+        toolbox::download_handoff::import_download_urls,
+        toolbox::metrics::get_metrics_status,
+        toolbox::checksum::compute_file_hashes,
+        toolbox::http_bench::benchmark_http,
+        toolbox::certs::inspect_tls_certificates,
+        toolbox::certs::parse_certificate_file,
+        toolbox::regex_tester::test_regex,
+        toolbox::text_diff::diff_text,
+        toolbox::text_diff::apply_text_patch,
+        toolbox::data_format::validate_document,
+        toolbox::data_format::convert_document,
+        toolbox::data_format::format_json,
+        toolbox::data_format::query_json_path,
+        toolbox::asset_generator::generate_qr_code,
+        toolbox::asset_generator::generate_favicons,
+        toolbox::asset_generator::generate_placeholder_image,
+        toolbox::image_optimizer::optimize_images,
+        toolbox::folder_sync::sync_folder,
+        toolbox::release_assets::list_hosting_tokens,
+        toolbox::release_assets::save_hosting_token,
+        toolbox::release_assets::delete_hosting_token,
+        toolbox::release_assets::list_release_assets,
+        toolbox::release_assets::download_release_asset,
         // Toolbox - Process
         toolbox::process::get_processes,
         toolbox::process::get_port_processes,
         toolbox::process::kill_process,
         toolbox::process::get_system_stats,
         toolbox::process::get_local_port_occupation,
+        toolbox::process::start_port_monitor,
+        toolbox::process::stop_port_monitor,
+        toolbox::port_conflict::kill_and_retry,
+        toolbox::elevated::kill_process_elevated,
+        toolbox::elevated::start_elevated_port_relay,
+        toolbox::elevated::stop_elevated_port_relay,
+        toolbox::elevated::get_elevated_port_relays,
+        toolbox::resource_alerts::get_resource_alert_rules,
+        toolbox::resource_alerts::save_resource_alert_rule,
+        toolbox::resource_alerts::delete_resource_alert_rule,
+        toolbox::resource_alerts::get_current_alerts,
         // Toolbox - Forwarder
         toolbox::forwarder::add_forward_rule,
         toolbox::forwarder::remove_forward_rule,
@@ -127,6 +304,14 @@ pub fn make_builder() -> Builder<tauri::Wry> {
         toolbox::ssh_tunnel::set_ssh_tunnel_group,
         toolbox::ssh_tunnel::test_ssh_tunnel,
         toolbox::ssh_tunnel::test_local_port,
+        // Toolbox - SSH Keys
+        toolbox::ssh_keys::list_ssh_keys,
+        toolbox::ssh_keys::generate_ssh_key,
+        toolbox::ssh_keys::get_ssh_public_key,
+        toolbox::ssh_keys::test_ssh_auth,
+        toolbox::ssh_keys::list_ssh_host_entries,
+        toolbox::ssh_keys::save_ssh_host_entry,
+        toolbox::ssh_keys::remove_ssh_host_entry,
         // Toolbox - Server
         toolbox::server::create_server,
         toolbox::server::start_server,
@@ -136,6 +321,8 @@ pub fn make_builder() -> Builder<tauri::Wry> {
         toolbox::server::get_server,
         toolbox::server::update_server,
         toolbox::server::generate_nginx_config,
+        toolbox::server::export_server_config,
+        toolbox::server::import_server_config,
         // Toolbox - Docker
         toolbox::docker::docker_check_available,
         toolbox::docker::docker_find_dockerfiles,
@@ -191,7 +378,33 @@ pub fn make_builder() -> Builder<tauri::Wry> {
         toolbox::netcat::netcat_clear_messages,
         toolbox::netcat::netcat_disconnect_client,
         toolbox::netcat::netcat_update_auto_send,
+        toolbox::netcat::netcat_set_log_mode,
+        toolbox::netcat::netcat_set_history_limit,
+        toolbox::netcat::netcat_get_log_stats,
         toolbox::netcat::netcat_fetch_http,
+        toolbox::netcat::netcat_set_session_group,
+        toolbox::netcat::netcat_start_group,
+        toolbox::netcat::netcat_stop_group,
+        toolbox::netcat::netcat_send_to_group,
+        toolbox::netcat::netcat_get_group_stats,
+        toolbox::netcat::netcat_get_payloads,
+        toolbox::netcat::netcat_save_payload,
+        toolbox::netcat::netcat_delete_payload,
+        toolbox::netcat::netcat_render_payload,
+        toolbox::netcat::netcat_export_sessions,
+        toolbox::netcat::netcat_import_sessions,
+        toolbox::netcat::mqtt_connect,
+        toolbox::netcat::mqtt_disconnect,
+        toolbox::netcat::mqtt_subscribe,
+        toolbox::netcat::mqtt_unsubscribe,
+        toolbox::netcat::mqtt_publish,
+        toolbox::netcat::mqtt_get_sessions,
+        toolbox::netcat::mqtt_get_messages,
+        toolbox::netcat::mqtt_clear_messages,
+        toolbox::netcat::modbus_request,
+        toolbox::pcap::start_traffic_capture,
+        toolbox::pcap::stop_traffic_capture,
+        toolbox::pcap::get_traffic_captures,
         // Toolbox - Shortcuts
         toolbox::shortcuts::get_shortcuts,
         toolbox::shortcuts::save_shortcuts,
@@ -250,6 +463,11 @@ pub fn make_builder() -> Builder<tauri::Wry> {
         // Storage admin
         storage_admin::list_data_backups,
         storage_admin::restore_from_backup,
+        storage_admin::check_settings_documents,
+        storage_admin::get_settings_migration_log,
+        // Local usage stats
+        usage_stats::get_usage_stats,
+        usage_stats::clear_usage_stats,
         // MCP gateway
         mcp_gateway::mcp_gateway_status,
         mcp_gateway::mcp_gateway_internal_endpoint,
@@ -268,6 +486,17 @@ pub fn make_builder() -> Builder<tauri::Wry> {
         extras::delete_skill,
         extras::list_dir_entries,
         extras::read_mention_file,
+        // Scratchpad
+        scratchpad::list_scratch_notes,
+        scratchpad::get_scratch_note,
+        scratchpad::create_scratch_note,
+        scratchpad::save_scratch_note,
+        scratchpad::rename_scratch_note,
+        scratchpad::delete_scratch_note,
+        scratchpad::list_scratch_note_versions,
+        scratchpad::get_scratch_note_version,
+        scratchpad::restore_scratch_note_version,
+        scratchpad::search_scratch_notes,
         // Workflows
         workflows::workflow_list,
         workflows::workflow_get,
@@ -275,6 +504,18 @@ pub fn make_builder() -> Builder<tauri::Wry> {
         workflows::workflow_delete,
         workflows::workflow_run_now,
         workflows::workflow_set_enabled,
+        // Mirror
+        mirror::mirror_job_list,
+        mirror::mirror_job_save,
+        mirror::mirror_job_delete,
+        mirror::mirror_job_set_enabled,
+        mirror::mirror_job_run_now,
+        divergence::list_divergence_watches,
+        divergence::save_divergence_watch,
+        divergence::delete_divergence_watch,
+        divergence::snooze_divergence_alert,
+        divergence::get_divergence_status,
+        divergence::check_divergence_now,
         // Chat bridge
         chat_bridge::chat_bridge_test,
         // Settings
@@ -295,6 +536,8 @@ pub fn make_builder() -> Builder<tauri::Wry> {
         settings::save_terminal_config,
         settings::get_app_settings,
         settings::save_app_settings,
+        settings::get_read_only_status,
+        settings::set_read_only_mode,
         settings::get_ui_state,
         settings::save_ui_state,
         settings::get_notifications,
@@ -302,6 +545,10 @@ pub fn make_builder() -> Builder<tauri::Wry> {
         settings::add_notification,
         settings::remove_notification,
         settings::clear_notifications,
+        settings::mark_notification_read,
+        settings::mark_all_notifications_read,
+        settings::run_notification_action,
+        settings::set_do_not_disturb,
         settings::get_app_shortcuts,
         settings::save_app_shortcuts,
         settings::get_recommended_template,
@@ -332,6 +579,17 @@ pub fn make_builder() -> Builder<tauri::Wry> {
         resume_node_agent::list_resume_agent_background,
         resume_node_agent::delete_resume_agent_background,
         resume_node_agent::delete_resume_agent_runs,
+        // Startup
+        startup::get_startup_report,
+        // Shutdown
+        shutdown::get_resume_state,
+        shutdown::get_resume_summary,
+        shutdown::request_app_exit,
+        // Tool windows
+        tool_windows::open_tool_window,
+        tool_windows::close_tool_window,
+        tool_windows::list_tool_windows,
+        tool_windows::get_tool_window_context,
         // Keyboard hook
         keyboard_hook::register_global_shortcuts,
         keyboard_hook::unregister_all_global_shortcuts,
@@ -0,0 +1,128 @@
+// 出站 HTTP 客户端工厂：所有 reqwest 客户端都从 builder() 创建，统一应用代理设置。
+//
+// 代理模式（AppSettings.proxy）：
+//   - system：与 reqwest 默认行为相同，读取 HTTP(S)_PROXY / ALL_PROXY / NO_PROXY 以及系统代理
+//   - none：不使用任何代理
+//   - manual：使用指定的 http/https/socks5 代理，bypass 列表中的主机直连
+// 三种模式下回环地址都直连，本地调试服务不会被代理拦下。
+
+use crate::error::{AppError, AppResult};
+use crate::storage::{self, AppSettings, ProxySettings};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use hyper_util::client::proxy::matcher::{Intercept, Matcher};
+use once_cell::sync::Lazy;
+use std::sync::RwLock;
+
+/// 始终直连的主机
+const LOOPBACK_BYPASS: &str = "localhost,127.0.0.0/8,::1";
+
+static PROXY: Lazy<RwLock<ProxySettings>> = Lazy::new(|| {
+    let settings = storage::documents::load::<AppSettings>(&storage::documents::APP_SETTINGS)
+        .map(|s| s.proxy)
+        .unwrap_or_default();
+    RwLock::new(settings)
+});
+
+/// 设置保存后更新代理（之后新建的客户端生效）
+pub fn set_proxy(settings: ProxySettings) {
+    if let Ok(mut guard) = PROXY.write() {
+        *guard = settings;
+    }
+}
+
+/// `*.example.com` 转成 reqwest NoProxy 识别的 `.example.com`
+fn no_proxy_list(bypass: &[String]) -> String {
+    bypass
+        .iter()
+        .map(|h| h.trim())
+        .filter(|h| !h.is_empty())
+        .map(|h| h.strip_prefix('*').unwrap_or(h))
+        .chain(std::iter::once(LOOPBACK_BYPASS))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn is_loopback(url: &reqwest::Url) -> bool {
+    match url.host() {
+        Some(url::Host::Domain(host)) => {
+            host.eq_ignore_ascii_case("localhost")
+                || host.to_ascii_lowercase().ends_with(".localhost")
+        }
+        Some(url::Host::Ipv4(ip)) => ip.is_loopback(),
+        Some(url::Host::Ipv6(ip)) => ip.is_loopback(),
+        None => false,
+    }
+}
+
+/// 代理认证信息：socks 为原始用户名密码，http 代理为 Basic 头
+fn proxy_credentials(intercept: &Intercept) -> Option<(String, String)> {
+    if let Some((user, pass)) = intercept.raw_auth() {
+        return Some((user.to_string(), pass.to_string()));
+    }
+    let encoded = intercept
+        .basic_auth()?
+        .to_str()
+        .ok()?
+        .strip_prefix("Basic ")?;
+    let decoded = String::from_utf8(STANDARD.decode(encoded).ok()?).ok()?;
+    let (user, pass) = decoded.split_once(':')?;
+    Some((user.to_string(), pass.to_string()))
+}
+
+/// 系统代理，回环地址除外。
+/// reqwest 只要添加了任何 Proxy 就不再自动使用系统代理，这里用它内部同一套匹配逻辑（hyper-util）自行实现
+fn system_proxy() -> reqwest::Proxy {
+    let matcher = Matcher::from_system();
+    reqwest::Proxy::custom(move |url| {
+        if is_loopback(url) {
+            return None;
+        }
+        let intercept = matcher.intercept(&url.as_str().parse().ok()?)?;
+        let mut proxy = reqwest::Url::parse(&intercept.uri().to_string()).ok()?;
+        if let Some((user, pass)) = proxy_credentials(&intercept) {
+            proxy.set_username(&user).ok()?;
+            proxy.set_password(Some(&pass)).ok()?;
+        }
+        Some(proxy)
+    })
+}
+
+fn manual_proxy(settings: &ProxySettings) -> AppResult<reqwest::Proxy> {
+    let url = settings
+        .url
+        .as_deref()
+        .map(str::trim)
+        .filter(|u| !u.is_empty())
+        .ok_or("手动代理模式需要填写代理地址")?;
+    let proxy =
+        reqwest::Proxy::all(url).map_err(|e| AppError::invalid(format!("代理地址无效: {}", e)))?;
+    Ok(proxy.no_proxy(reqwest::NoProxy::from_string(&no_proxy_list(
+        &settings.bypass,
+    ))))
+}
+
+/// 校验代理设置（保存前调用）
+pub fn validate_proxy(settings: &ProxySettings) -> AppResult<()> {
+    match settings.mode.as_str() {
+        "system" | "none" => Ok(()),
+        "manual" => manual_proxy(settings).map(|_| ()),
+        other => Err(AppError::invalid(format!("未知的代理模式: {}", other))),
+    }
+}
+
+/// 创建已应用代理设置的 ClientBuilder；调用方继续设置超时、UA 等
+pub fn builder() -> reqwest::ClientBuilder {
+    let settings = PROXY.read().map(|s| s.clone()).unwrap_or_default();
+    let builder = reqwest::Client::builder();
+    match settings.mode.as_str() {
+        "none" => builder.no_proxy(),
+        "manual" => match manual_proxy(&settings) {
+            Ok(proxy) => builder.proxy(proxy),
+            Err(e) => {
+                log::warn!("代理设置无效，改为直连: {}", e);
+                builder.no_proxy()
+            }
+        },
+        _ => builder.proxy(system_proxy()),
+    }
+}
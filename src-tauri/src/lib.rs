@@ -1,22 +1,47 @@
 mod app_setup;
 mod commands;
+mod elevation;
 pub mod error;
+mod favorites_menu;
+mod git_executable;
 mod handlers;
+mod http_client;
 mod keyboard_hook;
 pub mod mcp_gateway;
+mod notification_routing;
+mod path_compat;
+mod read_only;
+mod shutdown;
+mod startup;
 mod storage;
+mod tool_windows;
+mod tray_badge;
 
 use tauri::{Manager, RunEvent};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // 以提权助手身份启动时只执行单个操作后退出
+    if elevation::handle_helper_args() {
+        return;
+    }
+    // 生成带口令的 SSH 密钥时，ssh-keygen 以 SSH_ASKPASS 方式回调本程序读取口令
+    if commands::toolbox::ssh_keys::handle_askpass() {
+        return;
+    }
+    startup::mark_process_start();
     let specta_builder = handlers::make_builder();
+    let invoke_handler = specta_builder.invoke_handler();
 
     tauri::Builder::default()
         // 单实例插件：防止重复打开应用。
         // 开发模式和正式版使用不同的标识符，可以并行运行。
-        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
-            if let Some(window) = app.get_webview_window("main") {
+        .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+            // 任务栏跳转列表点击收藏项目时以 --open-favorite 启动
+            favorites_menu::handle_args(app, &args);
+            // 第二个实例带着 codeshelf-download:// 链接启动时，交给下载器
+            commands::toolbox::download_handoff::handle_links(app, args);
+            if let Some(window) = app.get_webview_window(tool_windows::MAIN_WINDOW) {
                 let _ = window.show();
                 let _ = window.unminimize();
                 let _ = window.set_focus();
@@ -27,23 +52,36 @@ pub fn run() {
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
-        .invoke_handler(specta_builder.invoke_handler())
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_notification::init())
+        // 只读模式在这里统一拦截修改类命令，命令本身无需感知
+        .invoke_handler(move |invoke| {
+            let message = &invoke.message;
+            if let Some(reason) = read_only::check(message.command(), message.payload()) {
+                invoke.resolver.reject(reason);
+                return true;
+            }
+            // 只统计真正分发出去的命令
+            let command = message.command().to_string();
+            let handled = invoke_handler(invoke);
+            if handled {
+                commands::usage_stats::record(&command);
+            }
+            handled
+        })
         .setup(move |app| {
             specta_builder.mount_events(app);
             app_setup::run_setup(app)
         })
-        // 拦截窗口关闭：隐藏到托盘而非退出。
-        .on_window_event(|window, event| {
-            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
-                api.prevent_close();
-                let _ = window.hide();
-            }
-        })
+        // 拦截窗口关闭：主窗口隐藏到托盘而非退出，工具窗口正常关闭。
+        .on_window_event(tool_windows::handle_window_event)
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
         .run(|app, event| {
-            if let RunEvent::Exit = event {
-                keyboard_hook::stop_hook_from_manager(app);
+            match event {
+                RunEvent::ExitRequested { api, .. } => shutdown::on_exit_requested(app, &api),
+                RunEvent::Exit => keyboard_hook::stop_hook_from_manager(app),
+                _ => {}
             }
         });
 }
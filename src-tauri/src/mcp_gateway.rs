@@ -124,7 +124,7 @@ pub async fn mcp_gateway_internal_endpoint() -> AppResult<Option<McpGatewayInter
         Some(u) => u,
         None => return Ok(None),
     };
-    let settings = crate::commands::settings::get_app_settings().await?;
+    let settings = crate::commands::settings::load_app_settings().await?;
     // keys 为空时，网关本身不鉴权（validate_mcp_auth 直接放行），api_key 返回 None
     let api_key = active_mcp_keys(&settings.mcp_gateway_keys)
         .first()
@@ -133,7 +133,7 @@ pub async fn mcp_gateway_internal_endpoint() -> AppResult<Option<McpGatewayInter
 }
 
 pub async fn apply_settings_from_storage() -> AppResult<McpGatewayStatus> {
-    let settings = crate::commands::settings::get_app_settings().await?;
+    let settings = crate::commands::settings::load_app_settings().await?;
     apply_settings(&settings).await
 }
 
@@ -272,7 +272,7 @@ async fn validate_mcp_auth(
     query: &HashMap<String, String>,
     request_id: Option<Value>,
 ) -> Result<(), (StatusCode, Json<JsonRpcResponse>)> {
-    let settings = match crate::commands::settings::get_app_settings().await {
+    let settings = match crate::commands::settings::load_app_settings().await {
         Ok(s) => s,
         Err(e) => {
             return Err((
@@ -498,6 +498,15 @@ async fn tools_call_result(params: Option<Value>) -> Result<Value, JsonRpcError>
         )
     })?;
 
+    // 网关绕过 invoke 层，只读模式需要在这里按同一套规则拦截
+    if let Some(reason) = crate::read_only::check_command("execute_api_endpoint", None) {
+        return Err(json_rpc_error(
+            -32000,
+            "Tool execution failed",
+            Some(json!({ "message": reason })),
+        ));
+    }
+
     let endpoints = list_api_endpoints().await.map_err(internal_error)?;
     let (_, tool_name_map) = build_mcp_tool_index(&endpoints);
     let endpoint_id = tool_name_map.get(&params.name).ok_or_else(|| {
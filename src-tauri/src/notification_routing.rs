@@ -0,0 +1,128 @@
+// 通知路由与免打扰
+//
+// 后台通知按类别（下载、服务、监控、Git、系统）决定去向：
+//   - in_app:    写入通知中心并广播 `notification-added`
+//   - os_toast:  弹出系统通知
+//   - tray_badge: 计入托盘角标的未查看数
+// 未配置的类别三者全部开启。免打扰期间（手动开启或处于某个时段内）不弹系统通知，
+// 其它去向照常；dnd_allow_errors 开启时错误级别的通知仍会弹出。
+
+use crate::error::{AppError, AppResult};
+use crate::storage::{DndSchedule, NotificationRouting};
+use chrono::{DateTime, Datelike, Local, NaiveTime, Timelike};
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+/// 通知类别
+pub const CATEGORIES: &[&str] = &["download", "server", "monitor", "git", "system"];
+
+/// 一条通知的实际去向
+#[derive(Debug, Clone, Copy)]
+pub struct Delivery {
+    pub in_app: bool,
+    pub os_toast: bool,
+    pub tray_badge: bool,
+}
+
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").ok()
+}
+
+/// 时段是否覆盖 now；结束早于开始时跨午夜，跨过的部分按开始那天的星期计算
+fn schedule_covers(schedule: &DndSchedule, now: &DateTime<Local>) -> bool {
+    let (Some(start), Some(end)) = (parse_time(&schedule.start), parse_time(&schedule.end)) else {
+        return false;
+    };
+    let time = NaiveTime::from_hms_opt(now.hour(), now.minute(), 0).unwrap_or_default();
+    let today = now.weekday().num_days_from_sunday() as u8;
+    let yesterday = (today + 6) % 7;
+    let on_day = |day: u8| schedule.days.is_empty() || schedule.days.contains(&day);
+
+    if start <= end {
+        start <= time && time < end && on_day(today)
+    } else {
+        (time >= start && on_day(today)) || (time < end && on_day(yesterday))
+    }
+}
+
+/// 当前是否处于免打扰
+pub fn dnd_active(routing: &NotificationRouting, now: &DateTime<Local>) -> bool {
+    let manual = routing
+        .dnd_until
+        .as_deref()
+        .and_then(|until| DateTime::parse_from_rfc3339(until).ok())
+        .is_some_and(|until| until > *now);
+    manual
+        || routing
+            .dnd_schedules
+            .iter()
+            .any(|s| schedule_covers(s, now))
+}
+
+/// 按类别与免打扰状态计算去向
+pub fn resolve(routing: &NotificationRouting, category: &str, severity: &str) -> Delivery {
+    let mut delivery = routing
+        .routes
+        .iter()
+        .find(|r| r.category == category)
+        .map(|r| Delivery {
+            in_app: r.in_app,
+            os_toast: r.os_toast,
+            tray_badge: r.tray_badge,
+        })
+        .unwrap_or(Delivery {
+            in_app: true,
+            os_toast: true,
+            tray_badge: true,
+        });
+    if delivery.os_toast
+        && dnd_active(routing, &Local::now())
+        && !(routing.dnd_allow_errors && severity == "error")
+    {
+        delivery.os_toast = false;
+    }
+    delivery
+}
+
+/// 保存设置前校验类别与时段格式
+pub fn validate(routing: &NotificationRouting) -> AppResult<()> {
+    if let Some(route) = routing
+        .routes
+        .iter()
+        .find(|r| !CATEGORIES.contains(&r.category.as_str()))
+    {
+        return Err(AppError::invalid(format!(
+            "无效的通知类别: {}",
+            route.category
+        )));
+    }
+    for schedule in &routing.dnd_schedules {
+        if parse_time(&schedule.start).is_none() || parse_time(&schedule.end).is_none() {
+            return Err(AppError::invalid(format!(
+                "免打扰时段格式应为 HH:MM: {}-{}",
+                schedule.start, schedule.end
+            )));
+        }
+        if schedule.days.iter().any(|d| *d > 6) {
+            return Err(AppError::invalid("免打扰星期应为 0（周日）到 6"));
+        }
+    }
+    if let Some(until) = &routing.dnd_until {
+        DateTime::parse_from_rfc3339(until)
+            .map_err(|_| AppError::invalid(format!("无效的免打扰结束时间: {}", until)))?;
+    }
+    Ok(())
+}
+
+/// 弹出系统通知
+pub fn show_os_toast(app: &AppHandle, title: &str, message: &str) {
+    if let Err(e) = app
+        .notification()
+        .builder()
+        .title(title)
+        .body(message)
+        .show()
+    {
+        log::warn!("弹出系统通知失败: {}", e);
+    }
+}
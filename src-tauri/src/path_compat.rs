@@ -0,0 +1,147 @@
+// Windows 长路径与非 ASCII 路径处理
+//
+// - native_path: 供 std::fs 使用。Windows 下接近 MAX_PATH 的绝对路径转为 `\\?\` 扩展路径
+//   （UNC 路径转为 `\\?\UNC\`），其它平台原样返回
+// - display_path: 去掉 `\\?\` 前缀并统一分隔符，传给 git、explorer 等外部程序
+// - git_command / git_in: 所有 git 子进程带上 core.longpaths 与 core.quotepath=false，
+//   中文文件名按 UTF-8 原样输出，不会变成 "\346\226\207" 这样的八进制转义；
+//   使用的 git 按 git_executable 中的全局 / 项目覆盖决定
+// - decode_text: 子进程输出与文本文件转为字符串。有 BOM 按 BOM；合法 UTF-8 直接使用；
+//   否则 Windows 下按系统 ANSI 代码页（中文系统为 GBK）解码
+
+use encoding_rs::Encoding;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// 创建目录时的长度上限是 MAX_PATH - 12（需留出 8.3 文件名的位置），超过即使用扩展路径
+#[cfg(target_os = "windows")]
+const LONG_PATH_THRESHOLD: usize = 248;
+
+const VERBATIM_PREFIX: &str = r"\\?\";
+const VERBATIM_UNC_PREFIX: &str = r"\\?\UNC\";
+
+const GIT_CONFIG_ARGS: &[&str] = &["-c", "core.longpaths=true", "-c", "core.quotepath=false"];
+
+/// 去掉扩展路径前缀；Windows 下统一为反斜杠（explorer 不识别正斜杠）
+pub fn display_path(path: &str) -> String {
+    let path = match path.strip_prefix(VERBATIM_UNC_PREFIX) {
+        Some(rest) => format!(r"\\{}", rest),
+        None => path
+            .strip_prefix(VERBATIM_PREFIX)
+            .unwrap_or(path)
+            .to_string(),
+    };
+    if cfg!(target_os = "windows") {
+        path.replace('/', "\\")
+    } else {
+        path
+    }
+}
+
+/// 供 std::fs 读写的路径
+pub fn native_path(path: impl AsRef<Path>) -> PathBuf {
+    #[cfg(target_os = "windows")]
+    {
+        let path = path.as_ref();
+        let raw = path.to_string_lossy();
+        if raw.starts_with(VERBATIM_PREFIX)
+            || !path.is_absolute()
+            || raw.encode_utf16().count() < LONG_PATH_THRESHOLD
+        {
+            return path.to_path_buf();
+        }
+        // 扩展路径不做任何规范化，这里自行去掉 "." 与 ".."
+        let display = display_path(&raw);
+        let (prefix, rest, root_parts) = match display.strip_prefix(r"\\") {
+            Some(unc) => (VERBATIM_UNC_PREFIX, unc, 2),
+            None => (VERBATIM_PREFIX, display.as_str(), 1),
+        };
+        let mut parts: Vec<&str> = Vec::new();
+        for part in rest.split('\\') {
+            match part {
+                "" | "." => {}
+                ".." if parts.len() > root_parts => {
+                    parts.pop();
+                }
+                ".." => {}
+                part => parts.push(part),
+            }
+        }
+        PathBuf::from(format!("{}{}", prefix, parts.join("\\")))
+    }
+    #[cfg(not(target_os = "windows"))]
+    path.as_ref().to_path_buf()
+}
+
+fn git_for(path: Option<&str>) -> Command {
+    let exe = crate::git_executable::resolve(path);
+    let mut cmd = match &exe {
+        Some(exe) => exe.command(),
+        None => Command::new("git"),
+    };
+    cmd.args(GIT_CONFIG_ARGS);
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+    if let Some(path) = path {
+        let path = display_path(path);
+        let path = match &exe {
+            Some(exe) => exe.repo_path(&path),
+            None => path,
+        };
+        cmd.args(["-C", &path]);
+    }
+    cmd
+}
+
+/// 新建 git 子进程（使用全局覆盖）；Windows 下不弹控制台窗口
+pub fn git_command() -> Command {
+    git_for(None)
+}
+
+/// 在仓库目录中执行的 git 命令（`git -C <path>`），项目设置了覆盖时使用项目的 git
+pub fn git_in(path: &str) -> Command {
+    git_for(Some(path))
+}
+
+#[cfg(target_os = "windows")]
+fn fallback_encoding() -> &'static Encoding {
+    // SAFETY: GetACP 没有参数，只读取系统设置
+    let code_page = unsafe { windows::Win32::Globalization::GetACP() };
+    match code_page {
+        936 => encoding_rs::GBK,
+        950 => encoding_rs::BIG5,
+        932 => encoding_rs::SHIFT_JIS,
+        949 => encoding_rs::EUC_KR,
+        cp => {
+            Encoding::for_label(format!("windows-{}", cp).as_bytes()).unwrap_or(encoding_rs::UTF_8)
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn fallback_encoding() -> &'static Encoding {
+    encoding_rs::UTF_8
+}
+
+/// 字节转字符串，见文件头说明；无法解码的字节替换为 U+FFFD
+pub fn decode_text(bytes: &[u8]) -> String {
+    if let Some((encoding, bom_len)) = Encoding::for_bom(bytes) {
+        return encoding
+            .decode_without_bom_handling(&bytes[bom_len..])
+            .0
+            .into_owned();
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => fallback_encoding()
+            .decode_without_bom_handling(bytes)
+            .0
+            .into_owned(),
+    }
+}
@@ -0,0 +1,356 @@
+// 只读模式：在 invoke 层统一拦截会修改数据或系统状态的命令，用于演示机、共享工作站。
+//
+// 开关与可选密码保存在 AppSettings（read_only_mode / read_only_password_hash），
+// 启动时载入到内存，guard 只查内存标志，不读磁盘。
+// 按允许名单放行：只有 ALLOWED_COMMANDS 中的命令可以执行，新增命令默认被拦截；
+// 个别命令只有带特定参数时才无副作用（如 sync_folder 的 dryRun），单独按参数判断。
+// MCP 网关等绕过 invoke 的入口通过 check_command 复用同一套判断。
+
+use crate::error::{AppError, AppResult};
+use crate::storage::{documents, AppSettings};
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::ipc::InvokeBody;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// 只读模式下允许执行的命令，其余命令一律拒绝。
+/// 只收录查询、预览、诊断以及停止 / 取消类操作；导出文件、写缓存或索引、git 拉取等
+/// 会落盘或改动仓库的命令都不在内。新增命令默认被拦截，确认无副作用后再补进来。
+const ALLOWED_COMMANDS: &[&str] = &[
+    // 项目与统计
+    "get_projects",
+    "reload_projects",
+    "scan_directory",
+    "is_git_repo",
+    "read_readme",
+    "read_mention_file",
+    "list_dir_entries",
+    "list_workspace_members",
+    "list_project_tasks",
+    "list_running_project_tasks",
+    "get_project_task_output",
+    "stop_project_task",
+    "list_project_links",
+    "open_project_link",
+    "list_path_roots",
+    "detect_docs_generators",
+    "get_docs_previews",
+    "stop_docs_preview",
+    "list_deploy_targets",
+    "list_deploy_history",
+    "get_project_compliance",
+    "list_bulk_operations",
+    "get_bulk_operation",
+    "cancel_bulk_operation",
+    "get_dashboard_stats",
+    "get_dashboard_widget_data",
+    "get_dashboard_layout",
+    "has_dirty_stats",
+    "get_stats_cache_size",
+    // 统计缓存由仓库数据推导，重建不改动用户数据，放行以保证仪表盘可用
+    "init_stats_cache",
+    "refresh_dashboard_stats",
+    "refresh_dirty_stats",
+    "compare_projects_activity",
+    "get_usage_stats",
+    "list_terminals",
+    "get_terminal_scrollback",
+    "resize_terminal",
+    "close_terminal",
+    // Git（git_fetch 在 check_command 中单独放行）
+    "get_git_status",
+    "get_branches",
+    "get_remotes",
+    "verify_remote_url",
+    "get_commit_history",
+    "get_commit_detail",
+    "get_commit_files",
+    "get_commit_index_status",
+    "search_commits",
+    "search_all_commits",
+    "compare_branches",
+    "diff_revisions",
+    "diff_paths",
+    "get_conflict_file_content",
+    "get_git_identity",
+    "get_signing_config",
+    "list_signing_keys",
+    "test_commit_signing",
+    "list_git_hooks",
+    "list_git_hook_templates",
+    "get_lfs_info",
+    "get_code_owners",
+    "get_git_queue",
+    "list_operations",
+    "cancel_operation",
+    "cancel_git_clone",
+    "check_git_executable",
+    "check_git_version",
+    "list_divergence_watches",
+    "get_divergence_status",
+    // 进程 / 容器 / 系统
+    "get_processes",
+    "get_port_processes",
+    "get_local_port_occupation",
+    "get_system_stats",
+    "get_runtime_overview",
+    "get_resource_alert_rules",
+    "get_current_alerts",
+    "get_elevated_port_relays",
+    "stop_elevated_port_relay",
+    "get_power_status",
+    "get_idle_policy_status",
+    "get_arch_status",
+    "get_current_platform",
+    "get_app_paths",
+    "get_wsl_config_dir",
+    "run_environment_doctor",
+    "check_node_version",
+    "validate_tool_path",
+    "test_terminal",
+    "docker_check_available",
+    "docker_list_containers",
+    "docker_list_images",
+    "docker_inspect_container_yaml",
+    "docker_find_dockerfiles",
+    "docker_read_dockerfile",
+    "docker_generate_dockerfile_template",
+    "docker_generate_dockerfile_ai",
+    // 服务 / 转发 / 隧道 / 下载
+    "get_servers",
+    "get_server",
+    "stop_server",
+    "generate_nginx_config",
+    "export_server_config",
+    "get_forward_rules",
+    "get_forward_rule",
+    "get_forward_stats",
+    "stop_forwarding",
+    "get_ssh_tunnels",
+    "get_ssh_tunnel",
+    "get_ssh_tunnel_stats",
+    "stop_ssh_tunnel",
+    "test_ssh_tunnel",
+    "test_ssh_auth",
+    "list_ssh_keys",
+    "get_ssh_public_key",
+    "list_ssh_config_hosts",
+    "list_ssh_host_entries",
+    "get_download_tasks",
+    "get_download_task",
+    "pause_download",
+    "cancel_download",
+    "get_download_history",
+    "get_download_history_stats",
+    "open_download_folder",
+    "get_download_handoff_status",
+    "get_virus_scanner",
+    "list_release_assets",
+    "list_hosting_tokens",
+    "get_metrics_status",
+    "mcp_gateway_status",
+    "mcp_gateway_internal_endpoint",
+    "pairdrop_status",
+    "pairdrop_peers",
+    "pairdrop_stop",
+    // 网络工具
+    "get_common_ports",
+    "list_local_ips",
+    "scan_ports",
+    "scan_local_dev_ports",
+    "get_scan_capabilities",
+    "stop_scan",
+    "check_port",
+    "test_local_port",
+    "start_port_monitor",
+    "stop_port_monitor",
+    "get_port_watches",
+    "get_http_monitors",
+    "get_http_monitor_stats",
+    "stop_traffic_capture",
+    "get_traffic_captures",
+    "inspect_tls_certificates",
+    "parse_certificate_file",
+    "netcat_init",
+    "netcat_get_sessions",
+    "netcat_get_session",
+    "netcat_get_messages",
+    "netcat_get_clients",
+    "netcat_get_group_stats",
+    "netcat_get_log_stats",
+    "netcat_get_payloads",
+    "netcat_render_payload",
+    "mqtt_get_sessions",
+    "mqtt_get_messages",
+    // 文本 / 数据工具
+    "diff_text",
+    "apply_text_patch",
+    "format_json",
+    "query_json_path",
+    "convert_document",
+    "validate_document",
+    "test_regex",
+    "compute_file_hashes",
+    "get_clipboard_history",
+    "get_clipboard_settings",
+    "get_cursor_position",
+    // Claude Code 配置
+    "check_all_claude_installations",
+    "check_claude_by_path",
+    "get_claude_installations_cache",
+    "get_claude_config_templates",
+    "get_claude_launch_dirs",
+    "scan_claude_config_dir",
+    "read_claude_config_file",
+    "open_claude_config_dir",
+    "get_config_profiles",
+    "get_quick_config_options",
+    "get_saved_quick_configs",
+    "get_global_memory",
+    // 对话 / API / 简历（只读查询与模型调用，不落盘）
+    "list_chat_sessions",
+    "get_chat_session",
+    "get_chat_history_dir",
+    "list_chat_tasks",
+    "chat_list_tools",
+    "chat_stream",
+    "chat_complete",
+    "chat_cancel",
+    "chat_bridge_test",
+    "list_compactions",
+    "get_compaction",
+    "list_api_chat_sessions",
+    "get_api_chat_session",
+    "list_api_endpoints",
+    "list_api_groups",
+    "build_api_tools",
+    "fetch_api_document_url",
+    "llm_proxy_request",
+    "get_resumes",
+    "get_resume_summary",
+    "get_resume_state",
+    "get_resume_agent_prompt_config",
+    "get_resume_agent_runs",
+    "list_resume_agent_background",
+    "load_resume_agent_background",
+    "read_resume_agent_artifact",
+    "cancel_resume_deep_agent",
+    "get_recommended_template",
+    "list_skills",
+    // 设置与数据
+    "get_app_settings",
+    "get_read_only_status",
+    "set_read_only_mode",
+    "get_update_channel",
+    "get_update_schedule",
+    "check_for_update",
+    "get_terminal_config",
+    "get_editors",
+    "get_labels",
+    "get_categories",
+    "get_app_shortcuts",
+    "get_shortcuts",
+    "get_ai_providers",
+    "get_sensitive_file_patterns",
+    "check_settings_documents",
+    "get_settings_migration_log",
+    "get_startup_report",
+    "list_data_backups",
+    "list_scratch_notes",
+    "get_scratch_note",
+    "list_scratch_note_versions",
+    "get_scratch_note_version",
+    "search_scratch_notes",
+    "list_app_profiles",
+    "workflow_list",
+    "workflow_get",
+    "mirror_job_list",
+    "get_notifications",
+    // 界面自身的状态（布局、已读标记、快捷键注册、复制到剪贴板），不属于用户数据，
+    // 拦截后界面无法正常使用，因此显式放行
+    "get_ui_state",
+    "save_ui_state",
+    "mark_notification_read",
+    "mark_all_notifications_read",
+    "register_global_shortcuts",
+    "unregister_all_global_shortcuts",
+    "write_to_clipboard",
+    // 窗口与外部打开
+    "open_tool_window",
+    "close_tool_window",
+    "list_tool_windows",
+    "get_tool_window_context",
+    "open_in_explorer",
+    "open_url",
+    "request_app_exit",
+];
+
+/// 启动时从设置载入开关
+pub fn init() {
+    let settings: AppSettings = documents::load(&documents::APP_SETTINGS).unwrap_or_default();
+    ENABLED.store(settings.read_only_mode, Ordering::Relaxed);
+    if settings.read_only_mode {
+        log::info!("只读模式已启用");
+    }
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// 只读模式下 invoke 命令被拦截时返回拒绝信息
+pub fn check(command: &str, payload: &InvokeBody) -> Option<String> {
+    let args = match payload {
+        InvokeBody::Json(value) => Some(value),
+        InvokeBody::Raw(_) => None,
+    };
+    check_command(command, args)
+}
+
+/// 按命令名与 JSON 参数判断是否拦截；args 为 None 时按不带参数处理
+pub fn check_command(command: &str, args: Option<&Value>) -> Option<String> {
+    if !is_enabled() {
+        return None;
+    }
+    let allowed = match command {
+        // fetch 只更新远程跟踪分支；pull / push 会改动工作区或远程，与其他 git 写操作一样拦截
+        "git_fetch" => true,
+        "sync_folder" => arg_is_true(args, "dryRun"),
+        _ => ALLOWED_COMMANDS.contains(&command),
+    };
+    (!allowed).then(|| format!("只读模式下不允许执行此操作（{}）", command))
+}
+
+fn arg_is_true(args: Option<&Value>, key: &str) -> bool {
+    args.and_then(|v| v.get(key))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// 生成 argon2id 密码哈希（PHC 字符串，盐取自系统随机源）
+pub fn hash_password(password: &str) -> AppResult<String> {
+    let mut salt = [0u8; 16];
+    getrandom::getrandom(&mut salt)
+        .map_err(|e| AppError::internal(format!("生成随机盐失败: {}", e)))?;
+    let salt = SaltString::encode_b64(&salt)
+        .map_err(|e| AppError::internal(format!("编码盐失败: {}", e)))?;
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| AppError::internal(format!("计算密码哈希失败: {}", e)))
+}
+
+/// 校验密码；只接受 hash_password 生成的 argon2 PHC 字符串
+pub fn verify_password(stored: &str, password: &str) -> AppResult<()> {
+    let hash = PasswordHash::new(stored).map_err(|_| AppError::internal("只读模式密码格式无效"))?;
+    Argon2::default()
+        .verify_password(password.as_bytes(), &hash)
+        .map_err(|_| AppError::invalid("密码错误"))
+}
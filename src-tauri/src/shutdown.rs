@@ -0,0 +1,362 @@
+// 优雅退出：托盘「退出」不再直接 app.exit(0)，而是先
+//   1. 记录当前仍在运行的服务/转发/隧道/下载/Netcat 会话（resume_state.json）
+//   2. 逐个停止监听、暂停下载（暂停会落盘进度）
+//   3. 保存 Netcat 消息历史，写出 PersistedStore 中未落盘的修改，关闭 SQLite 连接池
+//   4. 安装安排在退出时安装的更新
+// 整个过程有总超时，超时后仍然强制退出。其他途径触发的退出（如 macOS Cmd+Q、系统注销）
+// 由 RunEvent::ExitRequested 拦截后同样走这里。
+//
+// 下次启动时若开启了 auto_resume_services，由 startup::spawn_preload
+// 在各模块加载完成后调用 resume_services 恢复，并推送一条汇总通知；恢复后删除 resume_state.json，
+// 避免之后的启动重复恢复。
+
+use crate::commands::toolbox::netcat::{self, NetcatState, SessionStatus};
+use crate::commands::toolbox::{downloader, forwarder, server, ssh_tunnel};
+use crate::error::AppResult;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, ExitRequestApi, Manager};
+
+/// 整体退出超时，超过后不再等待直接退出
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(3);
+
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// 优雅退出已完成，之后的退出请求直接放行
+static SHUTDOWN_DONE: AtomicBool = AtomicBool::new(false);
+
+/// 退出时仍在运行的资源 id
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeState {
+    #[serde(default)]
+    pub servers: Vec<String>,
+    #[serde(default)]
+    pub forwarders: Vec<String>,
+    #[serde(default)]
+    pub ssh_tunnels: Vec<String>,
+    #[serde(default)]
+    pub downloads: Vec<String>,
+    #[serde(default)]
+    pub netcat_sessions: Vec<String>,
+    pub saved_at: String,
+}
+
+impl ResumeState {
+    fn is_empty(&self) -> bool {
+        self.servers.is_empty()
+            && self.forwarders.is_empty()
+            && self.ssh_tunnels.is_empty()
+            && self.downloads.is_empty()
+            && self.netcat_sessions.is_empty()
+    }
+}
+
+/// 发起优雅退出（重复调用只生效一次）
+pub fn request_exit(app: &AppHandle) {
+    if SHUTTING_DOWN.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let _ = app.emit("app-shutting-down", ());
+        if tokio::time::timeout(SHUTDOWN_TIMEOUT, shutdown(&app))
+            .await
+            .is_err()
+        {
+            log::warn!("优雅退出超时（{:?}），强制退出", SHUTDOWN_TIMEOUT);
+        }
+        SHUTDOWN_DONE.store(true, Ordering::SeqCst);
+        if crate::commands::updater::install_on_exit() {
+            app.restart();
+        }
+        app.exit(0);
+    });
+}
+
+/// RunEvent::ExitRequested：优雅退出完成前先拦下，改走 request_exit
+pub fn on_exit_requested(app: &AppHandle, api: &ExitRequestApi) {
+    if SHUTDOWN_DONE.load(Ordering::SeqCst) {
+        return;
+    }
+    api.prevent_exit();
+    request_exit(app);
+}
+
+async fn collect_running(app: &AppHandle) -> ResumeState {
+    let mut state = ResumeState {
+        saved_at: storage::current_iso_time(),
+        ..Default::default()
+    };
+
+    if let Ok(list) = server::get_servers().await {
+        state.servers = list
+            .into_iter()
+            .filter(|s| s.status == "running")
+            .map(|s| s.id)
+            .collect();
+    }
+    if let Ok(list) = forwarder::get_forward_rules().await {
+        state.forwarders = list
+            .into_iter()
+            .filter(|r| r.status == "running")
+            .map(|r| r.id)
+            .collect();
+    }
+    if let Ok(list) = ssh_tunnel::get_ssh_tunnels().await {
+        state.ssh_tunnels = list
+            .into_iter()
+            .filter(|t| matches!(t.status.as_str(), "running" | "reconnecting"))
+            .map(|t| t.id)
+            .collect();
+    }
+    if let Ok(list) = downloader::get_download_tasks().await {
+        state.downloads = list
+            .into_iter()
+            .filter(|t| t.status == "downloading")
+            .map(|t| t.id)
+            .collect();
+    }
+    if let Some(netcat_state) = app.try_state::<NetcatState>() {
+        let sessions = netcat_state.sessions.read().await;
+        for (id, s) in sessions.iter() {
+            let s = s.read().await;
+            if matches!(
+                s.session.status,
+                SessionStatus::Connected | SessionStatus::Listening
+            ) {
+                state.netcat_sessions.push(id.clone());
+            }
+        }
+    }
+
+    state
+}
+
+fn save_resume_state(state: &ResumeState) -> AppResult<()> {
+    let config = storage::get_storage_config()?;
+    config.ensure_dirs()?;
+    let content = serde_json::to_string_pretty(state)
+        .map_err(|e| crate::error::AppError::from(format!("序列化恢复状态失败: {}", e)))?;
+    std::fs::write(config.resume_state_file(), content)
+        .map_err(|e| crate::error::AppError::from(format!("保存恢复状态失败: {}", e)))?;
+    Ok(())
+}
+
+fn clear_resume_state() {
+    let Ok(config) = storage::get_storage_config() else {
+        return;
+    };
+    match std::fs::remove_file(config.resume_state_file()) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => log::warn!("删除恢复状态失败: {}", e),
+    }
+}
+
+fn load_resume_state() -> Option<ResumeState> {
+    let config = storage::get_storage_config().ok()?;
+    let content = std::fs::read_to_string(config.resume_state_file()).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+async fn shutdown(app: &AppHandle) {
+    log::info!("开始优雅退出");
+    let running = collect_running(app).await;
+    if let Err(e) = save_resume_state(&running) {
+        log::warn!("{}", e);
+    }
+
+    for id in &running.servers {
+        let _ = server::stop_server(id.clone()).await;
+    }
+    for id in &running.forwarders {
+        let _ = forwarder::stop_forwarding(id.clone()).await;
+    }
+    for id in &running.ssh_tunnels {
+        let _ = ssh_tunnel::stop_ssh_tunnel(id.clone()).await;
+    }
+    for id in &running.downloads {
+        let _ = downloader::pause_download(id.clone()).await;
+    }
+
+    if let Some(state) = app.try_state::<NetcatState>() {
+        for id in &running.netcat_sessions {
+            let _ = netcat::netcat_stop_session(state.clone(), id.clone()).await;
+        }
+        if let Err(e) = state.save_messages().await {
+            log::warn!("{}", e);
+        }
+    }
+
+    crate::commands::terminal::close_all();
+
+    storage::persisted_store::flush_all().await;
+    crate::commands::usage_stats::flush().await;
+    storage::db::close().await;
+    log::info!("优雅退出完成");
+}
+
+/// 单项恢复结果
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumedItem {
+    /// "server" | "forwarder" | "sshTunnel" | "download" | "netcat"
+    pub kind: String,
+    pub id: String,
+    pub error: Option<String>,
+}
+
+/// 启动时自动恢复的结果汇总
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeSummary {
+    pub restored: Vec<ResumedItem>,
+    pub failed: Vec<ResumedItem>,
+    pub saved_at: String,
+    pub resumed_at: String,
+}
+
+impl ResumeSummary {
+    fn record<T, E: std::fmt::Display>(&mut self, kind: &str, id: String, result: Result<T, E>) {
+        let error = match result {
+            Ok(_) => None,
+            Err(e) => {
+                log::warn!("恢复 {} {} 失败: {}", kind, id, e);
+                Some(e.to_string())
+            }
+        };
+        let item = ResumedItem {
+            kind: kind.to_string(),
+            id,
+            error,
+        };
+        if item.error.is_some() {
+            self.failed.push(item);
+        } else {
+            self.restored.push(item);
+        }
+    }
+
+    /// 通知正文，如 "服务 2、端口转发 1；失败 1 项"
+    fn describe(&self) -> String {
+        let labels = [
+            ("server", "服务"),
+            ("forwarder", "端口转发"),
+            ("sshTunnel", "SSH 隧道"),
+            ("download", "下载"),
+            ("netcat", "Netcat 会话"),
+        ];
+        let parts: Vec<String> = labels
+            .iter()
+            .filter_map(|(kind, label)| {
+                let count = self.restored.iter().filter(|i| i.kind == *kind).count();
+                (count > 0).then(|| format!("{} {}", label, count))
+            })
+            .collect();
+        let mut text = if parts.is_empty() {
+            "没有恢复任何服务".to_string()
+        } else {
+            parts.join("、")
+        };
+        if !self.failed.is_empty() {
+            text.push_str(&format!("；失败 {} 项，详见日志", self.failed.len()));
+        }
+        text
+    }
+}
+
+/// 本次启动自动恢复的结果
+static LAST_RESUME: std::sync::Mutex<Option<ResumeSummary>> = std::sync::Mutex::new(None);
+
+/// 恢复上次退出时运行中的资源（仅在设置开启时由启动流程调用），完成后推送汇总通知
+pub async fn resume_services(app: &AppHandle) {
+    let enabled = crate::commands::settings::load_app_settings()
+        .await
+        .map(|s| s.auto_resume_services)
+        .unwrap_or(false);
+    if !enabled {
+        return;
+    }
+    let Some(state) = load_resume_state() else {
+        return;
+    };
+    if state.is_empty() {
+        return;
+    }
+    log::info!("恢复上次运行中的服务（保存于 {}）", state.saved_at);
+
+    let mut summary = ResumeSummary {
+        saved_at: state.saved_at.clone(),
+        ..Default::default()
+    };
+    for id in state.servers {
+        let result = server::start_server(app.clone(), id.clone()).await;
+        summary.record("server", id, result);
+    }
+    for id in state.forwarders {
+        let result = forwarder::start_forwarding(app.clone(), id.clone()).await;
+        summary.record("forwarder", id, result);
+    }
+    for id in state.ssh_tunnels {
+        let result = ssh_tunnel::start_ssh_tunnel(id.clone()).await;
+        summary.record("sshTunnel", id, result);
+    }
+    for id in state.downloads {
+        let result = downloader::resume_download(id.clone()).await;
+        summary.record("download", id, result);
+    }
+    if let Some(netcat_state) = app.try_state::<NetcatState>() {
+        for id in state.netcat_sessions {
+            let result =
+                netcat::netcat_start_session(app.clone(), netcat_state.clone(), id.clone()).await;
+            summary.record("netcat", id, result);
+        }
+    }
+    summary.resumed_at = storage::current_iso_time();
+    // 结果已记入汇总（失败项见通知与日志），下次启动不再重复恢复
+    clear_resume_state();
+
+    let level = if summary.failed.is_empty() {
+        "success"
+    } else {
+        "warning"
+    };
+    crate::commands::settings::push_notification(
+        app,
+        "server",
+        level,
+        "已恢复上次运行的服务",
+        &summary.describe(),
+    )
+    .await;
+    let _ = app.emit("services-resumed", &summary);
+    *LAST_RESUME.lock().unwrap_or_else(|e| e.into_inner()) = Some(summary);
+}
+
+/// 本次启动自动恢复的结果（未开启或没有需要恢复的服务时为 None）
+#[tauri::command]
+#[specta::specta]
+pub async fn get_resume_summary() -> AppResult<Option<ResumeSummary>> {
+    Ok(LAST_RESUME
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone())
+}
+
+/// 获取上次退出时记录的运行状态
+#[tauri::command]
+#[specta::specta]
+pub async fn get_resume_state() -> AppResult<Option<ResumeState>> {
+    Ok(load_resume_state())
+}
+
+/// 前端触发优雅退出（与托盘「退出」相同）
+#[tauri::command]
+#[specta::specta]
+pub async fn request_app_exit(app: AppHandle) -> AppResult<()> {
+    request_exit(&app);
+    Ok(())
+}
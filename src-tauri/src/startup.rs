@@ -0,0 +1,133 @@
+// 启动耗时统计 + 后台预加载。
+//
+// setup 里的每个阶段用 time_phase 包一层记录耗时；
+// 各工具模块的 JSON 存储不再等第一次命令时才读盘，而是在 setup 之后由
+// spawn_preload 放到后台任务里加载，每完成一个发一次 `startup-module-ready`，
+// 全部完成后发 `startup-ready`。get_startup_report 返回整份报告。
+
+use crate::error::AppResult;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// 进程启动时刻（lib::run 开头强制初始化）
+static PROCESS_START: Lazy<Instant> = Lazy::new(Instant::now);
+
+static REPORT: Lazy<Mutex<StartupReport>> = Lazy::new(|| Mutex::new(StartupReport::default()));
+
+#[derive(Debug, Clone, Default, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupPhase {
+    pub name: String,
+    /// 相对进程启动的开始时间（毫秒）
+    pub started_at_ms: u64,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupReport {
+    /// setup 中同步执行的阶段
+    pub phases: Vec<StartupPhase>,
+    /// 后台预加载的模块
+    pub modules: Vec<StartupPhase>,
+    /// setup 完成时间（相对进程启动，毫秒）
+    pub setup_done_ms: Option<u64>,
+    /// 全部后台模块就绪时间
+    pub ready_ms: Option<u64>,
+}
+
+fn elapsed_ms(at: Instant) -> u64 {
+    at.duration_since(*PROCESS_START).as_millis() as u64
+}
+
+/// 记录进程启动时刻，越早调用越准
+pub fn mark_process_start() {
+    Lazy::force(&PROCESS_START);
+}
+
+/// 执行并记录一个同步阶段
+pub fn time_phase<T>(name: &str, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let out = f();
+    let phase = StartupPhase {
+        name: name.to_string(),
+        started_at_ms: elapsed_ms(start),
+        duration_ms: start.elapsed().as_millis() as u64,
+    };
+    log::debug!("启动阶段 {} 耗时 {}ms", phase.name, phase.duration_ms);
+    if let Ok(mut report) = REPORT.lock() {
+        report.phases.push(phase);
+    }
+    out
+}
+
+pub fn mark_setup_done() {
+    if let Ok(mut report) = REPORT.lock() {
+        report.setup_done_ms = Some(elapsed_ms(Instant::now()));
+    }
+}
+
+fn record_module(app: &AppHandle, name: &str, start: Instant) {
+    let phase = StartupPhase {
+        name: name.to_string(),
+        started_at_ms: elapsed_ms(start),
+        duration_ms: start.elapsed().as_millis() as u64,
+    };
+    if let Ok(mut report) = REPORT.lock() {
+        report.modules.push(phase.clone());
+    }
+    let _ = app.emit("startup-module-ready", phase);
+}
+
+/// 后台预加载各工具模块的持久化数据
+pub fn spawn_preload(app: AppHandle) {
+    use crate::commands::toolbox;
+
+    tauri::async_runtime::spawn(async move {
+        let start = Instant::now();
+        toolbox::forwarder::ensure_rules_loaded().await;
+        record_module(&app, "forwarder", start);
+
+        let start = Instant::now();
+        toolbox::server::ensure_servers_loaded().await;
+        record_module(&app, "server", start);
+
+        let start = Instant::now();
+        toolbox::downloader::ensure_tasks_loaded().await;
+        record_module(&app, "downloader", start);
+
+        let start = Instant::now();
+        toolbox::ssh_tunnel::ensure_tunnels_loaded().await;
+        record_module(&app, "sshTunnel", start);
+
+        let start = Instant::now();
+        if let Some(state) = app.try_state::<toolbox::netcat::NetcatState>() {
+            if let Err(e) = state.load_sessions().await {
+                log::warn!("预加载 Netcat 会话失败: {}", e);
+            }
+        }
+        record_module(&app, "netcat", start);
+
+        crate::shutdown::resume_services(&app).await;
+
+        let ready_ms = elapsed_ms(Instant::now());
+        if let Ok(mut report) = REPORT.lock() {
+            report.ready_ms = Some(ready_ms);
+        }
+        log::info!("后台模块预加载完成，距进程启动 {}ms", ready_ms);
+        let _ = app.emit("startup-ready", ready_ms);
+    });
+}
+
+/// 获取启动耗时报告
+#[tauri::command]
+#[specta::specta]
+pub async fn get_startup_report() -> AppResult<StartupReport> {
+    let report = REPORT
+        .lock()
+        .map_err(|e| crate::error::AppError::internal(e.to_string()))?;
+    Ok(report.clone())
+}
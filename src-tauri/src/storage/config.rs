@@ -5,11 +5,21 @@
 use crate::error::AppResult;
 use std::fs;
 use std::path::PathBuf;
-use std::sync::OnceLock;
+use std::sync::{OnceLock, RwLock};
 
 /// 存储配置（全局单例）
 static STORAGE_CONFIG: OnceLock<StorageConfig> = OnceLock::new();
 
+/// 当前配置档案的目录；None 为默认档案，数据直接放在 data_dir
+static ACTIVE_PROFILE_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// 切换配置档案：之后档案相关的文件路径都指向该目录
+pub fn set_active_profile_dir(dir: Option<PathBuf>) {
+    *ACTIVE_PROFILE_DIR
+        .write()
+        .unwrap_or_else(|e| e.into_inner()) = dir;
+}
+
 /// 存储配置
 #[derive(Debug, Clone)]
 pub struct StorageConfig {
@@ -53,6 +63,14 @@ impl StorageConfig {
         Ok(())
     }
 
+    /// 随配置档案切换的文件：有激活的档案时位于档案目录，否则位于 data_dir
+    fn profile_scoped(&self, file: &str) -> PathBuf {
+        match &*ACTIVE_PROFILE_DIR.read().unwrap_or_else(|e| e.into_inner()) {
+            Some(dir) => dir.join(file),
+            None => self.data_dir.join(file),
+        }
+    }
+
     // ============== 数据文件路径 ==============
 
     pub fn categories_file(&self) -> PathBuf {
@@ -64,17 +82,22 @@ impl StorageConfig {
     }
 
     pub fn editors_file(&self) -> PathBuf {
-        self.data_dir.join("editors.json")
+        self.profile_scoped("editors.json")
     }
 
     pub fn terminal_file(&self) -> PathBuf {
-        self.data_dir.join("terminal.json")
+        self.profile_scoped("terminal.json")
     }
 
     pub fn app_settings_file(&self) -> PathBuf {
         self.data_dir.join("app_settings.json")
     }
 
+    /// 本机凭据加密密钥（见 storage::secrets）
+    pub fn secret_key_file(&self) -> PathBuf {
+        self.data_dir.join("secret.key")
+    }
+
     pub fn ui_state_file(&self) -> PathBuf {
         self.data_dir.join("ui_state.json")
     }
@@ -95,8 +118,12 @@ impl StorageConfig {
         self.data_dir.join("download_tasks.json")
     }
 
+    pub fn download_history_file(&self) -> PathBuf {
+        self.data_dir.join("download_history.json")
+    }
+
     pub fn forward_rules_file(&self) -> PathBuf {
-        self.data_dir.join("forward_rules.json")
+        self.profile_scoped("forward_rules.json")
     }
 
     pub fn ssh_tunnels_file(&self) -> PathBuf {
@@ -104,13 +131,33 @@ impl StorageConfig {
     }
 
     pub fn server_configs_file(&self) -> PathBuf {
-        self.data_dir.join("server_configs.json")
+        self.profile_scoped("server_configs.json")
+    }
+
+    pub fn port_watches_file(&self) -> PathBuf {
+        self.data_dir.join("port_watches.json")
+    }
+
+    pub fn http_monitors_file(&self) -> PathBuf {
+        self.data_dir.join("http_monitors.json")
     }
 
     pub fn netcat_sessions_file(&self) -> PathBuf {
         self.data_dir.join("netcat_sessions.json")
     }
 
+    pub fn netcat_messages_file(&self) -> PathBuf {
+        self.data_dir.join("netcat_messages.json")
+    }
+
+    pub fn netcat_payloads_file(&self) -> PathBuf {
+        self.data_dir.join("netcat_payloads.json")
+    }
+
+    pub fn resume_state_file(&self) -> PathBuf {
+        self.data_dir.join("resume_state.json")
+    }
+
     pub fn claude_launch_dirs_file(&self) -> PathBuf {
         self.data_dir.join("claude_launch_dirs.json")
     }
@@ -148,10 +195,44 @@ impl StorageConfig {
         self.data_dir.join("skills")
     }
 
+    pub fn captures_dir(&self) -> PathBuf {
+        self.data_dir.join("captures")
+    }
+
+    pub fn resource_alerts_file(&self) -> PathBuf {
+        self.data_dir.join("resource_alerts.json")
+    }
+
+    pub fn quarantine_dir(&self) -> PathBuf {
+        self.data_dir.join("quarantine")
+    }
+
     pub fn workflows_dir(&self) -> PathBuf {
         self.data_dir.join("workflows")
     }
 
+    pub fn scratchpad_dir(&self) -> PathBuf {
+        self.data_dir.join("scratchpad")
+    }
+
+    /// 配置档案列表与当前激活的档案
+    pub fn app_profiles_file(&self) -> PathBuf {
+        self.data_dir.join("app_profiles.json")
+    }
+
+    /// 每个配置档案一个子目录，存放档案相关的文件
+    pub fn app_profiles_dir(&self) -> PathBuf {
+        self.data_dir.join("profiles")
+    }
+
+    pub fn mirror_jobs_file(&self) -> PathBuf {
+        self.data_dir.join("mirror_jobs.json")
+    }
+
+    pub fn git_hook_templates_file(&self) -> PathBuf {
+        self.data_dir.join("git_hook_templates.json")
+    }
+
     pub fn clipboard_settings_file(&self) -> PathBuf {
         self.data_dir.join("clipboard_settings.json")
     }
@@ -176,6 +257,22 @@ impl StorageConfig {
         self.data_dir.join("api_chat_sessions")
     }
 
+    pub fn project_icons_dir(&self) -> PathBuf {
+        self.data_dir.join("project_icons")
+    }
+
+    pub fn bulk_operations_file(&self) -> PathBuf {
+        self.data_dir.join("bulk_operations.json")
+    }
+
+    pub fn divergence_watches_file(&self) -> PathBuf {
+        self.data_dir.join("divergence_watches.json")
+    }
+
+    pub fn hosting_tokens_file(&self) -> PathBuf {
+        self.data_dir.join("hosting_tokens.json")
+    }
+
     /// SQLite 主库文件路径。阶段 2 起作为 projects / chat / clipboard / stats 的存储。
     pub fn db_file(&self) -> PathBuf {
         self.data_dir.join("codeshelf.db")
@@ -184,6 +281,18 @@ impl StorageConfig {
 
 /// 把 project_id 之类的标识符压成可以安全做文件名的形式：
 /// 只保留字母、数字、`-`、`_`，其它字符替换为 `_`。
+pub fn sanitize_id(id: &str) -> String {
+    id.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
 /// 初始化存储配置
 pub fn init_storage() -> AppResult<&'static StorageConfig> {
     let config = StorageConfig::new()?;
@@ -210,6 +319,7 @@ pub fn init_storage() -> AppResult<&'static StorageConfig> {
         }
     }
 
+    restore_active_profile(&config);
     let _ = STORAGE_CONFIG.set(config);
 
     log::info!(
@@ -223,6 +333,18 @@ pub fn init_storage() -> AppResult<&'static StorageConfig> {
     Ok(STORAGE_CONFIG.get().expect("STORAGE_CONFIG just set above"))
 }
 
+/// 启动时恢复上次激活的配置档案（档案目录已不存在时回到默认档案）
+fn restore_active_profile(config: &StorageConfig) {
+    let active = fs::read_to_string(config.app_profiles_file())
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|value| value.get("active")?.as_str().map(sanitize_id));
+    let dir = active
+        .map(|id| config.app_profiles_dir().join(id))
+        .filter(|dir| dir.is_dir());
+    set_active_profile_dir(dir);
+}
+
 /// macOS: 将旧目录中的文件迁移到新目录（仅当新目录为空时）
 #[cfg(target_os = "macos")]
 fn migrate_dir(src: &std::path::Path, dst: &std::path::Path) -> AppResult<()> {
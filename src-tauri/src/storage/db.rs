@@ -57,6 +57,13 @@ pub fn pool() -> &'static SqlitePool {
         .expect("DB pool 尚未初始化，启动时必须先调用 storage::db::init_db")
 }
 
+/// 关闭连接池（退出前调用，等待未完成的写入并落盘 WAL）。未初始化时忽略。
+pub async fn close() {
+    if let Some(pool) = DB_POOL.get() {
+        pool.close().await;
+    }
+}
+
 /// 读取当前已应用的最高 schema 版本号。表不存在或无记录返回 0。
 pub async fn get_schema_version() -> AppResult<u32> {
     let row: Option<(i64,)> =
@@ -72,11 +79,19 @@ pub async fn get_schema_version() -> AppResult<u32> {
 
 /// 标记某个版本已应用。同版本号重复写入会被主键拦下，返回错误。
 pub async fn set_schema_version(version: u32) -> AppResult<()> {
+    set_schema_version_with(pool(), version).await
+}
+
+/// 同 set_schema_version，可在迁移事务内执行
+pub async fn set_schema_version_with<'e, E>(executor: E, version: u32) -> AppResult<()>
+where
+    E: sqlx::SqliteExecutor<'e>,
+{
     let now = chrono::Utc::now().to_rfc3339();
     sqlx::query("INSERT INTO schema_version (version, applied_at) VALUES (?, ?)")
         .bind(version as i64)
         .bind(now)
-        .execute(pool())
+        .execute(executor)
         .await
         .map_err(|e| crate::error::AppError::from(format!("写入 schema_version 失败: {}", e)))?;
     Ok(())
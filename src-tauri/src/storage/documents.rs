@@ -0,0 +1,381 @@
+// 设置类 JSON 文件的版本化读写
+//
+// 文件统一包一层 `{ "version": N, "data": ... }`；旧版没有外层的文件视为 version 0。
+// 每种文件在 DOCUMENTS 里登记当前版本和迁移步骤，读取时按顺序把 data 升到当前版本：
+// - 升级前把原文件复制为 `<文件名>.v<旧版本>.bak`，再原子写回新格式
+// - 升级后的 data 反序列化失败时不再静默丢弃：原文件另存为 `.invalid-<时间>.bak` 后才使用默认值
+// - 每次升级 / 解析失败都追加到 settings_migrations.json，前端在「数据管理」里查看
+// - 文件版本高于当前程序（降级运行）时读取不做迁移，保存前先把新版本文件备份为 `.v<版本>.bak`
+
+use super::config::StorageConfig;
+use super::external_changes;
+use super::{current_iso_time, get_storage_config, AppSettings, EditorConfig, TerminalConfig};
+use crate::error::{AppError, AppResult};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 迁移日志最多保留条数
+const MAX_LOG_ENTRIES: usize = 200;
+
+/// 串行化读-迁移-写，避免启动时多处同时读取重复升级
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// 一个迁移步骤：把 data 从 `to - 1` 版本升到 `to` 版本
+pub struct MigrationStep {
+    pub to: u32,
+    pub description: &'static str,
+    pub apply: fn(&mut Value) -> AppResult<()>,
+}
+
+/// 一种版本化文件
+pub struct DocumentKind {
+    pub id: &'static str,
+    pub label: &'static str,
+    pub path: fn(&StorageConfig) -> PathBuf,
+    pub version: u32,
+    pub steps: &'static [MigrationStep],
+    /// 用目标类型试解析，返回错误信息
+    pub validate: fn(&Value) -> Result<(), String>,
+}
+
+fn validate_as<T: DeserializeOwned>(data: &Value) -> Result<(), String> {
+    T::deserialize(data).map(|_| ()).map_err(|e| e.to_string())
+}
+
+/// v1：旧文件只加外层，内容不变
+const WRAP_STEP: MigrationStep = MigrationStep {
+    to: 1,
+    description: "添加 version/data 外层",
+    apply: |_| Ok(()),
+};
+
+pub static APP_SETTINGS: DocumentKind = DocumentKind {
+    id: "app_settings",
+    label: "应用设置",
+    path: StorageConfig::app_settings_file,
+    version: 1,
+    steps: &[WRAP_STEP],
+    validate: validate_as::<AppSettings>,
+};
+
+pub static EDITORS: DocumentKind = DocumentKind {
+    id: "editors",
+    label: "编辑器配置",
+    path: StorageConfig::editors_file,
+    version: 1,
+    steps: &[WRAP_STEP],
+    validate: validate_as::<Vec<EditorConfig>>,
+};
+
+pub static TERMINAL: DocumentKind = DocumentKind {
+    id: "terminal",
+    label: "终端配置",
+    path: StorageConfig::terminal_file,
+    version: 1,
+    steps: &[WRAP_STEP],
+    validate: validate_as::<TerminalConfig>,
+};
+
+/// 所有登记的版本化文件
+pub static DOCUMENTS: &[&DocumentKind] = &[&APP_SETTINGS, &EDITORS, &TERMINAL];
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationLogEntry {
+    pub timestamp: String,
+    pub document: String,
+    pub label: String,
+    pub from_version: u32,
+    pub to_version: u32,
+    /// 已执行步骤的描述
+    pub steps: Vec<String>,
+    pub backup_file: Option<String>,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// dry-run 检查结果
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentCheck {
+    pub document: String,
+    pub label: String,
+    pub path: String,
+    pub exists: bool,
+    pub file_version: u32,
+    pub current_version: u32,
+    /// 读取时将执行的步骤
+    pub pending_steps: Vec<String>,
+    /// 迁移后能否解析为当前类型
+    pub valid: bool,
+    pub error: Option<String>,
+}
+
+/// 拆出 (版本, data)；没有外层的旧文件为 version 0
+fn unwrap_document(value: Value) -> (u32, Value) {
+    if let Value::Object(map) = &value {
+        if map.len() == 2 && map.contains_key("data") {
+            if let Some(version) = map.get("version").and_then(|v| v.as_u64()) {
+                let mut map = map.clone();
+                return (version as u32, map.remove("data").unwrap_or(Value::Null));
+            }
+        }
+    }
+    (0, value)
+}
+
+/// 在内存中执行迁移，返回执行过的步骤描述
+fn migrate(kind: &DocumentKind, from: u32, data: &mut Value) -> AppResult<Vec<String>> {
+    let mut done = Vec::new();
+    for step in kind
+        .steps
+        .iter()
+        .filter(|s| s.to > from && s.to <= kind.version)
+    {
+        (step.apply)(data).map_err(|e| {
+            AppError::internal(format!("{} 迁移到 v{} 失败: {}", kind.label, step.to, e))
+        })?;
+        done.push(format!("v{}: {}", step.to, step.description));
+    }
+    Ok(done)
+}
+
+/// 先写临时文件再改名，避免写到一半时留下截断的文件
+pub(crate) fn write_atomic(path: &Path, content: &str) -> AppResult<()> {
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, content)
+        .and_then(|_| fs::rename(&tmp, path))
+        .map_err(|e| AppError::from(format!("写入 {:?} 失败: {}", path, e)))
+}
+
+fn backup_path(path: &Path, suffix: &str) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!("{}.{}.bak", name, suffix))
+}
+
+fn log_file(config: &StorageConfig) -> PathBuf {
+    config.data_dir.join("settings_migrations.json")
+}
+
+fn append_log(entry: MigrationLogEntry) {
+    if entry.success {
+        log::info!(
+            "{} 从 v{} 升级到 v{}: {:?}",
+            entry.label,
+            entry.from_version,
+            entry.to_version,
+            entry.steps
+        );
+    } else {
+        log::error!("{} 迁移/解析失败: {:?}", entry.label, entry.error);
+    }
+    let Ok(config) = get_storage_config() else {
+        return;
+    };
+    let mut entries = read_log(config);
+    entries.push(entry);
+    if entries.len() > MAX_LOG_ENTRIES {
+        entries.drain(..entries.len() - MAX_LOG_ENTRIES);
+    }
+    if let Ok(content) = serde_json::to_string_pretty(&entries) {
+        let _ = write_atomic(&log_file(config), &content);
+    }
+}
+
+fn read_log(config: &StorageConfig) -> Vec<MigrationLogEntry> {
+    fs::read_to_string(log_file(config))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+/// 读取迁移日志（新的在前）
+pub fn migration_log() -> AppResult<Vec<MigrationLogEntry>> {
+    let config = get_storage_config()?;
+    let mut entries = read_log(config);
+    entries.reverse();
+    Ok(entries)
+}
+
+/// 读取并按需升级文件，返回当前版本的 data；文件不存在时为 None
+fn read_current(kind: &DocumentKind) -> AppResult<Option<Value>> {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let config = get_storage_config()?;
+    let path = (kind.path)(config);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| AppError::from(format!("读取{}失败: {}", kind.label, e)))?;
+    let Ok(value) = serde_json::from_str::<Value>(&content) else {
+        // 不是合法 JSON：不做迁移，交给调用方按解析失败处理
+        return Ok(Some(Value::String(content)));
+    };
+    let (version, mut data) = unwrap_document(value);
+
+    if version > kind.version {
+        log::warn!(
+            "{} 文件版本 v{} 高于当前程序支持的 v{}，按只读处理",
+            kind.label,
+            version,
+            kind.version
+        );
+        return Ok(Some(data));
+    }
+    if version == kind.version {
+        return Ok(Some(data));
+    }
+
+    let backup = backup_path(&path, &format!("v{}", version));
+    let mut entry = MigrationLogEntry {
+        timestamp: current_iso_time(),
+        document: kind.id.to_string(),
+        label: kind.label.to_string(),
+        from_version: version,
+        to_version: kind.version,
+        steps: Vec::new(),
+        backup_file: Some(backup.to_string_lossy().to_string()),
+        success: false,
+        error: None,
+    };
+
+    let result = fs::copy(&path, &backup)
+        .map_err(|e| AppError::from(format!("备份{}失败: {}", kind.label, e)))
+        .and_then(|_| migrate(kind, version, &mut data))
+        .and_then(|steps| {
+            entry.steps = steps;
+            let wrapped = serde_json::json!({ "version": kind.version, "data": data });
+            let content = serde_json::to_string(&wrapped)?;
+            external_changes::write_synced(kind.id, || write_atomic(&path, &content))
+        });
+    match result {
+        Ok(()) => {
+            entry.success = true;
+            append_log(entry);
+            Ok(Some(data))
+        }
+        Err(e) => {
+            entry.error = Some(e.to_string());
+            append_log(entry);
+            Err(e)
+        }
+    }
+}
+
+/// 读取文件并解析为 T。文件不存在时返回默认值；
+/// 解析失败时把原文件移到备份并记录日志，再返回默认值。
+/// 原文件移走后之后的读取直接得到默认值，不会每次读取都再备份一份。
+pub fn load<T: DeserializeOwned + Default>(kind: &DocumentKind) -> AppResult<T> {
+    let Some(data) = read_current(kind)? else {
+        return Ok(T::default());
+    };
+    match T::deserialize(&data) {
+        Ok(parsed) => Ok(parsed),
+        Err(e) => {
+            let config = get_storage_config()?;
+            let path = (kind.path)(config);
+            let ts = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+            let backup = backup_path(&path, &format!("invalid-{}", ts));
+            let backup_file = {
+                let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+                external_changes::write_synced(kind.id, || {
+                    fs::rename(&path, &backup)
+                        .map_err(|e| AppError::from(format!("备份{}失败: {}", kind.label, e)))
+                })
+                .ok()
+                .map(|_| backup.to_string_lossy().to_string())
+            };
+            append_log(MigrationLogEntry {
+                timestamp: current_iso_time(),
+                document: kind.id.to_string(),
+                label: kind.label.to_string(),
+                from_version: kind.version,
+                to_version: kind.version,
+                steps: Vec::new(),
+                backup_file,
+                success: false,
+                error: Some(format!("解析失败，已使用默认值: {}", e)),
+            });
+            Ok(T::default())
+        }
+    }
+}
+
+/// 以当前版本写出文件
+pub fn save<T: Serialize>(kind: &DocumentKind, data: &T) -> AppResult<()> {
+    let config = get_storage_config()?;
+    config.ensure_dirs()?;
+    let wrapped = serde_json::json!({ "version": kind.version, "data": data });
+    let content = serde_json::to_string(&wrapped)
+        .map_err(|e| AppError::from(format!("序列化{}失败: {}", kind.label, e)))?;
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = (kind.path)(config);
+    let existing = fs::read_to_string(&path)
+        .ok()
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+        .map(|v| unwrap_document(v).0);
+    if let Some(version) = existing.filter(|v| *v > kind.version) {
+        let _ = fs::copy(&path, backup_path(&path, &format!("v{}", version)));
+    }
+    external_changes::write_synced(kind.id, || write_atomic(&path, &content))
+}
+
+/// 启动时把所有文件升级到当前版本
+pub fn migrate_all() {
+    for kind in DOCUMENTS {
+        if let Err(e) = read_current(kind) {
+            log::error!("{}", e);
+        }
+    }
+}
+
+/// dry-run：检查每个文件的版本、待执行步骤以及迁移后能否解析，不写任何文件
+pub fn check_all() -> AppResult<Vec<DocumentCheck>> {
+    let config = get_storage_config()?;
+    let mut checks = Vec::new();
+    for kind in DOCUMENTS {
+        let path = (kind.path)(config);
+        let mut check = DocumentCheck {
+            document: kind.id.to_string(),
+            label: kind.label.to_string(),
+            path: path.to_string_lossy().to_string(),
+            exists: path.exists(),
+            file_version: kind.version,
+            current_version: kind.version,
+            pending_steps: Vec::new(),
+            valid: true,
+            error: None,
+        };
+        if check.exists {
+            let outcome = fs::read_to_string(&path)
+                .map_err(|e| AppError::from(e.to_string()))
+                .and_then(|s| Ok(serde_json::from_str::<Value>(&s)?))
+                .and_then(|value| {
+                    let (version, mut data) = unwrap_document(value);
+                    check.file_version = version;
+                    if version > kind.version {
+                        return Err(AppError::invalid(format!(
+                            "文件版本 v{} 高于当前程序支持的 v{}",
+                            version, kind.version
+                        )));
+                    }
+                    check.pending_steps = migrate(kind, version, &mut data)?;
+                    (kind.validate)(&data).map_err(AppError::invalid)
+                });
+            if let Err(e) = outcome {
+                check.valid = false;
+                check.error = Some(e.to_string());
+            }
+        }
+        checks.push(check);
+    }
+    Ok(checks)
+}
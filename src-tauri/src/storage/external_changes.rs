@@ -0,0 +1,198 @@
+// 设置文件的外部修改检测
+//
+// 标签、分类、应用设置、编辑器、终端配置可能被手动编辑，或由同步盘 / 另一个实例改写。
+// 这里记下每个文件最后一次由本程序读写后的内容指纹，后台每隔 POLL_INTERVAL 检查一次，
+// 指纹变了就是外部修改，通过全局广播发出 SettingsChange（前端订阅 `settings-changed`）。
+// 本程序自己的写入走 write_synced，写完立即更新指纹，不会被当作外部修改。
+//
+// 冲突：整表覆盖的写入（save_labels / save_categories）走 write_checked。
+// 若写入前文件已被外部改过而本程序尚未察觉，先把外部版本另存为 `<文件名>.conflict-<时间>.bak`
+// 再写入，并在广播里带上备份路径，由前端提示用户。
+
+use super::config::StorageConfig;
+use super::get_storage_config;
+use crate::error::AppResult;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tokio::sync::broadcast;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsChange {
+    /// "app_settings" | "labels" | "categories" | "editors" | "terminal"
+    pub document: String,
+    /// 冲突时被覆盖的外部版本的备份路径
+    pub conflict_backup: Option<String>,
+}
+
+struct WatchedFile {
+    id: &'static str,
+    path: fn(&StorageConfig) -> PathBuf,
+}
+
+const WATCHED: &[WatchedFile] = &[
+    WatchedFile {
+        id: "app_settings",
+        path: StorageConfig::app_settings_file,
+    },
+    WatchedFile {
+        id: "labels",
+        path: StorageConfig::labels_file,
+    },
+    WatchedFile {
+        id: "categories",
+        path: StorageConfig::categories_file,
+    },
+    WatchedFile {
+        id: "editors",
+        path: StorageConfig::editors_file,
+    },
+    WatchedFile {
+        id: "terminal",
+        path: StorageConfig::terminal_file,
+    },
+];
+
+/// 文件状态；文件不存在时各项为 None
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct FileState {
+    modified: Option<SystemTime>,
+    len: Option<u64>,
+    hash: Option<u64>,
+}
+
+/// 每个文件最后一次同步时的状态
+static STATES: Lazy<Mutex<HashMap<&'static str, FileState>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static CHANGES: Lazy<broadcast::Sender<SettingsChange>> = Lazy::new(|| broadcast::channel(64).0);
+
+/// 订阅设置文件的外部修改
+pub fn subscribe() -> broadcast::Receiver<SettingsChange> {
+    CHANGES.subscribe()
+}
+
+fn read_state(path: &Path) -> FileState {
+    let Ok(meta) = fs::metadata(path) else {
+        return FileState::default();
+    };
+    let hash = fs::read(path).ok().map(|content| {
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        hasher.finish()
+    });
+    FileState {
+        modified: meta.modified().ok(),
+        len: Some(meta.len()),
+        hash,
+    }
+}
+
+fn watched_path(id: &str) -> Option<(&'static str, PathBuf)> {
+    let config = get_storage_config().ok()?;
+    WATCHED
+        .iter()
+        .find(|w| w.id == id)
+        .map(|w| (w.id, (w.path)(config)))
+}
+
+/// 以本程序的写入为准更新指纹；id 不在监视列表中时直接执行写入
+pub fn write_synced(id: &str, write: impl FnOnce() -> AppResult<()>) -> AppResult<()> {
+    let mut states = STATES.lock().unwrap_or_else(|e| e.into_inner());
+    write()?;
+    if let Some((id, path)) = watched_path(id) {
+        states.insert(id, read_state(&path));
+    }
+    Ok(())
+}
+
+/// 整表覆盖写入：文件在上次同步后被外部修改过时先备份外部版本，返回备份路径
+pub fn write_checked(id: &str, write: impl FnOnce() -> AppResult<()>) -> AppResult<Option<String>> {
+    let mut states = STATES.lock().unwrap_or_else(|e| e.into_inner());
+    let Some((id, path)) = watched_path(id) else {
+        write()?;
+        return Ok(None);
+    };
+
+    let current = read_state(&path);
+    let known = states.get(id).copied();
+    let mut backup = None;
+    if current.hash.is_some() && known.is_some_and(|k| k.hash != current.hash) {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let ts = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let target = path.with_file_name(format!("{}.conflict-{}.bak", name, ts));
+        match fs::copy(&path, &target) {
+            Ok(_) => {
+                log::warn!("{} 已被外部修改，覆盖前备份到 {:?}", id, target);
+                backup = Some(target.to_string_lossy().to_string());
+            }
+            Err(e) => log::warn!("备份冲突文件失败: {}", e),
+        }
+    }
+
+    write()?;
+    states.insert(id, read_state(&path));
+    if backup.is_some() {
+        let _ = CHANGES.send(SettingsChange {
+            document: id.to_string(),
+            conflict_backup: backup.clone(),
+        });
+    }
+    Ok(backup)
+}
+
+/// 对比一轮，返回外部修改过的文件
+fn poll() -> Vec<&'static str> {
+    let Ok(config) = get_storage_config() else {
+        return Vec::new();
+    };
+    let mut states = STATES.lock().unwrap_or_else(|e| e.into_inner());
+    let mut changed = Vec::new();
+    for watched in WATCHED {
+        let path = (watched.path)(config);
+        let known = states.get(watched.id).copied();
+        // 修改时间和大小都没变时不读内容
+        if let (Some(known), Ok(meta)) = (known, fs::metadata(&path)) {
+            if known.modified == meta.modified().ok() && known.len == Some(meta.len()) {
+                continue;
+            }
+        }
+        let current = read_state(&path);
+        match known {
+            Some(known) if known.hash != current.hash => changed.push(watched.id),
+            _ => {}
+        }
+        states.insert(watched.id, current);
+    }
+    changed
+}
+
+/// 记录初始指纹并启动后台检查
+pub fn spawn_watcher() {
+    poll();
+    tauri::async_runtime::spawn(async {
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            let changed = tokio::task::spawn_blocking(poll).await.unwrap_or_default();
+            for id in changed {
+                log::info!("检测到设置文件被外部修改: {}", id);
+                let _ = CHANGES.send(SettingsChange {
+                    document: id.to_string(),
+                    conflict_backup: None,
+                });
+            }
+        }
+    });
+}
@@ -1,6 +1,17 @@
 // 迁移协调器：按版本号顺序应用未完成的迁移。
 //
-// 当前实现 v1：建表 + 从 JSON 搬迁现有数据。
+// - v1：建表 + 从 JSON 搬迁现有数据
+// - v2：project_tasks（项目固定命令）
+// - v3：commit_index（跨项目提交搜索 FTS5 索引）
+// - v4：usage_stats（本地使用统计）
+// - v5：project_compliance（项目合规扫描报告）
+// - v6：projects 增加 icon / color / description 列
+// - v7：project_links（项目快捷链接）
+// - v8：deploy_targets / deploy_runs（静态站点部署）
+// - v9：path_roots（路径根目录），projects 增加 root_id / relative_path 列
+// - v10：projects 增加 remote_url 列（查找被移动的项目）
+// - v11：projects 增加 git_executable 列（项目级 git 路径覆盖）
+// - v12：projects 增加 stats_branch 列（按分支统计提交）
 //
 // 重要约束：
 // - 任何 step 失败都不应破坏原 JSON 文件（用户能手动恢复）
@@ -12,14 +23,36 @@ use crate::error::AppResult;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::storage::db::{get_schema_version, pool, set_schema_version};
+use crate::storage::db::{get_schema_version, pool, set_schema_version, set_schema_version_with};
 
 mod v1_from_json;
 
 const V1_INITIAL_SQL: &str = include_str!("v1_initial.sql");
+const V2_PROJECT_TASKS_SQL: &str = include_str!("v2_project_tasks.sql");
+const V3_COMMIT_INDEX_SQL: &str = include_str!("v3_commit_index.sql");
+const V4_USAGE_STATS_SQL: &str = include_str!("v4_usage_stats.sql");
+const V5_PROJECT_COMPLIANCE_SQL: &str = include_str!("v5_project_compliance.sql");
+const V6_PROJECT_APPEARANCE_SQL: &str = include_str!("v6_project_appearance.sql");
+const V7_PROJECT_LINKS_SQL: &str = include_str!("v7_project_links.sql");
+const V8_DEPLOY_SQL: &str = include_str!("v8_deploy.sql");
+const V9_PATH_ROOTS_SQL: &str = include_str!("v9_path_roots.sql");
+const V10_PROJECT_REMOTE_SQL: &str = include_str!("v10_project_remote.sql");
+const V11_PROJECT_GIT_EXECUTABLE_SQL: &str = include_str!("v11_project_git_executable.sql");
+const V12_PROJECT_STATS_BRANCH_SQL: &str = include_str!("v12_project_stats_branch.sql");
 
 const PENDING_RESTORE_FLAG: &str = ".pending_restore";
 
+/// 在同一事务内执行迁移 SQL 并写入版本号。
+/// ALTER TABLE ADD COLUMN 不能重复执行，若加列成功而版本号未写入，之后每次启动都会迁移失败
+async fn migrate_in_transaction(version: u32, sql: &str) -> AppResult<()> {
+    let failed =
+        |e: sqlx::Error| crate::error::AppError::from(format!("v{} 迁移失败: {}", version, e));
+    let mut tx = pool().begin().await.map_err(failed)?;
+    sqlx::raw_sql(sql).execute(&mut *tx).await.map_err(failed)?;
+    set_schema_version_with(&mut *tx, version).await?;
+    tx.commit().await.map_err(failed)
+}
+
 /// 应用所有待执行的迁移。`data_dir` 是 JSON 文件所在目录。
 pub async fn run_migrations(data_dir: &Path) -> AppResult<()> {
     let current = get_schema_version().await?;
@@ -29,7 +62,99 @@ pub async fn run_migrations(data_dir: &Path) -> AppResult<()> {
         run_v1(data_dir).await?;
         set_schema_version(1).await?;
         log::info!("v1 迁移完成，schema_version=1");
-    } else {
+    }
+
+    if current < 2 {
+        log::info!("执行 v2 迁移：project_tasks");
+        sqlx::raw_sql(V2_PROJECT_TASKS_SQL)
+            .execute(pool())
+            .await
+            .map_err(|e| crate::error::AppError::from(format!("v2 建表失败: {}", e)))?;
+        set_schema_version(2).await?;
+        log::info!("v2 迁移完成，schema_version=2");
+    }
+
+    if current < 3 {
+        log::info!("执行 v3 迁移：commit_index");
+        sqlx::raw_sql(V3_COMMIT_INDEX_SQL)
+            .execute(pool())
+            .await
+            .map_err(|e| crate::error::AppError::from(format!("v3 建表失败: {}", e)))?;
+        set_schema_version(3).await?;
+        log::info!("v3 迁移完成，schema_version=3");
+    }
+
+    if current < 4 {
+        log::info!("执行 v4 迁移：usage_stats");
+        sqlx::raw_sql(V4_USAGE_STATS_SQL)
+            .execute(pool())
+            .await
+            .map_err(|e| crate::error::AppError::from(format!("v4 建表失败: {}", e)))?;
+        set_schema_version(4).await?;
+        log::info!("v4 迁移完成，schema_version=4");
+    }
+
+    if current < 5 {
+        log::info!("执行 v5 迁移：project_compliance");
+        sqlx::raw_sql(V5_PROJECT_COMPLIANCE_SQL)
+            .execute(pool())
+            .await
+            .map_err(|e| crate::error::AppError::from(format!("v5 建表失败: {}", e)))?;
+        set_schema_version(5).await?;
+        log::info!("v5 迁移完成，schema_version=5");
+    }
+
+    if current < 6 {
+        log::info!("执行 v6 迁移：project appearance");
+        migrate_in_transaction(6, V6_PROJECT_APPEARANCE_SQL).await?;
+        log::info!("v6 迁移完成，schema_version=6");
+    }
+
+    if current < 7 {
+        log::info!("执行 v7 迁移：project_links");
+        sqlx::raw_sql(V7_PROJECT_LINKS_SQL)
+            .execute(pool())
+            .await
+            .map_err(|e| crate::error::AppError::from(format!("v7 建表失败: {}", e)))?;
+        set_schema_version(7).await?;
+        log::info!("v7 迁移完成，schema_version=7");
+    }
+
+    if current < 8 {
+        log::info!("执行 v8 迁移：deploy");
+        sqlx::raw_sql(V8_DEPLOY_SQL)
+            .execute(pool())
+            .await
+            .map_err(|e| crate::error::AppError::from(format!("v8 建表失败: {}", e)))?;
+        set_schema_version(8).await?;
+        log::info!("v8 迁移完成，schema_version=8");
+    }
+
+    if current < 9 {
+        log::info!("执行 v9 迁移：path_roots");
+        migrate_in_transaction(9, V9_PATH_ROOTS_SQL).await?;
+        log::info!("v9 迁移完成，schema_version=9");
+    }
+
+    if current < 10 {
+        log::info!("执行 v10 迁移：projects.remote_url");
+        migrate_in_transaction(10, V10_PROJECT_REMOTE_SQL).await?;
+        log::info!("v10 迁移完成，schema_version=10");
+    }
+
+    if current < 11 {
+        log::info!("执行 v11 迁移：projects.git_executable");
+        migrate_in_transaction(11, V11_PROJECT_GIT_EXECUTABLE_SQL).await?;
+        log::info!("v11 迁移完成，schema_version=11");
+    }
+
+    if current < 12 {
+        log::info!("执行 v12 迁移：projects.stats_branch");
+        migrate_in_transaction(12, V12_PROJECT_STATS_BRANCH_SQL).await?;
+        log::info!("v12 迁移完成，schema_version=12");
+    }
+
+    if current >= 12 {
         log::debug!("数据库 schema_version={}，无迁移待执行", current);
     }
 
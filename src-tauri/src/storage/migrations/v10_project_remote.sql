@@ -0,0 +1,4 @@
+-- v10：记录项目的主远程地址
+-- 项目目录被移动 / 改名后无法再读取其远程，查找新位置时用这里记下的地址匹配
+
+ALTER TABLE projects ADD COLUMN remote_url TEXT;
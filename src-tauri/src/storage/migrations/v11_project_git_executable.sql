@@ -0,0 +1,2 @@
+-- v11：项目级 git 可执行文件覆盖（命令行，如 `wsl git`）
+ALTER TABLE projects ADD COLUMN git_executable TEXT;
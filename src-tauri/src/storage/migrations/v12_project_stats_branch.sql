@@ -0,0 +1,2 @@
+-- v12：项目统计使用的分支（NULL 为当前检出分支，* 为全部本地分支）
+ALTER TABLE projects ADD COLUMN stats_branch TEXT;
@@ -0,0 +1,21 @@
+-- v3：跨项目提交搜索索引（FTS5）
+-- trigram 分词支持中文与任意子串匹配；少于 3 个字符的查询走 LIKE
+
+CREATE VIRTUAL TABLE IF NOT EXISTS commit_index USING fts5(
+    message,
+    body,
+    author,
+    email,
+    project_path UNINDEXED,
+    hash UNINDEXED,
+    short_hash UNINDEXED,
+    date UNINDEXED,
+    tokenize = 'trigram'
+);
+
+-- 每个项目上次索引时的引用位置（JSON 数组），下次只索引新增提交
+CREATE TABLE IF NOT EXISTS commit_index_state (
+    project_path TEXT PRIMARY KEY,
+    tips TEXT NOT NULL DEFAULT '[]',
+    indexed_at TEXT NOT NULL
+);
@@ -0,0 +1,8 @@
+-- v4：本地使用统计（按天、按命令计数，仅在用户开启后记录，不会上传）
+
+CREATE TABLE IF NOT EXISTS usage_stats (
+    day TEXT NOT NULL,
+    command TEXT NOT NULL,
+    count INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (day, command)
+);
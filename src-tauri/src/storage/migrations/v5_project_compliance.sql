@@ -0,0 +1,9 @@
+-- v5：项目合规扫描报告（依赖许可证 + 疑似密钥），每个项目保留最近一次
+-- report 以 JSON 字符串存储（ComplianceReport）
+
+CREATE TABLE IF NOT EXISTS project_compliance (
+    project_id TEXT PRIMARY KEY,
+    report TEXT NOT NULL,
+    scanned_at TEXT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
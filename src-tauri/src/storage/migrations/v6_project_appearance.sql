@@ -0,0 +1,6 @@
+-- v6：项目外观与简介（大量项目时便于区分）
+-- icon 为缓存到数据目录 project_icons/ 下的图标文件路径，color 为 #RRGGBB
+
+ALTER TABLE projects ADD COLUMN icon TEXT;
+ALTER TABLE projects ADD COLUMN color TEXT;
+ALTER TABLE projects ADD COLUMN description TEXT;
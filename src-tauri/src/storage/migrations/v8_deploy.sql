@@ -0,0 +1,33 @@
+-- v8：静态站点部署目标与部署历史
+
+CREATE TABLE IF NOT EXISTS deploy_targets (
+    id TEXT PRIMARY KEY,
+    project_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    source_dir TEXT NOT NULL DEFAULT 'dist',
+    -- 目标配置（含凭据）的 JSON，见 DeployDestination
+    destination TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_deploy_targets_project ON deploy_targets(project_id);
+
+-- 删除部署目标后历史保留，随项目删除级联删除
+CREATE TABLE IF NOT EXISTS deploy_runs (
+    id TEXT PRIMARY KEY,
+    project_id TEXT NOT NULL,
+    target_id TEXT NOT NULL,
+    target_name TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    status TEXT NOT NULL,
+    files INTEGER NOT NULL DEFAULT 0,
+    bytes INTEGER NOT NULL DEFAULT 0,
+    message TEXT,
+    started_at TEXT NOT NULL,
+    finished_at TEXT,
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_deploy_runs_project ON deploy_runs(project_id, started_at);
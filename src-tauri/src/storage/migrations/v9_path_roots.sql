@@ -0,0 +1,15 @@
+-- v9：路径根目录（命名的基础目录，如 "work" -> D:\code）
+-- 项目可记录所属根目录与相对路径；projects.path 仍保存解析后的绝对路径，根目录移动时按相对路径重算
+
+CREATE TABLE IF NOT EXISTS path_roots (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    path TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+ALTER TABLE projects ADD COLUMN root_id TEXT;
+ALTER TABLE projects ADD COLUMN relative_path TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_projects_root ON projects(root_id);
@@ -2,8 +2,13 @@
 
 pub mod config;
 pub mod db;
+pub mod documents;
+pub mod external_changes;
 pub mod migrations;
+pub mod persisted_store;
 pub mod schema;
+pub mod secrets;
 
 pub use config::{get_storage_config, init_storage};
+pub use persisted_store::PersistedStore;
 pub use schema::*;
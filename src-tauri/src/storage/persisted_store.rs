@@ -0,0 +1,367 @@
+// 通用 JSON 持久化存储
+//
+// 工具模块（静态服务、端口转发、下载任务等）原先各自维护
+// `Lazy<Mutex<HashMap>>` + `*_LOADED` + load/save 三件套，这里统一成 PersistedStore<T>：
+// - 首次 ensure_loaded 时读盘，失败则下次重试
+// - save() 只标记脏数据并唤醒后台写入任务，短时间内的多次修改合并为一次写盘
+// - 每个 store 只有一个写入者，写临时文件后 rename，避免写到一半被读到
+// - 每次 save() 通过全局广播发出 StoreChange，前端可订阅 `store-changed`
+
+use super::config::StorageConfig;
+use crate::error::AppResult;
+use once_cell::sync::Lazy;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::{broadcast, Mutex, MutexGuard, Notify, TryLockError};
+use tokio::time::Duration;
+
+/// 写盘防抖间隔
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(300);
+
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreChange {
+    pub store: String,
+}
+
+static CHANGES: Lazy<broadcast::Sender<StoreChange>> = Lazy::new(|| broadcast::channel(64).0);
+
+/// 已启动写入任务的 store，退出时统一 flush
+static ACTIVE_STORES: Lazy<std::sync::Mutex<Vec<&'static dyn Flushable>>> =
+    Lazy::new(|| std::sync::Mutex::new(Vec::new()));
+
+/// 订阅所有 store 的变更通知
+pub fn subscribe() -> broadcast::Receiver<StoreChange> {
+    CHANGES.subscribe()
+}
+
+/// 立即写出所有有未保存修改的 store（退出前调用）
+pub async fn flush_all() {
+    let stores: Vec<&'static dyn Flushable> = match ACTIVE_STORES.lock() {
+        Ok(list) => list.clone(),
+        Err(_) => return,
+    };
+    for store in stores {
+        if let Err(e) = store.flush_boxed().await {
+            log::error!("{}", e);
+        }
+    }
+}
+
+/// 把无法解析的文件另存为 `<文件名>.invalid-<时间>.bak`，调用方随后按空数据处理
+pub(crate) fn backup_invalid_file(path: &Path, label: &str) -> Option<PathBuf> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let ts = chrono::Utc::now().format("%Y%m%dT%H%M%SZ");
+    let backup = path.with_file_name(format!("{}.invalid-{}.bak", name, ts));
+    match std::fs::copy(path, &backup) {
+        Ok(_) => {
+            log::warn!("已备份无法解析的{}到 {:?}", label, backup);
+            Some(backup)
+        }
+        Err(e) => {
+            log::error!("备份{}失败: {}", label, e);
+            None
+        }
+    }
+}
+
+trait Flushable: Send + Sync {
+    fn flush_boxed(&'static self) -> Pin<Box<dyn Future<Output = AppResult<()>> + Send>>;
+}
+
+pub struct PersistedStore<T> {
+    /// 日志/通知里使用的名称，如 "servers"
+    name: &'static str,
+    /// 中文描述，用于日志
+    label: &'static str,
+    path: fn(&StorageConfig) -> PathBuf,
+    key: fn(&T) -> String,
+    /// 加载后对每一项的修正（如重启后运行状态复位）
+    on_load: fn(&mut T),
+    items: Mutex<HashMap<String, T>>,
+    loaded: Mutex<bool>,
+    /// 串行化写盘（后台写入任务与 flush 共用）
+    write_lock: Mutex<()>,
+    dirty: AtomicBool,
+    writer_started: AtomicBool,
+    wake: Notify,
+}
+
+impl<T> PersistedStore<T>
+where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    pub fn new(
+        name: &'static str,
+        label: &'static str,
+        path: fn(&StorageConfig) -> PathBuf,
+        key: fn(&T) -> String,
+    ) -> Self {
+        Self {
+            name,
+            label,
+            path,
+            key,
+            on_load: |_| {},
+            items: Mutex::new(HashMap::new()),
+            loaded: Mutex::new(false),
+            write_lock: Mutex::new(()),
+            dirty: AtomicBool::new(false),
+            writer_started: AtomicBool::new(false),
+            wake: Notify::new(),
+        }
+    }
+
+    pub fn on_load(mut self, f: fn(&mut T)) -> Self {
+        self.on_load = f;
+        self
+    }
+
+    /// 确保已从文件加载（失败时保持未加载状态，下次重试）
+    pub async fn ensure_loaded(&self) {
+        let mut loaded = self.loaded.lock().await;
+        if *loaded {
+            return;
+        }
+        match self.load_from_file() {
+            Ok(items) => {
+                *self.items.lock().await = items;
+                *loaded = true;
+            }
+            Err(e) => {
+                log::warn!("加载{}失败，将在下次重试: {}", self.label, e);
+            }
+        }
+    }
+
+    fn load_from_file(&self) -> AppResult<HashMap<String, T>> {
+        let config = super::get_storage_config()?;
+        self.read_items(&(self.path)(config))
+    }
+
+    fn read_items(&self, path: &Path) -> AppResult<HashMap<String, T>> {
+        log::info!("加载{}: {:?}", self.label, path);
+        if !path.exists() {
+            return Ok(HashMap::new());
+        }
+
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| crate::error::AppError::from(format!("读取{}失败: {}", self.label, e)))?;
+
+        let list: Vec<T> = match serde_json::from_str(&content) {
+            Ok(list) => list,
+            Err(e) => {
+                log::error!(
+                    "解析{} JSON 失败: {}，内容: {}",
+                    self.label,
+                    e,
+                    content.chars().take(200).collect::<String>()
+                );
+                // 原文件另存一份再按空列表处理，避免下次保存时把无法解析的数据覆盖掉
+                backup_invalid_file(path, self.label);
+                Vec::new()
+            }
+        };
+
+        let items: HashMap<String, T> = list
+            .into_iter()
+            .map(|mut item| {
+                (self.on_load)(&mut item);
+                ((self.key)(&item), item)
+            })
+            .collect();
+
+        log::info!("共加载 {} 个{}", items.len(), self.label);
+        Ok(items)
+    }
+
+    /// 丢弃内存中的数据，下次访问时按当前路径重新读盘（切换配置档案时使用，调用前先 flush）。
+    /// 持有写锁并清除脏标记：flush 之后才产生的修改一并丢弃，
+    /// 否则后台写入任务会把旧档案的数据（或清空后的空列表）写到切换后的路径
+    pub async fn unload(&self) {
+        let _write = self.write_lock.lock().await;
+        let mut loaded = self.loaded.lock().await;
+        self.items.lock().await.clear();
+        self.dirty.store(false, Ordering::SeqCst);
+        *loaded = false;
+    }
+
+    pub async fn lock(&self) -> MutexGuard<'_, HashMap<String, T>> {
+        self.items.lock().await
+    }
+
+    pub fn try_lock(&self) -> Result<MutexGuard<'_, HashMap<String, T>>, TryLockError> {
+        self.items.try_lock()
+    }
+
+    /// 标记有修改，由后台写入任务防抖后落盘。
+    /// 只在数据目录不可用时立即返回错误，写盘本身的错误记录在日志中。
+    pub async fn save(&'static self) -> AppResult<()> {
+        let config = super::get_storage_config()?;
+        config.ensure_dirs()?;
+
+        self.dirty.store(true, Ordering::SeqCst);
+        self.ensure_writer();
+        self.wake.notify_one();
+
+        let _ = CHANGES.send(StoreChange {
+            store: self.name.to_string(),
+        });
+        Ok(())
+    }
+
+    fn ensure_writer(&'static self) {
+        if self.writer_started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        if let Ok(mut list) = ACTIVE_STORES.lock() {
+            list.push(self);
+        }
+        tauri::async_runtime::spawn(async move {
+            loop {
+                self.wake.notified().await;
+                tokio::time::sleep(SAVE_DEBOUNCE).await;
+                if let Err(e) = self.flush().await {
+                    log::error!("{}", e);
+                }
+            }
+        });
+    }
+
+    /// 立即写出未保存的修改
+    pub async fn flush(&self) -> AppResult<()> {
+        let _guard = self.write_lock.lock().await;
+        if !self.dirty.swap(false, Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        let result = self.write_to_file().await;
+        if result.is_err() {
+            // 写失败保留脏标记，等下一次 save/flush 重试
+            self.dirty.store(true, Ordering::SeqCst);
+        }
+        result
+    }
+
+    async fn write_to_file(&self) -> AppResult<()> {
+        let config = super::get_storage_config()?;
+        config.ensure_dirs()?;
+        self.write_items(&(self.path)(config)).await
+    }
+
+    async fn write_items(&self, path: &Path) -> AppResult<()> {
+        let (content, count) = {
+            let items = self.items.lock().await;
+            let list: Vec<&T> = items.values().collect();
+            let content = serde_json::to_string(&list).map_err(|e| {
+                crate::error::AppError::from(format!("序列化{}失败: {}", self.label, e))
+            })?;
+            (content, items.len())
+        };
+
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, content)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| crate::error::AppError::from(format!("写入{}失败: {}", self.label, e)))?;
+
+        log::debug!("{}已保存，共 {} 项", self.label, count);
+        Ok(())
+    }
+}
+
+impl<T> Flushable for PersistedStore<T>
+where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    fn flush_boxed(&'static self) -> Pin<Box<dyn Future<Output = AppResult<()>> + Send>> {
+        Box::pin(self.flush())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Item {
+        id: String,
+        running: bool,
+    }
+
+    fn store() -> PersistedStore<Item> {
+        PersistedStore::new(
+            "items",
+            "测试项",
+            |c: &StorageConfig| c.data_dir.join("items.json"),
+            |i: &Item| i.id.clone(),
+        )
+        .on_load(|i| i.running = false)
+    }
+
+    #[tokio::test]
+    async fn test_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("items.json");
+        let store = store();
+        {
+            let mut items = store.lock().await;
+            for id in ["a", "b"] {
+                let item = Item {
+                    id: id.to_string(),
+                    running: true,
+                };
+                items.insert(item.id.clone(), item);
+            }
+        }
+        store.write_items(&path).await.unwrap();
+        assert!(!path.with_extension("json.tmp").exists());
+
+        let loaded = store.read_items(&path).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(
+            loaded.get("b"),
+            Some(&Item {
+                id: "b".to_string(),
+                running: false,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let loaded = store().read_items(&dir.path().join("items.json")).unwrap();
+        assert!(loaded.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_file_is_backed_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("items.json");
+        std::fs::write(&path, "{not json").unwrap();
+
+        let loaded = store().read_items(&path).unwrap();
+        assert!(loaded.is_empty());
+
+        let backups: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|n| n.starts_with("items.json.invalid-"))
+            .collect();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(&backups[0])).unwrap(),
+            "{not json"
+        );
+    }
+}
@@ -21,6 +21,24 @@ pub struct Project {
     pub editor_id: Option<String>,
     #[serde(default)]
     pub claude_env_name: Option<String>,
+    /// 自定义图标（缓存在数据目录中的文件路径）
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// 强调色 #RRGGBB
+    #[serde(default)]
+    pub color: Option<String>,
+    /// 简短描述
+    #[serde(default)]
+    pub description: Option<String>,
+    /// 所属路径根目录，path 由根目录路径 + 相对路径得出
+    #[serde(default)]
+    pub root_id: Option<String>,
+    /// 项目专用的 git 可执行文件，优先于全局设置
+    #[serde(default)]
+    pub git_executable: Option<String>,
+    /// 统计提交使用的分支：None 为当前检出分支，"*" 为全部本地分支
+    #[serde(default)]
+    pub stats_branch: Option<String>,
 }
 
 // ============== 编辑器配置数据 ==============
@@ -88,6 +106,242 @@ pub struct AppSettings {
     /// macOS：是否在 Dock 显示应用图标（false=纯菜单栏应用，true=Dock + 菜单栏）
     #[serde(default)]
     pub show_dock_icon: bool,
+    /// 启动时是否自动恢复上次退出时仍在运行的服务/转发/隧道/下载/会话
+    #[serde(default)]
+    pub auto_resume_services: bool,
+    /// 出站 HTTP 代理（下载、HTTP 请求工具、AI 接口等共用）
+    #[serde(default)]
+    pub proxy: ProxySettings,
+    /// 是否接收浏览器交接的下载（codeshelf-download:// 链接 + 本地 HTTP 端点）
+    #[serde(default)]
+    pub download_handoff_enabled: bool,
+    /// 本地下载交接端点端口（仅监听 127.0.0.1）
+    #[serde(default = "default_download_handoff_port")]
+    pub download_handoff_port: u16,
+    /// 本地端点访问令牌，首次启用时生成
+    #[serde(default)]
+    pub download_handoff_token: Option<String>,
+    /// 是否开启 Prometheus 指标端点（仅监听 127.0.0.1）
+    #[serde(default)]
+    pub metrics_enabled: bool,
+    /// 指标端点端口
+    #[serde(default = "default_metrics_port")]
+    pub metrics_port: u16,
+    /// 只读模式：拦截所有会修改数据或系统状态的命令（演示机、共享工作站）
+    #[serde(default)]
+    pub read_only_mode: bool,
+    /// 解除只读模式所需密码的 argon2id 哈希（PHC 字符串），为空表示无需密码
+    #[serde(default)]
+    pub read_only_password_hash: Option<String>,
+    /// 是否记录本地使用统计（只存本地数据库，不上传）
+    #[serde(default)]
+    pub usage_stats_enabled: bool,
+    /// 下载完成后自动调用系统病毒扫描器（Windows Defender / clamscan）
+    #[serde(default)]
+    pub download_virus_scan: bool,
+    /// 发现威胁后的处理："none" 仅记录 | "quarantine" 移入隔离目录 | "delete" 删除
+    #[serde(default = "default_download_virus_action")]
+    pub download_virus_action: String,
+    /// 使用电池且电量低于阈值时暂停较重的后台任务（统计刷新、定时工作流、镜像任务）
+    #[serde(default)]
+    pub power_saver_enabled: bool,
+    /// 低电量阈值（百分比）
+    #[serde(default = "default_power_saver_threshold")]
+    pub power_saver_threshold: u8,
+    /// Git 提交身份模板（工作 / 个人等），按远程地址规则匹配项目
+    #[serde(default)]
+    pub git_identities: Vec<GitIdentityProfile>,
+    /// 提交前检查身份是否与远程地址规则匹配的模板一致，不一致时拒绝并提示
+    #[serde(default = "default_true")]
+    pub git_identity_guard: bool,
+    /// 全局 git 可执行文件覆盖（命令行，如 `D:\PortableGit\bin\git.exe`、`wsl git`）；为空时用 PATH 中的 git
+    #[serde(default)]
+    pub git_executable: Option<String>,
+    /// 托盘图标角标的数据来源："none" | "servers" 运行中的服务 | "downloads" 进行中的下载 | "notifications" 未查看的通知
+    #[serde(default = "default_tray_badge_source")]
+    pub tray_badge_source: String,
+    /// 首页仪表盘显示的小部件，按顺序排列
+    #[serde(default = "default_dashboard_widgets")]
+    pub dashboard_widgets: Vec<DashboardWidget>,
+    /// 空闲资源自动停止策略
+    #[serde(default)]
+    pub idle_policy: IdlePolicySettings,
+    /// 扫描目录：项目目录丢失时在这些目录下查找被移动 / 改名的仓库
+    #[serde(default)]
+    pub scan_roots: Vec<String>,
+    /// 通知保留策略
+    #[serde(default)]
+    pub notification_retention: NotificationRetention,
+    /// 通知路由与免打扰
+    #[serde(default)]
+    pub notification_routing: NotificationRouting,
+    /// 统计缓存保留的天数，更早的按日提交数自动清理；0 表示不清理
+    #[serde(default = "default_stats_retention_days")]
+    pub stats_retention_days: u32,
+    /// 更新通道："stable" | "beta"
+    #[serde(default = "default_update_channel")]
+    pub update_channel: String,
+    /// 推迟安装的版本，到期前自动检查不再提示该版本
+    #[serde(default)]
+    pub update_deferral: Option<UpdateDeferral>,
+}
+
+/// 推迟的更新
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateDeferral {
+    pub version: String,
+    /// RFC3339
+    pub until: String,
+}
+
+/// 空闲资源自动停止策略；时长为 None 或 0 时不启用对应规则
+#[derive(Debug, Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct IdlePolicySettings {
+    /// 静态服务连续多少分钟没有请求后停止
+    #[serde(default)]
+    pub server_idle_minutes: Option<u32>,
+    /// 转发规则连续多少分钟没有连接后停止
+    #[serde(default)]
+    pub forward_idle_minutes: Option<u32>,
+    /// 按流量计费的网络下暂停下载，切回不计费网络后自动恢复
+    #[serde(default)]
+    pub pause_downloads_on_metered: bool,
+    /// Netcat 会话多少小时没有收发数据后关闭
+    #[serde(default)]
+    pub netcat_idle_hours: Option<u32>,
+    /// 不受自动停止影响的服务 / 转发规则 / 下载 / 会话 id
+    #[serde(default)]
+    pub exempt_ids: Vec<String>,
+}
+
+/// 通知保留策略：超过条数先删已读的旧通知；超过天数的删除（可保留未读）
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationRetention {
+    #[serde(default = "default_notification_max_count")]
+    pub max_count: u32,
+    /// 0 表示不按时间删除
+    #[serde(default)]
+    pub max_age_days: u32,
+    /// 未读通知不按时间删除
+    #[serde(default = "default_true")]
+    pub keep_unread: bool,
+}
+
+fn default_notification_max_count() -> u32 {
+    100
+}
+
+impl Default for NotificationRetention {
+    fn default() -> Self {
+        Self {
+            max_count: default_notification_max_count(),
+            max_age_days: 0,
+            keep_unread: true,
+        }
+    }
+}
+
+/// 通知路由：按类别决定去向，免打扰期间不弹系统通知
+#[derive(Debug, Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationRouting {
+    /// 按类别覆盖去向；未列出的类别全部开启
+    #[serde(default)]
+    pub routes: Vec<NotificationRoute>,
+    /// 免打扰时段
+    #[serde(default)]
+    pub dnd_schedules: Vec<DndSchedule>,
+    /// 手动免打扰的结束时间（RFC 3339），如演示期间临时开启
+    #[serde(default)]
+    pub dnd_until: Option<String>,
+    /// 免打扰期间仍弹出错误级别的系统通知
+    #[serde(default)]
+    pub dnd_allow_errors: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationRoute {
+    /// "download" | "server" | "monitor" | "git" | "system"
+    pub category: String,
+    /// 写入通知中心
+    #[serde(default = "default_true")]
+    pub in_app: bool,
+    /// 弹出系统通知
+    #[serde(default = "default_true")]
+    pub os_toast: bool,
+    /// 计入托盘角标
+    #[serde(default = "default_true")]
+    pub tray_badge: bool,
+}
+
+/// 免打扰时段，按本地时间
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DndSchedule {
+    /// 生效的星期，0 = 周日；为空表示每天
+    #[serde(default)]
+    pub days: Vec<u8>,
+    /// "HH:MM"；结束早于开始表示跨午夜
+    pub start: String,
+    pub end: String,
+}
+
+/// 仪表盘小部件
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardWidget {
+    /// "stats" | "heatmap" | "recent_commits" | "running_services" | "watched_ports"
+    pub kind: String,
+    /// "small" | "medium" | "large"
+    #[serde(default = "default_widget_size")]
+    pub size: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxySettings {
+    /// "system" 跟随系统/环境变量 | "none" 不使用代理 | "manual" 手动指定
+    #[serde(default = "default_proxy_mode")]
+    pub mode: String,
+    /// 手动代理地址，支持 http:// https:// socks5:// socks5h://
+    #[serde(default)]
+    pub url: Option<String>,
+    /// 不走代理的主机，支持 `*.example.com`、`.example.com`、IP 与 CIDR
+    #[serde(default)]
+    pub bypass: Vec<String>,
+}
+
+fn default_proxy_mode() -> String {
+    "system".to_string()
+}
+
+impl Default for ProxySettings {
+    fn default() -> Self {
+        Self {
+            mode: default_proxy_mode(),
+            url: None,
+            bypass: Vec::new(),
+        }
+    }
+}
+
+/// Git 提交身份模板
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GitIdentityProfile {
+    pub id: String,
+    /// 显示名，如 "工作"、"个人"
+    pub name: String,
+    pub user_name: String,
+    pub email: String,
+    /// 远程地址规则，如 "github.com/my-company"、"gitlab.corp.com"、"*.corp.com"；
+    /// 项目任一远程地址匹配时应使用此身份
+    #[serde(default)]
+    pub remote_patterns: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
@@ -115,6 +369,53 @@ fn default_mcp_gateway_port() -> u16 {
     8787
 }
 
+fn default_download_handoff_port() -> u16 {
+    17654
+}
+
+fn default_metrics_port() -> u16 {
+    9464
+}
+
+fn default_download_virus_action() -> String {
+    "none".to_string()
+}
+
+fn default_stats_retention_days() -> u32 {
+    365
+}
+
+fn default_update_channel() -> String {
+    "stable".to_string()
+}
+
+fn default_tray_badge_source() -> String {
+    "none".to_string()
+}
+
+fn default_widget_size() -> String {
+    "medium".to_string()
+}
+
+/// 默认布局与改版前的首页一致
+fn default_dashboard_widgets() -> Vec<DashboardWidget> {
+    [
+        ("stats", "large"),
+        ("heatmap", "large"),
+        ("recent_commits", "medium"),
+    ]
+    .iter()
+    .map(|(kind, size)| DashboardWidget {
+        kind: kind.to_string(),
+        size: size.to_string(),
+    })
+    .collect()
+}
+
+fn default_power_saver_threshold() -> u8 {
+    30
+}
+
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
@@ -134,6 +435,32 @@ impl Default for AppSettings {
             mcp_gateway_port: default_mcp_gateway_port(),
             mcp_gateway_keys: Vec::new(),
             show_dock_icon: false,
+            auto_resume_services: false,
+            proxy: ProxySettings::default(),
+            download_handoff_enabled: false,
+            download_handoff_port: default_download_handoff_port(),
+            download_handoff_token: None,
+            metrics_enabled: false,
+            metrics_port: default_metrics_port(),
+            read_only_mode: false,
+            read_only_password_hash: None,
+            usage_stats_enabled: false,
+            download_virus_scan: false,
+            download_virus_action: default_download_virus_action(),
+            power_saver_enabled: false,
+            power_saver_threshold: default_power_saver_threshold(),
+            git_identities: Vec::new(),
+            git_identity_guard: true,
+            git_executable: None,
+            tray_badge_source: default_tray_badge_source(),
+            dashboard_widgets: default_dashboard_widgets(),
+            idle_policy: IdlePolicySettings::default(),
+            scan_roots: Vec::new(),
+            notification_retention: NotificationRetention::default(),
+            notification_routing: NotificationRouting::default(),
+            stats_retention_days: default_stats_retention_days(),
+            update_channel: default_update_channel(),
+            update_deferral: None,
         }
     }
 }
@@ -144,6 +471,9 @@ impl Default for AppSettings {
 #[derive(Debug, Serialize, Deserialize, Clone, Default, specta::Type)]
 pub struct UiState {
     pub recent_detail_project_ids: Vec<String>,
+    /// 上次打开的工具箱工具，启动后回到该工具
+    #[serde(default)]
+    pub active_toolbox_tool: Option<String>,
 }
 
 // ============== 通知数据 ==============
@@ -152,11 +482,30 @@ pub struct UiState {
 #[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
 pub struct Notification {
     pub id: String,
+    /// 严重程度："info" | "success" | "warning" | "error"
     pub notification_type: String,
     pub title: String,
     #[serde(default)]
     pub message: String,
     pub created_at: String,
+    /// 来源类别，见 notification_routing::CATEGORIES；前端发起的通知为空
+    #[serde(default)]
+    pub category: String,
+    #[serde(default)]
+    pub read: bool,
+    /// 通知上的操作按钮
+    #[serde(default)]
+    pub actions: Vec<NotificationAction>,
+}
+
+/// 通知操作
+#[derive(Debug, Serialize, Deserialize, Clone, specta::Type)]
+pub struct NotificationAction {
+    /// "open_folder" | "open_url" | "retry_download" | "navigate"
+    pub kind: String,
+    pub label: String,
+    /// 文件夹路径、地址、下载任务 id 或工具箱工具名
+    pub target: String,
 }
 
 // ============== Claude 快捷配置数据 ==============
@@ -0,0 +1,128 @@
+// 本机凭据加密
+//
+// 数据库里的敏感字段（部署目标的密码、私钥口令、S3 Secret Key 等）以 AES-256-GCM 加密后落盘，
+// 格式为 "enc:v1:" + base64(nonce || 密文)。密钥为数据目录下的 secret.key（32 字节随机数，
+// Unix 上权限 0600），首次使用时生成：先写临时文件并 fsync，再改名到位，避免留下截断的密钥。
+// 非空且没有加密前缀的值一律视为无效，不当作明文读取。
+// 另提供基于系统随机源的令牌生成，供本地端点与确认流程共用。
+
+use crate::error::{AppError, AppResult};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+const PREFIX: &str = "enc:v1:";
+const KEY_LEN: usize = 32;
+
+/// 已加载的密钥；读取失败时不缓存，下次重试
+static KEY: Mutex<Option<[u8; KEY_LEN]>> = Mutex::new(None);
+
+/// 读取密钥文件，不存在时生成
+fn load_or_create_key(path: &Path) -> AppResult<[u8; KEY_LEN]> {
+    match std::fs::read(path) {
+        Ok(bytes) => {
+            return bytes.as_slice().try_into().map_err(|_| {
+                AppError::internal(format!(
+                    "凭据密钥文件已损坏（长度 {} 字节，应为 {} 字节）: {}",
+                    bytes.len(),
+                    KEY_LEN,
+                    path.display()
+                ))
+            });
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(AppError::from(format!("读取凭据密钥失败: {}", e))),
+    }
+
+    let mut key = [0u8; KEY_LEN];
+    getrandom::getrandom(&mut key)
+        .map_err(|e| AppError::internal(format!("生成凭据密钥失败: {}", e)))?;
+
+    let tmp = path.with_extension("key.tmp");
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let written = options.open(&tmp).and_then(|mut file| {
+        file.write_all(&key)?;
+        file.sync_all()
+    });
+    written
+        .and_then(|_| std::fs::rename(&tmp, path))
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&tmp);
+            AppError::from(format!("创建凭据密钥失败: {}", e))
+        })?;
+    Ok(key)
+}
+
+fn key() -> AppResult<LessSafeKey> {
+    let mut cached = KEY.lock().unwrap_or_else(|e| e.into_inner());
+    let bytes = match *cached {
+        Some(bytes) => bytes,
+        None => {
+            let path = crate::storage::get_storage_config()?.secret_key_file();
+            let bytes = load_or_create_key(&path)?;
+            *cached = Some(bytes);
+            bytes
+        }
+    };
+    let unbound =
+        UnboundKey::new(&AES_256_GCM, &bytes).map_err(|_| AppError::internal("凭据密钥无效"))?;
+    Ok(LessSafeKey::new(unbound))
+}
+
+/// 生成随机令牌（128 位，取自系统随机源，十六进制），用于本地端点访问令牌与确认令牌
+pub fn new_token() -> AppResult<String> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes)
+        .map_err(|e| AppError::internal(format!("生成随机令牌失败: {}", e)))?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// 值是否已经是加密格式
+pub fn is_encrypted(value: &str) -> bool {
+    value.starts_with(PREFIX)
+}
+
+/// 加密一个敏感字段；空字符串与已加密的值原样返回
+pub fn encrypt(plain: &str) -> AppResult<String> {
+    if plain.is_empty() || is_encrypted(plain) {
+        return Ok(plain.to_string());
+    }
+    let key = key()?;
+    let mut nonce = [0u8; NONCE_LEN];
+    getrandom::getrandom(&mut nonce)
+        .map_err(|e| AppError::internal(format!("生成随机数失败: {}", e)))?;
+    let mut data = plain.as_bytes().to_vec();
+    key.seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut data)
+        .map_err(|_| AppError::internal("加密凭据失败"))?;
+    let mut out = nonce.to_vec();
+    out.extend_from_slice(&data);
+    Ok(format!("{}{}", PREFIX, STANDARD.encode(out)))
+}
+
+/// 解密一个敏感字段；空字符串原样返回，没有加密前缀的值视为无效
+pub fn decrypt(value: &str) -> AppResult<String> {
+    if value.is_empty() {
+        return Ok(String::new());
+    }
+    let failed =
+        || AppError::invalid("凭据无法解密（密钥文件已变更或数据来自其他设备），请重新填写");
+    let encoded = value.strip_prefix(PREFIX).ok_or_else(failed)?;
+    let mut data = STANDARD.decode(encoded).map_err(|_| failed())?;
+    if data.len() < NONCE_LEN {
+        return Err(failed());
+    }
+    let key = key()?;
+    let nonce = Nonce::try_assume_unique_for_key(&data[..NONCE_LEN]).map_err(|_| failed())?;
+    let plain = key
+        .open_in_place(nonce, Aad::empty(), &mut data[NONCE_LEN..])
+        .map_err(|_| failed())?;
+    String::from_utf8(plain.to_vec()).map_err(|_| failed())
+}
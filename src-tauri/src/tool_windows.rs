@@ -0,0 +1,221 @@
+// 独立工具窗口：把 netcat 会话、项目 git log 等工具拆到单独的 webview 窗口，
+// 方便多显示器下并排使用。
+//
+// 每个窗口在注册表里记一条 ToolWindowInfo（kind + resource_id），
+// 前端新窗口启动后用 get_tool_window_context 取回自己的上下文；
+// 后端需要只推给某个资源的窗口时用 emit_to_resource 按 resource_id 路由。
+
+use crate::error::AppResult;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder, Window, WindowEvent};
+
+pub const MAIN_WINDOW: &str = "main";
+
+/// 工具窗口 label 前缀（capabilities 里按 `tool-*` 授权）
+const TOOL_WINDOW_PREFIX: &str = "tool-";
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolWindowInput {
+    /// 工具类型，如 "netcat" / "gitLog"
+    pub kind: String,
+    /// 绑定的资源 id（会话 id、项目路径等），同一资源只开一个窗口
+    pub resource_id: Option<String>,
+    pub title: Option<String>,
+    pub width: Option<f64>,
+    pub height: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolWindowInfo {
+    pub label: String,
+    pub kind: String,
+    pub resource_id: Option<String>,
+    pub title: String,
+    pub created_at: String,
+}
+
+/// 已打开的工具窗口注册表（label → info）
+#[derive(Default)]
+pub struct ToolWindowRegistry(pub Mutex<HashMap<String, ToolWindowInfo>>);
+
+impl ToolWindowRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+pub fn is_main_window(label: &str) -> bool {
+    label == MAIN_WINDOW
+}
+
+/// 全局窗口事件：主窗口关闭时隐藏到托盘，工具窗口正常销毁并从注册表移除
+pub fn handle_window_event(window: &Window, event: &WindowEvent) {
+    match event {
+        WindowEvent::CloseRequested { api, .. } if is_main_window(window.label()) => {
+            api.prevent_close();
+            let _ = window.hide();
+        }
+        WindowEvent::Focused(true) if is_main_window(window.label()) => {
+            crate::tray_badge::mark_seen();
+        }
+        WindowEvent::Destroyed => {
+            if let Some(registry) = window.app_handle().try_state::<ToolWindowRegistry>() {
+                let removed = registry
+                    .0
+                    .lock()
+                    .ok()
+                    .and_then(|mut map| map.remove(window.label()));
+                if let Some(info) = removed {
+                    let _ = window.app_handle().emit("tool-window-closed", info);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 把事件只发给绑定了某个资源的工具窗口（主窗口不受影响，仍走全局 emit）
+pub fn emit_to_resource<S: Serialize + Clone>(
+    app: &AppHandle,
+    resource_id: &str,
+    event: &str,
+    payload: S,
+) {
+    let Some(registry) = app.try_state::<ToolWindowRegistry>() else {
+        return;
+    };
+    let labels: Vec<String> = match registry.0.lock() {
+        Ok(map) => map
+            .values()
+            .filter(|w| w.resource_id.as_deref() == Some(resource_id))
+            .map(|w| w.label.clone())
+            .collect(),
+        Err(_) => return,
+    };
+    for label in labels {
+        let _ = app.emit_to(label.as_str(), event, payload.clone());
+    }
+}
+
+fn window_label(kind: &str, resource_id: Option<&str>) -> String {
+    let raw = match resource_id {
+        Some(r) => format!("{}-{}", kind, r),
+        None => format!("{}-{}", kind, crate::storage::generate_id()),
+    };
+    // label 只允许字母数字和 -/_ 等少数字符
+    let safe: String = raw
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}{}", TOOL_WINDOW_PREFIX, safe)
+}
+
+// ============== 命令 ==============
+
+/// 打开（或聚焦已存在的）工具窗口，返回窗口 label
+#[tauri::command]
+#[specta::specta]
+pub async fn open_tool_window(app: AppHandle, input: ToolWindowInput) -> AppResult<String> {
+    if input.kind.trim().is_empty() {
+        return Err(crate::error::AppError::invalid("kind 不能为空"));
+    }
+
+    let label = window_label(&input.kind, input.resource_id.as_deref());
+    if let Some(existing) = app.get_webview_window(&label) {
+        let _ = existing.show();
+        let _ = existing.unminimize();
+        let _ = existing.set_focus();
+        return Ok(label);
+    }
+
+    let title = input
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("CodeShelf - {}", input.kind));
+
+    // 前端入口通过 query 判断当前是独立工具窗口
+    let url = format!("index.html?toolWindow={}", label);
+    WebviewWindowBuilder::new(&app, &label, WebviewUrl::App(url.into()))
+        .title(&title)
+        .inner_size(input.width.unwrap_or(960.0), input.height.unwrap_or(640.0))
+        .min_inner_size(480.0, 360.0)
+        .decorations(false)
+        .transparent(true)
+        .user_agent("CodeShelf-Tauri-Webview/1.0")
+        .build()
+        .map_err(|e| crate::error::AppError::from(format!("创建窗口失败: {}", e)))?;
+
+    let info = ToolWindowInfo {
+        label: label.clone(),
+        kind: input.kind,
+        resource_id: input.resource_id,
+        title,
+        created_at: crate::storage::current_iso_time(),
+    };
+    if let Some(registry) = app.try_state::<ToolWindowRegistry>() {
+        if let Ok(mut map) = registry.0.lock() {
+            map.insert(label.clone(), info.clone());
+        }
+    }
+    let _ = app.emit("tool-window-opened", info);
+
+    Ok(label)
+}
+
+/// 关闭工具窗口（主窗口不允许通过此命令关闭）
+#[tauri::command]
+#[specta::specta]
+pub async fn close_tool_window(app: AppHandle, label: String) -> AppResult<()> {
+    if is_main_window(&label) {
+        return Err(crate::error::AppError::invalid("不能关闭主窗口"));
+    }
+    if let Some(window) = app.get_webview_window(&label) {
+        window
+            .close()
+            .map_err(|e| crate::error::AppError::from(format!("关闭窗口失败: {}", e)))?;
+    }
+    Ok(())
+}
+
+/// 列出当前打开的工具窗口
+#[tauri::command]
+#[specta::specta]
+pub async fn list_tool_windows(app: AppHandle) -> AppResult<Vec<ToolWindowInfo>> {
+    let registry = app
+        .try_state::<ToolWindowRegistry>()
+        .ok_or("窗口注册表未初始化")?;
+    let map = registry
+        .0
+        .lock()
+        .map_err(|e| crate::error::AppError::internal(e.to_string()))?;
+    let mut list: Vec<ToolWindowInfo> = map.values().cloned().collect();
+    list.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    Ok(list)
+}
+
+/// 调用方窗口取回自己的上下文；主窗口返回 None
+#[tauri::command]
+#[specta::specta]
+pub async fn get_tool_window_context(
+    app: AppHandle,
+    window: Window,
+) -> AppResult<Option<ToolWindowInfo>> {
+    let Some(registry) = app.try_state::<ToolWindowRegistry>() else {
+        return Ok(None);
+    };
+    let map = registry
+        .0
+        .lock()
+        .map_err(|e| crate::error::AppError::internal(e.to_string()))?;
+    Ok(map.get(window.label()).cloned())
+}
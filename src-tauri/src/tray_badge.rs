@@ -0,0 +1,204 @@
+// 托盘图标角标：在托盘图标右上角叠加红色圆点和数字，反映应用状态。
+//
+// 角标来源由设置 tray_badge_source 决定：
+//   - servers:       运行中的静态服务数
+//   - downloads:     正在下载的任务数
+//   - notifications: 主窗口上次获得焦点后新增的通知数
+// 后台每隔 REFRESH_INTERVAL 统计一次，数值变化时才重绘图标。
+// 提示文字来自运行概览（服务 / 转发 / 下载 / Netcat / 进程各一行），内容变化时才更新。
+
+use crate::app_setup::TRAY_ID;
+use crate::commands;
+use crate::commands::runtime_overview::RuntimeOverview;
+use crate::storage::{documents, AppSettings};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::image::Image;
+use tauri::AppHandle;
+
+/// 可选的角标来源
+pub const SOURCES: &[&str] = &["none", "servers", "downloads", "notifications"];
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 表示「尚未绘制」，下一轮统计必定重绘
+const DIRTY: usize = usize::MAX;
+
+const BADGE_COLOR: [u8; 3] = [220, 38, 38];
+
+static SOURCE: Mutex<String> = Mutex::new(String::new());
+
+/// 上次绘制时的数值
+static RENDERED: AtomicUsize = AtomicUsize::new(DIRTY);
+
+/// 上次设置的提示文字
+static TOOLTIP: Mutex<String> = Mutex::new(String::new());
+
+/// 未查看的通知数
+static UNSEEN: AtomicUsize = AtomicUsize::new(0);
+
+/// 3x5 点阵数字，每行低 3 位从左到右
+const GLYPHS: [[u8; 5]; 11] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111], // 0
+    [0b010, 0b110, 0b010, 0b010, 0b111], // 1
+    [0b111, 0b001, 0b111, 0b100, 0b111], // 2
+    [0b111, 0b001, 0b111, 0b001, 0b111], // 3
+    [0b101, 0b101, 0b111, 0b001, 0b001], // 4
+    [0b111, 0b100, 0b111, 0b001, 0b111], // 5
+    [0b111, 0b100, 0b111, 0b101, 0b111], // 6
+    [0b111, 0b001, 0b010, 0b010, 0b010], // 7
+    [0b111, 0b101, 0b111, 0b101, 0b111], // 8
+    [0b111, 0b101, 0b111, 0b001, 0b111], // 9
+    [0b000, 0b010, 0b111, 0b010, 0b000], // +
+];
+
+fn source() -> String {
+    SOURCE.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// 设置变更时调用，下一轮按新来源重绘
+pub fn set_source(source: &str) {
+    *SOURCE.lock().unwrap_or_else(|e| e.into_inner()) = source.to_string();
+    invalidate();
+}
+
+/// 托盘图标被其它逻辑替换过（例如告警闪烁），下一轮重新绘制角标
+pub fn invalidate() {
+    RENDERED.store(DIRTY, Ordering::Relaxed);
+}
+
+/// 新增一条后台通知
+pub fn note_notification() {
+    UNSEEN.fetch_add(1, Ordering::Relaxed);
+}
+
+/// 主窗口获得焦点：通知视为已查看
+pub fn mark_seen() {
+    UNSEEN.store(0, Ordering::Relaxed);
+}
+
+/// 启动时从设置载入角标来源，并启动统计任务
+pub fn init(app: &AppHandle) {
+    let settings: AppSettings = documents::load(&documents::APP_SETTINGS).unwrap_or_default();
+    set_source(&settings.tray_badge_source);
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            refresh(&app).await;
+            tokio::time::sleep(REFRESH_INTERVAL).await;
+        }
+    });
+}
+
+async fn current_count(source: &str) -> usize {
+    match source {
+        "servers" => commands::toolbox::server::running_server_count().await,
+        "downloads" => commands::toolbox::downloader::active_download_count().await,
+        "notifications" => UNSEEN.load(Ordering::Relaxed),
+        _ => 0,
+    }
+}
+
+async fn refresh(app: &AppHandle) {
+    let source = source();
+    let count = current_count(&source).await;
+    let overview = commands::runtime_overview::collect(app).await;
+    let tooltip = tooltip(&source, count, &overview);
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+
+    if RENDERED.swap(count, Ordering::Relaxed) != count {
+        let Ok(base) = Image::from_bytes(include_bytes!("../icons/icon.png")) else {
+            return;
+        };
+        let icon = if count == 0 {
+            base
+        } else {
+            render_badge(&base, count)
+        };
+        let _ = tray.set_icon(Some(icon));
+    }
+
+    let mut current = TOOLTIP.lock().unwrap_or_else(|e| e.into_inner());
+    if *current != tooltip {
+        let _ = tray.set_tooltip(Some(&tooltip));
+        *current = tooltip;
+    }
+}
+
+fn tooltip(source: &str, count: usize, overview: &RuntimeOverview) -> String {
+    let mut lines = overview.summary_lines();
+    if source == "notifications" && count > 0 {
+        lines.push(format!("{} 条新通知", count));
+    }
+    if lines.is_empty() {
+        return "CodeShelf - 代码书架".to_string();
+    }
+    format!("CodeShelf\n{}", lines.join("\n"))
+}
+
+/// 在图标右上角画红色圆形角标，数字超过 9 显示「9+」
+fn render_badge(base: &Image<'_>, count: usize) -> Image<'static> {
+    let (width, height) = (base.width(), base.height());
+    let mut rgba = base.rgba().to_vec();
+    let size = width.min(height) as f32;
+    let radius = size * 0.3;
+    let (cx, cy) = (width as f32 - radius, radius);
+
+    let mut blend = |x: u32, y: u32, color: [u8; 3], alpha: f32| {
+        if x >= width || y >= height || alpha <= 0.0 {
+            return;
+        }
+        let i = ((y * width + x) * 4) as usize;
+        let alpha = alpha.min(1.0);
+        for (c, value) in color.iter().enumerate() {
+            rgba[i + c] = (rgba[i + c] as f32 * (1.0 - alpha) + *value as f32 * alpha) as u8;
+        }
+        rgba[i + 3] = rgba[i + 3].max((alpha * 255.0) as u8);
+    };
+
+    // 圆形底色，边缘按覆盖比例做简单抗锯齿
+    let left = (cx - radius).floor().max(0.0) as u32;
+    let bottom = (cy + radius).ceil() as u32;
+    for y in 0..=bottom {
+        for x in left..width {
+            let dx = x as f32 + 0.5 - cx;
+            let dy = y as f32 + 0.5 - cy;
+            let coverage = radius - (dx * dx + dy * dy).sqrt() + 0.5;
+            blend(x, y, BADGE_COLOR, coverage);
+        }
+    }
+
+    // 白色数字居中
+    let glyphs: Vec<usize> = if count > 9 { vec![9, 10] } else { vec![count] };
+    let scale = ((radius * 1.1 / 5.0) as u32).max(1);
+    let text_width = glyphs.len() as u32 * 4 * scale - scale;
+    let text_height = 5 * scale;
+    let origin_x = (cx - text_width as f32 / 2.0).round().max(0.0) as u32;
+    let origin_y = (cy - text_height as f32 / 2.0).round().max(0.0) as u32;
+    for (n, glyph) in glyphs.iter().enumerate() {
+        let glyph_x = origin_x + n as u32 * 4 * scale;
+        for (row, bits) in GLYPHS[*glyph].iter().enumerate() {
+            for col in 0..3u32 {
+                if bits & (0b100 >> col) == 0 {
+                    continue;
+                }
+                for dy in 0..scale {
+                    for dx in 0..scale {
+                        blend(
+                            glyph_x + col * scale + dx,
+                            origin_y + row as u32 * scale + dy,
+                            [255, 255, 255],
+                            1.0,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    Image::new_owned(rgba, width, height)
+}
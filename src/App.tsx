@@ -3,7 +3,7 @@ import { QueryClient, QueryClientProvider } from "@tanstack/react-query";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { MainLayout } from "@/components/layout";
-import { ToastContainer, UpdateNotification, ShortcutQuickLookup, ClipboardQuickAccess } from "@/components/ui";
+import { ToastContainer, UpdateNotification, ShortcutQuickLookup, ClipboardQuickAccess, showToast } from "@/components/ui";
 import { ConfirmHost } from "@/components/common/useConfirm";
 
 // 页面按需加载：各 page 拆成独立 chunk，避免初始 index.js 突破 1MB。
@@ -74,6 +74,9 @@ interface NotificationBackend {
   title: string;
   message: string;
   created_at: string;
+  category?: string;
+  read?: boolean;
+  actions?: Notification["actions"];
 }
 
 // 初始化应用：从后端 data 目录加载所有数据
@@ -113,6 +116,9 @@ async function initializeApp() {
       title: n.title,
       message: n.message,
       createdAt: n.created_at,
+      category: n.category || undefined,
+      read: n.read ?? false,
+      actions: n.actions ?? [],
     }));
 
     const normalizedAiProviders = useAiProvidersStore.getState().ensureAiDefaultProvider(aiProviders || []);
@@ -150,6 +156,59 @@ async function initializeApp() {
   }
 }
 
+// 后端广播的设置文件外部修改
+interface SettingsChange {
+  document: "app_settings" | "labels" | "categories" | "editors" | "terminal";
+  conflictBackup: string | null;
+}
+
+// 设置文件被外部修改（手动编辑、同步盘、另一个实例）后只重新加载对应的部分
+async function reloadSettingsDocument(change: SettingsChange) {
+  if (change.conflictBackup) {
+    showToast("warning", "设置文件在外部被修改过", `已覆盖，外部版本备份在 ${change.conflictBackup}`, 6000);
+    return;
+  }
+  try {
+    switch (change.document) {
+      case "app_settings": {
+        const settings = await invoke<AppSettings>("get_app_settings");
+        useSettingsStore.setState({
+          theme: (settings.theme || "light") as Theme,
+          viewMode: (settings.view_mode || "grid") as "grid" | "list",
+          sidebarCollapsed: settings.sidebar_collapsed || false,
+          scanDepth: settings.scan_depth || 3,
+          autoUpdate: settings.auto_update !== false,
+          chatHistoryDir: settings.chat_history_dir,
+          showDockIcon: settings.show_dock_icon === true,
+        });
+        break;
+      }
+      case "labels":
+        useProjectsStore.setState({ labels: (await invoke<string[]>("get_labels")) || [] });
+        break;
+      case "categories":
+        useProjectsStore.setState({ categories: (await invoke<string[]>("get_categories")) || [] });
+        break;
+      case "editors":
+        useEditorsStore.setState({ editors: (await invoke<EditorConfig[]>("get_editors")) || [] });
+        break;
+      case "terminal": {
+        const terminal = await invoke<TerminalConfigBackend>("get_terminal_config");
+        useEditorsStore.setState({
+          terminalConfig: {
+            type: (terminal.terminal_type || "default") as TerminalConfig["type"],
+            customPath: terminal.custom_path,
+            paths: terminal.terminal_path ? { [terminal.terminal_type]: terminal.terminal_path } : undefined,
+          },
+        });
+        break;
+      }
+    }
+  } catch (err) {
+    console.error("重新加载设置失败:", err);
+  }
+}
+
 function AppContent() {
   const initialized = useUiStore((state) => state.initialized);
   const popupAutoHideWindow = useUiStore((s) => s.popupAutoHideWindow);
@@ -160,6 +219,13 @@ function AppContent() {
 
   useAppShortcuts();
 
+  useEffect(() => {
+    const unlisten = listen<SettingsChange>("settings-changed", (event) => {
+      reloadSettingsDocument(event.payload);
+    });
+    return () => { unlisten.then((fn) => fn()); };
+  }, []);
+
   // 监听托盘菜单工具箱导航事件
   useEffect(() => {
     const unlisten = listen<string>("navigate-to-tool", (event) => {
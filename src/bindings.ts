@@ -6,9 +6,12 @@
 
 
 export const commands = {
-async scanDirectory(path: string, depth: number | null) : Promise<Result<GitRepo[], string>> {
+/**
+ * 扫描目录下的 Git 仓库；options 为空时沿用默认行为（跟随符号链接、不过滤）
+ */
+async scanDirectory(path: string, depth: number | null, options: ScanOptions | null) : Promise<Result<GitRepo[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("scan_directory", { path, depth }) };
+    return { status: "ok", data: await TAURI_INVOKE("scan_directory", { path, depth, options }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -22,6 +25,17 @@ async getGitStatus(path: string) : Promise<Result<GitStatus, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * 查询仓库当前执行中与排队中的写操作
+ */
+async getGitQueue(path: string) : Promise<Result<GitQueueState, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_git_queue", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async getCommitHistory(path: string, limit: number | null, refName: string | null) : Promise<Result<CommitInfo[], string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("get_commit_history", { path, limit, refName }) };
@@ -53,11 +67,11 @@ async getCommitFiles(path: string, commitHash: string) : Promise<Result<CommitFi
 }
 },
 /**
- * 搜索提交历史
+ * 搜索提交历史；options 可附加日期范围、跨分支、路径与内容（pickaxe）过滤
  */
-async searchCommits(path: string, query: string, searchType: string | null, limit: number | null) : Promise<Result<CommitInfo[], string>> {
+async searchCommits(path: string, query: string, searchType: string | null, limit: number | null, options: CommitSearchOptions | null) : Promise<Result<CommitInfo[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("search_commits", { path, query, searchType, limit }) };
+    return { status: "ok", data: await TAURI_INVOKE("search_commits", { path, query, searchType, limit, options }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -103,25 +117,47 @@ async removeRemote(path: string, name: string) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
-async gitPush(path: string, remote: string, branch: string, force: boolean) : Promise<Result<string, string>> {
+/**
+ * 在 SSH 与 HTTPS 之间转换远程地址；remote 为空时处理所有远程
+ */
+async convertRemoteProtocol(paths: string[], remote: string | null, protocol: string, apply: boolean) : Promise<Result<RemoteRewrite[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("convert_remote_protocol", { paths, remote, protocol, apply }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 批量替换远程地址的主机名（不区分大小写），协议、用户与路径保持不变
+ */
+async rewriteRemoteHost(paths: string[], fromHost: string, toHost: string, apply: boolean) : Promise<Result<RemoteRewrite[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("rewrite_remote_host", { paths, fromHost, toHost, apply }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async gitPush(path: string, remote: string, branch: string, force: boolean, opId: string | null, timeoutSecs: number | null) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("git_push", { path, remote, branch, force }) };
+    return { status: "ok", data: await TAURI_INVOKE("git_push", { path, remote, branch, force, opId, timeoutSecs }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async gitPull(path: string, remote: string, branch: string) : Promise<Result<string, string>> {
+async gitPull(path: string, remote: string, branch: string, opId: string | null, timeoutSecs: number | null) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("git_pull", { path, remote, branch }) };
+    return { status: "ok", data: await TAURI_INVOKE("git_pull", { path, remote, branch, opId, timeoutSecs }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async gitFetch(path: string, remote: string | null) : Promise<Result<string, string>> {
+async gitFetch(path: string, remote: string | null, opId: string | null, timeoutSecs: number | null) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("git_fetch", { path, remote }) };
+    return { status: "ok", data: await TAURI_INVOKE("git_fetch", { path, remote, opId, timeoutSecs }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -143,6 +179,28 @@ async cancelGitClone() : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * 取消进行中的操作：置取消标志并结束其子进程
+ */
+async cancelOperation(opId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_operation", { opId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 进行中的操作（按开始时间排序）
+ */
+async listOperations() : Promise<Result<OperationInfo[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_operations") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async syncToRemote(path: string, sourceRemote: string, targetRemote: string, syncAllBranches: boolean, force: boolean) : Promise<Result<string, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("sync_to_remote", { path, sourceRemote, targetRemote, syncAllBranches, force }) };
@@ -151,6 +209,17 @@ async syncToRemote(path: string, sourceRemote: string, targetRemote: string, syn
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * sync_to_remote 的预演：不推送，逐个分支返回会新建、快进还是强制覆盖（附提交范围）
+ */
+async previewSyncToRemote(path: string, sourceRemote: string, targetRemote: string, syncAllBranches: boolean, force: boolean) : Promise<Result<SyncBranchPreview[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("preview_sync_to_remote", { path, sourceRemote, targetRemote, syncAllBranches, force }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async checkoutBranch(path: string, branch: string) : Promise<Result<string, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("checkout_branch", { path, branch }) };
@@ -167,6 +236,17 @@ async createBranch(path: string, branch: string, checkout: boolean) : Promise<Re
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * 对比两个分支：ahead / behind、各自独有的提交，以及 head 相对合并基点的文件改动
+ */
+async compareBranches(path: string, base: string, head: string) : Promise<Result<BranchComparison, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("compare_branches", { path, base, head }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async gitAdd(path: string, files: string[]) : Promise<Result<string, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("git_add", { path, files }) };
@@ -255,2778 +335,7129 @@ async gitMarkResolved(path: string, file: string) : Promise<Result<string, strin
     else return { status: "error", error: e  as any };
 }
 },
-async gitCommit(path: string, message: string) : Promise<Result<string, string>> {
+/**
+ * 中止进行中的操作；kind 与 RepoOperation.kind 一致
+ */
+async abortOperation(path: string, kind: string) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("git_commit", { path, message }) };
+    return { status: "ok", data: await TAURI_INVOKE("abort_operation", { path, kind }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async gitAddAndCommit(path: string, files: string[], message: string) : Promise<Result<string, string>> {
+async gitCommit(path: string, message: string, allowIdentityMismatch: boolean | null) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("git_add_and_commit", { path, files, message }) };
+    return { status: "ok", data: await TAURI_INVOKE("git_commit", { path, message, allowIdentityMismatch }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async isGitRepo(path: string) : Promise<Result<boolean, string>> {
+async gitAddAndCommit(path: string, files: string[], message: string, allowIdentityMismatch: boolean | null) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("is_git_repo", { path }) };
+    return { status: "ok", data: await TAURI_INVOKE("git_add_and_commit", { path, files, message, allowIdentityMismatch }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async gitInit(path: string) : Promise<Result<string, string>> {
+/**
+ * 查看仓库当前的提交身份，并按身份模板检查是否正确
+ */
+async getGitIdentity(path: string) : Promise<Result<GitIdentityCheck, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("git_init", { path }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_git_identity", { path }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getProjects() : Promise<Result<Project[], string>> {
+/**
+ * 设置仓库级提交身份（写入 .git/config）；传空字符串时删除该项，改为继承全局配置
+ */
+async setGitIdentity(path: string, userName: string | null, email: string | null) : Promise<Result<GitIdentity, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_projects") };
+    return { status: "ok", data: await TAURI_INVOKE("set_git_identity", { path, userName, email }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async createProject(input: CreateProjectInput) : Promise<Result<Project, string>> {
+/**
+ * 把设置中的身份模板应用到仓库
+ */
+async applyGitIdentityProfile(path: string, profileId: string) : Promise<Result<GitIdentity, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("create_project", { input }) };
+    return { status: "ok", data: await TAURI_INVOKE("apply_git_identity_profile", { path, profileId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateProject(input: UpdateProjectInput) : Promise<Result<Project, string>> {
+/**
+ * 检测本机可用的签名密钥：GPG 私钥 + ~/.ssh 下带公钥的 SSH 密钥
+ */
+async listSigningKeys() : Promise<Result<SigningKey[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_project", { input }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_signing_keys") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deleteProject(id: string) : Promise<Result<null, string>> {
+/**
+ * 查看签名配置；path 为空时查看全局配置
+ */
+async getSigningConfig(path: string | null) : Promise<Result<SigningConfig, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_project", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_signing_config", { path }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deleteProjectDirectory(id: string) : Promise<Result<null, string>> {
+/**
+ * 写入签名配置：path 不为空时写入仓库配置，否则写入全局配置
+ */
+async setSigningConfig(path: string | null, format: string, signingKey: string, signCommits: boolean, signTags: boolean | null) : Promise<Result<SigningConfig, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_project_directory", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_signing_config", { path, format, signingKey, signCommits, signTags }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async toggleFavorite(id: string) : Promise<Result<Project, string>> {
+/**
+ * 在临时仓库中用当前生效的签名配置做一次测试提交，验证签名是否可用
+ */
+async testCommitSigning(path: string | null) : Promise<Result<SigningTestResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("toggle_favorite", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("test_commit_signing", { path }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateLastOpened(id: string) : Promise<Result<Project, string>> {
+async isGitRepo(path: string) : Promise<Result<boolean, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_last_opened", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("is_git_repo", { path }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async batchUpdateProjects(updates: UpdateProjectInput[]) : Promise<Result<Project[], string>> {
+async gitInit(path: string) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("batch_update_projects", { updates }) };
+    return { status: "ok", data: await TAURI_INVOKE("git_init", { path }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async batchDeleteProjects(ids: string[]) : Promise<Result<null, string>> {
+/**
+ * 列出仓库的 hooks（所有已知 hook，标注是否安装/启用）
+ */
+async listGitHooks(path: string) : Promise<Result<GitHookInfo[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("batch_delete_projects", { ids }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_git_hooks", { path }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async importProjects(newProjects: CreateProjectInput[]) : Promise<Result<Project[], string>> {
+async listGitHookTemplates() : Promise<Result<GitHookTemplate[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("import_projects", { newProjects }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_git_hook_templates") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 兼容旧 API：从持久层重新读取项目列表
+ * 新增或更新模板（id 为空时新建）
  */
-async reloadProjects() : Promise<Result<Project[], string>> {
+async saveGitHookTemplate(template: GitHookTemplate) : Promise<Result<GitHookTemplate, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("reload_projects") };
+    return { status: "ok", data: await TAURI_INVOKE("save_git_hook_template", { template }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setProjectEditor(id: string, editorId: string | null) : Promise<Result<Project, string>> {
+async deleteGitHookTemplate(id: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_project_editor", { id, editorId }) };
+    return { status: "ok", data: await TAURI_INVOKE("delete_git_hook_template", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setProjectClaudeEnv(id: string, claudeEnvName: string | null) : Promise<Result<Project, string>> {
+/**
+ * 从模板安装 hook。已有非本应用管理的脚本时需 overwrite=true，原脚本备份为 `<hook>.backup`
+ * （已有同名备份时追加序号，不覆盖旧备份）
+ */
+async installGitHook(path: string, templateId: string, overwrite: boolean | null) : Promise<Result<GitHookInfo, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_project_claude_env", { id, claudeEnvName }) };
+    return { status: "ok", data: await TAURI_INVOKE("install_git_hook", { path, templateId, overwrite }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getDashboardStats() : Promise<Result<CachedDashboardData, string>> {
+/**
+ * 删除本应用安装的 hook 脚本（启用与停用状态的都会删除）；不带模板标记的自定义脚本不删除
+ */
+async uninstallGitHook(path: string, hook: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_dashboard_stats") };
+    return { status: "ok", data: await TAURI_INVOKE("uninstall_git_hook", { path, hook }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 完整刷新所有项目统计
+ * 启用/停用 hook（通过 `.disabled` 后缀切换）
  */
-async refreshDashboardStats(projects: ProjectInfo[]) : Promise<Result<CachedDashboardData, string>> {
+async setGitHookEnabled(path: string, hook: string, enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("refresh_dashboard_stats", { projects }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_git_hook_enabled", { path, hook, enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 只刷新脏项目的统计数据（增量更新）
+ * 比较两个路径（同为文件或同为目录）
  */
-async refreshDirtyStats(projects: ProjectInfo[]) : Promise<Result<CachedDashboardData, string>> {
+async diffPaths(pathA: string, pathB: string) : Promise<Result<DiffResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("refresh_dirty_stats", { projects }) };
+    return { status: "ok", data: await TAURI_INVOKE("diff_paths", { pathA, pathB }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 启动时调用。如果 sqlite 中已有缓存（24 小时内）就直接用；否则标记所有项目为脏
+ * 比较仓库的两个版本（分支、标签、提交均可）
  */
-async initStatsCache(projects: ProjectInfo[]) : Promise<Result<CachedDashboardData, string>> {
+async diffRevisions(path: string, revA: string, revB: string) : Promise<Result<DiffResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("init_stats_cache", { projects }) };
+    return { status: "ok", data: await TAURI_INVOKE("diff_revisions", { path, revA, revB }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async markProjectDirty(projectPath: string) : Promise<Result<null, string>> {
+async getLfsInfo(path: string) : Promise<Result<LfsInfo, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("mark_project_dirty", { projectPath }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_lfs_info", { path }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async markAllProjectsDirty(projects: ProjectInfo[]) : Promise<Result<null, string>> {
+/**
+ * 下载并检出 LFS 对象；include 为逗号分隔的路径模式，只拉取匹配的文件
+ */
+async gitLfsPull(path: string, include: string | null) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("mark_all_projects_dirty", { projects }) };
+    return { status: "ok", data: await TAURI_INVOKE("git_lfs_pull", { path, include }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async hasDirtyStats() : Promise<Result<boolean, string>> {
+/**
+ * 只下载 LFS 对象到本地缓存，不改动工作区；all 时下载所有引用的对象
+ */
+async gitLfsFetch(path: string, remote: string | null, all: boolean) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("has_dirty_stats") };
+    return { status: "ok", data: await TAURI_INVOKE("git_lfs_fetch", { path, remote, all }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 清理已删除项目的缓存
+ * 清理本地不再需要的旧 LFS 对象；dry_run 时只报告将删除的内容
  */
-async cleanupStatsCache(currentProjectPaths: string[]) : Promise<Result<null, string>> {
+async gitLfsPrune(path: string, dryRun: boolean) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("cleanup_stats_cache", { currentProjectPaths }) };
+    return { status: "ok", data: await TAURI_INVOKE("git_lfs_prune", { path, dryRun }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async openInExplorer(path: string) : Promise<Result<null, string>> {
+/**
+ * 代码归属图；depth 为汇总的目录层级（默认 1，即顶层目录）
+ */
+async getCodeOwners(path: string, depth: number | null) : Promise<Result<CodeOwnersReport, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("open_in_explorer", { path }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_code_owners", { path, depth }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async openInEditor(path: string, editorPath: string | null) : Promise<Result<null, string>> {
+async getProjects() : Promise<Result<Project[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("open_in_editor", { path, editorPath }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_projects") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async openInTerminal(path: string, terminalType: string | null, customPath: string | null, terminalPath: string | null) : Promise<Result<null, string>> {
+async createProject(input: CreateProjectInput) : Promise<Result<Project, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("open_in_terminal", { path, terminalType, customPath, terminalPath }) };
+    return { status: "ok", data: await TAURI_INVOKE("create_project", { input }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async openUrl(url: string) : Promise<Result<null, string>> {
+async updateProject(input: UpdateProjectInput) : Promise<Result<Project, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("open_url", { url }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_project", { input }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async readReadme(path: string) : Promise<Result<string, string>> {
+async deleteProject(id: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("read_readme", { path }) };
+    return { status: "ok", data: await TAURI_INVOKE("delete_project", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async testTerminal(terminalType: string, customPath: string | null) : Promise<Result<TerminalTestResult, string>> {
+/**
+ * 删除项目目录（物理删除）。需二次确认：不带 confirm_token 时只返回确认请求，
+ * 带有效令牌时执行删除并返回 None
+ */
+async deleteProjectDirectory(id: string, confirmToken: string | null) : Promise<Result<ConfirmRequest | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("test_terminal", { terminalType, customPath }) };
+    return { status: "ok", data: await TAURI_INVOKE("delete_project_directory", { id, confirmToken }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async checkGitVersion() : Promise<Result<string, string>> {
+async toggleFavorite(id: string) : Promise<Result<Project, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("check_git_version") };
+    return { status: "ok", data: await TAURI_INVOKE("toggle_favorite", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async checkNodeVersion() : Promise<Result<string, string>> {
+async updateLastOpened(id: string) : Promise<Result<Project, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("check_node_version") };
+    return { status: "ok", data: await TAURI_INVOKE("update_last_opened", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getAppPaths() : Promise<Result<AppPaths, string>> {
+async batchUpdateProjects(updates: UpdateProjectInput[]) : Promise<Result<Project[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_app_paths") };
+    return { status: "ok", data: await TAURI_INVOKE("batch_update_projects", { updates }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async clearLogs() : Promise<Result<string, string>> {
+async batchDeleteProjects(ids: string[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("clear_logs") };
+    return { status: "ok", data: await TAURI_INVOKE("batch_delete_projects", { ids }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async importProjects(newProjects: CreateProjectInput[]) : Promise<Result<Project[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_projects", { newProjects }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 获取鼠标光标的屏幕坐标（同步命令，在主线程执行）
+ * 兼容旧 API：从持久层重新读取项目列表
  */
-async getCursorPosition() : Promise<Result<CursorPosition, string>> {
+async reloadProjects() : Promise<Result<Project[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_cursor_position") };
+    return { status: "ok", data: await TAURI_INVOKE("reload_projects") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getArchStatus() : Promise<Result<ArchStatus, string>> {
+async setProjectEditor(id: string, editorId: string | null) : Promise<Result<Project, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_arch_status") };
+    return { status: "ok", data: await TAURI_INVOKE("set_project_editor", { id, editorId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * 扫描端口
- */
-async scanPorts(config: ScanConfig) : Promise<Result<ScanResult[], string>> {
+async setProjectClaudeEnv(id: string, claudeEnvName: string | null) : Promise<Result<Project, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("scan_ports", { config }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_project_claude_env", { id, claudeEnvName }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 停止扫描
+ * 设置项目图标：把选择的图片复制到数据目录缓存，原文件移动或删除后图标仍然可用
  */
-async stopScan() : Promise<Result<null, string>> {
+async setProjectIcon(id: string, sourcePath: string) : Promise<Result<Project, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("stop_scan") };
+    return { status: "ok", data: await TAURI_INVOKE("set_project_icon", { id, sourcePath }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 获取常用端口列表
+ * 清除项目图标并删除缓存文件
  */
-async getCommonPorts() : Promise<Result<number[], string>> {
+async clearProjectIcon(id: string) : Promise<Result<Project, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_common_ports") };
+    return { status: "ok", data: await TAURI_INVOKE("clear_project_icon", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 扫描单个端口（用于快速检测）
+ * 设置强调色（#RRGGBB / #RGB）；传 None 或空字符串时清除
  */
-async checkPort(target: string, port: number, timeoutMs: number | null) : Promise<Result<ScanResult, string>> {
+async setProjectColor(id: string, color: string | null) : Promise<Result<Project, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("check_port", { target, port, timeoutMs }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_project_color", { id, color }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 扫描本地常用开发端口
+ * 设置项目简介；传 None 或空字符串时清除
  */
-async scanLocalDevPorts() : Promise<Result<ScanResult[], string>> {
+async setProjectDescription(id: string, description: string | null) : Promise<Result<Project, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("scan_local_dev_ports") };
+    return { status: "ok", data: await TAURI_INVOKE("set_project_description", { id, description }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 开始下载
+ * 设置项目专用的 git 可执行文件；传 None 或空字符串时改用全局设置
  */
-async startDownload(config: DownloadConfig) : Promise<Result<string, string>> {
+async setProjectGitExecutable(id: string, executable: string | null) : Promise<Result<Project, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("start_download", { config }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_project_git_executable", { id, executable }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 暂停下载
+ * 设置统计提交使用的分支："*" 为全部本地分支，None 或空字符串为当前检出分支。
+ * 修改后标记该项目的统计待刷新
  */
-async pauseDownload(taskId: string) : Promise<Result<null, string>> {
+async setProjectStatsBranch(id: string, branch: string | null) : Promise<Result<Project, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("pause_download", { taskId }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_project_stats_branch", { id, branch }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * 恢复下载
- */
-async resumeDownload(taskId: string) : Promise<Result<null, string>> {
+async listPathRoots() : Promise<Result<PathRoot[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("resume_download", { taskId }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_path_roots") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 取消下载
+ * 添加根目录，已有项目中落在其下的会自动绑定（嵌套根目录取最深的一个）
  */
-async cancelDownload(taskId: string) : Promise<Result<null, string>> {
+async addPathRoot(name: string, path: string) : Promise<Result<PathRoot, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("cancel_download", { taskId }) };
+    return { status: "ok", data: await TAURI_INVOKE("add_path_root", { name, path }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * 获取所有下载任务
- */
-async getDownloadTasks() : Promise<Result<DownloadTask[], string>> {
+async renamePathRoot(id: string, name: string) : Promise<Result<PathRoot, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_download_tasks") };
+    return { status: "ok", data: await TAURI_INVOKE("rename_path_root", { id, name }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 获取单个下载任务
+ * 删除根目录；项目保留当前绝对路径，改绑到其它包含它的根目录（如有）
  */
-async getDownloadTask(taskId: string) : Promise<Result<DownloadTask | null, string>> {
+async removePathRoot(id: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_download_task", { taskId }) };
+    return { status: "ok", data: await TAURI_INVOKE("remove_path_root", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 清除已完成的下载任务
+ * 根目录移动后重算其下项目的路径。dry_run 为 true 时只返回预览
  */
-async clearCompletedDownloads() : Promise<Result<number, string>> {
+async remapPathRoot(id: string, newPath: string, dryRun: boolean | null) : Promise<Result<RemapReport, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("clear_completed_downloads") };
+    return { status: "ok", data: await TAURI_INVOKE("remap_path_root", { id, newPath, dryRun }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 打开下载文件夹
+ * 在扫描目录中为路径已不存在的项目查找新位置，只返回建议，不修改数据
  */
-async openDownloadFolder(taskId: string) : Promise<Result<null, string>> {
+async relocateMissingProjects(roots: string[] | null) : Promise<Result<ProjectRelocation[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("open_download_folder", { taskId }) };
+    return { status: "ok", data: await TAURI_INVOKE("relocate_missing_projects", { roots }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 删除下载任务（可选删除文件）
+ * 写入确认后的新路径，并重新匹配路径根目录
  */
-async removeDownloadTask(taskId: string, deleteFile: boolean | null) : Promise<Result<null, string>> {
+async applyProjectRelocations(relocations: RelocationInput[]) : Promise<Result<Project[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("remove_download_task", { taskId, deleteFile }) };
+    return { status: "ok", data: await TAURI_INVOKE("apply_project_relocations", { relocations }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 获取进程列表
+ * 开始批量操作；不存在的项目记为 skipped。立即返回，进度通过事件推送
  */
-async getProcesses(filter: ProcessFilter | null) : Promise<Result<ProcessInfo[], string>> {
+async startBulkOperation(action: string, projectIds: string[], label: string | null) : Promise<Result<BulkOperation, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_processes", { filter }) };
+    return { status: "ok", data: await TAURI_INVOKE("start_bulk_operation", { action, projectIds, label }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 查询端口占用
+ * 取消批量操作：当前项完成后停止，剩余项保持 pending，可继续执行
  */
-async getPortProcesses(port: number) : Promise<Result<ProcessInfo[], string>> {
+async cancelBulkOperation(id: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_port_processes", { port }) };
+    return { status: "ok", data: await TAURI_INVOKE("cancel_bulk_operation", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 终止进程
+ * 继续执行已取消 / 中断的批量操作；retry_failed 为 true 时失败项也重新执行
  */
-async killProcess(pid: number, force: boolean | null) : Promise<Result<null, string>> {
+async resumeBulkOperation(id: string, retryFailed: boolean | null) : Promise<Result<BulkOperation, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("kill_process", { pid, force }) };
+    return { status: "ok", data: await TAURI_INVOKE("resume_bulk_operation", { id, retryFailed }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 获取系统资源使用情况
+ * 批量操作记录（最新在前）
  */
-async getSystemStats() : Promise<Result<SystemStats, string>> {
+async listBulkOperations() : Promise<Result<BulkOperation[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_system_stats") };
+    return { status: "ok", data: await TAURI_INVOKE("list_bulk_operations") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * 获取本地端口占用情况
- */
-async getLocalPortOccupation() : Promise<Result<PortOccupation[], string>> {
+async getBulkOperation(id: string) : Promise<Result<BulkOperation, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_local_port_occupation") };
+    return { status: "ok", data: await TAURI_INVOKE("get_bulk_operation", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 添加转发规则
+ * 删除已结束的批量操作记录
  */
-async addForwardRule(input: ForwardRuleInput) : Promise<Result<ForwardRule, string>> {
+async deleteBulkOperation(id: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("add_forward_rule", { input }) };
+    return { status: "ok", data: await TAURI_INVOKE("delete_bulk_operation", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * 移除转发规则
- */
-async removeForwardRule(ruleId: string) : Promise<Result<null, string>> {
+async listProjectTasks(projectId: string) : Promise<Result<ProjectTask[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("remove_forward_rule", { ruleId }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_project_tasks", { projectId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * 启动转发
- */
-async startForwarding(ruleId: string) : Promise<Result<null, string>> {
+async saveProjectTask(input: ProjectTaskInput) : Promise<Result<ProjectTask, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("start_forwarding", { ruleId }) };
+    return { status: "ok", data: await TAURI_INVOKE("save_project_task", { input }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * 停止转发
- */
-async stopForwarding(ruleId: string) : Promise<Result<null, string>> {
+async deleteProjectTask(id: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("stop_forwarding", { ruleId }) };
+    return { status: "ok", data: await TAURI_INVOKE("delete_project_task", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 获取所有转发规则
+ * 运行项目任务，返回运行实例（同一任务可同时运行多个实例）
  */
-async getForwardRules() : Promise<Result<ForwardRule[], string>> {
+async runProjectTask(taskId: string) : Promise<Result<ProjectTaskRun, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_forward_rules") };
+    return { status: "ok", data: await TAURI_INVOKE("run_project_task", { taskId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * 获取单个转发规则
- */
-async getForwardRule(ruleId: string) : Promise<Result<ForwardRule | null, string>> {
+async stopProjectTask(runId: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_forward_rule", { ruleId }) };
+    return { status: "ok", data: await TAURI_INVOKE("stop_project_task", { runId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * 获取转发统计
- */
-async getForwardStats(ruleId: string) : Promise<Result<ForwardStats, string>> {
+async listRunningProjectTasks(projectId: string | null) : Promise<Result<ProjectTaskRun[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_forward_stats", { ruleId }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_running_project_tasks", { projectId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 更新转发规则
+ * 获取运行实例最近的输出
  */
-async updateForwardRule(ruleId: string, input: ForwardRuleInput) : Promise<Result<ForwardRule, string>> {
+async getProjectTaskOutput(runId: string) : Promise<Result<ProjectTaskOutput[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_forward_rule", { ruleId, input }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_project_task_output", { runId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async addSshTunnel(input: SshTunnelInput) : Promise<Result<SshTunnel, string>> {
+async listProjectLinks(projectId: string) : Promise<Result<ProjectLink[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("add_ssh_tunnel", { input }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_project_links", { projectId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateSshTunnel(tunnelId: string, input: SshTunnelInput) : Promise<Result<SshTunnel, string>> {
+async saveProjectLink(input: ProjectLinkInput) : Promise<Result<ProjectLink, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_ssh_tunnel", { tunnelId, input }) };
+    return { status: "ok", data: await TAURI_INVOKE("save_project_link", { input }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async removeSshTunnel(tunnelId: string) : Promise<Result<null, string>> {
+async deleteProjectLink(projectId: string, linkId: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("remove_ssh_tunnel", { tunnelId }) };
+    return { status: "ok", data: await TAURI_INVOKE("delete_project_link", { projectId, linkId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async startSshTunnel(tunnelId: string) : Promise<Result<null, string>> {
+/**
+ * 在默认浏览器中打开项目链接
+ */
+async openProjectLink(projectId: string, linkId: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("start_ssh_tunnel", { tunnelId }) };
+    return { status: "ok", data: await TAURI_INVOKE("open_project_link", { projectId, linkId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async stopSshTunnel(tunnelId: string) : Promise<Result<null, string>> {
+async listDeployTargets(projectId: string) : Promise<Result<DeployTarget[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("stop_ssh_tunnel", { tunnelId }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_deploy_targets", { projectId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getSshTunnels() : Promise<Result<SshTunnel[], string>> {
+async saveDeployTarget(input: DeployTargetInput) : Promise<Result<DeployTarget, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_ssh_tunnels") };
+    return { status: "ok", data: await TAURI_INVOKE("save_deploy_target", { input }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getSshTunnel(tunnelId: string) : Promise<Result<SshTunnel | null, string>> {
+async deleteDeployTarget(projectId: string, targetId: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_ssh_tunnel", { tunnelId }) };
+    return { status: "ok", data: await TAURI_INVOKE("delete_deploy_target", { projectId, targetId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getSshTunnelStats(tunnelId: string) : Promise<Result<SshTunnelStats, string>> {
+/**
+ * 开始部署：校验并登记后在后台上传，立即返回 running 状态的部署记录
+ */
+async runDeploy(targetId: string) : Promise<Result<DeployRun, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_ssh_tunnel_stats", { tunnelId }) };
+    return { status: "ok", data: await TAURI_INVOKE("run_deploy", { targetId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async listSshConfigHosts() : Promise<Result<string[], string>> {
+/**
+ * 项目的部署历史（最新在前）
+ */
+async listDeployHistory(projectId: string, limit: number | null) : Promise<Result<DeployRun[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("list_ssh_config_hosts") };
+    return { status: "ok", data: await TAURI_INVOKE("list_deploy_history", { projectId, limit }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async testSshTunnel(tunnelId: string) : Promise<Result<TestPortResult, string>> {
+/**
+ * 扫描项目的依赖许可证与疑似密钥；路径属于已添加的项目时保存报告
+ */
+async scanProjectCompliance(path: string) : Promise<Result<ComplianceReport, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("test_ssh_tunnel", { tunnelId }) };
+    return { status: "ok", data: await TAURI_INVOKE("scan_project_compliance", { path }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async testLocalPort(port: number) : Promise<Result<TestPortResult, string>> {
+/**
+ * 获取项目最近一次的合规扫描报告
+ */
+async getProjectCompliance(path: string) : Promise<Result<ComplianceReport | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("test_local_port", { port }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_project_compliance", { path }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 创建服务
+ * 检测项目中的文档生成器
  */
-async createServer(input: ServerConfigInput) : Promise<Result<ServerConfig, string>> {
+async detectDocsGenerators(projectPath: string) : Promise<Result<DocsGenerator[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("create_server", { input }) };
+    return { status: "ok", data: await TAURI_INVOKE("detect_docs_generators", { projectPath }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 启动服务
+ * 启动文档预览并返回访问地址；kind 为空时使用检测到的第一个生成器，
+ * mode 为 "dev" / "static"，为空时自动选择。项目已有预览在运行时直接返回。
  */
-async startServer(serverId: string) : Promise<Result<string, string>> {
+async startDocsPreview(projectPath: string, kind: string | null, mode: string | null) : Promise<Result<DocsPreview, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("start_server", { serverId }) };
+    return { status: "ok", data: await TAURI_INVOKE("start_docs_preview", { projectPath, kind, mode }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 停止服务
+ * 停止项目的文档预览；复用的、原本就在运行的静态服务保持运行
  */
-async stopServer(serverId: string) : Promise<Result<null, string>> {
+async stopDocsPreview(projectPath: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("stop_server", { serverId }) };
+    return { status: "ok", data: await TAURI_INVOKE("stop_docs_preview", { projectPath }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 移除服务
+ * 获取运行中的文档预览
  */
-async removeServer(serverId: string) : Promise<Result<null, string>> {
+async getDocsPreviews() : Promise<Result<DocsPreview[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("remove_server", { serverId }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_docs_previews") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 获取所有服务
+ * 打开终端会话；指定 project_id 时在项目目录下打开，否则使用 cwd（默认主目录）
  */
-async getServers() : Promise<Result<ServerConfig[], string>> {
+async createTerminal(projectId: string | null, cwd: string | null, shell: string | null, cols: number, rows: number) : Promise<Result<TerminalSession, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_servers") };
+    return { status: "ok", data: await TAURI_INVOKE("create_terminal", { projectId, cwd, shell, cols, rows }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 获取单个服务
+ * 向终端写入输入（按键、粘贴内容）
  */
-async getServer(serverId: string) : Promise<Result<ServerConfig | null, string>> {
+async writeTerminal(sessionId: string, data: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_server", { serverId }) };
+    return { status: "ok", data: await TAURI_INVOKE("write_terminal", { sessionId, data }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 更新服务配置
+ * 调整终端尺寸（前端容器大小变化时调用）
  */
-async updateServer(serverId: string, input: ServerConfigInput) : Promise<Result<ServerConfig, string>> {
+async resizeTerminal(sessionId: string, cols: number, rows: number) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_server", { serverId, input }) };
+    return { status: "ok", data: await TAURI_INVOKE("resize_terminal", { sessionId, cols, rows }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async generateNginxConfig(serverId: string) : Promise<Result<string, string>> {
+/**
+ * 关闭终端：结束 shell 进程，退出事件由等待线程推送
+ */
+async closeTerminal(sessionId: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("generate_nginx_config", { serverId }) };
+    return { status: "ok", data: await TAURI_INVOKE("close_terminal", { sessionId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async dockerCheckAvailable() : Promise<Result<DockerStatus, string>> {
+/**
+ * 打开中的终端；指定 project_id 时只返回该项目的终端
+ */
+async listTerminals(projectId: string | null) : Promise<Result<TerminalSession[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("docker_check_available") };
+    return { status: "ok", data: await TAURI_INVOKE("list_terminals", { projectId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async dockerFindDockerfiles(projectPath: string) : Promise<Result<string[], string>> {
+/**
+ * 终端的回滚输出（前端重新挂载时补回）
+ */
+async getTerminalScrollback(sessionId: string) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("docker_find_dockerfiles", { projectPath }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_terminal_scrollback", { sessionId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async dockerReadDockerfile(projectPath: string, dockerfilePath: string) : Promise<Result<string, string>> {
+/**
+ * 列出 monorepo 的子项目（不是 workspace 时返回空列表）
+ */
+async listWorkspaceMembers(path: string) : Promise<Result<WorkspaceMember[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("docker_read_dockerfile", { projectPath, dockerfilePath }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_workspace_members", { path }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async dockerWriteDockerfile(projectPath: string, dockerfilePath: string, content: string) : Promise<Result<null, string>> {
+async getDashboardStats() : Promise<Result<CachedDashboardData, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("docker_write_dockerfile", { projectPath, dockerfilePath, content }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_dashboard_stats") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async dockerGenerateDockerfileTemplate(projectPath: string, template: string | null) : Promise<Result<string, string>> {
+/**
+ * 完整刷新所有项目统计
+ */
+async refreshDashboardStats(projects: ProjectInfo[]) : Promise<Result<CachedDashboardData, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("docker_generate_dockerfile_template", { projectPath, template }) };
+    return { status: "ok", data: await TAURI_INVOKE("refresh_dashboard_stats", { projects }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async dockerGenerateDockerfileAi(input: DockerAiGenerateInput) : Promise<Result<DockerAiGenerateOutput, string>> {
+/**
+ * 只刷新脏项目的统计数据（增量更新）
+ */
+async refreshDirtyStats(projects: ProjectInfo[]) : Promise<Result<CachedDashboardData, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("docker_generate_dockerfile_ai", { input }) };
+    return { status: "ok", data: await TAURI_INVOKE("refresh_dirty_stats", { projects }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async dockerBuildImage(input: DockerBuildInput) : Promise<Result<DockerCommandResult, string>> {
+/**
+ * 启动时调用。如果 sqlite 中已有缓存（24 小时内）就直接用；否则标记所有项目为脏
+ */
+async initStatsCache(projects: ProjectInfo[]) : Promise<Result<CachedDashboardData, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("docker_build_image", { input }) };
+    return { status: "ok", data: await TAURI_INVOKE("init_stats_cache", { projects }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async dockerListImages() : Promise<Result<DockerImageInfo[], string>> {
+async markProjectDirty(projectPath: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("docker_list_images") };
+    return { status: "ok", data: await TAURI_INVOKE("mark_project_dirty", { projectPath }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async dockerRemoveImage(image: string, force: boolean | null) : Promise<Result<DockerCommandResult, string>> {
+async markAllProjectsDirty(projects: ProjectInfo[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("docker_remove_image", { image, force }) };
+    return { status: "ok", data: await TAURI_INVOKE("mark_all_projects_dirty", { projects }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async dockerRunImage(input: DockerRunInput) : Promise<Result<DockerCommandResult, string>> {
+async hasDirtyStats() : Promise<Result<boolean, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("docker_run_image", { input }) };
+    return { status: "ok", data: await TAURI_INVOKE("has_dirty_stats") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async dockerListContainers() : Promise<Result<DockerContainerInfo[], string>> {
+/**
+ * 索引给定项目并清理已移除项目的索引；rebuild=true 时全量重建
+ */
+async updateCommitIndex(projects: ProjectInfo[], rebuild: boolean | null) : Promise<Result<number, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("docker_list_containers") };
+    return { status: "ok", data: await TAURI_INVOKE("update_commit_index", { projects, rebuild }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async dockerInspectContainerYaml(container: string) : Promise<Result<string, string>> {
+async getCommitIndexStatus() : Promise<Result<CommitIndexStatus, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("docker_inspect_container_yaml", { container }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_commit_index_status") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async dockerStopContainer(container: string) : Promise<Result<DockerCommandResult, string>> {
+/**
+ * 跨项目搜索提交信息（按提交时间倒序）
+ */
+async searchAllCommits(query: string, filters: CommitSearchFilters | null) : Promise<Result<CommitSearchHit[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("docker_stop_container", { container }) };
+    return { status: "ok", data: await TAURI_INVOKE("search_all_commits", { query, filters }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async dockerStartContainer(container: string) : Promise<Result<DockerCommandResult, string>> {
+/**
+ * 清理已删除项目的缓存
+ */
+async cleanupStatsCache(currentProjectPaths: string[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("docker_start_container", { container }) };
+    return { status: "ok", data: await TAURI_INVOKE("cleanup_stats_cache", { currentProjectPaths }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async dockerRestartContainer(container: string) : Promise<Result<DockerCommandResult, string>> {
+/**
+ * 统计缓存占用，供存储设置页展示
+ */
+async getStatsCacheSize() : Promise<Result<StatsCacheSize, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("docker_restart_container", { container }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_stats_cache_size") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async dockerRemoveContainer(container: string, force: boolean | null) : Promise<Result<DockerCommandResult, string>> {
+/**
+ * 清理统计缓存并压缩数据库（VACUUM），同时删除残留的 stats_cache.json
+ */
+async compactStatsCache() : Promise<Result<StatsCompactReport, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("docker_remove_container", { container, force }) };
+    return { status: "ok", data: await TAURI_INVOKE("compact_stats_cache") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async dockerPushImage(image: string) : Promise<Result<DockerCommandResult, string>> {
+/**
+ * 对比多个项目在同一时间窗口内的提交数、贡献者与改动量
+ */
+async compareProjectsActivity(paths: string[], range: string) : Promise<Result<ActivityComparison, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("docker_push_image", { image }) };
+    return { status: "ok", data: await TAURI_INVOKE("compare_projects_activity", { paths, range }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 检查所有环境的 Claude Code 安装情况
+ * 导出提交热力图（所有项目聚合）；range 同 compare_projects_activity，默认最近一年。
+ * output_path 不为空时同时写入文件
  */
-async checkAllClaudeInstallations() : Promise<Result<ClaudeCodeInfo[], string>> {
+async exportHeatmap(range: string | null, format: string, outputPath: string | null) : Promise<Result<HeatmapExport, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("check_all_claude_installations") };
+    return { status: "ok", data: await TAURI_INVOKE("export_heatmap", { range, format, outputPath }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * 根据指定路径检查 Claude Code 安装
- */
-async checkClaudeByPath(claudePath: string) : Promise<Result<ClaudeCodeInfo, string>> {
+async getDashboardLayout() : Promise<Result<DashboardWidget[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("check_claude_by_path", { claudePath }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_dashboard_layout") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 读取配置文件内容
+ * 保存布局；同一小部件只能出现一次，顺序即显示顺序
  */
-async readClaudeConfigFile(envType: EnvType, envName: string, path: string) : Promise<Result<string, string>> {
+async saveDashboardLayout(widgets: DashboardWidget[]) : Promise<Result<DashboardWidget[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("read_claude_config_file", { envType, envName, path }) };
+    return { status: "ok", data: await TAURI_INVOKE("save_dashboard_layout", { widgets }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 写入配置文件内容
+ * 取回已启用小部件的数据；kinds 可临时指定只刷新其中几个
  */
-async writeClaudeConfigFile(envType: EnvType, envName: string, path: string, content: string) : Promise<Result<null, string>> {
+async getDashboardWidgetData(kinds: string[] | null) : Promise<Result<DashboardWidgetData, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("write_claude_config_file", { envType, envName, path, content }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_dashboard_widget_data", { kinds }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 打开配置目录
+ * 获取运行概览
  */
-async openClaudeConfigDir(envType: EnvType, envName: string, configDir: string) : Promise<Result<null, string>> {
+async getRuntimeOverview() : Promise<Result<RuntimeOverview, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("open_claude_config_dir", { envType, envName, configDir }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_runtime_overview") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 获取快捷配置选项列表
+ * 获取计费网络状态与因此暂停的下载
  */
-async getQuickConfigOptions() : Promise<Result<QuickConfigOption[], string>> {
+async getIdlePolicyStatus() : Promise<Result<IdlePolicyStatus, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_quick_config_options") };
+    return { status: "ok", data: await TAURI_INVOKE("get_idle_policy_status") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 应用快捷配置
+ * 获取配置档案列表
  */
-async applyQuickConfig(envType: EnvType, envName: string, configPath: string, options: string[]) : Promise<Result<null, string>> {
+async listAppProfiles() : Promise<Result<AppProfileList, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("apply_quick_config", { envType, envName, configPath, options }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_app_profiles") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 获取保存的配置档案列表
+ * 新建档案；copy_current 为 true 时复制当前档案的项目范围与设置，否则从空白开始（全部项目可见）
  */
-async getConfigProfiles(envType: EnvType, envName: string) : Promise<Result<ConfigProfile[], string>> {
+async createAppProfile(name: string, copyCurrent: boolean | null) : Promise<Result<AppProfile, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_config_profiles", { envType, envName }) };
+    return { status: "ok", data: await TAURI_INVOKE("create_app_profile", { name, copyCurrent }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 保存配置档案（如果名称已存在则更新，否则新建）
+ * 重命名档案
  */
-async saveConfigProfile(envType: EnvType, envName: string, name: string, description: string | null, settings: JsonValue) : Promise<Result<ConfigProfile, string>> {
+async renameAppProfile(id: string, name: string) : Promise<Result<AppProfile, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_config_profile", { envType, envName, name, description, settings }) };
+    return { status: "ok", data: await TAURI_INVOKE("rename_app_profile", { id, name }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 删除配置档案
+ * 设置档案可见的项目；None 为全部项目
  */
-async deleteConfigProfile(envType: EnvType, envName: string, profileId: string) : Promise<Result<null, string>> {
+async setAppProfileProjects(id: string, projectIds: string[] | null) : Promise<Result<AppProfile, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_config_profile", { envType, envName, profileId }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_app_profile_projects", { id, projectIds }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 应用配置档案
+ * 删除档案（不能删除当前档案）
  */
-async applyConfigProfile(envType: EnvType, envName: string, configPath: string, profileId: string) : Promise<Result<null, string>> {
+async deleteAppProfile(id: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("apply_config_profile", { envType, envName, configPath, profileId }) };
+    return { status: "ok", data: await TAURI_INVOKE("delete_app_profile", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 从当前配置创建档案
+ * 切换档案；id 为 None 时回到默认档案。广播 `app-profile-switched`，前端收到后重新加载数据
  */
-async createProfileFromCurrent(envType: EnvType, envName: string, configPath: string, profileName: string, description: string | null) : Promise<Result<ConfigProfile, string>> {
+async switchAppProfile(id: string | null) : Promise<Result<AppProfileList, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("create_profile_from_current", { envType, envName, configPath, profileName, description }) };
+    return { status: "ok", data: await TAURI_INVOKE("switch_app_profile", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 扫描指定配置目录的配置文件
+ * 导出档案到 file_path；id 为 None 时导出默认档案
  */
-async scanClaudeConfigDir(envType: EnvType, envName: string, configDir: string) : Promise<Result<ConfigFileInfo[], string>> {
+async exportAppProfile(id: string | null, filePath: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("scan_claude_config_dir", { envType, envName, configDir }) };
+    return { status: "ok", data: await TAURI_INVOKE("export_app_profile", { id, filePath }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 非 Windows 系统的 stub
+ * 从导出文件新建档案；name 为空时沿用文件中的名称（重名时追加序号）
  */
-async getWslConfigDir(distro: string) : Promise<Result<WslConfigDirResult, string>> {
+async importAppProfile(filePath: string, name: string | null) : Promise<Result<AppProfile, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_wsl_config_dir", { distro }) };
+    return { status: "ok", data: await TAURI_INVOKE("import_app_profile", { filePath, name }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * 获取保存的 Claude 快捷配置
- */
-async getSavedQuickConfigs() : Promise<Result<ClaudeQuickConfig[], string>> {
+async openInExplorer(path: string) : Promise<Result<LaunchWarning | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_saved_quick_configs") };
+    return { status: "ok", data: await TAURI_INVOKE("open_in_explorer", { path }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * 保存 Claude 快捷配置
- */
-async saveQuickConfigs(configs: ClaudeQuickConfig[]) : Promise<Result<null, string>> {
+async openInEditor(path: string, editorPath: string | null) : Promise<Result<LaunchWarning | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_quick_configs", { configs }) };
+    return { status: "ok", data: await TAURI_INVOKE("open_in_editor", { path, editorPath }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 获取缓存的 Claude 安装信息
+ * 打开终端并进入目录；传入 command 时进入目录后执行该命令（终端保持打开）。
+ * 终端启动后立即异常退出时返回警告
  */
-async getClaudeInstallationsCache() : Promise<Result<ClaudeCodeInfo[] | null, string>> {
+async openInTerminal(path: string, terminalType: string | null, customPath: string | null, terminalPath: string | null, command: string | null) : Promise<Result<LaunchWarning | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_claude_installations_cache") };
+    return { status: "ok", data: await TAURI_INVOKE("open_in_terminal", { path, terminalType, customPath, terminalPath, command }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * 保存 Claude 安装信息缓存
- */
-async saveClaudeInstallationsCache(installs: ClaudeCodeInfo[]) : Promise<Result<null, string>> {
+async openUrl(url: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_claude_installations_cache", { installs }) };
+    return { status: "ok", data: await TAURI_INVOKE("open_url", { url }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * 清除 Claude 安装信息缓存
- */
-async clearClaudeInstallationsCache() : Promise<Result<null, string>> {
+async readReadme(path: string) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("clear_claude_installations_cache") };
+    return { status: "ok", data: await TAURI_INVOKE("read_readme", { path }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * 在终端中启动 Claude Code
- */
-async launchClaudeInTerminal(workDir: string | null, terminalType: string | null, customPath: string | null, terminalPath: string | null, envType: string | null, envName: string | null) : Promise<Result<null, string>> {
+async testTerminal(terminalType: string, customPath: string | null) : Promise<Result<TerminalTestResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("launch_claude_in_terminal", { workDir, terminalType, customPath, terminalPath, envType, envName }) };
+    return { status: "ok", data: await TAURI_INVOKE("test_terminal", { terminalType, customPath }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 获取保存的 Claude 启动目录列表
+ * 检查编辑器 / 终端路径是否可用：文件存在且可执行，或是 PATH 中的命令，或是 macOS .app 应用包
  */
-async getClaudeLaunchDirs() : Promise<Result<string[], string>> {
+async validateToolPath(path: string) : Promise<Result<ToolPathCheck, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_claude_launch_dirs") };
+    return { status: "ok", data: await TAURI_INVOKE("validate_tool_path", { path }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * 保存 Claude 启动目录列表
- */
-async saveClaudeLaunchDirs(dirs: string[]) : Promise<Result<null, string>> {
+async checkGitVersion() : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_claude_launch_dirs", { dirs }) };
+    return { status: "ok", data: await TAURI_INVOKE("check_git_version") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 初始化并加载已保存的会话
+ * 校验设置中填写的 git 可执行文件，返回其版本号
  */
-async netcatInit() : Promise<Result<null, string>> {
+async checkGitExecutable(executable: string) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("netcat_init") };
+    return { status: "ok", data: await TAURI_INVOKE("check_git_executable", { executable }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * 创建新会话
- */
-async netcatCreateSession(input: NetcatSessionInput) : Promise<Result<NetcatSession, string>> {
+async checkNodeVersion() : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("netcat_create_session", { input }) };
+    return { status: "ok", data: await TAURI_INVOKE("check_node_version") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 启动会话
+ * 运行环境检查，返回逐项报告
  */
-async netcatStartSession(sessionId: string) : Promise<Result<null, string>> {
+async runEnvironmentDoctor() : Promise<Result<DoctorReport, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("netcat_start_session", { sessionId }) };
+    return { status: "ok", data: await TAURI_INVOKE("run_environment_doctor") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * 停止会话
- */
-async netcatStopSession(sessionId: string) : Promise<Result<null, string>> {
+async getAppPaths() : Promise<Result<AppPaths, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("netcat_stop_session", { sessionId }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_app_paths") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * 删除会话
- */
-async netcatRemoveSession(sessionId: string) : Promise<Result<null, string>> {
+async clearLogs() : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("netcat_remove_session", { sessionId }) };
+    return { status: "ok", data: await TAURI_INVOKE("clear_logs") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 发送消息
+ * 获取鼠标光标的屏幕坐标（同步命令，在主线程执行）
  */
-async netcatSendMessage(input: SendMessageInput) : Promise<Result<NetcatMessage, string>> {
+async getCursorPosition() : Promise<Result<CursorPosition, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("netcat_send_message", { input }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_cursor_position") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * 获取所有会话
- */
-async netcatGetSessions() : Promise<Result<NetcatSession[], string>> {
+async getArchStatus() : Promise<Result<ArchStatus, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("netcat_get_sessions") };
+    return { status: "ok", data: await TAURI_INVOKE("get_arch_status") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 获取单个会话
+ * 当前更新通道
  */
-async netcatGetSession(sessionId: string) : Promise<Result<NetcatSession, string>> {
+async getUpdateChannel() : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("netcat_get_session", { sessionId }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_update_channel") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 获取会话消息
+ * 切换更新通道；已检查或下载的更新作废
  */
-async netcatGetMessages(sessionId: string, limit: number | null, offset: number | null) : Promise<Result<NetcatMessage[], string>> {
+async setUpdateChannel(channel: string) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("netcat_get_messages", { sessionId, limit, offset }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_update_channel", { channel }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 获取连接的客户端
+ * 按当前通道检查更新；manual 为 false（启动时的自动检查）时标出已推迟的版本
  */
-async netcatGetClients(sessionId: string) : Promise<Result<ConnectedClient[], string>> {
+async checkForUpdate(manual: boolean | null) : Promise<Result<UpdateCheck, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("netcat_get_clients", { sessionId }) };
+    return { status: "ok", data: await TAURI_INVOKE("check_for_update", { manual }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 清空会话消息
+ * 下载已检查到的更新，进度通过 `update-download-progress` 推送
  */
-async netcatClearMessages(sessionId: string) : Promise<Result<null, string>> {
+async downloadUpdate() : Promise<Result<UpdateSchedule, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("netcat_clear_messages", { sessionId }) };
+    return { status: "ok", data: await TAURI_INVOKE("download_update") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 断开指定客户端（仅服务器模式）
+ * 立即安装已下载的更新并重启
  */
-async netcatDisconnectClient(sessionId: string, clientId: string) : Promise<Result<null, string>> {
+async installUpdate() : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("netcat_disconnect_client", { sessionId, clientId }) };
+    return { status: "ok", data: await TAURI_INVOKE("install_update") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 更新会话的自动发送配置
+ * 安排已下载更新的安装时机：mode 为 "manual" | "onExit" | "at"（需要 at，RFC3339）
  */
-async netcatUpdateAutoSend(sessionId: string, config: AutoSendConfig) : Promise<Result<null, string>> {
+async scheduleUpdateInstall(mode: string, at: string | null) : Promise<Result<UpdateSchedule, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("netcat_update_auto_send", { sessionId, config }) };
+    return { status: "ok", data: await TAURI_INVOKE("schedule_update_install", { mode, at }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * HTTP 获取数据（用于自动发送的 HTTP 模式）
+ * 当前待安装的更新
  */
-async netcatFetchHttp(config: HttpFetchConfig) : Promise<Result<string, string>> {
+async getUpdateSchedule() : Promise<Result<UpdateSchedule | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("netcat_fetch_http", { config }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_update_schedule") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 获取所有快捷键，首次自动写入默认数据
+ * 推迟某个版本 days 天；days 为 0 时取消推迟
  */
-async getShortcuts() : Promise<Result<ShortcutEntry[], string>> {
+async deferUpdate(version: string, days: number) : Promise<Result<UpdateDeferral | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_shortcuts") };
+    return { status: "ok", data: await TAURI_INVOKE("defer_update", { version, days }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 全量保存快捷键（导入用）
+ * 获取电源状态
  */
-async saveShortcuts(shortcuts: ShortcutEntry[]) : Promise<Result<null, string>> {
+async getPowerStatus() : Promise<Result<PowerStatus, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_shortcuts", { shortcuts }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_power_status") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 添加用户自定义快捷键
+ * 扫描端口
  */
-async addShortcut(input: ShortcutInput) : Promise<Result<ShortcutEntry, string>> {
+async scanPorts(config: ScanConfig) : Promise<Result<ScanResult[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("add_shortcut", { input }) };
+    return { status: "ok", data: await TAURI_INVOKE("scan_ports", { config }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 编辑快捷键（默认项首次编辑时保存 originalKeys）
+ * 停止扫描
  */
-async updateShortcut(id: string, input: ShortcutInput) : Promise<Result<ShortcutEntry, string>> {
+async stopScan() : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_shortcut", { id, input }) };
+    return { status: "ok", data: await TAURI_INVOKE("stop_scan") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 删除快捷键（仅允许删除用户添加的）
+ * 获取常用端口列表
  */
-async deleteShortcut(id: string) : Promise<Result<null, string>> {
+async getCommonPorts() : Promise<Result<number[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_shortcut", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_common_ports") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 恢复所有默认项 + 保留用户自定义项
+ * 扫描单个端口（用于快速检测）
  */
-async resetShortcuts() : Promise<Result<ShortcutEntry[], string>> {
+async checkPort(target: string, port: number, timeoutMs: number | null) : Promise<Result<ScanResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("reset_shortcuts") };
+    return { status: "ok", data: await TAURI_INVOKE("check_port", { target, port, timeoutMs }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 返回当前平台
+ * 扫描本地常用开发端口
  */
-async getCurrentPlatform() : Promise<Result<string, string>> {
+async scanLocalDevPorts() : Promise<Result<ScanResult[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_current_platform") };
+    return { status: "ok", data: await TAURI_INVOKE("scan_local_dev_ports") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getClipboardHistory() : Promise<Result<ClipboardEntry[], string>> {
+/**
+ * 检测 SYN 扫描是否可用（权限 / 平台），供前端决定是否提供该选项
+ */
+async getScanCapabilities() : Promise<Result<ScanCapabilities, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_clipboard_history") };
+    return { status: "ok", data: await TAURI_INVOKE("get_scan_capabilities") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async addClipboardEntry(content: string) : Promise<Result<ClipboardEntry, string>> {
+/**
+ * 添加端口监控
+ */
+async addPortWatch(input: PortWatchInput) : Promise<Result<PortWatch, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("add_clipboard_entry", { content }) };
+    return { status: "ok", data: await TAURI_INVOKE("add_port_watch", { input }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deleteClipboardEntry(id: string) : Promise<Result<null, string>> {
+/**
+ * 更新端口监控配置（保留历史）
+ */
+async updatePortWatch(id: string, input: PortWatchInput) : Promise<Result<PortWatch, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_clipboard_entry", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_port_watch", { id, input }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async togglePinClipboardEntry(id: string) : Promise<Result<ClipboardEntry, string>> {
+/**
+ * 删除端口监控
+ */
+async removePortWatch(id: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("toggle_pin_clipboard_entry", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("remove_port_watch", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * 清空非置顶条目；置顶永远保留
+ * 获取所有端口监控
  */
-async clearClipboardHistory() : Promise<Result<null, string>> {
+async getPortWatches() : Promise<Result<PortWatch[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("clear_clipboard_history") };
+    return { status: "ok", data: await TAURI_INVOKE("get_port_watches") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getClipboardSettings() : Promise<Result<ClipboardSettings, string>> {
+/**
+ * 立即探测一次
+ */
+async checkPortWatchNow(id: string) : Promise<Result<PortWatch, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_clipboard_settings") };
+    return { status: "ok", data: await TAURI_INVOKE("check_port_watch_now", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveClipboardSettings(settings: ClipboardSettings) : Promise<Result<null, string>> {
+/**
+ * 添加 HTTP 监控
+ */
+async addHttpMonitor(input: HttpMonitorInput) : Promise<Result<HttpMonitor, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_clipboard_settings", { settings }) };
+    return { status: "ok", data: await TAURI_INVOKE("add_http_monitor", { input }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async writeToClipboard(content: string) : Promise<Result<null, string>> {
+/**
+ * 更新 HTTP 监控配置
+ */
+async updateHttpMonitor(id: string, input: HttpMonitorInput) : Promise<Result<HttpMonitor, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("write_to_clipboard", { content }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_http_monitor", { id, input }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateClipboardNote(id: string, note: string) : Promise<Result<ClipboardEntry, string>> {
+/**
+ * 删除 HTTP 监控
+ */
+async removeHttpMonitor(id: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_clipboard_note", { id, note }) };
+    return { status: "ok", data: await TAURI_INVOKE("remove_http_monitor", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getChatHistoryDir() : Promise<Result<string, string>> {
+/**
+ * 获取所有 HTTP 监控
+ */
+async getHttpMonitors() : Promise<Result<HttpMonitor[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_chat_history_dir") };
+    return { status: "ok", data: await TAURI_INVOKE("get_http_monitors") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async migrateChatHistoryDir(newDir: string) : Promise<Result<string, string>> {
+/**
+ * 立即检查一次
+ */
+async checkHttpMonitorNow(id: string) : Promise<Result<HttpMonitor, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("migrate_chat_history_dir", { newDir }) };
+    return { status: "ok", data: await TAURI_INVOKE("check_http_monitor_now", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async listChatSessions() : Promise<Result<ChatSessionSummary[], string>> {
+/**
+ * 可用率与延迟分位数（基于保留的历史窗口）
+ */
+async getHttpMonitorStats(id: string) : Promise<Result<HttpMonitorStats, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("list_chat_sessions") };
+    return { status: "ok", data: await TAURI_INVOKE("get_http_monitor_stats", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getChatSession(sessionId: string) : Promise<Result<ChatSession, string>> {
+/**
+ * 开始下载
+ */
+async startDownload(config: DownloadConfig) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_chat_session", { sessionId }) };
+    return { status: "ok", data: await TAURI_INVOKE("start_download", { config }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async createChatSession(input: CreateChatSessionInput) : Promise<Result<ChatSession, string>> {
+/**
+ * 暂停下载
+ */
+async pauseDownload(taskId: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("create_chat_session", { input }) };
+    return { status: "ok", data: await TAURI_INVOKE("pause_download", { taskId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveChatSession(session: ChatSession) : Promise<Result<ChatSession, string>> {
+/**
+ * 恢复下载
+ */
+async resumeDownload(taskId: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_chat_session", { session }) };
+    return { status: "ok", data: await TAURI_INVOKE("resume_download", { taskId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async renameChatSession(sessionId: string, title: string) : Promise<Result<ChatSession, string>> {
+/**
+ * 取消下载
+ */
+async cancelDownload(taskId: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("rename_chat_session", { sessionId, title }) };
+    return { status: "ok", data: await TAURI_INVOKE("cancel_download", { taskId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deleteChatSession(sessionId: string) : Promise<Result<null, string>> {
+/**
+ * 获取所有下载任务
+ */
+async getDownloadTasks() : Promise<Result<DownloadTask[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_chat_session", { sessionId }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_download_tasks") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveCompaction(input: SaveCompactionInput) : Promise<Result<CompactionMeta, string>> {
+/**
+ * 获取单个下载任务
+ */
+async getDownloadTask(taskId: string) : Promise<Result<DownloadTask | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_compaction", { input }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_download_task", { taskId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async listCompactions(sessionId: string) : Promise<Result<CompactionIndex, string>> {
+/**
+ * 清除已完成的下载任务
+ */
+async clearCompletedDownloads() : Promise<Result<number, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("list_compactions", { sessionId }) };
+    return { status: "ok", data: await TAURI_INVOKE("clear_completed_downloads") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getCompaction(sessionId: string, version: string | null) : Promise<Result<CompactionContent | null, string>> {
+/**
+ * 打开下载文件夹
+ */
+async openDownloadFolder(taskId: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_compaction", { sessionId, version }) };
+    return { status: "ok", data: await TAURI_INVOKE("open_download_folder", { taskId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async chatStream(request: ChatStreamRequest) : Promise<Result<null, string>> {
+/**
+ * 删除下载任务（可选删除文件）
+ */
+async removeDownloadTask(taskId: string, deleteFile: boolean | null) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("chat_stream", { request }) };
+    return { status: "ok", data: await TAURI_INVOKE("remove_download_task", { taskId, deleteFile }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async chatComplete(request: ChatStreamRequest) : Promise<Result<string, string>> {
+/**
+ * 手动扫描已完成的下载；发现威胁时按设置的处理方式隔离 / 删除
+ */
+async scanDownload(taskId: string) : Promise<Result<DownloadScanResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("chat_complete", { request }) };
+    return { status: "ok", data: await TAURI_INVOKE("scan_download", { taskId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async chatCancel(requestId: string) : Promise<Result<null, string>> {
+/**
+ * 当前可用的扫描器名称，没有时返回 None
+ */
+async getVirusScanner() : Promise<Result<string | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("chat_cancel", { requestId }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_virus_scanner") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async listApiGroups() : Promise<Result<ApiGroup[], string>> {
+/**
+ * 获取下载历史（按完成时间倒序）
+ */
+async getDownloadHistory(limit: number | null) : Promise<Result<DownloadHistoryEntry[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("list_api_groups") };
+    return { status: "ok", data: await TAURI_INVOKE("get_download_history", { limit }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveApiGroup(group: ApiGroup) : Promise<Result<ApiGroup, string>> {
+/**
+ * 汇总下载历史；days 为空时统计全部记录
+ */
+async getDownloadHistoryStats(days: number | null) : Promise<Result<DownloadHistoryStats, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_api_group", { group }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_download_history_stats", { days }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deleteApiGroup(id: string) : Promise<Result<null, string>> {
+/**
+ * 清空下载历史
+ */
+async clearDownloadHistory() : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_api_group", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("clear_download_history") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async listApiEndpoints() : Promise<Result<ApiEndpoint[], string>> {
+async getDownloadHandoffStatus() : Promise<Result<DownloadHandoffStatus, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("list_api_endpoints") };
+    return { status: "ok", data: await TAURI_INVOKE("get_download_handoff_status") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveApiEndpoint(endpoint: ApiEndpoint) : Promise<Result<ApiEndpoint, string>> {
+/**
+ * 重新生成端点令牌（浏览器扩展需同步更新）
+ */
+async regenerateDownloadHandoffToken() : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_api_endpoint", { endpoint }) };
+    return { status: "ok", data: await TAURI_INVOKE("regenerate_download_handoff_token") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deleteApiEndpoint(id: string) : Promise<Result<null, string>> {
+/**
+ * 解析粘贴的 URL 列表：每行一个，可用 Tab 分隔指定文件名；空行和 # 开头的行忽略。
+ * 先整体校验，再把有效行逐个加入下载队列；无效行与加入队列失败的行逐条返回原因
+ */
+async importDownloadUrls(text: string, saveDir: string | null) : Promise<Result<DownloadImportResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_api_endpoint", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("import_download_urls", { text, saveDir }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async listApiChatSessions() : Promise<Result<ApiChatSessionSummary[], string>> {
+/**
+ * 指标端点状态
+ */
+async getMetricsStatus() : Promise<Result<MetricsStatus, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("list_api_chat_sessions") };
+    return { status: "ok", data: await TAURI_INVOKE("get_metrics_status") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getApiChatSession(sessionId: string) : Promise<Result<ApiChatSession, string>> {
+/**
+ * 计算文件的校验和；algorithms 为空时计算全部算法，传入 expected 时比对结果
+ */
+async computeFileHashes(path: string, algorithms: string[], expected: string | null, jobId: string | null) : Promise<Result<FileHashResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_api_chat_session", { sessionId }) };
+    return { status: "ok", data: await TAURI_INVOKE("compute_file_hashes", { path, algorithms, expected, jobId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async createApiChatSession(input: CreateApiChatSessionInput) : Promise<Result<ApiChatSession, string>> {
+/**
+ * 对 URL 做固定时长的并发压测，返回 RPS、延迟分位数与错误统计
+ */
+async benchmarkHttp(url: string, concurrency: number, durationSecs: number, method: string | null, body: string | null, headers: Partial<{ [key in string]: string }> | null, timeoutMs: number | null, jobId: string | null) : Promise<Result<HttpBenchReport, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("create_api_chat_session", { input }) };
+    return { status: "ok", data: await TAURI_INVOKE("benchmark_http", { url, concurrency, durationSecs, method, body, headers, timeoutMs, jobId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveApiChatSession(session: ApiChatSession) : Promise<Result<ApiChatSession, string>> {
+/**
+ * 连接 host:port 完成 TLS 握手，返回证书链与协商信息
+ */
+async inspectTlsCertificates(host: string, port: number, serverName: string | null, warnDays: number | null, timeoutMs: number | null) : Promise<Result<TlsInspection, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_api_chat_session", { session }) };
+    return { status: "ok", data: await TAURI_INVOKE("inspect_tls_certificates", { host, port, serverName, warnDays, timeoutMs }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async renameApiChatSession(sessionId: string, title: string) : Promise<Result<ApiChatSession, string>> {
+/**
+ * 解析本地证书文件：PEM（可含多张证书）或单张 DER
+ */
+async parseCertificateFile(path: string, warnDays: number | null) : Promise<Result<CertificateInfo[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("rename_api_chat_session", { sessionId, title }) };
+    return { status: "ok", data: await TAURI_INVOKE("parse_certificate_file", { path, warnDays }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deleteApiChatSession(sessionId: string) : Promise<Result<null, string>> {
-    try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_api_chat_session", { sessionId }) };
+/**
+ * 用 Rust regex 语法测试正则；replacement 支持 $1 / ${name} 引用
+ */
+async testRegex(pattern: string, text: string, flags: RegexFlags | null, replacement: string | null, maxMatches: number | null, timeoutMs: number | null) : Promise<Result<RegexTestResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("test_regex", { pattern, text, flags, replacement, maxMatches, timeoutMs }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async buildApiTools(endpointIds: string[]) : Promise<Result<ApiToolsBundle, string>> {
+/**
+ * 比较两段文本；mode 为 word 时额外给出行内的词级差异
+ */
+async diffText(a: string, b: string, mode: TextDiffMode | null, contextLines: number | null, ignoreWhitespace: boolean | null, labelA: string | null, labelB: string | null) : Promise<Result<TextDiffResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("build_api_tools", { endpointIds }) };
+    return { status: "ok", data: await TAURI_INVOKE("diff_text", { a, b, mode, contextLines, ignoreWhitespace, labelA, labelB }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async executeApiEndpoint(endpointId: string, argumentsJson: string) : Promise<Result<ApiExecutionResult, string>> {
+/**
+ * 把 unified diff 应用到文本上；reverse 为 true 时撤销补丁
+ */
+async applyTextPatch(original: string, patch: string, reverse: boolean | null) : Promise<Result<ApplyPatchResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("execute_api_endpoint", { endpointId, argumentsJson }) };
+    return { status: "ok", data: await TAURI_INVOKE("apply_text_patch", { original, patch, reverse }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async fetchApiDocumentUrl(url: string) : Promise<Result<string, string>> {
+/**
+ * 校验文档，返回错误位置
+ */
+async validateDocument(format: DocumentFormat, text: string | null, filePath: string | null) : Promise<Result<DocumentValidation, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fetch_api_document_url", { url }) };
+    return { status: "ok", data: await TAURI_INVOKE("validate_document", { format, text, filePath }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async listDataBackups() : Promise<Result<string[], string>> {
+/**
+ * JSON / YAML / TOML 互转；indent 只影响 JSON 输出（默认 2，0 为压缩）
+ */
+async convertDocument(from: DocumentFormat, to: DocumentFormat, text: string | null, filePath: string | null, indent: number | null, outputPath: string | null) : Promise<Result<DocumentOutput, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("list_data_backups") };
+    return { status: "ok", data: await TAURI_INVOKE("convert_document", { from, to, text, filePath, indent, outputPath }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async restoreFromBackup(timestamp: string) : Promise<Result<string, string>> {
+/**
+ * JSON 格式化（indent 为 0 时压缩），保留键顺序与数字原文
+ */
+async formatJson(text: string | null, filePath: string | null, indent: number | null, outputPath: string | null) : Promise<Result<DocumentOutput, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("restore_from_backup", { timestamp }) };
+    return { status: "ok", data: await TAURI_INVOKE("format_json", { text, filePath, indent, outputPath }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async mcpGatewayStatus() : Promise<Result<McpGatewayStatus, string>> {
+/**
+ * JSONPath 查询，支持 $ . [n] [-n] ['key'] * ..key ..*；YAML / TOML 文档同样可查
+ */
+async queryJsonPath(format: DocumentFormat, expression: string, text: string | null, filePath: string | null) : Promise<Result<JsonPathMatch[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("mcp_gateway_status") };
+    return { status: "ok", data: await TAURI_INVOKE("query_json_path", { format, expression, text, filePath }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async mcpGatewayInternalEndpoint() : Promise<Result<McpGatewayInternalEndpoint | null, string>> {
+/**
+ * 生成二维码；scale 为每个模块的像素数，margin 为静区模块数（标准建议 4）
+ */
+async generateQrCode(text: string, format: string, scale: number | null, margin: number | null, errorCorrection: string | null, foreground: string | null, background: string | null, outputPath: string | null) : Promise<Result<GeneratedAsset, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("mcp_gateway_internal_endpoint") };
+    return { status: "ok", data: await TAURI_INVOKE("generate_qr_code", { text, format, scale, margin, errorCorrection, foreground, background, outputPath }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async chatListTools() : Promise<Result<ToolSchema[], string>> {
+/**
+ * 由一张图片生成 favicon.ico 与常用尺寸的 PNG 图标，写入 output_dir
+ */
+async generateFavicons(sourcePath: string, outputDir: string) : Promise<Result<FaviconResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("chat_list_tools") };
+    return { status: "ok", data: await TAURI_INVOKE("generate_favicons", { sourcePath, outputDir }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async chatExecuteTool(sessionId: string, toolName: string, argumentsJson: string) : Promise<Result<string, string>> {
+/**
+ * 生成占位图：背景色加对角线；SVG 中间显示 text（默认 "宽×高"）
+ */
+async generatePlaceholderImage(width: number, height: number, format: string, background: string | null, foreground: string | null, text: string | null, outputPath: string | null) : Promise<Result<GeneratedAsset, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("chat_execute_tool", { sessionId, toolName, argumentsJson }) };
+    return { status: "ok", data: await TAURI_INVOKE("generate_placeholder_image", { width, height, format, background, foreground, text, outputPath }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async listChatTasks(sessionId: string) : Promise<Result<ChatTask[], string>> {
+/**
+ * 批量压缩 source_dir 中的图片，写入 output_dir（需与源目录不同）
+ */
+async optimizeImages(sourceDir: string, outputDir: string, options: ImageOptimizeOptions | null, jobId: string | null) : Promise<Result<ImageOptimizeReport, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("list_chat_tasks", { sessionId }) };
+    return { status: "ok", data: await TAURI_INVOKE("optimize_images", { sourceDir, outputDir, options, jobId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async createChatTask(sessionId: string, subject: string, description: string, activeForm: string | null) : Promise<Result<ChatTask, string>> {
+/**
+ * 把 source 单向同步到 destination；dry_run 时只返回将要执行的变更
+ */
+async syncFolder(source: string, destination: string, options: FolderSyncOptions | null, dryRun: boolean, jobId: string | null) : Promise<Result<FolderSyncReport, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("create_chat_task", { sessionId, subject, description, activeForm }) };
+    return { status: "ok", data: await TAURI_INVOKE("sync_folder", { source, destination, options, dryRun, jobId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateChatTask(sessionId: string, taskId: string, status: string | null, subject: string | null, description: string | null) : Promise<Result<ChatTask, string>> {
+/**
+ * 已保存的代码托管令牌
+ */
+async listHostingTokens() : Promise<Result<HostingTokenInfo[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_chat_task", { sessionId, taskId, status, subject, description }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_hosting_tokens") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deleteChatTask(sessionId: string, taskId: string) : Promise<Result<null, string>> {
+/**
+ * 保存主机的访问令牌（同一主机覆盖）
+ */
+async saveHostingToken(host: string, provider: string, token: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_chat_task", { sessionId, taskId }) };
+    return { status: "ok", data: await TAURI_INVOKE("save_hosting_token", { host, provider, token }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getGlobalMemory() : Promise<Result<string, string>> {
+async deleteHostingToken(host: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_global_memory") };
+    return { status: "ok", data: await TAURI_INVOKE("delete_hosting_token", { host }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveGlobalMemory(content: string) : Promise<Result<null, string>> {
+/**
+ * 列出 Release 的附件；地址不带版本号时取最新版本
+ */
+async listReleaseAssets(url: string) : Promise<Result<ReleaseInfo, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_global_memory", { content }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_release_assets", { url }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async listSkills() : Promise<Result<Skill[], string>> {
+/**
+ * 下载 Release 附件：重新查询一次附件列表，按名称找到附件后交给下载器
+ */
+async downloadReleaseAsset(url: string, assetName: string, saveDir: string | null) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("list_skills") };
+    return { status: "ok", data: await TAURI_INVOKE("download_release_asset", { url, assetName, saveDir }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveSkill(skill: Skill) : Promise<Result<null, string>> {
+/**
+ * 获取进程列表
+ */
+async getProcesses(filter: ProcessFilter | null) : Promise<Result<ProcessInfo[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_skill", { skill }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_processes", { filter }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deleteSkill(name: string) : Promise<Result<null, string>> {
+/**
+ * 查询端口占用
+ */
+async getPortProcesses(port: number) : Promise<Result<ProcessInfo[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_skill", { name }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_port_processes", { port }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async listDirEntries(root: string, max: number | null) : Promise<Result<MentionFileEntry[], string>> {
+/**
+ * 终止进程。force 时需二次确认：不带 confirm_token 时只返回确认请求，
+ * 带有效令牌时执行并返回 None；普通终止直接执行
+ */
+async killProcess(pid: number, force: boolean | null, confirmToken: string | null) : Promise<Result<ConfirmRequest | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("list_dir_entries", { root, max }) };
+    return { status: "ok", data: await TAURI_INVOKE("kill_process", { pid, force, confirmToken }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async readMentionFile(root: string, relPath: string) : Promise<Result<string, string>> {
+/**
+ * 获取系统资源使用情况
+ */
+async getSystemStats() : Promise<Result<SystemStats, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("read_mention_file", { root, relPath }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_system_stats") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async workflowList() : Promise<Result<Workflow[], string>> {
+/**
+ * 获取本地端口占用情况
+ */
+async getLocalPortOccupation() : Promise<Result<PortOccupation[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("workflow_list") };
+    return { status: "ok", data: await TAURI_INVOKE("get_local_port_occupation") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async workflowGet(id: string) : Promise<Result<Workflow, string>> {
+/**
+ * 启动端口占用监控：按间隔取快照，有变化时推送 `port-occupation-changed`。
+ * 返回初始快照；重复调用会替换掉旧的监控任务（用于调整间隔）。
+ */
+async startPortMonitor(intervalSecs: number | null) : Promise<Result<PortOccupation[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("workflow_get", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("start_port_monitor", { intervalSecs }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async workflowSave(workflow: Workflow) : Promise<Result<Workflow, string>> {
+/**
+ * 停止端口占用监控
+ */
+async stopPortMonitor() : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("workflow_save", { workflow }) };
+    return { status: "ok", data: await TAURI_INVOKE("stop_port_monitor") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async workflowDelete(id: string) : Promise<Result<null, string>> {
+/**
+ * 结束占用端口的进程并重新启动服务/转发。
+ * 只允许结束当前确实占用该端口的进程；不带 confirm_token 时只返回确认请求。
+ */
+async killAndRetry(targetKind: string, targetId: string, port: number, pid: number, force: boolean | null, confirmToken: string | null) : Promise<Result<KillAndRetryResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("workflow_delete", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("kill_and_retry", { targetKind, targetId, port, pid, force, confirmToken }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async workflowRunNow(id: string) : Promise<Result<WorkflowRun, string>> {
+/**
+ * 以管理员权限结束进程（会弹出系统授权对话框）。
+ * 不带 confirm_token 时只返回确认请求，带有效令牌时执行并返回 None
+ */
+async killProcessElevated(pid: number, force: boolean | null, confirmToken: string | null) : Promise<Result<ConfirmRequest | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("workflow_run_now", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("kill_process_elevated", { pid, force, confirmToken }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async workflowSetEnabled(id: string, enabled: boolean) : Promise<Result<Workflow, string>> {
+/**
+ * 启动提权端口中继：监听 listen_host:listen_port，转发到 127.0.0.1:target_port
+ */
+async startElevatedPortRelay(listenPort: number, targetPort: number, listenHost: string | null) : Promise<Result<ElevatedRelay, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("workflow_set_enabled", { id, enabled }) };
+    return { status: "ok", data: await TAURI_INVOKE("start_elevated_port_relay", { listenPort, targetPort, listenHost }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async chatBridgeTest(relay: string) : Promise<Result<string, string>> {
+/**
+ * 停止提权端口中继
+ */
+async stopElevatedPortRelay(listenPort: number) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("chat_bridge_test", { relay }) };
+    return { status: "ok", data: await TAURI_INVOKE("stop_elevated_port_relay", { listenPort }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getLabels() : Promise<Result<string[], string>> {
+/**
+ * 获取正在运行的提权端口中继
+ */
+async getElevatedPortRelays() : Promise<Result<ElevatedRelay[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_labels") };
+    return { status: "ok", data: await TAURI_INVOKE("get_elevated_port_relays") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveLabels(labels: string[]) : Promise<Result<null, string>> {
+/**
+ * 获取告警规则
+ */
+async getResourceAlertRules() : Promise<Result<ResourceAlertRule[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_labels", { labels }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_resource_alert_rules") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async addLabel(label: string) : Promise<Result<string[], string>> {
+/**
+ * 新建（id 为空）或更新告警规则
+ */
+async saveResourceAlertRule(id: string | null, input: ResourceAlertRuleInput) : Promise<Result<ResourceAlertRule, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("add_label", { label }) };
+    return { status: "ok", data: await TAURI_INVOKE("save_resource_alert_rule", { id, input }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async removeLabel(label: string) : Promise<Result<string[], string>> {
+/**
+ * 删除告警规则
+ */
+async deleteResourceAlertRule(id: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("remove_label", { label }) };
+    return { status: "ok", data: await TAURI_INVOKE("delete_resource_alert_rule", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getCategories() : Promise<Result<string[], string>> {
+/**
+ * 获取当前正在触发的告警
+ */
+async getCurrentAlerts() : Promise<Result<ResourceAlert[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_categories") };
+    return { status: "ok", data: await TAURI_INVOKE("get_current_alerts") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveCategories(categories: string[]) : Promise<Result<null, string>> {
+/**
+ * 添加转发规则
+ */
+async addForwardRule(input: ForwardRuleInput) : Promise<Result<ForwardRule, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_categories", { categories }) };
+    return { status: "ok", data: await TAURI_INVOKE("add_forward_rule", { input }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async addCategory(category: string) : Promise<Result<string[], string>> {
+/**
+ * 移除转发规则
+ */
+async removeForwardRule(ruleId: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("add_category", { category }) };
+    return { status: "ok", data: await TAURI_INVOKE("remove_forward_rule", { ruleId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async removeCategory(category: string) : Promise<Result<string[], string>> {
+/**
+ * 启动转发
+ */
+async startForwarding(ruleId: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("remove_category", { category }) };
+    return { status: "ok", data: await TAURI_INVOKE("start_forwarding", { ruleId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getEditors() : Promise<Result<EditorConfig[], string>> {
+/**
+ * 停止转发
+ */
+async stopForwarding(ruleId: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_editors") };
+    return { status: "ok", data: await TAURI_INVOKE("stop_forwarding", { ruleId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async addEditor(input: EditorInput) : Promise<Result<EditorConfig[], string>> {
+/**
+ * 获取所有转发规则
+ */
+async getForwardRules() : Promise<Result<ForwardRule[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("add_editor", { input }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_forward_rules") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateEditor(id: string, input: EditorInput) : Promise<Result<EditorConfig[], string>> {
+/**
+ * 获取单个转发规则
+ */
+async getForwardRule(ruleId: string) : Promise<Result<ForwardRule | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_editor", { id, input }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_forward_rule", { ruleId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async removeEditor(id: string) : Promise<Result<EditorConfig[], string>> {
+/**
+ * 获取转发统计
+ */
+async getForwardStats(ruleId: string) : Promise<Result<ForwardStats, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("remove_editor", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_forward_stats", { ruleId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setDefaultEditor(id: string) : Promise<Result<EditorConfig[], string>> {
+/**
+ * 更新转发规则
+ */
+async updateForwardRule(ruleId: string, input: ForwardRuleInput) : Promise<Result<ForwardRule, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_default_editor", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_forward_rule", { ruleId, input }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getTerminalConfig() : Promise<Result<TerminalConfig, string>> {
+async addSshTunnel(input: SshTunnelInput) : Promise<Result<SshTunnel, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_terminal_config") };
+    return { status: "ok", data: await TAURI_INVOKE("add_ssh_tunnel", { input }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveTerminalConfig(input: TerminalInput) : Promise<Result<null, string>> {
+async updateSshTunnel(tunnelId: string, input: SshTunnelInput) : Promise<Result<SshTunnel, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_terminal_config", { input }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_ssh_tunnel", { tunnelId, input }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getAppSettings() : Promise<Result<AppSettings, string>> {
+async removeSshTunnel(tunnelId: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_app_settings") };
+    return { status: "ok", data: await TAURI_INVOKE("remove_ssh_tunnel", { tunnelId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveAppSettings(input: AppSettingsInput) : Promise<Result<AppSettings, string>> {
+async startSshTunnel(tunnelId: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_app_settings", { input }) };
+    return { status: "ok", data: await TAURI_INVOKE("start_ssh_tunnel", { tunnelId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getUiState() : Promise<Result<UiState, string>> {
+async stopSshTunnel(tunnelId: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_ui_state") };
+    return { status: "ok", data: await TAURI_INVOKE("stop_ssh_tunnel", { tunnelId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveUiState(input: UiStateInput) : Promise<Result<UiState, string>> {
+async getSshTunnels() : Promise<Result<SshTunnel[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_ui_state", { input }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_ssh_tunnels") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getNotifications() : Promise<Result<Notification[], string>> {
+async getSshTunnel(tunnelId: string) : Promise<Result<SshTunnel | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_notifications") };
+    return { status: "ok", data: await TAURI_INVOKE("get_ssh_tunnel", { tunnelId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveNotifications(notifications: Notification[]) : Promise<Result<null, string>> {
+async getSshTunnelStats(tunnelId: string) : Promise<Result<SshTunnelStats, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_notifications", { notifications }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_ssh_tunnel_stats", { tunnelId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async addNotification(input: NotificationInput) : Promise<Result<Notification[], string>> {
+async listSshConfigHosts() : Promise<Result<string[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("add_notification", { input }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_ssh_config_hosts") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async removeNotification(id: string) : Promise<Result<Notification[], string>> {
+/**
+ * 列出本机所有有效 IP（去重保序），供「远程主机」下拉选择。
+ * 复用 pairdrop 已有的跨平台网卡枚举实现，避免新增依赖。
+ */
+async listLocalIps() : Promise<Result<string[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("remove_notification", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_local_ips") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async clearNotifications() : Promise<Result<null, string>> {
+/**
+ * 仅迁移分组：只改 group 字段并持久化，不停止运行中的隧道
+ * （与 update_ssh_tunnel 不同，后者会先 stop 运行中的隧道）。
+ */
+async setSshTunnelGroup(tunnelId: string, group: string) : Promise<Result<SshTunnel, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("clear_notifications") };
+    return { status: "ok", data: await TAURI_INVOKE("set_ssh_tunnel_group", { tunnelId, group }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getAppShortcuts() : Promise<Result<AppShortcutBinding[], string>> {
+async testSshTunnel(tunnelId: string) : Promise<Result<TestPortResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_app_shortcuts") };
+    return { status: "ok", data: await TAURI_INVOKE("test_ssh_tunnel", { tunnelId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveAppShortcuts(shortcuts: AppShortcutBinding[]) : Promise<Result<null, string>> {
+async testLocalPort(port: number) : Promise<Result<TestPortResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_app_shortcuts", { shortcuts }) };
+    return { status: "ok", data: await TAURI_INVOKE("test_local_port", { port }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getRecommendedTemplate() : Promise<Result<string | null, string>> {
+/**
+ * 列出 ~/.ssh 下的密钥
+ */
+async listSshKeys() : Promise<Result<SshKeyInfo[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_recommended_template") };
+    return { status: "ok", data: await TAURI_INVOKE("list_ssh_keys") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveRecommendedTemplate(content: string) : Promise<Result<null, string>> {
-    try {
-    return { status: "ok", data: await TAURI_INVOKE("save_recommended_template", { content }) };
+/**
+ * 生成 ed25519 密钥；name 默认 id_ed25519，已存在时拒绝覆盖
+ */
+async generateSshKey(name: string | null, comment: string | null, passphrase: string | null) : Promise<Result<SshKeyInfo, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("generate_ssh_key", { name, comment, passphrase }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async resetRecommendedTemplate() : Promise<Result<null, string>> {
+/**
+ * 读取公钥内容（供前端复制）
+ */
+async getSshPublicKey(name: string) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("reset_recommended_template") };
+    return { status: "ok", data: await TAURI_INVOKE("get_ssh_public_key", { name }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getAiProviders() : Promise<Result<AiProviderConfig[], string>> {
+/**
+ * ssh -T 测试认证；host 可以是 "git@github.com" 或 ssh config 中的别名，
+ * 指定 key_name 时只使用该密钥
+ */
+async testSshAuth(host: string, keyName: string | null) : Promise<Result<SshAuthTestResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_ai_providers") };
+    return { status: "ok", data: await TAURI_INVOKE("test_ssh_auth", { host, keyName }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveAiProviders(providers: AiProviderConfig[]) : Promise<Result<AiProviderConfig[], string>> {
+/**
+ * ssh config 中的 Host 列表（含 HostName / User / IdentityFile）
+ */
+async listSshHostEntries() : Promise<Result<SshHostEntry[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_ai_providers", { providers }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_ssh_host_entries") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getSensitiveFilePatterns() : Promise<Result<string[], string>> {
+/**
+ * 把密钥映射到远程主机：新增或更新 ssh config 中的 Host 块
+ */
+async saveSshHostEntry(input: SshHostEntryInput) : Promise<Result<SshHostEntry, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_sensitive_file_patterns") };
+    return { status: "ok", data: await TAURI_INVOKE("save_ssh_host_entry", { input }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveSensitiveFilePatterns(patterns: string[]) : Promise<Result<null, string>> {
+/**
+ * 从 ssh config 中删除 Host
+ */
+async removeSshHostEntry(alias: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_sensitive_file_patterns", { patterns }) };
+    return { status: "ok", data: await TAURI_INVOKE("remove_ssh_host_entry", { alias }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getResumes() : Promise<Result<JsonValue, string>> {
+/**
+ * 创建服务
+ */
+async createServer(input: ServerConfigInput) : Promise<Result<ServerConfig, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_resumes") };
+    return { status: "ok", data: await TAURI_INVOKE("create_server", { input }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveResumes(data: JsonValue) : Promise<Result<null, string>> {
+/**
+ * 启动服务
+ */
+async startServer(serverId: string) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_resumes", { data }) };
+    return { status: "ok", data: await TAURI_INVOKE("start_server", { serverId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async saveResumeKnowledge(projectId: string, content: string, userEdited: boolean) : Promise<Result<null, string>> {
+/**
+ * 停止服务
+ */
+async stopServer(serverId: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("save_resume_knowledge", { projectId, content, userEdited }) };
+    return { status: "ok", data: await TAURI_INVOKE("stop_server", { serverId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async loadResumeKnowledge(projectId: string) : Promise<Result<string | null, string>> {
+/**
+ * 移除服务
+ */
+async removeServer(serverId: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("load_resume_knowledge", { projectId }) };
+    return { status: "ok", data: await TAURI_INVOKE("remove_server", { serverId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async listResumeKnowledge() : Promise<Result<string[], string>> {
+/**
+ * 获取所有服务
+ */
+async getServers() : Promise<Result<ServerConfig[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("list_resume_knowledge") };
+    return { status: "ok", data: await TAURI_INVOKE("get_servers") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async listResumeKnowledgeHistory(projectId: string) : Promise<Result<ResumeKnowledgeHistoryEntry[], string>> {
+/**
+ * 获取单个服务
+ */
+async getServer(serverId: string) : Promise<Result<ServerConfig | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("list_resume_knowledge_history", { projectId }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_server", { serverId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async readResumeKnowledgeHistory(projectId: string, timestamp: string) : Promise<Result<string, string>> {
+/**
+ * 更新服务配置
+ */
+async updateServer(serverId: string, input: ServerConfigInput) : Promise<Result<ServerConfig, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("read_resume_knowledge_history", { projectId, timestamp }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_server", { serverId, input }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deleteResumeKnowledge(projectId: string) : Promise<Result<null, string>> {
+async generateNginxConfig(serverId: string) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_resume_knowledge", { projectId }) };
+    return { status: "ok", data: await TAURI_INVOKE("generate_nginx_config", { serverId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async llmProxyRequest(request: LlmProxyRequest) : Promise<Result<LlmProxyResponse, string>> {
+/**
+ * 导出服务配置包（格式化的 JSON）
+ */
+async exportServerConfig(serverId: string) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("llm_proxy_request", { request }) };
+    return { status: "ok", data: await TAURI_INVOKE("export_server_config", { serverId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async resumeProjectIndex(projectId: string) : Promise<Result<ResumeProjectIndex, string>> {
+/**
+ * 导入服务配置包，新建一个停止状态的服务；root_dir 覆盖包中的根目录
+ */
+async importServerConfig(bundle: string, rootDir: string | null) : Promise<Result<ServerConfig, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("resume_project_index", { projectId }) };
+    return { status: "ok", data: await TAURI_INVOKE("import_server_config", { bundle, rootDir }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async resumeProjectListDir(projectId: string, path: string) : Promise<Result<string, string>> {
+async dockerCheckAvailable() : Promise<Result<DockerStatus, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("resume_project_list_dir", { projectId, path }) };
+    return { status: "ok", data: await TAURI_INVOKE("docker_check_available") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async resumeProjectReadFile(projectId: string, path: string) : Promise<Result<string, string>> {
+async dockerFindDockerfiles(projectPath: string) : Promise<Result<string[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("resume_project_read_file", { projectId, path }) };
+    return { status: "ok", data: await TAURI_INVOKE("docker_find_dockerfiles", { projectPath }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async resumeProjectGrep(projectId: string, pattern: string, glob: string | null) : Promise<Result<string, string>> {
+async dockerReadDockerfile(projectPath: string, dockerfilePath: string) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("resume_project_grep", { projectId, pattern, glob }) };
+    return { status: "ok", data: await TAURI_INVOKE("docker_read_dockerfile", { projectPath, dockerfilePath }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async runResumeAgent(request: RunResumeAgentRequest) : Promise<Result<RunResumeAgentResponse, string>> {
+async dockerWriteDockerfile(projectPath: string, dockerfilePath: string, content: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("run_resume_agent", { request }) };
+    return { status: "ok", data: await TAURI_INVOKE("docker_write_dockerfile", { projectPath, dockerfilePath, content }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async cancelResumeAgent(requestId: string) : Promise<Result<null, string>> {
+async dockerGenerateDockerfileTemplate(projectPath: string, template: string | null) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("cancel_resume_agent", { requestId }) };
+    return { status: "ok", data: await TAURI_INVOKE("docker_generate_dockerfile_template", { projectPath, template }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async runKnowledgeAgent(request: RunKnowledgeAgentRequest) : Promise<Result<RunKnowledgeAgentResponse, string>> {
+async dockerGenerateDockerfileAi(input: DockerAiGenerateInput) : Promise<Result<DockerAiGenerateOutput, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("run_knowledge_agent", { request }) };
+    return { status: "ok", data: await TAURI_INVOKE("docker_generate_dockerfile_ai", { input }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async cancelKnowledgeAgent(requestId: string) : Promise<Result<null, string>> {
+async dockerBuildImage(input: DockerBuildInput) : Promise<Result<DockerCommandResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("cancel_knowledge_agent", { requestId }) };
+    return { status: "ok", data: await TAURI_INVOKE("docker_build_image", { input }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async registerGlobalShortcuts(shortcuts: GlobalShortcutBinding[]) : Promise<Result<null, string>> {
+async dockerListImages() : Promise<Result<DockerImageInfo[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("register_global_shortcuts", { shortcuts }) };
+    return { status: "ok", data: await TAURI_INVOKE("docker_list_images") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async unregisterAllGlobalShortcuts() : Promise<Result<null, string>> {
+async dockerRemoveImage(image: string, force: boolean | null) : Promise<Result<DockerCommandResult, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("unregister_all_global_shortcuts") };
+    return { status: "ok", data: await TAURI_INVOKE("docker_remove_image", { image, force }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
+},
+async dockerRunImage(input: DockerRunInput) : Promise<Result<DockerCommandResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("docker_run_image", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
 }
+},
+async dockerListContainers() : Promise<Result<DockerContainerInfo[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("docker_list_containers") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
 }
-
-/** user-defined events **/
-
-
-
-/** user-defined constants **/
-
-
-
-/** user-defined types **/
-
-export type AiModelConfig = { id: string; model: string; enabled: boolean; isDefault: boolean; thinking: boolean; stream?: boolean; vision?: boolean }
-export type AiProviderConfig = { id: string; name: string; providerType: string; presetKey: string | null; baseUrl: string; apiKey: string | null; enabled: boolean; isDefaultProvider: boolean; models: AiModelConfig[] }
-/**
- * API 鉴权配置
- */
-export type ApiAuthConfig = { type: "none" } | { type: "bearer"; token: string } | { type: "basic"; username: string; password: string } | { type: "apiKey"; header: string; value: string } | { type: "session"; login_url: string; login_method: string; credentials_json: string; token_json_path: string | null; inject_as: SessionInject }
-/**
- * API 对话会话（与 ChatSession 字段大致对齐，额外携带绑定的接口集合）
- */
-export type ApiChatSession = { id: string; title: string; providerId: string; modelId: string; createdAt: string; updatedAt: string; messages: ChatMessage[]; selectedEndpointIds?: string[]; systemPrompt?: string | null; temperature?: number | null; maxTokens?: number | null; topP?: number | null; frequencyPenalty?: number | null; presencePenalty?: number | null; pinned?: boolean | null }
-export type ApiChatSessionSummary = { id: string; title: string; providerId: string; modelId: string; createdAt: string; updatedAt: string; messageCount: number; endpointCount: number; pinned?: boolean | null }
+},
+async dockerInspectContainerYaml(container: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("docker_inspect_container_yaml", { container }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async dockerStopContainer(container: string) : Promise<Result<DockerCommandResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("docker_stop_container", { container }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async dockerStartContainer(container: string) : Promise<Result<DockerCommandResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("docker_start_container", { container }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async dockerRestartContainer(container: string) : Promise<Result<DockerCommandResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("docker_restart_container", { container }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async dockerRemoveContainer(container: string, force: boolean | null) : Promise<Result<DockerCommandResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("docker_remove_container", { container, force }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async dockerPushImage(image: string) : Promise<Result<DockerCommandResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("docker_push_image", { image }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * 单个 API 接口
+ * 检查所有环境的 Claude Code 安装情况
  */
-export type ApiEndpoint = { id: string; name: string; description?: string | null; 
+async checkAllClaudeInstallations() : Promise<Result<ClaudeCodeInfo[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_all_claude_installations") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * None 表示独立接口（不属于任何组）
+ * 根据指定路径检查 Claude Code 安装
  */
-groupId?: string | null; 
+async checkClaudeByPath(claudePath: string) : Promise<Result<ClaudeCodeInfo, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_claude_by_path", { claudePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * GET / POST / PUT / PATCH / DELETE
+ * 读取配置文件内容
  */
-method: string; 
+async readClaudeConfigFile(envType: EnvType, envName: string, path: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("read_claude_config_file", { envType, envName, path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * 可含 {path_param}；或绝对 URL（此时忽略组 baseUrl）
+ * 写入配置文件内容
  */
-url: string; headers?: ([string, string])[]; 
+async writeClaudeConfigFile(envType: EnvType, envName: string, path: string, content: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("write_claude_config_file", { envType, envName, path, content }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * 覆盖组鉴权
+ * 打开配置目录
  */
-authOverride?: ApiAuthConfig | null; 
+async openClaudeConfigDir(envType: EnvType, envName: string, configDir: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_claude_config_dir", { envType, envName, configDir }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * 喂给 LLM function-calling 的 JSON Schema
+ * 获取快捷配置选项列表
  */
-paramsSchema: JsonValue; 
+async getQuickConfigOptions() : Promise<Result<QuickConfigOption[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_quick_config_options") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * 响应截断字节数（默认 8192）
+ * 应用快捷配置
  */
-responseTrimBytes?: number | null; createdAt: string; updatedAt: string }
+async applyQuickConfig(envType: EnvType, envName: string, configPath: string, options: string[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("apply_quick_config", { envType, envName, configPath, options }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * 单次接口调用的结构化返回：给前端做"调用链"展示用
+ * 获取保存的配置档案列表
+ */
+async getConfigProfiles(envType: EnvType, envName: string) : Promise<Result<ConfigProfile[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_config_profiles", { envType, envName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 保存配置档案（如果名称已存在则更新，否则新建）
+ */
+async saveConfigProfile(envType: EnvType, envName: string, name: string, description: string | null, settings: JsonValue) : Promise<Result<ConfigProfile, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_config_profile", { envType, envName, name, description, settings }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 删除配置档案
+ */
+async deleteConfigProfile(envType: EnvType, envName: string, profileId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_config_profile", { envType, envName, profileId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 应用配置档案
+ */
+async applyConfigProfile(envType: EnvType, envName: string, configPath: string, profileId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("apply_config_profile", { envType, envName, configPath, profileId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 从当前配置创建档案
+ */
+async createProfileFromCurrent(envType: EnvType, envName: string, configPath: string, profileName: string, description: string | null) : Promise<Result<ConfigProfile, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_profile_from_current", { envType, envName, configPath, profileName, description }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 扫描指定配置目录的配置文件
+ */
+async scanClaudeConfigDir(envType: EnvType, envName: string, configDir: string) : Promise<Result<ConfigFileInfo[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("scan_claude_config_dir", { envType, envName, configDir }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 非 Windows 系统的 stub
+ */
+async getWslConfigDir(distro: string) : Promise<Result<WslConfigDirResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_wsl_config_dir", { distro }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 获取保存的 Claude 快捷配置
+ */
+async getSavedQuickConfigs() : Promise<Result<ClaudeQuickConfig[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_saved_quick_configs") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 保存 Claude 快捷配置
+ */
+async saveQuickConfigs(configs: ClaudeQuickConfig[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_quick_configs", { configs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 获取缓存的 Claude 安装信息
+ */
+async getClaudeInstallationsCache() : Promise<Result<ClaudeCodeInfo[] | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_claude_installations_cache") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 保存 Claude 安装信息缓存
+ */
+async saveClaudeInstallationsCache(installs: ClaudeCodeInfo[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_claude_installations_cache", { installs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 清除 Claude 安装信息缓存
+ */
+async clearClaudeInstallationsCache() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clear_claude_installations_cache") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 在终端中启动 Claude Code
+ */
+async launchClaudeInTerminal(workDir: string | null, terminalType: string | null, customPath: string | null, terminalPath: string | null, envType: string | null, envName: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("launch_claude_in_terminal", { workDir, terminalType, customPath, terminalPath, envType, envName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 获取保存的 Claude 启动目录列表
+ */
+async getClaudeLaunchDirs() : Promise<Result<string[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_claude_launch_dirs") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 保存 Claude 启动目录列表
+ */
+async saveClaudeLaunchDirs(dirs: string[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_claude_launch_dirs", { dirs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 初始化并加载已保存的会话
+ */
+async netcatInit() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_init") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 创建新会话
+ */
+async netcatCreateSession(input: NetcatSessionInput) : Promise<Result<NetcatSession, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_create_session", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 启动会话
+ */
+async netcatStartSession(sessionId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_start_session", { sessionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 停止会话
+ */
+async netcatStopSession(sessionId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_stop_session", { sessionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 删除会话
+ */
+async netcatRemoveSession(sessionId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_remove_session", { sessionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 发送消息
+ */
+async netcatSendMessage(input: SendMessageInput) : Promise<Result<NetcatMessage, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_send_message", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 获取所有会话
+ */
+async netcatGetSessions() : Promise<Result<NetcatSession[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_get_sessions") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 获取单个会话
+ */
+async netcatGetSession(sessionId: string) : Promise<Result<NetcatSession, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_get_session", { sessionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 获取会话消息（最新在前），可按方向、客户端、时间范围和内容筛选后再分页
+ */
+async netcatGetMessages(sessionId: string, limit: number | null, offset: number | null, filter: NetcatMessageFilter | null) : Promise<Result<NetcatMessage[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_get_messages", { sessionId, limit, offset, filter }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 获取连接的客户端
+ */
+async netcatGetClients(sessionId: string) : Promise<Result<ConnectedClient[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_get_clients", { sessionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 清空会话消息
+ */
+async netcatClearMessages(sessionId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_clear_messages", { sessionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 断开指定客户端（仅服务器模式）
+ */
+async netcatDisconnectClient(sessionId: string, clientId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_disconnect_client", { sessionId, clientId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 更新会话的自动发送配置
+ */
+async netcatUpdateAutoSend(sessionId: string, config: AutoSendConfig) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_update_auto_send", { sessionId, config }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 设置会话的流量日志模式；log_payload 为空时保持不变
+ */
+async netcatSetLogMode(sessionId: string, mode: TrafficLogMode, logPayload: boolean | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_set_log_mode", { sessionId, mode, logPayload }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 设置会话的消息条数上限与溢出策略；上限调小时按策略立即裁剪已有消息
+ */
+async netcatSetHistoryLimit(sessionId: string, limit: number, policy: OverflowPolicy) : Promise<Result<NetcatSession, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_set_history_limit", { sessionId, limit, policy }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 会话的流量日志计数
+ */
+async netcatGetLogStats(sessionId: string) : Promise<Result<NetcatLogStats, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_get_log_stats", { sessionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * HTTP 请求：返回状态、响应头、耗时阶段与重定向链
+ */
+async netcatFetchHttp(config: HttpFetchConfig) : Promise<Result<HttpFetchResponse, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_fetch_http", { config }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 设置会话所属分组，group 为空时移出分组
+ */
+async netcatSetSessionGroup(sessionIds: string[], group: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_set_session_group", { sessionIds, group }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 启动分组内所有会话
+ */
+async netcatStartGroup(group: string) : Promise<Result<NetcatGroupOpResult[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_start_group", { group }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 停止分组内所有会话
+ */
+async netcatStopGroup(group: string) : Promise<Result<NetcatGroupOpResult[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_stop_group", { group }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 向分组内所有已连接的会话发送同一条数据；服务器模式会话广播给其全部客户端
+ */
+async netcatSendToGroup(group: string, data: string, format: DataFormat) : Promise<Result<NetcatGroupOpResult[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_send_to_group", { group, data, format }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 获取各分组的汇总统计
+ */
+async netcatGetGroupStats() : Promise<Result<NetcatGroupStats[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_get_group_stats") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 获取报文库
+ */
+async netcatGetPayloads() : Promise<Result<NetcatPayload[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_get_payloads") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 新建（id 为空）或更新报文
+ */
+async netcatSavePayload(id: string | null, input: NetcatPayloadInput) : Promise<Result<NetcatPayload, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_save_payload", { id, input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 删除报文
+ */
+async netcatDeletePayload(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_delete_payload", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 预览渲染结果（不发送）
+ */
+async netcatRenderPayload(template: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_render_payload", { template }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 导出选中的会话到 file_path；返回导出的会话数
+ */
+async netcatExportSessions(sessionIds: string[], includePayloads: boolean, filePath: string) : Promise<Result<number, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_export_sessions", { sessionIds, includePayloads, filePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 从导出文件导入会话（新建，不覆盖已有会话）与报文
+ */
+async netcatImportSessions(filePath: string) : Promise<Result<NetcatImportResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("netcat_import_sessions", { filePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 连接 MQTT 服务器
+ */
+async mqttConnect(input: MqttConnectInput) : Promise<Result<MqttSession, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mqtt_connect", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 断开并移除 MQTT 会话
+ */
+async mqttDisconnect(sessionId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mqtt_disconnect", { sessionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 订阅主题（支持 + / # 通配符）
+ */
+async mqttSubscribe(sessionId: string, topic: string, qos: number | null) : Promise<Result<number, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mqtt_subscribe", { sessionId, topic, qos }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 取消订阅
+ */
+async mqttUnsubscribe(sessionId: string, topic: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mqtt_unsubscribe", { sessionId, topic }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 发布消息；QoS 1 时等待 PUBACK
+ */
+async mqttPublish(sessionId: string, topic: string, payload: string, format: DataFormat, qos: number | null, retain: boolean | null) : Promise<Result<MqttMessage, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mqtt_publish", { sessionId, topic, payload, format, qos, retain }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 获取所有 MQTT 会话
+ */
+async mqttGetSessions() : Promise<Result<MqttSession[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mqtt_get_sessions") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 获取消息历史（最新的 limit 条，默认全部），可按主题过滤
+ */
+async mqttGetMessages(sessionId: string, topic: string | null, limit: number | null) : Promise<Result<MqttMessage[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mqtt_get_messages", { sessionId, topic, limit }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 清空消息历史
+ */
+async mqttClearMessages(sessionId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mqtt_clear_messages", { sessionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 发送一次 Modbus TCP 请求
+ */
+async modbusRequest(request: ModbusRequest) : Promise<Result<ModbusResponse, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("modbus_request", { request }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 开始抓包；path 为空时保存到数据目录下的 captures 目录
+ */
+async startTrafficCapture(kind: string, resourceId: string, path: string | null) : Promise<Result<TrafficCapture, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_traffic_capture", { kind, resourceId, path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 停止抓包并写盘，返回最终统计
+ */
+async stopTrafficCapture(resourceId: string) : Promise<Result<TrafficCapture, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("stop_traffic_capture", { resourceId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 获取正在进行的抓包
+ */
+async getTrafficCaptures() : Promise<Result<TrafficCapture[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_traffic_captures") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 获取所有快捷键，首次自动写入默认数据
+ */
+async getShortcuts() : Promise<Result<ShortcutEntry[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_shortcuts") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 全量保存快捷键（导入用）
+ */
+async saveShortcuts(shortcuts: ShortcutEntry[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_shortcuts", { shortcuts }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 添加用户自定义快捷键
+ */
+async addShortcut(input: ShortcutInput) : Promise<Result<ShortcutEntry, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_shortcut", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 编辑快捷键（默认项首次编辑时保存 originalKeys）
+ */
+async updateShortcut(id: string, input: ShortcutInput) : Promise<Result<ShortcutEntry, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_shortcut", { id, input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 删除快捷键（仅允许删除用户添加的）
+ */
+async deleteShortcut(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_shortcut", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 恢复所有默认项 + 保留用户自定义项
+ */
+async resetShortcuts() : Promise<Result<ShortcutEntry[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reset_shortcuts") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 返回当前平台
+ */
+async getCurrentPlatform() : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_current_platform") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getClipboardHistory() : Promise<Result<ClipboardEntry[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_clipboard_history") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async addClipboardEntry(content: string) : Promise<Result<ClipboardEntry, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_clipboard_entry", { content }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteClipboardEntry(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_clipboard_entry", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async togglePinClipboardEntry(id: string) : Promise<Result<ClipboardEntry, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("toggle_pin_clipboard_entry", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 清空非置顶条目；置顶永远保留
+ */
+async clearClipboardHistory() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clear_clipboard_history") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getClipboardSettings() : Promise<Result<ClipboardSettings, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_clipboard_settings") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveClipboardSettings(settings: ClipboardSettings) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_clipboard_settings", { settings }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async writeToClipboard(content: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("write_to_clipboard", { content }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateClipboardNote(id: string, note: string) : Promise<Result<ClipboardEntry, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_clipboard_note", { id, note }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 启动服务。port=0 表示由系统选择。
+ */
+async pairdropStart(port: number | null) : Promise<Result<ServiceStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("pairdrop_start", { port }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 停止服务
+ */
+async pairdropStop() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("pairdrop_stop") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 查询当前状态
+ */
+async pairdropStatus() : Promise<Result<ServiceStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("pairdrop_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 获取当前 peer 列表（用于桌面端不通过 WebSocket 时也能查看）
+ */
+async pairdropPeers() : Promise<Result<PeerInfo[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("pairdrop_peers") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 把缓存中的接收文件直接写到本地。一次性消费 — 调用后 token 立即失效，
+ * 避免再被 HTTP /api/file/:token 又下载一次。
+ */
+async pairdropSaveFile(token: string, savePath: string) : Promise<Result<number, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("pairdrop_save_file", { token, savePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getChatHistoryDir() : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_chat_history_dir") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async migrateChatHistoryDir(newDir: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("migrate_chat_history_dir", { newDir }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listChatSessions() : Promise<Result<ChatSessionSummary[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_chat_sessions") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getChatSession(sessionId: string) : Promise<Result<ChatSession, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_chat_session", { sessionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createChatSession(input: CreateChatSessionInput) : Promise<Result<ChatSession, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_chat_session", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveChatSession(session: ChatSession) : Promise<Result<ChatSession, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_chat_session", { session }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async renameChatSession(sessionId: string, title: string) : Promise<Result<ChatSession, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("rename_chat_session", { sessionId, title }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteChatSession(sessionId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_chat_session", { sessionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveCompaction(input: SaveCompactionInput) : Promise<Result<CompactionMeta, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_compaction", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listCompactions(sessionId: string) : Promise<Result<CompactionIndex, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_compactions", { sessionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getCompaction(sessionId: string, version: string | null) : Promise<Result<CompactionContent | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_compaction", { sessionId, version }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async chatStream(request: ChatStreamRequest) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("chat_stream", { request }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async chatComplete(request: ChatStreamRequest) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("chat_complete", { request }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async chatCancel(requestId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("chat_cancel", { requestId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listApiGroups() : Promise<Result<ApiGroup[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_api_groups") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveApiGroup(group: ApiGroup) : Promise<Result<ApiGroup, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_api_group", { group }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteApiGroup(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_api_group", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listApiEndpoints() : Promise<Result<ApiEndpoint[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_api_endpoints") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveApiEndpoint(endpoint: ApiEndpoint) : Promise<Result<ApiEndpoint, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_api_endpoint", { endpoint }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteApiEndpoint(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_api_endpoint", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listApiChatSessions() : Promise<Result<ApiChatSessionSummary[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_api_chat_sessions") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getApiChatSession(sessionId: string) : Promise<Result<ApiChatSession, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_api_chat_session", { sessionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createApiChatSession(input: CreateApiChatSessionInput) : Promise<Result<ApiChatSession, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_api_chat_session", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveApiChatSession(session: ApiChatSession) : Promise<Result<ApiChatSession, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_api_chat_session", { session }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async renameApiChatSession(sessionId: string, title: string) : Promise<Result<ApiChatSession, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("rename_api_chat_session", { sessionId, title }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteApiChatSession(sessionId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_api_chat_session", { sessionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async buildApiTools(endpointIds: string[]) : Promise<Result<ApiToolsBundle, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("build_api_tools", { endpointIds }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async executeApiEndpoint(endpointId: string, argumentsJson: string) : Promise<Result<ApiExecutionResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("execute_api_endpoint", { endpointId, argumentsJson }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async fetchApiDocumentUrl(url: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("fetch_api_document_url", { url }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listDataBackups() : Promise<Result<string[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_data_backups") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async restoreFromBackup(timestamp: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("restore_from_backup", { timestamp }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async checkSettingsDocuments() : Promise<Result<DocumentCheck[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_settings_documents") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getSettingsMigrationLog() : Promise<Result<MigrationLogEntry[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_settings_migration_log") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 查询使用统计；days 为空时统计全部记录
+ */
+async getUsageStats(days: number | null) : Promise<Result<UsageStats, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_usage_stats", { days }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 清空所有使用统计
+ */
+async clearUsageStats() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clear_usage_stats") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async mcpGatewayStatus() : Promise<Result<McpGatewayStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mcp_gateway_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async mcpGatewayInternalEndpoint() : Promise<Result<McpGatewayInternalEndpoint | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mcp_gateway_internal_endpoint") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async chatListTools() : Promise<Result<ToolSchema[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("chat_list_tools") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async chatExecuteTool(sessionId: string, toolName: string, argumentsJson: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("chat_execute_tool", { sessionId, toolName, argumentsJson }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listChatTasks(sessionId: string) : Promise<Result<ChatTask[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_chat_tasks", { sessionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createChatTask(sessionId: string, subject: string, description: string, activeForm: string | null) : Promise<Result<ChatTask, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_chat_task", { sessionId, subject, description, activeForm }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateChatTask(sessionId: string, taskId: string, status: string | null, subject: string | null, description: string | null) : Promise<Result<ChatTask, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_chat_task", { sessionId, taskId, status, subject, description }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteChatTask(sessionId: string, taskId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_chat_task", { sessionId, taskId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getGlobalMemory() : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_global_memory") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveGlobalMemory(content: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_global_memory", { content }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listSkills() : Promise<Result<Skill[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_skills") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveSkill(skill: Skill) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_skill", { skill }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteSkill(name: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_skill", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listDirEntries(root: string, max: number | null) : Promise<Result<MentionFileEntry[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_dir_entries", { root, max }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async readMentionFile(root: string, relPath: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("read_mention_file", { root, relPath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listScratchNotes() : Promise<Result<ScratchNoteSummary[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_scratch_notes") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getScratchNote(id: string) : Promise<Result<ScratchNote, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_scratch_note", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createScratchNote(name: string, content: string | null) : Promise<Result<ScratchNote, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_scratch_note", { name, content }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 自动保存入口；内容未变化时不更新时间。force_version 为 true 时不论间隔都先保存一个版本
+ */
+async saveScratchNote(id: string, content: string, forceVersion: boolean | null) : Promise<Result<ScratchNote, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_scratch_note", { id, content, forceVersion }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async renameScratchNote(id: string, name: string) : Promise<Result<ScratchNote, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("rename_scratch_note", { id, name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteScratchNote(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_scratch_note", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 历史版本，最新的在前
+ */
+async listScratchNoteVersions(id: string) : Promise<Result<ScratchNoteVersion[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_scratch_note_versions", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getScratchNoteVersion(id: string, versionId: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_scratch_note_version", { id, versionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 用历史版本覆盖当前内容；覆盖前先把当前内容存为一个版本，恢复本身可以撤销
+ */
+async restoreScratchNoteVersion(id: string, versionId: string) : Promise<Result<ScratchNote, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("restore_scratch_note_version", { id, versionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 全文搜索：按空白拆分关键词，忽略大小写，名称或内容包含全部关键词的笔记才返回
+ */
+async searchScratchNotes(query: string) : Promise<Result<ScratchSearchResult[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("search_scratch_notes", { query }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async workflowList() : Promise<Result<Workflow[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("workflow_list") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async workflowGet(id: string) : Promise<Result<Workflow, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("workflow_get", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async workflowSave(workflow: Workflow) : Promise<Result<Workflow, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("workflow_save", { workflow }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async workflowDelete(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("workflow_delete", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async workflowRunNow(id: string) : Promise<Result<WorkflowRun, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("workflow_run_now", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async workflowSetEnabled(id: string, enabled: boolean) : Promise<Result<Workflow, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("workflow_set_enabled", { id, enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async mirrorJobList() : Promise<Result<MirrorJob[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mirror_job_list") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async mirrorJobSave(job: MirrorJob) : Promise<Result<MirrorJob, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mirror_job_save", { job }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async mirrorJobDelete(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mirror_job_delete", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async mirrorJobSetEnabled(id: string, enabled: boolean) : Promise<Result<MirrorJob, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mirror_job_set_enabled", { id, enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async mirrorJobRunNow(id: string) : Promise<Result<MirrorRun, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mirror_job_run_now", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listDivergenceWatches() : Promise<Result<DivergenceWatch[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_divergence_watches") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 开启或更新项目的分歧监控
+ */
+async saveDivergenceWatch(input: DivergenceWatchInput) : Promise<Result<DivergenceWatch, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_divergence_watch", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteDivergenceWatch(projectId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_divergence_watch", { projectId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 稍后提醒：minutes 分钟内不再推送该项目的分歧提醒，0 表示取消
+ */
+async snoozeDivergenceAlert(projectId: string, minutes: number) : Promise<Result<DivergenceWatch, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("snooze_divergence_alert", { projectId, minutes }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 最近一次检查结果，落后最多的排在前面
+ */
+async getDivergenceStatus() : Promise<Result<DivergenceStatus[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_divergence_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 立即拉取并检查一个项目（不要求已开启监控，未开启时按默认阈值判断）
+ */
+async checkDivergenceNow(projectId: string) : Promise<Result<DivergenceStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("check_divergence_now", { projectId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async chatBridgeTest(relay: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("chat_bridge_test", { relay }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getLabels() : Promise<Result<string[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_labels") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveLabels(labels: string[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_labels", { labels }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async addLabel(label: string) : Promise<Result<string[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_label", { label }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async removeLabel(label: string) : Promise<Result<string[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("remove_label", { label }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getCategories() : Promise<Result<string[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_categories") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveCategories(categories: string[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_categories", { categories }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async addCategory(category: string) : Promise<Result<string[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_category", { category }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async removeCategory(category: string) : Promise<Result<string[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("remove_category", { category }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getEditors() : Promise<Result<EditorConfig[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_editors") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async addEditor(input: EditorInput) : Promise<Result<EditorConfig[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_editor", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateEditor(id: string, input: EditorInput) : Promise<Result<EditorConfig[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_editor", { id, input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async removeEditor(id: string) : Promise<Result<EditorConfig[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("remove_editor", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setDefaultEditor(id: string) : Promise<Result<EditorConfig[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_default_editor", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getTerminalConfig() : Promise<Result<TerminalConfig, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_terminal_config") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveTerminalConfig(input: TerminalInput) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_terminal_config", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 返回给前端的设置：去掉只读模式密码哈希
+ */
+async getAppSettings() : Promise<Result<AppSettings, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_app_settings") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveAppSettings(input: AppSettingsInput) : Promise<Result<AppSettings, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_app_settings", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getReadOnlyStatus() : Promise<Result<ReadOnlyStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_read_only_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 开关只读模式。设置过密码时，关闭只读或修改密码都要先验证 password；
+ * 开启时可通过 new_password 设置新密码（传空字符串清除密码）
+ */
+async setReadOnlyMode(enabled: boolean, password: string | null, newPassword: string | null) : Promise<Result<ReadOnlyStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_read_only_mode", { enabled, password, newPassword }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getUiState() : Promise<Result<UiState, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_ui_state") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveUiState(input: UiStateInput) : Promise<Result<UiState, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_ui_state", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getNotifications() : Promise<Result<Notification[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_notifications") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveNotifications(notifications: Notification[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_notifications", { notifications }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async addNotification(input: NotificationInput) : Promise<Result<Notification[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_notification", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async removeNotification(id: string) : Promise<Result<Notification[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("remove_notification", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async clearNotifications() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clear_notifications") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 标记单条通知已读；read 为 false 时标记为未读
+ */
+async markNotificationRead(id: string, read: boolean | null) : Promise<Result<Notification[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mark_notification_read", { id, read }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async markAllNotificationsRead() : Promise<Result<Notification[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mark_all_notifications_read") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 执行通知上的第 index 个操作并标记已读。
+ * navigate 由前端跳转，这里只返回操作本身
+ */
+async runNotificationAction(id: string, index: number) : Promise<Result<NotificationAction, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("run_notification_action", { id, index }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 临时开启免打扰 minutes 分钟（如演示前）；None 或 0 时立即结束手动免打扰，时段规则不受影响
+ */
+async setDoNotDisturb(minutes: number | null) : Promise<Result<NotificationRouting, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_do_not_disturb", { minutes }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getAppShortcuts() : Promise<Result<AppShortcutBinding[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_app_shortcuts") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveAppShortcuts(shortcuts: AppShortcutBinding[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_app_shortcuts", { shortcuts }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getRecommendedTemplate() : Promise<Result<string | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_recommended_template") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveRecommendedTemplate(content: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_recommended_template", { content }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async resetRecommendedTemplate() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reset_recommended_template") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 获取 Claude 配置模板目录。
+ * 
+ * 顺序：远程 GitHub → 本地缓存（上次成功拉取的"本地历史"）→ 内置默认。
+ * 任何错误（网络异常、文件不存在、解析失败）都静默回退，**永远返回 Ok**，保证调用方每次都能拿到内容。
+ * 远程拉取成功时会同步刷新本地缓存。
+ */
+async getClaudeConfigTemplates() : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_claude_config_templates") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getAiProviders() : Promise<Result<AiProviderConfig[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_ai_providers") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveAiProviders(providers: AiProviderConfig[]) : Promise<Result<AiProviderConfig[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_ai_providers", { providers }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getSensitiveFilePatterns() : Promise<Result<string[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_sensitive_file_patterns") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveSensitiveFilePatterns(patterns: string[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_sensitive_file_patterns", { patterns }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getResumes() : Promise<Result<JsonValue, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_resumes") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveResumes(data: JsonValue) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_resumes", { data }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async exportResumeDocx(resume: JsonValue, filePath: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_resume_docx", { resume, filePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async llmProxyRequest(request: LlmProxyRequest) : Promise<Result<LlmProxyResponse, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("llm_proxy_request", { request }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async runResumeDeepAgent(request: RunResumeDeepAgentRequest) : Promise<Result<JsonValue, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("run_resume_deep_agent", { request }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async generateResumeFromKnowledge(request: GenerateResumeFromKnowledgeRequest) : Promise<Result<JsonValue, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("generate_resume_from_knowledge", { request }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async generateResumeFragment(request: GenerateResumeFragmentRequest) : Promise<Result<JsonValue, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("generate_resume_fragment", { request }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async cancelResumeDeepAgent(requestId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_resume_deep_agent", { requestId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getResumeAgentRuns(projectId: string) : Promise<Result<JsonValue, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_resume_agent_runs", { projectId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async readResumeAgentArtifact(projectId: string, artifactId: string) : Promise<Result<JsonValue, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("read_resume_agent_artifact", { projectId, artifactId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getResumeAgentPromptConfig() : Promise<Result<JsonValue, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_resume_agent_prompt_config") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveResumeAgentPromptConfig(config: JsonValue) : Promise<Result<JsonValue, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_resume_agent_prompt_config", { config }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async resetResumeAgentPromptConfig() : Promise<Result<JsonValue, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reset_resume_agent_prompt_config") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async loadResumeAgentBackground(projectId: string) : Promise<Result<string | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("load_resume_agent_background", { projectId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async saveResumeAgentBackground(projectId: string, content: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_resume_agent_background", { projectId, content }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listResumeAgentBackground() : Promise<Result<JsonValue, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_resume_agent_background") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteResumeAgentBackground(projectId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_resume_agent_background", { projectId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteResumeAgentRuns(projectId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_resume_agent_runs", { projectId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 获取启动耗时报告
+ */
+async getStartupReport() : Promise<Result<StartupReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_startup_report") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 获取上次退出时记录的运行状态
+ */
+async getResumeState() : Promise<Result<ResumeState | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_resume_state") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 本次启动自动恢复的结果（未开启或没有需要恢复的服务时为 None）
+ */
+async getResumeSummary() : Promise<Result<ResumeSummary | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_resume_summary") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 前端触发优雅退出（与托盘「退出」相同）
+ */
+async requestAppExit() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("request_app_exit") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 打开（或聚焦已存在的）工具窗口，返回窗口 label
+ */
+async openToolWindow(input: ToolWindowInput) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_tool_window", { input }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 关闭工具窗口（主窗口不允许通过此命令关闭）
+ */
+async closeToolWindow(label: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("close_tool_window", { label }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 列出当前打开的工具窗口
+ */
+async listToolWindows() : Promise<Result<ToolWindowInfo[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_tool_windows") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * 调用方窗口取回自己的上下文；主窗口返回 None
+ */
+async getToolWindowContext() : Promise<Result<ToolWindowInfo | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_tool_window_context") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async registerGlobalShortcuts(shortcuts: GlobalShortcutBinding[]) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("register_global_shortcuts", { shortcuts }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async unregisterAllGlobalShortcuts() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("unregister_all_global_shortcuts") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+}
+}
+
+/** user-defined events **/
+
+
+
+/** user-defined constants **/
+
+
+
+/** user-defined types **/
+
+/**
+ * 跨项目活跃度对比结果
+ */
+export type ActivityComparison = { 
+/**
+ * 窗口起止日期（含），YYYY-MM-DD
+ */
+since: string; until: string; projects: ProjectActivity[] }
+export type AiModelConfig = { id: string; model: string; enabled: boolean; isDefault: boolean; thinking: boolean; stream?: boolean; vision?: boolean }
+export type AiProviderConfig = { id: string; name: string; providerType: string; presetKey: string | null; baseUrl: string; apiKey: string | null; enabled: boolean; isDefaultProvider: boolean; models: AiModelConfig[] }
+/**
+ * API 鉴权配置
+ */
+export type ApiAuthConfig = { type: "none" } | { type: "bearer"; token: string } | { type: "basic"; username: string; password: string } | { type: "apiKey"; header: string; value: string } | { type: "session"; login_url: string; login_method: string; credentials_json: string; token_json_path: string | null; inject_as: SessionInject }
+/**
+ * API 对话会话（与 ChatSession 字段大致对齐，额外携带绑定的接口集合）
+ */
+export type ApiChatSession = { id: string; title: string; providerId: string; modelId: string; createdAt: string; updatedAt: string; messages: ChatMessage[]; selectedEndpointIds?: string[]; systemPrompt?: string | null; temperature?: number | null; maxTokens?: number | null; topP?: number | null; frequencyPenalty?: number | null; presencePenalty?: number | null; pinned?: boolean | null }
+export type ApiChatSessionSummary = { id: string; title: string; providerId: string; modelId: string; createdAt: string; updatedAt: string; messageCount: number; endpointCount: number; pinned?: boolean | null }
+/**
+ * 单个 API 接口
+ */
+export type ApiEndpoint = { id: string; name: string; description?: string | null; 
+/**
+ * None 表示独立接口（不属于任何组）
+ */
+groupId?: string | null; 
+/**
+ * GET / POST / PUT / PATCH / DELETE
+ */
+method: string; 
+/**
+ * 可含 {path_param}；或绝对 URL（此时忽略组 baseUrl）
+ */
+url: string; headers?: ([string, string])[]; 
+/**
+ * 覆盖组鉴权
+ */
+authOverride?: ApiAuthConfig | null; 
+/**
+ * 喂给 LLM function-calling 的 JSON Schema
+ */
+paramsSchema: JsonValue; 
+/**
+ * 响应截断字节数（默认 8192）
+ */
+responseTrimBytes?: number | null; 
+/**
+ * 单接口请求超时（毫秒），缺省 30000
+ */
+timeoutMs?: number | null; createdAt: string; updatedAt: string }
+/**
+ * 单次接口调用的结构化返回：给前端做"调用链"展示用
  */
 export type ApiExecutionResult = { status: number; method: string; url: string; elapsedMs: number; totalBytes: number; truncated: boolean; body: string }
 /**
- * 接口分组（同一项目共享鉴权）
+ * 接口分组（同一项目共享鉴权）
+ */
+export type ApiGroup = { id: string; name: string; description?: string | null; baseUrl: string; auth: ApiAuthConfig; createdAt: string; updatedAt: string }
+export type ApiToolsBundle = { 
+/**
+ * OpenAI function tools 数组
+ */
+tools: JsonValue[]; 
+/**
+ * tool_name -> endpoint_id
+ */
+toolNameMap: Partial<{ [key in string]: string }> }
+export type AppPaths = { data_dir: string; config_dir: string; cache_dir: string; log_dir: string; install_dir: string }
+export type AppProfile = { id: string; name: string; 
+/**
+ * 档案内可见的项目；None 为全部项目
+ */
+projectIds: string[] | null; createdAt: string; updatedAt: string }
+export type AppProfileList = { 
+/**
+ * 当前档案 id，None 为默认档案
+ */
+active: string | null; profiles: AppProfile[] }
+/**
+ * 应用设置
+ */
+export type AppSettings = { theme: string; view_mode: string; sidebar_collapsed: boolean; scan_depth: number; auto_update?: boolean; chat_history_dir?: string | null; 
+/**
+ * 是否启用 OpenClaw 聊天桥接（外部聊天平台通过中继 relay 入站）
+ */
+chat_bridge_enabled?: boolean; 
+/**
+ * 中继服务 base URL，例如 https://relay.example.com
+ */
+openclaw_relay_endpoint?: string | null; 
+/**
+ * 桥接时用于回复的 provider id（复用 ai_providers 配置）
+ */
+bridge_provider_id?: string | null; 
+/**
+ * 桥接时使用的 model id
+ */
+bridge_model_id?: string | null; 
+/**
+ * 客户端标识，默认 codeshelf-<hostname>
+ */
+bridge_client_id?: string | null; 
+/**
+ * 是否启用内置 MCP HTTP 网关
+ */
+mcp_gateway_enabled?: boolean; 
+/**
+ * MCP HTTP 网关监听地址
+ */
+mcp_gateway_host?: string; 
+/**
+ * MCP HTTP 网关监听端口
+ */
+mcp_gateway_port?: number; 
+/**
+ * MCP Gateway 客户端访问密钥
+ */
+mcp_gateway_keys?: McpGatewayKey[]; 
+/**
+ * macOS：是否在 Dock 显示应用图标（false=纯菜单栏应用，true=Dock + 菜单栏）
+ */
+show_dock_icon?: boolean; 
+/**
+ * 启动时是否自动恢复上次退出时仍在运行的服务/转发/隧道/下载/会话
+ */
+auto_resume_services?: boolean; 
+/**
+ * 出站 HTTP 代理（下载、HTTP 请求工具、AI 接口等共用）
+ */
+proxy?: ProxySettings; 
+/**
+ * 是否接收浏览器交接的下载（codeshelf-download:// 链接 + 本地 HTTP 端点）
+ */
+download_handoff_enabled?: boolean; 
+/**
+ * 本地下载交接端点端口（仅监听 127.0.0.1）
+ */
+download_handoff_port?: number; 
+/**
+ * 本地端点访问令牌，首次启用时生成
+ */
+download_handoff_token?: string | null; 
+/**
+ * 是否开启 Prometheus 指标端点（仅监听 127.0.0.1）
+ */
+metrics_enabled?: boolean; 
+/**
+ * 指标端点端口
+ */
+metrics_port?: number; 
+/**
+ * 只读模式：拦截所有会修改数据或系统状态的命令（演示机、共享工作站）
+ */
+read_only_mode?: boolean; 
+/**
+ * 解除只读模式所需密码的 argon2id 哈希（PHC 字符串），为空表示无需密码
+ */
+read_only_password_hash?: string | null; 
+/**
+ * 是否记录本地使用统计（只存本地数据库，不上传）
+ */
+usage_stats_enabled?: boolean; 
+/**
+ * 下载完成后自动调用系统病毒扫描器（Windows Defender / clamscan）
+ */
+download_virus_scan?: boolean; 
+/**
+ * 发现威胁后的处理："none" 仅记录 | "quarantine" 移入隔离目录 | "delete" 删除
+ */
+download_virus_action?: string; 
+/**
+ * 使用电池且电量低于阈值时暂停较重的后台任务（统计刷新、定时工作流、镜像任务）
+ */
+power_saver_enabled?: boolean; 
+/**
+ * 低电量阈值（百分比）
+ */
+power_saver_threshold?: number; 
+/**
+ * Git 提交身份模板（工作 / 个人等），按远程地址规则匹配项目
+ */
+git_identities?: GitIdentityProfile[]; 
+/**
+ * 提交前检查身份是否与远程地址规则匹配的模板一致，不一致时拒绝并提示
+ */
+git_identity_guard?: boolean; 
+/**
+ * 全局 git 可执行文件覆盖（命令行，如 `D:\PortableGit\bin\git.exe`、`wsl git`）；为空时用 PATH 中的 git
+ */
+git_executable?: string | null; 
+/**
+ * 托盘图标角标的数据来源："none" | "servers" 运行中的服务 | "downloads" 进行中的下载 | "notifications" 未查看的通知
+ */
+tray_badge_source?: string; 
+/**
+ * 首页仪表盘显示的小部件，按顺序排列
+ */
+dashboard_widgets?: DashboardWidget[]; 
+/**
+ * 空闲资源自动停止策略
+ */
+idle_policy?: IdlePolicySettings; 
+/**
+ * 扫描目录：项目目录丢失时在这些目录下查找被移动 / 改名的仓库
+ */
+scan_roots?: string[]; 
+/**
+ * 通知保留策略
+ */
+notification_retention?: NotificationRetention; 
+/**
+ * 通知路由与免打扰
+ */
+notification_routing?: NotificationRouting; 
+/**
+ * 统计缓存保留的天数，更早的按日提交数自动清理；0 表示不清理
+ */
+stats_retention_days?: number; 
+/**
+ * 更新通道："stable" | "beta"
+ */
+update_channel?: string; 
+/**
+ * 推迟安装的版本，到期前自动检查不再提示该版本
+ */
+update_deferral?: UpdateDeferral | null }
+export type AppSettingsInput = { theme: string | null; view_mode: string | null; sidebar_collapsed: boolean | null; scan_depth: number | null; auto_update: boolean | null; chat_history_dir: string | null; chat_bridge_enabled: boolean | null; openclaw_relay_endpoint: string | null; bridge_provider_id: string | null; bridge_model_id: string | null; bridge_client_id: string | null; mcp_gateway_enabled: boolean | null; mcp_gateway_host: string | null; mcp_gateway_port: number | null; mcp_gateway_keys: McpGatewayKey[] | null; show_dock_icon: boolean | null; auto_resume_services: boolean | null; proxy: ProxySettings | null; download_handoff_enabled: boolean | null; download_handoff_port: number | null; metrics_enabled: boolean | null; metrics_port: number | null; usage_stats_enabled: boolean | null; download_virus_scan: boolean | null; download_virus_action: string | null; power_saver_enabled: boolean | null; power_saver_threshold: number | null; git_identities: GitIdentityProfile[] | null; git_identity_guard: boolean | null; 
+/**
+ * 空串表示清除覆盖
+ */
+git_executable: string | null; tray_badge_source: string | null; idle_policy: IdlePolicySettings | null; scan_roots: string[] | null; notification_retention: NotificationRetention | null; notification_routing: NotificationRouting | null; stats_retention_days: number | null }
+export type AppShortcutBinding = { id: string; label: string; description: string; keys: string; defaultKeys: string; enabled: boolean; global?: boolean }
+export type ApplyPatchResult = { text: string; hunksApplied: number; 
+/**
+ * 不在 hunk 头所写行号、靠查找上下文应用的 hunk 数
+ */
+hunksOffset: number }
+/**
+ * 当前 app 二进制 + 实际宿主机的架构状态
+ * 
+ * macOS 关键场景：Intel 二进制跑在 Apple Silicon 上时（Rosetta 翻译），
+ * 编译时的 ARCH = "x86_64" 但宿主其实是 "aarch64"。Tauri 更新器只会
+ * 按二进制架构去 latest.json 里查链接，永远拿不到匹配宿主的那一份。
+ */
+export type ArchStatus = { 
+/**
+ * 当前进程的编译目标架构（rust target arch）："x86_64" / "aarch64" / ...
+ */
+binaryArch: string; 
+/**
+ * 宿主机的真实物理架构
+ */
+hostArch: string; 
+/**
+ * 操作系统："macos" / "windows" / "linux"
+ */
+os: string; 
+/**
+ * 是否运行在 Rosetta 翻译层下（macOS 专有；其他平台恒 false）
+ */
+isRosetta: boolean; 
+/**
+ * binary_arch != host_arch 即视为不匹配
+ */
+mismatch: boolean }
+/**
+ * 自动发送配置
+ */
+export type AutoSendConfig = { 
+/**
+ * 是否启用
+ */
+enabled?: boolean; 
+/**
+ * 发送间隔（毫秒）
+ */
+intervalMs?: number; 
+/**
+ * 发送模式
+ */
+mode?: AutoSendMode; 
+/**
+ * 固定内容
+ */
+fixedData?: string; 
+/**
+ * CSV/多行数据
+ */
+csvData?: string; 
+/**
+ * 模板内容
+ */
+template?: string; 
+/**
+ * HTTP URL
+ */
+httpUrl?: string; 
+/**
+ * HTTP 请求方法
+ */
+httpMethod?: string; 
+/**
+ * HTTP 请求头（JSON 格式）
+ */
+httpHeaders?: string; 
+/**
+ * HTTP 请求体
+ */
+httpBody?: string; 
+/**
+ * HTTP JSON 路径（用于提取 JSON 响应中的特定字段）
+ */
+httpJsonPath?: string }
+/**
+ * 自动发送模式
+ */
+export type AutoSendMode = "fixed" | "csv" | "template" | "http"
+/**
+ * 两个分支的对比结果（head 相对 base，与 PR 对比页一致）
+ */
+export type BranchComparison = { base: string; head: string; mergeBase: string | null; 
+/**
+ * head 独有的提交数
+ */
+ahead: number; 
+/**
+ * base 独有的提交数
+ */
+behind: number; aheadCommits: CommitInfo[]; behindCommits: CommitInfo[]; 
+/**
+ * 提交数超过上限时列表被截断
+ */
+truncated: boolean; 
+/**
+ * 从 merge-base 到 head 的文件改动
+ */
+files: BranchDiffFile[]; insertions: number; deletions: number }
+/**
+ * compare_branches 中单个文件的改动
+ */
+export type BranchDiffFile = { path: string; 
+/**
+ * 重命名 / 复制前的路径
+ */
+oldPath: string | null; 
+/**
+ * git 的状态字母：A / M / D / R / C / T
+ */
+status: string; insertions: number; deletions: number; binary: boolean }
+export type BranchInfo = { name: string; is_current: boolean; is_remote: boolean; upstream: string | null }
+export type BulkItemResult = { projectId: string; projectName: string; projectPath: string; 
+/**
+ * "pending" | "success" | "failed" | "skipped"
+ */
+status: string; message: string | null; durationMs: number | null }
+export type BulkOperation = { id: string; action: string; 
+/**
+ * addLabel / removeLabel 使用的标签
+ */
+label: string | null; 
+/**
+ * "running" | "completed" | "cancelled" | "interrupted"
+ */
+status: string; items: BulkItemResult[]; createdAt: string; updatedAt: string; finishedAt: string | null }
+export type CachedDashboardData = { stats: DashboardStats; heatmap_data: DailyActivity[]; recent_commits: RecentCommit[] }
+export type CertificateInfo = { subject: string; issuer: string; commonName: string | null; 
+/**
+ * DNS / IP / email / URI 形式的备用名称
+ */
+sans: string[]; serial: string; 
+/**
+ * RFC 3339
+ */
+notBefore: string; notAfter: string; 
+/**
+ * 距到期的天数，已过期为负数
+ */
+daysRemaining: number; expired: boolean; 
+/**
+ * 尚未生效
+ */
+notYetValid: boolean; 
+/**
+ * warn_days 内到期（未过期）
+ */
+expiringSoon: boolean; selfSigned: boolean; isCa: boolean; signatureAlgorithm: string; 
+/**
+ * 如 "RSA 2048"、"EC P-256"
+ */
+publicKey: string; sha256Fingerprint: string; sha1Fingerprint: string }
+export type ChatMessage = { id: string; role: string; content: string; createdAt: string; tokens: number | null; thinking: boolean | null; thinkingContent: string | null; edited?: boolean | null; toolCalls?: JsonValue | null; toolCallId?: string | null; toolName?: string | null; toolStatus?: number | null; toolMethod?: string | null; toolUrl?: string | null; toolElapsedMs?: number | null; toolBodyBytes?: number | null; toolTruncated?: boolean | null; 
+/**
+ * 接口对话内联可重试错误气泡标记（assistant 角色 + error=true）
+ */
+error?: boolean | null; 
+/**
+ * 工具调用进行中占位（"调用中…"）；执行完成后清除并回填结果
+ */
+toolPending?: boolean | null; attachments?: JsonValue | null }
+export type ChatSession = { id: string; title: string; providerId: string; modelId: string; createdAt: string; updatedAt: string; messages: ChatMessage[]; systemPrompt?: string | null; temperature?: number | null; maxTokens?: number | null; topP?: number | null; frequencyPenalty?: number | null; presencePenalty?: number | null; pinned?: boolean | null; 
+/**
+ * 会话级"始终允许"的工具名列表（用户在授权弹窗勾"始终允许"后写入）
+ */
+allowedTools?: string[] | null; 
+/**
+ * 会话启用的工具集合；缺省使用全局默认
+ */
+enabledTools?: string[] | null; 
+/**
+ * 工具（Read/Write/Bash 等）允许操作的根目录；缺省则禁止写入/执行类工具
+ */
+allowedCwd?: string | null; 
+/**
+ * 是否在本会话里启用 MCP gateway 工具（从已注册的接口里取）。缺省 true。
+ * 仅当 MCP gateway 已启动时实际生效。
+ */
+useMcpGatewayTools?: boolean | null; 
+/**
+ * 当前生效的上下文压缩版本号（如 "v2"）。None 表示从未压缩
+ */
+currentCompactionVersion?: string | null }
+export type ChatSessionSummary = { id: string; title: string; providerId: string; modelId: string; createdAt: string; updatedAt: string; messageCount: number; pinned?: boolean | null }
+export type ChatStreamMessage = { role: string; 
+/**
+ * string 或 OpenAI 多模态内容数组
+ */
+content: JsonValue; reasoning_content?: string | null; tool_calls?: JsonValue[] | null; tool_call_id?: string | null; name?: string | null }
+export type ChatStreamRequest = { requestId: string; providerId: string; model: string; baseUrl: string; apiKey: string | null; thinking: boolean | null; stream: boolean | null; messages: ChatStreamMessage[]; temperature: number | null; maxTokens: number | null; topP: number | null; frequencyPenalty: number | null; presencePenalty: number | null; tools?: JsonValue[] | null; toolChoice?: JsonValue | null }
+export type ChatTask = { id: string; subject: string; description: string; activeForm: string | null; status: string; createdAt: string; updatedAt: string }
+/**
+ * Claude Code 安装信息
+ */
+export type ClaudeCodeInfo = { env_type: EnvType; env_name: string; installed: boolean; version: string | null; path: string | null; config_dir: string | null; config_files: ConfigFileInfo[] }
+/**
+ * 配置选项
+ */
+export type ClaudeConfigSelectOption = { label: string; value: JsonValue }
+/**
+ * Claude 快捷配置选项
+ */
+export type ClaudeQuickConfig = { id: string; name: string; description: string; category: string; configKey: string; valueType: string; defaultValue: JsonValue; options: ClaudeConfigSelectOption[] | null; placeholder: string | null; allowEmpty: boolean | null }
+export type ClipboardEntry = { id: string; content: string; contentPreview: string; timestamp: number; pinned: boolean; charCount: number; note?: string | null }
+export type ClipboardSettings = { enabled: boolean; maxItems: number; monitorIntervalMs: number }
+/**
+ * CODEOWNERS 中的一条规则
+ */
+export type CodeOwnerRule = { pattern: string; owners: string[]; 
+/**
+ * 在 CODEOWNERS 中的行号
+ */
+line: number }
+/**
+ * 代码归属图
+ */
+export type CodeOwnersReport = { 
+/**
+ * 找到的 CODEOWNERS 文件（相对路径）
+ */
+codeownersFile: string | null; rules: CodeOwnerRule[]; directories: DirectoryOwnership[] }
+export type CommandUsage = { command: string; feature: string; count: number; lastUsedDay: string }
+export type CommitFileChange = { insertions: number; deletions: number; filename: string }
+export type CommitIndexStatus = { projects: number; commits: number; lastIndexedAt: string | null }
+export type CommitInfo = { hash: string; short_hash: string; message: string; author: string; email: string; date: string; body?: string | null; files_changed?: number | null; insertions?: number | null; deletions?: number | null; refs?: string[] | null; parent_hashes?: string[] | null }
+export type CommitSearchFilters = { 
+/**
+ * 只搜索这些项目（为空搜索全部）
+ */
+projectPaths?: string[] | null; 
+/**
+ * 作者名或邮箱（模糊匹配）
+ */
+author?: string | null; 
+/**
+ * 起始日期，如 "2024-01-01"
+ */
+since?: string | null; 
+/**
+ * 截止日期（含当天）
+ */
+until?: string | null; limit?: number | null }
+export type CommitSearchHit = { projectPath: string; projectName: string | null; hash: string; shortHash: string; message: string; body: string | null; author: string; email: string; date: string }
+/**
+ * search_commits 的附加过滤条件
+ */
+export type CommitSearchOptions = { 
+/**
+ * 起始日期，git 可识别的格式（如 "2024-01-01"、"2 weeks ago"）
+ */
+since: string | null; until: string | null; 
+/**
+ * 搜索所有分支与标签（--all）
+ */
+allBranches?: boolean; 
+/**
+ * 只看改动了这些路径的提交，支持 pathspec 通配（如 "src/**"）
+ */
+paths?: string[]; 
+/**
+ * 内容搜索：增删了该字符串的提交（-S）
+ */
+pickaxe: string | null; 
+/**
+ * pickaxe 按正则匹配改动行（-G）
+ */
+pickaxeRegex?: boolean }
+export type CompactionContent = { version: string; content: string; meta: CompactionMeta | null }
+/**
+ * 压缩目录的索引文件 <sessionId>/compactions/index.json
+ */
+export type CompactionIndex = { 
+/**
+ * 当前生效版本号；None 表示无版本（不会出现在已写过的索引里）
+ */
+current?: string | null; versions: CompactionMeta[] }
+/**
+ * 单次压缩的元数据；正文 markdown 单独存放在 <sessionId>/compactions/<version>.md
+ */
+export type CompactionMeta = { 
+/**
+ * 版本号，如 "v1"、"v2"
+ */
+version: string; createdAt: string; 
+/**
+ * 压缩时摘要覆盖的原始消息条数（即被压缩的早期消息条数）
+ */
+sourceMessageCount: number; 
+/**
+ * 压缩时保留的尾部条数
+ */
+tailKept: number; 
+/**
+ * 摘要字符数
+ */
+charCount: number; 
+/**
+ * 生成摘要使用的模型（可选，便于排查）
+ */
+model?: string | null }
+/**
+ * 合规扫描报告
+ */
+export type ComplianceReport = { projectPath: string; 
+/**
+ * 项目自身声明的许可证
+ */
+projectLicense: string | null; dependencies: DependencyLicense[]; 
+/**
+ * license -> 依赖数量
+ */
+licenseSummary: Partial<{ [key in string]: number }>; secrets: SecretFinding[]; filesScanned: number; 
+/**
+ * 命中数超过上限被截断
+ */
+truncated: boolean; scannedAt: string }
+/**
+ * 配置文件信息
+ */
+export type ConfigFileInfo = { name: string; path: string; exists: boolean; size: number; modified: string | null; description: string }
+/**
+ * 保存的配置档案
+ */
+export type ConfigProfile = { id: string; name: string; description: string | null; settings: JsonValue; created_at: string; updated_at: string }
+/**
+ * 返回给前端的确认请求
+ */
+export type ConfirmRequest = { token: string; action: string; 
+/**
+ * 一句话说明将要发生什么
+ */
+summary: string; 
+/**
+ * 受影响的具体对象（路径、进程等），逐行展示
+ */
+details: string[]; expiresInSecs: number }
+export type ConflictFileContent = { file: string; base: string | null; current: string | null; incoming: string | null; worktree: string | null }
+/**
+ * 连接的客户端信息（服务器模式）
+ */
+export type ConnectedClient = { id: string; addr: string; connectedAt: number; lastActivity: number; bytesSent: number; bytesReceived: number; 
+/**
+ * 最近 10 秒的平均发送速率（字节/秒）
+ */
+sendRate?: number; 
+/**
+ * 最近 10 秒的平均接收速率（字节/秒）
+ */
+recvRate?: number }
+/**
+ * 对比窗口内某个贡献者的提交数
+ */
+export type ContributorActivity = { name: string; email: string; commits: number }
+export type CreateApiChatSessionInput = { title: string | null; providerId: string; modelId: string; selectedEndpointIds?: string[] }
+export type CreateChatSessionInput = { title: string | null; providerId: string; modelId: string }
+export type CreateProjectInput = { name: string; path: string; tags: string[] | null; labels: string[] | null }
+export type CursorPosition = { x: number; y: number }
+export type DailyActivity = { date: string; count: number }
+export type DashboardStats = { total_projects: number; today_commits: number; week_commits: number; unpushed_commits: number; unmerged_branches: number; last_updated: string }
+/**
+ * 仪表盘小部件
+ */
+export type DashboardWidget = { 
+/**
+ * "stats" | "heatmap" | "recent_commits" | "running_services" | "watched_ports"
+ */
+kind: string; 
+/**
+ * "small" | "medium" | "large"
+ */
+size?: string }
+/**
+ * 未启用的小部件对应字段为 None
+ */
+export type DashboardWidgetData = { widgets: DashboardWidget[]; stats: DashboardStats | null; heatmap: DailyActivity[] | null; recentCommits: RecentCommit[] | null; 
+/**
+ * 运行中的静态服务
+ */
+runningServices: ServerConfig[] | null; watchedPorts: PortWatch[] | null }
+/**
+ * 数据格式
+ */
+export type DataFormat = "text" | "hex" | "base64"
+/**
+ * 依赖许可证
+ */
+export type DependencyLicense = { name: string; version: string | null; 
+/**
+ * "npm" | "cargo" | "pypi"
+ */
+ecosystem: string; 
+/**
+ * 未能读取时为 None
+ */
+license: string | null; copyleft: boolean }
+/**
+ * 部署目标配置（前端 type 区分：ssh / s3 / rsync）
+ */
+export type DeployDestination = { type: "ssh"; host: string; port?: number; user?: string; auth: SshAuthMethod; remoteDir: string } | { type: "s3"; endpoint?: string; region: string; bucket: string; prefix?: string; accessKeyId: string; secretAccessKey: string; pathStyle?: boolean } | { type: "rsync"; target: string; sshPort?: number | null; keyPath?: string | null; delete?: boolean }
+export type DeployRun = { id: string; projectId: string; targetId: string; targetName: string; 
+/**
+ * "ssh" | "s3" | "rsync"
+ */
+kind: string; 
+/**
+ * "running" | "success" | "failed"
+ */
+status: string; files: number; bytes: number; message: string | null; startedAt: string; finishedAt: string | null }
+export type DeployTarget = { id: string; projectId: string; name: string; 
+/**
+ * 要上传的目录，相对项目目录
+ */
+sourceDir: string; destination: DeployDestination; createdAt: string; updatedAt: string }
+export type DeployTargetInput = { 
+/**
+ * 为空时新建
+ */
+id: string | null; projectId: string; name: string; sourceDir: string | null; destination: DeployDestination }
+export type DiffHunk = { 
+/**
+ * 原始 `@@ -a,b +c,d @@ ...` 行
+ */
+header: string; oldStart: number; oldLines: number; newStart: number; newLines: number; lines: DiffLine[] }
+export type DiffLine = { 
+/**
+ * "context" | "add" | "remove"
+ */
+kind: string; content: string; oldLine: number | null; newLine: number | null; 
+/**
+ * 词级差异片段，仅 diff_text 的 word 模式填充
+ */
+segments?: DiffSegment[] | null }
+/**
+ * 比较结果（目录/文件比较与版本比较共用）
+ */
+export type DiffResult = { files: FileDiff[]; added: number; removed: number; 
+/**
+ * 修改 + 重命名
+ */
+changed: number; insertions: number; deletions: number; 
+/**
+ * 行数超过上限时后续文件只保留文件级信息（hunks 为空）
+ */
+truncated: boolean }
+export type DiffSegment = { 
+/**
+ * "equal" | "add" | "remove"
+ */
+kind: string; text: string }
+/**
+ * 目录的归属信息
+ */
+export type DirectoryOwnership = { 
+/**
+ * 相对仓库根目录，根目录下的文件为 "."
+ */
+path: string; 
+/**
+ * CODEOWNERS 声明的负责人
+ */
+owners: string[]; 
+/**
+ * blame 行数最多的贡献者（按行数降序）
+ */
+contributors: OwnerContributor[]; fileCount: number; 
+/**
+ * 参与 blame 的文件数（每个目录抽样）
+ */
+sampledFiles: number }
+/**
+ * 最近一次检查结果
+ */
+export type DivergenceStatus = { projectId: string; projectName: string; branch: string | null; upstream: string | null; ahead: number; behind: number; threshold: number; 
+/**
+ * 落后数达到阈值
+ */
+exceeded: boolean; snoozed: boolean; checkedAt: string; 
+/**
+ * 拉取失败或没有上游分支时的说明
+ */
+error: string | null }
+export type DivergenceWatch = { projectId: string; enabled: boolean; 
+/**
+ * 落后上游超过多少个提交时提醒
+ */
+behindThreshold: number; 
+/**
+ * 定时拉取间隔（分钟）
+ */
+fetchIntervalMinutes: number; 
+/**
+ * 稍后提醒截止时间（RFC3339），之前不推送
+ */
+snoozedUntil?: string | null; lastFetchedAt?: string | null }
+export type DivergenceWatchInput = { projectId: string; enabled: boolean | null; behindThreshold: number | null; fetchIntervalMinutes: number | null }
+/**
+ * 免打扰时段，按本地时间
+ */
+export type DndSchedule = { 
+/**
+ * 生效的星期，0 = 周日；为空表示每天
+ */
+days?: number[]; 
+/**
+ * "HH:MM"；结束早于开始表示跨午夜
+ */
+start: string; end: string }
+export type DockerAiGenerateInput = { projectPath: string; dockerfilePath: string | null; imageName: string | null; providerId: string | null; modelId: string | null }
+export type DockerAiGenerateOutput = { content: string; providerName: string; modelName: string }
+export type DockerBuildInput = { projectPath: string; dockerfilePath: string; imageName: string; tag: string | null; noCache: boolean | null }
+export type DockerCommandResult = { success: boolean; stdout: string; stderr: string; command: string }
+export type DockerContainerInfo = { id: string; image: string; names: string; status: string; 
+/**
+ * docker ps 的 State 字段：running / exited / paused / created / restarting / dead / removing
+ */
+state: string; ports: string; composeProject: string | null; composeService: string | null; composeWorkingDir: string | null; composeConfigFiles: string[] }
+export type DockerImageInfo = { id: string; repository: string; tag: string; size: string; createdSince: string }
+export type DockerRunInput = { image: string; containerName: string | null; ports: string[] | null; env: string[] | null; volumes: string[] | null; network: string | null; restart: string | null; user: string | null; workdir: string | null; command: string | null; privileged: boolean | null; readOnly: boolean | null; extraArgs: string[] | null }
+export type DockerStatus = { available: boolean; version: string | null; error: string | null }
+/**
+ * 检测到的文档生成器
+ */
+export type DocsGenerator = { 
+/**
+ * "mkdocs" | "docusaurus" | "mdbook" | "vitepress"
+ */
+kind: string; name: string; 
+/**
+ * 配置文件路径
+ */
+configPath: string; 
+/**
+ * 运行开发服务器的目录
+ */
+workingDir: string; 
+/**
+ * 开发服务器命令，{port} 为占位符
+ */
+devCommand: string; 
+/**
+ * 构建产物目录
+ */
+buildDir: string; 
+/**
+ * 构建产物是否已存在（可直接静态预览）
+ */
+buildReady: boolean }
+/**
+ * 运行中的文档预览
+ */
+export type DocsPreview = { projectPath: string; kind: string; 
+/**
+ * "dev" | "static"
+ */
+mode: string; url: string; 
+/**
+ * dev 模式的进程 PID
+ */
+pid: number | null; 
+/**
+ * static 模式使用的静态服务 id
+ */
+serverId: string | null; startedAt: string }
+export type DoctorCheck = { 
+/**
+ * "git" | "node" | "wsl" | "long_paths" | "data_dir" | "ports"
+ */
+id: string; title: string; 
+/**
+ * "ok" | "info" | "warning" | "error" | "skipped"
+ */
+status: string; detail: string; fixes: DoctorFix[] }
+export type DoctorFix = { label: string; 
+/**
+ * "url" 在浏览器打开 | "command" 需在终端执行的命令（前端提供复制）
+ */
+kind: string; value: string }
+export type DoctorReport = { os: string; checks: DoctorCheck[]; 
+/**
+ * 是否存在 error 级别的检查项
+ */
+hasErrors: boolean; checkedAt: string }
+/**
+ * dry-run 检查结果
+ */
+export type DocumentCheck = { document: string; label: string; path: string; exists: boolean; fileVersion: number; currentVersion: number; 
+/**
+ * 读取时将执行的步骤
+ */
+pendingSteps: string[]; 
+/**
+ * 迁移后能否解析为当前类型
+ */
+valid: boolean; error: string | null }
+/**
+ * 解析错误位置，行列均从 1 开始
+ */
+export type DocumentError = { message: string; line: number | null; column: number | null }
+export type DocumentFormat = "json" | "yaml" | "toml"
+/**
+ * 传入 output_path 时结果写入文件，text 为 None
+ */
+export type DocumentOutput = { text: string | null; writtenTo: string | null; bytes: number }
+export type DocumentValidation = { valid: boolean; error: DocumentError | null }
+/**
+ * 下载配置
+ */
+export type DownloadConfig = { url: string; saveDir: string | null; fileName: string | null; maxRetries: number | null; headers?: Partial<{ [key in string]: string }> | null; 
+/**
+ * 备用镜像地址：下载前测速选择最快的源，下载中停滞时切换到下一个
+ */
+mirrors?: string[]; 
+/**
+ * 超过该秒数没有收到数据视为停滞（重试或切换源），默认 20，0 表示不检测；
+ * 服务器本身很慢时可调大
+ */
+stallTimeoutSecs?: number | null }
+/**
+ * 按天汇总
+ */
+export type DownloadDailyStats = { 
+/**
+ * YYYY-MM-DD（本地时间）
+ */
+date: string; downloads: number; bytes: number; averageSpeed: number }
+export type DownloadHandoffStatus = { enabled: boolean; running: boolean; port: number; 
+/**
+ * 端点地址（运行中才有）
+ */
+endpoint: string | null; token: string | null; 
+/**
+ * 系统是否已把 codeshelf-download:// 交给本应用
+ */
+protocolRegistered: boolean }
+/**
+ * 一次完成的下载
+ */
+export type DownloadHistoryEntry = { id: string; taskId: string; fileName: string; url: string; 
+/**
+ * 实际下载所用源的主机名
+ */
+host: string; 
+/**
+ * 文件大小（字节）
+ */
+size: number; 
+/**
+ * 本次传输的字节数
+ */
+transferred: number; durationMs: number; 
+/**
+ * 平均速度（字节/秒）
+ */
+averageSpeed: number; 
+/**
+ * 完成时间（毫秒时间戳）
+ */
+completedAt: number }
+export type DownloadHistoryStats = { totalDownloads: number; totalBytes: number; averageSpeed: number; 
+/**
+ * 按日期升序
+ */
+daily: DownloadDailyStats[]; 
+/**
+ * 按下载量降序
+ */
+hosts: DownloadHostStats[] }
+/**
+ * 按主机汇总
+ */
+export type DownloadHostStats = { host: string; downloads: number; bytes: number; averageSpeed: number; 
+/**
+ * 多次下载平均速度明显低于整体水平
+ */
+slow: boolean }
+/**
+ * 批量导入中无效的一行
+ */
+export type DownloadImportError = { 
+/**
+ * 行号（从 1 开始）
+ */
+line: number; text: string; error: string }
+export type DownloadImportResult = { 
+/**
+ * 已创建的下载任务 id（按行顺序）
+ */
+taskIds: string[]; errors: DownloadImportError[]; 
+/**
+ * 与前面行重复而跳过的 URL 数
+ */
+duplicates: number }
+/**
+ * 下载文件的病毒扫描结果
+ */
+export type DownloadScanResult = { 
+/**
+ * 使用的扫描器，如 "Windows Defender" / "ClamAV"
+ */
+scanner: string | null; 
+/**
+ * "scanning" | "clean" | "infected" | "error" | "unavailable"
+ */
+verdict: string; detail: string | null; 
+/**
+ * 发现威胁后执行的动作（"deleted" / "quarantined: <路径>"）
+ */
+action: string | null; scannedAt: string }
+/**
+ * 下载任务
+ */
+export type DownloadTask = { id: string; url: string; savePath: string; fileName: string; totalSize: number; downloadedSize: number; status: string; speed: number; error: string | null; createdAt: string; updatedAt: string; 
+/**
+ * 附加请求头（Referer、Cookie 等，浏览器交接时携带），恢复下载时沿用
+ */
+headers?: Partial<{ [key in string]: string }>; 
+/**
+ * 备用镜像地址（不含 url 本身）
+ */
+mirrors?: string[]; 
+/**
+ * 当前正在使用的下载源，未使用镜像时为 None
+ */
+activeUrl?: string | null; 
+/**
+ * 下载后病毒扫描结果
+ */
+scan?: DownloadScanResult | null; 
+/**
+ * 需要代码托管平台令牌时记录令牌所属主机，令牌本身在发请求时再读取，不随任务保存
+ */
+authHost?: string | null; 
+/**
+ * 停滞判定秒数，None 为默认 20 秒，0 表示不检测
+ */
+stallTimeoutSecs?: number | null }
+/**
+ * 编辑器配置
+ */
+export type EditorConfig = { id: string; name: string; path: string; icon: string | null; is_default: boolean }
+export type EditorInput = { name: string; path: string; icon: string | null; is_default: boolean | null }
+/**
+ * 提权端口中继
+ */
+export type ElevatedRelay = { listenHost: string; listenPort: number; 
+/**
+ * 转发到 127.0.0.1 的端口
+ */
+targetPort: number; startedAt: string }
+/**
+ * 环境类型
+ */
+export type EnvType = "host" | "wsl"
+export type FaviconResult = { files: GeneratedFile[]; 
+/**
+ * 放进 <head> 的 link 标签
+ */
+html: string }
+export type FeatureUsage = { feature: string; count: number; activeDays: number }
+export type FileDiff = { 
+/**
+ * "added" | "removed" | "modified" | "renamed"
+ */
+status: string; 
+/**
+ * 相对比较根目录的路径（新增文件为 None）
+ */
+oldPath: string | null; 
+/**
+ * 删除文件为 None
+ */
+newPath: string | null; binary: boolean; insertions: number; deletions: number; hunks: DiffHunk[] }
+export type FileHash = { algorithm: string; 
+/**
+ * 小写十六进制
+ */
+value: string }
+export type FileHashResult = { path: string; size: number; hashes: FileHash[]; 
+/**
+ * 与期望值一致的算法；未传期望值或都不一致时为 None
+ */
+matchedAlgorithm: string | null; 
+/**
+ * 未传期望值时为 None
+ */
+matches: boolean | null; elapsedMs: number }
+export type FolderSyncEntry = { 
+/**
+ * 相对路径，以 / 分隔；目录以 / 结尾
+ */
+relativePath: string; 
+/**
+ * "create" | "update" | "delete"
+ */
+action: string; bytes: number }
+export type FolderSyncError = { relativePath: string; message: string }
+export type FolderSyncOptions = { 
+/**
+ * 非空时只同步匹配的文件
+ */
+include: string[]; 
+/**
+ * 匹配的文件和目录都跳过
+ */
+exclude: string[]; 
+/**
+ * 删除目标中源目录没有的文件和目录
+ */
+deleteExtraneous: boolean }
+export type FolderSyncReport = { dryRun: boolean; 
+/**
+ * dry_run 时为将要执行的变更，否则为已成功执行的变更
+ */
+entries: FolderSyncEntry[]; created: number; updated: number; deleted: number; unchanged: number; 
+/**
+ * 需要（或已经）复制的字节数
+ */
+bytesCopied: number; errors: FolderSyncError[]; elapsedMs: number }
+/**
+ * 转发规则
+ */
+export type ForwardRule = { id: string; name: string; localPort: number; remoteHost: string; remotePort: number; 
+/**
+ * 文档路径，如 "doc.html" 或 "swagger-ui.html"，用于快速访问
+ */
+docPath: string | null; 
+/**
+ * 目标类型："tcp"（默认，remote_host:remote_port）| "unix"（Unix 域套接字）|
+ * "pipe"（Windows 命名管道）。后两者只监听 127.0.0.1
+ */
+targetKind?: string; 
+/**
+ * target_kind 为 unix / pipe 时的路径，如 /var/run/docker.sock、\\.\pipe\docker_engine
+ */
+socketPath?: string | null; 
+/**
+ * 备用目标，主目标（remote_host:remote_port）不可用时依次尝试
+ */
+backupTargets?: ForwardTarget[]; 
+/**
+ * 目标选择策略："failover"（默认，优先主目标）| "round_robin"（在健康目标间轮询）
+ */
+balance?: string; 
+/**
+ * 主动健康检查间隔（秒），None 时只在连接失败时标记不可用
+ */
+healthCheckIntervalSecs?: number | null; status?: string; connections?: number; bytesIn?: number; bytesOut?: number; createdAt: string }
+/**
+ * 创建转发规则的输入
+ */
+export type ForwardRuleInput = { name: string; localPort: number; remoteHost: string; remotePort: number; 
+/**
+ * 文档路径，如 "doc.html" 或 "swagger-ui.html"
+ */
+docPath: string | null; targetKind?: string | null; socketPath?: string | null; backupTargets?: ForwardTarget[] | null; balance?: string | null; healthCheckIntervalSecs?: number | null }
+/**
+ * 转发统计
+ */
+export type ForwardStats = { ruleId: string; connections: number; bytesIn: number; bytesOut: number; 
+/**
+ * 各目标健康状态（未运行时为空）
+ */
+targets?: ForwardTargetStatus[] }
+/**
+ * 转发目标
+ */
+export type ForwardTarget = { host: string; port: number }
+/**
+ * 转发目标的运行时健康状态
+ */
+export type ForwardTargetStatus = { address: string; healthy: boolean; 
+/**
+ * 最近一次检查或连接失败的原因
+ */
+lastError: string | null }
+export type GenerateResumeFragmentRequest = { requestId: string; provider: AiProviderConfig; jobDirection: NodeJobDirection; jdKeywords: string[]; tone: NodeTone; knowledgeDocs: KnowledgeInput[]; fragment: JsonValue }
+export type GenerateResumeFromKnowledgeRequest = { requestId: string; provider: AiProviderConfig; jobDirection: NodeJobDirection; jdKeywords: string[]; tone: NodeTone; knowledgeDocs: KnowledgeInput[]; promptConfig?: JsonValue | null }
+export type GeneratedAsset = { 
+/**
+ * "png" | "svg"
+ */
+format: string; 
+/**
+ * svg 为文本，png 为 base64
+ */
+content: string; width: number; height: number; 
+/**
+ * 指定了输出路径时写入的文件
+ */
+path: string | null }
+export type GeneratedFile = { path: string; bytes: number }
+export type GitHookInfo = { 
+/**
+ * hook 名，如 "pre-commit"
+ */
+name: string; 
+/**
+ * 是否存在脚本（启用或停用都算）
+ */
+installed: boolean; enabled: boolean; 
+/**
+ * 由模板库安装时记录的模板 id
+ */
+templateId: string | null }
+/**
+ * Git hook 脚本模板（保存在应用数据目录）
+ */
+export type GitHookTemplate = { id: string; name: string; 
+/**
+ * 目标 hook 名
+ */
+hook: string; description?: string; content: string; builtin?: boolean }
+/**
+ * 仓库当前生效的提交身份
+ */
+export type GitIdentity = { userName: string | null; email: string | null; 
+/**
+ * "local" 仓库配置 | "global" 继承全局配置 | "none" 未配置
+ */
+source: string }
+/**
+ * 提交身份检查结果
+ */
+export type GitIdentityCheck = { current: GitIdentity; 
+/**
+ * 按远程地址规则匹配到的身份模板
+ */
+expected: GitIdentityProfile | null; mismatch: boolean; 
+/**
+ * 不一致的原因说明
+ */
+reason: string | null }
+/**
+ * Git 提交身份模板
+ */
+export type GitIdentityProfile = { id: string; 
+/**
+ * 显示名，如 "工作"、"个人"
+ */
+name: string; userName: string; email: string; 
+/**
+ * 远程地址规则，如 "github.com/my-company"、"gitlab.corp.com"、"*.corp.com"；
+ * 项目任一远程地址匹配时应使用此身份
+ */
+remotePatterns?: string[] }
+/**
+ * 仓库的写操作队列
+ */
+export type GitQueueState = { path: string; 
+/**
+ * 正在执行的操作
+ */
+running: string | null; 
+/**
+ * 排队中的操作，按先后顺序（第 N 个即排在第 N 位）
+ */
+waiting: string[] }
+export type GitRepo = { path: string; name: string; 
+/**
+ * "normal" | "worktree" | "bare"
+ */
+kind?: string }
+export type GitStatus = { branch: string; is_clean: boolean; staged: string[]; unstaged: string[]; untracked: string[]; conflicted: string[]; ahead: number; behind: number; 
+/**
+ * 使用 LFS 的仓库中工作区仍是指针文件（未拉取）的数量；按仓库缓存 1 分钟，拉取 LFS 后更新
+ */
+lfs_missing?: number; 
+/**
+ * HEAD 未指向分支（此时 branch 为短哈希，变基中为原分支名）
+ */
+detached?: boolean; 
+/**
+ * 进行中的合并 / 变基 / 拣选等操作
+ */
+operation?: RepoOperation | null }
+export type GlobalShortcutBinding = { id: string; keys: string }
+/**
+ * 热力图导出结果
+ */
+export type HeatmapExport = { 
+/**
+ * "csv" | "json" | "svg" | "png"
+ */
+format: string; 
+/**
+ * 文本格式为文件内容，png 为 base64
+ */
+content: string; 
+/**
+ * 指定了输出路径时写入的文件
+ */
+path: string | null; since: string; until: string; totalCommits: number }
+/**
+ * 返回给前端的令牌信息（令牌本身打码）
+ */
+export type HostingTokenInfo = { host: string; provider: string; maskedToken: string }
+export type HttpBenchErrorCount = { message: string; count: number }
+export type HttpBenchLatency = { minMs: number; meanMs: number; p50Ms: number; p90Ms: number; p99Ms: number; maxMs: number }
+/**
+ * 压测报告；进度事件与最终结果共用，finished 区分
+ */
+export type HttpBenchReport = { jobId: string; url: string; method: string; concurrency: number; elapsedMs: number; 
+/**
+ * 收到响应的请求数（含 4xx / 5xx）
+ */
+requests: number; 
+/**
+ * 连接失败、超时等未收到响应的请求数
+ */
+errors: number; 
+/**
+ * 状态码 >= 400 的响应数
+ */
+nonSuccess: number; 
+/**
+ * 整体平均每秒请求数
+ */
+rps: number; 
+/**
+ * 最近一个进度间隔内的每秒请求数
+ */
+currentRps: number; bytesReceived: number; 
+/**
+ * 仅统计收到响应的请求；没有响应时为 None
+ */
+latency: HttpBenchLatency | null; statusCounts: HttpBenchStatusCount[]; errorKinds: HttpBenchErrorCount[]; finished: boolean }
+export type HttpBenchStatusCount = { status: number; count: number }
+/**
+ * 单次 HTTP 检查记录
+ */
+export type HttpCheckRecord = { time: string; status: string; statusCode: number | null; latencyMs: number | null; error: string | null }
+/**
+ * HTTP 请求配置
+ */
+export type HttpFetchConfig = { url: string; 
+/**
+ * 任意 HTTP 方法，默认 GET
+ */
+method?: string | null; headers?: Partial<{ [key in string]: string }> | null; body?: string | null; jsonPath?: string | null; 
+/**
+ * 校验 TLS 证书，默认 false（本地调试常用自签名证书）
+ */
+verifyTls?: boolean | null; 
+/**
+ * 跟随重定向，默认 true
+ */
+followRedirects?: boolean | null; 
+/**
+ * 最多跟随的重定向次数，默认 10
+ */
+maxRedirects?: number | null; 
+/**
+ * 整个请求的超时（毫秒），默认 10000
+ */
+timeoutMs?: number | null }
+/**
+ * HTTP 请求的完整响应
+ */
+export type HttpFetchResponse = { status: number; statusText: string; 
+/**
+ * "HTTP/1.1" / "HTTP/2" ...
+ */
+version: string; headers: HttpHeader[]; body: string; 
+/**
+ * 响应体字节数（截断前）
+ */
+bodySize: number; 
+/**
+ * 响应体超过上限被截断
+ */
+bodyTruncated: boolean; 
+/**
+ * 指定 jsonPath 且响应为 JSON 时的提取结果
+ */
+extracted: string | null; finalUrl: string; remoteAddr: string | null; redirects: HttpRedirectHop[]; timing: HttpTiming; 
+/**
+ * 含重定向在内的总耗时
+ */
+totalMs: number }
+/**
+ * 响应头（按原始顺序，允许重复）
+ */
+export type HttpHeader = { name: string; value: string }
+/**
+ * HTTP(S) 健康检查监控项
+ */
+export type HttpMonitor = { id: string; name: string; url: string; method?: string; 
+/**
+ * 期望状态码，None 表示任意 2xx
+ */
+expectedStatus?: number | null; 
+/**
+ * 响应体需包含的子串
+ */
+bodyContains?: string | null; intervalSecs?: number; timeoutMs?: number; enabled?: boolean; status?: string; lastError?: string | null; lastChecked?: string | null; lastChange?: string | null; history?: HttpCheckRecord[]; createdAt: string }
+/**
+ * 创建/编辑 HTTP 监控的输入
+ */
+export type HttpMonitorInput = { name: string; url: string; method: string | null; expectedStatus: number | null; bodyContains: string | null; intervalSecs: number | null; timeoutMs: number | null; enabled: boolean | null }
+/**
+ * 基于历史记录的可用率与延迟分位数
+ */
+export type HttpMonitorStats = { monitorId: string; checks: number; uptimePercent: number; latencyP50: number | null; latencyP90: number | null; latencyP99: number | null }
+/**
+ * 重定向链中的一跳
+ */
+export type HttpRedirectHop = { url: string; status: number; location: string; durationMs: number }
+/**
+ * 最后一跳的耗时阶段（毫秒，均从该跳开始计时的累计值，同 curl 的 time_*）
+ */
+export type HttpTiming = { 
+/**
+ * DNS 解析完成
+ */
+dnsMs: number; 
+/**
+ * TCP 连接建立（单独的探测连接测得）
+ */
+connectMs: number; 
+/**
+ * 收到响应头（首字节）
+ */
+ttfbMs: number; 
+/**
+ * 响应体读取完成
+ */
+totalMs: number }
+/**
+ * 空闲资源自动停止策略；时长为 None 或 0 时不启用对应规则
+ */
+export type IdlePolicySettings = { 
+/**
+ * 静态服务连续多少分钟没有请求后停止
+ */
+serverIdleMinutes?: number | null; 
+/**
+ * 转发规则连续多少分钟没有连接后停止
+ */
+forwardIdleMinutes?: number | null; 
+/**
+ * 按流量计费的网络下暂停下载，切回不计费网络后自动恢复
+ */
+pauseDownloadsOnMetered?: boolean; 
+/**
+ * Netcat 会话多少小时没有收发数据后关闭
+ */
+netcatIdleHours?: number | null; 
+/**
+ * 不受自动停止影响的服务 / 转发规则 / 下载 / 会话 id
+ */
+exemptIds?: string[] }
+export type IdlePolicyStatus = { 
+/**
+ * 当前网络是否按流量计费
+ */
+metered: boolean; 
+/**
+ * 因计费网络被暂停、等待自动恢复的下载任务 id
+ */
+pausedDownloads: string[] }
+export type ImageOptimizeOptions = { 
+/**
+ * JPEG 质量 1-100；只作用于 JPEG，WebP 始终无损重编码
+ */
+jpegQuality: number; 
+/**
+ * oxipng 等级 0-6
+ */
+pngLevel: number; 
+/**
+ * 去掉 PNG 中不影响显示的元数据块
+ */
+stripMetadata: boolean; 
+/**
+ * 包含子目录，输出时保留相对路径
+ */
+recursive: boolean }
+export type ImageOptimizeReport = { outputDir: string; files: OptimizedImage[]; 
+/**
+ * 以下统计不含失败的文件
+ */
+originalBytes: number; optimizedBytes: number; failed: number; elapsedMs: number }
+export type JsonPathMatch = { 
+/**
+ * 规范化路径，如 $.items[0]['display name']
+ */
+path: string; 
+/**
+ * 匹配值的 JSON 文本
+ */
+value: string }
+export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
+export type KillAndRetryResult = { 
+/**
+ * 不为空时尚未执行，用户确认后带上其中的 token 再调用一次
+ */
+confirm: ConfirmRequest | null; 
+/**
+ * 重新启动后的服务 URL（转发为 None）
+ */
+url: string | null }
+export type KnowledgeInput = { projectId: string; projectName: string; projectPath: string; content: string }
+/**
+ * 外部程序启动后立即异常退出（路径错误、参数不支持等）时返回的警告
+ */
+export type LaunchWarning = { program: string; exitCode: number | null; stderr: string; message: string }
+/**
+ * 仓库的 Git LFS 使用情况
+ */
+export type LfsInfo = { 
+/**
+ * .gitattributes 中声明了 filter=lfs
+ */
+usesLfs: boolean; 
+/**
+ * 本机是否安装 git-lfs
+ */
+installed: boolean; version: string | null; trackedPatterns: string[]; objectCount: number; 
+/**
+ * 字节；旧版 git-lfs 无法取得大小时为 0
+ */
+totalSize: number; 
+/**
+ * 工作区仍是指针文件的对象
+ */
+missingCount: number; missingSize: number }
+export type LlmProxyHeader = { name: string; value: string }
+export type LlmProxyRequest = { method: string; url: string; headers: LlmProxyHeader[]; body: string | null }
+export type LlmProxyResponse = { status: number; status_text: string; headers: LlmProxyHeader[]; body: string }
+/**
+ * 供前端"以 MCP 客户端身份"调用本地网关时使用：
+ * - url：HTTP 端点（含 scheme/host/port），如果网关未运行则不返回
+ * - api_key：从 mcp_gateway_keys 里挑第一个有效 key。若 keys 为空（网关无鉴权）则为 None
+ */
+export type McpGatewayInternalEndpoint = { url: string; apiKey: string | null }
+export type McpGatewayKey = { id: string; name: string; key: string; enabled?: boolean; createdAt: string; expiresAt?: string | null }
+export type McpGatewayStatus = { running: boolean; url: string | null; host: string | null; port: number | null; startedAt: string | null }
+export type MentionFileEntry = { path: string; isDir: boolean }
+/**
+ * 消息方向
+ */
+export type MessageDirection = "sent" | "received"
+export type MetricsStatus = { enabled: boolean; running: boolean; port: number; 
+/**
+ * 抓取地址（运行中才有）
+ */
+endpoint: string | null }
+export type MigrationLogEntry = { timestamp: string; document: string; label: string; fromVersion: number; toVersion: number; 
+/**
+ * 已执行步骤的描述
+ */
+steps: string[]; backupFile: string | null; success: boolean; error: string | null }
+export type MirrorJob = { id: string; name: string; mode: string; 
+/**
+ * push 模式为远程 URL（支持 `{name}` 占位），bundle 模式为备份目录
+ */
+target: string; projectPaths: string[]; 
+/**
+ * 执行间隔（分钟），0 表示仅手动执行
+ */
+intervalMinutes?: number; 
+/**
+ * bundle 模式下每个项目保留的备份份数
+ */
+keepBundles?: number; enabled?: boolean; 
+/**
+ * 低电量省电模式下仍然执行
+ */
+allowOnBattery?: boolean; history?: MirrorRun[]; createdAt: string; updatedAt: string }
+export type MirrorProjectResult = { path: string; success: boolean; message: string }
+export type MirrorRun = { startedAt: string; finishedAt: string; status: string; results: MirrorProjectResult[] }
+/**
+ * Modbus 请求
+ */
+export type ModbusRequest = { host: string; 
+/**
+ * 默认 502
+ */
+port: number | null; 
+/**
+ * 从站地址，默认 1
+ */
+unitId: number | null; 
+/**
+ * 功能码：1 / 2 / 3 / 4 / 5 / 6 / 15 / 16
+ */
+function: number; 
+/**
+ * 起始地址（0 基）
+ */
+address: number; 
+/**
+ * 读取数量（读功能码使用）
+ */
+quantity: number | null; 
+/**
+ * 写入的值：线圈用 0 / 1，寄存器用 0-65535
+ */
+values?: number[]; timeoutMs: number | null }
+/**
+ * Modbus 响应
+ */
+export type ModbusResponse = { function: number; 
+/**
+ * 读取到的值（线圈为 0 / 1）；写操作返回设备回显的值
+ */
+values: number[]; requestHex: string; responseHex: string; elapsedMs: number }
+/**
+ * MQTT 连接配置
+ */
+export type MqttConnectInput = { host: string; port: number; name: string | null; 
+/**
+ * 为空时自动生成
+ */
+clientId: string | null; username: string | null; password: string | null; 
+/**
+ * 心跳间隔（秒），默认 60
+ */
+keepAliveSecs: number | null; 
+/**
+ * 默认 true
+ */
+cleanSession: boolean | null; timeoutMs: number | null }
+export type MqttMessage = { id: string; sessionId: string; direction: MessageDirection; topic: string; 
+/**
+ * UTF-8 文本，非文本内容显示为十六进制
+ */
+payload: string; size: number; qos: number; retain: boolean; timestamp: number }
+export type MqttSession = { id: string; name: string; host: string; port: number; clientId: string; status: SessionStatus; errorMessage: string | null; connectedAt: number; 
+/**
+ * 已订阅主题 -> QoS
+ */
+subscriptions: Partial<{ [key in string]: number }>; messagesSent: number; messagesReceived: number }
+/**
+ * 分组批量操作中单个会话的结果
+ */
+export type NetcatGroupOpResult = { sessionId: string; name: string; success: boolean; error: string | null }
+/**
+ * 分组汇总统计
+ */
+export type NetcatGroupStats = { group: string; sessionCount: number; 
+/**
+ * 已连接 / 监听中的会话数
+ */
+activeCount: number; errorCount: number; clientCount: number; bytesSent: number; bytesReceived: number; messageCount: number; lastActivity: number | null }
+/**
+ * 会话导入结果
+ */
+export type NetcatImportResult = { sessions: NetcatSession[]; payloadsImported: number; 
+/**
+ * 报文库中已有同名报文而跳过的数量
+ */
+payloadsSkipped: number }
+/**
+ * 会话流量日志计数
+ */
+export type NetcatLogStats = { sessionId: string; mode: TrafficLogMode; logPayload: boolean; 
+/**
+ * 已写入日志的收发行数
+ */
+loggedLines: number; 
+/**
+ * 因关闭或抽样被丢弃的行数
+ */
+droppedLines: number }
+/**
+ * 消息记录
+ */
+export type NetcatMessage = { id: string; sessionId: string; direction: MessageDirection; data: string; format: DataFormat; size: number; timestamp: number; 
+/**
+ * 来源/目标客户端（服务器模式）
+ */
+clientId: string | null; clientAddr: string | null }
+/**
+ * 消息筛选条件（netcat_get_messages），各条件同时满足
+ */
+export type NetcatMessageFilter = { direction?: MessageDirection | null; 
+/**
+ * 客户端 ID 或地址
+ */
+client?: string | null; 
+/**
+ * 起始时间戳（毫秒，含）
+ */
+since?: number | null; 
+/**
+ * 截止时间戳（毫秒，含）
+ */
+until?: number | null; 
+/**
+ * 内容包含的文本（不区分大小写）
+ */
+text?: string | null; 
+/**
+ * 内容包含的字节序列，十六进制如 "48 65" / "0x4865"
+ */
+hex?: string | null }
+/**
+ * 报文库中的报文
+ */
+export type NetcatPayload = { id: string; name: string; 
+/**
+ * 报文内容，可包含 {{timestamp}} 等模板变量
+ */
+data: string; format: DataFormat; description?: string | null; createdAt: number; updatedAt: number }
+/**
+ * 新建 / 更新报文的输入
+ */
+export type NetcatPayloadInput = { name: string; data: string; format: DataFormat; description: string | null }
+/**
+ * 会话配置
+ */
+export type NetcatSession = { id: string; name: string; protocol: Protocol; mode: SessionMode; host: string; port: number; status: SessionStatus; autoReconnect: boolean; timeoutMs: number; createdAt: number; connectedAt: number | null; lastActivity: number | null; bytesSent: number; bytesReceived: number; messageCount: number; errorMessage: string | null; 
+/**
+ * 本地地址（客户端模式连接后分配的本地 IP:PORT）
+ */
+localAddr: string | null; 
+/**
+ * 连接的客户端数量（仅服务器模式）
+ */
+clientCount: number; 
+/**
+ * 自动发送配置
+ */
+autoSend?: AutoSendConfig; 
+/**
+ * 所属分组（如 "设备农场"），用于批量启停与广播
+ */
+group?: string | null; 
+/**
+ * 流量日志模式
+ */
+logMode?: TrafficLogMode; 
+/**
+ * 是否以 debug 级别输出报文内容
+ */
+logPayload?: boolean; 
+/**
+ * 保留的消息条数上限
  */
-export type ApiGroup = { id: string; name: string; description?: string | null; baseUrl: string; auth: ApiAuthConfig; createdAt: string; updatedAt: string }
-export type ApiToolsBundle = { 
+historyLimit?: number; 
 /**
- * OpenAI function tools 数组
+ * 达到上限后的处理方式
  */
-tools: JsonValue[]; 
+overflowPolicy?: OverflowPolicy; 
 /**
- * tool_name -> endpoint_id
+ * 因达到上限（stopCapturing）已停止记录消息，清空历史后恢复
  */
-toolNameMap: Partial<{ [key in string]: string }> }
-export type AppPaths = { data_dir: string; config_dir: string; cache_dir: string; log_dir: string; install_dir: string }
+captureStopped?: boolean; 
 /**
- * 应用设置
+ * 最近 10 秒的平均发送速率（字节/秒），查询时计算
  */
-export type AppSettings = { theme: string; view_mode: string; sidebar_collapsed: boolean; scan_depth: number; auto_update?: boolean; chat_history_dir?: string | null; 
+sendRate?: number; 
 /**
- * 是否启用 OpenClaw 聊天桥接（外部聊天平台通过中继 relay 入站）
+ * 最近 10 秒的平均接收速率（字节/秒）
  */
-chat_bridge_enabled?: boolean; 
+recvRate?: number }
 /**
- * 中继服务 base URL，例如 https://relay.example.com
+ * 创建会话的输入参数
  */
-openclaw_relay_endpoint?: string | null; 
+export type NetcatSessionInput = { protocol: Protocol; mode: SessionMode; host: string; port: number; name: string | null; autoReconnect: boolean | null; timeoutMs: number | null; 
 /**
- * 桥接时用于回复的 provider id（复用 ai_providers 配置）
+ * 所属分组
  */
-bridge_provider_id?: string | null; 
+group?: string | null }
 /**
- * 桥接时使用的 model id
+ * 网卡 URL 信息（用于多网卡环境下生成多个 QR）
  */
-bridge_model_id?: string | null; 
+export type NetworkUrl = { interface: string; ip: string; url: string }
+export type NodeJobDirection = "backend" | "frontend" | "fullstack"
+export type NodeTone = "professional" | "concise"
 /**
- * 客户端标识，默认 codeshelf-<hostname>
+ * 单条通知
  */
-bridge_client_id?: string | null; 
+export type Notification = { id: string; 
 /**
- * 是否启用内置 MCP HTTP 网关
+ * 严重程度："info" | "success" | "warning" | "error"
  */
-mcp_gateway_enabled?: boolean; 
+notification_type: string; title: string; message?: string; created_at: string; 
 /**
- * MCP HTTP 网关监听地址
+ * 来源类别，见 notification_routing::CATEGORIES；前端发起的通知为空
  */
-mcp_gateway_host?: string; 
+category?: string; read?: boolean; 
 /**
- * MCP HTTP 网关监听端口
+ * 通知上的操作按钮
  */
-mcp_gateway_port?: number; 
+actions?: NotificationAction[] }
 /**
- * MCP Gateway 客户端访问密钥
+ * 通知操作
  */
-mcp_gateway_keys?: McpGatewayKey[];
+export type NotificationAction = { 
 /**
- * macOS：是否在 Dock 显示应用图标（false=纯菜单栏应用，true=Dock + 菜单栏）
+ * "open_folder" | "open_url" | "retry_download" | "navigate"
  */
-show_dock_icon?: boolean }
-export type AppSettingsInput = { theme: string | null; view_mode: string | null; sidebar_collapsed: boolean | null; scan_depth: number | null; auto_update: boolean | null; chat_history_dir: string | null; chat_bridge_enabled: boolean | null; openclaw_relay_endpoint: string | null; bridge_provider_id: string | null; bridge_model_id: string | null; bridge_client_id: string | null; mcp_gateway_enabled: boolean | null; mcp_gateway_host: string | null; mcp_gateway_port: number | null; mcp_gateway_keys: McpGatewayKey[] | null; show_dock_icon: boolean | null }
-export type AppShortcutBinding = { id: string; label: string; description: string; keys: string; defaultKeys: string; enabled: boolean; global?: boolean }
+kind: string; label: string; 
 /**
- * 当前 app 二进制 + 实际宿主机的架构状态
- * 
- * macOS 关键场景：Intel 二进制跑在 Apple Silicon 上时（Rosetta 翻译），
- * 编译时的 ARCH = "x86_64" 但宿主其实是 "aarch64"。Tauri 更新器只会
- * 按二进制架构去 latest.json 里查链接，永远拿不到匹配宿主的那一份。
+ * 文件夹路径、地址、下载任务 id 或工具箱工具名
  */
-export type ArchStatus = { 
+target: string }
+export type NotificationInput = { 
 /**
- * 当前进程的编译目标架构（rust target arch）："x86_64" / "aarch64" / ...
+ * 严重程度："info" | "success" | "warning" | "error"，其它值按 info 处理
  */
-binaryArch: string; 
+notification_type: string; title: string; message?: string; 
 /**
- * 宿主机的真实物理架构
+ * 来源类别，前端发起的通知留空
  */
-hostArch: string; 
+category?: string; actions?: NotificationAction[] }
 /**
- * 操作系统："macos" / "windows" / "linux"
+ * 通知保留策略：超过条数先删已读的旧通知；超过天数的删除（可保留未读）
  */
-os: string; 
+export type NotificationRetention = { maxCount?: number; 
 /**
- * 是否运行在 Rosetta 翻译层下（macOS 专有；其他平台恒 false）
+ * 0 表示不按时间删除
  */
-isRosetta: boolean; 
+maxAgeDays?: number; 
 /**
- * binary_arch != host_arch 即视为不匹配
+ * 未读通知不按时间删除
  */
-mismatch: boolean }
+keepUnread?: boolean }
+export type NotificationRoute = { 
 /**
- * 自动发送配置
+ * "download" | "server" | "monitor" | "git" | "system"
  */
-export type AutoSendConfig = { 
+category: string; 
 /**
- * 是否启用
+ * 写入通知中心
  */
-enabled?: boolean; 
+inApp?: boolean; 
 /**
- * 发送间隔（毫秒）
+ * 弹出系统通知
  */
-intervalMs?: number; 
+osToast?: boolean; 
 /**
- * 发送模式
+ * 计入托盘角标
  */
-mode?: AutoSendMode; 
+trayBadge?: boolean }
 /**
- * 固定内容
+ * 通知路由：按类别决定去向，免打扰期间不弹系统通知
  */
-fixedData?: string; 
+export type NotificationRouting = { 
 /**
- * CSV/多行数据
+ * 按类别覆盖去向；未列出的类别全部开启
  */
-csvData?: string; 
+routes?: NotificationRoute[]; 
 /**
- * 模板内容
+ * 免打扰时段
  */
-template?: string; 
+dndSchedules?: DndSchedule[]; 
 /**
- * HTTP URL
+ * 手动免打扰的结束时间（RFC 3339），如演示期间临时开启
  */
-httpUrl?: string; 
+dndUntil?: string | null; 
 /**
- * HTTP 请求方法
+ * 免打扰期间仍弹出错误级别的系统通知
  */
-httpMethod?: string; 
+dndAllowErrors?: boolean }
+export type OperationInfo = { id: string; label: string; timeoutSecs: number | null; startedAt: string }
+export type OptimizedImage = { 
 /**
- * HTTP 请求头（JSON 格式）
+ * 相对源目录，以 / 分隔
  */
-httpHeaders?: string; 
+relativePath: string; 
 /**
- * HTTP 请求体
+ * "png" | "jpeg" | "webp"
  */
-httpBody?: string; 
+format: string; originalBytes: number; 
 /**
- * HTTP JSON 路径（用于提取 JSON 响应中的特定字段）
+ * 失败时为 0
  */
-httpJsonPath?: string }
+optimizedBytes: number; 
 /**
- * 自动发送模式
+ * 压缩后没有变小，输出的是原图
  */
-export type AutoSendMode = "fixed" | "csv" | "template" | "http"
-export type BranchInfo = { name: string; is_current: boolean; is_remote: boolean; upstream: string | null }
-export type CachedDashboardData = { stats: DashboardStats; heatmap_data: DailyActivity[]; recent_commits: RecentCommit[] }
-export type ChatMessage = { id: string; role: string; content: string; createdAt: string; tokens: number | null; thinking: boolean | null; thinkingContent: string | null; edited?: boolean | null; toolCalls?: JsonValue | null; toolCallId?: string | null; toolName?: string | null; toolStatus?: number | null; toolMethod?: string | null; toolUrl?: string | null; toolElapsedMs?: number | null; toolBodyBytes?: number | null; toolTruncated?: boolean | null; attachments?: JsonValue | null }
-export type ChatSession = { id: string; title: string; providerId: string; modelId: string; createdAt: string; updatedAt: string; messages: ChatMessage[]; systemPrompt?: string | null; temperature?: number | null; maxTokens?: number | null; topP?: number | null; frequencyPenalty?: number | null; presencePenalty?: number | null; pinned?: boolean | null; 
+keptOriginal: boolean; error: string | null }
 /**
- * 会话级"始终允许"的工具名列表（用户在授权弹窗勾"始终允许"后写入）
+ * 消息历史达到上限后的处理方式
  */
-allowedTools?: string[] | null; 
+export type OverflowPolicy = 
 /**
- * 会话启用的工具集合；缺省使用全局默认
+ * 丢弃最早的消息
  */
-enabledTools?: string[] | null; 
+"dropOldest" | 
 /**
- * 工具（Read/Write/Bash 等）允许操作的根目录；缺省则禁止写入/执行类工具
+ * 停止记录新消息，直到清空历史
  */
-allowedCwd?: string | null; 
+"stopCapturing"
 /**
- * 是否在本会话里启用 MCP gateway 工具（从已注册的接口里取）。缺省 true。
- * 仅当 MCP gateway 已启动时实际生效。
+ * 根据 blame 统计的贡献者
  */
-useMcpGatewayTools?: boolean | null; 
+export type OwnerContributor = { name: string; email: string; 
 /**
- * 当前生效的上下文压缩版本号（如 "v2"）。None 表示从未压缩
+ * 当前代码中归属此人的行数
  */
-currentCompactionVersion?: string | null }
-export type ChatSessionSummary = { id: string; title: string; providerId: string; modelId: string; createdAt: string; updatedAt: string; messageCount: number; pinned?: boolean | null }
-export type ChatStreamMessage = { role: string; 
+lines: number; percent: number }
+export type PathRoot = { id: string; name: string; path: string; 
 /**
- * string 或 OpenAI 多模态内容数组
+ * 绑定在该根目录下的项目数
  */
-content: JsonValue; tool_calls?: JsonValue[] | null; tool_call_id?: string | null; name?: string | null }
-export type ChatStreamRequest = { requestId: string; providerId: string; model: string; baseUrl: string; apiKey: string | null; thinking: boolean | null; stream: boolean | null; messages: ChatStreamMessage[]; temperature: number | null; maxTokens: number | null; topP: number | null; frequencyPenalty: number | null; presencePenalty: number | null; tools?: JsonValue[] | null; toolChoice?: JsonValue | null }
-export type ChatTask = { id: string; subject: string; description: string; activeForm: string | null; status: string; createdAt: string; updatedAt: string }
+projectCount: number; 
 /**
- * Claude Code 安装信息
+ * 根目录在本机是否存在
  */
-export type ClaudeCodeInfo = { env_type: EnvType; env_name: string; installed: boolean; version: string | null; path: string | null; config_dir: string | null; config_files: ConfigFileInfo[] }
+exists: boolean; createdAt: string; updatedAt: string }
 /**
- * 配置选项
+ * 单个 peer 的信息
  */
-export type ClaudeConfigSelectOption = { label: string; value: JsonValue }
+export type PeerInfo = { peerId: string; displayName: string; deviceType: string; userAgent: string; 
 /**
- * Claude 快捷配置选项
+ * 是否是当前桌面客户端自身
  */
-export type ClaudeQuickConfig = { id: string; name: string; description: string; category: string; configKey: string; valueType: string; defaultValue: JsonValue; options: ClaudeConfigSelectOption[] | null; placeholder: string | null; allowEmpty: boolean | null }
-export type ClipboardEntry = { id: string; content: string; contentPreview: string; timestamp: number; pinned: boolean; charCount: number; note?: string | null }
-export type ClipboardSettings = { enabled: boolean; maxItems: number; monitorIntervalMs: number }
-export type CommitFileChange = { insertions: number; deletions: number; filename: string }
-export type CommitInfo = { hash: string; short_hash: string; message: string; author: string; email: string; date: string; body?: string | null; files_changed?: number | null; insertions?: number | null; deletions?: number | null; refs?: string[] | null; parent_hashes?: string[] | null }
-export type CompactionContent = { version: string; content: string; meta: CompactionMeta | null }
+isSelf: boolean }
 /**
- * 压缩目录的索引文件 <sessionId>/compactions/index.json
+ * 端口占用信息
  */
-export type CompactionIndex = { 
+export type PortOccupation = { port: number; protocol: string; pid: number; processName: string; localAddr: string; state: string }
 /**
- * 当前生效版本号；None 表示无版本（不会出现在已写过的索引里）
+ * 端口监控项（host:port 定时探测）
  */
-current?: string | null; versions: CompactionMeta[] }
+export type PortWatch = { id: string; name: string; host: string; port: number; 
 /**
- * 单次压缩的元数据；正文 markdown 单独存放在 <sessionId>/compactions/<version>.md
+ * 探测间隔（秒）
  */
-export type CompactionMeta = { 
+intervalSecs?: number; 
 /**
- * 版本号，如 "v1"、"v2"
+ * 连接超时（毫秒）
  */
-version: string; createdAt: string; 
+timeoutMs?: number; enabled?: boolean; status?: string; latencyMs?: number | null; lastChecked?: string | null; 
 /**
- * 压缩时摘要覆盖的原始消息条数（即被压缩的早期消息条数）
+ * 最近一次状态切换时间
  */
-sourceMessageCount: number; 
+lastChange?: string | null; history?: PortWatchRecord[]; createdAt: string }
 /**
- * 压缩时保留的尾部条数
+ * 创建/编辑端口监控的输入
  */
-tailKept: number; 
+export type PortWatchInput = { name: string; host: string; port: number; intervalSecs: number | null; timeoutMs: number | null; enabled: boolean | null }
 /**
- * 摘要字符数
+ * 单次探测记录
  */
-charCount: number; 
+export type PortWatchRecord = { time: string; status: string; latencyMs: number | null }
+export type PowerStatus = { 
 /**
- * 生成摘要使用的模型（可选，便于排查）
+ * 是否检测到电池（台式机为 false）
  */
-model?: string | null }
+hasBattery: boolean; 
 /**
- * 配置文件信息
+ * 当前是否由电池供电
  */
-export type ConfigFileInfo = { name: string; path: string; exists: boolean; size: number; modified: string | null; description: string }
+onBattery: boolean; 
 /**
- * 保存的配置档案
+ * 电量百分比，未知时为 None
  */
-export type ConfigProfile = { id: string; name: string; description: string | null; settings: JsonValue; created_at: string; updated_at: string }
-export type ConflictFileContent = { file: string; base: string | null; current: string | null; incoming: string | null; worktree: string | null }
+batteryPercent: number | null; 
 /**
- * 连接的客户端信息（服务器模式）
+ * 是否启用了低电量暂停
  */
-export type ConnectedClient = { id: string; addr: string; connectedAt: number; lastActivity: number; bytesSent: number; bytesReceived: number }
-export type CreateApiChatSessionInput = { title: string | null; providerId: string; modelId: string; selectedEndpointIds?: string[] }
-export type CreateChatSessionInput = { title: string | null; providerId: string; modelId: string }
-export type CreateProjectInput = { name: string; path: string; tags: string[] | null; labels: string[] | null }
-export type CursorPosition = { x: number; y: number }
-export type DailyActivity = { date: string; count: number }
-export type DashboardStats = { total_projects: number; today_commits: number; week_commits: number; unpushed_commits: number; unmerged_branches: number; last_updated: string }
+saverEnabled: boolean; saverThreshold: number; 
 /**
- * 数据格式
+ * 后台任务当前是否被暂停
  */
-export type DataFormat = "text" | "hex" | "base64"
-export type DockerAiGenerateInput = { projectPath: string; dockerfilePath: string | null; imageName: string | null; providerId: string | null; modelId: string | null }
-export type DockerAiGenerateOutput = { content: string; providerName: string; modelName: string }
-export type DockerBuildInput = { projectPath: string; dockerfilePath: string; imageName: string; tag: string | null; noCache: boolean | null }
-export type DockerCommandResult = { success: boolean; stdout: string; stderr: string; command: string }
-export type DockerContainerInfo = { id: string; image: string; names: string; status: string; 
+backgroundPaused: boolean }
+/**
+ * 进程查询过滤
+ */
+export type ProcessFilter = { port: number | null; name: string | null; pid: number | null }
+/**
+ * 进程信息
+ */
+export type ProcessInfo = { pid: number; name: string; port: number | null; protocol: string | null; localAddr: string | null; remoteAddr: string | null; status: string; memory: number; cpu: number; workingDir: string | null; cmd: string | null; 
+/**
+ * 由项目任务启动时为任务名
+ */
+projectTask?: string | null }
+/**
+ * 项目
+ */
+export type Project = { id: string; name: string; path: string; isFavorite: boolean; tags: string[]; labels: string[]; createdAt: string; updatedAt: string; lastOpened: string | null; editorId?: string | null; claudeEnvName?: string | null; 
+/**
+ * 自定义图标（缓存在数据目录中的文件路径）
+ */
+icon?: string | null; 
+/**
+ * 强调色 #RRGGBB
+ */
+color?: string | null; 
+/**
+ * 简短描述
+ */
+description?: string | null; 
+/**
+ * 所属路径根目录，path 由根目录路径 + 相对路径得出
+ */
+rootId?: string | null; 
+/**
+ * 项目专用的 git 可执行文件，优先于全局设置
+ */
+gitExecutable?: string | null; 
+/**
+ * 统计提交使用的分支：None 为当前检出分支，"*" 为全部本地分支
+ */
+statsBranch?: string | null }
+/**
+ * 单个项目在对比窗口内的活跃度
+ */
+export type ProjectActivity = { name: string; path: string; commits: number; contributorCount: number; 
+/**
+ * 按提交数降序
+ */
+contributors: ContributorActivity[]; 
+/**
+ * 新增行数（不含二进制文件）
+ */
+insertions: number; deletions: number; 
+/**
+ * 改动过的文件数（去重）
+ */
+filesChanged: number; commitsByDate: DailyActivity[]; 
+/**
+ * 读取失败（非 git 仓库等）时的错误信息
+ */
+error: string | null }
+export type ProjectInfo = { id: string | null; name: string; path: string }
+export type ProjectLink = { id: string; projectId: string; title: string; url: string; 
+/**
+ * "issues" | "staging" | "production" | "ci" | "docs" | "other"
+ */
+kind: string; sortOrder: number; createdAt: string; updatedAt: string }
+export type ProjectLinkInput = { 
+/**
+ * 为空时新建
+ */
+id: string | null; projectId: string; title: string; url: string; kind: string | null; sortOrder: number | null }
+export type ProjectRelocation = { projectId: string; name: string; oldPath: string; 
+/**
+ * 记录过的远程地址
+ */
+remoteUrl: string | null; 
+/**
+ * 远程匹配在前
+ */
+candidates: RelocationCandidate[]; 
+/**
+ * 只有一个远程匹配、或没有远程匹配但只有一个名称匹配时给出建议路径
+ */
+suggestedPath: string | null }
+export type ProjectTask = { id: string; projectId: string; name: string; command: string; 
+/**
+ * 工作目录，相对路径基于项目目录；为空则使用项目目录
+ */
+cwd: string | null; env: Partial<{ [key in string]: string }>; sortOrder: number; createdAt: string; updatedAt: string }
+export type ProjectTaskInput = { 
+/**
+ * 为空时新建
+ */
+id: string | null; projectId: string; name: string; command: string; cwd: string | null; env: Partial<{ [key in string]: string }> | null; sortOrder: number | null }
+export type ProjectTaskOutput = { runId: string; 
+/**
+ * "stdout" | "stderr"
+ */
+stream: string; line: string }
+/**
+ * 运行中的任务实例
+ */
+export type ProjectTaskRun = { runId: string; taskId: string; projectId: string; name: string; command: string; pid: number | null; startedAt: string }
+/**
+ * 协议类型
+ */
+export type Protocol = "tcp" | "udp"
+/**
+ * 代理配置
+ */
+export type ProxyConfig = { prefix: string; target: string; 
+/**
+ * 转发时去掉匹配的前缀（/api/users → /users）；关闭时原样保留
+ */
+stripPrefix?: boolean; 
+/**
+ * 转发前在路径前追加的前缀（如 /v1）
+ */
+addPrefix?: string | null; 
+/**
+ * 转发请求时设置的请求头，同名时覆盖客户端发来的值
+ */
+requestHeaders?: Partial<{ [key in string]: string }>; 
+/**
+ * 写入代理响应的响应头
+ */
+responseHeaders?: Partial<{ [key in string]: string }> }
+export type ProxySettings = { 
+/**
+ * "system" 跟随系统/环境变量 | "none" 不使用代理 | "manual" 手动指定
+ */
+mode?: string; 
+/**
+ * 手动代理地址，支持 http:// https:// socks5:// socks5h://
+ */
+url?: string | null; 
+/**
+ * 不走代理的主机，支持 `*.example.com`、`.example.com`、IP 与 CIDR
+ */
+bypass?: string[] }
+/**
+ * 快捷配置选项
+ */
+export type QuickConfigOption = { id: string; name: string; description: string; category: string; config_key: string; config_value: JsonValue }
+export type ReadOnlyStatus = { enabled: boolean; hasPassword: boolean }
+export type RecentCommit = { hash: string; short_hash: string; message: string; author: string; email: string; date: string; project_name: string; project_path: string }
+export type RegexFlags = { 
+/**
+ * i
+ */
+caseInsensitive: boolean; 
+/**
+ * m：^ $ 匹配行首行尾
+ */
+multiLine: boolean; 
+/**
+ * s：. 匹配换行
+ */
+dotMatchesNewLine: boolean; 
+/**
+ * x：忽略空白并允许 # 注释
+ */
+ignoreWhitespace: boolean; 
+/**
+ * U：默认非贪婪
+ */
+swapGreed: boolean }
+export type RegexGroup = { 
+/**
+ * 组序号，从 1 开始
+ */
+index: number; name: string | null; start: number; end: number; text: string }
+export type RegexMatch = { start: number; end: number; text: string; 
+/**
+ * 捕获组（不含组 0）；未参与匹配的组为 None
+ */
+groups: (RegexGroup | null)[] }
+export type RegexTestResult = { matches: RegexMatch[]; 
+/**
+ * 捕获组名称（不含组 0），无名组为 None
+ */
+groupNames: (string | null)[]; 
+/**
+ * 达到 max_matches 后停止
+ */
+truncated: boolean; 
+/**
+ * 超时后停止，matches 为超时前的结果
+ */
+timedOut: boolean; 
+/**
+ * 传入 replacement 且未中断时的替换结果
+ */
+replaced: string | null; compileMs: number; matchMs: number }
+export type ReleaseAsset = { name: string; 
+/**
+ * GitLab 的外部链接没有大小
+ */
+size: number | null; contentType: string | null; 
+/**
+ * 实际下载地址（私有仓库为 API 地址）
+ */
+downloadUrl: string; 
+/**
+ * 下载时是否需要带令牌
+ */
+requiresAuth: boolean }
+export type ReleaseInfo = { provider: string; host: string; 
+/**
+ * 仓库路径，如 "owner/repo"、"group/sub/project"
+ */
+repository: string; tag: string; name: string | null; publishedAt: string | null; assets: ReleaseAsset[]; 
+/**
+ * 是否使用了已保存的令牌
+ */
+authenticated: boolean }
+export type RelocationCandidate = { path: string; 
+/**
+ * "remote" 远程地址一致 | "name" 仓库目录名与项目名或原目录名一致
+ */
+matchedBy: string }
+export type RelocationInput = { projectId: string; newPath: string }
+export type RemapReport = { root: PathRoot; projects: RemappedProject[]; 
+/**
+ * 为 true 时只预览，未写入
+ */
+dryRun: boolean }
+export type RemappedProject = { id: string; name: string; oldPath: string; newPath: string; 
 /**
- * docker ps 的 State 字段：running / exited / paused / created / restarting / dead / removing
+ * 新路径在本机是否存在
  */
-state: string; ports: string; composeProject: string | null; composeService: string | null; composeWorkingDir: string | null; composeConfigFiles: string[] }
-export type DockerImageInfo = { id: string; repository: string; tag: string; size: string; createdSince: string }
-export type DockerRunInput = { image: string; containerName: string | null; ports: string[] | null; env: string[] | null; volumes: string[] | null; network: string | null; restart: string | null; user: string | null; workdir: string | null; command: string | null; privileged: boolean | null; readOnly: boolean | null; extraArgs: string[] | null }
-export type DockerStatus = { available: boolean; version: string | null; error: string | null }
+exists: boolean }
+export type RemoteInfo = { name: string; url: string; fetch_url: string | null; push_url: string | null }
 /**
- * 下载配置
+ * 一条远程地址改写（预览或执行结果）
  */
-export type DownloadConfig = { url: string; saveDir: string | null; fileName: string | null; maxRetries: number | null }
+export type RemoteRewrite = { projectPath: string; remote: string; 
 /**
- * 下载任务
+ * 是否为 pushurl（否则是 url）
  */
-export type DownloadTask = { id: string; url: string; savePath: string; fileName: string; totalSize: number; downloadedSize: number; status: string; speed: number; error: string | null; createdAt: string; updatedAt: string }
+push: boolean; oldUrl: string; newUrl: string; 
 /**
- * 编辑器配置
+ * 预览时为 false
  */
-export type EditorConfig = { id: string; name: string; path: string; icon: string | null; is_default: boolean }
-export type EditorInput = { name: string; path: string; icon: string | null; is_default: boolean | null }
+applied: boolean; error: string | null }
 /**
- * 环境类型
+ * 仓库中未完成的操作，从 .git 下的状态文件识别
  */
-export type EnvType = "host" | "wsl"
+export type RepoOperation = { 
 /**
- * 转发规则
+ * "merge" | "rebase" | "am" | "cherryPick" | "revert" | "bisect"
  */
-export type ForwardRule = { id: string; name: string; localPort: number; remoteHost: string; remotePort: number; 
+kind: string; 
 /**
- * 文档路径，如 "doc.html" 或 "swagger-ui.html"，用于快速访问
+ * 正在合并 / 拣选 / 回滚的提交，变基时为 onto
  */
-docPath: string | null; status?: string; connections?: number; bytesIn?: number; bytesOut?: number; createdAt: string }
+target: string | null; 
 /**
- * 创建转发规则的输入
+ * target 的可读名称（git name-rev）
  */
-export type ForwardRuleInput = { name: string; localPort: number; remoteHost: string; remotePort: number; 
+targetName: string | null; 
 /**
- * 文档路径，如 "doc.html" 或 "swagger-ui.html"
+ * 变基前所在的分支
  */
-docPath: string | null }
+headName: string | null; 
 /**
- * 转发统计
+ * 变基 / am 的进度
  */
-export type ForwardStats = { ruleId: string; connections: number; bytesIn: number; bytesOut: number }
-export type GitRepo = { path: string; name: string }
-export type GitStatus = { branch: string; is_clean: boolean; staged: string[]; unstaged: string[]; untracked: string[]; conflicted: string[]; ahead: number; behind: number }
-export type GlobalShortcutBinding = { id: string; keys: string }
+step: number | null; total: number | null }
 /**
- * HTTP 请求配置
+ * 正在触发的告警
  */
-export type HttpFetchConfig = { url: string; method?: string | null; headers?: Partial<{ [key in string]: string }> | null; body?: string | null; jsonPath?: string | null }
-export type JobDirection = "backend" | "frontend" | "fullstack"
-export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
-export type KnowledgeDoc = { projectId: string; projectName: string; content: string; updatedAt: string }
-export type LlmProxyHeader = { name: string; value: string }
-export type LlmProxyRequest = { method: string; url: string; headers: LlmProxyHeader[]; body: string | null }
-export type LlmProxyResponse = { status: number; status_text: string; headers: LlmProxyHeader[]; body: string }
+export type ResourceAlert = { ruleId: string; name: string; metric: string; 
 /**
- * 供前端"以 MCP 客户端身份"调用本地网关时使用：
- * - url：HTTP 端点（含 scheme/host/port），如果网关未运行则不返回
- * - api_key：从 mcp_gateway_keys 里挑第一个有效 key。若 keys 为空（网关无鉴权）则为 None
+ * 当前值（cpu / memory 为百分比，disk 为剩余 GB）
  */
-export type McpGatewayInternalEndpoint = { url: string; apiKey: string | null }
-export type McpGatewayKey = { id: string; name: string; key: string; enabled?: boolean; createdAt: string; expiresAt?: string | null }
-export type McpGatewayStatus = { running: boolean; url: string | null; host: string | null; port: number | null; startedAt: string | null }
-export type MentionFileEntry = { path: string; isDir: boolean }
+value: number; threshold: number; 
 /**
- * 消息方向
+ * 触发的磁盘挂载点（disk 规则）
  */
-export type MessageDirection = "sent" | "received"
+target: string | null; since: string }
 /**
- * 消息记录
+ * 告警规则
  */
-export type NetcatMessage = { id: string; sessionId: string; direction: MessageDirection; data: string; format: DataFormat; size: number; timestamp: number; 
+export type ResourceAlertRule = { id: string; name: string; 
 /**
- * 来源/目标客户端（服务器模式）
+ * "cpu" | "memory" | "disk"
  */
-clientId: string | null; clientAddr: string | null }
+metric: string; 
 /**
- * 会话配置
+ * cpu / memory 为百分比（超过告警）；disk 为剩余 GB（低于告警）
  */
-export type NetcatSession = { id: string; name: string; protocol: Protocol; mode: SessionMode; host: string; port: number; status: SessionStatus; autoReconnect: boolean; timeoutMs: number; createdAt: number; connectedAt: number | null; lastActivity: number | null; bytesSent: number; bytesReceived: number; messageCount: number; errorMessage: string | null; 
+threshold: number; 
 /**
- * 本地地址（客户端模式连接后分配的本地 IP:PORT）
+ * disk 规则的挂载点（如 "/"、"C:\\"），为空时检查所有磁盘
  */
-localAddr: string | null; 
+diskPath?: string | null; 
 /**
- * 连接的客户端数量（仅服务器模式）
+ * 持续超过阈值多少秒才告警
  */
-clientCount: number; 
+sustainSecs?: number; 
 /**
- * 自动发送配置
+ * 告警时闪烁托盘图标
  */
-autoSend?: AutoSendConfig }
+flashTray?: boolean; enabled: boolean }
+export type ResourceAlertRuleInput = { name: string; metric: string; threshold: number; diskPath: string | null; sustainSecs: number | null; flashTray: boolean | null; enabled: boolean | null }
 /**
- * 创建会话的输入参数
+ * 退出时仍在运行的资源 id
  */
-export type NetcatSessionInput = { protocol: Protocol; mode: SessionMode; host: string; port: number; name: string | null; autoReconnect: boolean | null; timeoutMs: number | null }
+export type ResumeState = { servers?: string[]; forwarders?: string[]; sshTunnels?: string[]; downloads?: string[]; netcatSessions?: string[]; savedAt: string }
 /**
- * 单条通知
+ * 启动时自动恢复的结果汇总
  */
-export type Notification = { id: string; notification_type: string; title: string; message?: string; created_at: string }
-export type NotificationInput = { notification_type: string; title: string; message?: string }
+export type ResumeSummary = { restored: ResumedItem[]; failed: ResumedItem[]; savedAt: string; resumedAt: string }
 /**
- * 端口占用信息
+ * 单项恢复结果
  */
-export type PortOccupation = { port: number; protocol: string; pid: number; processName: string; localAddr: string; state: string }
+export type ResumedItem = { 
 /**
- * 进程查询过滤
+ * "server" | "forwarder" | "sshTunnel" | "download" | "netcat"
  */
-export type ProcessFilter = { port: number | null; name: string | null; pid: number | null }
+kind: string; id: string; error: string | null }
+export type RunResumeDeepAgentRequest = { requestId: string; projectId: string; provider: AiProviderConfig; jobDirection: NodeJobDirection; jdKeywords: string[]; tone: NodeTone; promptConfig?: JsonValue | null; toolPermissionMode?: ToolPermissionMode | null }
+export type RuntimeDownload = { id: string; fileName: string; downloadedSize: number; 
 /**
- * 进程信息
+ * 未知时为 0
  */
-export type ProcessInfo = { pid: number; name: string; port: number | null; protocol: string | null; localAddr: string | null; remoteAddr: string | null; status: string; memory: number; cpu: number; workingDir: string | null; cmd: string | null }
+totalSize: number; 
 /**
- * 项目
+ * 字节/秒
  */
-export type Project = { id: string; name: string; path: string; isFavorite: boolean; tags: string[]; labels: string[]; createdAt: string; updatedAt: string; lastOpened: string | null; editorId?: string | null; claudeEnvName?: string | null }
-export type ProjectInfo = { id: string | null; name: string; path: string }
+speed: number }
+export type RuntimeForward = { id: string; name: string; localPort: number; 
 /**
- * 协议类型
+ * host:port，或 unix / pipe 目标的路径
  */
-export type Protocol = "tcp" | "udp"
+target: string; connections: number; bytesIn: number; bytesOut: number }
+export type RuntimeNetcatSession = { id: string; name: string; protocol: Protocol; mode: SessionMode; host: string; port: number; status: SessionStatus; bytesSent: number; bytesReceived: number; 
 /**
- * 代理配置
+ * 仅服务器模式
  */
-export type ProxyConfig = { prefix: string; target: string }
+clientCount: number }
+export type RuntimeOverview = { servers: RuntimeServer[]; forwards: RuntimeForward[]; downloads: RuntimeDownload[]; netcatSessions: RuntimeNetcatSession[]; processes: RuntimeProcess[]; 
 /**
- * 快捷配置选项
+ * 所有下载的速度之和，字节/秒
  */
-export type QuickConfigOption = { id: string; name: string; description: string; category: string; config_key: string; config_value: JsonValue }
-export type RecentCommit = { hash: string; short_hash: string; message: string; author: string; email: string; date: string; project_name: string; project_path: string }
-export type RemoteInfo = { name: string; url: string; fetch_url: string | null; push_url: string | null }
-export type ResumeKnowledgeHistoryEntry = { 
+downloadSpeed: number }
+export type RuntimeProcess = { 
 /**
- * 文件名上的 timestamp（毫秒级 unix），同时充当主键
+ * 项目任务为 run_id，终端为会话 id，文档预览为项目路径
  */
-timestamp: string; 
+id: string; 
 /**
- * 文件字节数（便于 UI 给个尺寸提示）
+ * "project_task" | "terminal" | "docs_preview"
  */
-size: number }
-export type ResumeProjectExperience = { projectId: string; projectName: string; techStack: string[]; starExperience: StarExperience; customDescription?: string | null; isEdited: boolean }
-export type ResumeProjectIndex = { root_name: string; files: ResumeProjectIndexFile[]; directories: string[]; stats: ResumeProjectIndexStats }
-export type ResumeProjectIndexFile = { path: string; size: number; extension: string | null }
-export type ResumeProjectIndexStats = { file_count: number; directory_count: number; total_bytes: number }
-export type ResumeV2 = { id: string; createdAt: string; updatedAt: string; jobDirection: JobDirection; jdKeywords: string[]; tone: Tone; summary?: string | null; skills: string[]; experiences: ResumeProjectExperience[]; isSaved: boolean }
-export type RunKnowledgeAgentRequest = { requestId: string; provider: AiProviderConfig; projectId: string; initialBackground?: string | null }
-export type RunKnowledgeAgentResponse = { background: string }
-export type RunResumeAgentRequest = { requestId: string; provider: AiProviderConfig; knowledgeDocs: KnowledgeDoc[]; jobDirection: JobDirection; jdKeywords: string[]; tone: Tone }
-export type RunResumeAgentResponse = { resume: ResumeV2 }
+kind: string; name: string; pid: number | null; startedAt: string }
+export type RuntimeServer = { id: string; name: string; port: number; urlPrefix: string; rootDir: string }
 export type SaveCompactionInput = { sessionId: string; content: string; sourceMessageCount: number; tailKept: number; model: string | null }
+/**
+ * SYN 扫描是否可用
+ */
+export type ScanCapabilities = { synAvailable: boolean; 
+/**
+ * 不可用的原因（缺少权限 / 平台不支持）
+ */
+synUnavailableReason: string | null }
 /**
  * 扫描配置
  */
 export type ScanConfig = { 
 /**
- * 目标 IP 地址
+ * 目标 IP 地址，或 IPv4 网段（CIDR，如 192.168.1.0/24）
  */
 target: string; 
 /**
@@ -3048,11 +7479,65 @@ timeoutMs: number | null;
 /**
  * 并发数，默认 100
  */
-concurrency: number | null }
+concurrency: number | null; 
+/**
+ * 扫描方式："connect"（默认，完整 TCP 连接）| "syn"（原始套接字半开扫描，需要权限，
+ * 不可用时自动退回 connect）
+ */
+mode?: string | null }
+/**
+ * scan_directory 的过滤选项
+ */
+export type ScanOptions = { 
+/**
+ * 只收录相对扫描目录匹配这些模式的仓库（gitignore 风格，如 "work/**"）
+ */
+include?: string[]; 
+/**
+ * 跳过匹配这些模式的目录，如 "node_modules"、"archive/**"
+ */
+exclude?: string[]; ignoreCase?: boolean; 
+/**
+ * 进入符号链接指向的目录
+ */
+followSymlinks?: boolean; 
+/**
+ * 找到这么多仓库后停止扫描
+ */
+maxRepos: number | null }
 /**
  * 扫描结果
  */
 export type ScanResult = { ip: string; port: number; status: string; service: string | null }
+export type ScratchNote = { id: string; name: string; content: string; createdAt: string; updatedAt: string }
+export type ScratchNoteSummary = { id: string; name: string; createdAt: string; updatedAt: string; bytes: number; 
+/**
+ * 第一行非空内容
+ */
+preview: string; versionCount: number }
+export type ScratchNoteVersion = { id: string; savedAt: string; bytes: number }
+export type ScratchSearchLine = { 
+/**
+ * 从 1 开始
+ */
+line: number; text: string }
+export type ScratchSearchResult = { id: string; name: string; updatedAt: string; nameMatches: boolean; lines: ScratchSearchLine[] }
+/**
+ * 疑似密钥
+ */
+export type SecretFinding = { rule: string; 
+/**
+ * "high" | "medium" | "low"
+ */
+severity: string; 
+/**
+ * 相对项目根目录的路径
+ */
+file: string; line: number; 
+/**
+ * 打码后的匹配内容
+ */
+preview: string }
 /**
  * 发送消息的输入
  */
@@ -3064,7 +7549,15 @@ targetClient: string | null;
 /**
  * 是否广播给所有客户端（仅服务器模式）
  */
-broadcast: boolean | null }
+broadcast: boolean | null; 
+/**
+ * 使用报文库中的报文（忽略 data / format）
+ */
+payloadId?: string | null; 
+/**
+ * 发送前渲染 {{timestamp}} 等模板变量（使用报文库报文时总是渲染）
+ */
+render?: boolean | null }
 /**
  * 服务配置
  */
@@ -3080,7 +7573,23 @@ indexPage: string | null;
 /**
  * 多个代理规则
  */
-proxies: ProxyConfig[]; status?: string; createdAt: string }
+proxies: ProxyConfig[]; 
+/**
+ * 同时处理的最大请求数，超出返回 503；None 不限制
+ */
+maxConcurrentRequests?: number | null; 
+/**
+ * 同时保持的最大连接数（含 keep-alive 空闲连接），超出的连接收到 503 后被关闭；None 不限制
+ */
+maxConnections?: number | null; 
+/**
+ * 每个客户端 IP 每秒允许的请求数（可突发 2 倍），超出返回 429；None 不限制
+ */
+rateLimitPerIp?: number | null; status?: string; 
+/**
+ * status 为 "error" 时的原因，如根目录被删除 / 卸载
+ */
+error?: string | null; createdAt: string }
 /**
  * 创建服务的输入
  */
@@ -3096,7 +7605,11 @@ indexPage: string | null;
 /**
  * 多个代理规则
  */
-proxies: ProxyConfig[] | null }
+proxies: ProxyConfig[] | null; maxConcurrentRequests?: number | null; maxConnections?: number | null; rateLimitPerIp?: number | null }
+/**
+ * 服务运行状态
+ */
+export type ServiceStatus = { running: boolean; port: number; urls: NetworkUrl[]; peerCount: number }
 /**
  * Session 鉴权中 token 如何注入后续请求
  */
@@ -3125,6 +7638,46 @@ export type ShortcutEntry = { id: string; category: string; description: string;
  * 添加/编辑快捷键的输入
  */
 export type ShortcutInput = { category: string | null; description: string | null; keys: string | null; platform: string | null }
+/**
+ * 当前生效的签名配置
+ */
+export type SigningConfig = { 
+/**
+ * "local" 仓库配置 | "global" 全局配置
+ */
+scope: string; format: string; signingKey: string | null; signCommits: boolean; signTags: boolean; allowedSignersFile: string | null }
+/**
+ * 可用于签名提交的密钥
+ */
+export type SigningKey = { 
+/**
+ * "openpgp" | "ssh"（对应 gpg.format）
+ */
+format: string; 
+/**
+ * 写入 user.signingkey 的值：GPG 长 ID，或 SSH 公钥文件路径
+ */
+key: string; 
+/**
+ * GPG 的 uid / SSH 公钥注释
+ */
+label: string | null; fingerprint: string | null; 
+/**
+ * GPG 密钥过期日期（YYYY-MM-DD）
+ */
+expires: string | null }
+/**
+ * 签名验证结果（在临时仓库中做一次测试提交）
+ */
+export type SigningTestResult = { 
+/**
+ * 提交是否带上了签名
+ */
+signed: boolean; 
+/**
+ * git verify-commit 是否通过
+ */
+verified: boolean; output: string }
 export type Skill = { name: string; description: string; argsHint: string | null; body: string }
 /**
  * SSH 认证方式（前端 tag 区分：key / password / sshConfig）
@@ -3142,6 +7695,50 @@ export type SshAuthMethod =
  * 读取 ~/.ssh/config 的 Host 别名
  */
 { type: "sshConfig"; hostAlias: string }
+/**
+ * ssh -T 认证测试结果
+ */
+export type SshAuthTestResult = { success: boolean; 
+/**
+ * 服务端返回的认证用户名（GitHub / GitLab 欢迎语中提取）
+ */
+account: string | null; output: string; durationMs: number }
+/**
+ * ssh config 中的一个 Host 块
+ */
+export type SshHostEntry = { alias: string; hostName: string | null; user: string | null; port: number | null; identityFiles: string[] }
+/**
+ * 写入 ssh config 的 Host 映射
+ */
+export type SshHostEntryInput = { alias: string; hostName: string | null; user: string | null; port: number | null; 
+/**
+ * 使用的私钥文件名（~/.ssh 下）
+ */
+keyName: string }
+/**
+ * ~/.ssh 下的一对密钥
+ */
+export type SshKeyInfo = { 
+/**
+ * 私钥文件名（如 id_ed25519）
+ */
+name: string; privateKeyPath: string; 
+/**
+ * 对应的 .pub 文件，不存在时为 None
+ */
+publicKeyPath: string | null; 
+/**
+ * 密钥类型：ssh-ed25519 / ssh-rsa / ecdsa-sha2-nistp256 ...
+ */
+keyType: string | null; 
+/**
+ * SHA256 指纹（ssh-keygen -lf）
+ */
+fingerprint: string | null; comment: string | null; 
+/**
+ * ssh config 中通过 IdentityFile 使用该密钥的 Host
+ */
+hosts: string[] }
 /**
  * SSH 隧道规则
  */
@@ -3173,16 +7770,105 @@ sshUser?: string;
 /**
  * 认证方式
  */
-auth: SshAuthMethod; status?: string; connections?: number; bytesIn?: number; bytesOut?: number; lastError?: string | null; createdAt: string }
+auth: SshAuthMethod; status?: string; connections?: number; bytesIn?: number; bytesOut?: number; lastError?: string | null; 
+/**
+ * 断线后自动重连（网络切换/休眠恢复）；缺省开启
+ */
+autoReconnect?: boolean; 
+/**
+ * 累计自动重连成功次数（运行期统计，加载时重置）
+ */
+reconnects?: number; 
+/**
+ * 所属分组；旧数据无此字段时落入「默认分组」
+ */
+group?: string; createdAt: string }
 /**
  * 创建/更新 SSH 隧道的输入
  */
-export type SshTunnelInput = { name: string; localPort: number; remoteHost: string; remotePort: number; sshHost: string; sshPort: number | null; sshUser: string | null; auth: SshAuthMethod }
+export type SshTunnelInput = { name: string; localPort: number; remoteHost: string; remotePort: number; sshHost: string; sshPort: number | null; sshUser: string | null; auth: SshAuthMethod; 
+/**
+ * 断线后自动重连；缺省开启
+ */
+autoReconnect?: boolean | null; 
+/**
+ * 所属分组；为空时落入「默认分组」
+ */
+group?: string | null }
 /**
  * SSH 隧道统计
  */
 export type SshTunnelStats = { tunnelId: string; connections: number; bytesIn: number; bytesOut: number }
-export type StarExperience = { situation: string; task: string; action: string; result: string }
+export type StartupPhase = { name: string; 
+/**
+ * 相对进程启动的开始时间（毫秒）
+ */
+startedAtMs: number; durationMs: number }
+export type StartupReport = { 
+/**
+ * setup 中同步执行的阶段
+ */
+phases: StartupPhase[]; 
+/**
+ * 后台预加载的模块
+ */
+modules: StartupPhase[]; 
+/**
+ * setup 完成时间（相对进程启动，毫秒）
+ */
+setupDoneMs: number | null; 
+/**
+ * 全部后台模块就绪时间
+ */
+readyMs: number | null }
+/**
+ * 统计缓存占用
+ */
+export type StatsCacheSize = { project_count: number; 
+/**
+ * 已不在项目列表中的项目
+ */
+orphaned_projects: number; daily_rows: number; 
+/**
+ * 早于保留天数的按日记录
+ */
+expired_daily_rows: number; recent_commit_rows: number; 
+/**
+ * 数据库文件（含 -wal / -shm）字节数，统计缓存与其它数据共用同一个库
+ */
+database_bytes: number; 
+/**
+ * 迁移到 sqlite 后残留的 stats_cache.json 字节数
+ */
+legacy_file_bytes: number; retention_days: number }
+/**
+ * 压缩结果
+ */
+export type StatsCompactReport = { removed_projects: number; removed_daily_rows: number; removed_legacy_files: number; bytes_before: number; bytes_after: number }
+/**
+ * sync_to_remote 预览：单个分支推送到目标远程后的效果
+ */
+export type SyncBranchPreview = { branch: string; 
+/**
+ * "create" | "fastForward" | "forceUpdate" | "upToDate"
+ */
+action: string; sourceSha: string; targetSha: string | null; 
+/**
+ * 将推送到目标的提交数
+ */
+ahead: number | null; 
+/**
+ * 强制更新时目标上被丢弃的提交数；本地没有目标提交对象时为 None
+ */
+discarded: number | null; 
+/**
+ * 提交范围（target..source），可用于 git log 查看
+ */
+range: string | null; 
+/**
+ * 未开启 force 时该分支会被拒绝
+ */
+requiresForce: boolean; isDefault: boolean }
 /**
  * 系统统计信息
  */
@@ -3192,6 +7878,7 @@ export type SystemStats = { totalMemory: number; usedMemory: number; totalSwap:
  */
 export type TerminalConfig = { terminal_type: string; custom_path: string | null; terminal_path: string | null }
 export type TerminalInput = { terminal_type: string; custom_path: string | null; terminal_path: string | null }
+export type TerminalSession = { id: string; projectId: string | null; cwd: string; shell: string; cols: number; rows: number; pid: number | null; startedAt: string }
 export type TerminalTestResult = { available: boolean; error: string | null; suggested_path: string | null }
 /**
  * 端口连通性测试结果
@@ -3205,17 +7892,156 @@ output: string;
  * 检测方式："nc" / "Test-NetConnection" / "tcp"
  */
 method: string; durationMs: number }
-export type Tone = "professional" | "concise"
+export type TextDiffMode = "line" | "word"
+export type TextDiffResult = { identical: boolean; insertions: number; deletions: number; hunks: DiffHunk[]; 
+/**
+ * unified diff 文本，可直接保存为 .patch
+ */
+patch: string; 
+/**
+ * 行数超过上限，hunks 不完整（patch 仍是完整的）
+ */
+truncated: boolean }
+export type TlsInspection = { host: string; port: number; 
+/**
+ * 如 "TLSv1_3"
+ */
+protocol: string | null; cipherSuite: string | null; 
+/**
+ * 叶子证书的 SAN（无 SAN 时退回 CN）是否匹配 host
+ */
+hostnameMatches: boolean; 
+/**
+ * 服务端发送的证书链，叶子证书在前
+ */
+chain: CertificateInfo[] }
+/**
+ * 工具路径检查结果（设置里配置编辑器 / 终端路径时使用）
+ */
+export type ToolPathCheck = { valid: boolean; 
+/**
+ * 实际使用的路径：绝对路径原样返回，命令名按 PATH 查找
+ */
+resolvedPath: string | null; 
+/**
+ * 不可用的原因
+ */
+message: string | null }
+export type ToolPermissionMode = "read_only" | "workspace_write" | "full_agent"
 export type ToolSchema = { name: string; description: string; parameters: JsonValue; requiresCwd: boolean }
+export type ToolWindowInfo = { label: string; kind: string; resourceId: string | null; title: string; createdAt: string }
+export type ToolWindowInput = { 
+/**
+ * 工具类型，如 "netcat" / "gitLog"
+ */
+kind: string; 
+/**
+ * 绑定的资源 id（会话 id、项目路径等），同一资源只开一个窗口
+ */
+resourceId: string | null; title: string | null; width: number | null; height: number | null }
+/**
+ * 抓包状态
+ */
+export type TrafficCapture = { 
+/**
+ * 会话 ID / 转发规则 ID
+ */
+resourceId: string; 
+/**
+ * "netcat" | "forward"
+ */
+kind: string; path: string; packets: number; bytes: number; truncated: boolean; startedAt: string }
+/**
+ * 收发流量的日志模式
+ */
+export type TrafficLogMode = 
+/**
+ * 不记录
+ */
+"off" | 
+/**
+ * 按频率抽样记录
+ */
+"sampled" | 
+/**
+ * 每条都记录
+ */
+"full"
 /**
  * UI 状态
  */
-export type UiState = { recent_detail_project_ids: string[] }
-export type UiStateInput = { recent_detail_project_ids: string[] | null }
+export type UiState = { recent_detail_project_ids: string[]; 
+/**
+ * 上次打开的工具箱工具，启动后回到该工具
+ */
+active_toolbox_tool?: string | null }
+export type UiStateInput = { recent_detail_project_ids: string[] | null; 
+/**
+ * 空字符串表示回到工具列表
+ */
+active_toolbox_tool: string | null }
+/**
+ * 检查结果
+ */
+export type UpdateCheck = { channel: string; available: boolean; currentVersion: string; version: string | null; date: string | null; 
+/**
+ * 更新说明（latest.json 中的 notes）
+ */
+notes: string | null; 
+/**
+ * 该版本已被推迟，自动检查时不提示
+ */
+deferred: boolean }
+/**
+ * 推迟的更新
+ */
+export type UpdateDeferral = { version: string; 
+/**
+ * RFC3339
+ */
+until: string }
 export type UpdateProjectInput = { id: string; name: string | null; tags: string[] | null; labels: string[] | null }
-export type Workflow = { id: string; name: string; cron: string; enabled?: boolean; nodes: WorkflowNode[]; lastRun?: WorkflowRun | null; createdAt: string; updatedAt: string }
+/**
+ * 已下载更新的安装安排
+ */
+export type UpdateSchedule = { version: string; downloaded: boolean; 
+/**
+ * "manual" | "onExit" | "at"
+ */
+mode: string; 
+/**
+ * mode 为 "at" 时的安装时间（RFC3339）
+ */
+at: string | null }
+export type UsageStats = { enabled: boolean; 
+/**
+ * 统计区间起始日期（含），None 表示全部
+ */
+since: string | null; totalCalls: number; 
+/**
+ * 按调用次数降序
+ */
+features: FeatureUsage[]; commands: CommandUsage[] }
+export type Workflow = { id: string; name: string; cron: string; enabled?: boolean; 
+/**
+ * 低电量省电模式下仍然按计划执行
+ */
+allowOnBattery?: boolean; nodes: WorkflowNode[]; lastRun?: WorkflowRun | null; createdAt: string; updatedAt: string }
 export type WorkflowNode = { id: string; nodeType: string; config: JsonValue; dependsOn?: string[] }
 export type WorkflowRun = { startedAt: string; finishedAt: string; status: string; outputs: Partial<{ [key in string]: string }>; error: string | null }
+export type WorkspaceMember = { name: string; 
+/**
+ * 绝对路径
+ */
+path: string; 
+/**
+ * 相对仓库根目录的路径（统一使用 `/`）
+ */
+relativePath: string; 
+/**
+ * "pnpm" | "npm" | "cargo"
+ */
+kind: string }
 /**
  * WSL 配置目录结果
  */
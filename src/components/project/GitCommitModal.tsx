@@ -1,8 +1,9 @@
 import { useState, useEffect } from "react";
 import { X, GitCommit, CloudUpload, FileText, Plus, Minus, Circle, CheckSquare, Square, Loader2 } from "lucide-react";
 import { showToast } from "@/components/ui";
+import { useConfirm } from "@/components/common";
 import type { GitStatus, RemoteInfo } from "@/types";
-import { getGitStatus, getRemotes, gitAdd, gitUnstage, gitCommit, gitPush } from "@/services/git";
+import { getGitStatus, getRemotes, gitAdd, gitUnstage, gitCommit, gitPush, getGitIdentity } from "@/services/git";
 
 interface FileItem {
   path: string;
@@ -35,6 +36,7 @@ export function GitCommitModal({ projectPath, currentRemote, currentBranch, onCl
   const [selectedRemote, setSelectedRemote] = useState<string>("");
   // Whether to push after commit
   const [pushAfterCommit, setPushAfterCommit] = useState(true);
+  const confirm = useConfirm();
 
   useEffect(() => {
     loadGitInfo();
@@ -108,6 +110,25 @@ export function GitCommitModal({ projectPath, currentRemote, currentBranch, onCl
       return;
     }
 
+    // 提交身份与规则不符时先让用户确认，确认后跳过后端的同一检查
+    let allowIdentityMismatch = false;
+    try {
+      const identity = await getGitIdentity(projectPath);
+      if (identity.mismatch) {
+        const ok = await confirm({
+          title: "提交身份可能不正确",
+          description: identity.reason,
+          variant: "warning",
+          confirmLabel: "仍然提交",
+        });
+        if (!ok) return;
+        allowIdentityMismatch = true;
+      }
+    } catch (error) {
+      // 检查失败时交给后端判断
+      console.error("Failed to check git identity:", error);
+    }
+
     try {
       setCommitting(true);
 
@@ -145,7 +166,7 @@ export function GitCommitModal({ projectPath, currentRemote, currentBranch, onCl
 
       // Commit
       try {
-        await gitCommit(projectPath, message.trim());
+        await gitCommit(projectPath, message.trim(), allowIdentityMismatch);
         showToast("success", "提交成功", "代码已提交到本地仓库");
       } catch (error) {
         console.error("Failed to commit:", error);
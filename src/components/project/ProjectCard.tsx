@@ -1,8 +1,9 @@
 import { useState, useEffect } from "react";
+import { convertFileSrc } from "@tauri-apps/api/core";
 import { Plus, Copy, FolderOpen, Terminal, Trash2 } from "lucide-react";
 import type { Project, GitStatus } from "@/types";
 import { getGitStatus, getRemotes } from "@/services/git";
-import { openInTerminal, openInExplorer, openInEditor, toggleFavorite, removeProject, deleteProjectDirectory, updateProject } from "@/services/db";
+import { openInTerminal, openInExplorer, openInEditor, formatLaunchWarning, toggleFavorite, removeProject, deleteProjectDirectory, updateProject } from "@/services/db";
 import { DeleteConfirmDialog } from "./DeleteConfirmDialog";
 import { LabelSelector } from "./LabelSelector";
 import { EditorContextMenu } from "./EditorContextMenu";
@@ -10,6 +11,8 @@ import { TerminalContextMenu } from "./TerminalContextMenu";
 import { useEditorsStore } from "@/stores/editorsStore";
 import { useProjectsStore } from "@/stores/projectsStore";
 import { getEditorForProject, getEditorConfigForProject, getEditorIcon } from "@/utils/editor";
+import { runWithConfirmToken } from "@/utils/confirmToken";
+import { useConfirm } from "@/components/common";
 
 interface ProjectCardProps {
   project: Project;
@@ -27,6 +30,7 @@ export function ProjectCard({ project, onUpdate, onShowDetail, onDelete }: Omit<
   const [showTerminalMenu, setShowTerminalMenu] = useState<{ x: number; y: number } | null>(null);
   const [editingLabels, setEditingLabels] = useState<string[]>([]);
   const [copiedPath, setCopiedPath] = useState(false);
+  const confirm = useConfirm();
   const terminalConfig = useEditorsStore((s) => s.terminalConfig);
   const editors = useEditorsStore((s) => s.editors);
 
@@ -81,7 +85,8 @@ export function ProjectCard({ project, onUpdate, onShowDetail, onDelete }: Omit<
     try {
       const termType = terminalConfig.type === "default" ? undefined : terminalConfig.type;
       const termPath = terminalConfig.paths?.[terminalConfig.type as keyof typeof terminalConfig.paths];
-      await openInTerminal(project.path, termType, terminalConfig.customPath, termPath);
+      const warning = await openInTerminal(project.path, termType, terminalConfig.customPath, termPath);
+      if (warning) alert(`打开终端失败：${formatLaunchWarning(warning)}`);
     } catch (error) {
       console.error("Failed to open terminal:", error);
       alert("打开终端失败：" + error);
@@ -91,7 +96,8 @@ export function ProjectCard({ project, onUpdate, onShowDetail, onDelete }: Omit<
   async function handleOpenExplorer(e: React.MouseEvent) {
     e.stopPropagation();
     try {
-      await openInExplorer(project.path);
+      const warning = await openInExplorer(project.path);
+      if (warning) alert(`打开文件夹失败：${formatLaunchWarning(warning)}`);
     } catch (error) {
       console.error("Failed to open explorer:", error);
       alert("打开文件夹失败：" + error);
@@ -102,7 +108,8 @@ export function ProjectCard({ project, onUpdate, onShowDetail, onDelete }: Omit<
     e.stopPropagation();
     try {
       const editorPath = getEditorForProject(storeProject, editors);
-      await openInEditor(project.path, editorPath);
+      const warning = await openInEditor(project.path, editorPath);
+      if (warning) alert(`打开编辑器失败：${formatLaunchWarning(warning)}`);
     } catch (error) {
       console.error("Failed to open editor:", error);
       alert("打开编辑器失败：" + error);
@@ -131,7 +138,13 @@ export function ProjectCard({ project, onUpdate, onShowDetail, onDelete }: Omit<
   async function handleDelete(deleteDirectory: boolean) {
     try {
       if (deleteDirectory) {
-        await deleteProjectDirectory(project.id);
+        setShowDeleteDialog(false);
+        const done = await runWithConfirmToken(
+          (token) => deleteProjectDirectory(project.id, token),
+          confirm,
+          "确认删除项目目录？",
+        );
+        if (!done) return;
       } else {
         await removeProject(project.id);
       }
@@ -184,9 +197,15 @@ export function ProjectCard({ project, onUpdate, onShowDetail, onDelete }: Omit<
       <div
         onClick={() => onShowDetail?.(project)}
         className="re-card"
+        style={project.color ? { borderLeft: `3px solid ${project.color}` } : undefined}
       >
         <div className="re-card-header">
-          <h4>{project.name}</h4>
+          <h4 className="flex items-center gap-1.5 min-w-0">
+            {project.icon && (
+              <img src={convertFileSrc(project.icon)} alt="" className="w-4 h-4 rounded-sm object-contain flex-shrink-0" />
+            )}
+            <span className="truncate">{project.name}</span>
+          </h4>
           <span
             className="re-star"
             title={project.isFavorite ? "取消收藏" : "收藏"}
@@ -200,6 +219,12 @@ export function ProjectCard({ project, onUpdate, onShowDetail, onDelete }: Omit<
           {getRemoteLabel()} {gitStatus?.branch ? `· ${gitStatus.branch}` : ""}
         </div>
 
+        {project.description && (
+          <div className="px-4 pb-1 text-xs text-gray-500 truncate" title={project.description}>
+            {project.description}
+          </div>
+        )}
+
         <div className="re-card-cat">
           分类：{project.tags.length > 0 ? project.tags.join(", ") : "未分类"}
         </div>
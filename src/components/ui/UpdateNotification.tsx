@@ -5,6 +5,8 @@ import {
   silentCheckForUpdates,
   downloadUpdate,
   installUpdate,
+  scheduleUpdateInstall,
+  deferUpdate,
   getArchStatus,
   openCorrectArchDownload,
   type UpdateInfo,
@@ -42,7 +44,7 @@ export function UpdateNotification() {
       setState("checking");
       const info = await silentCheckForUpdates();
 
-      if (info?.available) {
+      if (info?.available && !info.deferred) {
         setUpdateInfo(info);
         setState("available");
         const notes = getReleaseNotes(info);
@@ -150,6 +152,27 @@ export function UpdateNotification() {
     }
   }
 
+  async function handleInstallOnExit() {
+    try {
+      await scheduleUpdateInstall("onExit");
+      showToast("info", "已安排更新", "将在退出应用时安装");
+      setDismissed(true);
+    } catch (error) {
+      showToast("error", "安排更新失败", String(error));
+    }
+  }
+
+  async function handleDefer() {
+    if (updateInfo?.version) {
+      try {
+        await deferUpdate(updateInfo.version, 7);
+      } catch (error) {
+        console.error("Defer update failed:", error);
+      }
+    }
+    setDismissed(true);
+  }
+
   async function handleOpenReleases() {
     await open(RELEASES_URL);
   }
@@ -272,6 +295,23 @@ export function UpdateNotification() {
             </button>
           )}
 
+          {state === "ready" && (
+            <div className="mt-2 flex gap-2">
+              <button
+                onClick={handleInstallOnExit}
+                className="flex-1 px-3 py-1.5 text-xs text-gray-600 border border-gray-200 rounded-lg hover:bg-gray-50 transition-colors"
+              >
+                退出时安装
+              </button>
+              <button
+                onClick={handleDefer}
+                className="flex-1 px-3 py-1.5 text-xs text-gray-600 border border-gray-200 rounded-lg hover:bg-gray-50 transition-colors"
+              >
+                7 天内不再提示
+              </button>
+            </div>
+          )}
+
           {state === "error" && (
             <button
               onClick={handleOpenReleases}
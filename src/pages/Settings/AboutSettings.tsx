@@ -3,6 +3,8 @@ import { invoke } from "@tauri-apps/api/core";
 import { getVersion } from "@tauri-apps/api/app";
 import { CheckCircle, XCircle, Loader2, ExternalLink, Github, Heart, FolderOpen, Copy, Check, Trash2, Download, AlertCircle } from "lucide-react";
 import { useCopyToClipboard } from "@/hooks/useCopyToClipboard";
+import { compactStatsCache, getStatsCacheSize, type StatsCacheSize } from "@/services/stats";
+import { formatBytes } from "@/services/toolbox";
 
 interface DependencyStatus {
   name: string;
@@ -32,6 +34,9 @@ export function AboutSettings(_props: AboutSettingsProps) {
   const { copy, copiedLabel: copiedPath } = useCopyToClipboard();
   const [clearingLogs, setClearingLogs] = useState(false);
   const [clearLogResult, setClearLogResult] = useState<string | null>(null);
+  const [statsCache, setStatsCache] = useState<StatsCacheSize | null>(null);
+  const [compacting, setCompacting] = useState(false);
+  const [compactResult, setCompactResult] = useState<string | null>(null);
   const [dependencies, setDependencies] = useState<DependencyStatus[]>([
     {
       name: "Git",
@@ -62,8 +67,35 @@ export function AboutSettings(_props: AboutSettingsProps) {
 
     // 获取应用路径
     loadAppPaths();
+
+    loadStatsCache();
   }, []);
 
+  const loadStatsCache = async () => {
+    try {
+      setStatsCache(await getStatsCacheSize());
+    } catch (e) {
+      console.error("Failed to get stats cache size:", e);
+    }
+  };
+
+  const compactStats = async () => {
+    setCompacting(true);
+    setCompactResult(null);
+    try {
+      const report = await compactStatsCache();
+      setCompactResult(
+        `已清理 ${report.removed_projects} 个已移除项目、${report.removed_daily_rows} 条过期记录，` +
+          `数据库 ${formatBytes(report.bytes_before)} → ${formatBytes(report.bytes_after)}`
+      );
+      await loadStatsCache();
+    } catch (e) {
+      setCompactResult(`压缩失败: ${e}`);
+    } finally {
+      setCompacting(false);
+    }
+  };
+
   const loadAppPaths = async () => {
     try {
       const paths = await invoke<AppPaths>("get_app_paths");
@@ -363,6 +395,39 @@ export function AboutSettings(_props: AboutSettingsProps) {
         </p>
       </div>
 
+      {/* 统计缓存 */}
+      {statsCache && (
+        <div className="space-y-4">
+          <h3 className="text-sm font-medium text-gray-500 uppercase tracking-wider">统计缓存</h3>
+
+          <div className="re-card p-4 flex items-center justify-between">
+            <div className="text-xs text-gray-500 space-y-1">
+              <p>
+                数据库 <span className="font-medium text-gray-700">{formatBytes(statsCache.database_bytes)}</span>
+                ，{statsCache.project_count} 个项目、{statsCache.daily_rows} 条按日记录
+              </p>
+              <p>
+                可清理：{statsCache.orphaned_projects} 个已移除项目、{statsCache.expired_daily_rows} 条超过
+                {statsCache.retention_days > 0 ? ` ${statsCache.retention_days} 天` : "保留期"}的记录
+                {statsCache.legacy_file_bytes > 0 && `、旧版缓存文件 ${formatBytes(statsCache.legacy_file_bytes)}`}
+              </p>
+            </div>
+            <button
+              onClick={compactStats}
+              disabled={compacting}
+              className="flex items-center gap-1 px-3 py-1.5 text-sm text-gray-600 border border-gray-200 rounded-lg hover:border-blue-300 hover:text-blue-500 transition-colors disabled:opacity-50"
+            >
+              {compacting ? <Loader2 size={14} className="animate-spin" /> : <Trash2 size={14} />}
+              清理并压缩
+            </button>
+          </div>
+
+          {compactResult && (
+            <p className="text-xs text-green-600 bg-green-50 px-3 py-2 rounded">{compactResult}</p>
+          )}
+        </div>
+      )}
+
       {/* 技术栈 */}
       <div className="space-y-4">
         <h3 className="text-sm font-medium text-gray-500 uppercase tracking-wider">技术栈</h3>
@@ -2,6 +2,7 @@ import { useState } from "react";
 import { Plus, Trash2, FolderOpen, AlertCircle, Check, X, Star } from "lucide-react";
 import { useEditorsStore, type EditorConfig } from "@/stores/editorsStore";
 import { open } from "@tauri-apps/plugin-dialog";
+import { validateToolPath } from "@/services/db";
 
 interface EditorSettingsProps {
   onClose?: () => void;
@@ -28,12 +29,17 @@ export function EditorSettings({ onClose }: EditorSettingsProps) {
     }
   }
 
-  function handleAddEditor() {
+  async function handleAddEditor() {
     if (!newEditor.name.trim() || !newEditor.path.trim()) {
       alert("请填写编辑器名称和路径");
       return;
     }
 
+    const check = await validateToolPath(newEditor.path.trim()).catch(() => null);
+    if (check && !check.valid && !confirm(`${check.message ?? "路径不可用"}，仍要添加吗？`)) {
+      return;
+    }
+
     const editor: EditorConfig = {
       id: Date.now().toString(),
       name: newEditor.name.trim(),
@@ -4,6 +4,7 @@ import { useEditorsStore, type TerminalConfig } from "@/stores/editorsStore";
 import { open } from "@tauri-apps/plugin-dialog";
 import { invoke } from "@tauri-apps/api/core";
 import { detectPlatform } from "@/utils/platform";
+import { validateToolPath } from "@/services/db";
 
 interface TerminalSettingsProps {
   onClose?: () => void;
@@ -54,8 +55,12 @@ export function TerminalSettings({ onClose }: TerminalSettingsProps) {
     setTerminalConfig(newConfig);
   }
 
-  function handleSaveCustomPath() {
+  async function handleSaveCustomPath() {
     if (customPath.trim()) {
+      const check = await validateToolPath(customPath.trim()).catch(() => null);
+      if (check && !check.valid && !confirm(`${check.message ?? "路径不可用"}，仍要保存吗？`)) {
+        return;
+      }
       setTerminalConfig({ ...terminalConfig, type: "custom", customPath: customPath.trim() });
     }
   }
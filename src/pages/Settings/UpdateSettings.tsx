@@ -3,6 +3,9 @@ import { Download, RefreshCw, CheckCircle, AlertCircle, Loader2, ExternalLink }
 import {
   checkForUpdates,
   downloadAndInstallUpdate,
+  getUpdateChannel,
+  setUpdateChannel,
+  type UpdateChannel,
   getArchStatus,
   openCorrectArchDownload,
   type UpdateInfo,
@@ -21,13 +24,26 @@ export function UpdateSettings() {
   const [error, setError] = useState<string | null>(null);
   const [currentVersion, setCurrentVersion] = useState<string>("...");
   const [archMismatch, setArchMismatch] = useState<ArchStatus | null>(null);
+  const [channel, setChannel] = useState<UpdateChannel>("stable");
   const autoUpdate = useSettingsStore((state) => state.autoUpdate);
   const setAutoUpdate = useSettingsStore((state) => state.setAutoUpdate);
 
   useEffect(() => {
     getVersion().then(setCurrentVersion).catch(() => setCurrentVersion("未知"));
+    getUpdateChannel().then(setChannel).catch(() => {});
   }, []);
 
+  async function handleChannelChange(next: UpdateChannel) {
+    try {
+      setChannel(await setUpdateChannel(next));
+      // 通道变了，之前的检查结果不再有效
+      setUpdateInfo(null);
+      setError(null);
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
   async function handleCheckUpdate() {
     setChecking(true);
     setError(null);
@@ -185,6 +201,27 @@ export function UpdateSettings() {
         </div>
       )}
 
+      {/* 更新通道 */}
+      <div className="p-4 bg-gray-50 border border-gray-200 rounded-lg">
+        <div className="flex items-center justify-between">
+          <div>
+            <p className="text-sm font-medium text-gray-900">更新通道</p>
+            <p className="text-xs text-gray-500 mt-1">
+              {channel === "beta" ? "测试版：更早获得新功能，可能不够稳定" : "正式版：只接收正式发布的版本"}
+            </p>
+          </div>
+          <select
+            value={channel}
+            onChange={(e) => handleChannelChange(e.target.value as UpdateChannel)}
+            disabled={checking || downloading}
+            className="px-3 py-1.5 text-sm border border-gray-200 rounded-lg bg-white disabled:opacity-50"
+          >
+            <option value="stable">正式版</option>
+            <option value="beta">测试版</option>
+          </select>
+        </div>
+      </div>
+
       {/* 自动更新开关 */}
       <div className="p-4 bg-gray-50 border border-gray-200 rounded-lg">
         <div className="flex items-center justify-between">
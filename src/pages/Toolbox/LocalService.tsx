@@ -1,8 +1,9 @@
-import { FileCode, Globe, Plus, RefreshCw } from "lucide-react";
+import { FileCode, Globe, Plus, RefreshCw, Upload } from "lucide-react";
 import { Button } from "@/components/ui";
 import { LoadingSpinner } from "@/components/common";
 import { ToolPanelHeader } from "./index";
 import { DeleteConfirmDialog } from "./local-service/DeleteConfirmDialog";
+import { MetricsToggle } from "./local-service/MetricsToggle";
 import { NginxConfigDialog } from "./local-service/NginxConfigDialog";
 import { ServiceFormDialog } from "./local-service/ServiceFormDialog";
 import { ServiceList } from "./local-service/ServiceList";
@@ -40,6 +41,10 @@ export function LocalService({ onBack }: LocalServiceProps) {
               <RefreshCw size={16} className={service.loading ? "animate-spin mr-2" : "mr-2"} />
               刷新
             </Button>
+            <Button onClick={service.handleImportServer} variant="secondary" size="sm">
+              <Upload size={16} className="mr-2" />
+              导入配置
+            </Button>
             <Button onClick={service.openCreateDialog} variant="primary" size="sm">
               <Plus size={16} className="mr-2" />
               创建服务
@@ -66,10 +71,13 @@ export function LocalService({ onBack }: LocalServiceProps) {
                 </button>
               ))}
             </div>
-            <Button onClick={service.handleOpenNginxManual} variant="secondary" size="sm">
-              <FileCode size={16} className="mr-2" />
-              nginx 手册
-            </Button>
+            <div className="flex items-center gap-3">
+              <MetricsToggle />
+              <Button onClick={service.handleOpenNginxManual} variant="secondary" size="sm">
+                <FileCode size={16} className="mr-2" />
+                nginx 手册
+              </Button>
+            </div>
           </div>
 
           {service.loading && service.servers.length === 0 && service.forwardRules.length === 0 ? (
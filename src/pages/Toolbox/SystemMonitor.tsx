@@ -31,6 +31,8 @@ import { SystemMonitorOccupation } from "./SystemMonitorOccupation";
 import { SystemMonitorScan } from "./SystemMonitorScan";
 import { SystemMonitorProcess } from "./SystemMonitorProcess";
 import { SystemMonitorKillConfirm } from "./SystemMonitorKillConfirm";
+import { useConfirm } from "@/components/common";
+import { runWithConfirmToken } from "@/utils/confirmToken";
 
 interface SystemMonitorProps {
   onBack: () => void;
@@ -65,6 +67,7 @@ export function SystemMonitor({ onBack }: SystemMonitorProps) {
 
   // 终止进程确认：source 用于 kill 成功后刷新对应 tab 的数据
   const [killTarget, setKillTarget] = useState<KillTarget | null>(null);
+  const confirm = useConfirm();
 
   useEffect(() => {
     getCommonPorts().then(setCommonPorts).catch(console.error);
@@ -173,8 +176,14 @@ export function SystemMonitor({ onBack }: SystemMonitorProps) {
   async function handleKill(pid: number, force: boolean) {
     const source = killTarget?.source;
     try {
-      await killProcess(pid, force);
       setKillTarget(null);
+      // 强制结束需要后端二次确认，普通结束直接执行
+      const done = await runWithConfirmToken(
+        (token) => killProcess(pid, force, token),
+        confirm,
+        "确认强制结束进程？",
+      );
+      if (!done) return;
       if (source === "occupation") {
         loadOccupations();
       } else {
@@ -1,4 +1,4 @@
-import { useState, useEffect } from "react";
+import { useState, useEffect, useRef } from "react";
 import {
   Search,
   Activity,
@@ -17,6 +17,8 @@ import {
 import { useSettingsStore } from "@/stores/settingsStore";
 import { useUiStore } from "@/stores/uiStore";
 import { MacWindowControls } from "@/components/layout/MacWindowControls";
+import { invoke } from "@tauri-apps/api/core";
+import { saveUiState } from "@/stores/_persistence";
 import type { ToolType } from "@/types/toolbox";
 
 // 子页面组件
@@ -128,6 +130,28 @@ export function ToolboxPage() {
   const [activeTool, setActiveTool] = useState<ToolType | null>(null);
   const [searchQuery, setSearchQuery] = useState("");
 
+  // 回到上次打开的工具（外部导航请求优先）；读取完成前不写回，避免覆盖
+  const toolRestored = useRef(false);
+  useEffect(() => {
+    invoke<{ active_toolbox_tool?: string | null }>("get_ui_state")
+      .then((state) => {
+        const tool = state.active_toolbox_tool as ToolType | null | undefined;
+        if (tool && tools.some((t) => t.id === tool)) {
+          setActiveTool((current) => current ?? tool);
+        }
+      })
+      .catch(() => {})
+      .finally(() => {
+        toolRestored.current = true;
+      });
+  }, []);
+
+  useEffect(() => {
+    if (toolRestored.current) {
+      saveUiState({ active_toolbox_tool: activeTool ?? "" });
+    }
+  }, [activeTool]);
+
   // 响应外部导航请求（如全局快捷键）
   useEffect(() => {
     if (toolboxNavigateTarget) {
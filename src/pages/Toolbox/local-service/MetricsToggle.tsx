@@ -0,0 +1,50 @@
+import { useEffect, useState } from "react";
+import { Activity } from "lucide-react";
+import { showToast } from "@/components/ui/Toast";
+import { getMetricsStatus, setMetricsEndpoint } from "@/services/toolbox";
+import type { MetricsStatus } from "@/types/toolbox";
+
+/** Prometheus 指标端点开关：转发、Web 服务与下载的计数供 Grafana 抓取 */
+export function MetricsToggle() {
+  const [status, setStatus] = useState<MetricsStatus | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    getMetricsStatus().then(setStatus).catch((e) => console.error("读取指标端点状态失败:", e));
+  }, []);
+
+  async function toggle() {
+    if (!status) return;
+    setBusy(true);
+    try {
+      setStatus(await setMetricsEndpoint(!status.enabled, status.port));
+    } catch (e) {
+      showToast("error", "切换指标端点失败", String(e));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  async function copyEndpoint() {
+    if (!status?.endpoint) return;
+    await navigator.clipboard.writeText(status.endpoint);
+    showToast("success", "已复制抓取地址");
+  }
+
+  if (!status) return null;
+
+  return (
+    <div className="flex items-center gap-2 text-xs text-gray-500 dark:text-gray-400">
+      <Activity size={14} className={status.running ? "text-green-500" : undefined} />
+      <label className="flex items-center gap-1.5 cursor-pointer">
+        <input type="checkbox" checked={status.enabled} disabled={busy} onChange={toggle} />
+        Prometheus 指标
+      </label>
+      {status.endpoint && (
+        <button onClick={copyEndpoint} className="font-mono hover:text-blue-500" title="复制抓取地址">
+          {status.endpoint}
+        </button>
+      )}
+    </div>
+  );
+}
@@ -33,12 +33,30 @@ interface ServiceFormDialogProps {
   onFormDocPathChange: (value: string) => void;
   onSelectDir: () => void;
   onAddProxy: () => void;
-  onUpdateProxy: (index: number, field: "prefix" | "target", value: string) => void;
+  onUpdateProxy: <K extends keyof ProxyConfig>(index: number, field: K, value: ProxyConfig[K]) => void;
   onRemoveProxy: (index: number) => void;
   onCancel: () => void;
   onSubmit: () => void;
 }
 
+/** 请求头 / 响应头按 "Name: value" 每行一个编辑 */
+function formatHeaderLines(headers?: Record<string, string>): string {
+  return Object.entries(headers ?? {})
+    .map(([name, value]) => `${name}: ${value}`)
+    .join("\n");
+}
+
+function parseHeaderLines(text: string): Record<string, string> {
+  const headers: Record<string, string> = {};
+  for (const line of text.split("\n")) {
+    const pos = line.indexOf(":");
+    if (pos <= 0) continue;
+    const name = line.slice(0, pos).trim();
+    if (name) headers[name] = line.slice(pos + 1).trim();
+  }
+  return headers;
+}
+
 export function ServiceFormDialog({
   serviceType,
   editingServer,
@@ -224,6 +242,35 @@ export function ServiceFormDialog({
                             onChange={(e) => onUpdateProxy(index, "target", e.target.value)}
                             placeholder="代理目标地址，如: http://192.168.1.100:8080/api"
                           />
+                          <div className="flex items-center gap-3">
+                            <label className="flex items-center gap-1.5 text-xs text-gray-600 dark:text-gray-400 whitespace-nowrap">
+                              <input
+                                type="checkbox"
+                                checked={proxy.stripPrefix ?? true}
+                                onChange={(e) => onUpdateProxy(index, "stripPrefix", e.target.checked)}
+                              />
+                              去掉前缀
+                            </label>
+                            <Input
+                              value={proxy.addPrefix ?? ""}
+                              onChange={(e) => onUpdateProxy(index, "addPrefix", e.target.value || null)}
+                              placeholder="追加前缀（可选），如: /v1"
+                            />
+                          </div>
+                          <textarea
+                            defaultValue={formatHeaderLines(proxy.requestHeaders)}
+                            onBlur={(e) => onUpdateProxy(index, "requestHeaders", parseHeaderLines(e.target.value))}
+                            placeholder="请求头（每行一个），如: Authorization: Bearer xxx"
+                            rows={2}
+                            className="w-full px-3 py-2 text-xs font-mono border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700 text-gray-900 dark:text-white"
+                          />
+                          <textarea
+                            defaultValue={formatHeaderLines(proxy.responseHeaders)}
+                            onBlur={(e) => onUpdateProxy(index, "responseHeaders", parseHeaderLines(e.target.value))}
+                            placeholder="响应头（每行一个），如: X-Frame-Options: DENY"
+                            rows={2}
+                            className="w-full px-3 py-2 text-xs font-mono border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700 text-gray-900 dark:text-white"
+                          />
                         </div>
                         <button
                           onClick={() => onRemoveProxy(index)}
@@ -234,7 +281,8 @@ export function ServiceFormDialog({
                       </div>
                     ))}
                     <p className="text-xs text-gray-400">
-                      访问本地路径的请求将被转发到代理目标地址，如: /api/* → http://192.168.1.100:8080/api/*
+                      访问本地路径的请求将被转发到代理目标地址，如: /api/* → http://192.168.1.100:8080/api/*；
+                      不去掉前缀时转发为 …/api/api/*，追加前缀 /v1 时转发为 …/api/v1/*
                     </p>
                   </div>
                 )}
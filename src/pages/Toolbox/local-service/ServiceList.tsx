@@ -2,6 +2,7 @@ import {
   ArrowLeftRight,
   Check,
   Copy,
+  Download,
   Edit2,
   ExternalLink,
   FileCode,
@@ -106,6 +107,13 @@ export function ServiceList({ servers, rules, copiedId, callbacks }: ServiceList
               >
                 <FileCode size={16} />
               </button>
+              <button
+                onClick={() => callbacks.onExportServer(server)}
+                className="p-2 hover:bg-gray-100 dark:hover:bg-gray-700 rounded-lg transition-colors text-gray-500"
+                title="导出配置包"
+              >
+                <Download size={16} />
+              </button>
               {server.status === "running" ? (
                 <>
                   <button
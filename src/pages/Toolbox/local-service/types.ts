@@ -25,6 +25,7 @@ export interface ServiceListCallbacks {
   onCopyServerUrl: (server: ServerConfig) => void;
   onCopyForwardUrl: (rule: ForwardRule) => void;
   onGenerateNginx: (server: ServerConfig) => void;
+  onExportServer: (server: ServerConfig) => void;
   onStartServer: (serverId: string) => void;
   onStopServer: (serverId: string) => void;
   onEditServer: (server: ServerConfig) => void;
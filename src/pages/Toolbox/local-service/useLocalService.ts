@@ -1,13 +1,15 @@
 import { useEffect, useState } from "react";
 import { open, save } from "@tauri-apps/plugin-dialog";
 import { open as shellOpen } from "@tauri-apps/plugin-shell";
-import { writeTextFile } from "@tauri-apps/plugin-fs";
+import { readTextFile, writeTextFile } from "@tauri-apps/plugin-fs";
 import {
   addForwardRule,
   createServer,
+  exportServerConfig,
   generateNginxConfig,
   getForwardRules,
   getServers,
+  importServerConfig,
   removeForwardRule,
   removeServer,
   startForwarding,
@@ -20,7 +22,7 @@ import {
 import type { ForwardRule, ForwardRuleInput, ProxyConfig, ServerConfig, ServerConfigInput } from "@/types/toolbox";
 import { NGINX_MANUAL_TEMPLATE } from "./nginxSnippets";
 import type { DeleteConfirmState, NginxPreviewState, ServiceType, TabType } from "./types";
-import { getForwardUrl, getServerUrl, nginxFileName } from "./utils";
+import { bundleFileName, getForwardUrl, getServerUrl, nginxFileName } from "./utils";
 
 export function useLocalService() {
   const [activeTab, setActiveTab] = useState<TabType>("all");
@@ -120,9 +122,9 @@ export function useLocalService() {
     setFormProxies([...formProxies, { prefix: "/api", target: "" }]);
   }
 
-  function updateProxyRule(index: number, field: "prefix" | "target", value: string) {
+  function updateProxyRule<K extends keyof ProxyConfig>(index: number, field: K, value: ProxyConfig[K]) {
     const next = [...formProxies];
-    next[index][field] = value;
+    next[index] = { ...next[index], [field]: value };
     setFormProxies(next);
   }
 
@@ -344,6 +346,48 @@ export function useLocalService() {
     }
   }
 
+  async function handleExportServer(server: ServerConfig) {
+    try {
+      const content = await exportServerConfig(server.id);
+      const path = await save({
+        title: "导出服务配置",
+        defaultPath: bundleFileName(server),
+        filters: [{ name: "JSON", extensions: ["json"] }],
+      });
+      if (!path) return;
+      await writeTextFile(path, content);
+    } catch (error) {
+      console.error("导出服务配置失败:", error);
+      alert(`导出服务配置失败: ${error}`);
+    }
+  }
+
+  async function handleImportServer() {
+    try {
+      const picked = await open({
+        multiple: false,
+        title: "导入服务配置",
+        filters: [{ name: "JSON", extensions: ["json"] }],
+      });
+      if (!picked) return;
+      const content = await readTextFile(picked as string);
+      try {
+        await importServerConfig(content);
+      } catch (error) {
+        // 根目录在本机不存在时让用户重新选择
+        if (!String(error).includes("目录不存在")) throw error;
+        if (!confirm(`${error}\n\n是否选择本机的静态文件目录？`)) return;
+        const dir = await open({ directory: true, multiple: false, title: "选择静态文件目录" });
+        if (!dir) return;
+        await importServerConfig(content, dir as string);
+      }
+      await loadAll();
+    } catch (error) {
+      console.error("导入服务配置失败:", error);
+      alert(`导入服务配置失败: ${error}`);
+    }
+  }
+
   function handleOpenNginxManual() {
     setNginxPreview({
       title: "nginx 配置手册",
@@ -408,6 +452,7 @@ export function useLocalService() {
     loadAll,
     openCreateDialog,
     handleOpenNginxManual,
+    handleImportServer,
     serviceListCallbacks: {
       getServerUrl,
       getForwardUrl,
@@ -416,6 +461,7 @@ export function useLocalService() {
       onCopyServerUrl: handleCopyUrl,
       onCopyForwardUrl: handleCopyForwardUrl,
       onGenerateNginx: handleGenerateNginx,
+      onExportServer: handleExportServer,
       onStartServer: handleStartServer,
       onStopServer: handleStopServer,
       onEditServer: openEditServerDialog,
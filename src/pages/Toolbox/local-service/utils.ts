@@ -25,3 +25,8 @@ export function nginxFileName(server: ServerConfig): string {
   const safeName = server.name.trim().replace(/[^\w\u4e00-\u9fa5.-]+/g, "-") || "service";
   return `${safeName}-nginx.conf`;
 }
+
+export function bundleFileName(server: ServerConfig): string {
+  const safeName = server.name.trim().replace(/[^\w\u4e00-\u9fa5.-]+/g, "-") || "service";
+  return `${safeName}.server.json`;
+}
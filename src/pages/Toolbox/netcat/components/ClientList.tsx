@@ -1,6 +1,7 @@
 // 已连接客户端列表组件
 
 import { Users, Monitor, X } from "lucide-react";
+import { formatSpeed } from "@/services/toolbox";
 import type { ConnectedClient } from "@/types/toolbox";
 
 interface ClientListProps {
@@ -30,6 +31,11 @@ export default function ClientList({ clients, onDisconnectClient }: ClientListPr
             <span className="text-gray-400 dark:text-gray-500 text-xs">
               {new Date(client.connectedAt).toLocaleTimeString()}
             </span>
+            {(client.sendRate >= 1 || client.recvRate >= 1) && (
+              <span className="text-gray-500 dark:text-gray-400 text-xs">
+                ↑{formatSpeed(Math.round(client.sendRate))} ↓{formatSpeed(Math.round(client.recvRate))}
+              </span>
+            )}
             <button
               onClick={() => onDisconnectClient(client.id)}
               className="p-0.5 hover:bg-red-100 dark:hover:bg-red-900/30 rounded text-red-500"
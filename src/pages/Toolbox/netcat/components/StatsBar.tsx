@@ -1,7 +1,7 @@
 // 统计栏组件
 
-import { ArrowUpRight, ArrowDownLeft, Copy, Trash, RefreshCw } from "lucide-react";
-import { formatBytes } from "@/services/toolbox";
+import { ArrowUpRight, ArrowDownLeft, Copy, Trash, RefreshCw, AlertTriangle } from "lucide-react";
+import { formatBytes, formatSpeed } from "@/services/toolbox";
 import type { NetcatSession, NetcatMessage } from "@/types/toolbox";
 
 interface StatsBarProps {
@@ -28,14 +28,25 @@ export default function StatsBar({
       <div className="flex items-center gap-1.5 text-gray-600 dark:text-gray-400">
         <ArrowUpRight size={14} className="text-green-500" />
         发送: <span className="font-medium text-gray-900 dark:text-white">{formatBytes(session.bytesSent)}</span>
+        {session.sendRate >= 1 && <span className="text-xs text-gray-500">{formatSpeed(session.sendRate)}</span>}
       </div>
       <div className="flex items-center gap-1.5 text-gray-600 dark:text-gray-400">
         <ArrowDownLeft size={14} className="text-blue-500" />
         接收: <span className="font-medium text-gray-900 dark:text-white">{formatBytes(session.bytesReceived)}</span>
+        {session.recvRate >= 1 && <span className="text-xs text-gray-500">{formatSpeed(session.recvRate)}</span>}
       </div>
       <div className="text-gray-600 dark:text-gray-400">
         消息: <span className="font-medium text-gray-900 dark:text-white">{session.messageCount}</span>
       </div>
+      {session.captureStopped && (
+        <div
+          className="flex items-center gap-1.5 text-amber-600 dark:text-amber-400"
+          title="清空历史后恢复记录"
+        >
+          <AlertTriangle size={14} />
+          已达 {session.historyLimit} 条上限，停止记录新消息
+        </div>
+      )}
       <div className="flex items-center gap-2 ml-auto">
         {/* 复制所有消息 */}
         <button
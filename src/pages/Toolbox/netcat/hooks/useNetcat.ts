@@ -296,6 +296,12 @@ export function useNetcat() {
               )
             );
             break;
+
+          case "captureStopped":
+            setSessions((prev) =>
+              prev.map((s) => (s.id === data.sessionId ? { ...s, captureStopped: true } : s))
+            );
+            break;
         }
       });
 
@@ -377,14 +383,18 @@ export function useNetcat() {
               console.error("HTTP headers 解析失败，应为 JSON 格式");
             }
           }
-          const data = await netcatFetchHttp({
+          const res = await netcatFetchHttp({
             url: config.httpUrl,
             method: config.httpMethod || "GET",
             headers,
             body: config.httpBody || undefined,
             jsonPath: config.httpJsonPath || undefined,
           });
-          return data || null;
+          if (res.status >= 400) {
+            console.error(`HTTP 获取失败: ${res.status} ${res.statusText}`);
+            return null;
+          }
+          return (res.extracted ?? res.body) || null;
         } catch (err) {
           console.error("HTTP 获取失败:", err);
           return null;
@@ -686,6 +696,9 @@ export function useNetcat() {
     try {
       await netcatClearMessages(selectedSessionId);
       setMessages([]);
+      setSessions((prev) =>
+        prev.map((s) => (s.id === selectedSessionId ? { ...s, captureStopped: false } : s))
+      );
     } catch (err) {
       console.error("清空消息失败:", err);
     }
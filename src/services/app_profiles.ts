@@ -0,0 +1,51 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface AppProfile {
+  id: string;
+  name: string;
+  /** 档案内可见的项目；null 为全部项目 */
+  projectIds: string[] | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface AppProfileList {
+  /** 当前档案 id，null 为默认档案 */
+  active: string | null;
+  profiles: AppProfile[];
+}
+
+export async function listAppProfiles(): Promise<AppProfileList> {
+  return invoke("list_app_profiles");
+}
+/** copyCurrent 为 true 时复制当前档案的项目范围、服务、转发、编辑器与终端设置 */
+export async function createAppProfile(name: string, copyCurrent?: boolean): Promise<AppProfile> {
+  return invoke("create_app_profile", { name, copyCurrent });
+}
+export async function renameAppProfile(id: string, name: string): Promise<AppProfile> {
+  return invoke("rename_app_profile", { id, name });
+}
+/** projectIds 为 null 时该档案显示全部项目 */
+export async function setAppProfileProjects(
+  id: string,
+  projectIds: string[] | null
+): Promise<AppProfile> {
+  return invoke("set_app_profile_projects", { id, projectIds });
+}
+export async function deleteAppProfile(id: string): Promise<void> {
+  return invoke("delete_app_profile", { id });
+}
+/**
+ * 切换档案，id 为 null 回到默认档案；运行中的服务和转发会被停止。
+ * 完成后广播 `app-profile-switched`，各页面需重新加载项目与工具箱数据
+ */
+export async function switchAppProfile(id: string | null): Promise<AppProfileList> {
+  return invoke("switch_app_profile", { id });
+}
+export async function exportAppProfile(id: string | null, filePath: string): Promise<void> {
+  return invoke("export_app_profile", { id, filePath });
+}
+/** name 为空时沿用文件中的名称 */
+export async function importAppProfile(filePath: string, name?: string): Promise<AppProfile> {
+  return invoke("import_app_profile", { filePath, name });
+}
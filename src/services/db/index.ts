@@ -1,5 +1,6 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { Project, CreateProjectInput, UpdateProjectInput } from "@/types";
+import type { Project, CreateProjectInput, UpdateProjectInput, ConfirmRequest } from "@/types";
+import type { SshAuthMethod } from "@/types/toolbox";
 
 export async function addProject(input: CreateProjectInput): Promise<Project> {
   return invoke("create_project", { input });
@@ -9,8 +10,11 @@ export async function removeProject(id: string): Promise<void> {
   return invoke("delete_project", { id });
 }
 
-export async function deleteProjectDirectory(id: string): Promise<void> {
-  return invoke("delete_project_directory", { id });
+export async function deleteProjectDirectory(
+  id: string,
+  confirmToken?: string
+): Promise<ConfirmRequest | null> {
+  return invoke("delete_project_directory", { id, confirmToken });
 }
 
 export async function getProjects(): Promise<Project[]> {
@@ -53,11 +57,56 @@ export async function setProjectClaudeEnv(id: string, claudeEnvName: string | nu
   return invoke("set_project_claude_env", { id, claudeEnvName });
 }
 
-export async function openInEditor(path: string, editorPath?: string): Promise<void> {
+// 图标文件会被复制到数据目录缓存
+export async function setProjectIcon(id: string, sourcePath: string): Promise<Project> {
+  return invoke("set_project_icon", { id, sourcePath });
+}
+
+export async function clearProjectIcon(id: string): Promise<Project> {
+  return invoke("clear_project_icon", { id });
+}
+
+export async function setProjectColor(id: string, color: string | null): Promise<Project> {
+  return invoke("set_project_color", { id, color });
+}
+
+export async function setProjectDescription(id: string, description: string | null): Promise<Project> {
+  return invoke("set_project_description", { id, description });
+}
+
+export async function setProjectGitExecutable(id: string, executable: string | null): Promise<Project> {
+  return invoke("set_project_git_executable", { id, executable });
+}
+
+// 统计提交使用的分支："*" 为全部本地分支，null 为当前检出分支
+export async function setProjectStatsBranch(id: string, branch: string | null): Promise<Project> {
+  return invoke("set_project_stats_branch", { id, branch });
+}
+
+// 校验 git 可执行文件，返回版本号
+export async function checkGitExecutable(executable: string): Promise<string> {
+  return invoke("check_git_executable", { executable });
+}
+
+// 外部程序启动后立即异常退出时返回的警告
+export interface LaunchWarning {
+  program: string;
+  exitCode: number | null;
+  stderr: string;
+  message: string;
+}
+
+export interface ToolPathCheck {
+  valid: boolean;
+  resolvedPath: string | null;
+  message: string | null;
+}
+
+export async function openInEditor(path: string, editorPath?: string): Promise<LaunchWarning | null> {
   return invoke("open_in_editor", { path, editorPath });
 }
 
-export async function openInExplorer(path: string): Promise<void> {
+export async function openInExplorer(path: string): Promise<LaunchWarning | null> {
   return invoke("open_in_explorer", { path });
 }
 
@@ -65,11 +114,240 @@ export async function openInTerminal(
   path: string,
   terminalType?: string,
   customPath?: string,
-  terminalPath?: string
-): Promise<void> {
-  return invoke("open_in_terminal", { path, terminalType, customPath, terminalPath });
+  terminalPath?: string,
+  command?: string
+): Promise<LaunchWarning | null> {
+  return invoke("open_in_terminal", { path, terminalType, customPath, terminalPath, command });
+}
+
+// 检查编辑器 / 终端路径是否可用（命令名按 PATH 查找）
+export async function validateToolPath(path: string): Promise<ToolPathCheck> {
+  return invoke("validate_tool_path", { path });
+}
+
+export function formatLaunchWarning(warning: LaunchWarning): string {
+  return warning.stderr ? `${warning.message}\n${warning.stderr}` : warning.message;
 }
 
 export async function openUrl(url: string): Promise<void> {
   return invoke("open_url", { url });
 }
+
+// ============== 环境检查 ==============
+
+export type DoctorStatus = "ok" | "info" | "warning" | "error" | "skipped";
+
+export interface DoctorFix {
+  label: string;
+  // url: 在浏览器打开；command: 需在终端执行的命令，前端提供复制
+  kind: "url" | "command";
+  value: string;
+}
+
+export interface DoctorCheck {
+  id: "git" | "node" | "wsl" | "long_paths" | "data_dir" | "ports";
+  title: string;
+  status: DoctorStatus;
+  detail: string;
+  fixes: DoctorFix[];
+}
+
+export interface DoctorReport {
+  os: string;
+  checks: DoctorCheck[];
+  hasErrors: boolean;
+  checkedAt: string;
+}
+
+// 首次启动引导页调用：git / node / WSL / 长路径 / 数据目录 / 端口
+export async function runEnvironmentDoctor(): Promise<DoctorReport> {
+  return invoke("run_environment_doctor");
+}
+
+// ============== 批量操作 ==============
+
+export type BulkAction = "fetch" | "prune" | "refreshStats" | "addLabel" | "removeLabel";
+
+export interface BulkItemResult {
+  projectId: string;
+  projectName: string;
+  projectPath: string;
+  status: "pending" | "success" | "failed" | "skipped";
+  message: string | null;
+  durationMs: number | null;
+}
+
+export interface BulkOperation {
+  id: string;
+  action: BulkAction;
+  label: string | null;
+  status: "running" | "completed" | "cancelled" | "interrupted";
+  items: BulkItemResult[];
+  createdAt: string;
+  updatedAt: string;
+  finishedAt: string | null;
+}
+
+// 事件 bulk-operation-progress 的负载；结束时推送 bulk-operation-finished（BulkOperation）
+export interface BulkProgress {
+  operationId: string;
+  index: number;
+  done: number;
+  total: number;
+  item: BulkItemResult;
+}
+
+export async function startBulkOperation(
+  action: BulkAction,
+  projectIds: string[],
+  label?: string
+): Promise<BulkOperation> {
+  return invoke("start_bulk_operation", { action, projectIds, label });
+}
+
+export async function cancelBulkOperation(id: string): Promise<void> {
+  return invoke("cancel_bulk_operation", { id });
+}
+
+export async function resumeBulkOperation(id: string, retryFailed?: boolean): Promise<BulkOperation> {
+  return invoke("resume_bulk_operation", { id, retryFailed });
+}
+
+export async function listBulkOperations(): Promise<BulkOperation[]> {
+  return invoke("list_bulk_operations");
+}
+
+export async function getBulkOperation(id: string): Promise<BulkOperation> {
+  return invoke("get_bulk_operation", { id });
+}
+
+export async function deleteBulkOperation(id: string): Promise<void> {
+  return invoke("delete_bulk_operation", { id });
+}
+
+// ============== 项目快捷链接 ==============
+
+export type ProjectLinkKind = "issues" | "staging" | "production" | "ci" | "docs" | "other";
+
+export interface ProjectLink {
+  id: string;
+  projectId: string;
+  title: string;
+  url: string;
+  kind: ProjectLinkKind;
+  sortOrder: number;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface ProjectLinkInput {
+  id?: string; // 为空时新建
+  projectId: string;
+  title: string;
+  url: string;
+  kind?: ProjectLinkKind;
+  sortOrder?: number;
+}
+
+export async function listProjectLinks(projectId: string): Promise<ProjectLink[]> {
+  return invoke("list_project_links", { projectId });
+}
+
+export async function saveProjectLink(input: ProjectLinkInput): Promise<ProjectLink> {
+  return invoke("save_project_link", { input });
+}
+
+export async function deleteProjectLink(projectId: string, linkId: string): Promise<void> {
+  return invoke("delete_project_link", { projectId, linkId });
+}
+
+export async function openProjectLink(projectId: string, linkId: string): Promise<void> {
+  return invoke("open_project_link", { projectId, linkId });
+}
+
+// ============== 静态站点部署 ==============
+
+/** 后端返回的目标中，已保存的密码 / 口令 / Secret Key 以此代替；保存时原样传回表示不修改 */
+export const DEPLOY_SECRET_MASK = "********";
+
+export type DeployDestination =
+  | { type: "ssh"; host: string; port?: number; user?: string; auth: SshAuthMethod; remoteDir: string }
+  | {
+      type: "s3";
+      /** 为空时使用 AWS 区域地址 */
+      endpoint?: string;
+      region: string;
+      bucket: string;
+      prefix?: string;
+      accessKeyId: string;
+      secretAccessKey: string;
+      /** 路径风格地址（MinIO 等） */
+      pathStyle?: boolean;
+    }
+  | { type: "rsync"; target: string; sshPort?: number | null; keyPath?: string | null; delete?: boolean };
+
+export interface DeployTarget {
+  id: string;
+  projectId: string;
+  name: string;
+  /** 要上传的目录，相对项目目录 */
+  sourceDir: string;
+  destination: DeployDestination;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface DeployTargetInput {
+  id?: string; // 为空时新建
+  projectId: string;
+  name: string;
+  sourceDir?: string; // 默认 dist
+  destination: DeployDestination;
+}
+
+export interface DeployRun {
+  id: string;
+  projectId: string;
+  targetId: string;
+  targetName: string;
+  kind: DeployDestination["type"];
+  status: "running" | "success" | "failed";
+  files: number;
+  bytes: number;
+  message?: string | null;
+  startedAt: string;
+  finishedAt?: string | null;
+}
+
+/** `deploy-progress` 事件；结束时推送 `deploy-finished`（DeployRun） */
+export interface DeployProgress {
+  runId: string;
+  projectId: string;
+  targetId: string;
+  filesDone: number;
+  filesTotal: number;
+  bytesDone: number;
+  bytesTotal: number;
+  current?: string | null;
+}
+
+export async function listDeployTargets(projectId: string): Promise<DeployTarget[]> {
+  return invoke("list_deploy_targets", { projectId });
+}
+
+export async function saveDeployTarget(input: DeployTargetInput): Promise<DeployTarget> {
+  return invoke("save_deploy_target", { input });
+}
+
+export async function deleteDeployTarget(projectId: string, targetId: string): Promise<void> {
+  return invoke("delete_deploy_target", { projectId, targetId });
+}
+
+/** 开始部署，立即返回 running 状态的记录，进度见 deploy-progress 事件 */
+export async function runDeploy(targetId: string): Promise<DeployRun> {
+  return invoke("run_deploy", { targetId });
+}
+
+export async function listDeployHistory(projectId: string, limit?: number): Promise<DeployRun[]> {
+  return invoke("list_deploy_history", { projectId, limit });
+}
@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import type {
   GitStatus,
   CommitInfo,
@@ -6,6 +7,7 @@ import type {
   BranchInfo,
   RemoteInfo,
   GitRepo,
+  RepoOperation,
 } from "@/types";
 
 export interface ConflictFileContent {
@@ -16,14 +18,45 @@ export interface ConflictFileContent {
   worktree?: string;
 }
 
-export async function scanDirectory(path: string, depth?: number): Promise<GitRepo[]> {
-  return invoke("scan_directory", { path, depth });
+export interface ScanOptions {
+  /** 只收录匹配这些模式的仓库（相对扫描目录，gitignore 风格） */
+  include?: string[];
+  /** 跳过匹配这些模式的目录 */
+  exclude?: string[];
+  ignoreCase?: boolean;
+  /** 默认 true */
+  followSymlinks?: boolean;
+  maxRepos?: number;
+}
+
+export async function scanDirectory(
+  path: string,
+  depth?: number,
+  options?: ScanOptions
+): Promise<GitRepo[]> {
+  return invoke("scan_directory", { path, depth, options });
 }
 
 export async function getGitStatus(path: string): Promise<GitStatus> {
   return invoke("get_git_status", { path });
 }
 
+// 仓库的写操作队列；waiting 中第 N 个即排在第 N 位
+export interface GitQueueState {
+  path: string;
+  running?: string;
+  waiting: string[];
+}
+
+export async function getGitQueue(path: string): Promise<GitQueueState> {
+  return invoke("get_git_queue", { path });
+}
+
+// 队列变化时触发（开始排队、开始执行、执行结束）
+export function onGitQueueChanged(handler: (state: GitQueueState) => void): Promise<UnlistenFn> {
+  return listen<GitQueueState>("git-queue-changed", (event) => handler(event.payload));
+}
+
 export async function getCommitHistory(
   path: string,
   limit?: number,
@@ -46,13 +79,28 @@ export async function getCommitFiles(
   return invoke("get_commit_files", { path, commitHash });
 }
 
+export interface CommitSearchOptions {
+  /** git 可识别的日期，如 "2024-01-01"、"2 weeks ago" */
+  since?: string;
+  until?: string;
+  /** 搜索所有分支与标签 */
+  allBranches?: boolean;
+  /** 只看改动了这些路径的提交，如 "src/**" */
+  paths?: string[];
+  /** 内容搜索：增删了该字符串的提交 */
+  pickaxe?: string;
+  /** pickaxe 按正则匹配改动行 */
+  pickaxeRegex?: boolean;
+}
+
 export async function searchCommits(
   path: string,
   query: string,
   searchType?: "author" | "message" | "hash",
-  limit?: number
+  limit?: number,
+  options?: CommitSearchOptions
 ): Promise<CommitInfo[]> {
-  return invoke("search_commits", { path, query, searchType, limit });
+  return invoke("search_commits", { path, query, searchType, limit, options });
 }
 
 export async function getBranches(path: string): Promise<BranchInfo[]> {
@@ -83,24 +131,27 @@ export async function gitPush(
   path: string,
   remote: string,
   branch: string,
-  force: boolean = false
+  force: boolean = false,
+  options: OperationOptions = {}
 ): Promise<string> {
-  return invoke("git_push", { path, remote, branch, force });
+  return invoke("git_push", { path, remote, branch, force, ...options });
 }
 
 export async function gitPull(
   path: string,
   remote: string,
-  branch: string
+  branch: string,
+  options: OperationOptions = {}
 ): Promise<string> {
-  return invoke("git_pull", { path, remote, branch });
+  return invoke("git_pull", { path, remote, branch, ...options });
 }
 
 export async function gitFetch(
   path: string,
-  remote?: string
+  remote?: string,
+  options: OperationOptions = {}
 ): Promise<string> {
-  return invoke("git_fetch", { path, remote });
+  return invoke("git_fetch", { path, remote, ...options });
 }
 
 export async function syncToRemote(
@@ -119,6 +170,69 @@ export async function syncToRemote(
   });
 }
 
+export interface BranchDiffFile {
+  path: string;
+  oldPath?: string;
+  /** A / M / D / R / C / T */
+  status: string;
+  insertions: number;
+  deletions: number;
+  binary: boolean;
+}
+
+export interface BranchComparison {
+  base: string;
+  head: string;
+  mergeBase?: string;
+  ahead: number;
+  behind: number;
+  aheadCommits: CommitInfo[];
+  behindCommits: CommitInfo[];
+  truncated: boolean;
+  files: BranchDiffFile[];
+  insertions: number;
+  deletions: number;
+}
+
+/** 对比两个分支（head 相对 base） */
+export async function compareBranches(
+  path: string,
+  base: string,
+  head: string
+): Promise<BranchComparison> {
+  return invoke("compare_branches", { path, base, head });
+}
+
+export interface SyncBranchPreview {
+  branch: string;
+  /** "create" | "fastForward" | "forceUpdate" | "upToDate" */
+  action: string;
+  sourceSha: string;
+  targetSha?: string;
+  ahead?: number;
+  discarded?: number;
+  range?: string;
+  requiresForce: boolean;
+  isDefault: boolean;
+}
+
+/** syncToRemote 的预演：不推送，返回每个分支将如何更新 */
+export async function previewSyncToRemote(
+  path: string,
+  sourceRemote: string,
+  targetRemote: string,
+  syncAllBranches: boolean,
+  force: boolean = false
+): Promise<SyncBranchPreview[]> {
+  return invoke("preview_sync_to_remote", {
+    path,
+    sourceRemote,
+    targetRemote,
+    syncAllBranches,
+    force,
+  });
+}
+
 export async function checkoutBranch(
   path: string,
   branch: string
@@ -195,19 +309,129 @@ export async function gitMarkResolved(path: string, file: string): Promise<strin
   return invoke("git_mark_resolved", { path, file });
 }
 
+/** 中止进行中的合并 / 变基 / 拣选等操作，kind 取自 GitStatus.operation.kind */
+export async function abortOperation(
+  path: string,
+  kind: RepoOperation["kind"]
+): Promise<string> {
+  return invoke("abort_operation", { path, kind });
+}
+
 export async function gitCommit(
   path: string,
-  message: string
+  message: string,
+  allowIdentityMismatch?: boolean
 ): Promise<string> {
-  return invoke("git_commit", { path, message });
+  return invoke("git_commit", { path, message, allowIdentityMismatch });
 }
 
 export async function gitAddAndCommit(
   path: string,
   files: string[],
-  message: string
+  message: string,
+  allowIdentityMismatch?: boolean
 ): Promise<string> {
-  return invoke("git_add_and_commit", { path, files, message });
+  return invoke("git_add_and_commit", {
+    path,
+    files,
+    message,
+    allowIdentityMismatch,
+  });
+}
+
+export interface GitIdentity {
+  userName?: string;
+  email?: string;
+  source: "local" | "global" | "none";
+}
+
+export interface GitIdentityProfile {
+  id: string;
+  name: string;
+  userName: string;
+  email: string;
+  remotePatterns: string[];
+}
+
+export interface GitIdentityCheck {
+  current: GitIdentity;
+  expected?: GitIdentityProfile;
+  mismatch: boolean;
+  reason?: string;
+}
+
+export async function getGitIdentity(path: string): Promise<GitIdentityCheck> {
+  return invoke("get_git_identity", { path });
+}
+
+export async function setGitIdentity(
+  path: string,
+  userName?: string,
+  email?: string
+): Promise<GitIdentity> {
+  return invoke("set_git_identity", { path, userName, email });
+}
+
+export async function applyGitIdentityProfile(
+  path: string,
+  profileId: string
+): Promise<GitIdentity> {
+  return invoke("apply_git_identity_profile", { path, profileId });
+}
+
+export interface SigningKey {
+  format: "openpgp" | "ssh";
+  /** 写入 user.signingkey 的值：GPG 长 ID 或 SSH 公钥路径 */
+  key: string;
+  label?: string;
+  fingerprint?: string;
+  expires?: string;
+}
+
+export interface SigningConfig {
+  scope: "local" | "global";
+  format: string;
+  signingKey?: string;
+  signCommits: boolean;
+  signTags: boolean;
+  allowedSignersFile?: string;
+}
+
+export interface SigningTestResult {
+  signed: boolean;
+  verified: boolean;
+  output: string;
+}
+
+export async function listSigningKeys(): Promise<SigningKey[]> {
+  return invoke("list_signing_keys");
+}
+
+/** path 为空时读写全局配置 */
+export async function getSigningConfig(path?: string): Promise<SigningConfig> {
+  return invoke("get_signing_config", { path });
+}
+
+export async function setSigningConfig(
+  path: string | undefined,
+  format: string,
+  signingKey: string,
+  signCommits: boolean,
+  signTags?: boolean
+): Promise<SigningConfig> {
+  return invoke("set_signing_config", {
+    path,
+    format,
+    signingKey,
+    signCommits,
+    signTags,
+  });
+}
+
+export async function testCommitSigning(
+  path?: string
+): Promise<SigningTestResult> {
+  return invoke("test_commit_signing", { path });
 }
 
 export async function isGitRepo(path: string): Promise<boolean> {
@@ -221,3 +445,90 @@ export async function gitInit(path: string): Promise<string> {
 export async function cancelGitClone(): Promise<void> {
   return invoke("cancel_git_clone");
 }
+
+// ============== 可取消的操作 ==============
+
+// 长耗时命令（push / pull / fetch）的可选参数：opId 用于 cancelOperation，timeoutSecs 超时后结束子进程
+export interface OperationOptions {
+  opId?: string;
+  timeoutSecs?: number;
+}
+
+export interface OperationInfo {
+  id: string;
+  label: string;
+  timeoutSecs: number | null;
+  startedAt: string;
+}
+
+export async function cancelOperation(opId: string): Promise<void> {
+  return invoke("cancel_operation", { opId });
+}
+
+export async function listOperations(): Promise<OperationInfo[]> {
+  return invoke("list_operations");
+}
+
+// ============== 上游分歧提醒 ==============
+
+export interface DivergenceWatch {
+  projectId: string;
+  enabled: boolean;
+  behindThreshold: number;
+  fetchIntervalMinutes: number;
+  snoozedUntil?: string;
+  lastFetchedAt?: string;
+}
+
+export interface DivergenceWatchInput {
+  projectId: string;
+  enabled?: boolean;
+  behindThreshold?: number;
+  fetchIntervalMinutes?: number;
+}
+
+export interface DivergenceStatus {
+  projectId: string;
+  projectName: string;
+  branch?: string;
+  upstream?: string;
+  ahead: number;
+  behind: number;
+  threshold: number;
+  exceeded: boolean;
+  snoozed: boolean;
+  checkedAt: string;
+  error?: string;
+}
+
+export async function listDivergenceWatches(): Promise<DivergenceWatch[]> {
+  return invoke("list_divergence_watches");
+}
+
+export async function saveDivergenceWatch(
+  input: DivergenceWatchInput
+): Promise<DivergenceWatch> {
+  return invoke("save_divergence_watch", { input });
+}
+
+export async function deleteDivergenceWatch(projectId: string): Promise<void> {
+  return invoke("delete_divergence_watch", { projectId });
+}
+
+/** minutes 为 0 时取消稍后提醒 */
+export async function snoozeDivergenceAlert(
+  projectId: string,
+  minutes: number
+): Promise<DivergenceWatch> {
+  return invoke("snooze_divergence_alert", { projectId, minutes });
+}
+
+export async function getDivergenceStatus(): Promise<DivergenceStatus[]> {
+  return invoke("get_divergence_status");
+}
+
+export async function checkDivergenceNow(
+  projectId: string
+): Promise<DivergenceStatus> {
+  return invoke("check_divergence_now", { projectId });
+}
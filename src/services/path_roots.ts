@@ -0,0 +1,51 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface PathRoot {
+  id: string;
+  name: string;
+  path: string;
+  /** 绑定在该根目录下的项目数 */
+  projectCount: number;
+  /** 根目录在本机是否存在 */
+  exists: boolean;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface RemappedProject {
+  id: string;
+  name: string;
+  oldPath: string;
+  newPath: string;
+  /** 新路径在本机是否存在 */
+  exists: boolean;
+}
+
+export interface RemapReport {
+  root: PathRoot;
+  projects: RemappedProject[];
+  dryRun: boolean;
+}
+
+export async function listPathRoots(): Promise<PathRoot[]> {
+  return invoke("list_path_roots");
+}
+/** 已有项目中落在该目录下的会自动绑定 */
+export async function addPathRoot(name: string, path: string): Promise<PathRoot> {
+  return invoke("add_path_root", { name, path });
+}
+export async function renamePathRoot(id: string, name: string): Promise<PathRoot> {
+  return invoke("rename_path_root", { id, name });
+}
+/** 项目保留当前路径，只解除绑定 */
+export async function removePathRoot(id: string): Promise<void> {
+  return invoke("remove_path_root", { id });
+}
+/** 根目录移动后按相对路径重算项目路径；dryRun 为 true 时只返回预览 */
+export async function remapPathRoot(
+  id: string,
+  newPath: string,
+  dryRun?: boolean
+): Promise<RemapReport> {
+  return invoke("remap_path_root", { id, newPath, dryRun });
+}
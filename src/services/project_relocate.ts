@@ -0,0 +1,34 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { Project } from "@/types";
+
+export interface RelocationCandidate {
+  path: string;
+  /** remote：远程地址一致；name：目录名与项目名或原目录名一致 */
+  matchedBy: "remote" | "name";
+}
+
+export interface ProjectRelocation {
+  projectId: string;
+  name: string;
+  oldPath: string;
+  /** 记录过的远程地址 */
+  remoteUrl: string | null;
+  /** 远程匹配在前 */
+  candidates: RelocationCandidate[];
+  /** 匹配唯一时给出的建议路径 */
+  suggestedPath: string | null;
+}
+
+/**
+ * 为路径已不存在的项目查找新位置，只返回建议。
+ * roots 为空时在设置的扫描目录（scanRoots）和路径根目录下查找
+ */
+export async function relocateMissingProjects(roots?: string[]): Promise<ProjectRelocation[]> {
+  return invoke("relocate_missing_projects", { roots });
+}
+/** 写入用户确认的新路径 */
+export async function applyProjectRelocations(
+  relocations: { projectId: string; newPath: string }[]
+): Promise<Project[]> {
+  return invoke("apply_project_relocations", { relocations });
+}
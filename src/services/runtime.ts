@@ -0,0 +1,80 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { Protocol, SessionMode, SessionStatus } from "@/types/toolbox";
+
+export interface RuntimeServer {
+  id: string;
+  name: string;
+  port: number;
+  urlPrefix: string;
+  rootDir: string;
+}
+
+export interface RuntimeForward {
+  id: string;
+  name: string;
+  localPort: number;
+  /** host:port，或 unix / pipe 目标的路径 */
+  target: string;
+  connections: number;
+  bytesIn: number;
+  bytesOut: number;
+}
+
+export interface RuntimeDownload {
+  id: string;
+  fileName: string;
+  downloadedSize: number;
+  /** 未知时为 0 */
+  totalSize: number;
+  /** 字节/秒 */
+  speed: number;
+}
+
+export interface RuntimeNetcatSession {
+  id: string;
+  name: string;
+  protocol: Protocol;
+  mode: SessionMode;
+  host: string;
+  port: number;
+  status: SessionStatus;
+  bytesSent: number;
+  bytesReceived: number;
+  clientCount: number;
+}
+
+export interface RuntimeProcess {
+  /** 项目任务为 runId，终端为会话 id，文档预览为项目路径 */
+  id: string;
+  kind: "project_task" | "terminal" | "docs_preview";
+  name: string;
+  pid: number | null;
+  startedAt: string;
+}
+
+export interface RuntimeOverview {
+  servers: RuntimeServer[];
+  forwards: RuntimeForward[];
+  downloads: RuntimeDownload[];
+  netcatSessions: RuntimeNetcatSession[];
+  processes: RuntimeProcess[];
+  /** 所有下载的速度之和，字节/秒 */
+  downloadSpeed: number;
+}
+
+/** 一次取回所有运行中的服务、转发、下载、Netcat 会话和进程，供状态栏使用 */
+export async function getRuntimeOverview(): Promise<RuntimeOverview> {
+  return invoke("get_runtime_overview");
+}
+
+export interface IdlePolicyStatus {
+  /** 当前网络是否按流量计费 */
+  metered: boolean;
+  /** 因计费网络被暂停、等待自动恢复的下载任务 id */
+  pausedDownloads: string[];
+}
+
+/** 空闲自动停止策略在设置 idlePolicy 中配置；这里只返回计费网络状态 */
+export async function getIdlePolicyStatus(): Promise<IdlePolicyStatus> {
+  return invoke("get_idle_policy_status");
+}
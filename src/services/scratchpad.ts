@@ -0,0 +1,73 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export interface ScratchNote {
+  id: string;
+  name: string;
+  /** Markdown */
+  content: string;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface ScratchNoteSummary {
+  id: string;
+  name: string;
+  createdAt: string;
+  updatedAt: string;
+  bytes: number;
+  /** 第一行非空内容 */
+  preview: string;
+  versionCount: number;
+}
+
+export interface ScratchNoteVersion {
+  id: string;
+  savedAt: string;
+  bytes: number;
+}
+
+export interface ScratchSearchResult {
+  id: string;
+  name: string;
+  updatedAt: string;
+  nameMatches: boolean;
+  /** line 从 1 开始 */
+  lines: { line: number; text: string }[];
+}
+
+export async function listScratchNotes(): Promise<ScratchNoteSummary[]> {
+  return invoke("list_scratch_notes");
+}
+export async function getScratchNote(id: string): Promise<ScratchNote> {
+  return invoke("get_scratch_note", { id });
+}
+export async function createScratchNote(name: string, content?: string): Promise<ScratchNote> {
+  return invoke("create_scratch_note", { name, content });
+}
+/** 自动保存用；距上次快照超过 5 分钟时后端自动保留旧内容为一个版本，forceVersion 立即保留 */
+export async function saveScratchNote(
+  id: string,
+  content: string,
+  forceVersion?: boolean
+): Promise<ScratchNote> {
+  return invoke("save_scratch_note", { id, content, forceVersion });
+}
+export async function renameScratchNote(id: string, name: string): Promise<ScratchNote> {
+  return invoke("rename_scratch_note", { id, name });
+}
+export async function deleteScratchNote(id: string): Promise<void> {
+  return invoke("delete_scratch_note", { id });
+}
+export async function listScratchNoteVersions(id: string): Promise<ScratchNoteVersion[]> {
+  return invoke("list_scratch_note_versions", { id });
+}
+export async function getScratchNoteVersion(id: string, versionId: string): Promise<string> {
+  return invoke("get_scratch_note_version", { id, versionId });
+}
+export async function restoreScratchNoteVersion(id: string, versionId: string): Promise<ScratchNote> {
+  return invoke("restore_scratch_note_version", { id, versionId });
+}
+/** 空白分隔多个关键词，忽略大小写，全部命中才返回 */
+export async function searchScratchNotes(query: string): Promise<ScratchSearchResult[]> {
+  return invoke("search_scratch_notes", { query });
+}
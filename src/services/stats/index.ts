@@ -1,5 +1,6 @@
 import { invoke } from "@tauri-apps/api/core";
 import type { DashboardStats, DailyActivity, CommitInfo } from "@/types";
+import type { ServerConfig } from "@/types/toolbox";
 
 export interface ProjectInfo {
   id?: string;
@@ -18,30 +19,113 @@ export interface CachedDashboardData {
   recentCommits: RecentCommit[];
 }
 
+export interface ContributorActivity {
+  name: string;
+  email: string;
+  commits: number;
+}
+
+export interface ProjectActivity {
+  name: string;
+  path: string;
+  commits: number;
+  contributorCount: number;
+  contributors: ContributorActivity[];
+  insertions: number;
+  deletions: number;
+  filesChanged: number;
+  commitsByDate: DailyActivity[];
+  error?: string | null;
+}
+
+export interface ActivityComparison {
+  since: string;
+  until: string;
+  projects: ProjectActivity[];
+}
+
+export interface HeatmapExport {
+  format: "csv" | "json" | "svg" | "png";
+  /** File content; base64 for png */
+  content: string;
+  path?: string | null;
+  since: string;
+  until: string;
+  totalCommits: number;
+}
+
+export type DashboardWidgetKind =
+  | "stats"
+  | "heatmap"
+  | "recent_commits"
+  | "running_services"
+  | "watched_ports";
+
+export interface DashboardWidget {
+  kind: DashboardWidgetKind;
+  size: "small" | "medium" | "large";
+}
+
+export interface WatchedPort {
+  id: string;
+  name: string;
+  host: string;
+  port: number;
+  enabled: boolean;
+  status: "up" | "down" | "unknown";
+  latencyMs: number | null;
+  lastChecked: string | null;
+  lastChange: string | null;
+  /** Most recent probes only */
+  history: { time: string; status: string; latencyMs: number | null }[];
+}
+
+/** Fields of widgets that are not enabled are null */
+export interface DashboardWidgetData {
+  widgets: DashboardWidget[];
+  stats: DashboardStats | null;
+  heatmap: DailyActivity[] | null;
+  recentCommits: RecentCommit[] | null;
+  runningServices: ServerConfig[] | null;
+  watchedPorts: WatchedPort[] | null;
+}
+
 // Transform snake_case from Rust to camelCase for TypeScript
+function transformDashboardStats(stats: any): DashboardStats {
+  return {
+    totalProjects: stats.total_projects,
+    todayCommits: stats.today_commits,
+    weekCommits: stats.week_commits,
+    unpushedCommits: stats.unpushed_commits,
+    unmergedBranches: stats.unmerged_branches,
+  };
+}
+
+function transformHeatmap(items: any[]): DailyActivity[] {
+  return items.map((item: any) => ({
+    date: item.date,
+    count: item.count,
+  }));
+}
+
+function transformRecentCommits(commits: any[]): RecentCommit[] {
+  return commits.map((commit: any) => ({
+    hash: commit.hash,
+    shortHash: commit.short_hash,
+    message: commit.message,
+    author: commit.author,
+    email: commit.email,
+    date: commit.date,
+    projectName: commit.project_name,
+    projectPath: commit.project_path,
+  }));
+}
+
 function transformStats(data: any): CachedDashboardData {
   return {
-    stats: {
-      totalProjects: data.stats.total_projects,
-      todayCommits: data.stats.today_commits,
-      weekCommits: data.stats.week_commits,
-      unpushedCommits: data.stats.unpushed_commits,
-      unmergedBranches: data.stats.unmerged_branches,
-    },
-    heatmapData: data.heatmap_data.map((item: any) => ({
-      date: item.date,
-      count: item.count,
-    })),
-    recentCommits: data.recent_commits.map((commit: any) => ({
-      hash: commit.hash,
-      shortHash: commit.short_hash,
-      message: commit.message,
-      author: commit.author,
-      email: commit.email,
-      date: commit.date,
-      projectName: commit.project_name,
-      projectPath: commit.project_path,
-    })),
+    stats: transformDashboardStats(data.stats),
+    heatmapData: transformHeatmap(data.heatmap_data),
+    recentCommits: transformRecentCommits(data.recent_commits),
   };
 }
 
@@ -110,3 +194,86 @@ export async function hasDirtyStats(): Promise<boolean> {
 export async function cleanupStatsCache(currentProjectPaths: string[]): Promise<void> {
   await invoke("cleanup_stats_cache", { currentProjectPaths });
 }
+
+export interface StatsCacheSize {
+  project_count: number;
+  orphaned_projects: number;
+  daily_rows: number;
+  expired_daily_rows: number;
+  recent_commit_rows: number;
+  database_bytes: number;
+  legacy_file_bytes: number;
+  retention_days: number;
+}
+
+export interface StatsCompactReport {
+  removed_projects: number;
+  removed_daily_rows: number;
+  removed_legacy_files: number;
+  bytes_before: number;
+  bytes_after: number;
+}
+
+/**
+ * Report stats cache row counts and database file size
+ */
+export async function getStatsCacheSize(): Promise<StatsCacheSize> {
+  return await invoke("get_stats_cache_size");
+}
+
+/**
+ * Prune removed projects and entries outside the retention window, then VACUUM
+ */
+export async function compactStatsCache(): Promise<StatsCompactReport> {
+  return await invoke("compact_stats_cache");
+}
+
+/**
+ * Compare commit counts, contributors and churn across projects
+ * range: "7d" / "30d" / "12w" / "6m" / "1y" or "2024-01-01..2024-03-31"
+ */
+export async function compareProjectsActivity(
+  paths: string[],
+  range: string
+): Promise<ActivityComparison> {
+  return await invoke("compare_projects_activity", { paths, range });
+}
+
+/**
+ * Export the aggregated commit heatmap as CSV / JSON / GitHub-style SVG / PNG
+ * Writes to outputPath when given
+ */
+export async function exportHeatmap(
+  format: HeatmapExport["format"],
+  range?: string,
+  outputPath?: string
+): Promise<HeatmapExport> {
+  return await invoke("export_heatmap", { range, format, outputPath });
+}
+
+export async function getDashboardLayout(): Promise<DashboardWidget[]> {
+  return await invoke("get_dashboard_layout");
+}
+
+/**
+ * Save which widgets are shown, in display order; each kind may appear once
+ */
+export async function saveDashboardLayout(widgets: DashboardWidget[]): Promise<DashboardWidget[]> {
+  return await invoke("save_dashboard_layout", { widgets });
+}
+
+/**
+ * Fetch data for all enabled widgets in one call
+ * Pass kinds to refresh only some of them
+ */
+export async function getDashboardWidgetData(
+  kinds?: DashboardWidgetKind[]
+): Promise<DashboardWidgetData> {
+  const data: any = await invoke("get_dashboard_widget_data", { kinds });
+  return {
+    ...data,
+    stats: data.stats ? transformDashboardStats(data.stats) : null,
+    heatmap: data.heatmap ? transformHeatmap(data.heatmap) : null,
+    recentCommits: data.recentCommits ? transformRecentCommits(data.recentCommits) : null,
+  };
+}
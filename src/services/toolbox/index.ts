@@ -4,8 +4,40 @@ import { invoke } from "@tauri-apps/api/core";
 import type {
   ScanConfig,
   ScanResult,
+  ScanCapabilities,
   DownloadConfig,
   DownloadTask,
+  DownloadHistoryEntry,
+  DownloadHistoryStats,
+  DownloadScanResult,
+  DownloadImportResult,
+  FileHashResult,
+  HashAlgorithm,
+  HttpBenchReport,
+  CertificateInfo,
+  TlsInspection,
+  RegexFlags,
+  RegexTestResult,
+  TextDiffMode,
+  TextDiffResult,
+  ApplyPatchResult,
+  DocumentFormat,
+  DocumentValidation,
+  DocumentOutput,
+  JsonPathMatch,
+  GeneratedAsset,
+  FaviconResult,
+  ImageOptimizeOptions,
+  ImageOptimizeReport,
+  FolderSyncOptions,
+  FolderSyncReport,
+  HostingTokenInfo,
+  ReleaseInfo,
+  ElevatedRelay,
+  ResourceAlertRule,
+  ResourceAlertRuleInput,
+  ResourceAlert,
+  PowerStatus,
   ProcessInfo,
   ProcessFilter,
   SystemStats,
@@ -15,6 +47,7 @@ import type {
   ForwardStats,
   ServerConfig,
   ServerConfigInput,
+  MetricsStatus,
   DockerStatus,
   DockerCommandResult,
   DockerImageInfo,
@@ -27,7 +60,12 @@ import type {
   SshTunnelInput,
   SshTunnelStats,
   TestPortResult,
+  SshKeyInfo,
+  SshHostEntry,
+  SshHostEntryInput,
+  SshAuthTestResult,
 } from "@/types/toolbox";
+import type { ConfirmRequest } from "@/types";
 
 // ============== 端口扫描服务 ==============
 
@@ -36,6 +74,10 @@ export async function scanPorts(config: ScanConfig): Promise<ScanResult[]> {
   return invoke("scan_ports", { config });
 }
 
+export async function getScanCapabilities(): Promise<ScanCapabilities> {
+  return invoke("get_scan_capabilities");
+}
+
 export async function stopScan(): Promise<void> {
   return invoke("stop_scan");
 }
@@ -63,6 +105,14 @@ export async function startDownload(config: DownloadConfig): Promise<string> {
   return invoke("start_download", { config });
 }
 
+/** 批量导入粘贴的 URL：每行一个，可用 Tab 分隔文件名；无效行在 errors 中返回 */
+export async function importDownloadUrls(
+  text: string,
+  saveDir?: string
+): Promise<DownloadImportResult> {
+  return invoke("import_download_urls", { text, saveDir });
+}
+
 export async function pauseDownload(taskId: string): Promise<void> {
   return invoke("pause_download", { taskId });
 }
@@ -97,6 +147,256 @@ export async function removeDownloadTask(taskId: string, deleteFile?: boolean):
   return invoke("remove_download_task", { taskId, deleteFile });
 }
 
+/** 手动扫描已完成的下载，发现威胁时按设置隔离 / 删除 */
+export async function scanDownload(taskId: string): Promise<DownloadScanResult> {
+  return invoke("scan_download", { taskId });
+}
+
+/** 当前可用的病毒扫描器名称，没有时为 null */
+export async function getVirusScanner(): Promise<string | null> {
+  return invoke("get_virus_scanner");
+}
+
+export async function getDownloadHistory(limit?: number): Promise<DownloadHistoryEntry[]> {
+  return invoke("get_download_history", { limit });
+}
+
+/** 汇总下载历史；不传 days 时统计全部记录 */
+export async function getDownloadHistoryStats(days?: number): Promise<DownloadHistoryStats> {
+  return invoke("get_download_history_stats", { days });
+}
+
+export async function clearDownloadHistory(): Promise<void> {
+  return invoke("clear_download_history");
+}
+
+// ============== 文件校验和 ==============
+
+/**
+ * 计算文件校验和；algorithms 为空时计算全部算法，传入 expected 时返回比对结果。
+ * 进度通过 `file-hash-progress` 事件推送，传入 jobId 后可用 cancelOperation(jobId) 中止
+ */
+export async function computeFileHashes(
+  path: string,
+  algorithms: HashAlgorithm[] = [],
+  expected?: string,
+  jobId?: string
+): Promise<FileHashResult> {
+  return invoke("compute_file_hashes", { path, algorithms, expected, jobId });
+}
+
+// ============== HTTP 压测 ==============
+
+/** 压测期间每秒推送 `http-bench-progress`，传入 jobId 可用 cancelOperation 提前结束 */
+export async function benchmarkHttp(
+  url: string,
+  concurrency: number,
+  durationSecs: number,
+  options: {
+    method?: string;
+    body?: string;
+    headers?: Record<string, string>;
+    timeoutMs?: number;
+    jobId?: string;
+  } = {}
+): Promise<HttpBenchReport> {
+  return invoke("benchmark_http", { url, concurrency, durationSecs, ...options });
+}
+
+// ============== 证书检查 ==============
+
+/** 抓取 host:port 的 TLS 证书链；连 IP 时可用 serverName 指定 SNI */
+export async function inspectTlsCertificates(
+  host: string,
+  port: number,
+  options: { serverName?: string; warnDays?: number; timeoutMs?: number } = {}
+): Promise<TlsInspection> {
+  return invoke("inspect_tls_certificates", { host, port, ...options });
+}
+
+/** 解析本地 PEM / CRT / DER 证书文件 */
+export async function parseCertificateFile(path: string, warnDays?: number): Promise<CertificateInfo[]> {
+  return invoke("parse_certificate_file", { path, warnDays });
+}
+
+// ============== 正则测试 ==============
+
+/** Rust regex 语法；返回的位置为 UTF-16 下标，可直接用于高亮 */
+export async function testRegex(
+  pattern: string,
+  text: string,
+  options: {
+    flags?: Partial<RegexFlags>;
+    replacement?: string;
+    maxMatches?: number;
+    timeoutMs?: number;
+  } = {}
+): Promise<RegexTestResult> {
+  return invoke("test_regex", { pattern, text, ...options });
+}
+
+// ============== 文本比较 ==============
+
+/** 比较两段文本；word 模式额外给出行内词级差异，label 用作补丁中的文件名 */
+export async function diffText(
+  a: string,
+  b: string,
+  options: {
+    mode?: TextDiffMode;
+    contextLines?: number;
+    ignoreWhitespace?: boolean;
+    labelA?: string;
+    labelB?: string;
+  } = {}
+): Promise<TextDiffResult> {
+  return invoke("diff_text", { a, b, ...options });
+}
+
+/** 把 unified diff 应用到文本；reverse 时反向应用（撤销补丁） */
+export async function applyTextPatch(
+  original: string,
+  patch: string,
+  reverse?: boolean
+): Promise<ApplyPatchResult> {
+  return invoke("apply_text_patch", { original, patch, reverse });
+}
+
+// ============== 数据格式（JSON / YAML / TOML） ==============
+
+/** 文档来源：text 与 filePath 二选一 */
+export interface DocumentSource {
+  text?: string;
+  filePath?: string;
+}
+
+export async function validateDocument(
+  format: DocumentFormat,
+  source: DocumentSource
+): Promise<DocumentValidation> {
+  return invoke("validate_document", { format, ...source });
+}
+
+/** indent 只影响 JSON 输出（0 为压缩）；传 outputPath 时结果写入文件 */
+export async function convertDocument(
+  from: DocumentFormat,
+  to: DocumentFormat,
+  source: DocumentSource,
+  options: { indent?: number; outputPath?: string } = {}
+): Promise<DocumentOutput> {
+  return invoke("convert_document", { from, to, ...source, ...options });
+}
+
+/** indent 为 0 时压缩；保留键顺序与数字原文 */
+export async function formatJson(
+  source: DocumentSource,
+  options: { indent?: number; outputPath?: string } = {}
+): Promise<DocumentOutput> {
+  return invoke("format_json", { ...source, ...options });
+}
+
+export async function queryJsonPath(
+  format: DocumentFormat,
+  expression: string,
+  source: DocumentSource
+): Promise<JsonPathMatch[]> {
+  return invoke("query_json_path", { format, expression, ...source });
+}
+
+// ============== 素材生成 ==============
+
+/** PNG 结果为 base64，SVG 为文本；传 outputPath 时同时写入文件 */
+export async function generateQrCode(
+  text: string,
+  format: "png" | "svg",
+  options: {
+    scale?: number;
+    margin?: number;
+    errorCorrection?: "L" | "M" | "Q" | "H";
+    foreground?: string;
+    background?: string;
+    outputPath?: string;
+  } = {}
+): Promise<GeneratedAsset> {
+  return invoke("generate_qr_code", { text, format, ...options });
+}
+
+export async function generateFavicons(sourcePath: string, outputDir: string): Promise<FaviconResult> {
+  return invoke("generate_favicons", { sourcePath, outputDir });
+}
+
+/** PNG 只有背景与对角线，文字仅在 SVG 中显示 */
+export async function generatePlaceholderImage(
+  width: number,
+  height: number,
+  format: "png" | "svg",
+  options: { background?: string; foreground?: string; text?: string; outputPath?: string } = {}
+): Promise<GeneratedAsset> {
+  return invoke("generate_placeholder_image", { width, height, format, ...options });
+}
+
+// ============== 图片压缩 ==============
+
+/**
+ * 批量压缩目录中的 PNG / JPEG / WebP，写入 outputDir，原图不动
+ * 进度通过 `image-optimize-progress` 事件推送，传入 jobId 后可用 cancelOperation(jobId) 中止
+ */
+export async function optimizeImages(
+  sourceDir: string,
+  outputDir: string,
+  options?: Partial<ImageOptimizeOptions>,
+  jobId?: string
+): Promise<ImageOptimizeReport> {
+  return invoke("optimize_images", { sourceDir, outputDir, options, jobId });
+}
+
+// ============== 目录同步 ==============
+
+/**
+ * 单向同步 source → destination；dryRun 时只返回将要执行的变更（只读模式下也可用）
+ * 进度通过 `folder-sync-progress` 事件推送，传入 jobId 后可用 cancelOperation(jobId) 中止
+ */
+export async function syncFolder(
+  source: string,
+  destination: string,
+  options: Partial<FolderSyncOptions> = {},
+  dryRun = false,
+  jobId?: string
+): Promise<FolderSyncReport> {
+  return invoke("sync_folder", { source, destination, options, dryRun, jobId });
+}
+
+// ============== Release 附件下载 ==============
+
+export async function listHostingTokens(): Promise<HostingTokenInfo[]> {
+  return invoke("list_hosting_tokens");
+}
+
+export async function saveHostingToken(
+  host: string,
+  provider: "github" | "gitlab",
+  token: string
+): Promise<void> {
+  return invoke("save_hosting_token", { host, provider, token });
+}
+
+export async function deleteHostingToken(host: string): Promise<void> {
+  return invoke("delete_hosting_token", { host });
+}
+
+/** 列出 Release 附件；地址不带版本号时取最新版本 */
+export async function listReleaseAssets(url: string): Promise<ReleaseInfo> {
+  return invoke("list_release_assets", { url });
+}
+
+/** 下载 Release 附件，返回下载任务 id */
+export async function downloadReleaseAsset(
+  url: string,
+  assetName: string,
+  saveDir?: string
+): Promise<string> {
+  return invoke("download_release_asset", { url, assetName, saveDir });
+}
+
 // ============== 进程管理服务 ==============
 
 export async function getProcesses(
@@ -111,15 +411,67 @@ export async function getPortProcesses(port: number): Promise<ProcessInfo[]> {
 
 export async function killProcess(
   pid: number,
-  force?: boolean
-): Promise<void> {
-  return invoke("kill_process", { pid, force });
+  force?: boolean,
+  confirmToken?: string
+): Promise<ConfirmRequest | null> {
+  return invoke("kill_process", { pid, force, confirmToken });
+}
+
+/** 以管理员权限结束进程（弹出系统授权对话框），用于普通终止因权限不足失败时；需配合 runWithConfirmToken 二次确认 */
+export async function killProcessElevated(
+  pid: number,
+  force?: boolean,
+  confirmToken?: string
+): Promise<ConfirmRequest | null> {
+  return invoke("kill_process_elevated", { pid, force, confirmToken });
+}
+
+/** 提权端口中继：以管理员权限监听低位端口并转发到本机 targetPort */
+export async function startElevatedPortRelay(
+  listenPort: number,
+  targetPort: number,
+  listenHost?: string
+): Promise<ElevatedRelay> {
+  return invoke("start_elevated_port_relay", { listenPort, targetPort, listenHost });
+}
+
+export async function stopElevatedPortRelay(listenPort: number): Promise<void> {
+  return invoke("stop_elevated_port_relay", { listenPort });
+}
+
+export async function getElevatedPortRelays(): Promise<ElevatedRelay[]> {
+  return invoke("get_elevated_port_relays");
 }
 
 export async function getSystemStats(): Promise<SystemStats> {
   return invoke("get_system_stats");
 }
 
+/** 电源状态；电池低电量时统计刷新、定时工作流、镜像任务会暂停 */
+export async function getPowerStatus(): Promise<PowerStatus> {
+  return invoke("get_power_status");
+}
+
+// 资源告警
+export async function getResourceAlertRules(): Promise<ResourceAlertRule[]> {
+  return invoke("get_resource_alert_rules");
+}
+
+export async function saveResourceAlertRule(
+  id: string | null,
+  input: ResourceAlertRuleInput
+): Promise<ResourceAlertRule> {
+  return invoke("save_resource_alert_rule", { id, input });
+}
+
+export async function deleteResourceAlertRule(id: string): Promise<void> {
+  return invoke("delete_resource_alert_rule", { id });
+}
+
+export async function getCurrentAlerts(): Promise<ResourceAlert[]> {
+  return invoke("get_current_alerts");
+}
+
 export async function getLocalPortOccupation(): Promise<PortOccupation[]> {
   return invoke("get_local_port_occupation");
 }
@@ -227,6 +579,45 @@ export async function testLocalPort(port: number): Promise<TestPortResult> {
   return invoke("test_local_port", { port });
 }
 
+// ============== SSH 密钥服务 ==============
+
+export async function listSshKeys(): Promise<SshKeyInfo[]> {
+  return invoke("list_ssh_keys");
+}
+
+export async function generateSshKey(
+  name?: string,
+  comment?: string,
+  passphrase?: string
+): Promise<SshKeyInfo> {
+  return invoke("generate_ssh_key", { name, comment, passphrase });
+}
+
+export async function getSshPublicKey(name: string): Promise<string> {
+  return invoke("get_ssh_public_key", { name });
+}
+
+export async function testSshAuth(
+  host: string,
+  keyName?: string
+): Promise<SshAuthTestResult> {
+  return invoke("test_ssh_auth", { host, keyName });
+}
+
+export async function listSshHostEntries(): Promise<SshHostEntry[]> {
+  return invoke("list_ssh_host_entries");
+}
+
+export async function saveSshHostEntry(
+  input: SshHostEntryInput
+): Promise<SshHostEntry> {
+  return invoke("save_ssh_host_entry", { input });
+}
+
+export async function removeSshHostEntry(alias: string): Promise<void> {
+  return invoke("remove_ssh_host_entry", { alias });
+}
+
 // ============== 静态服务 ==============
 
 export async function createServer(
@@ -268,6 +659,27 @@ export async function generateNginxConfig(serverId: string): Promise<string> {
   return invoke("generate_nginx_config", { serverId });
 }
 
+/** Prometheus 指标端点状态 */
+export async function getMetricsStatus(): Promise<MetricsStatus> {
+  return invoke("get_metrics_status");
+}
+
+/** 开关指标端点（写入应用设置后立即生效） */
+export async function setMetricsEndpoint(enabled: boolean, port?: number): Promise<MetricsStatus> {
+  await invoke("save_app_settings", { input: { metrics_enabled: enabled, metrics_port: port ?? null } });
+  return getMetricsStatus();
+}
+
+/** 导出服务配置包（JSON 文本） */
+export async function exportServerConfig(serverId: string): Promise<string> {
+  return invoke("export_server_config", { serverId });
+}
+
+/** 导入服务配置包，rootDir 覆盖包中的根目录 */
+export async function importServerConfig(bundle: string, rootDir?: string | null): Promise<ServerConfig> {
+  return invoke("import_server_config", { bundle, rootDir: rootDir ?? null });
+}
+
 // ============== Docker 镜像服务 ==============
 
 export async function dockerCheckAvailable(): Promise<DockerStatus> {
@@ -635,8 +1047,18 @@ import type {
   NetcatSession,
   SendMessageInput,
   NetcatMessage,
+  NetcatMessageFilter,
   ConnectedClient,
   AutoSendConfig,
+  DataFormat,
+  NetcatGroupOpResult,
+  NetcatGroupStats,
+  NetcatLogStats,
+  TrafficLogMode,
+  OverflowPolicy,
+  NetcatPayload,
+  NetcatPayloadInput,
+  NetcatImportResult,
 } from "@/types/toolbox";
 
 export async function netcatInit(): Promise<void> {
@@ -674,9 +1096,10 @@ export async function netcatGetSession(sessionId: string): Promise<NetcatSession
 export async function netcatGetMessages(
   sessionId: string,
   limit?: number,
-  offset?: number
+  offset?: number,
+  filter?: NetcatMessageFilter
 ): Promise<NetcatMessage[]> {
-  return invoke("netcat_get_messages", { sessionId, limit, offset });
+  return invoke("netcat_get_messages", { sessionId, limit, offset, filter });
 }
 
 export async function netcatGetClients(sessionId: string): Promise<ConnectedClient[]> {
@@ -695,16 +1118,197 @@ export async function netcatUpdateAutoSend(sessionId: string, config: AutoSendCo
   return invoke("netcat_update_auto_send", { sessionId, config });
 }
 
+// 会话流量日志：mode 控制收发日志频率，logPayload 控制是否以 debug 级别输出报文内容
+export async function netcatSetLogMode(
+  sessionId: string,
+  mode: TrafficLogMode,
+  logPayload?: boolean
+): Promise<void> {
+  return invoke("netcat_set_log_mode", { sessionId, mode, logPayload });
+}
+
+// 消息条数上限与溢出策略，上限调小时立即裁剪已有消息
+export async function netcatSetHistoryLimit(
+  sessionId: string,
+  limit: number,
+  policy: OverflowPolicy
+): Promise<NetcatSession> {
+  return invoke("netcat_set_history_limit", { sessionId, limit, policy });
+}
+
+export async function netcatGetLogStats(sessionId: string): Promise<NetcatLogStats> {
+  return invoke("netcat_get_log_stats", { sessionId });
+}
+
+export async function netcatSetSessionGroup(sessionIds: string[], group: string | null): Promise<void> {
+  return invoke("netcat_set_session_group", { sessionIds, group });
+}
+
+export async function netcatStartGroup(group: string): Promise<NetcatGroupOpResult[]> {
+  return invoke("netcat_start_group", { group });
+}
+
+export async function netcatStopGroup(group: string): Promise<NetcatGroupOpResult[]> {
+  return invoke("netcat_stop_group", { group });
+}
+
+export async function netcatSendToGroup(
+  group: string,
+  data: string,
+  format: DataFormat
+): Promise<NetcatGroupOpResult[]> {
+  return invoke("netcat_send_to_group", { group, data, format });
+}
+
+export async function netcatGetGroupStats(): Promise<NetcatGroupStats[]> {
+  return invoke("netcat_get_group_stats");
+}
+
+export async function netcatGetPayloads(): Promise<NetcatPayload[]> {
+  return invoke("netcat_get_payloads");
+}
+
+export async function netcatSavePayload(
+  input: NetcatPayloadInput,
+  id?: string
+): Promise<NetcatPayload> {
+  return invoke("netcat_save_payload", { id, input });
+}
+
+export async function netcatDeletePayload(id: string): Promise<void> {
+  return invoke("netcat_delete_payload", { id });
+}
+
+export async function netcatRenderPayload(template: string): Promise<string> {
+  return invoke("netcat_render_payload", { template });
+}
+
+// 会话导入导出：导出选中会话的配置（可附带报文库），导入时新建会话、报文按名称去重
+export async function netcatExportSessions(
+  sessionIds: string[],
+  includePayloads: boolean,
+  filePath: string
+): Promise<number> {
+  return invoke("netcat_export_sessions", { sessionIds, includePayloads, filePath });
+}
+
+export async function netcatImportSessions(filePath: string): Promise<NetcatImportResult> {
+  return invoke("netcat_import_sessions", { filePath });
+}
+
 export interface HttpFetchConfig {
   url: string;
   method?: string;
   headers?: Record<string, string>;
   body?: string;
   jsonPath?: string;
+  /** 校验 TLS 证书，默认 false */
+  verifyTls?: boolean;
+  /** 跟随重定向，默认 true */
+  followRedirects?: boolean;
+  maxRedirects?: number;
+  timeoutMs?: number;
+}
+
+export interface HttpFetchResponse {
+  status: number;
+  statusText: string;
+  version: string;
+  headers: { name: string; value: string }[];
+  body: string;
+  bodySize: number;
+  bodyTruncated: boolean;
+  /** 指定 jsonPath 时的提取结果 */
+  extracted?: string;
+  finalUrl: string;
+  remoteAddr?: string;
+  redirects: { url: string; status: number; location: string; durationMs: number }[];
+  /** 最后一跳的累计耗时（毫秒） */
+  timing: { dnsMs: number; connectMs: number; ttfbMs: number; totalMs: number };
+  totalMs: number;
+}
+
+export async function netcatFetchHttp(
+  config: HttpFetchConfig
+): Promise<HttpFetchResponse> {
+  return invoke("netcat_fetch_http", { config });
 }
 
-export async function netcatFetchHttp(config: HttpFetchConfig): Promise<string> {
-  return invoke("netcat_fetch_http", { config });
+// ============== MQTT / Modbus 服务 ==============
+
+import type {
+  MqttConnectInput,
+  MqttSession,
+  MqttMessage,
+  ModbusRequest,
+  ModbusResponse,
+} from "@/types/toolbox";
+
+export async function mqttConnect(input: MqttConnectInput): Promise<MqttSession> {
+  return invoke("mqtt_connect", { input });
+}
+
+export async function mqttDisconnect(sessionId: string): Promise<void> {
+  return invoke("mqtt_disconnect", { sessionId });
+}
+
+export async function mqttSubscribe(sessionId: string, topic: string, qos?: number): Promise<number> {
+  return invoke("mqtt_subscribe", { sessionId, topic, qos });
+}
+
+export async function mqttUnsubscribe(sessionId: string, topic: string): Promise<void> {
+  return invoke("mqtt_unsubscribe", { sessionId, topic });
+}
+
+export async function mqttPublish(
+  sessionId: string,
+  topic: string,
+  payload: string,
+  format: DataFormat,
+  qos?: number,
+  retain?: boolean
+): Promise<MqttMessage> {
+  return invoke("mqtt_publish", { sessionId, topic, payload, format, qos, retain });
+}
+
+export async function mqttGetSessions(): Promise<MqttSession[]> {
+  return invoke("mqtt_get_sessions");
+}
+
+export async function mqttGetMessages(
+  sessionId: string,
+  topic?: string,
+  limit?: number
+): Promise<MqttMessage[]> {
+  return invoke("mqtt_get_messages", { sessionId, topic, limit });
+}
+
+export async function mqttClearMessages(sessionId: string): Promise<void> {
+  return invoke("mqtt_clear_messages", { sessionId });
+}
+
+export async function modbusRequest(request: ModbusRequest): Promise<ModbusResponse> {
+  return invoke("modbus_request", { request });
+}
+
+// ============== 流量抓包服务 ==============
+
+import type { TrafficCapture, TrafficCaptureKind } from "@/types/toolbox";
+
+export async function startTrafficCapture(
+  kind: TrafficCaptureKind,
+  resourceId: string,
+  path?: string
+): Promise<TrafficCapture> {
+  return invoke("start_traffic_capture", { kind, resourceId, path });
+}
+
+export async function stopTrafficCapture(resourceId: string): Promise<TrafficCapture> {
+  return invoke("stop_traffic_capture", { resourceId });
+}
+
+export async function getTrafficCaptures(): Promise<TrafficCapture[]> {
+  return invoke("get_traffic_captures");
 }
 
 // ============== 快捷键备忘服务 ==============
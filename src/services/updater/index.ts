@@ -1,10 +1,11 @@
-import { check, type Update } from "@tauri-apps/plugin-updater";
-import { relaunch } from "@tauri-apps/plugin-process";
 import { resolveResource } from "@tauri-apps/api/path";
 import { exists } from "@tauri-apps/plugin-fs";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { open as openUrl } from "@tauri-apps/plugin-shell";
 
+export type UpdateChannel = "stable" | "beta";
+
 export interface UpdateInfo {
   available: boolean;
   currentVersion: string;
@@ -12,11 +13,30 @@ export interface UpdateInfo {
   date?: string;
   body?: string;
   isPortable?: boolean;
+  channel?: UpdateChannel;
+  /** 该版本已被推迟，自动检查时不提示 */
+  deferred?: boolean;
+}
+
+/** 已下载更新的安装安排 */
+export interface UpdateSchedule {
+  version: string;
+  downloaded: boolean;
+  mode: "manual" | "onExit" | "at";
+  /** mode 为 "at" 时的安装时间（RFC3339） */
+  at?: string;
+}
+
+interface UpdateCheck {
+  channel: UpdateChannel;
+  available: boolean;
+  currentVersion: string;
+  version?: string;
+  date?: string;
+  notes?: string;
+  deferred: boolean;
 }
 
-// 缓存已检查的更新对象，以及已完成下载、可安装的更新对象
-let cachedUpdate: Update | null = null;
-let downloadedUpdate: Update | null = null;
 let isPortableVersion: boolean | null = null;
 
 // 检查是否为便携版
@@ -39,7 +59,17 @@ export async function checkIsPortable(): Promise<boolean> {
   return isPortableVersion;
 }
 
-export async function checkForUpdates(): Promise<UpdateInfo> {
+export async function getUpdateChannel(): Promise<UpdateChannel> {
+  return invoke("get_update_channel");
+}
+
+// 切换通道后已检查 / 下载的更新作废，需要重新检查
+export async function setUpdateChannel(channel: UpdateChannel): Promise<UpdateChannel> {
+  return invoke("set_update_channel", { channel });
+}
+
+// manual 为 false 时（启动时的自动检查）会标出已推迟的版本
+export async function checkForUpdates(manual = true): Promise<UpdateInfo> {
   // 便携版跳过更新检查
   const portable = await checkIsPortable();
   if (portable) {
@@ -51,25 +81,15 @@ export async function checkForUpdates(): Promise<UpdateInfo> {
   }
 
   try {
-    const update = await check();
-    cachedUpdate = update;
-    if (!update || downloadedUpdate?.version !== update.version) {
-      downloadedUpdate = null;
-    }
-
-    if (update) {
-      return {
-        available: true,
-        currentVersion: update.currentVersion,
-        version: update.version,
-        date: update.date,
-        body: update.body,
-      };
-    }
-
+    const check = await invoke<UpdateCheck>("check_for_update", { manual });
     return {
-      available: false,
-      currentVersion: "",
+      available: check.available,
+      currentVersion: check.currentVersion,
+      version: check.version,
+      date: check.date,
+      body: check.notes,
+      channel: check.channel,
+      deferred: check.deferred,
     };
   } catch (error) {
     console.error("Failed to check for updates:", error);
@@ -80,97 +100,61 @@ export async function checkForUpdates(): Promise<UpdateInfo> {
 // 静默检查更新（不抛出错误）
 export async function silentCheckForUpdates(): Promise<UpdateInfo | null> {
   try {
-    return await checkForUpdates();
+    return await checkForUpdates(false);
   } catch (error) {
     console.error("Silent update check failed:", error);
     return null;
   }
 }
 
-// 仅下载更新（不安装）
+// 仅下载更新（不安装），需先检查更新
 export async function downloadUpdate(
   onProgress?: (progress: number, total: number) => void
-): Promise<void> {
-  if (!cachedUpdate) {
-    const update = await check();
-    if (!update) {
-      throw new Error("No update available");
+): Promise<UpdateSchedule> {
+  const unlisten = await listen<{ downloaded: number; total?: number }>(
+    "update-download-progress",
+    (event) => {
+      const { downloaded, total } = event.payload;
+      if (onProgress && total) {
+        onProgress(downloaded, total);
+      }
     }
-    cachedUpdate = update;
-    downloadedUpdate = null;
+  );
+  try {
+    return await invoke<UpdateSchedule>("download_update");
+  } finally {
+    unlisten();
   }
-
-  const update = cachedUpdate;
-  let downloaded = 0;
-  let contentLength = 0;
-
-  await update.download((event) => {
-    switch (event.event) {
-      case "Started":
-        contentLength = event.data.contentLength || 0;
-        console.log(`Started downloading ${contentLength} bytes`);
-        break;
-      case "Progress":
-        downloaded += event.data.chunkLength;
-        if (onProgress && contentLength > 0) {
-          onProgress(downloaded, contentLength);
-        }
-        console.log(`Downloaded ${downloaded} of ${contentLength}`);
-        break;
-      case "Finished":
-        console.log("Download finished");
-        break;
-    }
-  });
-  downloadedUpdate = update;
 }
 
 // 安装已下载的更新并重启
 export async function installUpdate(): Promise<void> {
-  if (!downloadedUpdate) {
-    throw new Error("No update downloaded");
-  }
-  await downloadedUpdate.install();
-  downloadedUpdate = null;
-  await relaunch();
+  await invoke("install_update");
 }
 
-// 下载并安装更新（保留原有功能）
+// 下载并安装更新
 export async function downloadAndInstallUpdate(
   onProgress?: (progress: number, total: number) => void
 ): Promise<void> {
-  let update = cachedUpdate;
-  if (!update) {
-    update = await check();
-    if (!update) {
-      throw new Error("No update available");
-    }
-    cachedUpdate = update;
-  }
+  await downloadUpdate(onProgress);
+  await installUpdate();
+}
 
-  let downloaded = 0;
-  let contentLength = 0;
-
-  await update.downloadAndInstall((event) => {
-    switch (event.event) {
-      case "Started":
-        contentLength = event.data.contentLength || 0;
-        console.log(`Started downloading ${contentLength} bytes`);
-        break;
-      case "Progress":
-        downloaded += event.data.chunkLength;
-        if (onProgress && contentLength > 0) {
-          onProgress(downloaded, contentLength);
-        }
-        console.log(`Downloaded ${downloaded} of ${contentLength}`);
-        break;
-      case "Finished":
-        console.log("Download finished");
-        break;
-    }
-  });
-  downloadedUpdate = null;
-  await relaunch();
+// 安排已下载更新的安装时机："manual" 手动 | "onExit" 退出时 | "at" 指定时间（优雅退出后安装并重启）
+export async function scheduleUpdateInstall(
+  mode: UpdateSchedule["mode"],
+  at?: string
+): Promise<UpdateSchedule> {
+  return invoke("schedule_update_install", { mode, at });
+}
+
+export async function getUpdateSchedule(): Promise<UpdateSchedule | null> {
+  return invoke("get_update_schedule");
+}
+
+// 推迟某个版本 days 天，期间启动时不再提示；days 为 0 时取消
+export async function deferUpdate(version: string, days: number): Promise<void> {
+  await invoke("defer_update", { version, days });
 }
 
 // ========== 架构检测（处理 Intel 二进制装在 Apple Silicon 上的更新错配） ==========
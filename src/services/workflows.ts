@@ -20,6 +20,8 @@ export interface Workflow {
   name: string;
   cron: string;
   enabled: boolean;
+  /** 低电量省电模式下仍然按计划执行 */
+  allowOnBattery?: boolean;
   nodes: WorkflowNode[];
   lastRun?: WorkflowRun | null;
   createdAt: string;
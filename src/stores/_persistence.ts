@@ -39,7 +39,7 @@ export const saveAppSettings = debounce(
 );
 
 export const saveUiState = debounce(
-  async (state: { recent_detail_project_ids?: string[] }) => {
+  async (state: { recent_detail_project_ids?: string[]; active_toolbox_tool?: string }) => {
     try {
       await invoke("save_ui_state", { input: state });
     } catch (err) {
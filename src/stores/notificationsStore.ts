@@ -1,6 +1,6 @@
 import { create } from "zustand";
 import { invoke } from "@tauri-apps/api/core";
-import type { Notification } from "@/types";
+import type { Notification, NotificationAction } from "@/types";
 
 interface NotificationsState {
   notifications: Notification[];
@@ -10,6 +10,11 @@ interface NotificationsState {
   ) => void;
   removeNotification: (id: string) => void;
   clearAllNotifications: () => void;
+  markRead: (id: string, read?: boolean) => void;
+  markAllRead: () => void;
+  runAction: (id: string, index: number) => Promise<NotificationAction>;
+  // 临时免打扰 minutes 分钟，0 为结束
+  setDoNotDisturb: (minutes: number) => Promise<void>;
 }
 
 export const useNotificationsStore = create<NotificationsState>()((set) => ({
@@ -29,6 +34,7 @@ export const useNotificationsStore = create<NotificationsState>()((set) => ({
         notification_type: notification.type,
         title: notification.title,
         message: notification.message || "",
+        actions: notification.actions || [],
       },
     }).catch(console.error);
   },
@@ -42,4 +48,34 @@ export const useNotificationsStore = create<NotificationsState>()((set) => ({
     set({ notifications: [] });
     invoke("clear_notifications").catch(console.error);
   },
+  markRead: (id, read = true) => {
+    set((state) => ({
+      notifications: state.notifications.map((n) =>
+        n.id === id ? { ...n, read } : n
+      ),
+    }));
+    invoke("mark_notification_read", { id, read }).catch(console.error);
+  },
+  markAllRead: () => {
+    set((state) => ({
+      notifications: state.notifications.map((n) => ({ ...n, read: true })),
+    }));
+    invoke("mark_all_notifications_read").catch(console.error);
+  },
+  // 打开文件夹 / 链接、重试下载由后端执行；navigate 返回给调用方处理跳转
+  runAction: async (id, index) => {
+    const action = await invoke<NotificationAction>("run_notification_action", {
+      id,
+      index,
+    });
+    set((state) => ({
+      notifications: state.notifications.map((n) =>
+        n.id === id ? { ...n, read: true } : n
+      ),
+    }));
+    return action;
+  },
+  setDoNotDisturb: async (minutes) => {
+    await invoke("set_do_not_disturb", { minutes: minutes > 0 ? minutes : null });
+  },
 }));
@@ -11,6 +11,12 @@ export interface Project {
   lastOpened?: string;
   editorId?: string;
   claudeEnvName?: string;
+  icon?: string; // 缓存的图标文件路径
+  color?: string; // 强调色 #RRGGBB
+  description?: string;
+  rootId?: string; // 所属路径根目录
+  gitExecutable?: string; // 项目专用的 git 可执行文件
+  statsBranch?: string; // 统计提交的分支，"*" 为全部本地分支，为空时统计当前分支
   remoteUrl?: string;
   remoteType?: "github" | "gitee" | "gitlab" | "other" | "none";
 }
@@ -39,6 +45,19 @@ export interface GitStatus {
   conflicted: string[];
   ahead: number;
   behind: number;
+  /** HEAD 未指向分支 */
+  detached?: boolean;
+  /** 进行中的合并 / 变基 / 拣选等操作 */
+  operation?: RepoOperation;
+}
+
+export interface RepoOperation {
+  kind: "merge" | "rebase" | "am" | "cherryPick" | "revert" | "bisect";
+  target?: string;
+  targetName?: string;
+  headName?: string;
+  step?: number;
+  total?: number;
 }
 
 export interface CommitInfo {
@@ -81,6 +100,7 @@ export interface RemoteInfo {
 export interface GitRepo {
   path: string;
   name: string;
+  kind?: "normal" | "worktree" | "bare";
 }
 
 // Notification types
@@ -92,6 +112,17 @@ export interface Notification {
   title: string;
   message?: string;
   createdAt: string;
+  // 来源类别："download" | "server" | "monitor" | "git" | "system"，前端发起的为空
+  category?: string;
+  read?: boolean;
+  actions?: NotificationAction[];
+}
+
+// 通知上的操作按钮
+export interface NotificationAction {
+  kind: "open_folder" | "open_url" | "retry_download" | "navigate";
+  label: string;
+  target: string;
 }
 
 // View types
@@ -292,3 +323,12 @@ export interface ApiChatSessionSummary {
   endpointCount: number;
   pinned?: boolean;
 }
+
+/** 危险操作的二次确认请求：带 token 再调用一次才会真正执行 */
+export interface ConfirmRequest {
+  token: string;
+  action: string;
+  summary: string;
+  details: string[];
+  expiresInSecs: number;
+}
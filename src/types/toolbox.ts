@@ -9,6 +9,13 @@ export interface ScanConfig {
   portEnd?: number;
   timeoutMs?: number;
   concurrency?: number;
+  /** "connect"（默认）| "syn"：SYN 扫描不可用时后端自动退回 connect */
+  mode?: "connect" | "syn";
+}
+
+export interface ScanCapabilities {
+  synAvailable: boolean;
+  synUnavailableReason?: string | null;
 }
 
 export interface ScanResult {
@@ -31,6 +38,11 @@ export interface DownloadConfig {
   saveDir?: string;
   fileName?: string;
   maxRetries?: number;
+  headers?: Record<string, string>;
+  /** 备用镜像地址：下载前测速选择最快的源，下载中停滞时切换到下一个 */
+  mirrors?: string[];
+  /** 超过该秒数没有收到数据视为停滞，默认 20，0 表示不检测 */
+  stallTimeoutSecs?: number;
 }
 
 export interface DownloadTask {
@@ -45,6 +57,411 @@ export interface DownloadTask {
   error?: string;
   createdAt: string;
   updatedAt: string;
+  headers?: Record<string, string>;
+  mirrors?: string[];
+  /** 当前正在使用的下载源 */
+  activeUrl?: string | null;
+  /** 下载后病毒扫描结果 */
+  scan?: DownloadScanResult | null;
+  /** 需要托管平台令牌时的令牌主机（令牌本身不随任务保存） */
+  authHost?: string | null;
+  stallTimeoutSecs?: number | null;
+}
+
+export interface DownloadImportError {
+  /** 行号（从 1 开始） */
+  line: number;
+  text: string;
+  error: string;
+}
+
+export interface DownloadImportResult {
+  taskIds: string[];
+  errors: DownloadImportError[];
+  /** 与前面行重复而跳过的 URL 数 */
+  duplicates: number;
+}
+
+// ============== 文件校验和 ==============
+
+export type HashAlgorithm = "md5" | "sha1" | "sha256" | "sha512" | "crc32";
+
+export interface FileHash {
+  algorithm: HashAlgorithm;
+  /** 小写十六进制 */
+  value: string;
+}
+
+export interface FileHashResult {
+  path: string;
+  size: number;
+  hashes: FileHash[];
+  /** 与期望值一致的算法 */
+  matchedAlgorithm?: HashAlgorithm | null;
+  /** 未传期望值时为空 */
+  matches?: boolean | null;
+  elapsedMs: number;
+}
+
+/** `file-hash-progress` 事件 */
+export interface FileHashProgress {
+  jobId: string;
+  path: string;
+  processed: number;
+  total: number;
+}
+
+// ============== HTTP 压测 ==============
+
+export interface HttpBenchLatency {
+  minMs: number;
+  meanMs: number;
+  p50Ms: number;
+  p90Ms: number;
+  p99Ms: number;
+  maxMs: number;
+}
+
+/** 压测报告；`http-bench-progress` 事件与最终结果共用，finished 区分 */
+export interface HttpBenchReport {
+  jobId: string;
+  url: string;
+  method: string;
+  concurrency: number;
+  elapsedMs: number;
+  /** 收到响应的请求数（含 4xx / 5xx） */
+  requests: number;
+  /** 连接失败、超时等未收到响应的请求数 */
+  errors: number;
+  nonSuccess: number;
+  rps: number;
+  currentRps: number;
+  bytesReceived: number;
+  latency: HttpBenchLatency | null;
+  statusCounts: { status: number; count: number }[];
+  errorKinds: { message: string; count: number }[];
+  finished: boolean;
+}
+
+// ============== 证书检查 ==============
+
+export interface CertificateInfo {
+  subject: string;
+  issuer: string;
+  commonName: string | null;
+  /** DNS / IP，邮箱和 URI 带 "email:" / "URI:" 前缀 */
+  sans: string[];
+  serial: string;
+  notBefore: string;
+  notAfter: string;
+  /** 已过期为负数 */
+  daysRemaining: number;
+  expired: boolean;
+  notYetValid: boolean;
+  expiringSoon: boolean;
+  selfSigned: boolean;
+  isCa: boolean;
+  signatureAlgorithm: string;
+  publicKey: string;
+  sha256Fingerprint: string;
+  sha1Fingerprint: string;
+}
+
+export interface TlsInspection {
+  host: string;
+  port: number;
+  protocol: string | null;
+  cipherSuite: string | null;
+  hostnameMatches: boolean;
+  /** 叶子证书在前 */
+  chain: CertificateInfo[];
+}
+
+// ============== 正则测试 ==============
+
+export interface RegexFlags {
+  caseInsensitive: boolean;
+  multiLine: boolean;
+  dotMatchesNewLine: boolean;
+  ignoreWhitespace: boolean;
+  swapGreed: boolean;
+}
+
+export interface RegexGroup {
+  index: number;
+  name: string | null;
+  start: number;
+  end: number;
+  text: string;
+}
+
+export interface RegexMatch {
+  start: number;
+  end: number;
+  text: string;
+  /** 未参与匹配的组为 null */
+  groups: (RegexGroup | null)[];
+}
+
+export interface RegexTestResult {
+  matches: RegexMatch[];
+  groupNames: (string | null)[];
+  truncated: boolean;
+  timedOut: boolean;
+  replaced: string | null;
+  compileMs: number;
+  matchMs: number;
+}
+
+// ============== 文本比较 ==============
+
+export type TextDiffMode = "line" | "word";
+
+export interface DiffSegment {
+  kind: "equal" | "add" | "remove";
+  text: string;
+}
+
+export interface DiffLine {
+  kind: "context" | "add" | "remove";
+  content: string;
+  oldLine: number | null;
+  newLine: number | null;
+  /** 词级差异片段，仅 word 模式下的改动行有 */
+  segments?: DiffSegment[];
+}
+
+export interface DiffHunk {
+  /** 原始 `@@ -a,b +c,d @@ ...` 行 */
+  header: string;
+  oldStart: number;
+  oldLines: number;
+  newStart: number;
+  newLines: number;
+  lines: DiffLine[];
+}
+
+export interface TextDiffResult {
+  identical: boolean;
+  insertions: number;
+  deletions: number;
+  hunks: DiffHunk[];
+  /** unified diff 文本，可直接保存为 .patch */
+  patch: string;
+  /** 行数超过上限，hunks 不完整（patch 仍是完整的） */
+  truncated: boolean;
+}
+
+export interface ApplyPatchResult {
+  text: string;
+  hunksApplied: number;
+  /** 靠查找上下文、不在 hunk 头所写行号应用的 hunk 数 */
+  hunksOffset: number;
+}
+
+// ============== 数据格式（JSON / YAML / TOML） ==============
+
+export type DocumentFormat = "json" | "yaml" | "toml";
+
+/** 行列从 1 开始 */
+export interface DocumentError {
+  message: string;
+  line: number | null;
+  column: number | null;
+}
+
+export interface DocumentValidation {
+  valid: boolean;
+  error: DocumentError | null;
+}
+
+/** 写入文件时 text 为 null */
+export interface DocumentOutput {
+  text: string | null;
+  writtenTo: string | null;
+  bytes: number;
+}
+
+export interface JsonPathMatch {
+  path: string;
+  /** JSON 文本 */
+  value: string;
+}
+
+// ============== 素材生成 ==============
+
+export interface GeneratedAsset {
+  format: "png" | "svg";
+  /** svg 为文本，png 为 base64 */
+  content: string;
+  width: number;
+  height: number;
+  path: string | null;
+}
+
+export interface FaviconResult {
+  files: { path: string; bytes: number }[];
+  /** 放进 <head> 的 link 标签 */
+  html: string;
+}
+
+// ============== 图片压缩 ==============
+
+export interface ImageOptimizeOptions {
+  /** JPEG 质量 1-100，默认 80；只作用于 JPEG，WebP 始终无损重编码 */
+  jpegQuality: number;
+  /** oxipng 等级 0-6，默认 2 */
+  pngLevel: number;
+  stripMetadata: boolean;
+  recursive: boolean;
+}
+
+export interface OptimizedImage {
+  relativePath: string;
+  format: "png" | "jpeg" | "webp";
+  originalBytes: number;
+  optimizedBytes: number;
+  /** 压缩后没有变小，输出的是原图 */
+  keptOriginal: boolean;
+  error: string | null;
+}
+
+export interface ImageOptimizeReport {
+  outputDir: string;
+  files: OptimizedImage[];
+  /** 不含失败的文件 */
+  originalBytes: number;
+  optimizedBytes: number;
+  failed: number;
+  elapsedMs: number;
+}
+
+/** `image-optimize-progress` 事件 */
+export interface ImageOptimizeProgress {
+  jobId: string;
+  relativePath: string;
+  processed: number;
+  total: number;
+}
+
+// ============== 目录同步 ==============
+
+export interface FolderSyncOptions {
+  /** gitignore 风格；非空时只同步匹配的文件 */
+  include: string[];
+  /** 匹配的文件和目录都跳过，目标中被排除的文件不会被删除 */
+  exclude: string[];
+  /** 删除目标中源目录没有的文件和目录 */
+  deleteExtraneous: boolean;
+}
+
+export interface FolderSyncEntry {
+  /** 目录以 / 结尾 */
+  relativePath: string;
+  action: "create" | "update" | "delete";
+  bytes: number;
+}
+
+export interface FolderSyncReport {
+  dryRun: boolean;
+  /** dryRun 时为将要执行的变更，否则为已成功执行的变更 */
+  entries: FolderSyncEntry[];
+  created: number;
+  updated: number;
+  deleted: number;
+  unchanged: number;
+  bytesCopied: number;
+  errors: { relativePath: string; message: string }[];
+  elapsedMs: number;
+}
+
+/** `folder-sync-progress` 事件 */
+export interface FolderSyncProgress {
+  jobId: string;
+  phase: "scan" | "copy" | "delete" | "done";
+  processed: number;
+  total: number;
+  bytesDone: number;
+  bytesTotal: number;
+  current: string | null;
+}
+
+// ============== Release 附件 ==============
+
+export interface HostingTokenInfo {
+  host: string;
+  provider: "github" | "gitlab";
+  maskedToken: string;
+}
+
+export interface ReleaseAsset {
+  name: string;
+  size?: number | null;
+  contentType?: string | null;
+  downloadUrl: string;
+  requiresAuth: boolean;
+}
+
+export interface ReleaseInfo {
+  provider: "github" | "gitlab";
+  host: string;
+  repository: string;
+  tag: string;
+  name?: string | null;
+  publishedAt?: string | null;
+  assets: ReleaseAsset[];
+  authenticated: boolean;
+}
+
+export interface DownloadScanResult {
+  /** "Windows Defender" / "ClamAV" */
+  scanner?: string | null;
+  verdict: "scanning" | "clean" | "infected" | "error" | "unavailable";
+  detail?: string | null;
+  /** 发现威胁后执行的动作："deleted" / "quarantined: <路径>" */
+  action?: string | null;
+  scannedAt: string;
+}
+
+export interface DownloadHistoryEntry {
+  id: string;
+  taskId: string;
+  fileName: string;
+  url: string;
+  /** 实际下载所用源的主机名 */
+  host: string;
+  size: number;
+  /** 本次传输的字节数（暂停恢复的任务只算恢复后的部分） */
+  transferred: number;
+  durationMs: number;
+  /** 字节/秒 */
+  averageSpeed: number;
+  /** 毫秒时间戳 */
+  completedAt: number;
+}
+
+export interface DownloadDailyStats {
+  date: string;
+  downloads: number;
+  bytes: number;
+  averageSpeed: number;
+}
+
+export interface DownloadHostStats {
+  host: string;
+  downloads: number;
+  bytes: number;
+  averageSpeed: number;
+  /** 多次下载平均速度明显低于整体水平 */
+  slow: boolean;
+}
+
+export interface DownloadHistoryStats {
+  totalDownloads: number;
+  totalBytes: number;
+  averageSpeed: number;
+  daily: DownloadDailyStats[];
+  hosts: DownloadHostStats[];
 }
 
 export interface DownloadProgress {
@@ -57,6 +474,63 @@ export interface DownloadProgress {
 
 // ============== 进程管理 ==============
 
+/** 电源状态 */
+export interface PowerStatus {
+  hasBattery: boolean;
+  onBattery: boolean;
+  batteryPercent?: number | null;
+  /** 是否启用了低电量暂停后台任务 */
+  saverEnabled: boolean;
+  saverThreshold: number;
+  /** 后台任务当前是否被暂停 */
+  backgroundPaused: boolean;
+}
+
+/** 资源告警规则 */
+export interface ResourceAlertRule {
+  id: string;
+  name: string;
+  metric: "cpu" | "memory" | "disk";
+  /** cpu / memory 为百分比（超过告警）；disk 为剩余 GB（低于告警） */
+  threshold: number;
+  /** disk 规则的挂载点，为空时检查所有磁盘 */
+  diskPath?: string | null;
+  /** 持续超过阈值多少秒才告警 */
+  sustainSecs: number;
+  flashTray: boolean;
+  enabled: boolean;
+}
+
+export interface ResourceAlertRuleInput {
+  name: string;
+  metric: "cpu" | "memory" | "disk";
+  threshold: number;
+  diskPath?: string;
+  sustainSecs?: number;
+  flashTray?: boolean;
+  enabled?: boolean;
+}
+
+/** 正在触发的资源告警（也是 resource-alert 事件的负载） */
+export interface ResourceAlert {
+  ruleId: string;
+  name: string;
+  metric: string;
+  value: number;
+  threshold: number;
+  target?: string | null;
+  since: string;
+}
+
+/** 提权端口中继 */
+export interface ElevatedRelay {
+  listenHost: string;
+  listenPort: number;
+  /** 转发到 127.0.0.1 的端口 */
+  targetPort: number;
+  startedAt: string;
+}
+
 export interface ProcessInfo {
   pid: number;
   name: string;
@@ -105,6 +579,15 @@ export interface ForwardRule {
   remotePort: number;
   /** 文档路径，如 "doc.html" 或 "swagger-ui.html" */
   docPath?: string;
+  /** 目标类型：tcp（默认）、unix（Unix 域套接字）、pipe（Windows 命名管道） */
+  targetKind?: "tcp" | "unix" | "pipe";
+  socketPath?: string | null;
+  /** 备用目标，主目标不可用时依次尝试 */
+  backupTargets?: ForwardTarget[];
+  /** 目标选择策略 */
+  balance?: "failover" | "round_robin";
+  /** 主动健康检查间隔（秒） */
+  healthCheckIntervalSecs?: number | null;
   status: "running" | "stopped";
   connections: number;
   bytesIn: number;
@@ -119,6 +602,23 @@ export interface ForwardRuleInput {
   remotePort: number;
   /** 文档路径，如 "doc.html" 或 "swagger-ui.html" */
   docPath?: string;
+  /** 目标类型：tcp（默认）、unix（Unix 域套接字）、pipe（Windows 命名管道） */
+  targetKind?: "tcp" | "unix" | "pipe";
+  socketPath?: string | null;
+  backupTargets?: ForwardTarget[];
+  balance?: "failover" | "round_robin";
+  healthCheckIntervalSecs?: number | null;
+}
+
+export interface ForwardTarget {
+  host: string;
+  port: number;
+}
+
+export interface ForwardTargetStatus {
+  address: string;
+  healthy: boolean;
+  lastError?: string | null;
 }
 
 export interface ForwardStats {
@@ -126,6 +626,8 @@ export interface ForwardStats {
   connections: number;
   bytesIn: number;
   bytesOut: number;
+  /** 各目标健康状态（未运行时为空） */
+  targets: ForwardTargetStatus[];
 }
 
 // ============== SSH 隧道 ==============
@@ -192,11 +694,68 @@ export interface TestPortResult {
   durationMs: number;
 }
 
+// ============== SSH 密钥 ==============
+
+export interface SshKeyInfo {
+  /** 私钥文件名（如 id_ed25519） */
+  name: string;
+  privateKeyPath: string;
+  publicKeyPath?: string;
+  keyType?: string;
+  /** SHA256 指纹 */
+  fingerprint?: string;
+  comment?: string;
+  /** ssh config 中使用该密钥的 Host */
+  hosts: string[];
+}
+
+export interface SshHostEntry {
+  alias: string;
+  hostName?: string;
+  user?: string;
+  port?: number;
+  identityFiles: string[];
+}
+
+export interface SshHostEntryInput {
+  alias: string;
+  hostName?: string;
+  user?: string;
+  port?: number;
+  /** ~/.ssh 下的私钥文件名 */
+  keyName: string;
+}
+
+export interface SshAuthTestResult {
+  success: boolean;
+  /** 服务端欢迎语中的账号 */
+  account?: string;
+  output: string;
+  durationMs: number;
+}
+
 // ============== 静态服务 ==============
 
 export interface ProxyConfig {
   prefix: string;
   target: string;
+  /** 转发时去掉匹配的前缀，默认 true */
+  stripPrefix?: boolean;
+  /** 转发前追加的路径前缀，如 /v1 */
+  addPrefix?: string | null;
+  /** 转发时设置的请求头 */
+  requestHeaders?: Record<string, string>;
+  /** 写入代理响应的响应头 */
+  responseHeaders?: Record<string, string>;
+}
+
+/** Prometheus 指标端点状态 */
+export interface MetricsStatus {
+  enabled: boolean;
+  running: boolean;
+  port: number;
+  /** 抓取地址（运行中才有） */
+  endpoint: string | null;
 }
 
 export interface ServerConfig {
@@ -213,7 +772,15 @@ export interface ServerConfig {
   indexPage?: string;
   /** 多个代理规则 */
   proxies: ProxyConfig[];
-  status: "running" | "stopped";
+  /** 同时处理的最大请求数，超出返回 503 */
+  maxConcurrentRequests?: number | null;
+  /** 同时保持的最大连接数（含 keep-alive 空闲连接），超出的连接返回 503 并断开 */
+  maxConnections?: number | null;
+  /** 每个客户端 IP 每秒允许的请求数，超出返回 429 */
+  rateLimitPerIp?: number | null;
+  status: "running" | "stopped" | "error";
+  /** status 为 error 时的原因，如根目录被删除 / 卸载 */
+  error?: string | null;
   createdAt: string;
 }
 
@@ -230,6 +797,9 @@ export interface ServerConfigInput {
   indexPage?: string | null;
   /** 多个代理规则 */
   proxies?: ProxyConfig[];
+  maxConcurrentRequests?: number | null;
+  maxConnections?: number | null;
+  rateLimitPerIp?: number | null;
 }
 
 // ============== Docker 镜像 ==============
@@ -394,6 +964,7 @@ export interface NetcatSessionInput {
   name?: string;
   autoReconnect?: boolean;
   timeoutMs?: number;
+  group?: string;
 }
 
 export interface NetcatSession {
@@ -418,6 +989,57 @@ export interface NetcatSession {
   clientCount: number;
   /** 自动发送配置 */
   autoSend: AutoSendConfig;
+  /** 所属分组，用于批量启停与广播 */
+  group?: string | null;
+  /** 流量日志模式 */
+  logMode: TrafficLogMode;
+  /** 是否以 debug 级别输出报文内容 */
+  logPayload: boolean;
+  /** 保留的消息条数上限 */
+  historyLimit: number;
+  /** 达到上限后的处理方式 */
+  overflowPolicy: OverflowPolicy;
+  /** 因达到上限已停止记录消息，清空历史后恢复 */
+  captureStopped: boolean;
+  /** 最近 10 秒的平均发送速率（字节/秒） */
+  sendRate: number;
+  /** 最近 10 秒的平均接收速率（字节/秒） */
+  recvRate: number;
+}
+
+/** dropOldest：丢弃最早的消息；stopCapturing：停止记录新消息 */
+export type OverflowPolicy = "dropOldest" | "stopCapturing";
+
+/** off：不记录；sampled：每秒最多几行，其余计入丢弃数；full：每条都记录 */
+export type TrafficLogMode = "off" | "sampled" | "full";
+
+export interface NetcatLogStats {
+  sessionId: string;
+  mode: TrafficLogMode;
+  logPayload: boolean;
+  loggedLines: number;
+  /** 因关闭或抽样被丢弃的行数 */
+  droppedLines: number;
+}
+
+export interface NetcatGroupOpResult {
+  sessionId: string;
+  name: string;
+  success: boolean;
+  error?: string | null;
+}
+
+export interface NetcatGroupStats {
+  group: string;
+  sessionCount: number;
+  /** 已连接 / 监听中的会话数 */
+  activeCount: number;
+  errorCount: number;
+  clientCount: number;
+  bytesSent: number;
+  bytesReceived: number;
+  messageCount: number;
+  lastActivity?: number | null;
 }
 
 export interface SendMessageInput {
@@ -426,6 +1048,35 @@ export interface SendMessageInput {
   format: DataFormat;
   targetClient?: string;
   broadcast?: boolean;
+  /** 使用报文库中的报文（忽略 data / format） */
+  payloadId?: string;
+  /** 发送前渲染 {{timestamp}} 等模板变量 */
+  render?: boolean;
+}
+
+export interface NetcatPayload {
+  id: string;
+  name: string;
+  /** 报文内容，可包含 {{timestamp}} 等模板变量 */
+  data: string;
+  format: DataFormat;
+  description?: string | null;
+  createdAt: number;
+  updatedAt: number;
+}
+
+export interface NetcatPayloadInput {
+  name: string;
+  data: string;
+  format: DataFormat;
+  description?: string | null;
+}
+
+export interface NetcatImportResult {
+  sessions: NetcatSession[];
+  payloadsImported: number;
+  /** 报文库中已有同名报文而跳过的数量 */
+  payloadsSkipped: number;
 }
 
 export interface NetcatMessage {
@@ -440,6 +1091,20 @@ export interface NetcatMessage {
   clientAddr?: string;
 }
 
+/** 消息筛选条件，各条件同时满足 */
+export interface NetcatMessageFilter {
+  direction?: MessageDirection;
+  /** 客户端 ID 或地址 */
+  client?: string;
+  /** 时间范围（毫秒时间戳，含两端） */
+  since?: number;
+  until?: number;
+  /** 内容包含的文本（不区分大小写） */
+  text?: string;
+  /** 内容包含的字节序列，如 "48 65" */
+  hex?: string;
+}
+
 export interface ConnectedClient {
   id: string;
   addr: string;
@@ -447,13 +1112,107 @@ export interface ConnectedClient {
   lastActivity: number;
   bytesSent: number;
   bytesReceived: number;
+  /** 最近 10 秒的平均发送速率（字节/秒） */
+  sendRate: number;
+  /** 最近 10 秒的平均接收速率（字节/秒） */
+  recvRate: number;
 }
 
 export type NetcatEvent =
   | { type: "statusChanged"; sessionId: string; status: SessionStatus; error?: string }
   | { type: "messageReceived"; sessionId: string; message: NetcatMessage }
   | { type: "clientConnected"; sessionId: string; client: ConnectedClient }
-  | { type: "clientDisconnected"; sessionId: string; clientId: string };
+  | { type: "clientDisconnected"; sessionId: string; clientId: string }
+  /** 消息历史达到上限，已停止记录新消息 */
+  | { type: "captureStopped"; sessionId: string; limit: number };
+
+// ============== MQTT / Modbus ==============
+
+export interface MqttConnectInput {
+  host: string;
+  port: number;
+  name?: string;
+  /** 为空时自动生成 */
+  clientId?: string;
+  username?: string;
+  password?: string;
+  /** 心跳间隔（秒），默认 60 */
+  keepAliveSecs?: number;
+  cleanSession?: boolean;
+  timeoutMs?: number;
+}
+
+export interface MqttSession {
+  id: string;
+  name: string;
+  host: string;
+  port: number;
+  clientId: string;
+  status: SessionStatus;
+  errorMessage?: string | null;
+  connectedAt: number;
+  /** 已订阅主题 -> QoS */
+  subscriptions: Record<string, number>;
+  messagesSent: number;
+  messagesReceived: number;
+}
+
+export interface MqttMessage {
+  id: string;
+  sessionId: string;
+  direction: MessageDirection;
+  topic: string;
+  payload: string;
+  size: number;
+  qos: number;
+  retain: boolean;
+  timestamp: number;
+}
+
+export type MqttEvent =
+  | { type: "statusChanged"; sessionId: string; status: SessionStatus; error?: string | null }
+  | { type: "messageReceived"; sessionId: string; message: MqttMessage };
+
+/** 功能码：1 读线圈 / 2 读离散输入 / 3 读保持寄存器 / 4 读输入寄存器 / 5 写单线圈 / 6 写单寄存器 / 15 写多线圈 / 16 写多寄存器 */
+export type ModbusFunction = 1 | 2 | 3 | 4 | 5 | 6 | 15 | 16;
+
+export interface ModbusRequest {
+  host: string;
+  /** 默认 502 */
+  port?: number;
+  /** 从站地址，默认 1 */
+  unitId?: number;
+  function: ModbusFunction;
+  address: number;
+  quantity?: number;
+  values?: number[];
+  timeoutMs?: number;
+}
+
+export interface ModbusResponse {
+  function: number;
+  values: number[];
+  requestHex: string;
+  responseHex: string;
+  elapsedMs: number;
+}
+
+// ============== 流量抓包 ==============
+
+export type TrafficCaptureKind = "netcat" | "forward";
+
+export interface TrafficCapture {
+  /** 会话 ID / 转发规则 ID */
+  resourceId: string;
+  kind: TrafficCaptureKind;
+  /** pcapng 文件路径 */
+  path: string;
+  packets: number;
+  bytes: number;
+  /** 超过大小上限后停止写入 */
+  truncated: boolean;
+  startedAt: string;
+}
 
 // ============== 快捷键备忘 ==============
 
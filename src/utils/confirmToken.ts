@@ -0,0 +1,33 @@
+import { createElement } from "react";
+import type { ConfirmOptions } from "@/components/common";
+import type { ConfirmRequest } from "@/types";
+
+/**
+ * 执行需要二次确认的后端命令：第一次调用拿到 ConfirmRequest，
+ * 把受影响的对象展示给用户，确认后带 token 再调用一次。
+ * 返回 true 表示已执行，false 表示用户取消。
+ */
+export async function runWithConfirmToken(
+  call: (confirmToken?: string) => Promise<ConfirmRequest | null>,
+  confirm: (options: ConfirmOptions) => Promise<boolean>,
+  title: string,
+): Promise<boolean> {
+  const request = await call();
+  if (!request) return true;
+
+  const ok = await confirm({
+    title,
+    variant: "danger",
+    description: request.summary,
+    notice: createElement(
+      "ul",
+      { className: "space-y-1 break-all" },
+      request.details.map((line, i) => createElement("li", { key: i }, line)),
+    ),
+    confirmLabel: "确认执行",
+  });
+  if (!ok) return false;
+
+  await call(request.token);
+  return true;
+}